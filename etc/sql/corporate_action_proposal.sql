@@ -0,0 +1,30 @@
+create table if not exists public.corporate_action_proposal
+(
+    serial         bigserial
+        primary key,
+    stock_symbol   varchar(24)                                             not null,
+    source         varchar(24)                                             not null,
+    reference      varchar(512)                                            not null,
+    action_type    varchar(24)                                             not null,
+    effective_date date,
+    share_ratio    numeric(18, 8),
+    note           varchar(255)             default ''::character varying not null,
+    status         varchar(16)              default 'pending'             not null,
+    created_time   timestamp with time zone default now()                 not null,
+    updated_time   timestamp with time zone default now()                 not null
+);
+
+comment on table public.corporate_action_proposal is '待人工確認的公司行動建議：由重大訊息、恢復買賣公告與報價跳空自動產生，確認後才寫入 corporate_action';
+
+comment on column public.corporate_action_proposal.serial is '流水號';
+comment on column public.corporate_action_proposal.stock_symbol is '股票代號';
+comment on column public.corporate_action_proposal.source is '建議來源：mops（重大訊息）、twse / tpex（減資與變更面額恢復買賣）、price_gap（無除權息的報價跳空）';
+comment on column public.corporate_action_proposal.reference is '來源內的識別，例如重大訊息的發言時間與主旨；同來源同代號不重複建議';
+comment on column public.corporate_action_proposal.action_type is '事件類型：split / capital_reduction';
+comment on column public.corporate_action_proposal.effective_date is '預估生效日；來源未揭露時為 null，需人工補齊';
+comment on column public.corporate_action_proposal.share_ratio is '預估股數變動比例；來源未揭露時為 null，需人工補齊';
+comment on column public.corporate_action_proposal.note is '備註';
+comment on column public.corporate_action_proposal.status is '狀態：pending（待確認）/ confirmed（已確認）/ rejected（已駁回）';
+
+create unique index if not exists "corporate_action_proposal-source-stock_symbol-reference-uidx"
+    on public.corporate_action_proposal (source, stock_symbol, reference);
@@ -0,0 +1,28 @@
+create table if not exists public.dividend_gap_fill
+(
+    security_code        varchar(24)                            not null,
+    year                 integer                                not null,
+    quarter              varchar(4)                             not null,
+    ex_dividend_date     date                                   not null,
+    cash_dividend        numeric(18, 4)                         not null,
+    pre_ex_close         numeric(18, 4)                         not null,
+    filled_on            date,
+    trading_days_to_fill integer,
+    created_time         timestamp with time zone default now() not null,
+    updated_time         timestamp with time zone default now() not null,
+    primary key (security_code, year, quarter)
+);
+
+comment on table public.dividend_gap_fill is '除息填息追蹤：除息前收盤價與收盤價回到該價位所需的交易日數';
+
+comment on column public.dividend_gap_fill.security_code is '證券代號';
+comment on column public.dividend_gap_fill.year is '發放年度，對應 dividend.year';
+comment on column public.dividend_gap_fill.quarter is '期間標記，對應 dividend.quarter';
+comment on column public.dividend_gap_fill.ex_dividend_date is '除息日';
+comment on column public.dividend_gap_fill.cash_dividend is '現金股利';
+comment on column public.dividend_gap_fill.pre_ex_close is '除息前一交易日收盤價（填息目標價）';
+comment on column public.dividend_gap_fill.filled_on is '填息日；尚未填息時為 null';
+comment on column public.dividend_gap_fill.trading_days_to_fill is '除息日起算到填息日的交易日數（含頭尾，除息當日填息為 1）';
+
+create index if not exists "dividend_gap_fill-ex_dividend_date-idx"
+    on public.dividend_gap_fill (ex_dividend_date);
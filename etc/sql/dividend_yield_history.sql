@@ -0,0 +1,19 @@
+create table if not exists public.dividend_yield_history
+(
+    security_code varchar(24)                            not null,
+    date          date                                   not null,
+    closing_price numeric(18, 4)                         not null,
+    dividend      numeric(18, 4)                         not null,
+    yield         numeric(18, 4)                         not null,
+    created_time  timestamp with time zone default now() not null,
+    updated_time  timestamp with time zone default now() not null,
+    primary key (security_code, date)
+);
+
+comment on table public.dividend_yield_history is '每日歷史殖利率：收盤價對應當時最近一個年度的股利合計';
+
+comment on column public.dividend_yield_history.security_code is '證券代號';
+comment on column public.dividend_yield_history.date is '交易日';
+comment on column public.dividend_yield_history.closing_price is '當日收盤價';
+comment on column public.dividend_yield_history.dividend is '當時最近一個年度的股利合計，對應 dividend.sum';
+comment on column public.dividend_yield_history.yield is '殖利率（%）';
@@ -0,0 +1,47 @@
+-- 領域事件派發後只在記憶體中處理，處理完即消失。這張表依派發順序追加保存每一個事件，
+-- sequence 即重播順序；payload 為事件的 JSON（type 欄位與 event_type 相同）。
+create table if not exists public.domain_event_log
+(
+    sequence      bigserial
+        primary key,
+    event_type    varchar(64)                            not null,
+    symbol        varchar(24),
+    occurred_at   timestamp with time zone               not null,
+    payload       jsonb                                  not null,
+    recorded_time timestamp with time zone default now() not null
+);
+
+create index if not exists "domain_event_log-event_type-occurred_at-idx"
+    on public.domain_event_log (event_type, occurred_at);
+
+create index if not exists "domain_event_log-symbol-occurred_at-idx"
+    on public.domain_event_log (symbol, occurred_at)
+    where symbol is not null;
+
+create index if not exists "domain_event_log-occurred_at-idx"
+    on public.domain_event_log (occurred_at);
+
+comment on table public.domain_event_log is '領域事件日誌（只追加）';
+
+comment on column public.domain_event_log.sequence is '日誌序號，依寫入順序遞增';
+comment on column public.domain_event_log.event_type is '事件種類 StockRegistered StockIdentityChanged NetAssetValueUpdated StockIndexUpdated DerivativesMarketUpdated MoneyFlowRecalculated ExDividendReminderTriggered';
+comment on column public.domain_event_log.symbol is '證券代碼（全市場事件為空）';
+comment on column public.domain_event_log.occurred_at is '事件發生時間';
+comment on column public.domain_event_log.payload is '事件內容 JSON';
+comment on column public.domain_event_log.recorded_time is '寫入日誌時間';
+
+-- 已寫入的事件不可修改；過舊事件的清理仍以 delete 進行。
+create or replace function public.reject_domain_event_log_update() returns trigger
+    language plpgsql as
+$$
+begin
+    raise exception 'domain_event_log is append-only';
+end;
+$$;
+
+drop trigger if exists "domain_event_log-reject-update" on public.domain_event_log;
+create trigger "domain_event_log-reject-update"
+    before update
+    on public.domain_event_log
+    for each row
+execute function public.reject_domain_event_log_update();
@@ -0,0 +1,29 @@
+create table if not exists public.estimate_backtest
+(
+    date              date                                   not null,
+    horizon_months    integer                                not null,
+    scope             varchar(16)                            not null,
+    scope_id          integer                                not null,
+    signal            varchar(16)                            not null,
+    samples           bigint                                 not null,
+    mean_return_pct   numeric(18, 4),
+    median_return_pct numeric(18, 4),
+    win_rate_pct      numeric(7, 2),
+    created_time      timestamp with time zone default now() not null,
+    primary key (date, horizon_months, scope, scope_id, signal)
+);
+
+comment on table public.estimate_backtest is '估價訊號回測：各估價日低估／合理／高估股票之後 3、6、12 個月的含息報酬彙總';
+
+comment on column public.estimate_backtest.date is '估價日（estimate.date）';
+comment on column public.estimate_backtest.horizon_months is '持有月數：3、6、12';
+comment on column public.estimate_backtest.scope is '統計範圍：all、market、industry';
+comment on column public.estimate_backtest.scope_id is '範圍編號：all 為 0，market 為 stock_exchange_market_id，industry 為 stock_industry_id';
+comment on column public.estimate_backtest.signal is '訊號：undervalued（收盤價≤便宜價）、fair、overvalued（收盤價≥昂貴價）';
+comment on column public.estimate_backtest.samples is '樣本數';
+comment on column public.estimate_backtest.mean_return_pct is '含息不再投入的平均區間報酬率（%），無樣本時為 null';
+comment on column public.estimate_backtest.median_return_pct is '區間報酬率中位數（%），無樣本時為 null';
+comment on column public.estimate_backtest.win_rate_pct is '報酬為正的樣本比例（%），無樣本時為 null';
+
+create index if not exists "estimate_backtest-horizon_months-scope-scope_id-date-idx"
+    on public.estimate_backtest (horizon_months, scope, scope_id, date);
@@ -0,0 +1,42 @@
+create table if not exists public.financial_health_score
+(
+    security_code         varchar(10)                            not null,
+    year                  integer                                not null,
+    quarter               varchar(2)                             not null,
+    period_end            date                                   not null,
+    f_score               smallint,
+    f_score_criteria      text                                   not null,
+    altman_z_score        numeric(18, 4),
+    accruals_ratio        numeric(18, 4),
+    cash_conversion_ratio numeric(18, 4),
+    fcf_yield_pct         numeric(18, 4),
+    interest_coverage     numeric(18, 4),
+    debt_to_equity        numeric(18, 4),
+    debt_to_equity_change numeric(18, 4),
+    inputs                text                                   not null,
+    created_time          timestamp with time zone default now() not null,
+    updated_time          timestamp with time zone default now() not null,
+    primary key (security_code, year, quarter)
+);
+
+create index if not exists financial_health_score_security_code_period_end_idx
+    on public.financial_health_score (security_code, period_end desc);
+
+comment on table public.financial_health_score is '由三大報表科目計算的每季財務體質分數';
+
+comment on column public.financial_health_score.security_code is '股票代號';
+comment on column public.financial_health_score.year is '財報年度（西元）';
+comment on column public.financial_health_score.quarter is '財報季別 Q1～Q4';
+comment on column public.financial_health_score.period_end is '季末日期';
+comment on column public.financial_health_score.f_score is 'Piotroski F-score（0～9），任一項資料不足時為空';
+comment on column public.financial_health_score.f_score_criteria is 'F-score 九項檢查結果（JSON）';
+comment on column public.financial_health_score.altman_z_score is 'Altman Z-score';
+comment on column public.financial_health_score.accruals_ratio is '應計比率：(近四季淨利 - 營業現金流) / 平均總資產';
+comment on column public.financial_health_score.cash_conversion_ratio is '現金轉換率：近四季營業現金流 / 淨利';
+comment on column public.financial_health_score.fcf_yield_pct is '自由現金流量殖利率（%）';
+comment on column public.financial_health_score.interest_coverage is '利息保障倍數：近四季息前稅前淨利 / 財務成本';
+comment on column public.financial_health_score.debt_to_equity is '負債權益比';
+comment on column public.financial_health_score.debt_to_equity_change is '負債權益比較去年同季的變化';
+comment on column public.financial_health_score.inputs is '計算採用的科目數值與季末收盤價（JSON）';
+comment on column public.financial_health_score.created_time is '建立時間';
+comment on column public.financial_health_score.updated_time is '最後更新時間';
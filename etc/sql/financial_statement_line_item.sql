@@ -0,0 +1,32 @@
+create table if not exists public.financial_statement_line_item
+(
+    security_code varchar(10)                            not null,
+    year          integer                                not null,
+    quarter       varchar(2)                             not null,
+    statement     varchar(20)                            not null,
+    item_code     varchar(50)                            not null,
+    period_type   varchar(20)                            not null,
+    value         numeric(24, 4)                         not null,
+    element       varchar(200)                           not null,
+    consolidated  boolean                                not null,
+    created_time  timestamp with time zone default now() not null,
+    updated_time  timestamp with time zone default now() not null,
+    primary key (security_code, year, quarter, item_code, period_type)
+);
+
+create index if not exists financial_statement_line_item_year_quarter_idx
+    on public.financial_statement_line_item (year, quarter);
+
+comment on table public.financial_statement_line_item is 'MOPS XBRL 季報解析出的三大報表正規化科目';
+
+comment on column public.financial_statement_line_item.security_code is '股票代號';
+comment on column public.financial_statement_line_item.year is '財報年度（西元）';
+comment on column public.financial_statement_line_item.quarter is '財報季別 Q1～Q4';
+comment on column public.financial_statement_line_item.statement is '報表種類：income_statement 綜合損益表、balance_sheet 資產負債表、cash_flow 現金流量表';
+comment on column public.financial_statement_line_item.item_code is '正規化科目代碼，例如 revenue、total_assets、operating_cash_flow';
+comment on column public.financial_statement_line_item.period_type is '期間：instant 季末餘額、quarter 單季、year_to_date 年初至季末累計';
+comment on column public.financial_statement_line_item.value is '科目金額（元）；每股盈餘為每股元';
+comment on column public.financial_statement_line_item.element is '來源 XBRL 元素，例如 ifrs-full:Revenue';
+comment on column public.financial_statement_line_item.consolidated is '是否取自合併報表（否則為個體報表）';
+comment on column public.financial_statement_line_item.created_time is '建立時間';
+comment on column public.financial_statement_line_item.updated_time is '最後更新時間';
@@ -0,0 +1,112 @@
+-- 月營收、財務報表與股利都是就地 upsert，舊值會被覆蓋，也沒有記錄市場何時得知該數字。
+-- 這張表由觸發器在每次寫入的數值與前一版不同時追加一筆版本，保留首次得知時間，
+-- 回測即可用「當時已知」的數字，避免使用事後修正或尚未公布的資料（前視偏差）。
+create table if not exists public.fundamental_revision
+(
+    serial          bigserial
+        primary key,
+    kind            varchar(32)                            not null,
+    security_code   varchar(24)                            not null,
+    period          varchar(16)                            not null,
+    revision        integer                                not null,
+    payload         jsonb                                  not null,
+    first_seen_time timestamp with time zone               not null,
+    recorded_time   timestamp with time zone default now() not null,
+    created_time    timestamp with time zone default now() not null
+);
+
+create unique index if not exists "fundamental_revision-kind-security_code-period-revision-uidx"
+    on public.fundamental_revision (kind, security_code, period, revision);
+
+create index if not exists "fundamental_revision-kind-security_code-recorded_time-idx"
+    on public.fundamental_revision (kind, security_code, recorded_time);
+
+comment on table public.fundamental_revision is '月營收、財務報表與股利的版本歷史（只追加）';
+
+comment on column public.fundamental_revision.kind is '資料種類 revenue:月營收 financial_statement:財務報表 dividend:股利';
+comment on column public.fundamental_revision.security_code is '股票代號';
+comment on column public.fundamental_revision.period is '資料期間 月營收:YYYYMM 財務報表與股利:年度-季別（季別空白為全年度）';
+comment on column public.fundamental_revision.revision is '版本序號，從 1 開始';
+comment on column public.fundamental_revision.payload is '該版本的整列資料（不含建立與更新時間）';
+comment on column public.fundamental_revision.first_seen_time is '首次得知該期數字的時間，同一期各版本相同';
+comment on column public.fundamental_revision.recorded_time is '得知此版本數值的時間';
+comment on column public.fundamental_revision.created_time is '建立時間';
+
+-- 觸發器共用函式，TG_ARGV[0] 為資料種類。數值與最新一版相同時（例如只更新了時間欄位）不追加。
+create or replace function public.record_fundamental_revision() returns trigger
+    language plpgsql as
+$$
+declare
+    row_payload   jsonb := to_jsonb(new) - 'CreateTime' - 'created_time' - 'updated_time';
+    row_code      varchar(24);
+    row_period    varchar(16);
+    last_revision record;
+begin
+    row_code := coalesce(row_payload ->> 'security_code', row_payload ->> 'SecurityCode');
+    if tg_argv[0] = 'revenue' then
+        row_period := row_payload ->> 'Date';
+    else
+        row_period := (row_payload ->> 'year') || '-' || (row_payload ->> 'quarter');
+    end if;
+
+    select revision, payload, first_seen_time
+    into last_revision
+    from public.fundamental_revision
+    where kind = tg_argv[0]
+      and security_code = row_code
+      and period = row_period
+    order by revision desc
+    limit 1;
+
+    if not found then
+        insert into public.fundamental_revision (kind, security_code, period, revision, payload, first_seen_time)
+        values (tg_argv[0], row_code, row_period, 1, row_payload, now());
+    elsif last_revision.payload is distinct from row_payload then
+        insert into public.fundamental_revision (kind, security_code, period, revision, payload, first_seen_time)
+        values (tg_argv[0], row_code, row_period, last_revision.revision + 1, row_payload,
+                last_revision.first_seen_time);
+    end if;
+
+    return null;
+end;
+$$;
+
+drop trigger if exists "Revenue-record_fundamental_revision" on public."Revenue";
+create trigger "Revenue-record_fundamental_revision"
+    after insert or update
+    on public."Revenue"
+    for each row
+execute function public.record_fundamental_revision('revenue');
+
+drop trigger if exists "financial_statement-record_fundamental_revision" on public.financial_statement;
+create trigger "financial_statement-record_fundamental_revision"
+    after insert or update
+    on public.financial_statement
+    for each row
+execute function public.record_fundamental_revision('financial_statement');
+
+drop trigger if exists "dividend-record_fundamental_revision" on public.dividend;
+create trigger "dividend-record_fundamental_revision"
+    after insert or update
+    on public.dividend
+    for each row
+execute function public.record_fundamental_revision('dividend');
+
+-- 既有資料建立第一版。爬蟲寫入時間一定不早於實際公布時間，以建立時間當作首次得知時間
+-- 只會讓回測晚一點用到數字，不會引入前視偏差。
+insert into public.fundamental_revision (kind, security_code, period, revision, payload, first_seen_time, recorded_time)
+select 'revenue', r."SecurityCode", r."Date"::text, 1, to_jsonb(r) - 'CreateTime', r."CreateTime", r."CreateTime"
+from public."Revenue" r
+on conflict do nothing;
+
+insert into public.fundamental_revision (kind, security_code, period, revision, payload, first_seen_time, recorded_time)
+select 'financial_statement', f.security_code, f.year || '-' || f.quarter, 1,
+       to_jsonb(f) - 'created_time' - 'updated_time', f.created_time, f.updated_time
+from public.financial_statement f
+on conflict do nothing;
+
+insert into public.fundamental_revision (kind, security_code, period, revision, payload, first_seen_time, recorded_time)
+select 'dividend', d.security_code, d.year || '-' || d.quarter, 1,
+       to_jsonb(d) - 'created_time' - 'updated_time', d.created_time, d.updated_time
+from public.dividend d
+on conflict do nothing;
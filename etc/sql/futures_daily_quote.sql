@@ -0,0 +1,31 @@
+create table if not exists public.futures_daily_quote
+(
+    date             date                                        not null,
+    contract         varchar(16)                                 not null,
+    contract_month   varchar(16)                                 not null,
+    open             numeric(12, 2),
+    high             numeric(12, 2),
+    low              numeric(12, 2),
+    close            numeric(12, 2),
+    change           numeric(12, 2),
+    volume           bigint                   default 0          not null,
+    settlement_price numeric(12, 2),
+    open_interest    bigint                   default 0          not null,
+    created_time     timestamp with time zone default now()      not null,
+    updated_time     timestamp with time zone default now()      not null,
+    primary key (date, contract, contract_month)
+);
+
+comment on table public.futures_daily_quote is '期交所期貨每日行情（臺股期貨、小型臺指期貨），僅收錄一般交易時段的單月契約';
+
+comment on column public.futures_daily_quote.date is '交易日期';
+comment on column public.futures_daily_quote.contract is '契約代號（TX、MTX）';
+comment on column public.futures_daily_quote.contract_month is '到期月份（YYYYMM，週契約帶 W 後綴）';
+comment on column public.futures_daily_quote.open is '開盤價，無成交時為 null';
+comment on column public.futures_daily_quote.high is '最高價';
+comment on column public.futures_daily_quote.low is '最低價';
+comment on column public.futures_daily_quote.close is '收盤價';
+comment on column public.futures_daily_quote.change is '漲跌價';
+comment on column public.futures_daily_quote.volume is '成交量（口）';
+comment on column public.futures_daily_quote.settlement_price is '結算價';
+comment on column public.futures_daily_quote.open_interest is '未沖銷契約數（口）';
@@ -0,0 +1,29 @@
+create table if not exists public.futures_institutional_position
+(
+    date                     date                                        not null,
+    contract                 varchar(16)                                 not null,
+    trader_type              varchar(24)                                 not null,
+    long_trade_volume        bigint                   default 0          not null,
+    short_trade_volume       bigint                   default 0          not null,
+    net_trade_volume         bigint                   default 0          not null,
+    long_open_interest       bigint                   default 0          not null,
+    short_open_interest      bigint                   default 0          not null,
+    net_open_interest        bigint                   default 0          not null,
+    net_open_interest_amount bigint                   default 0          not null,
+    created_time             timestamp with time zone default now()      not null,
+    updated_time             timestamp with time zone default now()      not null,
+    primary key (date, contract, trader_type)
+);
+
+comment on table public.futures_institutional_position is '期交所三大法人期貨交易與未平倉部位（臺股期貨、小型臺指期貨），單位為口';
+
+comment on column public.futures_institutional_position.date is '交易日期';
+comment on column public.futures_institutional_position.contract is '契約代號（TX、MTX）';
+comment on column public.futures_institutional_position.trader_type is '身份別（dealer 自營商、investment_trust 投信、foreign 外資及陸資）';
+comment on column public.futures_institutional_position.long_trade_volume is '多方交易口數';
+comment on column public.futures_institutional_position.short_trade_volume is '空方交易口數';
+comment on column public.futures_institutional_position.net_trade_volume is '多空交易口數淨額';
+comment on column public.futures_institutional_position.long_open_interest is '多方未平倉口數';
+comment on column public.futures_institutional_position.short_open_interest is '空方未平倉口數';
+comment on column public.futures_institutional_position.net_open_interest is '多空未平倉口數淨額';
+comment on column public.futures_institutional_position.net_open_interest_amount is '多空未平倉契約金額淨額（千元）';
@@ -0,0 +1,39 @@
+create table if not exists public.industry_daily_metric
+(
+    date                      date                                   not null,
+    stock_industry_id         integer                                not null,
+    stocks                    integer                                not null,
+    advances                  integer                                not null,
+    declines                  integer                                not null,
+    unchanged                 integer                                not null,
+    cap_weighted_return_pct   numeric(12, 4),
+    equal_weighted_return_pct numeric(12, 4),
+    index_level               numeric(18, 4)                         not null,
+    trade_value               numeric(22, 4)                         not null,
+    turnover_share_pct        numeric(9, 4),
+    revenue_period            bigint,
+    revenue_companies         integer                                not null default 0,
+    revenue_yoy_pct           numeric(12, 4),
+    created_time              timestamp with time zone default now() not null,
+    updated_time              timestamp with time zone default now() not null,
+    primary key (date, stock_industry_id)
+);
+
+comment on table public.industry_daily_metric is '產業每日指標：市值加權與等權重報酬、漲跌家數、成交金額佔比與月營收年增率';
+
+comment on column public.industry_daily_metric.date is '交易日';
+comment on column public.industry_daily_metric.stock_industry_id is '產業分類編號 stock_industry（不含 9000 以上的 ETF、ETN、受益證券）';
+comment on column public.industry_daily_metric.stocks is '納入統計的上市櫃股票數（未停牌、當日有收盤價）';
+comment on column public.industry_daily_metric.advances is '上漲家數';
+comment on column public.industry_daily_metric.declines is '下跌家數';
+comment on column public.industry_daily_metric.unchanged is '平盤家數';
+comment on column public.industry_daily_metric.cap_weighted_return_pct is '以前一日市值（發行股數 × 昨收）加權的當日報酬率（%）';
+comment on column public.industry_daily_metric.equal_weighted_return_pct is '個股當日報酬率的簡單平均（%）';
+comment on column public.industry_daily_metric.index_level is '產業指數：市值加權報酬逐日連乘，自第一筆 100 起算';
+comment on column public.industry_daily_metric.trade_value is '成交金額合計';
+comment on column public.industry_daily_metric.turnover_share_pct is '成交金額佔全體上市櫃股票的比例（%）';
+comment on column public.industry_daily_metric.revenue_period is '營收年增率採用的營收月份（YYYYMM），次月 10 日起才採用';
+comment on column public.industry_daily_metric.revenue_companies is '該營收月份有去年同月營收可比較的公司數';
+comment on column public.industry_daily_metric.revenue_yoy_pct is '產業合計月營收相對去年同月的成長率（%）';
+comment on column public.industry_daily_metric.created_time is '建立時間';
+comment on column public.industry_daily_metric.updated_time is '最後更新時間';
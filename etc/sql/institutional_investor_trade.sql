@@ -0,0 +1,43 @@
+create table if not exists public.institutional_investor_trade
+(
+    stock_symbol           varchar(24)                                 not null,
+    date                   date                                        not null,
+    foreign_buy            bigint                   default 0          not null,
+    foreign_sell           bigint                   default 0          not null,
+    foreign_net            bigint                   default 0          not null,
+    foreign_dealer_net     bigint                   default 0          not null,
+    investment_trust_buy   bigint                   default 0          not null,
+    investment_trust_sell  bigint                   default 0          not null,
+    investment_trust_net   bigint                   default 0          not null,
+    dealer_buy             bigint                   default 0          not null,
+    dealer_sell            bigint                   default 0          not null,
+    dealer_net             bigint                   default 0          not null,
+    dealer_proprietary_net bigint                   default 0          not null,
+    dealer_hedge_net       bigint                   default 0          not null,
+    total_net              bigint                   default 0          not null,
+    created_time           timestamp with time zone default now()      not null,
+    updated_time           timestamp with time zone default now()      not null,
+    primary key (date, stock_symbol)
+);
+
+comment on table public.institutional_investor_trade is '三大法人個股每日買賣超（TWSE T86、TPEx 三大法人買賣明細），單位為股';
+
+comment on column public.institutional_investor_trade.stock_symbol is '股票代號';
+comment on column public.institutional_investor_trade.date is '交易日期';
+comment on column public.institutional_investor_trade.foreign_buy is '外陸資買進股數（不含外資自營商）';
+comment on column public.institutional_investor_trade.foreign_sell is '外陸資賣出股數（不含外資自營商）';
+comment on column public.institutional_investor_trade.foreign_net is '外陸資買賣超股數（不含外資自營商）';
+comment on column public.institutional_investor_trade.foreign_dealer_net is '外資自營商買賣超股數';
+comment on column public.institutional_investor_trade.investment_trust_buy is '投信買進股數';
+comment on column public.institutional_investor_trade.investment_trust_sell is '投信賣出股數';
+comment on column public.institutional_investor_trade.investment_trust_net is '投信買賣超股數';
+comment on column public.institutional_investor_trade.dealer_buy is '自營商買進股數（自行買賣＋避險）';
+comment on column public.institutional_investor_trade.dealer_sell is '自營商賣出股數（自行買賣＋避險）';
+comment on column public.institutional_investor_trade.dealer_net is '自營商買賣超股數（自行買賣＋避險）';
+comment on column public.institutional_investor_trade.dealer_proprietary_net is '自營商買賣超股數（自行買賣）';
+comment on column public.institutional_investor_trade.dealer_hedge_net is '自營商買賣超股數（避險）';
+comment on column public.institutional_investor_trade.total_net is '三大法人買賣超股數合計';
+
+-- 主鍵以日期為前導欄位，供整日替換與全市場排行使用；個股歷史查詢另建索引。
+create index if not exists "institutional_investor_trade-stock_symbol-date-idx"
+    on public.institutional_investor_trade (stock_symbol, date desc);
@@ -0,0 +1,47 @@
+create table if not exists public.margin_trading
+(
+    stock_symbol                  varchar(24)                                 not null,
+    date                          date                                        not null,
+    margin_buy                    bigint                   default 0          not null,
+    margin_sell                   bigint                   default 0          not null,
+    margin_redemption             bigint                   default 0          not null,
+    margin_previous_balance       bigint                   default 0          not null,
+    margin_balance                bigint                   default 0          not null,
+    margin_quota                  bigint                   default 0          not null,
+    short_sell                    bigint                   default 0          not null,
+    short_cover                   bigint                   default 0          not null,
+    short_redemption              bigint                   default 0          not null,
+    short_previous_balance        bigint                   default 0          not null,
+    short_balance                 bigint                   default 0          not null,
+    short_quota                   bigint                   default 0          not null,
+    offset_volume                 bigint                   default 0          not null,
+    margin_utilization_percent    numeric(10, 4),
+    short_to_margin_ratio_percent numeric(12, 4),
+    created_time                  timestamp with time zone default now()      not null,
+    updated_time                  timestamp with time zone default now()      not null,
+    primary key (date, stock_symbol)
+);
+
+comment on table public.margin_trading is '個股每日融資融券餘額（TWSE MI_MARGN、TPEx 融資融券餘額），單位為張';
+
+comment on column public.margin_trading.stock_symbol is '股票代號';
+comment on column public.margin_trading.date is '交易日期';
+comment on column public.margin_trading.margin_buy is '融資買進';
+comment on column public.margin_trading.margin_sell is '融資賣出';
+comment on column public.margin_trading.margin_redemption is '融資現金償還';
+comment on column public.margin_trading.margin_previous_balance is '融資前日餘額';
+comment on column public.margin_trading.margin_balance is '融資今日餘額';
+comment on column public.margin_trading.margin_quota is '融資限額';
+comment on column public.margin_trading.short_sell is '融券賣出（放空）';
+comment on column public.margin_trading.short_cover is '融券買進（回補）';
+comment on column public.margin_trading.short_redemption is '融券現券償還';
+comment on column public.margin_trading.short_previous_balance is '融券前日餘額';
+comment on column public.margin_trading.short_balance is '融券今日餘額';
+comment on column public.margin_trading.short_quota is '融券限額';
+comment on column public.margin_trading.offset_volume is '資券互抵';
+comment on column public.margin_trading.margin_utilization_percent is '融資使用率（%），融資餘額 ÷ 融資限額；限額為 0 時為 null';
+comment on column public.margin_trading.short_to_margin_ratio_percent is '券資比（%），融券餘額 ÷ 融資餘額；融資餘額為 0 時為 null';
+
+-- 主鍵以日期為前導欄位，供整日替換使用；個股歷史與選股的最新一筆查詢另建索引。
+create index if not exists "margin_trading-stock_symbol-date-idx"
+    on public.margin_trading (stock_symbol, date desc);
@@ -0,0 +1,49 @@
+create table if not exists public.market_breadth_indicator
+(
+    date                        date                                   not null,
+    stock_exchange_market_id    integer                                not null,
+    advances                    integer                                not null,
+    declines                    integer                                not null,
+    unchanged                   integer                                not null,
+    net_advances                integer                                not null,
+    ad_line                     bigint                                 not null,
+    ratio_adjusted_net_advances numeric(14, 4)                         not null,
+    ema_19                      numeric(14, 4)                         not null,
+    ema_39                      numeric(14, 4)                         not null,
+    mcclellan_oscillator        numeric(14, 4)                         not null,
+    summation_index             numeric(18, 4)                         not null,
+    new_highs                   integer                                not null,
+    new_lows                    integer                                not null,
+    pct_above_ma5               numeric(7, 2),
+    pct_above_ma20              numeric(7, 2),
+    pct_above_ma60              numeric(7, 2),
+    pct_above_ma120             numeric(7, 2),
+    pct_above_ma240             numeric(7, 2),
+    created_time                timestamp with time zone default now() not null,
+    updated_time                timestamp with time zone default now() not null,
+    primary key (date, stock_exchange_market_id)
+);
+
+comment on table public.market_breadth_indicator is '市場廣度衍生指標：騰落線、McClellan 擺盪指標與總和指數、年內新高新低與站上均線比例';
+
+comment on column public.market_breadth_indicator.date is '統計日期';
+comment on column public.market_breadth_indicator.stock_exchange_market_id is '市場類型 (TWSE: 2, TPEx: 4, ALL: 0)';
+comment on column public.market_breadth_indicator.advances is '上漲家數（daily_stock_price_stats.stocks_up）';
+comment on column public.market_breadth_indicator.declines is '下跌家數（daily_stock_price_stats.stocks_down）';
+comment on column public.market_breadth_indicator.unchanged is '平盤家數（daily_stock_price_stats.stocks_unchanged）';
+comment on column public.market_breadth_indicator.net_advances is '上漲減下跌家數';
+comment on column public.market_breadth_indicator.ad_line is '騰落線：淨上漲家數自第一筆起的累計值';
+comment on column public.market_breadth_indicator.ratio_adjusted_net_advances is '比例調整淨上漲：(上漲 - 下跌) / (上漲 + 下跌) * 1000';
+comment on column public.market_breadth_indicator.ema_19 is '比例調整淨上漲的 19 日指數移動平均（平滑係數 0.1）';
+comment on column public.market_breadth_indicator.ema_39 is '比例調整淨上漲的 39 日指數移動平均（平滑係數 0.05）';
+comment on column public.market_breadth_indicator.mcclellan_oscillator is 'McClellan 擺盪指標：ema_19 - ema_39';
+comment on column public.market_breadth_indicator.summation_index is 'McClellan 總和指數：擺盪指標自第一筆起的累計值';
+comment on column public.market_breadth_indicator.new_highs is '最高價觸及近 240 個交易日最高價的家數（排除年線未成形的新上市股）';
+comment on column public.market_breadth_indicator.new_lows is '最低價觸及近 240 個交易日最低價的家數（排除年線未成形的新上市股）';
+comment on column public.market_breadth_indicator.pct_above_ma5 is '股價高於五日均線的家數比例（%）';
+comment on column public.market_breadth_indicator.pct_above_ma20 is '股價高於二十日均線的家數比例（%）';
+comment on column public.market_breadth_indicator.pct_above_ma60 is '股價高於六十日均線的家數比例（%）';
+comment on column public.market_breadth_indicator.pct_above_ma120 is '股價高於一百二十日均線的家數比例（%）';
+comment on column public.market_breadth_indicator.pct_above_ma240 is '股價高於二百四十日均線的家數比例（%）';
+comment on column public.market_breadth_indicator.created_time is '建立時間';
+comment on column public.market_breadth_indicator.updated_time is '最後更新時間';
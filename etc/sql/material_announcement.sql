@@ -0,0 +1,34 @@
+create table if not exists public.material_announcement
+(
+    serial       bigserial
+        primary key,
+    stock_symbol varchar(24)                                             not null,
+    company_name varchar(64)              default ''::character varying not null,
+    announced_at timestamp                                               not null,
+    subject      text                                                    not null,
+    clause       varchar(64)              default ''::character varying not null,
+    fact_date    date,
+    description  text                     default ''::text               not null,
+    category     varchar(24)              default 'other'                not null,
+    created_time timestamp with time zone default now()                  not null
+);
+
+comment on table public.material_announcement is '公開資訊觀測站每日重大訊息（上市 t187ap04_L、上櫃 t187ap04_O）';
+
+comment on column public.material_announcement.serial is '流水號';
+comment on column public.material_announcement.stock_symbol is '股票代號';
+comment on column public.material_announcement.company_name is '公司名稱';
+comment on column public.material_announcement.announced_at is '發言日期與時間（台北時間）';
+comment on column public.material_announcement.subject is '主旨';
+comment on column public.material_announcement.clause is '符合條款';
+comment on column public.material_announcement.fact_date is '事實發生日';
+comment on column public.material_announcement.description is '說明';
+comment on column public.material_announcement.category is '關鍵字分類：capital_reduction / split / buyback / dividend / executive_change / other';
+
+-- 盤中反覆輪詢會重複取得同一則訊息，以（代號, 發言時間, 主旨）去重；
+-- 主旨可能很長，索引改用 md5 雜湊。
+create unique index if not exists "material_announcement-dedup-uidx"
+    on public.material_announcement (stock_symbol, announced_at, md5(subject));
+
+create index if not exists "material_announcement-stock_symbol-announced_at-idx"
+    on public.material_announcement (stock_symbol, announced_at desc);
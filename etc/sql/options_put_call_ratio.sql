@@ -0,0 +1,23 @@
+create table if not exists public.options_put_call_ratio
+(
+    date                        date                                        not null
+        primary key,
+    put_volume                  bigint                   default 0          not null,
+    call_volume                 bigint                   default 0          not null,
+    volume_ratio_percent        numeric(10, 2)                              not null,
+    put_open_interest           bigint                   default 0          not null,
+    call_open_interest          bigint                   default 0          not null,
+    open_interest_ratio_percent numeric(10, 2)                              not null,
+    created_time                timestamp with time zone default now()      not null,
+    updated_time                timestamp with time zone default now()      not null
+);
+
+comment on table public.options_put_call_ratio is '期交所臺指選擇權每日賣權買權比（Put/Call Ratio）';
+
+comment on column public.options_put_call_ratio.date is '交易日期';
+comment on column public.options_put_call_ratio.put_volume is '賣權成交量';
+comment on column public.options_put_call_ratio.call_volume is '買權成交量';
+comment on column public.options_put_call_ratio.volume_ratio_percent is '買賣權成交量比率（%），賣權 ÷ 買權';
+comment on column public.options_put_call_ratio.put_open_interest is '賣權未平倉量';
+comment on column public.options_put_call_ratio.call_open_interest is '買權未平倉量';
+comment on column public.options_put_call_ratio.open_interest_ratio_percent is '買賣權未平倉量比率（%），賣權 ÷ 買權';
@@ -0,0 +1,28 @@
+create table if not exists public.public_subscription
+(
+    stock_symbol        varchar(24)                                             not null,
+    offering_start_date date                                                    not null,
+    stock_name          varchar(64)              default ''::character varying not null,
+    market              varchar(32)              default ''::character varying not null,
+    offering_end_date   date                                                    not null,
+    drawing_date        date,
+    offering_price      numeric(18, 4),
+    issue_date          date,
+    created_time        timestamp with time zone default now()                  not null,
+    updated_time        timestamp with time zone default now()                  not null,
+    primary key (stock_symbol, offering_start_date)
+);
+
+comment on table public.public_subscription is '公開申購公告（證交所 announcement/publicForm）';
+
+comment on column public.public_subscription.stock_symbol is '股票代號';
+comment on column public.public_subscription.offering_start_date is '申購開始日';
+comment on column public.public_subscription.stock_name is '股票名稱';
+comment on column public.public_subscription.market is '發行市場';
+comment on column public.public_subscription.offering_end_date is '申購截止日';
+comment on column public.public_subscription.drawing_date is '抽籤日；尚未公布時為 null';
+comment on column public.public_subscription.offering_price is '實際承銷價；尚未公布時為 null';
+comment on column public.public_subscription.issue_date is '撥券日；尚未公布時為 null';
+
+create index if not exists "public_subscription-offering_end_date-idx"
+    on public.public_subscription (offering_end_date);
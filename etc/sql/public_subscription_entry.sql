@@ -0,0 +1,28 @@
+create table if not exists public.public_subscription_entry
+(
+    serial              bigserial
+        primary key,
+    stock_symbol        varchar(24)                            not null,
+    offering_start_date date                                   not null,
+    member_id           bigint                                 not null,
+    won                 boolean,
+    sell_price          numeric(18, 4),
+    realized_profit     numeric(18, 4),
+    created_time        timestamp with time zone default now() not null,
+    updated_time        timestamp with time zone default now() not null,
+    foreign key (stock_symbol, offering_start_date)
+        references public.public_subscription (stock_symbol, offering_start_date)
+);
+
+comment on table public.public_subscription_entry is '成員參與的公開申購、抽籤結果與已實現損益';
+
+comment on column public.public_subscription_entry.serial is '流水號';
+comment on column public.public_subscription_entry.stock_symbol is '股票代號';
+comment on column public.public_subscription_entry.offering_start_date is '申購開始日';
+comment on column public.public_subscription_entry.member_id is '成員編號';
+comment on column public.public_subscription_entry.won is '是否中籤；尚未抽籤時為 null';
+comment on column public.public_subscription_entry.sell_price is '中籤後的賣出價';
+comment on column public.public_subscription_entry.realized_profit is '已實現損益（扣除申購處理費、中籤工本費與證交稅，不含券商手續費）';
+
+create unique index if not exists "public_subscription_entry-stock_symbol-offering_start_date-member_id-uidx"
+    on public.public_subscription_entry (stock_symbol, offering_start_date, member_id);
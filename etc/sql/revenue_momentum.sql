@@ -0,0 +1,40 @@
+create table if not exists public.revenue_momentum
+(
+    security_code        varchar(24)                            not null,
+    month                bigint                                 not null,
+    revenue              numeric(18, 4)                         not null,
+    rolling_3m           numeric(20, 4),
+    rolling_12m          numeric(20, 4),
+    yoy_pct              numeric(18, 4),
+    rolling_3m_yoy_pct   numeric(18, 4),
+    rolling_12m_yoy_pct  numeric(18, 4),
+    yoy_acceleration_pct numeric(18, 4),
+    all_time_high        boolean                                not null,
+    seasonal_expected    numeric(20, 4),
+    surprise_pct         numeric(18, 4),
+    seasonal_years       smallint                               not null,
+    created_time         timestamp with time zone default now() not null,
+    updated_time         timestamp with time zone default now() not null,
+    primary key (security_code, month)
+);
+
+create index if not exists revenue_momentum_month_idx
+    on public.revenue_momentum (month);
+
+comment on table public.revenue_momentum is '由月營收推導的滾動合計、年增率加速度、創新高與季節性驚喜';
+
+comment on column public.revenue_momentum.security_code is '股票代號';
+comment on column public.revenue_momentum.month is '營收月份（YYYYMM，與 Revenue."Date" 相同）';
+comment on column public.revenue_momentum.revenue is '當月營收';
+comment on column public.revenue_momentum.rolling_3m is '近 3 個月營收合計，任一月缺值時為空';
+comment on column public.revenue_momentum.rolling_12m is '近 12 個月營收合計，任一月缺值時為空';
+comment on column public.revenue_momentum.yoy_pct is '單月營收年增率（%）';
+comment on column public.revenue_momentum.rolling_3m_yoy_pct is '近 3 個月合計年增率（%）';
+comment on column public.revenue_momentum.rolling_12m_yoy_pct is '近 12 個月合計年增率（%）';
+comment on column public.revenue_momentum.yoy_acceleration_pct is '年增率加速度（百分點）：近 3 個月年增率減前一個 3 個月區間年增率';
+comment on column public.revenue_momentum.all_time_high is '當月營收是否創歷史新高（至少 12 個月歷史才判斷）';
+comment on column public.revenue_momentum.seasonal_expected is '依歷年同月季節性推估的當月營收';
+comment on column public.revenue_momentum.surprise_pct is '營收驚喜（%）：實際營收相對季節性推估的差距';
+comment on column public.revenue_momentum.seasonal_years is '推估季節性採用的歷年樣本數';
+comment on column public.revenue_momentum.created_time is '建立時間';
+comment on column public.revenue_momentum.updated_time is '最後更新時間';
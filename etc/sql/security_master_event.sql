@@ -0,0 +1,69 @@
+-- stocks 只保存證券的現況（下市只是 SuspendListing 旗標，更名、轉市場會直接覆蓋），
+-- 無法回答「某一天有哪些股票掛牌」。這張表只追加異動紀錄，每筆帶有異動後的完整狀態，
+-- 某日的投資範圍即每檔證券生效日在當日（含）之前最新一筆且仍掛牌者。
+create table if not exists public.security_master_event
+(
+    serial                   bigserial
+        primary key,
+    security_code            varchar(24)                            not null,
+    kind                     varchar(32)                            not null,
+    effective_date           date                                   not null,
+    name                     varchar(255)                           not null,
+    stock_exchange_market_id integer                                not null,
+    stock_industry_id        integer                                not null,
+    listed                   boolean                                not null,
+    previous_name            varchar(255),
+    previous_market_id       integer,
+    previous_industry_id     integer,
+    related_symbol           varchar(24),
+    created_time             timestamp with time zone default now() not null
+);
+
+create unique index if not exists "security_master_event-security_code-kind-effective_date-uidx"
+    on public.security_master_event (security_code, kind, effective_date);
+
+create index if not exists "security_master_event-effective_date-idx"
+    on public.security_master_event (effective_date);
+
+comment on table public.security_master_event is '時點證券主檔異動紀錄（只追加）';
+
+comment on column public.security_master_event.security_code is '股票代號';
+comment on column public.security_master_event.kind is '異動種類 listed:掛牌 delisted:終止上市櫃 market_transfer:轉換市場 renamed:更名 industry_reclassified:產業重新分類 symbol_changed:變更代號';
+comment on column public.security_master_event.effective_date is '異動生效日';
+comment on column public.security_master_event.name is '異動後的名稱';
+comment on column public.security_master_event.stock_exchange_market_id is '異動後的交易所市場編號參考 stock_exchange_market';
+comment on column public.security_master_event.stock_industry_id is '異動後的產業分類編號參考 stock_industry';
+comment on column public.security_master_event.listed is '異動後是否仍掛牌';
+comment on column public.security_master_event.previous_name is '異動前的名稱（僅更名）';
+comment on column public.security_master_event.previous_market_id is '異動前的市場編號（僅轉換市場）';
+comment on column public.security_master_event.previous_industry_id is '異動前的產業分類編號（僅產業重新分類）';
+comment on column public.security_master_event.related_symbol is '變更代號時對應的另一個代號';
+comment on column public.security_master_event.created_time is '建立時間';
+
+-- 既有證券建立初始紀錄：以第一個有報價的交易日（沒有報價時用建檔日）當作掛牌日，
+-- 已下市者以最後一個有報價交易日的隔天當作下市日。更早的更名與轉市場歷史無從得知。
+insert into public.security_master_event (security_code, kind, effective_date, name, stock_exchange_market_id,
+                                          stock_industry_id, listed)
+select s.stock_symbol, 'listed', coalesce(q.first_date, s."CreateTime"::date), s."Name",
+       s.stock_exchange_market_id, s.stock_industry_id, true
+from public.stocks s
+         left join (select stock_symbol, min("Date") as first_date
+                    from public."DailyQuotes"
+                    group by stock_symbol) q on q.stock_symbol = s.stock_symbol
+where not exists (select 1 from public.security_master_event e where e.security_code = s.stock_symbol)
+on conflict do nothing;
+
+insert into public.security_master_event (security_code, kind, effective_date, name, stock_exchange_market_id,
+                                          stock_industry_id, listed)
+select s.stock_symbol, 'delisted', coalesce(q.last_date + 1, s."CreateTime"::date), s."Name",
+       s.stock_exchange_market_id, s.stock_industry_id, false
+from public.stocks s
+         left join (select stock_symbol, max("Date") as last_date
+                    from public."DailyQuotes"
+                    group by stock_symbol) q on q.stock_symbol = s.stock_symbol
+where s."SuspendListing"
+  and not exists (select 1
+                  from public.security_master_event e
+                  where e.security_code = s.stock_symbol
+                    and e.kind = 'delisted')
+on conflict do nothing;
@@ -0,0 +1,25 @@
+create table if not exists public.shareholding_distribution
+(
+    stock_symbol varchar(24)                                 not null,
+    date         date                                        not null,
+    level        smallint                                    not null,
+    holders      bigint                   default 0          not null,
+    shares       bigint                   default 0          not null,
+    percentage   numeric(7, 4)            default 0          not null,
+    created_time timestamp with time zone default now()      not null,
+    updated_time timestamp with time zone default now()      not null,
+    primary key (date, stock_symbol, level)
+);
+
+comment on table public.shareholding_distribution is '集保戶股權分散表每週快照（TDCC 開放資料 1-5）';
+
+comment on column public.shareholding_distribution.stock_symbol is '證券代號';
+comment on column public.shareholding_distribution.date is '資料日期（每週最後一個營業日）';
+comment on column public.shareholding_distribution.level is '持股分級：1–15 為持股區間，16 為差異數調整，17 為合計';
+comment on column public.shareholding_distribution.holders is '人數';
+comment on column public.shareholding_distribution.shares is '股數';
+comment on column public.shareholding_distribution.percentage is '占集保庫存數比例（%）';
+
+-- 主鍵以日期為前導欄位，供整週替換使用；個股最近數週查詢另建索引。
+create index if not exists "shareholding_distribution-stock_symbol-date-idx"
+    on public.shareholding_distribution (stock_symbol, date desc);
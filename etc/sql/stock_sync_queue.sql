@@ -0,0 +1,33 @@
+-- 證券主檔異動後要同步給外部服務（Go 服務）。原本每筆異動直接呼叫一次 gRPC，失敗只記 log，
+-- 兩邊會逐漸不一致。改為先排入這張表，由背景工作批次送出，失敗依次數延後重試；
+-- 同一檔證券只保留一筆，送出時讀取 stocks 的最新內容。
+create table if not exists public.stock_sync_queue
+(
+    serial            bigserial
+        primary key,
+    stock_symbol      varchar(24)                            not null,
+    reason            varchar(32)                            not null,
+    attempts          integer                  default 0     not null,
+    version           integer                  default 1     not null,
+    next_attempt_time timestamp with time zone default now() not null,
+    last_error        text,
+    created_time      timestamp with time zone default now() not null,
+    updated_time      timestamp with time zone default now() not null
+);
+
+create unique index if not exists "stock_sync_queue-stock_symbol-uidx"
+    on public.stock_sync_queue (stock_symbol);
+
+create index if not exists "stock_sync_queue-next_attempt_time-idx"
+    on public.stock_sync_queue (next_attempt_time);
+
+comment on table public.stock_sync_queue is '證券主檔對外同步佇列';
+
+comment on column public.stock_sync_queue.stock_symbol is '股票代號';
+comment on column public.stock_sync_queue.reason is '最近一次排入的原因 registered:新增 identity_changed:名稱市場產業變更 net_asset_value_updated:淨值變更 delisted:下市 reconcile:全量比對差異';
+comment on column public.stock_sync_queue.attempts is '已失敗次數';
+comment on column public.stock_sync_queue.version is '每次重新排入遞增，避免送出期間的新異動被當成已完成';
+comment on column public.stock_sync_queue.next_attempt_time is '下次可送出的時間';
+comment on column public.stock_sync_queue.last_error is '最近一次失敗原因';
+comment on column public.stock_sync_queue.created_time is '建立時間';
+comment on column public.stock_sync_queue.updated_time is '更新時間';
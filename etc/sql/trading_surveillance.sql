@@ -0,0 +1,33 @@
+create table if not exists public.trading_surveillance
+(
+    serial       bigserial
+        primary key,
+    stock_symbol varchar(24)                                             not null,
+    name         varchar(64)              default ''::character varying not null,
+    kind         varchar(16)                                             not null,
+    announced_on date                                                    not null,
+    start_date   date                                                    not null,
+    end_date     date                                                    not null,
+    reason       text                     default ''::text               not null,
+    measure      text                     default ''::text               not null,
+    created_time timestamp with time zone default now()                  not null,
+    updated_time timestamp with time zone default now()                  not null
+);
+
+comment on table public.trading_surveillance is '注意股與處置股列入期間的歷史（證交所 announcement/notice、punish 與櫃買中心對應資料）';
+
+comment on column public.trading_surveillance.serial is '流水號';
+comment on column public.trading_surveillance.stock_symbol is '股票代號';
+comment on column public.trading_surveillance.name is '股票名稱';
+comment on column public.trading_surveillance.kind is '種類：attention（注意股）/ disposition（處置股）';
+comment on column public.trading_surveillance.announced_on is '公告日期；注意股連續公告時為最近一次';
+comment on column public.trading_surveillance.start_date is '起日（含）';
+comment on column public.trading_surveillance.end_date is '迄日（含）；注意股連續交易日重複公告時延長';
+comment on column public.trading_surveillance.reason is '列入原因（注意交易資訊或處置條件）';
+comment on column public.trading_surveillance.measure is '處置措施；注意股為空字串';
+
+create unique index if not exists "trading_surveillance-stock_symbol-kind-start_date-uidx"
+    on public.trading_surveillance (stock_symbol, kind, start_date);
+
+create index if not exists "trading_surveillance-end_date-start_date-idx"
+    on public.trading_surveillance (end_date, start_date);
@@ -0,0 +1,33 @@
+create table if not exists public.valuation_band
+(
+    security_code varchar(24)                            not null,
+    date          date                                   not null,
+    metric        varchar(16)                            not null,
+    years         integer                                not null,
+    current       numeric(18, 4)                         not null,
+    percentile    numeric(6, 2)                          not null,
+    minimum       numeric(18, 4)                         not null,
+    median        numeric(18, 4)                         not null,
+    maximum       numeric(18, 4)                         not null,
+    samples       integer                                not null,
+    created_time  timestamp with time zone default now() not null,
+    updated_time  timestamp with time zone default now() not null,
+    primary key (security_code, date, metric, years)
+);
+
+comment on table public.valuation_band is '歷史估值區間：當日殖利率、本益比、股價淨值比在自身 5／10 年歷史中的位置';
+
+comment on column public.valuation_band.security_code is '證券代號';
+comment on column public.valuation_band.date is '計算基準日';
+comment on column public.valuation_band.metric is '指標：dividend_yield 殖利率、per 本益比、pbr 股價淨值比';
+comment on column public.valuation_band.years is '回顧年數（5 或 10）';
+comment on column public.valuation_band.current is '基準日的指標數值';
+comment on column public.valuation_band.percentile is '回顧期間內不高於基準日數值的樣本比例（%）';
+comment on column public.valuation_band.minimum is '回顧期間最小值';
+comment on column public.valuation_band.median is '回顧期間中位數';
+comment on column public.valuation_band.maximum is '回顧期間最大值';
+comment on column public.valuation_band.samples is '回顧期間的有效樣本（交易日）數';
+
+-- 條件選股依股票取最新基準日；以 (security_code, years, date desc) 直接定位。
+create index if not exists "valuation_band-security_code-years-date-desc-idx"
+    on public.valuation_band (security_code, years, date desc);
@@ -0,0 +1,28 @@
+create table if not exists public.valuation_model_estimate
+(
+    security_code varchar(24)                            not null,
+    date          date                                   not null,
+    model         varchar(16)                            not null,
+    closing_price numeric(18, 4)                         not null,
+    cheap         numeric(18, 4)                         not null,
+    fair          numeric(18, 4)                         not null,
+    expensive     numeric(18, 4)                         not null,
+    assumptions   text                                   not null,
+    created_time  timestamp with time zone default now() not null,
+    updated_time  timestamp with time zone default now() not null,
+    primary key (security_code, date, model)
+);
+
+comment on table public.valuation_model_estimate is '各估價模型每日的便宜、合理、昂貴價';
+
+comment on column public.valuation_model_estimate.security_code is '證券代號';
+comment on column public.valuation_model_estimate.date is '計算日';
+comment on column public.valuation_model_estimate.model is '模型：yield_band、per_band、pbr_band、graham、ddm';
+comment on column public.valuation_model_estimate.closing_price is '計算時採用的收盤價';
+comment on column public.valuation_model_estimate.cheap is '便宜價';
+comment on column public.valuation_model_estimate.fair is '合理價';
+comment on column public.valuation_model_estimate.expensive is '昂貴價';
+comment on column public.valuation_model_estimate.assumptions is '計算時的模型假設（JSON）';
+
+create index if not exists "valuation_model_estimate-model-date-idx"
+    on public.valuation_model_estimate (model, date);
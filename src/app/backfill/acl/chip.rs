@@ -0,0 +1,189 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+
+use crate::{
+    domain::chip::{
+        CreditBalance, InstitutionalTrade, MarginTrading, ShareholdingDistribution,
+        ShareholdingTier, TradeVolume,
+    },
+    infra::crawler::share::{InstitutionalTradeDto, MarginTradingDto, ShareholdingTierDto},
+};
+
+/// 三大法人買賣超爬蟲資料防腐層轉譯器。
+///
+/// 上市（T86）與上櫃的欄位差異已在爬蟲端整理成相同口徑，這裡只負責
+/// 組成領域實體的值物件，不再做欄位推導。
+pub struct InstitutionalTradeAclMapper;
+
+impl InstitutionalTradeAclMapper {
+    /// 將爬蟲 DTO 轉譯為領域模型 `InstitutionalTrade`。
+    pub fn from_dto(dto: &InstitutionalTradeDto) -> InstitutionalTrade {
+        InstitutionalTrade::new(
+            dto.stock_symbol.clone(),
+            dto.date,
+            TradeVolume::new(dto.foreign_buy, dto.foreign_sell, dto.foreign_net),
+            dto.foreign_dealer_net,
+            TradeVolume::new(
+                dto.investment_trust_buy,
+                dto.investment_trust_sell,
+                dto.investment_trust_net,
+            ),
+            TradeVolume::new(dto.dealer_buy, dto.dealer_sell, dto.dealer_net),
+            dto.dealer_proprietary_net,
+            dto.dealer_hedge_net,
+            dto.total_net,
+        )
+    }
+}
+
+/// 融資融券餘額爬蟲資料防腐層轉譯器。
+pub struct MarginTradingAclMapper;
+
+impl MarginTradingAclMapper {
+    /// 將爬蟲 DTO 轉譯為領域模型 `MarginTrading`。
+    ///
+    /// 融券的買進（回補）與賣出（放空）直接對應 [`CreditBalance`] 的 `buy` / `sell`。
+    pub fn from_dto(dto: &MarginTradingDto) -> MarginTrading {
+        MarginTrading::new(
+            dto.stock_symbol.clone(),
+            dto.date,
+            CreditBalance::new(
+                dto.margin_buy,
+                dto.margin_sell,
+                dto.margin_redemption,
+                dto.margin_previous_balance,
+                dto.margin_balance,
+                dto.margin_quota,
+            ),
+            CreditBalance::new(
+                dto.short_cover,
+                dto.short_sell,
+                dto.short_redemption,
+                dto.short_previous_balance,
+                dto.short_balance,
+                dto.short_quota,
+            ),
+            dto.offset,
+        )
+    }
+}
+
+/// 集保戶股權分散表爬蟲資料防腐層轉譯器。
+pub struct ShareholdingDistributionAclMapper;
+
+impl ShareholdingDistributionAclMapper {
+    /// 將逐分級的爬蟲 DTO 依「代號 × 資料日期」彙整為領域模型 `ShareholdingDistribution`。
+    ///
+    /// 回傳順序依代號與日期排序，與來源列順序無關。
+    pub fn from_dtos(dtos: &[ShareholdingTierDto]) -> Vec<ShareholdingDistribution> {
+        let mut grouped: BTreeMap<(&str, NaiveDate), Vec<ShareholdingTier>> = BTreeMap::new();
+        for dto in dtos {
+            grouped
+                .entry((dto.stock_symbol.as_str(), dto.date))
+                .or_default()
+                .push(ShareholdingTier::new(
+                    dto.level,
+                    dto.holders,
+                    dto.shares,
+                    dto.percentage,
+                ));
+        }
+
+        grouped
+            .into_iter()
+            .map(|((symbol, date), tiers)| {
+                ShareholdingDistribution::new(symbol.to_string(), date, tiers)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn test_institutional_trade_acl_mapping() {
+        let dto = InstitutionalTradeDto {
+            stock_symbol: "2330".to_string(),
+            date: NaiveDate::from_ymd_opt(2026, 4, 30).unwrap(),
+            foreign_buy: 1,
+            foreign_sell: 2,
+            foreign_net: 3,
+            foreign_dealer_net: 4,
+            investment_trust_buy: 5,
+            investment_trust_sell: 6,
+            investment_trust_net: 7,
+            dealer_buy: 8,
+            dealer_sell: 9,
+            dealer_net: 10,
+            dealer_proprietary_net: 11,
+            dealer_hedge_net: 12,
+            total_net: 13,
+        };
+
+        let trade = InstitutionalTradeAclMapper::from_dto(&dto);
+
+        assert_eq!(trade.stock_symbol, "2330");
+        assert_eq!(trade.date, dto.date);
+        assert_eq!(trade.foreign, TradeVolume::new(1, 2, 3));
+        assert_eq!(trade.foreign_dealer_net, 4);
+        assert_eq!(trade.investment_trust, TradeVolume::new(5, 6, 7));
+        assert_eq!(trade.dealer, TradeVolume::new(8, 9, 10));
+        assert_eq!(trade.dealer_proprietary_net, 11);
+        assert_eq!(trade.dealer_hedge_net, 12);
+        assert_eq!(trade.total_net, 13);
+    }
+
+    #[test]
+    fn test_margin_trading_acl_mapping() {
+        let dto = MarginTradingDto {
+            stock_symbol: "2330".to_string(),
+            date: NaiveDate::from_ymd_opt(2026, 4, 30).unwrap(),
+            margin_buy: 1,
+            margin_sell: 2,
+            margin_redemption: 3,
+            margin_previous_balance: 4,
+            margin_balance: 5,
+            margin_quota: 6,
+            short_sell: 7,
+            short_cover: 8,
+            short_redemption: 9,
+            short_previous_balance: 10,
+            short_balance: 11,
+            short_quota: 12,
+            offset: 13,
+        };
+
+        let trading = MarginTradingAclMapper::from_dto(&dto);
+
+        assert_eq!(trading.stock_symbol, "2330");
+        assert_eq!(trading.margin, CreditBalance::new(1, 2, 3, 4, 5, 6));
+        assert_eq!(trading.short, CreditBalance::new(8, 7, 9, 10, 11, 12));
+        assert_eq!(trading.offset, 13);
+    }
+
+    #[test]
+    fn test_shareholding_distribution_acl_groups_by_symbol_and_date() {
+        let date = NaiveDate::from_ymd_opt(2026, 4, 24).unwrap();
+        let tier = |symbol: &str, level: i32| ShareholdingTierDto {
+            stock_symbol: symbol.to_string(),
+            date,
+            level,
+            holders: i64::from(level),
+            shares: 1_000,
+            percentage: Decimal::ONE,
+        };
+        let dtos = vec![tier("2330", 15), tier("0050", 1), tier("2330", 1)];
+
+        let distributions = ShareholdingDistributionAclMapper::from_dtos(&dtos);
+
+        assert_eq!(distributions.len(), 2);
+        assert_eq!(distributions[0].stock_symbol, "0050");
+        assert_eq!(distributions[1].stock_symbol, "2330");
+        let levels: Vec<i32> = distributions[1].tiers.iter().map(|t| t.level).collect();
+        assert_eq!(levels, vec![1, 15]);
+    }
+}
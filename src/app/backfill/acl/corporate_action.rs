@@ -0,0 +1,98 @@
+use crate::{
+    domain::performance::{CorporateAction, CorporateActionProposal, implied_share_ratio},
+    infra::crawler::share::CapitalChangeDto,
+};
+
+/// 減資／變更面額恢復買賣資料防腐層轉譯器。
+pub struct CorporateActionProposalAclMapper;
+
+impl CorporateActionProposalAclMapper {
+    /// 將恢復買賣參考價轉譯為待確認的公司行動建議。
+    ///
+    /// 生效日為恢復買賣日期；股數變動比例以停止買賣前收盤價除以恢復買賣參考價
+    /// 推估。任一價格非正數（來源尚未公布參考價）時回傳 `None`，待下一輪再建議。
+    pub fn from_capital_change(
+        source: &str,
+        dto: &CapitalChangeDto,
+    ) -> Option<CorporateActionProposal> {
+        let share_ratio = implied_share_ratio(dto.last_close, dto.reference_price)?;
+        let mut note = format!(
+            "{}恢復買賣：停止買賣前收盤 {}，恢復買賣參考價 {}",
+            dto.kind.label(),
+            dto.last_close.normalize(),
+            dto.reference_price.normalize()
+        );
+        if !dto.reason.is_empty() {
+            note.push_str(&format!("（{}）", dto.reason));
+        }
+
+        Some(CorporateActionProposal {
+            stock_symbol: dto.stock_symbol.clone(),
+            source: source.to_string(),
+            reference: format!("{} {}", dto.resumed_on, dto.kind.code()),
+            action_type: CorporateAction::action_type_for(share_ratio).to_string(),
+            effective_date: Some(dto.resumed_on),
+            share_ratio: Some(share_ratio),
+            note,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use rust_decimal_macros::dec;
+
+    use super::*;
+    use crate::infra::crawler::share::CapitalChangeKind;
+
+    fn dto(kind: CapitalChangeKind, last_close: rust_decimal::Decimal) -> CapitalChangeDto {
+        CapitalChangeDto {
+            stock_symbol: "2024".to_string(),
+            company_name: "志聯".to_string(),
+            kind,
+            resumed_on: NaiveDate::from_ymd_opt(2026, 5, 12).unwrap(),
+            last_close,
+            reference_price: dec!(50),
+            reason: "彌補虧損".to_string(),
+        }
+    }
+
+    #[test]
+    fn capital_reduction_becomes_a_ratio_below_one() {
+        let proposal = CorporateActionProposalAclMapper::from_capital_change(
+            "twse",
+            &dto(CapitalChangeKind::CapitalReduction, dec!(30)),
+        )
+        .expect("價格齊全應產生建議");
+
+        assert_eq!(proposal.source, "twse");
+        assert_eq!(proposal.reference, "2026-05-12 capital_reduction");
+        assert_eq!(proposal.action_type, "capital_reduction");
+        assert_eq!(proposal.share_ratio, Some(dec!(0.6)));
+        assert_eq!(
+            proposal.effective_date,
+            NaiveDate::from_ymd_opt(2026, 5, 12)
+        );
+        assert!(proposal.note.contains("彌補虧損"));
+    }
+
+    #[test]
+    fn par_value_change_becomes_a_split_and_missing_prices_are_skipped() {
+        let proposal = CorporateActionProposalAclMapper::from_capital_change(
+            "tpex",
+            &dto(CapitalChangeKind::ParValueChange, dec!(500)),
+        )
+        .expect("價格齊全應產生建議");
+        assert_eq!(proposal.action_type, "split");
+        assert_eq!(proposal.share_ratio, Some(dec!(10)));
+
+        assert!(
+            CorporateActionProposalAclMapper::from_capital_change(
+                "tpex",
+                &dto(CapitalChangeKind::ParValueChange, dec!(0)),
+            )
+            .is_none()
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use chrono::NaiveDate;
+
+use crate::{
+    domain::derivatives::{
+        DerivativesDailySnapshot, FuturesDailyQuote, FuturesInstitutionalPosition, PutCallRatio,
+        TraderType,
+    },
+    infra::crawler::share::{
+        FuturesDailyQuoteDto, FuturesInstitutionalPositionDto, PutCallRatioDto,
+    },
+};
+
+/// 期交所期貨選擇權爬蟲資料防腐層轉譯器。
+///
+/// 三份來源各自解析，這裡把它們組成單一交易日的快照，並把來源的
+/// 中文身份別轉成領域的 [`TraderType`]。
+pub struct DerivativesAclMapper;
+
+impl DerivativesAclMapper {
+    /// 將三份爬蟲 DTO 組成 `date` 當日的 [`DerivativesDailySnapshot`]。
+    ///
+    /// 非 `date` 的資料列與無法辨識身份別的法人部位會被略過。
+    pub fn from_dtos(
+        date: NaiveDate,
+        futures: &[FuturesDailyQuoteDto],
+        positions: &[FuturesInstitutionalPositionDto],
+        put_call_ratio: Option<&PutCallRatioDto>,
+    ) -> DerivativesDailySnapshot {
+        let futures = futures
+            .iter()
+            .filter(|dto| dto.date == date)
+            .map(|dto| FuturesDailyQuote {
+                date: dto.date,
+                contract: dto.contract.clone(),
+                contract_month: dto.contract_month.clone(),
+                open: dto.open,
+                high: dto.high,
+                low: dto.low,
+                close: dto.close,
+                change: dto.change,
+                volume: dto.volume,
+                settlement_price: dto.settlement_price,
+                open_interest: dto.open_interest,
+            })
+            .collect();
+
+        let positions = positions
+            .iter()
+            .filter(|dto| dto.date == date)
+            .filter_map(|dto| {
+                let Some(trader_type) = TraderType::from_source_name(&dto.trader_type) else {
+                    tracing::warn!("略過無法辨識的期貨法人身份別 {}", dto.trader_type);
+                    return None;
+                };
+                Some(FuturesInstitutionalPosition {
+                    date: dto.date,
+                    contract: dto.contract.clone(),
+                    trader_type,
+                    long_trade_volume: dto.long_trade_volume,
+                    short_trade_volume: dto.short_trade_volume,
+                    net_trade_volume: dto.net_trade_volume,
+                    long_open_interest: dto.long_open_interest,
+                    short_open_interest: dto.short_open_interest,
+                    net_open_interest: dto.net_open_interest,
+                    net_open_interest_amount: dto.net_open_interest_amount,
+                })
+            })
+            .collect();
+
+        let put_call_ratio =
+            put_call_ratio
+                .filter(|dto| dto.date == date)
+                .map(|dto| PutCallRatio {
+                    date: dto.date,
+                    put_volume: dto.put_volume,
+                    call_volume: dto.call_volume,
+                    volume_ratio_percent: dto.volume_ratio_percent,
+                    put_open_interest: dto.put_open_interest,
+                    call_open_interest: dto.call_open_interest,
+                    open_interest_ratio_percent: dto.open_interest_ratio_percent,
+                });
+
+        DerivativesDailySnapshot {
+            date,
+            futures,
+            positions,
+            put_call_ratio,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use crate::{
+    domain::disclosure::{AnnouncementCategory, MaterialAnnouncement},
+    infra::crawler::share::MaterialInformationDto,
+};
+
+/// 重大訊息爬蟲資料防腐層轉譯器。
+///
+/// 關鍵字分類在轉譯時完成，寫入資料庫的每一則訊息都帶有分類。
+pub struct MaterialAnnouncementAclMapper;
+
+impl MaterialAnnouncementAclMapper {
+    /// 將爬蟲 DTO 轉譯為領域模型 `MaterialAnnouncement`。
+    pub fn from_dto(dto: &MaterialInformationDto) -> MaterialAnnouncement {
+        MaterialAnnouncement {
+            stock_symbol: dto.stock_symbol.clone(),
+            company_name: dto.company_name.clone(),
+            announced_at: dto.announced_at,
+            subject: dto.subject.clone(),
+            clause: dto.clause.clone(),
+            fact_date: dto.fact_date,
+            description: dto.description.clone(),
+            category: AnnouncementCategory::classify(&dto.subject),
+        }
+    }
+}
@@ -1,5 +1,9 @@
+use std::collections::HashMap;
+
 use rust_decimal::Decimal;
 
+use crate::domain::financial::line_item::{FinancialLineItem, LineItem, PeriodType, StatementKind};
+
 /// 更新每股淨值命令。
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct UpdateNetAssetValueCommand {
@@ -140,6 +144,240 @@ impl FinancialStatementAclMapper {
     }
 }
 
+/// IFRS 分類標準元素（不含前綴）與正規化科目的對照，依優先順序排列。
+///
+/// 一般業 2019 年前使用 `tifrs-bsci-ci` 自訂元素，之後改用 `ifrs-full`；
+/// 金融保險業則各有自訂元素。同一科目有多個元素申報時取排在前面的。
+const XBRL_LINE_ITEMS: &[(LineItem, &[&str])] = &[
+    (
+        LineItem::Revenue,
+        &[
+            "Revenue",
+            "OperatingRevenue",
+            "TotalNetRevenue",
+            "NetRevenue",
+        ],
+    ),
+    (LineItem::CostOfRevenue, &["CostOfSales", "OperatingCosts"]),
+    (
+        LineItem::GrossProfit,
+        &["GrossProfit", "GrossProfitLossFromOperations"],
+    ),
+    (
+        LineItem::OperatingExpenses,
+        &["OperatingExpense", "OperatingExpenses"],
+    ),
+    (
+        LineItem::OperatingIncome,
+        &[
+            "ProfitLossFromOperatingActivities",
+            "NetOperatingIncomeLoss",
+        ],
+    ),
+    (
+        LineItem::NonOperatingIncome,
+        &[
+            "NonoperatingIncomeAndExpenses",
+            "TotalNonoperatingIncomeAndExpenses",
+        ],
+    ),
+    (LineItem::FinanceCosts, &["FinanceCosts", "FinanceCostsNet"]),
+    (
+        LineItem::ProfitBeforeTax,
+        &[
+            "ProfitLossBeforeTax",
+            "ProfitLossFromContinuingOperationsBeforeTax",
+        ],
+    ),
+    (
+        LineItem::IncomeTaxExpense,
+        &["IncomeTaxExpenseContinuingOperations", "TaxExpenseIncome"],
+    ),
+    (LineItem::NetIncome, &["ProfitLoss"]),
+    (
+        LineItem::NetIncomeAttributableToParent,
+        &["ProfitLossAttributableToOwnersOfParent"],
+    ),
+    (
+        LineItem::BasicEarningsPerShare,
+        &["BasicEarningsLossPerShare"],
+    ),
+    (
+        LineItem::DilutedEarningsPerShare,
+        &["DilutedEarningsLossPerShare"],
+    ),
+    (LineItem::CashAndEquivalents, &["CashAndCashEquivalents"]),
+    (
+        LineItem::AccountsReceivable,
+        &[
+            "AccountsReceivableNet",
+            "CurrentTradeReceivables",
+            "TradeAndOtherCurrentReceivables",
+        ],
+    ),
+    (
+        LineItem::Inventories,
+        &["Inventories", "CurrentInventories"],
+    ),
+    (LineItem::CurrentAssets, &["CurrentAssets"]),
+    (
+        LineItem::PropertyPlantAndEquipment,
+        &["PropertyPlantAndEquipment"],
+    ),
+    (LineItem::TotalAssets, &["Assets"]),
+    (
+        LineItem::ShortTermBorrowings,
+        &["ShorttermBorrowings", "ShortTermBorrowings"],
+    ),
+    (LineItem::CurrentLiabilities, &["CurrentLiabilities"]),
+    (
+        LineItem::BondsPayable,
+        &["BondsPayable", "NoncurrentPortionOfNoncurrentBondsIssued"],
+    ),
+    (
+        LineItem::LongTermBorrowings,
+        &[
+            "LongtermBorrowings",
+            "NoncurrentPortionOfNoncurrentLoansReceived",
+        ],
+    ),
+    (LineItem::NoncurrentLiabilities, &["NoncurrentLiabilities"]),
+    (LineItem::TotalLiabilities, &["Liabilities"]),
+    (LineItem::OrdinaryShare, &["OrdinaryShare"]),
+    (LineItem::RetainedEarnings, &["RetainedEarnings"]),
+    (
+        LineItem::EquityAttributableToParent,
+        &["EquityAttributableToOwnersOfParent"],
+    ),
+    (LineItem::TotalEquity, &["Equity"]),
+    (
+        LineItem::Depreciation,
+        &["DepreciationExpense", "AdjustmentsForDepreciationExpense"],
+    ),
+    (
+        LineItem::Amortisation,
+        &[
+            "AmortizationExpense",
+            "AmortisationExpense",
+            "AdjustmentsForAmortisationExpense",
+        ],
+    ),
+    (
+        LineItem::OperatingCashFlow,
+        &[
+            "CashFlowsFromUsedInOperatingActivities",
+            "NetCashFlowsFromUsedInOperatingActivities",
+        ],
+    ),
+    (
+        LineItem::CapitalExpenditure,
+        &[
+            "AcquisitionOfPropertyPlantAndEquipment",
+            "PurchaseOfPropertyPlantAndEquipmentClassifiedAsInvestingActivities",
+        ],
+    ),
+    (
+        LineItem::InvestingCashFlow,
+        &[
+            "CashFlowsFromUsedInInvestingActivities",
+            "NetCashFlowsFromUsedInInvestingActivities",
+        ],
+    ),
+    (
+        LineItem::DividendsPaid,
+        &[
+            "CashDividendsPaid",
+            "DividendsPaidClassifiedAsFinancingActivities",
+        ],
+    ),
+    (
+        LineItem::FinancingCashFlow,
+        &[
+            "CashFlowsFromUsedInFinancingActivities",
+            "NetCashFlowsFromUsedInFinancingActivities",
+        ],
+    ),
+    (
+        LineItem::NetChangeInCash,
+        &[
+            "IncreaseDecreaseInCashAndCashEquivalents",
+            "NetIncreaseDecreaseInCashAndCashEquivalents",
+        ],
+    ),
+];
+
+/// MOPS XBRL 季報防腐層轉譯器。
+pub struct FinancialLineItemAclMapper;
+
+impl FinancialLineItemAclMapper {
+    /// 將單一公司的 XBRL 申報轉譯為正規化科目。
+    ///
+    /// 時點事實對應季末餘額；區間事實依起始日分為單季（季初起算）與年初累計
+    /// （1 月 1 日起算），第一季兩者相同，兩種期間各寫一筆。對照表以外的元素略過。
+    pub fn from_xbrl(
+        filing: &crate::infra::crawler::mops::xbrl::XbrlFiling,
+    ) -> Vec<FinancialLineItem> {
+        use crate::infra::crawler::mops::xbrl::XbrlPeriod;
+        use chrono::{Datelike, Months};
+
+        let quarter_start =
+            crate::infra::crawler::mops::xbrl::quarter_end(filing.year, filing.quarter)
+                .with_day(1)
+                .and_then(|month| month.checked_sub_months(Months::new(2)));
+
+        // (科目, 期間) → (優先順序, 科目數值)
+        let mut best: HashMap<(LineItem, PeriodType), (usize, FinancialLineItem)> = HashMap::new();
+        for fact in &filing.facts {
+            let Some((item, rank)) = XBRL_LINE_ITEMS.iter().find_map(|(item, elements)| {
+                elements
+                    .iter()
+                    .position(|element| *element == fact.local_name())
+                    .map(|rank| (*item, rank))
+            }) else {
+                continue;
+            };
+
+            let periods: Vec<PeriodType> = match (item.statement(), fact.period) {
+                (StatementKind::BalanceSheet, XbrlPeriod::Instant(_)) => vec![PeriodType::Instant],
+                (StatementKind::BalanceSheet, _) | (_, XbrlPeriod::Instant(_)) => continue,
+                (_, XbrlPeriod::Duration { start, .. }) => {
+                    let mut periods = Vec::with_capacity(2);
+                    if Some(start) == quarter_start {
+                        periods.push(PeriodType::Quarter);
+                    }
+                    if start.month() == 1 && start.day() == 1 && start.year() == filing.year {
+                        periods.push(PeriodType::YearToDate);
+                    }
+                    periods
+                }
+            };
+
+            for period in periods {
+                let candidate = FinancialLineItem {
+                    security_code: filing.security_code.clone(),
+                    year: filing.year,
+                    quarter: filing.quarter,
+                    item,
+                    period,
+                    value: fact.value,
+                    element: fact.element.clone(),
+                    consolidated: filing.consolidated,
+                };
+                match best.get(&(item, period)) {
+                    Some((existing, _)) if *existing <= rank => {}
+                    _ => {
+                        best.insert((item, period), (rank, candidate));
+                    }
+                }
+            }
+        }
+
+        let mut items: Vec<FinancialLineItem> = best.into_values().map(|(_, item)| item).collect();
+        items.sort_by_key(|item| (item.item, item.period));
+        items
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -254,4 +492,99 @@ mod tests {
         assert_eq!(entity.profit_before_tax, dec!(13.0));
         assert_eq!(entity.gross_profit, dec!(0));
     }
+
+    #[test]
+    fn test_financial_line_items_from_xbrl() {
+        use crate::core::declare::Quarter;
+        use crate::infra::crawler::mops::xbrl::{XbrlFact, XbrlFiling, XbrlPeriod};
+        use chrono::NaiveDate;
+
+        let date = |m, d| NaiveDate::from_ymd_opt(2024, m, d).unwrap();
+        let fact = |element: &str, period, value| XbrlFact {
+            element: element.to_string(),
+            period,
+            value,
+        };
+        let quarter = XbrlPeriod::Duration {
+            start: date(4, 1),
+            end: date(6, 30),
+        };
+        let ytd = XbrlPeriod::Duration {
+            start: date(1, 1),
+            end: date(6, 30),
+        };
+        let instant = XbrlPeriod::Instant(date(6, 30));
+        let filing = XbrlFiling {
+            security_code: "2330".to_string(),
+            year: 2024,
+            quarter: Quarter::Q2,
+            consolidated: true,
+            facts: vec![
+                // 較舊的自訂元素排在 ifrs-full 之後，兩者並存時取 ifrs-full。
+                fact("tifrs-bsci-ci:OperatingRevenue", quarter, dec!(1)),
+                fact("ifrs-full:Revenue", quarter, dec!(673510177000)),
+                fact("ifrs-full:Revenue", ytd, dec!(1266003608000)),
+                fact("ifrs-full:Assets", instant, dec!(6165648490000)),
+                fact(
+                    "tifrs-SCF:AcquisitionOfPropertyPlantAndEquipment",
+                    ytd,
+                    dec!(-459281044000),
+                ),
+                fact("ifrs-full:SomethingElse", ytd, dec!(5)),
+            ],
+        };
+
+        let items = FinancialLineItemAclMapper::from_xbrl(&filing);
+        assert_eq!(items.len(), 4);
+        let revenue = items
+            .iter()
+            .find(|item| item.item == LineItem::Revenue && item.period == PeriodType::Quarter)
+            .unwrap();
+        assert_eq!(revenue.value, dec!(673510177000));
+        assert_eq!(revenue.element, "ifrs-full:Revenue");
+        assert!(items.iter().any(|item| item.item == LineItem::Revenue
+            && item.period == PeriodType::YearToDate
+            && item.value == dec!(1266003608000)));
+        assert!(
+            items.iter().any(
+                |item| item.item == LineItem::TotalAssets && item.period == PeriodType::Instant
+            )
+        );
+        assert!(
+            items
+                .iter()
+                .any(|item| item.item == LineItem::CapitalExpenditure
+                    && item.period == PeriodType::YearToDate
+                    && item.statement() == StatementKind::CashFlow)
+        );
+    }
+
+    #[test]
+    fn test_financial_line_items_first_quarter_is_both_periods() {
+        use crate::core::declare::Quarter;
+        use crate::infra::crawler::mops::xbrl::{XbrlFact, XbrlFiling, XbrlPeriod};
+        use chrono::NaiveDate;
+
+        let filing = XbrlFiling {
+            security_code: "1101".to_string(),
+            year: 2024,
+            quarter: Quarter::Q1,
+            consolidated: false,
+            facts: vec![XbrlFact {
+                element: "ifrs-full:ProfitLoss".to_string(),
+                period: XbrlPeriod::Duration {
+                    start: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+                    end: NaiveDate::from_ymd_opt(2024, 3, 31).unwrap(),
+                },
+                value: dec!(100),
+            }],
+        };
+
+        let items = FinancialLineItemAclMapper::from_xbrl(&filing);
+        assert_eq!(
+            items.iter().map(|item| item.period).collect::<Vec<_>>(),
+            vec![PeriodType::Quarter, PeriodType::YearToDate]
+        );
+        assert!(items.iter().all(|item| !item.consolidated));
+    }
 }
@@ -2,6 +2,10 @@
 //!
 //! 用於隔離外部爬蟲資料結構（Crawler DTO）與應用層/領域層之業務邏輯命令或實體。
 
+pub mod chip;
+pub mod corporate_action;
+pub mod derivatives;
+pub mod disclosure;
 pub mod dividend;
 pub mod financial;
 pub mod index;
@@ -9,11 +13,23 @@ pub mod misc;
 pub mod quote;
 pub mod revenue;
 pub mod stock;
+pub mod subscription;
+pub mod surveillance;
 
+pub use chip::{
+    InstitutionalTradeAclMapper, MarginTradingAclMapper, ShareholdingDistributionAclMapper,
+};
+pub use corporate_action::CorporateActionProposalAclMapper;
+pub use derivatives::DerivativesAclMapper;
+pub use disclosure::MaterialAnnouncementAclMapper;
 pub use dividend::{DividendAclMapper, YahooDividendAclMapper};
-pub use financial::{FinancialStatementAclMapper, NetAssetValueAclMapper};
+pub use financial::{
+    FinancialLineItemAclMapper, FinancialStatementAclMapper, NetAssetValueAclMapper,
+};
 pub use index::IndexAclMapper;
 pub use misc::{QfiiAclMapper, SaveStockWeightCommand, StockWeightAclMapper, UpdateQfiiCommand};
 pub use quote::QuoteAclMapper;
 pub use revenue::{RevenueAclMapper, UpdateRevenueCommand};
 pub use stock::{DelistedCompanyAclMapper, EtfAclMapper, IsinAclMapper, RegisterStockCommand};
+pub use subscription::PublicSubscriptionAclMapper;
+pub use surveillance::TradingSurveillanceAclMapper;
@@ -1,3 +1,6 @@
+use chrono::{Datelike, NaiveDate};
+
+use crate::core::util::datetime::{parse_taiwan_date, parse_taiwan_date_short};
 use crate::infra::cache::SHARE;
 use crate::infra::crawler::share::EtfInfo;
 use crate::infra::crawler::twse::international_securities_identification_number::InternationalSecuritiesIdentificationNumber;
@@ -14,6 +17,8 @@ pub struct RegisterStockCommand {
     pub market_id: i32,
     /// 產業分類 ID
     pub industry_id: i32,
+    /// 掛牌日期；來源格式無法解析時為 `None`
+    pub listing_date: Option<NaiveDate>,
 }
 
 /// 終止上市 (下市) 處理命令。
@@ -21,6 +26,18 @@ pub struct RegisterStockCommand {
 pub struct DelistedCompanyCommand {
     /// 證券代號
     pub symbol: String,
+    /// 下市日期；來源格式無法解析時為 `None`
+    pub delisting_date: Option<NaiveDate>,
+}
+
+/// 解析來源的日期欄位：西元 `YYYY/MM/DD`、民國 `YYY/MM/DD` 或民國 `YYYMMDD`。
+fn parse_source_date(date: &str) -> Option<NaiveDate> {
+    let date = date.trim();
+    NaiveDate::parse_from_str(date, "%Y/%m/%d")
+        .ok()
+        .filter(|parsed| parsed.year() > 1911)
+        .or_else(|| parse_taiwan_date_short(date))
+        .or_else(|| parse_taiwan_date(date))
 }
 
 /// ISIN 爬蟲資料防腐層轉譯器。
@@ -51,6 +68,7 @@ impl IsinAclMapper {
             name: dto.name.clone(),
             market_id,
             industry_id,
+            listing_date: parse_source_date(&dto.listing_date),
         })
     }
 }
@@ -76,6 +94,7 @@ impl DelistedCompanyAclMapper {
 
         Some(DelistedCompanyCommand {
             symbol: dto.stock_symbol.clone(),
+            delisting_date: parse_source_date(&dto.delisting_date),
         })
     }
 }
@@ -95,6 +114,7 @@ impl EtfAclMapper {
             name: dto.name.clone(),
             market_id,
             industry_id: dto.industry_id,
+            listing_date: parse_source_date(&dto.listing_date),
         }
     }
 }
@@ -122,6 +142,7 @@ mod tests {
         assert_eq!(cmd.name, "台積電");
         assert_eq!(cmd.market_id, 2);
         assert_eq!(cmd.industry_id, 24);
+        assert_eq!(cmd.listing_date, NaiveDate::from_ymd_opt(1994, 9, 5));
     }
 
     #[test]
@@ -150,7 +171,23 @@ mod tests {
 
         let cmd = DelistedCompanyAclMapper::from_suspend_listing(&dto);
         assert!(cmd.is_some());
-        assert_eq!(cmd.unwrap().symbol, "9999");
+        let cmd = cmd.unwrap();
+        assert_eq!(cmd.symbol, "9999");
+        assert_eq!(cmd.delisting_date, NaiveDate::from_ymd_opt(2023, 5, 20));
+    }
+
+    /// 西元與民國（含分隔符號）格式的日期都能解析。
+    #[test]
+    fn test_parse_source_date_formats() {
+        assert_eq!(
+            parse_source_date("2024/01/15"),
+            NaiveDate::from_ymd_opt(2024, 1, 15)
+        );
+        assert_eq!(
+            parse_source_date("113/08/14"),
+            NaiveDate::from_ymd_opt(2024, 8, 14)
+        );
+        assert_eq!(parse_source_date("無"), None);
     }
 
     /// 驗證下市日期含多位元組字元時回傳 None 而不是 panic。
@@ -0,0 +1,50 @@
+use crate::{domain::subscription::PublicSubscription, infra::crawler::twse::public::Public};
+
+/// 公開申購資料防腐層轉譯器。
+pub struct PublicSubscriptionAclMapper;
+
+impl PublicSubscriptionAclMapper {
+    /// 將證交所公開申購公告轉譯為領域模型。
+    ///
+    /// 中央登錄公債不是股票申購，申購起訖日未公布時無法追蹤，兩者皆回傳 `None`。
+    pub fn from_public(dto: &Public) -> Option<PublicSubscription> {
+        if dto.market == "中央登錄公債" {
+            return None;
+        }
+
+        Some(PublicSubscription {
+            stock_symbol: dto.stock_symbol.clone(),
+            stock_name: dto.stock_name.clone(),
+            market: dto.market.clone(),
+            offering_start_date: dto.offering_start_date?,
+            offering_end_date: dto.offering_end_date?,
+            drawing_date: dto.drawing_date,
+            offering_price: dto.offering_price,
+            issue_date: dto.issue_date,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn skips_bonds_and_undated_rows() {
+        let mut dto = Public::new("6789".to_string(), "測試".to_string(), "初上市".to_string());
+        assert!(PublicSubscriptionAclMapper::from_public(&dto).is_none());
+
+        dto.offering_start_date = NaiveDate::from_ymd_opt(2026, 7, 14);
+        dto.offering_end_date = NaiveDate::from_ymd_opt(2026, 7, 16);
+        dto.offering_price = Some(dec!(52.5));
+        let subscription = PublicSubscriptionAclMapper::from_public(&dto).unwrap();
+        assert_eq!(subscription.offering_price, Some(dec!(52.5)));
+        assert_eq!(subscription.drawing_date, None);
+
+        dto.market = "中央登錄公債".to_string();
+        assert!(PublicSubscriptionAclMapper::from_public(&dto).is_none());
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{
+    domain::surveillance::{SurveillanceKind, SurveillancePeriod},
+    infra::crawler::share::TradingSurveillanceDto,
+};
+
+/// 注意股與處置股資料防腐層轉譯器。
+pub struct TradingSurveillanceAclMapper;
+
+impl TradingSurveillanceAclMapper {
+    /// 將爬蟲公告轉譯為監視期間。
+    pub fn from_dto(dto: &TradingSurveillanceDto) -> SurveillancePeriod {
+        SurveillancePeriod {
+            stock_symbol: dto.stock_symbol.clone(),
+            name: dto.name.clone(),
+            kind: if dto.is_disposition {
+                SurveillanceKind::Disposition
+            } else {
+                SurveillanceKind::Attention
+            },
+            announced_on: dto.announced_on,
+            start_date: dto.start_date,
+            end_date: dto.end_date,
+            reason: dto.reason.clone(),
+            measure: dto.measure.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    #[test]
+    fn maps_disposition_flag_to_kind() {
+        let on = NaiveDate::from_ymd_opt(2026, 4, 21).unwrap();
+        let mut dto = TradingSurveillanceDto {
+            stock_symbol: "3008".to_string(),
+            name: "大立光".to_string(),
+            is_disposition: true,
+            announced_on: on,
+            start_date: on.succ_opt().unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2026, 5, 5).unwrap(),
+            reason: "連續三個營業日達注意交易資訊標準".to_string(),
+            measure: "第一次處置".to_string(),
+        };
+
+        let period = TradingSurveillanceAclMapper::from_dto(&dto);
+        assert_eq!(period.kind, SurveillanceKind::Disposition);
+        assert_eq!(period.start_date, on.succ_opt().unwrap());
+        assert_eq!(period.measure, "第一次處置");
+
+        dto.is_disposition = false;
+        assert_eq!(
+            TradingSurveillanceAclMapper::from_dto(&dto).kind,
+            SurveillanceKind::Attention
+        );
+    }
+}
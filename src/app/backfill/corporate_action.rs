@@ -0,0 +1,125 @@
+use anyhow::Result;
+use chrono::{Duration, Local, NaiveDate};
+use scopeguard::defer;
+
+use crate::{
+    app::backfill::acl::CorporateActionProposalAclMapper,
+    core::{alert, util::datetime::Weekend, util::text},
+    domain::performance::{
+        CorporateActionProposal, CorporateActionProposalRepository, PriceGap, price_limit_on,
+    },
+    infra::{
+        crawler::{
+            share::{CapitalChangeDto, CapitalChangeKind},
+            tpex, twse,
+        },
+        database::repository::corporate_action::PgCorporateActionProposalRepository,
+    },
+};
+
+/// 恢復買賣公告往前涵蓋的天數，容許排程漏跑幾天仍能補上。
+const ANNOUNCEMENT_LOOKBACK_DAYS: i64 = 7;
+/// 恢復買賣公告往後涵蓋的天數；公告通常在恢復買賣前一到兩週發布。
+const ANNOUNCEMENT_LOOKAHEAD_DAYS: i64 = 30;
+/// 報價跳空往前掃描的天數。
+const PRICE_GAP_LOOKBACK_DAYS: i64 = 7;
+
+/// 偵測減資、變更面額等公司行動，產生待人工確認的建議。
+///
+/// 兩個來源依序執行：
+/// 1. 證交所與櫃買中心的減資／變更面額恢復買賣參考價，比例由停牌前收盤價與
+///    恢復買賣參考價推估。
+/// 2. `DailyQuotes` 中超出漲跌幅、且無除權息可解釋的跳空 —— 涵蓋公告來源
+///    沒有的事件（例如 ETF 受益權單位分割）。
+///
+/// 公告先寫入，報價跳空查詢會排除同代號同生效日已有建議者，同一事件不會
+/// 因兩個來源而重複建議。建議不會直接寫入公司行動，需在 backfill admin 頁面
+/// 確認；有新建議時發送通知提醒審核。
+pub async fn execute() -> Result<()> {
+    let today = Local::now().date_naive();
+
+    if Local::now().is_weekend() {
+        return Ok(());
+    }
+    tracing::info!("偵測公司行動開始");
+    defer! {
+       tracing::info!("偵測公司行動結束");
+    }
+
+    let from = today - Duration::days(ANNOUNCEMENT_LOOKBACK_DAYS);
+    let to = today + Duration::days(ANNOUNCEMENT_LOOKAHEAD_DAYS);
+    let (twse_reduction, twse_par_value, tpex_reduction, tpex_par_value) = tokio::join!(
+        twse::capital_change::visit(CapitalChangeKind::CapitalReduction, from, to),
+        twse::capital_change::visit(CapitalChangeKind::ParValueChange, from, to),
+        tpex::capital_change::visit(CapitalChangeKind::CapitalReduction, from, to),
+        tpex::capital_change::visit(CapitalChangeKind::ParValueChange, from, to),
+    );
+
+    let mut announced = Vec::new();
+    for (source, kind, result) in [
+        ("twse", CapitalChangeKind::CapitalReduction, twse_reduction),
+        ("twse", CapitalChangeKind::ParValueChange, twse_par_value),
+        ("tpex", CapitalChangeKind::CapitalReduction, tpex_reduction),
+        ("tpex", CapitalChangeKind::ParValueChange, tpex_par_value),
+    ] {
+        // 單一來源失敗不影響其他來源與報價跳空的偵測。
+        match result {
+            Ok(dtos) => announced.extend(announcement_proposals(source, &dtos)),
+            Err(why) => tracing::warn!("取得 {source} {}恢復買賣資料失敗: {why:#}", kind.label()),
+        }
+    }
+    let mut created = save(&announced).await?;
+
+    let gap_from = today - Duration::days(PRICE_GAP_LOOKBACK_DAYS);
+    created += save(&price_gap_proposals(gap_from, today).await?).await?;
+
+    tracing::info!("偵測公司行動：新增 {created} 筆待確認建議");
+    if created > 0 {
+        let msg = format!("有 {created} 筆新的公司行動建議待確認，請至 backfill admin 頁面審核");
+        alert::send_message(&text::escape_markdown_v2(msg)).await;
+    }
+
+    Ok(())
+}
+
+/// 將恢復買賣資料轉為建議；參考價尚未公布的資料略過。
+fn announcement_proposals(source: &str, dtos: &[CapitalChangeDto]) -> Vec<CorporateActionProposal> {
+    dtos.iter()
+        .filter_map(|dto| CorporateActionProposalAclMapper::from_capital_change(source, dto))
+        .collect()
+}
+
+/// 掃描區間內無法以除權息解釋、且超出漲跌幅的報價跳空並轉為建議。
+async fn price_gap_proposals(
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<CorporateActionProposal>> {
+    // 資料庫端以區間內較寬鬆的漲跌幅粗篩，逐日的精確判斷交給領域模型。
+    let min_change = price_limit_on(from).min(price_limit_on(to));
+    let gaps = PgCorporateActionProposalRepository::new()
+        .fetch_unexplained_price_gaps(from, to, min_change)
+        .await?;
+
+    Ok(gaps.iter().filter_map(PriceGap::to_proposal).collect())
+}
+
+/// 逐筆寫入建議，回傳新建立的筆數；已存在（含已確認或駁回）的建議不重複建立。
+pub(crate) async fn save(proposals: &[CorporateActionProposal]) -> Result<usize> {
+    let repository = PgCorporateActionProposalRepository::new();
+    let mut created = 0;
+
+    for proposal in proposals {
+        if repository.propose(proposal).await? {
+            tracing::info!(
+                "新增公司行動建議 {} {} {:?} 比例 {:?}",
+                proposal.stock_symbol,
+                proposal.action_type,
+                proposal.effective_date,
+                proposal.share_ratio
+            );
+            created += 1;
+        }
+    }
+
+    Ok(created)
+}
@@ -1,12 +1,22 @@
 use std::collections::HashSet;
 
 use crate::{
-    app::backfill::acl::DelistedCompanyAclMapper, core::declare::StockExchangeMarket,
-    core::util::datetime::Weekend, domain::registry::repository::StockRepository,
-    infra::crawler::twse, infra::database::repository::stock::PgStockRepository,
+    app::backfill::acl::DelistedCompanyAclMapper,
+    app::event::stock_sync,
+    core::declare::StockExchangeMarket,
+    core::util::datetime::Weekend,
+    domain::registry::{
+        history::SecurityMasterEvent,
+        repository::{SecurityMasterRepository, StockRepository},
+        sync::SyncReason,
+    },
+    infra::crawler::twse,
+    infra::database::repository::{
+        security_master::PgSecurityMasterRepository, stock::PgStockRepository,
+    },
 };
 use anyhow::{Context, Result};
-use chrono::Local;
+use chrono::{Days, Local};
 use scopeguard::defer;
 
 /// 單一市場 ISIN 名冊的合理最小筆數（安全閥）。
@@ -39,6 +49,8 @@ pub async fn execute() -> Result<()> {
     }
     let delisted = twse::suspend_listing::visit().await?;
     let repo = PgStockRepository::new();
+    let security_master = PgSecurityMasterRepository::new();
+    let mut marked = Vec::new();
 
     for company in delisted {
         // 透過防腐層轉譯為內部命令，內含格式與民國年分過濾邏輯
@@ -54,9 +66,25 @@ pub async fn execute() -> Result<()> {
 
             if let Err(why) = repo.save(&another).await {
                 tracing::error!("Failed to update_suspend_listing because {:?}", why);
+                continue;
+            }
+            marked.push(stock.symbol().0.clone());
+
+            // 官方名單附有下市日期，解析失敗時才以今天記錄。
+            let delisting_date = cmd
+                .delisting_date
+                .unwrap_or_else(|| Local::now().date_naive());
+            let change = SecurityMasterEvent::delisted(&stock, delisting_date);
+            if let Err(why) = security_master.record(&[change]).await {
+                tracing::error!(
+                    "Failed to record delisting of {} because {:?}",
+                    cmd.symbol,
+                    why
+                );
             }
         }
     }
+    stock_sync::enqueue(marked, SyncReason::Delisted).await;
 
     // 第二階段獨立執行、失敗不影響第一階段的成果——
     // TWSE 名單已寫入的下市標記不因 ISIN 來源異常而回滾。
@@ -151,14 +179,25 @@ pub async fn mark_stocks_missing_from_isin_roster(max_delist: usize) -> Result<u
         );
     }
 
-    let mut marked = 0usize;
+    // 名冊沒有下市日期，以最後一個有成交交易日的隔天推估。
+    let security_master = PgSecurityMasterRepository::new();
+    let last_trading_dates = security_master
+        .fetch_last_trading_dates()
+        .await
+        .unwrap_or_else(|why| {
+            tracing::error!("Failed to fetch last trading dates because {:?}", why);
+            Default::default()
+        });
+    let today = Local::now().date_naive();
+
+    let mut marked = Vec::new();
     for stock in missing {
         let mut another = stock.clone();
         another.update_suspension(true);
 
         match repo.save(&another).await {
             Ok(_) => {
-                marked += 1;
+                marked.push(stock.symbol().0.clone());
                 // 標記下市屬於低頻但重要的狀態變更，用 info 留下可追查的紀錄。
                 tracing::info!(
                     "已標記下市（ISIN 名冊差集）: {} {}（market_id={}）",
@@ -166,6 +205,19 @@ pub async fn mark_stocks_missing_from_isin_roster(max_delist: usize) -> Result<u
                     stock.name(),
                     stock.market_id()
                 );
+
+                let delisting_date = last_trading_dates
+                    .get(&stock.symbol().0)
+                    .and_then(|date| date.checked_add_days(Days::new(1)))
+                    .map_or(today, |date| date.min(today));
+                let change = SecurityMasterEvent::delisted(&stock, delisting_date);
+                if let Err(why) = security_master.record(&[change]).await {
+                    tracing::error!(
+                        "Failed to record delisting of {} because {:?}",
+                        stock.symbol().0,
+                        why
+                    );
+                }
             }
             Err(why) => {
                 tracing::error!(
@@ -177,7 +229,10 @@ pub async fn mark_stocks_missing_from_isin_roster(max_delist: usize) -> Result<u
         }
     }
 
-    Ok(marked)
+    let count = marked.len();
+    stock_sync::enqueue(marked, SyncReason::Delisted).await;
+
+    Ok(count)
 }
 
 #[cfg(test)]
@@ -0,0 +1,118 @@
+use crate::{
+    app::backfill::acl::DerivativesAclMapper,
+    app::event::handlers::get_global_dispatcher,
+    core::util::datetime::Weekend,
+    domain::derivatives::{CONTRACT_TX, DerivativesRepository, TraderType},
+    domain::events::DomainEvent,
+    infra::crawler::{
+        share::{FuturesDailyQuoteDto, FuturesInstitutionalPositionDto, PutCallRatioDto},
+        taifex,
+    },
+    infra::database::repository::derivatives::PgDerivativesRepository,
+};
+use anyhow::Result;
+use chrono::{Local, NaiveDate};
+use scopeguard::defer;
+
+/// 回補當日臺指期貨行情、三大法人期貨部位與臺指選擇權賣權買權比。
+///
+/// 期貨一般交易時段 13:45 收盤，期交所約於 15:00 前後公布三份資料，
+/// 排程應晚於此時間執行。寫入成功後派發 [`DomainEvent::DerivativesMarketUpdated`]，
+/// 讓收盤摘要帶上期貨選擇權資訊。
+pub async fn execute() -> Result<()> {
+    let now = Local::now();
+
+    if now.is_weekend() {
+        return Ok(());
+    }
+    tracing::info!("更新期貨選擇權市場資料開始");
+    defer! {
+       tracing::info!("更新期貨選擇權市場資料結束");
+    }
+
+    let date = now.date_naive();
+    let (futures, positions, put_call_ratio) = tokio::try_join!(
+        taifex::futures_daily::visit(date),
+        taifex::institutional_open_interest::visit(date),
+        taifex::put_call_ratio::visit(date)
+    )?;
+    let written = save(date, futures, positions, put_call_ratio, true).await?;
+    tracing::info!("更新期貨選擇權市場資料 {date}: {written} 筆");
+
+    Ok(())
+}
+
+/// 以整日替換寫入期貨選擇權市場資料，回傳寫入筆數。
+///
+/// 期貨行情為空代表休市或來源尚未公布，不寫入以免清掉既有資料。
+/// `notify` 為 `true` 時於寫入後派發收盤摘要事件；重跑封存資料時不應重複通知。
+pub(crate) async fn save(
+    date: NaiveDate,
+    futures: Vec<FuturesDailyQuoteDto>,
+    positions: Vec<FuturesInstitutionalPositionDto>,
+    put_call_ratio: Option<PutCallRatioDto>,
+    notify: bool,
+) -> Result<usize> {
+    if futures.is_empty() {
+        tracing::warn!("期貨每日行情 {date} 無資料，略過寫入");
+        return Ok(0);
+    }
+    if positions.is_empty() || put_call_ratio.is_none() {
+        tracing::warn!(
+            "期貨選擇權 {date} 資料不完整（法人部位 {} 筆、賣權買權比{}），仍寫入已取得部分",
+            positions.len(),
+            if put_call_ratio.is_some() {
+                "已取得"
+            } else {
+                "缺漏"
+            }
+        );
+    }
+
+    let snapshot =
+        DerivativesAclMapper::from_dtos(date, &futures, &positions, put_call_ratio.as_ref());
+    let written = PgDerivativesRepository::new()
+        .replace_by_date(&snapshot)
+        .await?;
+
+    if notify {
+        let front = snapshot.front_month(CONTRACT_TX);
+        let event = DomainEvent::DerivativesMarketUpdated {
+            date,
+            futures_close: front.and_then(|quote| quote.close),
+            futures_change: front.and_then(|quote| quote.change),
+            foreign_net_open_interest: snapshot
+                .net_open_interest(CONTRACT_TX, TraderType::ForeignInvestor),
+            put_call_ratio_percent: snapshot
+                .put_call_ratio
+                .as_ref()
+                .map(|ratio| ratio.open_interest_ratio_percent),
+            occurred_at: Local::now(),
+        };
+        get_global_dispatcher().dispatch_async(vec![event]).await;
+    }
+
+    Ok(written as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 驗證期貨選擇權市場資料回補流程。
+    #[tokio::test]
+    #[ignore]
+    async fn test_execute() {
+        dotenvy::dotenv().ok();
+        tracing::debug!("開始 execute");
+
+        match execute().await {
+            Ok(_) => {}
+            Err(why) => {
+                tracing::debug!("Failed to execute because {:?}", why);
+            }
+        }
+
+        tracing::debug!("結束 execute");
+    }
+}
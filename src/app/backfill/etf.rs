@@ -90,8 +90,9 @@ async fn update_stock_info(cmd: &backfill::acl::RegisterStockCommand) -> Result<
         .await
         .map_err(|why| anyhow::anyhow!("資料庫 upsert 失敗: {:?}", why))?;
 
-    // 3. 提取領域事件並非同步派發
+    // 3. 提取領域事件，先寫入時點證券主檔再非同步派發
     let events = stock.pull_events();
+    backfill::record_security_master(&events, cmd.listing_date).await;
     get_global_dispatcher().dispatch_async(events).await;
 
     Ok(())
@@ -0,0 +1,181 @@
+//! 台股季報三大報表科目（MOPS XBRL）更新流程。
+//!
+//! 公開資訊觀測站每季提供全體上市櫃公司的 XBRL 壓縮檔，公司陸續申報期間
+//! 壓縮檔內容會持續增加；因此排程每週重抓一次目前目標季度，已寫入的科目
+//! 以 upsert 覆寫。歷史季度由手動回補入口一次補齊。
+//!
+//! 目標季度沿用 [`crate::core::util::datetime::backfill_report_quarter_targets_for_listed_and_otc`]，
+//! 與季報 ROE/ROA 補欄位流程一致。科目更新後接著重算該季的財務體質分數。
+
+use anyhow::{Context, Result};
+use chrono::{Datelike, Local};
+
+use crate::{
+    app::{backfill::acl::FinancialLineItemAclMapper, calculation::financial_health},
+    core::{declare::Quarter, util::datetime::ReportQuarter},
+    domain::financial::{line_item::FinancialLineItem, repository::FinancialLineItemRepository},
+    infra::{
+        crawler::mops::xbrl::{self, XbrlArchive},
+        database::repository::financial_line_item::PgFinancialLineItemRepository,
+    },
+};
+
+/// 每批寫入的科目筆數，避免單一 UNNEST 陣列過大。
+const UPSERT_CHUNK_SIZE: usize = 5000;
+
+/// MOPS 提供 IFRS XBRL 的第一個年度。
+pub const FIRST_XBRL_YEAR: i32 = 2013;
+
+/// 重抓目前目標季度的 XBRL 並更新三大報表科目。
+pub async fn execute() -> Result<()> {
+    for target in crate::core::util::datetime::backfill_report_quarter_targets_for_listed_and_otc(
+        Local::now(),
+    ) {
+        match update(target).await {
+            Ok(written) => tracing::info!(
+                "financial line items {}{} updated: {}",
+                target.year,
+                target.quarter,
+                written
+            ),
+            Err(why) => {
+                tracing::error!(
+                    "Failed to update financial line items {}{} because {:?}",
+                    target.year,
+                    target.quarter,
+                    why
+                );
+                continue;
+            }
+        }
+
+        if let Err(why) = financial_health::calculate(target).await {
+            tracing::error!(
+                "Failed to calculate financial health {}{} because {:?}",
+                target.year,
+                target.quarter,
+                why
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// 下載並寫入單一季度全體公司的三大報表科目，回傳寫入筆數。
+pub async fn update(target: ReportQuarter) -> Result<u64> {
+    let archive = xbrl::visit(target.year, target.quarter).await?;
+    // 解壓與正規表示式解析屬於 CPU 密集工作，移出 async runtime。
+    let items = tokio::task::spawn_blocking(move || to_line_items(archive))
+        .await
+        .context("Failed to join XBRL parsing task")?;
+
+    let repo = PgFinancialLineItemRepository::new();
+    let mut written = 0;
+    for chunk in items.chunks(UPSERT_CHUNK_SIZE) {
+        written += repo.upsert(chunk).await?;
+    }
+
+    Ok(written)
+}
+
+/// 依序回補 `from_year` 第一季起到目前目標季度的科目，回傳寫入筆數。
+///
+/// `skip_loaded` 為真時略過資料庫已有科目的季度，中斷後可直接重跑續補。
+/// 單季失敗（例如來源暫時無法下載）只記錄錯誤，不中止其他季度。
+pub async fn backfill(from_year: i32, skip_loaded: bool) -> Result<u64> {
+    let repo = PgFinancialLineItemRepository::new();
+    let mut written = 0;
+    for target in quarters_until(from_year, latest_target()) {
+        if skip_loaded && repo.count_companies(target.year, target.quarter).await? > 0 {
+            continue;
+        }
+
+        match update(target).await {
+            Ok(count) => {
+                tracing::info!(
+                    "financial line items {}{} backfilled: {}",
+                    target.year,
+                    target.quarter,
+                    count
+                );
+                written += count;
+            }
+            Err(why) => tracing::error!(
+                "Failed to backfill financial line items {}{} because {:?}",
+                target.year,
+                target.quarter,
+                why
+            ),
+        }
+    }
+
+    Ok(written)
+}
+
+/// 目前應已可取得的最新季度。
+pub fn latest_target() -> ReportQuarter {
+    let now = Local::now();
+    crate::core::util::datetime::backfill_report_quarter_targets_for_listed_and_otc(now)
+        .into_iter()
+        .max_by_key(|target| (target.year, target.quarter))
+        .unwrap_or_else(|| ReportQuarter::new(now.year() - 1, Quarter::Q4))
+}
+
+/// `from_year` 第一季到 `to`（含）的所有季度。
+fn quarters_until(from_year: i32, to: ReportQuarter) -> Vec<ReportQuarter> {
+    let quarters = [Quarter::Q1, Quarter::Q2, Quarter::Q3, Quarter::Q4];
+    (from_year.max(FIRST_XBRL_YEAR)..=to.year)
+        .flat_map(|year| {
+            quarters
+                .into_iter()
+                .map(move |quarter| ReportQuarter::new(year, quarter))
+        })
+        .filter(|target| (target.year, target.quarter) <= (to.year, to.quarter))
+        .collect()
+}
+
+/// 解析壓縮檔內全部公司並轉譯為科目；單一公司解析失敗只記錄警告。
+fn to_line_items(mut archive: XbrlArchive) -> Vec<FinancialLineItem> {
+    let mut items = Vec::new();
+    for filing in archive.filings() {
+        match filing {
+            Ok(filing) => items.extend(FinancialLineItemAclMapper::from_xbrl(&filing)),
+            Err(why) => tracing::warn!("Skip XBRL filing because {:?}", why),
+        }
+    }
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::infra::cache::SHARE;
+
+    use super::*;
+
+    /// 回補範圍從 IFRS 第一年起算，並停在目標季度。
+    #[test]
+    fn quarters_until_stops_at_target() {
+        let quarters = quarters_until(2010, ReportQuarter::new(2014, Quarter::Q2));
+        assert_eq!(quarters.len(), 6);
+        assert_eq!(quarters[0], ReportQuarter::new(2013, Quarter::Q1));
+        assert_eq!(quarters[5], ReportQuarter::new(2014, Quarter::Q2));
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_execute() {
+        dotenvy::dotenv().ok();
+        SHARE.load().await;
+        tracing::debug!("開始 execute");
+
+        match execute().await {
+            Ok(_) => {}
+            Err(why) => {
+                tracing::debug!("Failed to execute because {:?}", why);
+            }
+        }
+
+        tracing::debug!("結束 execute");
+    }
+}
@@ -18,6 +18,8 @@ use crate::{
 
 /// 更新台股年度財報
 pub mod annual;
+/// 下載 MOPS XBRL 季報並更新三大報表科目
+pub mod line_item;
 /// 更新台股季度財報
 pub mod quarter;
 
@@ -0,0 +1,87 @@
+use crate::{
+    app::backfill::acl::InstitutionalTradeAclMapper,
+    core::util::datetime::Weekend,
+    domain::chip::{InstitutionalTrade, InstitutionalTradeRepository},
+    infra::crawler::{share::InstitutionalTradeDto, tpex, twse},
+    infra::database::repository::chip::PgInstitutionalTradeRepository,
+};
+use anyhow::Result;
+use chrono::{Local, NaiveDate};
+use scopeguard::defer;
+
+/// 回補當日上市與上櫃三大法人買賣超。
+///
+/// 交易所約於 16:00 後公布，排程應晚於此時間執行。
+pub async fn execute() -> Result<()> {
+    let now = Local::now();
+
+    if now.is_weekend() {
+        return Ok(());
+    }
+    tracing::info!("更新三大法人買賣超開始");
+    defer! {
+       tracing::info!("更新三大法人買賣超結束");
+    }
+
+    let date = now.date_naive();
+    let (listed, otc) = tokio::try_join!(
+        twse::institutional_investor::visit(date),
+        tpex::institutional_investor::visit(date)
+    )?;
+    let written = save(date, listed, otc).await?;
+    tracing::info!("更新三大法人買賣超 {date}: {written} 筆");
+
+    Ok(())
+}
+
+/// 以整日替換寫入上市與上櫃的三大法人買賣超，回傳寫入筆數。
+///
+/// 任一市場沒有資料（休市或尚未公布）時不寫入：整日替換只收到一個市場會把
+/// 另一個市場既有的資料刪掉，寧可等下次排程或手動重跑補齊。
+pub(crate) async fn save(
+    date: NaiveDate,
+    listed: Vec<InstitutionalTradeDto>,
+    otc: Vec<InstitutionalTradeDto>,
+) -> Result<usize> {
+    if listed.is_empty() || otc.is_empty() {
+        tracing::warn!(
+            "三大法人買賣超 {date} 資料不完整（上市 {} 筆、上櫃 {} 筆），略過寫入",
+            listed.len(),
+            otc.len()
+        );
+        return Ok(0);
+    }
+
+    let trades: Vec<InstitutionalTrade> = listed
+        .iter()
+        .chain(otc.iter())
+        .map(InstitutionalTradeAclMapper::from_dto)
+        .collect();
+    let written = PgInstitutionalTradeRepository::new()
+        .replace_by_date(date, &trades)
+        .await?;
+
+    Ok(written as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 驗證三大法人買賣超回補流程。
+    #[tokio::test]
+    #[ignore]
+    async fn test_execute() {
+        dotenvy::dotenv().ok();
+        tracing::debug!("開始 execute");
+
+        match execute().await {
+            Ok(_) => {}
+            Err(why) => {
+                tracing::debug!("Failed to execute because {:?}", why);
+            }
+        }
+
+        tracing::debug!("結束 execute");
+    }
+}
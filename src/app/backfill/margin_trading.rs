@@ -0,0 +1,86 @@
+use crate::{
+    app::backfill::acl::MarginTradingAclMapper,
+    core::util::datetime::Weekend,
+    domain::chip::{MarginTrading, MarginTradingRepository},
+    infra::crawler::{share::MarginTradingDto, tpex, twse},
+    infra::database::repository::chip::PgMarginTradingRepository,
+};
+use anyhow::Result;
+use chrono::{Local, NaiveDate};
+use scopeguard::defer;
+
+/// 回補當日上市與上櫃融資融券餘額。
+///
+/// 交易所約於 21:00 公布信用交易資料，排程應晚於此時間執行。
+pub async fn execute() -> Result<()> {
+    let now = Local::now();
+
+    if now.is_weekend() {
+        return Ok(());
+    }
+    tracing::info!("更新融資融券餘額開始");
+    defer! {
+       tracing::info!("更新融資融券餘額結束");
+    }
+
+    let date = now.date_naive();
+    let (listed, otc) = tokio::try_join!(
+        twse::margin_trading::visit(date),
+        tpex::margin_trading::visit(date)
+    )?;
+    let written = save(date, listed, otc).await?;
+    tracing::info!("更新融資融券餘額 {date}: {written} 筆");
+
+    Ok(())
+}
+
+/// 以整日替換寫入上市與上櫃的融資融券餘額，回傳寫入筆數。
+///
+/// 任一市場沒有資料時不寫入，理由同三大法人買賣超。
+pub(crate) async fn save(
+    date: NaiveDate,
+    listed: Vec<MarginTradingDto>,
+    otc: Vec<MarginTradingDto>,
+) -> Result<usize> {
+    if listed.is_empty() || otc.is_empty() {
+        tracing::warn!(
+            "融資融券餘額 {date} 資料不完整（上市 {} 筆、上櫃 {} 筆），略過寫入",
+            listed.len(),
+            otc.len()
+        );
+        return Ok(0);
+    }
+
+    let tradings: Vec<MarginTrading> = listed
+        .iter()
+        .chain(otc.iter())
+        .map(MarginTradingAclMapper::from_dto)
+        .collect();
+    let written = PgMarginTradingRepository::new()
+        .replace_by_date(date, &tradings)
+        .await?;
+
+    Ok(written as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 驗證融資融券餘額回補流程。
+    #[tokio::test]
+    #[ignore]
+    async fn test_execute() {
+        dotenvy::dotenv().ok();
+        tracing::debug!("開始 execute");
+
+        match execute().await {
+            Ok(_) => {}
+            Err(why) => {
+                tracing::debug!("Failed to execute because {:?}", why);
+            }
+        }
+
+        tracing::debug!("結束 execute");
+    }
+}
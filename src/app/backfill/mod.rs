@@ -1,15 +1,23 @@
 /// 防腐層 (Anti-Corruption Layer)
 pub mod acl;
+/// 調用 twse、tpex API 及報價跳空偵測公司行動（減資／變更面額／分割）並建立待確認建議
+pub mod corporate_action;
 /// 調用 twse API 更新終止上市公司
 pub mod delisted_company;
+/// 調用 taifex API 取得並更新臺指期貨、法人期貨部位與賣權買權比
+pub mod derivatives;
 /// 更新股利發送數據
 pub mod dividend;
 /// 調用 twse API 取得 ETF 並更新股票相關欄位
 pub mod etf;
 /// 回補財報
 pub mod financial_statement;
+/// 調用 twse、tpex API 取得並更新三大法人買賣超
+pub mod institutional_investor;
 /// 調用 twse API 取得數據後更新股票相關欄位
 pub mod isin;
+/// 調用 twse、tpex API 取得並更新融資融券餘額
+pub mod margin_trading;
 /// 回補每股淨值為零的股票更新其數據
 pub mod net_asset_value_per_share;
 /// 回補流程對外部資料來源的需求介面
@@ -20,14 +28,28 @@ pub mod qualified_foreign_institutional_investor;
 pub mod quote;
 /// 以「代號 × 月份區間」回補歷史日報價（補既有缺口）
 pub mod quote_history;
+/// 以本機封存的原始回應重跑防腐層映射
+pub mod reprocess;
 /// 調用 twse API 取得並更新每月營收
 pub mod revenue;
+/// 調用 tdcc API 取得並更新集保戶股權分散表
+pub mod shareholding_distribution;
 /// 查詢 taifex 提供個股權值比重
 pub mod stock_weight;
+/// 寫入人工確認的證券代號變更
+pub mod symbol_change;
 /// 調用 twse API 取得並更新台股加權指數
 pub mod taiwan_stock_index;
 
-use crate::infra::cache::SHARE;
+use chrono::NaiveDate;
+
+use crate::domain::{
+    events::DomainEvent,
+    registry::{history::SecurityMasterEvent, repository::SecurityMasterRepository},
+};
+use crate::infra::{
+    cache::SHARE, database::repository::security_master::PgSecurityMasterRepository,
+};
 
 /// 判斷股票主檔是否為新資料，或關鍵欄位是否有變動。
 pub(crate) async fn is_stock_identity_new_or_changed(
@@ -53,3 +75,19 @@ pub(crate) async fn is_stock_identity_new_or_changed(
         _ => false,
     }
 }
+
+/// 將證券主檔聚合根產生的註冊與身份變更事件寫入時點證券主檔。
+///
+/// 寫入失敗只記錄錯誤，不影響主檔本身的更新與事件派發。
+pub(crate) async fn record_security_master(
+    events: &[DomainEvent],
+    listing_date: Option<NaiveDate>,
+) {
+    let changes: Vec<SecurityMasterEvent> = events
+        .iter()
+        .flat_map(|event| SecurityMasterEvent::from_domain_event(event, listing_date))
+        .collect();
+    if let Err(why) = PgSecurityMasterRepository::new().record(&changes).await {
+        tracing::error!("Failed to record security master events because {:?}", why);
+    }
+}
@@ -0,0 +1,276 @@
+use anyhow::Result;
+use chrono::NaiveDate;
+
+use crate::{
+    app::backfill::{
+        derivatives, institutional_investor, margin_trading, quote, shareholding_distribution,
+    },
+    infra::archive,
+    infra::crawler::{taifex, tdcc, tpex, twse},
+};
+
+/// 可由封存原始回應重跑的資料種類。
+///
+/// 每個種類對應一組爬蟲解析函式與防腐層映射；同一種類的多個來源必須一起
+/// 重跑，因為寫入是「整日原子替換」——只重跑上市會把同日上櫃資料一併刪掉。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReprocessTarget {
+    /// 上市櫃每日收盤報價（`twse::quote` + `tpex::quote` → `QuoteAclMapper`）。
+    DailyQuote,
+    /// 上市櫃三大法人買賣超（`twse::institutional_investor` + `tpex::institutional_investor`
+    /// → `InstitutionalTradeAclMapper`）。
+    InstitutionalTrade,
+    /// 上市櫃融資融券餘額（`twse::margin_trading` + `tpex::margin_trading`
+    /// → `MarginTradingAclMapper`）。
+    MarginTrading,
+    /// 集保戶股權分散表（`tdcc::shareholding_distribution` → `ShareholdingDistributionAclMapper`）。
+    ///
+    /// 來源每週一份，封存只存在於資料日期；區間內其他日期會計入缺少封存。
+    ShareholdingDistribution,
+    /// 期貨選擇權市場資料（`taifex::futures_daily` + `taifex::institutional_open_interest`
+    /// + `taifex::put_call_ratio` → `DerivativesAclMapper`）。
+    ///
+    /// 重跑不會再派發收盤摘要事件。
+    Derivatives,
+}
+
+impl ReprocessTarget {
+    /// 由對外代碼解析種類。
+    ///
+    /// 也接受個別爬蟲的名稱（如 `twse::quote`），但仍會重跑整個種類的所有來源。
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "quote" | "twse::quote" | "tpex::quote" => Some(Self::DailyQuote),
+            "institutional_investor"
+            | "twse::institutional_investor"
+            | "tpex::institutional_investor" => Some(Self::InstitutionalTrade),
+            "margin_trading" | "twse::margin_trading" | "tpex::margin_trading" => {
+                Some(Self::MarginTrading)
+            }
+            "shareholding_distribution" | "tdcc::shareholding_distribution" => {
+                Some(Self::ShareholdingDistribution)
+            }
+            "derivatives"
+            | "taifex::futures_daily"
+            | "taifex::institutional_open_interest"
+            | "taifex::put_call_ratio" => Some(Self::Derivatives),
+            _ => None,
+        }
+    }
+
+    /// 回傳對外代碼。
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::DailyQuote => "quote",
+            Self::InstitutionalTrade => "institutional_investor",
+            Self::MarginTrading => "margin_trading",
+            Self::ShareholdingDistribution => "shareholding_distribution",
+            Self::Derivatives => "derivatives",
+        }
+    }
+}
+
+/// 單次重跑的結果摘要。
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ReprocessSummary {
+    /// 區間內的日曆日數。
+    pub dates_requested: usize,
+    /// 成功重跑並寫入的日期數。
+    pub dates_processed: usize,
+    /// 缺少封存（或封存內容為空）而略過的日期數。
+    pub dates_missing: usize,
+    /// 解析或寫入失敗的日期數。
+    pub dates_failed: usize,
+    /// 寫入的資料筆數。
+    pub rows_written: usize,
+}
+
+/// 以本機封存的原始回應，重跑 `[from, to]` 區間內每一天的防腐層映射並寫入資料庫。
+///
+/// 流程與線上抓取完全相同，只是資料來源換成 `infra::archive`：
+///
+/// 1. 封存解析不經過 TTL 去重。線上爬蟲會以 TTL 略過「24 小時內已處理過」的代號，
+///    重跑若沿用這層去重，剛寫入過的日期只會得到部分資料，接著整日原子替換就會
+///    把其餘資料刪掉；改由 `visit_archived` 直接略過檢查，而不是清空線上排程
+///    與通知節流共用的全域快取。
+/// 2. 逐日讀取封存並解析；任一來源缺少封存就略過該日，不做半個市場的替換。
+/// 3. 單日失敗只記錄並計數，不中斷整個區間，讓一次 job 能盡量補完。
+/// 4. 收盤價快取只接受不早於快取日期的報價，重跑過去日期不會蓋掉最新收盤價。
+pub async fn execute(
+    target: ReprocessTarget,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<ReprocessSummary> {
+    let mut summary = ReprocessSummary::default();
+    for date in from.iter_days().take_while(|date| *date <= to) {
+        summary.dates_requested += 1;
+
+        let result = match target {
+            ReprocessTarget::DailyQuote => reprocess_daily_quotes(date).await,
+            ReprocessTarget::InstitutionalTrade => reprocess_institutional_trades(date).await,
+            ReprocessTarget::MarginTrading => reprocess_margin_trading(date).await,
+            ReprocessTarget::ShareholdingDistribution => {
+                reprocess_shareholding_distribution(date).await
+            }
+            ReprocessTarget::Derivatives => reprocess_derivatives(date).await,
+        };
+        match result {
+            Ok(Some(rows)) => {
+                summary.dates_processed += 1;
+                summary.rows_written += rows;
+            }
+            Ok(None) => summary.dates_missing += 1,
+            Err(why) => {
+                summary.dates_failed += 1;
+                tracing::warn!(
+                    "重跑封存資料失敗 target={} date={date}: {why:#}",
+                    target.code()
+                );
+            }
+        }
+    }
+
+    tracing::info!(
+        "重跑封存資料結束 target={} {from}~{to}: {:?}",
+        target.code(),
+        summary
+    );
+
+    Ok(summary)
+}
+
+/// 重跑單日收盤報價；缺少任一來源的封存或內容為空時回傳 `None`。
+async fn reprocess_daily_quotes(date: NaiveDate) -> Result<Option<usize>> {
+    let (twse, tpex) = tokio::try_join!(
+        twse::quote::visit_archived(date),
+        tpex::quote::visit_archived(date)
+    )?;
+    let (Some(mut quotes), Some(mut tpex)) = (twse, tpex) else {
+        return Ok(None);
+    };
+    quotes.append(&mut tpex);
+    if quotes.is_empty() {
+        return Ok(None);
+    }
+
+    quote::process_quotes(date, quotes).await.map(Some)
+}
+
+/// 重跑單日三大法人買賣超；缺少任一來源的封存或內容為空時回傳 `None`。
+async fn reprocess_institutional_trades(date: NaiveDate) -> Result<Option<usize>> {
+    let (twse, tpex) = tokio::try_join!(
+        twse::institutional_investor::visit_archived(date),
+        tpex::institutional_investor::visit_archived(date)
+    )?;
+    let (Some(listed), Some(otc)) = (twse, tpex) else {
+        return Ok(None);
+    };
+    if listed.is_empty() || otc.is_empty() {
+        return Ok(None);
+    }
+
+    institutional_investor::save(date, listed, otc)
+        .await
+        .map(Some)
+}
+
+/// 重跑單日融資融券餘額；缺少任一來源的封存或內容為空時回傳 `None`。
+async fn reprocess_margin_trading(date: NaiveDate) -> Result<Option<usize>> {
+    let (twse, tpex) = tokio::try_join!(
+        twse::margin_trading::visit_archived(date),
+        tpex::margin_trading::visit_archived(date)
+    )?;
+    let (Some(listed), Some(otc)) = (twse, tpex) else {
+        return Ok(None);
+    };
+    if listed.is_empty() || otc.is_empty() {
+        return Ok(None);
+    }
+
+    margin_trading::save(date, listed, otc).await.map(Some)
+}
+
+/// 重跑單週集保戶股權分散表；缺少封存或內容為空時回傳 `None`。
+async fn reprocess_shareholding_distribution(date: NaiveDate) -> Result<Option<usize>> {
+    let Some(rows) = tdcc::shareholding_distribution::visit_archived(date).await? else {
+        return Ok(None);
+    };
+    if rows.is_empty() {
+        return Ok(None);
+    }
+
+    shareholding_distribution::save(rows).await.map(Some)
+}
+
+/// 重跑單日期貨選擇權市場資料；缺少任一來源的封存或期貨行情為空時回傳 `None`。
+async fn reprocess_derivatives(date: NaiveDate) -> Result<Option<usize>> {
+    let (futures, positions, put_call_ratio) = tokio::try_join!(
+        taifex::futures_daily::visit_archived(date),
+        taifex::institutional_open_interest::visit_archived(date),
+        taifex::put_call_ratio::visit_archived(date)
+    )?;
+    let (Some(futures), Some(positions), Some(put_call_ratio)) =
+        (futures, positions, put_call_ratio)
+    else {
+        return Ok(None);
+    };
+    if futures.is_empty() {
+        return Ok(None);
+    }
+
+    derivatives::save(date, futures, positions, put_call_ratio, false)
+        .await
+        .map(Some)
+}
+
+/// 依保留天數清除過期的原始回應封存。
+///
+/// 由排程每日執行；未啟用封存時不做任何事。
+pub async fn purge_archive() -> Result<()> {
+    let removed = archive::purge_expired().await?;
+    tracing::info!("清除過期原始回應封存完成: {removed} 個檔案");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 對外代碼可雙向轉換，未知代碼在建立 job 前就能擋下。
+    #[test]
+    fn target_code_roundtrip() {
+        assert_eq!(
+            ReprocessTarget::from_code("quote"),
+            Some(ReprocessTarget::DailyQuote)
+        );
+        assert_eq!(ReprocessTarget::DailyQuote.code(), "quote");
+        assert_eq!(
+            ReprocessTarget::from_code("tpex::quote"),
+            Some(ReprocessTarget::DailyQuote)
+        );
+        assert_eq!(
+            ReprocessTarget::from_code("twse::institutional_investor"),
+            Some(ReprocessTarget::InstitutionalTrade)
+        );
+        assert_eq!(
+            ReprocessTarget::InstitutionalTrade.code(),
+            "institutional_investor"
+        );
+        assert_eq!(
+            ReprocessTarget::from_code("tpex::margin_trading"),
+            Some(ReprocessTarget::MarginTrading)
+        );
+        assert_eq!(ReprocessTarget::MarginTrading.code(), "margin_trading");
+        assert_eq!(
+            ReprocessTarget::from_code("tdcc::shareholding_distribution"),
+            Some(ReprocessTarget::ShareholdingDistribution)
+        );
+        assert_eq!(
+            ReprocessTarget::from_code("taifex::put_call_ratio"),
+            Some(ReprocessTarget::Derivatives)
+        );
+        assert_eq!(ReprocessTarget::Derivatives.code(), "derivatives");
+        assert_eq!(ReprocessTarget::from_code("twse::revenue"), None);
+        assert_eq!(ReprocessTarget::from_code(""), None);
+    }
+}
@@ -1,5 +1,8 @@
 use crate::{
-    app::backfill::acl::{RevenueAclMapper, UpdateRevenueCommand},
+    app::{
+        backfill::acl::{RevenueAclMapper, UpdateRevenueCommand},
+        calculation::revenue_momentum,
+    },
     core::util,
     infra::cache::SHARE,
     infra::crawler::twse,
@@ -38,6 +41,7 @@ async fn process_revenues(last_month_timezone: chrono::DateTime<FixedOffset>) ->
 
     let revenues = twse::revenue::visit(last_month_timezone).await?;
     let cmds: Vec<UpdateRevenueCommand> = revenues.iter().map(RevenueAclMapper::from_dto).collect();
+    let revenue_count = cmds.len();
 
     stream::iter(cmds)
         .for_each_concurrent(util::concurrent_limit_16(), |cmd| async move {
@@ -49,6 +53,27 @@ async fn process_revenues(last_month_timezone: chrono::DateTime<FixedOffset>) ->
 
     financial_repo.rebuild_revenue_last_date().await?;
 
+    // 公布期間每天都會重抓，訂閱端以筆數變化判斷是否有新公布的公司。
+    if let Some(first_day) = NaiveDate::from_ymd_opt(year, month, 1) {
+        crate::app::ports::publish_event(crate::app::ports::OutboundEvent::DataReady {
+            dataset: crate::app::ports::ReadyDataset::MonthlyRevenue,
+            date: first_day,
+            records: revenue_count as u64,
+            occurred_at: Local::now(),
+        });
+    }
+
+    // 公司在 1～10 日陸續公布，每天重算該月動能，讓月中摘要讀到最新結果。
+    let revenue_month = i64::from(year) * 100 + i64::from(month);
+    match revenue_momentum::calculate(revenue_month).await {
+        Ok(count) => tracing::info!("revenue momentum {revenue_month} updated: {count}"),
+        Err(why) => tracing::error!(
+            "Failed to calculate revenue momentum {} because {:?}",
+            revenue_month,
+            why
+        ),
+    }
+
     Ok(())
 }
 
@@ -0,0 +1,67 @@
+use crate::{
+    app::backfill::acl::ShareholdingDistributionAclMapper,
+    domain::chip::ShareholdingDistributionRepository,
+    infra::crawler::{share::ShareholdingTierDto, tdcc},
+    infra::database::repository::chip::PgShareholdingDistributionRepository,
+};
+use anyhow::Result;
+use scopeguard::defer;
+
+/// 取得最新一週的集保戶股權分散表並寫入資料庫。
+///
+/// 排程於週五晚間與週六早上各執行一次；以資料日期整週替換，重複執行
+/// 同一週只會覆寫成相同內容。
+pub async fn execute() -> Result<()> {
+    tracing::info!("更新集保戶股權分散表開始");
+    defer! {
+       tracing::info!("更新集保戶股權分散表結束");
+    }
+
+    let rows = tdcc::shareholding_distribution::visit().await?;
+    let written = save(rows).await?;
+    tracing::info!("更新集保戶股權分散表: {written} 筆");
+
+    Ok(())
+}
+
+/// 以資料日期整週替換寫入股權分散表，回傳寫入的分級列數。
+///
+/// 來源一次只提供單一週；若混入多個資料日期，只寫入最新的一週並記錄 warning。
+pub(crate) async fn save(rows: Vec<ShareholdingTierDto>) -> Result<usize> {
+    let Some(date) = rows.iter().map(|row| row.date).max() else {
+        tracing::warn!("集保戶股權分散表無資料，略過寫入");
+        return Ok(0);
+    };
+    if rows.iter().any(|row| row.date != date) {
+        tracing::warn!("集保戶股權分散表含多個資料日期，只寫入 {date}");
+    }
+
+    let distributions = ShareholdingDistributionAclMapper::from_dtos(&rows);
+    let written = PgShareholdingDistributionRepository::new()
+        .replace_by_date(date, &distributions)
+        .await?;
+
+    Ok(written as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 驗證集保戶股權分散表回補流程。
+    #[tokio::test]
+    #[ignore]
+    async fn test_execute() {
+        dotenvy::dotenv().ok();
+        tracing::debug!("開始 execute");
+
+        match execute().await {
+            Ok(_) => {}
+            Err(why) => {
+                tracing::debug!("Failed to execute because {:?}", why);
+            }
+        }
+
+        tracing::debug!("結束 execute");
+    }
+}
@@ -0,0 +1,35 @@
+use anyhow::{Context, Result, bail};
+use chrono::NaiveDate;
+
+use crate::{
+    domain::registry::{
+        history::SecurityMasterEvent,
+        repository::{SecurityMasterRepository, StockRepository},
+    },
+    infra::database::repository::{
+        security_master::PgSecurityMasterRepository, stock::PgStockRepository,
+    },
+};
+
+/// 將人工確認的證券代號變更寫入證券主檔異動紀錄。
+///
+/// 現有資料來源沒有代號變更公告，因此由維運入口（manual backfill）呼叫。舊代號
+/// 記一筆停止掛牌、新代號記一筆開始掛牌，名稱、市場與產業沿用舊代號目前的主檔，
+/// 兩筆以 `related_symbol` 互相對應。同一組紀錄重複寫入時略過，回傳實際寫入筆數。
+pub async fn record(old_symbol: &str, new_symbol: &str, effective_date: NaiveDate) -> Result<u64> {
+    if old_symbol == new_symbol {
+        bail!("new symbol must differ from the old symbol {old_symbol}");
+    }
+    let stock = PgStockRepository::new()
+        .find_by_symbol(old_symbol)
+        .await?
+        .with_context(|| format!("unknown stock symbol {old_symbol}"))?;
+
+    let changes = SecurityMasterEvent::symbol_changed(&stock, new_symbol, effective_date);
+    let recorded = PgSecurityMasterRepository::new().record(&changes).await?;
+    tracing::info!(
+        "證券代號變更 {old_symbol} → {new_symbol}（{effective_date}）寫入 {recorded} 筆"
+    );
+
+    Ok(recorded)
+}
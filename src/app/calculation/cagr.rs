@@ -639,6 +639,24 @@ mod tests {
                 .collect())
         }
 
+        async fn fetch_last_quote_within(
+            &self,
+            symbols: &[String],
+            from: NaiveDate,
+            to: NaiveDate,
+        ) -> Result<Vec<(String, NaiveDate, Decimal)>> {
+            self.record("fetch_last_quote_within");
+            Ok(symbols
+                .iter()
+                .filter_map(|symbol| {
+                    self.prices
+                        .get(symbol)
+                        .and_then(|series| series.range(from..=to).next_back())
+                        .map(|(quote_date, price)| (symbol.clone(), *quote_date, *price))
+                })
+                .collect())
+        }
+
         async fn fetch_dividend_events_since(
             &self,
             since: NaiveDate,
@@ -0,0 +1,46 @@
+use anyhow::Result;
+use chrono::{NaiveDate, TimeDelta};
+
+use crate::{
+    domain::dividend::repository::DividendGapFillRepository,
+    infra::database::repository::dividend_gap_fill::PgDividendGapFillRepository,
+};
+
+/// 未填息事件持續追蹤的天數；超過兩年仍未填息者保留最後結果，不再每日重算。
+const TRACKING_DAYS: i64 = 730;
+
+/// 更新截至指定交易日的填息結果，回傳本次寫入的筆數。
+///
+/// 第一次執行會計算所有歷史除息事件；之後每日只重算兩年內尚未填息的事件。
+pub async fn calculate(date: NaiveDate) -> Result<usize> {
+    let repo = PgDividendGapFillRepository::new();
+    let mut pending = repo
+        .fetch_pending(date - TimeDelta::days(TRACKING_DAYS), date)
+        .await?;
+
+    for gap_fill in pending.iter_mut() {
+        let closes = repo
+            .fetch_closes(&gap_fill.security_code, gap_fill.ex_dividend_date)
+            .await?;
+        gap_fill.track(&closes);
+    }
+
+    repo.upsert(&pending).await?;
+    Ok(pending.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_calculate() {
+        dotenvy::dotenv().ok();
+        let date = chrono::Local::now().date_naive();
+        match calculate(date).await {
+            Ok(count) => tracing::debug!("updated {count} gap fills"),
+            Err(why) => tracing::debug!("Failed to calculate because {:?}", why),
+        }
+    }
+}
@@ -0,0 +1,384 @@
+use std::collections::{HashMap, hash_map::Entry};
+
+use anyhow::{Context, Result};
+use chrono::{Months, NaiveDate};
+use rust_decimal::Decimal;
+
+use crate::domain::{
+    performance::{
+        CagrSourceRepository, CorporateAction, DividendEvent,
+        entity::PRINCIPAL,
+        simulator::{SimulationInput, simulate},
+    },
+    valuation::{
+        BacktestHorizon, EstimateBacktestRepository, EstimateSignal, EstimateSnapshot,
+        ForwardReturnSample, summarize,
+    },
+};
+
+/// 排程單次最多評估的「估價日 × 持有期間」組數。
+///
+/// 第一次上線時全部歷史估價日都待評估，分批收斂避免單次排程跑太久；
+/// 之後每天只會新增三組（每個持有期間各一天）。
+const MAX_EVALUATIONS_PER_RUN: usize = 300;
+
+/// 估價回測的執行摘要。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EstimateBacktestSummary {
+    /// 本次評估的「估價日 × 持有期間」組數。
+    pub evaluations: usize,
+    /// 尚待下次排程評估的組數。
+    pub remaining: usize,
+    /// 納入統計的報酬樣本數。
+    pub samples: usize,
+    /// 期末交易日沒有報價、改以持有期間內最後一筆收盤價出場的股票數。
+    pub early_exits: usize,
+    /// 寫入的彙總列數。
+    pub rows_written: u64,
+}
+
+/// 排程進入點：評估持有期已走完、但尚未評估過的估價日。
+///
+/// 排在 CAGR 之後：兩者共用除權息與公司行動資料，需等前一晚的股利回補完成。
+pub async fn execute_scheduled() -> Result<()> {
+    let summary = execute(Some(MAX_EVALUATIONS_PER_RUN)).await?;
+    tracing::info!(
+        evaluations = summary.evaluations,
+        remaining = summary.remaining,
+        samples = summary.samples,
+        early_exits = summary.early_exits,
+        rows_written = summary.rows_written,
+        "估價訊號回測完成"
+    );
+    Ok(())
+}
+
+/// 評估待處理的估價日；`limit` 為 `None` 時一次處理全部。
+pub async fn execute(limit: Option<usize>) -> Result<EstimateBacktestSummary> {
+    let source = crate::infra::database::repository::cagr_source::PgCagrSourceRepository::new();
+    let repository =
+        crate::infra::database::repository::estimate_backtest::PgEstimateBacktestRepository::new();
+    evaluate(&source, &repository, limit).await
+}
+
+/// [`execute`] 的可注入版本。
+///
+/// 報價、除權息與公司行動沿用 CAGR 的資料來源：同樣需要「一次撈齊」，
+/// 也同樣需要去重後的股利事件與人工登錄的分割資料，沒有理由再寫一份。
+pub async fn evaluate(
+    source: &dyn CagrSourceRepository,
+    repository: &dyn EstimateBacktestRepository,
+    limit: Option<usize>,
+) -> Result<EstimateBacktestSummary> {
+    let mut summary = EstimateBacktestSummary::default();
+    let Some(latest) = source
+        .fetch_latest_trading_day()
+        .await
+        .context("Failed to fetch latest trading day")?
+    else {
+        return Ok(summary);
+    };
+
+    let mut pending: Vec<(NaiveDate, BacktestHorizon)> = Vec::new();
+    for horizon in BacktestHorizon::ALL {
+        let Some(until) = latest.checked_sub_months(Months::new(horizon.months())) else {
+            continue;
+        };
+        let dates = repository
+            .fetch_pending_dates(horizon, until)
+            .await
+            .with_context(|| format!("Failed to fetch pending dates for {}", horizon.code()))?;
+        pending.extend(dates.into_iter().map(|date| (date, horizon)));
+    }
+    pending.sort();
+    let total = pending.len();
+    if let Some(limit) = limit {
+        pending.truncate(limit);
+    }
+    summary.remaining = total - pending.len();
+
+    let Some(earliest) = pending.first().map(|(date, _)| *date) else {
+        return Ok(summary);
+    };
+
+    let events = group_by_symbol(
+        source
+            .fetch_dividend_events_since(earliest)
+            .await
+            .context("Failed to fetch dividend events")?,
+        |event| &event.stock_symbol,
+    );
+    let corporate_actions = group_by_symbol(
+        source
+            .fetch_corporate_actions_since(earliest)
+            .await
+            .context("Failed to fetch corporate actions")?,
+        |action| &action.stock_symbol,
+    );
+    // 無法以除權息或公司行動解釋的跳空代表資料有未建模事件，報酬不可信，整筆排除。
+    let mut anomalies: HashMap<String, Vec<NaiveDate>> = HashMap::new();
+    for (symbol, date) in source
+        .fetch_anomaly_events(earliest, latest)
+        .await
+        .context("Failed to fetch anomaly events")?
+    {
+        anomalies.entry(symbol).or_default().push(date);
+    }
+
+    let mut snapshots_cache: HashMap<NaiveDate, Vec<EstimateSnapshot>> = HashMap::new();
+    let mut prices_cache: HashMap<NaiveDate, HashMap<String, Decimal>> = HashMap::new();
+    for (date, horizon) in pending {
+        if let Entry::Vacant(entry) = snapshots_cache.entry(date) {
+            let snapshots = repository
+                .fetch_snapshots(date)
+                .await
+                .with_context(|| format!("Failed to fetch estimate snapshots on {date}"))?;
+            entry.insert(snapshots);
+        }
+
+        let mut samples = Vec::new();
+        let end_date = match date.checked_add_months(Months::new(horizon.months())) {
+            Some(target) => source
+                .fetch_trading_day_on_or_before(target)
+                .await
+                .with_context(|| format!("Failed to align end date for {date}"))?
+                .filter(|end| *end > date),
+            None => None,
+        };
+        if let Some(end_date) = end_date {
+            if let Entry::Vacant(entry) = prices_cache.entry(end_date) {
+                let prices = source
+                    .fetch_closing_prices_on(end_date)
+                    .await
+                    .with_context(|| format!("Failed to fetch closing prices on {end_date}"))?;
+                entry.insert(prices.into_iter().collect());
+            }
+            let end_prices = &prices_cache[&end_date];
+
+            let snapshots: Vec<&EstimateSnapshot> = snapshots_cache[&date]
+                .iter()
+                .filter(|snapshot| {
+                    !anomalies
+                        .get(&snapshot.security_code)
+                        .is_some_and(|days| days.iter().any(|day| *day > date && *day <= end_date))
+                })
+                .collect();
+            // 期末交易日沒有報價（持有期間內下市或停牌）的股票仍要計入，
+            // 否則只剩撐到期末的股票，報酬會系統性偏高。
+            let missing: Vec<String> = snapshots
+                .iter()
+                .filter(|snapshot| !end_prices.contains_key(&snapshot.security_code))
+                .map(|snapshot| snapshot.security_code.clone())
+                .collect();
+            let last_quotes: HashMap<String, (NaiveDate, Decimal)> = source
+                .fetch_last_quote_within(&missing, date, end_date)
+                .await
+                .with_context(|| format!("Failed to fetch last quotes before {end_date}"))?
+                .into_iter()
+                .map(|(symbol, quote_date, price)| (symbol, (quote_date, price)))
+                .collect();
+
+            for snapshot in snapshots {
+                let symbol = snapshot.security_code.as_str();
+                let Some((exit_date, exit_price)) =
+                    exit_quote(symbol, end_date, end_prices, &last_quotes)
+                else {
+                    continue;
+                };
+                if exit_date < end_date {
+                    summary.early_exits += 1;
+                }
+                if let Some(sample) = forward_return(
+                    snapshot,
+                    date,
+                    exit_date,
+                    exit_price,
+                    events.get(symbol).map(Vec::as_slice).unwrap_or_default(),
+                    corporate_actions
+                        .get(symbol)
+                        .map(Vec::as_slice)
+                        .unwrap_or_default(),
+                ) {
+                    samples.push(sample);
+                }
+            }
+        }
+
+        // 沒有樣本也照樣寫入全市場的空彙總，該估價日才不會每天被重新評估。
+        let rows = repository
+            .replace_summaries(date, horizon, &summarize(date, horizon, &samples))
+            .await
+            .with_context(|| {
+                format!("Failed to save estimate backtest {date} {}", horizon.code())
+            })?;
+        summary.evaluations += 1;
+        summary.samples += samples.len();
+        summary.rows_written += rows;
+    }
+
+    Ok(summary)
+}
+
+/// 決定出場的日期與收盤價。
+///
+/// 期末交易日有報價時以該日結算；沒有時改用持有期間內最後一筆收盤價，
+/// 等同在下市或停牌前最後一個交易日出場。區間內完全沒有報價才回傳 `None`。
+fn exit_quote(
+    symbol: &str,
+    end_date: NaiveDate,
+    end_prices: &HashMap<String, Decimal>,
+    last_quotes: &HashMap<String, (NaiveDate, Decimal)>,
+) -> Option<(NaiveDate, Decimal)> {
+    end_prices
+        .get(symbol)
+        .map(|price| (end_date, *price))
+        .or_else(|| last_quotes.get(symbol).copied())
+}
+
+/// 以估價日收盤價買入、持有至出場日的含息（不再投入）報酬樣本。
+///
+/// 估價不完整或模擬失敗（價格非正數）時回傳 `None`。
+fn forward_return(
+    snapshot: &EstimateSnapshot,
+    base_date: NaiveDate,
+    end_date: NaiveDate,
+    end_price: Decimal,
+    events: &[DividendEvent],
+    corporate_actions: &[CorporateAction],
+) -> Option<ForwardReturnSample> {
+    let signal =
+        EstimateSignal::classify(snapshot.closing_price, snapshot.cheap, snapshot.expensive)?;
+    // 口徑 B 不會用到除息日價格；再投入口徑不在此比較範圍內。
+    let no_reinvest = |_: NaiveDate| None;
+    let result = simulate(&SimulationInput {
+        principal: Decimal::from(PRINCIPAL),
+        base_date,
+        end_date,
+        base_price: snapshot.closing_price,
+        end_price,
+        events,
+        corporate_actions,
+        reinvest_prices: &no_reinvest,
+    })?;
+    Some(ForwardReturnSample {
+        security_code: snapshot.security_code.clone(),
+        market_id: snapshot.market_id,
+        industry_id: snapshot.industry_id,
+        signal,
+        return_pct: result.total.total_return_pct,
+    })
+}
+
+/// 依股票代號分組。
+fn group_by_symbol<T>(items: Vec<T>, symbol: impl Fn(&T) -> &String) -> HashMap<String, Vec<T>> {
+    let mut grouped: HashMap<String, Vec<T>> = HashMap::new();
+    for item in items {
+        grouped.entry(symbol(&item).clone()).or_default().push(item);
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).expect("測試日期應合法")
+    }
+
+    fn snapshot(closing_price: Decimal) -> EstimateSnapshot {
+        EstimateSnapshot {
+            security_code: "2330".to_string(),
+            market_id: 2,
+            industry_id: 24,
+            closing_price,
+            cheap: dec!(50),
+            expensive: dec!(80),
+        }
+    }
+
+    /// 持有期間內的現金股利必須計入報酬，否則高殖利率的低估股會被系統性低估。
+    #[test]
+    fn forward_return_includes_cash_dividend() {
+        let event = DividendEvent {
+            stock_symbol: "2330".to_string(),
+            ex_dividend_date_cash: Some(date(2025, 2, 10)),
+            ex_dividend_date_stock: None,
+            cash_dividend: dec!(2),
+            stock_dividend: Decimal::ZERO,
+        };
+        let sample = forward_return(
+            &snapshot(dec!(50)),
+            date(2025, 1, 2),
+            date(2025, 4, 2),
+            dec!(53),
+            &[event],
+            &[],
+        )
+        .expect("應產生樣本");
+        assert_eq!(sample.signal, EstimateSignal::Undervalued);
+        assert_eq!(sample.return_pct.round_dp(2), dec!(10));
+    }
+
+    /// 估價不完整的股票不分組，也就不產生樣本。
+    #[test]
+    fn forward_return_skips_incomplete_estimate() {
+        let mut incomplete = snapshot(dec!(60));
+        incomplete.cheap = Decimal::ZERO;
+        assert!(
+            forward_return(
+                &incomplete,
+                date(2025, 1, 2),
+                date(2025, 4, 2),
+                dec!(60),
+                &[],
+                &[],
+            )
+            .is_none()
+        );
+    }
+
+    /// 期末沒有報價的股票以持有期間內最後一筆收盤價出場，不能被略過。
+    #[test]
+    fn exit_quote_falls_back_to_last_close_before_end_date() {
+        let end_prices = HashMap::from([("2330".to_string(), dec!(60))]);
+        let last_quotes = HashMap::from([("1101".to_string(), (date(2025, 3, 14), dec!(12)))]);
+
+        assert_eq!(
+            exit_quote("2330", date(2025, 4, 2), &end_prices, &last_quotes),
+            Some((date(2025, 4, 2), dec!(60)))
+        );
+        assert_eq!(
+            exit_quote("1101", date(2025, 4, 2), &end_prices, &last_quotes),
+            Some((date(2025, 3, 14), dec!(12)))
+        );
+        assert_eq!(
+            exit_quote("9999", date(2025, 4, 2), &end_prices, &last_quotes),
+            None
+        );
+
+        // 下市前大跌的股票以虧損計入樣本。
+        let sample = forward_return(
+            &snapshot(dec!(40)),
+            date(2025, 1, 2),
+            date(2025, 3, 14),
+            dec!(12),
+            &[],
+            &[],
+        )
+        .expect("應產生樣本");
+        assert_eq!(sample.return_pct.round_dp(2), dec!(-70));
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_execute() {
+        dotenvy::dotenv().ok();
+        match execute(Some(3)).await {
+            Ok(summary) => tracing::debug!("evaluated {summary:?}"),
+            Err(why) => tracing::debug!("Failed to evaluate because {:?}", why),
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use crate::{
+    core::{declare::Quarter, util::datetime::ReportQuarter},
+    domain::financial::{
+        health::{BalanceSnapshot, FinancialHealthInputs, FinancialHealthScore, FlowSnapshot},
+        line_item::FinancialLineItem,
+        repository::{FinancialHealthRepository, FinancialLineItemRepository},
+    },
+    infra::database::repository::{
+        financial_health::PgFinancialHealthRepository,
+        financial_line_item::PgFinancialLineItemRepository,
+    },
+};
+
+/// 計算指定季度全部公司的財務體質分數，回傳寫入的筆數。
+///
+/// 需要本季、去年同季與去年第四季的三大報表科目；去年資料缺漏的公司
+/// 仍會寫入，只是需要比較或近四季數值的分數為空。
+pub async fn calculate(target: ReportQuarter) -> Result<usize> {
+    let line_items = PgFinancialLineItemRepository::new();
+    let current = line_items
+        .fetch_quarter(target.year, target.quarter)
+        .await?;
+    if current.is_empty() {
+        return Ok(0);
+    }
+    let prior = line_items
+        .fetch_quarter(target.year - 1, target.quarter)
+        .await?;
+    let prior_annual = if target.quarter == Quarter::Q4 {
+        Vec::new()
+    } else {
+        line_items
+            .fetch_quarter(target.year - 1, Quarter::Q4)
+            .await?
+    };
+
+    let repo = PgFinancialHealthRepository::new();
+    let prices = repo.fetch_closing_prices(target.end_date()).await?;
+    let scores = evaluate(target, &current, &prior, &prior_annual, &prices);
+    repo.upsert(&scores).await?;
+    Ok(scores.len())
+}
+
+/// 依序重算 `from` 到 `to`（含）每一季的分數，回傳寫入的筆數。
+pub async fn rebuild(from: ReportQuarter, to: ReportQuarter) -> Result<usize> {
+    let mut written = 0;
+    let mut target = from;
+    while (target.year, target.quarter) <= (to.year, to.quarter) {
+        written += calculate(target).await?;
+        target = target.next();
+    }
+    Ok(written)
+}
+
+/// 依公司分組三季科目並計算分數。
+fn evaluate(
+    target: ReportQuarter,
+    current: &[FinancialLineItem],
+    prior: &[FinancialLineItem],
+    prior_annual: &[FinancialLineItem],
+    prices: &HashMap<String, (NaiveDate, Decimal)>,
+) -> Vec<FinancialHealthScore> {
+    let current = group(current);
+    let prior = group(prior);
+    let prior_annual = group(prior_annual);
+
+    let mut codes: Vec<&str> = current.keys().copied().collect();
+    codes.sort_unstable();
+    codes
+        .into_iter()
+        .map(|code| {
+            let items = &current[code];
+            let prior_items = prior.get(code).map(Vec::as_slice).unwrap_or_default();
+            let annual_items = prior_annual
+                .get(code)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+
+            let year_to_date = FlowSnapshot::from_items(items);
+            let prior_year_to_date = FlowSnapshot::from_items(prior_items);
+            let trailing = FlowSnapshot::trailing(
+                target.quarter,
+                &year_to_date,
+                &FlowSnapshot::from_items(annual_items),
+                &prior_year_to_date,
+            );
+            let price = prices.get(code);
+            let inputs = FinancialHealthInputs {
+                balance: BalanceSnapshot::from_items(items),
+                prior_balance: BalanceSnapshot::from_items(prior_items),
+                year_to_date,
+                prior_year_to_date,
+                trailing,
+                price_date: price.map(|(date, _)| *date),
+                closing_price: price.map(|(_, price)| *price),
+            };
+            FinancialHealthScore::evaluate(
+                code,
+                target.year,
+                target.quarter,
+                target.end_date(),
+                inputs,
+            )
+        })
+        .collect()
+}
+
+fn group(items: &[FinancialLineItem]) -> HashMap<&str, Vec<FinancialLineItem>> {
+    let mut grouped: HashMap<&str, Vec<FinancialLineItem>> = HashMap::new();
+    for item in items {
+        grouped
+            .entry(item.security_code.as_str())
+            .or_default()
+            .push(item.clone());
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use crate::domain::financial::line_item::{LineItem, PeriodType};
+
+    use super::*;
+
+    fn item(
+        security_code: &str,
+        year: i32,
+        quarter: Quarter,
+        item: LineItem,
+        period: PeriodType,
+        value: Decimal,
+    ) -> FinancialLineItem {
+        FinancialLineItem {
+            security_code: security_code.to_string(),
+            year,
+            quarter,
+            item,
+            period,
+            value,
+            element: String::new(),
+            consolidated: true,
+        }
+    }
+
+    /// 近四季以去年第四季與去年同季推算，季末收盤價帶入輸入。
+    #[test]
+    fn evaluate_combines_three_filings() {
+        let target = ReportQuarter::new(2024, Quarter::Q2);
+        let current = vec![
+            item(
+                "2330",
+                2024,
+                Quarter::Q2,
+                LineItem::NetIncome,
+                PeriodType::YearToDate,
+                dec!(30),
+            ),
+            item(
+                "2330",
+                2024,
+                Quarter::Q2,
+                LineItem::NetIncome,
+                PeriodType::Quarter,
+                dec!(16),
+            ),
+            item(
+                "2330",
+                2024,
+                Quarter::Q2,
+                LineItem::TotalAssets,
+                PeriodType::Instant,
+                dec!(500),
+            ),
+            item(
+                "1101",
+                2024,
+                Quarter::Q2,
+                LineItem::NetIncome,
+                PeriodType::YearToDate,
+                dec!(5),
+            ),
+        ];
+        let prior = vec![item(
+            "2330",
+            2023,
+            Quarter::Q2,
+            LineItem::NetIncome,
+            PeriodType::YearToDate,
+            dec!(20),
+        )];
+        let prior_annual = vec![item(
+            "2330",
+            2023,
+            Quarter::Q4,
+            LineItem::NetIncome,
+            PeriodType::YearToDate,
+            dec!(50),
+        )];
+        let price_date = NaiveDate::from_ymd_opt(2024, 6, 28).unwrap();
+        let prices = HashMap::from([("2330".to_string(), (price_date, dec!(900)))]);
+
+        let scores = evaluate(target, &current, &prior, &prior_annual, &prices);
+        assert_eq!(scores.len(), 2);
+        assert_eq!(scores[0].security_code, "1101");
+        assert_eq!(scores[0].inputs.trailing.net_income, None);
+
+        let tsmc = &scores[1];
+        assert_eq!(
+            tsmc.period_end,
+            NaiveDate::from_ymd_opt(2024, 6, 30).unwrap()
+        );
+        assert_eq!(tsmc.inputs.year_to_date.net_income, Some(dec!(30)));
+        assert_eq!(tsmc.inputs.trailing.net_income, Some(dec!(60)));
+        assert_eq!(tsmc.inputs.balance.total_assets, Some(dec!(500)));
+        assert_eq!(tsmc.inputs.price_date, Some(price_date));
+        assert_eq!(tsmc.f_score_criteria.positive_roa, Some(true));
+        assert_eq!(tsmc.f_score, None);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_calculate() {
+        dotenvy::dotenv().ok();
+        match calculate(ReportQuarter::new(2024, Quarter::Q4)).await {
+            Ok(count) => tracing::debug!("updated {count} financial health scores"),
+            Err(why) => tracing::debug!("Failed to calculate because {:?}", why),
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::{HashMap, hash_map::Entry};
+
+use anyhow::Result;
+use chrono::{NaiveDate, TimeDelta};
+
+use crate::{
+    domain::industry::{
+        IndustryDailyAggregate, IndustryDailyMetric, IndustryMetricRepository,
+        IndustryRevenueGrowth, revenue_period_as_of,
+    },
+    infra::database::repository::industry::PgIndustryMetricRepository,
+};
+
+/// 重算歷史時每批讀取的日曆天數，避免單一查詢掃過多年報價。
+const REBUILD_WINDOW_DAYS: i64 = 90;
+
+/// 計算指定交易日的產業指標，回傳寫入的產業數。
+pub async fn calculate(date: NaiveDate) -> Result<usize> {
+    rebuild(date, date).await
+}
+
+/// 依序重算區間內（含頭尾）的產業指標，回傳寫入的筆數。
+///
+/// 產業指數逐日連乘，修正過去某天的報價後需從該日重算到最新一天。
+pub async fn rebuild(from: NaiveDate, to: NaiveDate) -> Result<usize> {
+    let repo = PgIndustryMetricRepository::new();
+    let mut previous: HashMap<i32, IndustryDailyMetric> = repo
+        .fetch_latest_before(from)
+        .await?
+        .into_iter()
+        .map(|metric| (metric.industry_id, metric))
+        .collect();
+    let mut revenue: HashMap<i64, HashMap<i32, IndustryRevenueGrowth>> = HashMap::new();
+
+    let mut written = 0;
+    let mut start = from;
+    while start <= to {
+        let end = (start + TimeDelta::days(REBUILD_WINDOW_DAYS - 1)).min(to);
+        let aggregates = repo.fetch_daily_aggregates(start, end).await?;
+        for aggregate in &aggregates {
+            let period = revenue_period_as_of(aggregate.date);
+            if let Entry::Vacant(entry) = revenue.entry(period) {
+                let growth = repo.fetch_revenue_growth(period).await?;
+                entry.insert(
+                    growth
+                        .into_iter()
+                        .map(|growth| (growth.industry_id, growth))
+                        .collect(),
+                );
+            }
+        }
+        let metrics = chain(&aggregates, &revenue, &mut previous);
+        repo.upsert(&metrics).await?;
+        written += metrics.len();
+        start = end + TimeDelta::days(1);
+    }
+
+    Ok(written)
+}
+
+/// 依日期順序組出各產業的指標，`previous` 會更新為各產業最後一筆。
+fn chain(
+    aggregates: &[IndustryDailyAggregate],
+    revenue: &HashMap<i64, HashMap<i32, IndustryRevenueGrowth>>,
+    previous: &mut HashMap<i32, IndustryDailyMetric>,
+) -> Vec<IndustryDailyMetric> {
+    aggregates
+        .iter()
+        .map(|aggregate| {
+            let period = revenue_period_as_of(aggregate.date);
+            let growth = revenue
+                .get(&period)
+                .and_then(|growth| growth.get(&aggregate.industry_id));
+            let metric = IndustryDailyMetric::next(
+                aggregate,
+                period,
+                growth,
+                previous.get(&aggregate.industry_id),
+            );
+            previous.insert(aggregate.industry_id, metric.clone());
+            metric
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn aggregate(day: u32, industry_id: i32, pct: Decimal) -> IndustryDailyAggregate {
+        IndustryDailyAggregate {
+            date: NaiveDate::from_ymd_opt(2025, 3, day).expect("測試日期應合法"),
+            industry_id,
+            stocks: 1,
+            advances: 1,
+            declines: 0,
+            unchanged: 0,
+            cap_weighted_return_pct: Some(pct),
+            equal_weighted_return_pct: Some(pct),
+            trade_value: Decimal::ZERO,
+            turnover_share_pct: None,
+        }
+    }
+
+    /// 跨過營收公告期限時改用新月份，產業指數各自連乘。
+    #[test]
+    fn chain_switches_revenue_period_at_deadline() {
+        let growth = |period_pct| {
+            HashMap::from([(
+                24,
+                IndustryRevenueGrowth {
+                    industry_id: 24,
+                    companies: 2,
+                    yoy_pct: Some(period_pct),
+                },
+            )])
+        };
+        let revenue = HashMap::from([(202501, growth(dec!(5))), (202502, growth(dec!(8)))]);
+        let mut previous = HashMap::new();
+        let metrics = chain(
+            &[
+                aggregate(7, 24, dec!(1)),
+                aggregate(7, 1, dec!(1)),
+                aggregate(10, 24, dec!(10)),
+            ],
+            &revenue,
+            &mut previous,
+        );
+
+        assert_eq!(metrics[0].revenue_yoy_pct, Some(dec!(5)));
+        assert_eq!(metrics[1].revenue_period, None);
+        assert_eq!(metrics[2].revenue_period, Some(202502));
+        assert_eq!(metrics[2].revenue_yoy_pct, Some(dec!(8)));
+        assert_eq!(metrics[2].index_level, dec!(110));
+        assert_eq!(previous[&1].index_level, dec!(100));
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_calculate() {
+        dotenvy::dotenv().ok();
+        let date = chrono::Local::now().date_naive();
+        match calculate(date).await {
+            Ok(count) => tracing::debug!("updated {count} industry metrics"),
+            Err(why) => tracing::debug!("Failed to calculate because {:?}", why),
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::{NaiveDate, TimeDelta};
+
+use crate::{
+    domain::breadth::{BreadthCounts, BreadthIndicator, BreadthIndicatorRepository},
+    infra::database::repository::breadth::PgBreadthIndicatorRepository,
+};
+
+/// 重算歷史時每批讀取的日曆天數；新高新低需要掃 `"DailyQuotes"`，分批避免單一查詢過大。
+const REBUILD_WINDOW_DAYS: i64 = 180;
+
+/// 計算指定交易日的廣度指標，回傳寫入的市場數。
+///
+/// 需在當日 `daily_stock_price_stats` 寫入後執行；遞推起點為各市場前一筆已存的指標。
+pub async fn calculate(date: NaiveDate) -> Result<usize> {
+    rebuild(date, date).await
+}
+
+/// 依序重算區間內（含頭尾）的廣度指標，回傳寫入的筆數。
+///
+/// 騰落線、EMA 與總和指數都是逐日遞推，補寫或修正過去某天的統計後，
+/// 必須從該日一路重算到最新一天，後面的數值才會跟著更新。
+pub async fn rebuild(from: NaiveDate, to: NaiveDate) -> Result<usize> {
+    let repo = PgBreadthIndicatorRepository::new();
+    let mut previous: HashMap<i32, BreadthIndicator> = repo
+        .fetch_latest_before(from)
+        .await?
+        .into_iter()
+        .map(|indicator| (indicator.market_id, indicator))
+        .collect();
+
+    let mut written = 0;
+    let mut start = from;
+    while start <= to {
+        let end = (start + TimeDelta::days(REBUILD_WINDOW_DAYS - 1)).min(to);
+        let counts = repo.fetch_counts(start, end).await?;
+        let indicators = chain(&counts, &mut previous);
+        repo.upsert(&indicators).await?;
+        written += indicators.len();
+        start = end + TimeDelta::days(1);
+    }
+
+    Ok(written)
+}
+
+/// 依日期順序遞推各市場的指標，`previous` 會更新為各市場最後一筆。
+fn chain(
+    counts: &[BreadthCounts],
+    previous: &mut HashMap<i32, BreadthIndicator>,
+) -> Vec<BreadthIndicator> {
+    counts
+        .iter()
+        .map(|counts| {
+            let indicator = BreadthIndicator::next(counts, previous.get(&counts.market_id));
+            previous.insert(counts.market_id, indicator.clone());
+            indicator
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn counts(day: u32, market_id: i32, advances: i32, declines: i32) -> BreadthCounts {
+        BreadthCounts {
+            date: NaiveDate::from_ymd_opt(2025, 3, day).expect("測試日期應合法"),
+            market_id,
+            advances,
+            declines,
+            unchanged: 0,
+            new_highs: 0,
+            new_lows: 0,
+            above_moving_average: [0; 5],
+            below_moving_average: [0; 5],
+        }
+    }
+
+    /// 各市場各自遞推，不會把上市的前一日狀態接到上櫃。
+    #[test]
+    fn chain_keeps_markets_separate() {
+        let mut previous = HashMap::new();
+        let indicators = chain(
+            &[
+                counts(3, 2, 60, 40),
+                counts(3, 4, 10, 30),
+                counts(4, 2, 50, 50),
+                counts(4, 4, 30, 10),
+            ],
+            &mut previous,
+        );
+
+        assert_eq!(indicators[2].ad_line, 20);
+        assert_eq!(indicators[3].ad_line, 0);
+        assert_eq!(indicators[3].ema_19, dec!(-400));
+        assert_eq!(previous[&2].date, indicators[2].date);
+        assert_eq!(previous[&4].ema_39, dec!(-450));
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_calculate() {
+        dotenvy::dotenv().ok();
+        let date = chrono::Local::now().date_naive();
+        match calculate(date).await {
+            Ok(count) => tracing::debug!("updated {count} breadth indicators"),
+            Err(why) => tracing::debug!("Failed to calculate because {:?}", why),
+        }
+    }
+}
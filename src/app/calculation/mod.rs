@@ -2,9 +2,25 @@
 pub mod cagr;
 /// 股票每日行情
 pub mod daily_quotes;
+/// 除息填息追蹤
+pub mod dividend_gap_fill;
 /// 計算股票股息收入
 pub mod dividend_record;
+/// 便宜、合理、昂貴價訊號的向後報酬回測
+pub mod estimate_backtest;
 /// 估算便宜、合理、昂貴價
 pub mod estimated_price;
+/// Piotroski F-score、Altman Z-score 與現金流量等財務體質分數
+pub mod financial_health;
+/// 產業市值加權與等權重報酬、成交佔比、營收年增率與產業指數
+pub mod industry_metric;
+/// 騰落線、McClellan 與新高新低等市場廣度指標
+pub mod market_breadth;
 /// 計算每日市值
 pub mod money_history;
+/// 月營收滾動合計、年增率加速度、創新高與季節性驚喜
+pub mod revenue_momentum;
+/// 歷史殖利率、本益比、股價淨值比的估值區間
+pub mod valuation_band;
+/// 可插拔估價模型（區間、葛拉漢數、股利折現）
+pub mod valuation_model;
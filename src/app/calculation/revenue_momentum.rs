@@ -0,0 +1,83 @@
+use anyhow::Result;
+
+use crate::{
+    domain::financial::{
+        repository::RevenueMomentumRepository,
+        revenue_momentum::{RevenueMomentum, RevenuePoint, group_histories},
+    },
+    infra::database::repository::revenue_momentum::PgRevenueMomentumRepository,
+};
+
+/// 每批寫入的筆數，避免重算歷史時單一 UNNEST 陣列過大。
+const UPSERT_CHUNK_SIZE: usize = 5000;
+
+/// 計算指定月份（`YYYYMM`）全部公司的營收動能，回傳寫入的筆數。
+///
+/// 創新高需要比較全部歷史，因此每次都讀取該月之前的完整營收。
+pub async fn calculate(month: i64) -> Result<usize> {
+    rebuild(month, month).await
+}
+
+/// 重算 `from` 到 `to`（`YYYYMM`，含）之間每個月份的營收動能，回傳寫入的筆數。
+pub async fn rebuild(from: i64, to: i64) -> Result<usize> {
+    let repo = PgRevenueMomentumRepository::new();
+    let points = repo.fetch_revenues(to).await?;
+    let momentums = evaluate(&points, from, to);
+    for chunk in momentums.chunks(UPSERT_CHUNK_SIZE) {
+        repo.upsert(chunk).await?;
+    }
+    Ok(momentums.len())
+}
+
+/// 依公司分組營收並計算區間內每個有營收月份的動能。
+fn evaluate(points: &[RevenuePoint], from: i64, to: i64) -> Vec<RevenueMomentum> {
+    group_histories(points)
+        .values()
+        .flat_map(|history| {
+            history
+                .months()
+                .filter(|month| (from..=to).contains(month))
+                .filter_map(|month| history.momentum(month))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+
+    use super::*;
+
+    /// 只輸出區間內的月份，區間前的營收仍作為歷史參與計算。
+    #[test]
+    fn evaluate_limits_months_but_keeps_history() {
+        let points: Vec<RevenuePoint> = (1..=12)
+            .map(|month| RevenuePoint {
+                security_code: "2330".to_string(),
+                month: 202400 + month,
+                revenue: Decimal::from(month),
+            })
+            .collect();
+
+        let momentums = evaluate(&points, 202411, 202412);
+        assert_eq!(
+            momentums
+                .iter()
+                .map(|momentum| momentum.month)
+                .collect::<Vec<_>>(),
+            vec![202411, 202412]
+        );
+        assert_eq!(momentums[1].rolling_3m, Some(Decimal::from(33)));
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_calculate() {
+        dotenvy::dotenv().ok();
+        match calculate(202509).await {
+            Ok(count) => tracing::debug!("updated {count} revenue momentum rows"),
+            Err(why) => tracing::debug!("Failed to calculate because {:?}", why),
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use anyhow::Result;
+use chrono::NaiveDate;
+
+use crate::{
+    domain::valuation::ValuationBandRepository,
+    infra::database::repository::valuation_band::PgValuationBandRepository,
+};
+
+/// 補齊每日歷史殖利率後，重建指定交易日的 5 年與 10 年估值區間。
+///
+/// 回傳 `(歷史殖利率寫入筆數, 估值區間寫入筆數)`。
+pub async fn calculate(date: NaiveDate) -> Result<(u64, u64)> {
+    let repo = PgValuationBandRepository::new();
+    let yields = repo.rebuild_daily_yields(date).await?;
+    let bands = repo.rebuild_bands(date).await?;
+    Ok((yields, bands))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_calculate() {
+        dotenvy::dotenv().ok();
+        let date = chrono::Local::now().date_naive();
+        match calculate(date).await {
+            Ok((yields, bands)) => tracing::debug!("updated {yields} yields and {bands} bands"),
+            Err(why) => tracing::debug!("Failed to calculate because {:?}", why),
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use anyhow::Result;
+use chrono::NaiveDate;
+
+use crate::{
+    domain::{
+        config::repository::ConfigRepository,
+        valuation::{
+            ValuationModel, ValuationModelKind, ValuationModelRepository, build_model, estimate_all,
+        },
+    },
+    infra::database::repository::{
+        config::PgConfigRepository, valuation_model::PgValuationModelRepository,
+    },
+};
+
+/// 以系統設定的假設建立全部估價模型。
+///
+/// 每個模型的假設存放在 `valuation-model:<模型代碼>` 設定鍵（JSON），未設定時
+/// 使用預設值；設定值無法解析的模型記錄錯誤後略過，不以預設值頂替。
+pub async fn load_models() -> Result<Vec<Box<dyn ValuationModel>>> {
+    let config_repo = PgConfigRepository::new();
+    let mut models = Vec::with_capacity(ValuationModelKind::ALL.len());
+    for kind in ValuationModelKind::ALL {
+        let config = config_repo.find_by_key(&kind.config_key()).await?;
+        match build_model(kind, config.as_ref().map(|config| config.val.as_str())) {
+            Ok(model) => models.push(model),
+            Err(why) => tracing::error!("Skip valuation model {}: {:?}", kind.code(), why),
+        }
+    }
+    Ok(models)
+}
+
+/// 以全部估價模型計算指定交易日每檔股票的便宜、合理、昂貴價，回傳寫入的筆數。
+///
+/// 與 `estimated_price` 的加權估價並存：這裡每個模型各自保存結果與假設，
+/// 供比較不同模型與回測。
+pub async fn calculate(date: NaiveDate) -> Result<usize> {
+    let models = load_models().await?;
+    let repo = PgValuationModelRepository::new();
+    let mut inputs = repo.fetch_inputs(date).await?;
+
+    for request in models.iter().filter_map(|model| model.quantile_request()) {
+        let mut quantiles = repo.fetch_quantiles(date, &request).await?;
+        for input in inputs.iter_mut() {
+            if let Some(values) = quantiles.remove(&input.security_code) {
+                input.quantiles.insert(request.metric, values);
+            }
+        }
+    }
+
+    let estimates: Vec<_> = inputs
+        .iter()
+        .flat_map(|input| estimate_all(&models, input))
+        .collect();
+    repo.upsert_estimates(&estimates).await?;
+    Ok(estimates.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_calculate() {
+        dotenvy::dotenv().ok();
+        let date = chrono::Local::now().date_naive();
+        match calculate(date).await {
+            Ok(count) => tracing::debug!("updated {count} model estimates"),
+            Err(why) => tracing::debug!("Failed to calculate because {:?}", why),
+        }
+    }
+}
@@ -1,10 +1,14 @@
 //! # 領域事件派發器模組
 //!
 //! 負責接收領域事件 ([`DomainEvent`]) 並在背景非同步處理對應的副作用，
-//! 例如 Telegram 通知與 gRPC 同步推送。
+//! 例如 Telegram 通知與排入證券主檔同步佇列。
 //! 此模組的目的是將核心業務邏輯 (Use Case) 與外部副作用解耦，
 //! 使 Use Case 僅負責商業編排，不直接耦合基礎設施。
 //!
+//! 生產環境的派發器會在處理每個事件前先寫入事件日誌 ([`EventLogRepository`])，
+//! 日誌序號即處理順序，並發布到已註冊的對外事件匯流排；由
+//! [`replay`](crate::app::event::replay) 重播的事件不會重複寫入或發布。
+//!
 //! 事件類型對應的副作用處理拆分至子模組：
 //! - [`debounce`]：Telegram 訊息防震批次發送器。
 //! - [`money_flow`]：`MoneyFlowRecalculated` 市值變化通知。
@@ -16,6 +20,7 @@ use std::sync::OnceLock;
 use anyhow::Result;
 use tokio::sync::mpsc;
 
+use crate::domain::event_log::EventLogRepository;
 use crate::domain::events::DomainEvent;
 
 use debounce::TelegramDebouncer;
@@ -33,8 +38,8 @@ static EVENT_DISPATCHER: OnceLock<EventDispatcher> = OnceLock::new();
 /// 事件處理函式的型別別名。
 ///
 /// 接收一個 `DomainEvent`，回傳一個非同步的 `Result<()>`。
-/// 使用 trait object 以支援測試時替換為 fake handler。
-type EventHandlerFn = Box<
+/// 使用 trait object 以支援測試時替換為 fake handler，亦作為重播事件的接收端。
+pub type EventHandlerFn = Box<
     dyn Fn(DomainEvent) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + Send>>
         + Send
         + Sync,
@@ -47,7 +52,14 @@ type EventHandlerFn = Box<
 /// </summary>
 pub struct EventDispatcher {
     /// 事件發送端，Use Case 透過此通道將事件送入背景處理迴圈。
-    event_sender: mpsc::Sender<DomainEvent>,
+    event_sender: mpsc::Sender<QueuedEvent>,
+}
+
+/// 背景通道中的事件。
+struct QueuedEvent {
+    event: DomainEvent,
+    /// 重播的事件已在日誌中且已對外發布過，只交給處理器。
+    replayed: bool,
 }
 
 impl EventDispatcher {
@@ -59,7 +71,23 @@ impl EventDispatcher {
     /// - `handler`: 自訂的事件處理函式。若為 `None`，則使用預設的生產處理器。
     /// - `buffer_size`: mpsc 通道的緩衝區大小。
     pub fn new_with_handler(handler: Option<EventHandlerFn>, buffer_size: usize) -> Self {
-        let (tx, mut rx) = mpsc::channel::<DomainEvent>(buffer_size);
+        Self::new_with_event_log(handler, buffer_size, None)
+    }
+
+    /// <summary>
+    /// 建立會將事件寫入事件日誌的事件派發器，並啟動背景事件處理迴圈。
+    /// </summary>
+    ///
+    /// # 參數
+    /// - `handler`: 自訂的事件處理函式。若為 `None`，則使用預設的生產處理器。
+    /// - `buffer_size`: mpsc 通道的緩衝區大小。
+    /// - `event_log`: 事件日誌倉儲。若為 `None`，則不保存事件。
+    pub fn new_with_event_log(
+        handler: Option<EventHandlerFn>,
+        buffer_size: usize,
+        event_log: Option<Arc<dyn EventLogRepository>>,
+    ) -> Self {
+        let (tx, mut rx) = mpsc::channel::<QueuedEvent>(buffer_size);
 
         let debouncer = Arc::new(TelegramDebouncer::new());
 
@@ -74,7 +102,20 @@ impl EventDispatcher {
 
         // 啟動背景事件處理迴圈
         tokio::spawn(async move {
-            while let Some(event) = rx.recv().await {
+            while let Some(QueuedEvent { event, replayed }) = rx.recv().await {
+                if !replayed {
+                    // 在背景迴圈中寫入，日誌順序與處理順序一致；寫入失敗不影響事件處理。
+                    if let Some(event_log) = &event_log
+                        && let Err(why) = event_log.append(&event).await
+                    {
+                        tracing::error!("領域事件寫入日誌失敗: {:?}", why);
+                    }
+                    // 只排入對外發布佇列，由獨立 task 發布，中介緩慢不會延遲後續副作用。
+                    crate::app::ports::publish_event(crate::app::ports::OutboundEvent::Domain(
+                        event.clone(),
+                    ));
+                }
+
                 if let Err(why) = handler(event).await {
                     tracing::error!("領域事件處理失敗: {:?}", why);
                 }
@@ -86,10 +127,16 @@ impl EventDispatcher {
 
     /// <summary>
     /// 建立使用預設生產環境處理器的事件派發器。
-    /// 通道緩衝區大小預設為 100。
+    /// 通道緩衝區大小預設為 100，事件寫入 PostgreSQL 事件日誌。
     /// </summary>
     pub fn new() -> Self {
-        Self::new_with_handler(None, 100)
+        Self::new_with_event_log(
+            None,
+            100,
+            Some(Arc::new(
+                crate::infra::database::repository::event_log::PgEventLogRepository::new(),
+            )),
+        )
     }
 }
 
@@ -105,9 +152,25 @@ impl EventDispatcher {
     /// 此方法不會阻塞呼叫端，事件會透過 mpsc 通道送出。
     /// </summary>
     pub async fn dispatch_async(&self, events: Vec<DomainEvent>) {
+        self.send(events, false).await;
+    }
+
+    /// <summary>
+    /// 將事件日誌中的事件重新送入背景處理迴圈，觸發與原派發相同的副作用。
+    /// 重新派發的事件不會再寫入事件日誌，也不會再對外發布。
+    /// </summary>
+    pub async fn redispatch_async(&self, events: Vec<DomainEvent>) {
+        self.send(events, true).await;
+    }
+
+    async fn send(&self, events: Vec<DomainEvent>, replayed: bool) {
         for event in events {
-            if let Err(why) = self.event_sender.send(event).await {
-                tracing::error!("無法送出領域事件至背景通道: {:?}", why);
+            if let Err(why) = self
+                .event_sender
+                .send(QueuedEvent { event, replayed })
+                .await
+            {
+                tracing::error!("無法送出領域事件至背景通道: {:?}", why.0.event);
             }
         }
     }
@@ -120,7 +183,9 @@ impl EventDispatcher {
         event: DomainEvent,
         debouncer: Arc<TelegramDebouncer>,
     ) -> Result<()> {
+        use crate::app::event::stock_sync;
         use crate::core::declare::StockExchangeMarket;
+        use crate::domain::registry::sync::SyncReason;
         // MarkdownV2 跳脫工具在 core 層（不是 interfaces::bot）：
         // app 只依賴內層，訊息實際送往哪個管道由已註冊的 AlertSink 決定。
         use crate::core::util::text;
@@ -134,8 +199,8 @@ impl EventDispatcher {
                 industry_id,
                 ..
             } => {
-                // 副作用 1：透過 gRPC 同步通知 Go 微服務
-                Self::push_to_go_service(symbol, name, market_id, industry_id).await;
+                // 副作用 1：排入同步佇列，由背景工作批次同步至 Go 微服務
+                stock_sync::enqueue(vec![symbol.clone()], SyncReason::Registered).await;
 
                 // 副作用 2：產生日誌與寫入 Telegram 緩衝區進行防震批次發送
                 let market = StockExchangeMarket::from(market_id);
@@ -160,37 +225,34 @@ impl EventDispatcher {
             }
             DomainEvent::StockIdentityChanged {
                 ref symbol,
+                ref old_name,
                 ref new_name,
+                old_market_id,
                 new_market_id,
+                old_industry_id,
                 new_industry_id,
                 ..
             } => {
-                // 副作用 1：透過 gRPC 同步通知 Go 微服務
-                Self::push_to_go_service(symbol, new_name, new_market_id, new_industry_id).await;
+                // 副作用 1：排入同步佇列，由背景工作批次同步至 Go 微服務
+                stock_sync::enqueue(vec![symbol.clone()], SyncReason::IdentityChanged).await;
 
                 // 副作用 2：產生日誌與寫入 Telegram 緩衝區進行防震批次發送
-                let market = StockExchangeMarket::from(new_market_id);
-                let market_name = match market {
-                    None => " - ".to_string(),
-                    Some(sem) => sem.name(),
-                };
-                let industry_name = SHARE
-                    .get_industry_name(new_industry_id)
-                    .unwrap_or_else(|| " - ".to_string());
-
-                let log_msg = format!(
-                    "新增股票︰ {stock_symbol} {stock_name} {market_name} {industry_name}",
-                    stock_symbol = symbol,
-                    stock_name = text::escape_markdown_v2(new_name),
-                    market_name = market_name,
-                    industry_name = industry_name
+                let log_msg = stock_identity_changed_message(
+                    symbol,
+                    old_name,
+                    new_name,
+                    old_market_id,
+                    new_market_id,
+                    old_industry_id,
+                    new_industry_id,
                 );
 
                 tracing::info!("{}", log_msg.clone());
                 debouncer.add_message(log_msg).await;
             }
-            DomainEvent::NetAssetValueUpdated { .. } => {
-                // 目前每股淨值更新不需要額外副作用
+            DomainEvent::NetAssetValueUpdated { symbol, .. } => {
+                // Go 微服務也保存每股淨值，排入同步佇列
+                stock_sync::enqueue(vec![symbol], SyncReason::NetAssetValueUpdated).await;
             }
             DomainEvent::StockIndexUpdated {
                 date,
@@ -206,6 +268,23 @@ impl EventDispatcher {
                 );
                 debouncer.add_message(msg).await;
             }
+            DomainEvent::DerivativesMarketUpdated {
+                date,
+                futures_close,
+                futures_change,
+                foreign_net_open_interest,
+                put_call_ratio_percent,
+                ..
+            } => {
+                let msg = derivatives_market_message(
+                    date,
+                    futures_close,
+                    futures_change,
+                    foreign_net_open_interest,
+                    put_call_ratio_percent,
+                );
+                debouncer.add_message(msg).await;
+            }
             DomainEvent::MoneyFlowRecalculated { date, .. } => {
                 Self::handle_money_flow_recalculated(date).await?;
             }
@@ -220,22 +299,85 @@ impl EventDispatcher {
 
         Ok(())
     }
+}
 
-    /// 將股票資訊同步推送至外部服務（目前為 Go 微服務）。
-    /// 推送失敗時僅記錄錯誤日誌，不中斷流程。
-    ///
-    /// 透過 `app::ports::push_stock_info` 抽象介面推送——handler 不再直接
-    /// 依賴 gRPC 產生的 `StockInfoRequest` DTO（傳輸層細節），實際的
-    /// gRPC 轉換與呼叫由 `main` 啟動時註冊的 adapter 負責。
-    async fn push_to_go_service(symbol: &str, name: &str, market_id: i32, industry_id: i32) {
-        crate::app::ports::push_stock_info(crate::app::ports::StockInfoPush {
-            stock_symbol: symbol.to_string(),
-            name: name.to_string(),
-            stock_exchange_market_id: market_id,
-            stock_industry_id: industry_id,
-        })
-        .await;
+/// 組成股票異動訊息（MarkdownV2），只列出有變動的欄位並以「舊 → 新」呈現。
+///
+/// 市場或產業代碼查無對應名稱時以 `-` 顯示。
+fn stock_identity_changed_message(
+    symbol: &str,
+    old_name: &str,
+    new_name: &str,
+    old_market_id: i32,
+    new_market_id: i32,
+    old_industry_id: i32,
+    new_industry_id: i32,
+) -> String {
+    use crate::core::declare::StockExchangeMarket;
+    use crate::core::util::text;
+    use crate::infra::cache::SHARE;
+
+    let market_name = |id: i32| {
+        StockExchangeMarket::from(id)
+            .map(|market| market.name())
+            .unwrap_or_else(|| "-".to_string())
+    };
+    let industry_name = |id: i32| {
+        SHARE
+            .get_industry_name(id)
+            .unwrap_or_else(|| "-".to_string())
+    };
+
+    let mut msg = format!("股票異動︰ {symbol}");
+    if old_name != new_name {
+        msg.push_str(&format!(
+            " 名稱︰{} → {}",
+            text::escape_markdown_v2(old_name),
+            text::escape_markdown_v2(new_name)
+        ));
+    } else {
+        msg.push_str(&format!(" {}", text::escape_markdown_v2(new_name)));
     }
+    if old_market_id != new_market_id {
+        msg.push_str(&format!(
+            " 市場︰{} → {}",
+            market_name(old_market_id),
+            market_name(new_market_id)
+        ));
+    }
+    if old_industry_id != new_industry_id {
+        msg.push_str(&format!(
+            " 產業︰{} → {}",
+            industry_name(old_industry_id),
+            industry_name(new_industry_id)
+        ));
+    }
+    msg
+}
+
+/// 組成期貨選擇權收盤摘要訊息（MarkdownV2），由 15:30 的期貨選擇權排程單獨送出，
+/// 不與 15:00 收盤行情的大盤指數訊息同批。
+///
+/// 期交所資料缺漏的欄位以 `-` 顯示，不補 0 以免誤讀為平盤或零部位。
+fn derivatives_market_message(
+    date: chrono::NaiveDate,
+    futures_close: Option<rust_decimal::Decimal>,
+    futures_change: Option<rust_decimal::Decimal>,
+    foreign_net_open_interest: Option<i64>,
+    put_call_ratio_percent: Option<rust_decimal::Decimal>,
+) -> String {
+    use crate::core::util::text;
+
+    let show =
+        |value: Option<String>| text::escape_markdown_v2(value.unwrap_or_else(|| "-".to_string()));
+    format!(
+        "{} 臺指期︰{} 漲跌︰{} 外資期貨淨未平倉︰{} 口 P/C Ratio︰{}%",
+        text::escape_markdown_v2(date.to_string()),
+        show(futures_close.map(|v| v.to_string())),
+        show(futures_change.map(|v| v.to_string())),
+        show(foreign_net_open_interest.map(|v| v.to_string())),
+        show(put_call_ratio_percent.map(|v| v.to_string())),
+    )
 }
 
 /// <summary>
@@ -257,6 +399,7 @@ pub fn get_global_dispatcher() -> &'static EventDispatcher {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::event_log::test_double::InMemoryEventLog;
     use std::sync::Arc;
     use tokio::sync::Mutex;
 
@@ -412,6 +555,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn stock_identity_changed_message_lists_changed_fields() {
+        let renamed = stock_identity_changed_message("2330", "台積電", "台積電-新", 2, 2, 24, 24);
+        assert_eq!(renamed, "股票異動︰ 2330 名稱︰台積電 → 台積電\\-新");
+
+        let moved = stock_identity_changed_message("6488", "環球晶", "環球晶", 4, 2, -1, -1);
+        assert_eq!(moved, "股票異動︰ 6488 環球晶 市場︰上櫃 → 上市");
+    }
+
+    #[test]
+    fn derivatives_market_message_escapes_and_marks_missing() {
+        let msg = derivatives_market_message(
+            chrono::NaiveDate::from_ymd_opt(2026, 4, 24).unwrap(),
+            Some(rust_decimal_macros::dec!(21100)),
+            Some(rust_decimal_macros::dec!(-12.5)),
+            Some(-25_000),
+            None,
+        );
+
+        assert_eq!(
+            msg,
+            "2026\\-04\\-24 臺指期︰21100 漲跌︰\\-12\\.5 外資期貨淨未平倉︰\\-25000 口 P/C Ratio︰\\-%"
+        );
+    }
+
     #[tokio::test]
     async fn test_dispatch_multiple_events() {
         let event_log = Arc::new(Mutex::new(Vec::new()));
@@ -445,6 +613,29 @@ mod tests {
         assert_eq!(log.len(), 2, "應收到 2 個事件");
     }
 
+    /// 派發的事件先寫入日誌再處理；重新派發的事件只處理不重複寫入。
+    #[tokio::test]
+    async fn test_dispatch_records_event_log_but_redispatch_does_not() {
+        let handled = Arc::new(Mutex::new(Vec::new()));
+        let event_log = Arc::new(InMemoryEventLog::default());
+        let dispatcher = EventDispatcher::new_with_event_log(
+            Some(fake_handler(handled.clone())),
+            10,
+            Some(event_log.clone()),
+        );
+        let event = DomainEvent::MoneyFlowRecalculated {
+            date: chrono::NaiveDate::from_ymd_opt(2026, 6, 5).unwrap(),
+            occurred_at: chrono::Local::now(),
+        };
+
+        dispatcher.dispatch_async(vec![event.clone()]).await;
+        dispatcher.redispatch_async(vec![event.clone()]).await;
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+        assert_eq!(event_log.appended(), vec![event.clone()]);
+        assert_eq!(*handled.lock().await, vec![event.clone(), event]);
+    }
+
     #[tokio::test]
     async fn test_dispatch_empty_events_is_noop() {
         let event_log = Arc::new(Mutex::new(Vec::new()));
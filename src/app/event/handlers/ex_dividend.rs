@@ -1,6 +1,6 @@
 //! `ExDividendReminderTriggered` 事件處理：發送除權息提醒、重新計算持股股利並發送通知。
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Write;
 
 use anyhow::Result;
@@ -16,10 +16,12 @@ use crate::app::event::taiwan_stock::{
 use crate::core::declare::Industry;
 use crate::core::{alert, util::text};
 use crate::domain::dividend::entity::StockDividendInfo;
-use crate::domain::dividend::repository::DividendRepository;
+use crate::domain::dividend::gap_fill::GapFillSummary;
+use crate::domain::dividend::repository::{DividendGapFillRepository, DividendRepository};
 use crate::domain::portfolio::entity::StockOwnershipDetail;
 use crate::domain::portfolio::repository::PortfolioRepository;
 use crate::infra::database::repository::dividend::PgDividendRepository;
+use crate::infra::database::repository::dividend_gap_fill::PgDividendGapFillRepository;
 use crate::infra::database::repository::portfolio::PgPortfolioRepository;
 
 use super::EventDispatcher;
@@ -38,10 +40,12 @@ impl EventDispatcher {
         Self::sort_market_dividend_info(&mut stocks_dividend_info);
 
         // 發送今日市場清單
+        let gap_fills = Self::fetch_gap_fill_summaries(date, &stocks_dividend_info).await;
         Self::send_market_dividend_message(
             date,
             "進行除權息的股票與 ETF 如下︰",
             &stocks_dividend_info,
+            &gap_fills,
         )
         .await;
 
@@ -56,10 +60,12 @@ impl EventDispatcher {
                 .fetch_stocks_with_dividends_on_date(next_trading_date)
                 .await?;
             Self::sort_market_dividend_info(&mut next_stocks);
+            let gap_fills = Self::fetch_gap_fill_summaries(next_trading_date, &next_stocks).await;
             Self::send_market_dividend_message(
                 next_trading_date,
                 "預計進行除權息的股票與 ETF 如下︰",
                 &next_stocks,
+                &gap_fills,
             )
             .await;
             return Ok(());
@@ -86,16 +92,64 @@ impl EventDispatcher {
             .fetch_stocks_with_dividends_on_date(next_trading_date)
             .await?;
         Self::sort_market_dividend_info(&mut next_stocks);
+        let gap_fills = Self::fetch_gap_fill_summaries(next_trading_date, &next_stocks).await;
         Self::send_market_dividend_message(
             next_trading_date,
             "預計進行除權息的股票與 ETF 如下︰",
             &next_stocks,
+            &gap_fills,
         )
         .await;
 
         Ok(())
     }
 
+    /// 取得清單內各股票在 `date` 之前的歷史填息統計。
+    ///
+    /// 統計只是提醒的附加資訊，讀取失敗時記錄錯誤並回傳空表，不影響提醒發送。
+    async fn fetch_gap_fill_summaries(
+        date: chrono::NaiveDate,
+        stocks_dividend_info: &[StockDividendInfo],
+    ) -> HashMap<String, GapFillSummary> {
+        if stocks_dividend_info.is_empty() {
+            return HashMap::new();
+        }
+
+        let symbols: Vec<String> = stocks_dividend_info
+            .iter()
+            .map(|stock| stock.stock_symbol.clone())
+            .collect();
+        let gap_fills = match PgDividendGapFillRepository::new()
+            .fetch_by_security_codes(&symbols)
+            .await
+        {
+            Ok(gap_fills) => gap_fills,
+            Err(why) => {
+                tracing::error!("Failed to fetch dividend gap fills because {:?}", why);
+                return HashMap::new();
+            }
+        };
+
+        // 排除當次（以及之後）的除息，只統計已經發生過的歷史事件。
+        let mut grouped: HashMap<String, Vec<_>> = HashMap::new();
+        for gap_fill in gap_fills
+            .into_iter()
+            .filter(|gap_fill| gap_fill.ex_dividend_date < date)
+        {
+            grouped
+                .entry(gap_fill.security_code.clone())
+                .or_default()
+                .push(gap_fill);
+        }
+
+        grouped
+            .into_iter()
+            .filter_map(|(symbol, events)| {
+                GapFillSummary::from_events(&events).map(|summary| (symbol, summary))
+            })
+            .collect()
+    }
+
     /// 判斷一筆除權息資料是否屬於 ETF。
     fn is_etf(stock: &StockDividendInfo) -> bool {
         stock.stock_industry_id == Industry::ExchangeTradedFund.serial()
@@ -125,6 +179,7 @@ impl EventDispatcher {
         msg: &mut String,
         title: &str,
         stocks: impl Iterator<Item = &'a StockDividendInfo>,
+        gap_fills: &HashMap<String, GapFillSummary>,
     ) {
         let mut has_rows = false;
         for stock in stocks {
@@ -145,14 +200,36 @@ impl EventDispatcher {
                 text::escape_markdown_v2(stock.cash_dividend_yield.normalize().to_string()),
                 text::escape_markdown_v2(stock.dividend_yield.normalize().to_string())
             );
+            if let Some(summary) = gap_fills.get(&stock.stock_symbol) {
+                let _ = writeln!(
+                    msg,
+                    "        {}",
+                    text::escape_markdown_v2(Self::format_gap_fill_summary(summary))
+                );
+            }
         }
     }
 
+    /// 將歷史填息統計格式化為提醒中的一行文字（未跳脫）。
+    fn format_gap_fill_summary(summary: &GapFillSummary) -> String {
+        let median = summary
+            .median_days_to_fill
+            .map_or_else(|| "-".to_string(), |days| format!("{}天", days.normalize()));
+        format!(
+            "歷史填息率:{}%({}/{}) 填息天數中位數:{}",
+            summary.fill_ratio.normalize(),
+            summary.filled,
+            summary.events,
+            median
+        )
+    }
+
     /// 組出指定日期的市場除權息清單訊息。
     fn build_market_dividend_message(
         date: chrono::NaiveDate,
         title: &str,
         stocks_dividend_info: &[StockDividendInfo],
+        gap_fills: &HashMap<String, GapFillSummary>,
     ) -> String {
         let mut msg = String::with_capacity(2048);
         if writeln!(
@@ -169,6 +246,7 @@ impl EventDispatcher {
                 stocks_dividend_info
                     .iter()
                     .filter(|stock| !Self::is_etf(stock)),
+                gap_fills,
             );
             Self::write_market_dividend_rows(
                 &mut msg,
@@ -176,6 +254,7 @@ impl EventDispatcher {
                 stocks_dividend_info
                     .iter()
                     .filter(|stock| Self::is_etf(stock)),
+                gap_fills,
             );
         }
 
@@ -187,12 +266,13 @@ impl EventDispatcher {
         date: chrono::NaiveDate,
         title: &str,
         stocks_dividend_info: &[StockDividendInfo],
+        gap_fills: &HashMap<String, GapFillSummary>,
     ) {
         if stocks_dividend_info.is_empty() {
             return;
         }
 
-        let msg = Self::build_market_dividend_message(date, title, stocks_dividend_info);
+        let msg = Self::build_market_dividend_message(date, title, stocks_dividend_info, gap_fills);
         alert::send_message(&msg).await;
     }
 
@@ -495,6 +575,7 @@ mod tests {
             today,
             "進行除權息的股票與 ETF 如下︰",
             &stocks,
+            &HashMap::new(),
         );
 
         let stock_section = msg.find("股票︰").unwrap();
@@ -511,4 +592,44 @@ mod tests {
         assert!(etf_section < high_yield_etf);
         assert!(high_yield_etf < low_yield_etf);
     }
+
+    /// 有歷史填息紀錄的股票在清單行下方附上填息率與填息天數中位數。
+    #[test]
+    fn test_market_dividend_message_appends_gap_fill_summary() {
+        use chrono::NaiveDate;
+        use rust_decimal_macros::dec;
+
+        let today = NaiveDate::from_ymd_opt(2026, 7, 1).unwrap();
+        let stocks = vec![StockDividendInfo {
+            stock_symbol: "2330".to_string(),
+            name: "台積電".to_string(),
+            stock_industry_id: crate::core::declare::Industry::Semiconductor.serial(),
+            cash_dividend: dec!(5),
+            stock_dividend: Decimal::ZERO,
+            sum: dec!(5),
+            closing_price: dec!(1000),
+            dividend_yield: dec!(0.5),
+            cash_dividend_yield: dec!(0.5),
+            is_cash_ex_dividend_on_date: true,
+            is_stock_ex_dividend_on_date: false,
+        }];
+        let gap_fills = HashMap::from([(
+            "2330".to_string(),
+            GapFillSummary {
+                events: 8,
+                filled: 7,
+                fill_ratio: dec!(87.50),
+                median_days_to_fill: Some(dec!(3.5)),
+            },
+        )]);
+
+        let msg = EventDispatcher::build_market_dividend_message(
+            today,
+            "進行除權息的股票與 ETF 如下︰",
+            &stocks,
+            &gap_fills,
+        );
+
+        assert!(msg.contains("歷史填息率:87\\.5%\\(7/8\\) 填息天數中位數:3\\.5天"));
+    }
 }
@@ -1,5 +1,9 @@
 /// 領域事件處理器與派發器
 pub mod handlers;
+/// 領域事件日誌重播
+pub mod replay;
+/// 證券主檔對外同步佇列與全量比對
+pub mod stock_sync;
 /// 台股事件
 pub mod taiwan_stock;
 /// 追踪 ex.即時股價是否達到高低標
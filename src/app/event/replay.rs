@@ -0,0 +1,166 @@
+//! # 領域事件重播
+//!
+//! 依 [`EventQuery`] 逐頁讀出事件日誌，依序號順序交給處理器，用於重建衍生狀態
+//! 或追查某則通知為何發出。處理器可以是自訂函式（例如重算某張表）、只記錄事件的
+//! [`log_handler`]，或透過 [`dispatcher_handler`] 交回派發器重新觸發生產環境的副作用。
+//!
+//! 維運入口為 manual backfill 的事件重播 job（`POST /api/manual-backfill/event-replay`
+//! 與 gRPC `StartEventReplay`）：預設只記錄，request 明確設定 `redispatch` 時才交回派發器。
+
+use anyhow::Result;
+
+use crate::app::event::handlers::{EventDispatcher, EventHandlerFn};
+use crate::domain::event_log::{EventLogRepository, EventQuery};
+
+/// 重播結果摘要。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReplayReport {
+    /// 交給處理器的事件數。
+    pub replayed: usize,
+    /// 處理器回傳錯誤的事件數。
+    pub failed: usize,
+    /// 最後一個重播事件的序號；中斷後可作為 `after_sequence` 接續。
+    pub last_sequence: Option<i64>,
+}
+
+/// 將符合條件的事件依序號順序逐一交給處理器。
+///
+/// `query.limit` 為每頁筆數，會持續翻頁直到沒有更多事件。單一事件處理失敗只記錄並計數，
+/// 不中斷重播；讀取日誌失敗則直接回傳錯誤。
+pub async fn replay(
+    repo: &dyn EventLogRepository,
+    query: &EventQuery,
+    handler: &EventHandlerFn,
+) -> Result<ReplayReport> {
+    let mut report = ReplayReport::default();
+    let mut page_query = query.clone();
+
+    loop {
+        let page = repo.query(&page_query).await?;
+        let page_len = page.len();
+
+        for stored in page {
+            report.replayed += 1;
+            report.last_sequence = Some(stored.sequence);
+            if let Err(why) = handler(stored.event).await {
+                report.failed += 1;
+                tracing::error!("重播領域事件 #{} 失敗: {:?}", stored.sequence, why);
+            }
+        }
+
+        match report.last_sequence {
+            Some(sequence) if page_len as i64 >= query.limit && query.limit > 0 => {
+                page_query = query.next_page(sequence);
+            }
+            _ => break,
+        }
+    }
+
+    Ok(report)
+}
+
+/// 只把事件寫進 log、不觸發任何副作用的處理器，用於追查某則通知為何發出。
+pub fn log_handler() -> EventHandlerFn {
+    Box::new(|event| {
+        Box::pin(async move {
+            tracing::info!("重播領域事件（僅記錄）: {:?}", event);
+            Ok(())
+        })
+    })
+}
+
+/// 將重播的事件送回派發器的處理器，觸發與原派發相同的副作用（通知、推送等）。
+///
+/// 事件透過 [`EventDispatcher::redispatch_async`] 送出，不會再寫入事件日誌或對外發布；
+/// 實際處理在背景非同步進行，因此處理失敗不會反映在 [`ReplayReport::failed`]。
+pub fn dispatcher_handler(dispatcher: &'static EventDispatcher) -> EventHandlerFn {
+    Box::new(move |event| {
+        Box::pin(async move {
+            dispatcher.redispatch_async(vec![event]).await;
+            Ok(())
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::{Duration, Local};
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::domain::event_log::StoredEvent;
+    use crate::domain::event_log::test_double::InMemoryEventLog;
+    use crate::domain::events::DomainEvent;
+
+    fn nav_event(sequence: i64, symbol: &str) -> StoredEvent {
+        let occurred_at = Local::now() - Duration::minutes(10 - sequence);
+        StoredEvent {
+            sequence,
+            recorded_at: occurred_at,
+            event: DomainEvent::NetAssetValueUpdated {
+                symbol: symbol.to_string(),
+                old_nav: rust_decimal::Decimal::from(sequence),
+                new_nav: rust_decimal::Decimal::from(sequence + 1),
+                occurred_at,
+            },
+        }
+    }
+
+    /// 跨頁依序號重播符合條件的事件，處理失敗只計數不中斷。
+    #[tokio::test]
+    async fn replay_pages_in_sequence_and_counts_failures() {
+        let repo = InMemoryEventLog::with_events(
+            (1..=6)
+                .map(|sequence| nav_event(sequence, if sequence == 3 { "2317" } else { "2330" }))
+                .collect(),
+        );
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let handler: EventHandlerFn = {
+            let seen = seen.clone();
+            Box::new(move |event| {
+                let seen = seen.clone();
+                Box::pin(async move {
+                    let DomainEvent::NetAssetValueUpdated { old_nav, .. } = event else {
+                        unreachable!()
+                    };
+                    seen.lock().await.push(old_nav);
+                    if old_nav == rust_decimal::Decimal::from(4) {
+                        anyhow::bail!("handler failed");
+                    }
+                    Ok(())
+                })
+            })
+        };
+
+        let report = replay(
+            &repo,
+            &EventQuery {
+                symbol: Some("2330".to_string()),
+                limit: 2,
+                ..Default::default()
+            },
+            &handler,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            report,
+            ReplayReport {
+                replayed: 5,
+                failed: 1,
+                last_sequence: Some(6),
+            }
+        );
+        let seen: Vec<i64> = seen
+            .lock()
+            .await
+            .iter()
+            .map(|nav| nav.mantissa() as i64)
+            .collect();
+        assert_eq!(seen, vec![1, 2, 4, 5, 6]);
+        assert_eq!(repo.appended().len(), 6, "重播不應寫入事件日誌");
+    }
+}
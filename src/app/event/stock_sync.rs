@@ -0,0 +1,415 @@
+//! # 證券主檔對外同步
+//!
+//! 證券主檔異動（新增、名稱市場產業變更、淨值更新、下市）先排入持久化的同步佇列
+//! （[`StockSyncQueueRepository`]），再由背景工作以 `SyncStocks` 串流批次送給外部服務
+//! （目前為 Go 服務）。送出失敗的證券依失敗次數延後重試，不會因為一次連線失敗就遺失；
+//! 另有每日全量比對，把兩邊仍不一致的證券重新排入。
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use tokio::sync::Mutex;
+
+use crate::{
+    app::ports::{self, StockSyncGateway},
+    domain::registry::{
+        repository::StockSyncQueueRepository,
+        sync::{RegistryDiff, StockSyncTask, SyncReason, diff_registry},
+    },
+    infra::database::repository::stock_sync_queue::PgStockSyncQueueRepository,
+};
+
+/// 每批送出的證券數上限。
+const BATCH_SIZE: i64 = 500;
+
+/// 同一時間只允許一個送出流程，避免同一批證券被重複送出。
+static DRAIN_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// 一次送出流程的結果摘要。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DrainReport {
+    /// 同步成功的證券數。
+    pub synced: usize,
+    /// 外部服務拒絕寫入、已延後重試的證券數。
+    pub rejected: usize,
+    /// 整批送出失敗、已延後重試的證券數。
+    pub failed: usize,
+    /// 主檔已不存在而移出佇列的證券數。
+    pub dropped: usize,
+}
+
+/// 全量比對的結果摘要。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReconcileReport {
+    /// 比對結果。
+    pub diff: RegistryDiff,
+    /// 重新排入佇列的證券數。
+    pub enqueued: u64,
+    /// 比對後送出佇列的結果。
+    pub drain: DrainReport,
+}
+
+/// 排入待同步的證券，並在背景立即嘗試送出。
+///
+/// 排入失敗只記錄錯誤；遺漏的差異會由每日全量比對補回。
+pub async fn enqueue(stock_symbols: Vec<String>, reason: SyncReason) {
+    if stock_symbols.is_empty() {
+        return;
+    }
+    if let Err(why) = PgStockSyncQueueRepository::new()
+        .enqueue(&stock_symbols, reason)
+        .await
+    {
+        tracing::error!(
+            "Failed to enqueue stock sync {:?} ({}) because {:?}",
+            stock_symbols,
+            reason,
+            why
+        );
+        return;
+    }
+
+    tokio::spawn(async {
+        if let Err(why) = execute().await {
+            tracing::error!("Failed to drain stock sync queue because {:?}", why);
+        }
+    });
+}
+
+/// 送出佇列中已到重試時間的證券。
+///
+/// gateway 尚未註冊或已有其他送出流程進行中時直接返回，佇列內容留待下次送出。
+pub async fn execute() -> Result<()> {
+    let Some(gateway) = ports::stock_sync_gateway() else {
+        return Ok(());
+    };
+    let Ok(_guard) = DRAIN_LOCK.try_lock() else {
+        return Ok(());
+    };
+
+    let report = drain(&PgStockSyncQueueRepository::new(), gateway.as_ref()).await?;
+    if report != DrainReport::default() {
+        tracing::info!("stock sync queue drained: {:?}", report);
+    }
+
+    Ok(())
+}
+
+/// 比對本地證券主檔與外部服務，將不一致的證券重新排入並送出。
+pub async fn reconcile() -> Result<()> {
+    let Some(gateway) = ports::stock_sync_gateway() else {
+        tracing::warn!("stock sync gateway not registered; reconcile skipped");
+        return Ok(());
+    };
+    let _guard = DRAIN_LOCK.lock().await;
+
+    let report = reconcile_with(&PgStockSyncQueueRepository::new(), gateway.as_ref()).await?;
+    tracing::info!(
+        "stock registry reconciled: missing_remote={} changed={} unknown_remote={} {:?}",
+        report.diff.missing_remote.len(),
+        report.diff.changed.len(),
+        report.diff.unknown_remote.len(),
+        report.drain
+    );
+
+    Ok(())
+}
+
+/// 逐批送出到期的證券，直到佇列沒有到期項目或外部服務無法連線。
+pub(crate) async fn drain(
+    repo: &dyn StockSyncQueueRepository,
+    gateway: &dyn StockSyncGateway,
+) -> Result<DrainReport> {
+    let mut report = DrainReport::default();
+
+    loop {
+        let tasks = repo.fetch_due(BATCH_SIZE).await?;
+        if tasks.is_empty() {
+            break;
+        }
+
+        let (tasks, missing): (Vec<StockSyncTask>, Vec<StockSyncTask>) =
+            tasks.into_iter().partition(|task| task.record.is_some());
+        report.dropped += repo.complete(&missing).await? as usize;
+        if tasks.is_empty() {
+            continue;
+        }
+
+        let records = tasks
+            .iter()
+            .filter_map(|task| task.record.clone())
+            .collect();
+        let rejections = match gateway.sync_stocks(records).await {
+            Ok(rejections) => rejections,
+            Err(why) => {
+                // 外部服務無法連線時整批延後，本輪不再嘗試後續批次。
+                repo.fail(&tasks, &format!("{why:#}")).await?;
+                report.failed += tasks.len();
+                break;
+            }
+        };
+
+        let reasons: HashMap<&str, &str> = rejections
+            .iter()
+            .map(|rejection| (rejection.stock_symbol.as_str(), rejection.reason.as_str()))
+            .collect();
+        let (rejected, synced): (Vec<StockSyncTask>, Vec<StockSyncTask>) = tasks
+            .into_iter()
+            .partition(|task| reasons.contains_key(task.stock_symbol.as_str()));
+
+        repo.complete(&synced).await?;
+        report.synced += synced.len();
+        for task in &rejected {
+            let reason = reasons[task.stock_symbol.as_str()];
+            tracing::warn!("stock sync rejected: {} {}", task.stock_symbol, reason);
+            repo.fail(std::slice::from_ref(task), reason).await?;
+        }
+        report.rejected += rejected.len();
+
+        if synced.is_empty() {
+            break;
+        }
+    }
+
+    Ok(report)
+}
+
+/// 全量比對並把差異排入佇列後送出。
+pub(crate) async fn reconcile_with(
+    repo: &dyn StockSyncQueueRepository,
+    gateway: &dyn StockSyncGateway,
+) -> Result<ReconcileReport> {
+    let local = repo.fetch_sync_records().await?;
+    let remote = gateway.fetch_remote_stocks().await?;
+    let diff = diff_registry(&local, &remote);
+
+    if !diff.unknown_remote.is_empty() {
+        tracing::warn!(
+            "stocks only known by the remote service: {:?}",
+            diff.unknown_remote
+        );
+    }
+    let enqueued = repo.enqueue(&diff.to_sync(), SyncReason::Reconcile).await?;
+    let drain = drain(repo, gateway).await?;
+
+    Ok(ReconcileReport {
+        diff,
+        enqueued,
+        drain,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use rust_decimal_macros::dec;
+    use std::sync::Mutex as StdMutex;
+
+    use super::*;
+    use crate::domain::registry::sync::{StockSyncRecord, SyncRejection};
+
+    fn record(symbol: &str) -> StockSyncRecord {
+        StockSyncRecord {
+            stock_symbol: symbol.to_string(),
+            name: format!("測試{symbol}"),
+            market_id: 2,
+            industry_id: 24,
+            net_asset_value_per_share: dec!(10),
+            suspend_listing: false,
+        }
+    }
+
+    /// 以記憶體保存佇列的假倉儲；失敗的證券移出到期清單以模擬延後重試。
+    #[derive(Default)]
+    struct FakeQueue {
+        stocks: Vec<StockSyncRecord>,
+        due: StdMutex<Vec<StockSyncTask>>,
+        failed: StdMutex<Vec<(String, String)>>,
+    }
+
+    impl FakeQueue {
+        fn with_due(symbols: &[(&str, bool)]) -> Self {
+            let due = symbols
+                .iter()
+                .enumerate()
+                .map(|(serial, (symbol, exists))| StockSyncTask {
+                    serial: serial as i64,
+                    stock_symbol: symbol.to_string(),
+                    reason: SyncReason::Registered,
+                    attempts: 0,
+                    version: 1,
+                    record: exists.then(|| record(symbol)),
+                })
+                .collect();
+            FakeQueue {
+                due: StdMutex::new(due),
+                ..Default::default()
+            }
+        }
+
+        fn remove(&self, tasks: &[StockSyncTask]) -> u64 {
+            let mut due = self.due.lock().unwrap();
+            let before = due.len();
+            due.retain(|task| !tasks.iter().any(|done| done.serial == task.serial));
+            (before - due.len()) as u64
+        }
+    }
+
+    #[async_trait]
+    impl StockSyncQueueRepository for FakeQueue {
+        async fn enqueue(&self, stock_symbols: &[String], reason: SyncReason) -> Result<u64> {
+            let mut due = self.due.lock().unwrap();
+            for symbol in stock_symbols {
+                let serial = due.len() as i64 + 100;
+                due.push(StockSyncTask {
+                    serial,
+                    stock_symbol: symbol.clone(),
+                    reason,
+                    attempts: 0,
+                    version: 1,
+                    record: self
+                        .stocks
+                        .iter()
+                        .find(|stock| &stock.stock_symbol == symbol)
+                        .cloned(),
+                });
+            }
+            Ok(stock_symbols.len() as u64)
+        }
+
+        async fn fetch_due(&self, limit: i64) -> Result<Vec<StockSyncTask>> {
+            Ok(self
+                .due
+                .lock()
+                .unwrap()
+                .iter()
+                .take(limit as usize)
+                .cloned()
+                .collect())
+        }
+
+        async fn complete(&self, tasks: &[StockSyncTask]) -> Result<u64> {
+            Ok(self.remove(tasks))
+        }
+
+        async fn fail(&self, tasks: &[StockSyncTask], error: &str) -> Result<()> {
+            self.remove(tasks);
+            self.failed.lock().unwrap().extend(
+                tasks
+                    .iter()
+                    .map(|task| (task.stock_symbol.clone(), error.to_string())),
+            );
+            Ok(())
+        }
+
+        async fn fetch_sync_records(&self) -> Result<Vec<StockSyncRecord>> {
+            Ok(self.stocks.clone())
+        }
+    }
+
+    /// 記錄收到的批次並依設定拒絕或整批失敗的假 gateway。
+    #[derive(Default)]
+    struct FakeGateway {
+        reject: Vec<&'static str>,
+        unavailable: bool,
+        remote: Vec<StockSyncRecord>,
+        received: StdMutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl StockSyncGateway for FakeGateway {
+        async fn sync_stocks(&self, records: Vec<StockSyncRecord>) -> Result<Vec<SyncRejection>> {
+            if self.unavailable {
+                anyhow::bail!("connection refused");
+            }
+            self.received
+                .lock()
+                .unwrap()
+                .extend(records.iter().map(|record| record.stock_symbol.clone()));
+            Ok(records
+                .iter()
+                .filter(|record| self.reject.contains(&record.stock_symbol.as_str()))
+                .map(|record| SyncRejection {
+                    stock_symbol: record.stock_symbol.clone(),
+                    reason: "invalid industry".to_string(),
+                })
+                .collect())
+        }
+
+        async fn fetch_remote_stocks(&self) -> Result<Vec<StockSyncRecord>> {
+            Ok(self.remote.clone())
+        }
+    }
+
+    /// 成功的移出佇列、被拒絕的延後重試、主檔已刪除的直接移除。
+    #[tokio::test]
+    async fn drain_completes_rejects_and_drops() {
+        let queue = FakeQueue::with_due(&[("2330", true), ("2317", true), ("9999", false)]);
+        let gateway = FakeGateway {
+            reject: vec!["2317"],
+            ..Default::default()
+        };
+
+        let report = drain(&queue, &gateway).await.unwrap();
+
+        assert_eq!(
+            report,
+            DrainReport {
+                synced: 1,
+                rejected: 1,
+                failed: 0,
+                dropped: 1,
+            }
+        );
+        assert_eq!(*gateway.received.lock().unwrap(), vec!["2330", "2317"]);
+        assert_eq!(
+            *queue.failed.lock().unwrap(),
+            vec![("2317".to_string(), "invalid industry".to_string())]
+        );
+        assert!(queue.due.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn drain_defers_whole_batch_when_remote_unavailable() {
+        let queue = FakeQueue::with_due(&[("2330", true), ("2317", true)]);
+        let gateway = FakeGateway {
+            unavailable: true,
+            ..Default::default()
+        };
+
+        let report = drain(&queue, &gateway).await.unwrap();
+
+        assert_eq!(report.failed, 2);
+        assert_eq!(report.synced, 0);
+        let failed = queue.failed.lock().unwrap();
+        assert_eq!(failed.len(), 2);
+        assert!(failed[0].1.contains("connection refused"));
+    }
+
+    /// 全量比對只重送遠端缺少或不一致的證券。
+    #[tokio::test]
+    async fn reconcile_enqueues_and_sends_differences() {
+        let queue = FakeQueue {
+            stocks: vec![record("2330"), record("2317"), record("0050")],
+            ..Default::default()
+        };
+        let gateway = FakeGateway {
+            remote: vec![
+                record("2330"),
+                StockSyncRecord {
+                    name: "舊名稱".to_string(),
+                    ..record("2317")
+                },
+                record("9999"),
+            ],
+            ..Default::default()
+        };
+
+        let report = reconcile_with(&queue, &gateway).await.unwrap();
+
+        assert_eq!(report.enqueued, 2);
+        assert_eq!(report.diff.unknown_remote, vec!["9999"]);
+        assert_eq!(report.drain.synced, 2);
+        assert_eq!(*gateway.received.lock().unwrap(), vec!["0050", "2317"]);
+    }
+}
@@ -84,12 +84,49 @@ pub(crate) async fn aggregate(date: NaiveDate) -> Result<()> {
     yield_rank_repo.rebuild_by_date(date).await?;
     tracing::info!("重建 yield_rank 表內的數據結束");
 
+    // 歷史估值區間只供查詢與選股，失敗不中斷收盤匯總與市值通知。
+    match calculation::valuation_band::calculate(date).await {
+        Ok((yields, bands)) => tracing::info!("重建歷史估值區間結束:{}/{}", yields, bands),
+        Err(why) => tracing::error!("Failed to calculate valuation bands because {:?}", why),
+    }
+
+    match calculation::valuation_model::calculate(date).await {
+        Ok(count) => tracing::info!("計算估價模型結束:{}", count),
+        Err(why) => tracing::error!("Failed to calculate valuation models because {:?}", why),
+    }
+
     calculation::money_history::calculate_money_history(date).await?;
     tracing::info!("計算帳戶內市值結束");
 
+    // 廣度指標以上一步寫入的 daily_stock_price_stats 遞推，失敗不中斷收盤匯總。
+    match calculation::market_breadth::calculate(date).await {
+        Ok(count) => tracing::info!("更新市場廣度指標結束:{}", count),
+        Err(why) => tracing::error!("Failed to calculate market breadth because {:?}", why),
+    }
+
+    // 產業指標只供分析查詢，失敗不中斷收盤匯總。
+    match calculation::industry_metric::calculate(date).await {
+        Ok(count) => tracing::info!("更新產業指標結束:{}", count),
+        Err(why) => tracing::error!("Failed to calculate industry metrics because {:?}", why),
+    }
+
+    // 填息統計只影響查詢與提醒的附加資訊，失敗不中斷收盤匯總與市值通知。
+    match calculation::dividend_gap_fill::calculate(date).await {
+        Ok(count) => tracing::info!("更新填息追蹤結束:{}", count),
+        Err(why) => tracing::error!("Failed to calculate dividend gap fill because {:?}", why),
+    }
+
     // 清除記憶與Redis內所有的快取
     TTL.clear();
 
+    // 通知訂閱端本日收盤資料與衍生計算已完成
+    crate::app::ports::publish_event(crate::app::ports::OutboundEvent::DataReady {
+        dataset: crate::app::ports::ReadyDataset::DailyQuotes,
+        date,
+        records: daily_quote_count as u64,
+        occurred_at: chrono::Local::now(),
+    });
+
     // 派發領域事件以非同步處理本日與前一個交易日的市值變化通知
     let dispatcher = crate::app::event::get_global_dispatcher();
     dispatcher
@@ -0,0 +1,237 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use anyhow::Result;
+
+// 通知走 core::alert（port），跳脫工具走 core::util::text——
+// app 層不 import interfaces::bot，維持「外層依賴內層」的合法方向。
+use crate::{
+    app::backfill::acl::MaterialAnnouncementAclMapper,
+    core::{alert, util::text},
+    domain::{
+        disclosure::{MaterialAnnouncement, MaterialAnnouncementRepository},
+        performance::{CorporateActionProposal, CorporateActionProposalRepository},
+        portfolio::repository::PortfolioRepository,
+        trace::repository::TraceRepository,
+    },
+    infra::{
+        crawler::mops,
+        database::repository::{
+            corporate_action::PgCorporateActionProposalRepository,
+            disclosure::PgMaterialAnnouncementRepository, portfolio::PgPortfolioRepository,
+            trace::PgTraceRepository,
+        },
+    },
+};
+
+/// 公司行動建議的來源代碼。
+const PROPOSAL_SOURCE: &str = "mops";
+
+/// 輪詢重大訊息，寫入資料庫並通知持股與追蹤清單內的新訊息。
+///
+/// 排程於交易日白天反覆執行；資料庫去重後只處理這一輪才出現的訊息，
+/// 分類為減資或面額變更者另外寫入待確認的公司行動建議。
+pub async fn execute() -> Result<()> {
+    let dtos = mops::material_information::visit().await?;
+    if dtos.is_empty() {
+        return Ok(());
+    }
+
+    let announcements: Vec<MaterialAnnouncement> = dtos
+        .iter()
+        .map(MaterialAnnouncementAclMapper::from_dto)
+        .collect();
+    let fresh = PgMaterialAnnouncementRepository::new()
+        .insert_new(&announcements)
+        .await?;
+    if fresh.is_empty() {
+        return Ok(());
+    }
+    tracing::info!("新增重大訊息 {} 則", fresh.len());
+
+    let proposed = propose_corporate_actions(&fresh).await;
+
+    let symbols: Vec<String> = fresh
+        .iter()
+        .map(|announcement| announcement.stock_symbol.clone())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let held: HashSet<String> = PgPortfolioRepository::new()
+        .fetch_active_holdings(Some(symbols))
+        .await?
+        .into_iter()
+        .map(|holding| holding.security_code)
+        .collect();
+    let traced: HashSet<String> = PgTraceRepository::new()
+        .fetch_all()
+        .await?
+        .into_iter()
+        .map(|trace| trace.stock_symbol)
+        .collect();
+
+    if let Some(msg) = build_alert_message(&fresh, &held, &traced, &proposed) {
+        alert::send_message(&text::escape_markdown_v2(msg)).await;
+    }
+
+    Ok(())
+}
+
+/// 為減資與面額變更訊息建立公司行動建議，回傳成功建立（或已存在）建議的代號。
+///
+/// 單筆寫入失敗只記錄 warning，不影響通知。
+async fn propose_corporate_actions(announcements: &[MaterialAnnouncement]) -> HashSet<String> {
+    let repo = PgCorporateActionProposalRepository::new();
+    let mut proposed = HashSet::new();
+
+    for announcement in announcements
+        .iter()
+        .filter(|announcement| announcement.category.suggests_corporate_action())
+    {
+        let proposal = to_proposal(announcement);
+        match repo.propose(&proposal).await {
+            Ok(_) => {
+                proposed.insert(announcement.stock_symbol.clone());
+            }
+            Err(why) => tracing::warn!(
+                "建立公司行動建議失敗 {} {}: {why:#}",
+                announcement.stock_symbol,
+                announcement.subject
+            ),
+        }
+    }
+
+    proposed
+}
+
+/// 將重大訊息轉為公司行動建議；生效日與比例留待人工確認時補齊。
+fn to_proposal(announcement: &MaterialAnnouncement) -> CorporateActionProposal {
+    CorporateActionProposal {
+        stock_symbol: announcement.stock_symbol.clone(),
+        source: PROPOSAL_SOURCE.to_string(),
+        reference: truncate_chars(
+            &format!("{} {}", announcement.announced_at, announcement.subject),
+            512,
+        ),
+        action_type: announcement.category.code().to_string(),
+        effective_date: None,
+        share_ratio: None,
+        note: truncate_chars(&announcement.subject, 255),
+    }
+}
+
+/// 依字元數截斷字串，避免超出資料表欄位長度。
+fn truncate_chars(value: &str, max_chars: usize) -> String {
+    value.chars().take(max_chars).collect()
+}
+
+/// 組成持股與追蹤清單的重大訊息通知（未跳脫）；沒有相關訊息時回傳 `None`。
+fn build_alert_message(
+    announcements: &[MaterialAnnouncement],
+    held: &HashSet<String>,
+    traced: &HashSet<String>,
+    proposed: &HashSet<String>,
+) -> Option<String> {
+    let mut msg = String::new();
+
+    for announcement in announcements {
+        let symbol = &announcement.stock_symbol;
+        let watch = match (held.contains(symbol), traced.contains(symbol)) {
+            (true, _) => "持股",
+            (false, true) => "追蹤",
+            (false, false) => continue,
+        };
+        let _ = write!(
+            &mut msg,
+            "[{watch}] {symbol} {name} 〔{label}〕 {time} {subject}",
+            name = announcement.company_name,
+            label = announcement.category.label(),
+            time = announcement.announced_at.format("%m/%d %H:%M"),
+            subject = announcement.subject,
+        );
+        if announcement.category.suggests_corporate_action() && proposed.contains(symbol) {
+            msg.push_str("（已建立公司行動建議，請確認）");
+        }
+        msg.push('\n');
+    }
+
+    if msg.is_empty() {
+        return None;
+    }
+
+    Some(format!("重大訊息︰\n{msg}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::domain::disclosure::AnnouncementCategory;
+
+    fn announcement(symbol: &str, subject: &str) -> MaterialAnnouncement {
+        MaterialAnnouncement {
+            stock_symbol: symbol.to_string(),
+            company_name: format!("公司{symbol}"),
+            announced_at: NaiveDate::from_ymd_opt(2026, 4, 24)
+                .unwrap()
+                .and_hms_opt(14, 5, 0)
+                .unwrap(),
+            subject: subject.to_string(),
+            clause: String::new(),
+            fact_date: None,
+            description: String::new(),
+            category: AnnouncementCategory::classify(subject),
+        }
+    }
+
+    /// 只通知持股或追蹤清單內的代號，持股優先標示，減資訊息附上建議提示。
+    #[test]
+    fn build_alert_message_only_includes_watched_symbols() {
+        let announcements = vec![
+            announcement("2330", "公告本公司董事會決議現金減資"),
+            announcement("2317", "公告總經理異動"),
+            announcement("1101", "公告取得資產"),
+        ];
+        let held = HashSet::from(["2330".to_string()]);
+        let traced = HashSet::from(["2330".to_string(), "2317".to_string()]);
+        let proposed = HashSet::from(["2330".to_string()]);
+
+        let msg = build_alert_message(&announcements, &held, &traced, &proposed).unwrap();
+
+        assert_eq!(
+            msg,
+            "重大訊息︰\n\
+             [持股] 2330 公司2330 〔減資〕 04/24 14:05 公告本公司董事會決議現金減資（已建立公司行動建議，請確認）\n\
+             [追蹤] 2317 公司2317 〔人事異動〕 04/24 14:05 公告總經理異動\n"
+        );
+        assert!(
+            build_alert_message(&announcements, &HashSet::new(), &HashSet::new(), &proposed)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn to_proposal_leaves_ratio_for_review() {
+        let proposal = to_proposal(&announcement("2330", "公告本公司變更每股面額"));
+
+        assert_eq!(proposal.source, "mops");
+        assert_eq!(proposal.action_type, "split");
+        assert_eq!(
+            proposal.reference,
+            "2026-04-24 14:05:00 公告本公司變更每股面額"
+        );
+        assert_eq!(proposal.share_ratio, None);
+        assert_eq!(proposal.effective_date, None);
+    }
+
+    /// 手動輪詢重大訊息。
+    #[tokio::test]
+    #[ignore]
+    async fn test_execute() {
+        dotenvy::dotenv().ok();
+        if let Err(why) = execute().await {
+            tracing::debug!("Failed to execute because {:?}", why);
+        }
+    }
+}
@@ -4,12 +4,18 @@ pub mod annual_eps;
 pub mod closing;
 /// 除息日的事件
 pub mod ex_dividend;
+/// 重大訊息輪詢與持股通知
+pub mod material_information;
 /// 股利發放日的事件
 pub mod payable_date;
 /// 公開申購公告
 pub mod public;
 /// 財務季報
 pub mod quarter_eps;
+/// 月營收公布後的營收驚喜摘要
+pub mod revenue_surprise;
+/// 注意股與處置股的進出通知
+pub mod trading_surveillance;
 
 use rust_decimal::Decimal;
 
@@ -1,112 +1,261 @@
 use std::fmt::Write;
 
 use anyhow::Result;
-use chrono::Local;
-use rust_decimal::{Decimal, prelude::ToPrimitive};
-use rust_decimal_macros::dec;
+use chrono::{Local, NaiveDate, TimeDelta};
+use rust_decimal::Decimal;
 
+use super::{format_decimal_with_commas, member_label};
 use crate::domain::quote::repository::QuoteRepository;
+use crate::domain::subscription::{
+    PublicSubscription, SubscriptionEntry, SubscriptionReminder, SubscriptionRepository,
+    rank_by_expected_profit,
+};
 // 通知走 core::alert（port），跳脫工具走 core::util::text——
 // app 層不 import interfaces::bot，維持「外層依賴內層」的合法方向。
 use crate::{
+    app::backfill::acl::PublicSubscriptionAclMapper,
     core::alert,
     core::declare,
-    core::util::{convert::FromValue, map::Keyable, text},
-    infra::database::repository::quote::PgQuoteRepository,
+    core::util::text,
+    infra::database::repository::{
+        quote::PgQuoteRepository, subscription::PgSubscriptionRepository,
+    },
 };
 
-/// 提醒目前可公開申購的股票。
+/// 今日需要提醒的一檔公開申購。
+struct Reminder {
+    subscription: PublicSubscription,
+    kind: SubscriptionReminder,
+    /// 最新收盤價；尚未掛牌的初上市（櫃）股票沒有報價。
+    market_price: Option<Decimal>,
+    /// 已登記參與這檔申購的成員。
+    members: Vec<i64>,
+}
+
+/// 提醒公開申購：申購期間內依中籤一張的預期獲利排序提醒一次，截止日與
+/// 抽籤日再各提醒一次。
+///
+/// 每次執行會先把證交所公告寫入資料庫，提醒則以資料庫為準，讓已從公告
+/// 頁面下架、但還沒抽籤的申購也能在抽籤日提醒。
 pub async fn execute() -> Result<()> {
-    let ps = crate::infra::crawler::twse::public::visit().await?;
-    let mut msg = String::with_capacity(2048);
     let now = Local::now().date_naive();
+    let repo = PgSubscriptionRepository::new();
+    let subscriptions: Vec<PublicSubscription> = crate::infra::crawler::twse::public::visit()
+        .await?
+        .iter()
+        .filter_map(PublicSubscriptionAclMapper::from_public)
+        .collect();
+    repo.upsert(&subscriptions).await?;
 
-    for stock in ps {
-        if stock.market == "中央登錄公債" {
+    let entries = repo.fetch_entries_since(now - TimeDelta::days(30)).await?;
+    let quote_repo = PgQuoteRepository::new();
+    let mut reminders = Vec::new();
+
+    for subscription in repo.fetch_since(now).await? {
+        let Some(kind) = subscription.reminder_on(now) else {
             continue;
-        }
+        };
 
-        if let (Some(start), Some(end)) = (stock.offering_start_date, stock.offering_end_date)
-            && now >= start
-            && now <= end
-        {
-            let cache_key = stock.key_with_prefix();
-            let is_jump = crate::infra::nosql::redis::CLIENT
+        if kind == SubscriptionReminder::Open {
+            // 申購期間只提醒一次，快取到截止日為止。
+            let cache_key = format!(
+                "Public:{}:{}",
+                subscription.stock_symbol, subscription.offering_start_date
+            );
+            if crate::infra::nosql::redis::CLIENT
                 .get_bool(&cache_key)
-                .await?;
-
-            if is_jump {
+                .await?
+            {
                 continue;
             }
-
-            // 實例化報價倉儲，並獲取個股最新收盤價（封裝雙層快取策略）
-            let quote_repo = PgQuoteRepository::new();
-            let stock_last_price = quote_repo.fetch_last_quote(&stock.stock_symbol).await?;
-            let last_price = match stock_last_price {
-                None => String::from(" - "),
-                Some(last_quote) => match last_quote.closing_price.to_f64() {
-                    None => String::from(" - "),
-                    Some(price) => price.to_string(),
-                },
-            };
-            let last_price_dec = last_price.get_decimal(None);
-            let offering_price = stock.offering_price.unwrap_or(Decimal::ZERO);
-            let price_change = calculate_price_change(offering_price, last_price_dec);
-            let _ = writeln!(
-                &mut msg,
-                "{stock_symbol} {stock_name} 起迄日︰{start}~{end} 承銷價︰{offering_price} 參考價︰{last_price} {price_change}發行市場:{market}",
-                market = stock.market.clone(),
-                stock_symbol = stock.stock_symbol,
-                stock_name = stock.stock_name,
-                start = start,
-                end = end,
-                offering_price = offering_price,
-                last_price = last_price,
-                price_change = price_change,
-            );
-            let mut duration = (end - now).num_seconds() as usize;
-
+            let mut duration = (subscription.offering_end_date - now).num_seconds() as usize;
             if duration == 0 {
                 duration = declare::ONE_DAYS_IN_SECONDS;
             }
-
             crate::infra::nosql::redis::CLIENT
                 .set(cache_key, true, duration)
                 .await?;
         }
+
+        // 實例化報價倉儲，並獲取個股最新收盤價（封裝雙層快取策略）
+        let market_price = quote_repo
+            .fetch_last_quote(&subscription.stock_symbol)
+            .await?
+            .map(|quote| quote.closing_price)
+            .filter(|price| *price > Decimal::ZERO);
+        let members = members_of(&entries, &subscription);
+        reminders.push(Reminder {
+            subscription,
+            kind,
+            market_price,
+            members,
+        });
     }
 
-    if !msg.is_empty() {
+    if let Some(msg) = build_reminder_message(now, &reminders) {
         // 整段訊息為動態內容（含日期、價格的小數點），送出前統一做 MarkdownV2 跳脫。
-        let to_bot_msg = text::escape_markdown_v2(format!("{now} 可以申購的股票如下︰\n{msg}"));
-        alert::send_message(&to_bot_msg).await;
-        return Ok(());
+        alert::send_message(&text::escape_markdown_v2(msg)).await;
     }
 
     Ok(())
 }
 
-/// 計算參考價與承銷價之間的價差與報酬率。
-fn calculate_price_change(offering_price: Decimal, last_price: Decimal) -> String {
-    if offering_price == Decimal::ZERO || last_price == Decimal::ZERO {
-        return String::from(" - ");
+/// 找出已登記參與指定申購的成員。
+fn members_of(entries: &[SubscriptionEntry], subscription: &PublicSubscription) -> Vec<i64> {
+    entries
+        .iter()
+        .filter(|entry| {
+            entry.stock_symbol == subscription.stock_symbol
+                && entry.offering_start_date == subscription.offering_start_date
+        })
+        .map(|entry| entry.member_id)
+        .collect()
+}
+
+/// 組成公開申購提醒（未跳脫）；沒有需要提醒的申購時回傳 `None`。
+///
+/// 申購期間與截止日兩段都依中籤一張的預期獲利由高到低排序。
+fn build_reminder_message(today: NaiveDate, reminders: &[Reminder]) -> Option<String> {
+    if reminders.is_empty() {
+        return None;
+    }
+
+    let mut msg = format!("{today} 公開申購︰\n");
+    for (kind, title) in [
+        (SubscriptionReminder::Open, "可以申購"),
+        (SubscriptionReminder::Deadline, "今日截止申購"),
+        (SubscriptionReminder::Drawing, "今日抽籤"),
+    ] {
+        let mut items: Vec<(&Reminder, Option<Decimal>)> = reminders
+            .iter()
+            .filter(|reminder| reminder.kind == kind)
+            .map(|reminder| {
+                let profit = reminder
+                    .market_price
+                    .and_then(|price| reminder.subscription.expected_profit_per_lot(price));
+                (reminder, profit)
+            })
+            .collect();
+        if items.is_empty() {
+            continue;
+        }
+        if kind != SubscriptionReminder::Drawing {
+            rank_by_expected_profit(&mut items);
+        }
+        let _ = writeln!(&mut msg, "{title}︰");
+        for (reminder, profit) in items {
+            write_reminder_line(&mut msg, reminder, profit);
+        }
+    }
+
+    Some(msg)
+}
+
+/// 寫入一檔申購的提醒內容。
+fn write_reminder_line(msg: &mut String, reminder: &Reminder, profit: Option<Decimal>) {
+    let subscription = &reminder.subscription;
+    let _ = write!(
+        msg,
+        "{symbol} {name} 起迄日︰{start}~{end}",
+        symbol = subscription.stock_symbol,
+        name = subscription.stock_name,
+        start = subscription.offering_start_date,
+        end = subscription.offering_end_date,
+    );
+    if let Some(drawing_date) = subscription.drawing_date {
+        let _ = write!(msg, " 抽籤日︰{drawing_date}");
+    }
+    let offering_price = subscription
+        .offering_price
+        .map_or_else(|| "-".to_string(), format_decimal_with_commas);
+    let market_price = reminder
+        .market_price
+        .map_or_else(|| "-".to_string(), format_decimal_with_commas);
+    let _ = write!(msg, " 承銷價︰{offering_price} 參考價︰{market_price}");
+    if let Some(price) = reminder.market_price
+        && let (Some(spread), Some(percentage)) = (
+            subscription.spread(price),
+            subscription.spread_percentage(price),
+        )
+    {
+        let _ = write!(
+            msg,
+            " 價差︰{} ({percentage}%)",
+            format_decimal_with_commas(spread)
+        );
+    }
+    if let Some(profit) = profit {
+        let _ = write!(
+            msg,
+            " 中籤一張預期獲利︰{}",
+            format_decimal_with_commas(profit)
+        );
     }
-    //價差 = 參考價 - 承銷價
-    //價差百分比 = 價差 / 參考價 * 100%
-    let gap = last_price - offering_price;
-    let change = (gap / last_price) * dec!(100);
-    format!(
-        "價差︰{gap} ({change}%) ",
-        gap = gap,
-        change = change.round_dp(2)
-    )
+    let _ = write!(msg, " 發行市場︰{}", subscription.market);
+    if !reminder.members.is_empty() {
+        let members: Vec<String> = reminder.members.iter().copied().map(member_label).collect();
+        let _ = write!(msg, " 已申購︰{}", members.join("、"));
+    }
+    msg.push('\n');
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::infra::cache::SHARE;
+    use rust_decimal_macros::dec;
 
     use super::*;
+    use crate::infra::cache::SHARE;
+
+    fn reminder(
+        symbol: &str,
+        kind: SubscriptionReminder,
+        market_price: Option<Decimal>,
+        members: Vec<i64>,
+    ) -> Reminder {
+        Reminder {
+            subscription: PublicSubscription {
+                stock_symbol: symbol.to_string(),
+                stock_name: format!("公司{symbol}"),
+                market: "上市".to_string(),
+                offering_start_date: NaiveDate::from_ymd_opt(2026, 7, 14).unwrap(),
+                offering_end_date: NaiveDate::from_ymd_opt(2026, 7, 16).unwrap(),
+                drawing_date: NaiveDate::from_ymd_opt(2026, 7, 20),
+                offering_price: Some(dec!(50)),
+                issue_date: None,
+            },
+            kind,
+            market_price,
+            members,
+        }
+    }
+
+    /// 申購期間依預期獲利排序，沒有報價者排最後；抽籤日附上已申購的成員。
+    #[test]
+    fn build_reminder_message_ranks_open_subscriptions() {
+        let today = NaiveDate::from_ymd_opt(2026, 7, 15).unwrap();
+        let msg = build_reminder_message(
+            today,
+            &[
+                reminder("1111", SubscriptionReminder::Open, None, vec![]),
+                reminder("2222", SubscriptionReminder::Open, Some(dec!(55)), vec![]),
+                reminder("3333", SubscriptionReminder::Open, Some(dec!(62.5)), vec![]),
+                reminder("4444", SubscriptionReminder::Drawing, None, vec![1, 3]),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            msg,
+            "2026-07-15 公開申購︰\n\
+             可以申購︰\n\
+             3333 公司3333 起迄日︰2026-07-14~2026-07-16 抽籤日︰2026-07-20 承銷價︰50 參考價︰62.5 價差︰12.5 (20.00%) 中籤一張預期獲利︰12,430 發行市場︰上市\n\
+             2222 公司2222 起迄日︰2026-07-14~2026-07-16 抽籤日︰2026-07-20 承銷價︰50 參考價︰55 價差︰5 (9.09%) 中籤一張預期獲利︰4,930 發行市場︰上市\n\
+             1111 公司1111 起迄日︰2026-07-14~2026-07-16 抽籤日︰2026-07-20 承銷價︰50 參考價︰- 發行市場︰上市\n\
+             今日抽籤︰\n\
+             4444 公司4444 起迄日︰2026-07-14~2026-07-16 抽籤日︰2026-07-20 承銷價︰50 參考價︰- 發行市場︰上市 已申購︰Eddie、Hugo\n"
+        );
+        assert!(build_reminder_message(today, &[]).is_none());
+    }
 
     /// 驗證公開申購提醒流程可執行。
     #[tokio::test]
@@ -118,8 +267,6 @@ mod tests {
         dotenvy::dotenv().ok();
         SHARE.load().await;
         tracing::info!("開始 execute");
-        //let date = NaiveDate::from_ymd_opt(2023, 6, 15);
-        //let today: NaiveDate = Local::today().naive_local();
         match execute().await {
             Ok(_) => {}
             Err(why) => {
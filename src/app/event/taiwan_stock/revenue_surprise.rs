@@ -0,0 +1,224 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use anyhow::Result;
+use chrono::{Datelike, Local, NaiveDate};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::{
+    core::{alert, util::text},
+    domain::{
+        financial::{repository::RevenueMomentumRepository, revenue_momentum::RevenueMomentum},
+        portfolio::repository::PortfolioRepository,
+        trace::repository::TraceRepository,
+    },
+    infra::{
+        cache::SHARE,
+        database::repository::{
+            portfolio::PgPortfolioRepository, revenue_momentum::PgRevenueMomentumRepository,
+            trace::PgTraceRepository,
+        },
+    },
+};
+
+/// 季節性驚喜幅度（%）絕對值達到此門檻才列入摘要。
+const SURPRISE_THRESHOLD_PCT: Decimal = dec!(20);
+
+/// 月營收公布截止（每月 10 日）後，彙整持股與追蹤清單中營收明顯超出或
+/// 低於季節性常態、或創歷史新高的股票並發送摘要。
+///
+/// 動能指標由每日的月營收更新流程計算，這裡只讀取結果。
+pub async fn execute() -> Result<()> {
+    let month = previous_month(Local::now().date_naive());
+
+    let held: HashSet<String> = PgPortfolioRepository::new()
+        .fetch_active_holdings(None)
+        .await?
+        .into_iter()
+        .map(|holding| holding.security_code)
+        .collect();
+    let traced: HashSet<String> = PgTraceRepository::new()
+        .fetch_all()
+        .await?
+        .into_iter()
+        .map(|trace| trace.stock_symbol)
+        .collect();
+    let symbols: Vec<String> = held.union(&traced).cloned().collect();
+    if symbols.is_empty() {
+        return Ok(());
+    }
+
+    let momentums = PgRevenueMomentumRepository::new()
+        .fetch_month(month, Some(symbols))
+        .await?;
+    let mut names = HashMap::new();
+    for momentum in &momentums {
+        if let Some(stock) = SHARE.get_stock(&momentum.security_code).await {
+            names.insert(momentum.security_code.clone(), stock.name().to_string());
+        }
+    }
+
+    if let Some(msg) = build_digest_message(month, &momentums, &held, &traced, &names) {
+        alert::send_message(&text::escape_markdown_v2(msg)).await;
+    }
+
+    Ok(())
+}
+
+/// 上個月份（`YYYYMM`）。
+fn previous_month(today: NaiveDate) -> i64 {
+    let (year, month) = match today.month() {
+        1 => (today.year() - 1, 12),
+        month => (today.year(), month - 1),
+    };
+    i64::from(year) * 100 + i64::from(month)
+}
+
+/// 組成月營收驚喜摘要（未跳脫）；沒有值得提醒的股票時回傳 `None`。
+///
+/// 持股排在追蹤清單前面，同組內依驚喜幅度由高到低排列。
+fn build_digest_message(
+    month: i64,
+    momentums: &[RevenueMomentum],
+    held: &HashSet<String>,
+    traced: &HashSet<String>,
+    names: &HashMap<String, String>,
+) -> Option<String> {
+    let mut notable: Vec<(&'static str, &RevenueMomentum)> = momentums
+        .iter()
+        .filter(|momentum| momentum.is_notable(SURPRISE_THRESHOLD_PCT))
+        .filter_map(|momentum| {
+            let code = &momentum.security_code;
+            let watch = if held.contains(code) {
+                "持股"
+            } else if traced.contains(code) {
+                "追蹤"
+            } else {
+                return None;
+            };
+            Some((watch, momentum))
+        })
+        .collect();
+    if notable.is_empty() {
+        return None;
+    }
+    notable.sort_by(|(a_watch, a), (b_watch, b)| {
+        (*a_watch != "持股")
+            .cmp(&(*b_watch != "持股"))
+            .then(b.surprise_pct.cmp(&a.surprise_pct))
+            .then(a.security_code.cmp(&b.security_code))
+    });
+
+    let mut msg = format!("{}年{:02}月營收驚喜︰\n", month / 100, month % 100);
+    for (watch, momentum) in notable {
+        let name = names
+            .get(&momentum.security_code)
+            .map(String::as_str)
+            .unwrap_or("-");
+        let _ = write!(&mut msg, "[{watch}] {} {name}", momentum.security_code);
+        if let Some(yoy) = momentum.yoy_pct {
+            let _ = write!(&mut msg, " 年增 {}%", signed(yoy));
+        }
+        if let Some(surprise) = momentum.surprise_pct {
+            let _ = write!(&mut msg, " 季節性驚喜 {}%", signed(surprise));
+        }
+        if let Some(acceleration) = momentum.yoy_acceleration_pct {
+            let _ = write!(&mut msg, " 近3月年增加速 {}", signed(acceleration));
+        }
+        if momentum.all_time_high {
+            msg.push_str(" 創歷史新高");
+        }
+        msg.push('\n');
+    }
+
+    Some(msg)
+}
+
+/// 帶正負號、四捨五入到小數一位的數字。
+fn signed(value: Decimal) -> String {
+    let value = value.round_dp(1);
+    if value > Decimal::ZERO {
+        format!("+{value}")
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn momentum(
+        symbol: &str,
+        surprise_pct: Option<Decimal>,
+        all_time_high: bool,
+    ) -> RevenueMomentum {
+        RevenueMomentum {
+            security_code: symbol.to_string(),
+            month: 202609,
+            revenue: dec!(100),
+            rolling_3m: None,
+            rolling_12m: None,
+            yoy_pct: Some(dec!(15.26)),
+            rolling_3m_yoy_pct: None,
+            rolling_12m_yoy_pct: None,
+            yoy_acceleration_pct: None,
+            all_time_high,
+            seasonal_expected: None,
+            surprise_pct,
+            seasonal_years: 3,
+        }
+    }
+
+    #[test]
+    fn previous_month_wraps_year() {
+        assert_eq!(
+            previous_month(NaiveDate::from_ymd_opt(2026, 1, 11).unwrap()),
+            202512
+        );
+        assert_eq!(
+            previous_month(NaiveDate::from_ymd_opt(2026, 10, 11).unwrap()),
+            202609
+        );
+    }
+
+    /// 只列出持股與追蹤清單中值得提醒的股票，持股在前。
+    #[test]
+    fn build_digest_message_lists_notable_watched_symbols() {
+        let momentums = vec![
+            momentum("2317", Some(dec!(35)), false),
+            momentum("2330", Some(dec!(-22.04)), false),
+            momentum("1101", None, true),
+            // 幅度不足門檻。
+            momentum("2454", Some(dec!(5)), false),
+            // 不在清單內。
+            momentum("2603", Some(dec!(80)), true),
+        ];
+        let held = HashSet::from(["2330".to_string(), "2454".to_string()]);
+        let traced = HashSet::from(["2317".to_string(), "1101".to_string()]);
+        let names = HashMap::from([("2330".to_string(), "台積電".to_string())]);
+
+        let msg = build_digest_message(202609, &momentums, &held, &traced, &names).unwrap();
+
+        assert_eq!(
+            msg,
+            "2026年09月營收驚喜︰\n\
+             [持股] 2330 台積電 年增 +15.3% 季節性驚喜 -22.0%\n\
+             [追蹤] 2317 - 年增 +15.3% 季節性驚喜 +35%\n\
+             [追蹤] 1101 - 年增 +15.3% 創歷史新高\n"
+        );
+        assert!(build_digest_message(202609, &momentums[3..4], &held, &traced, &names).is_none());
+    }
+
+    /// 手動發送上個月的營收驚喜摘要。
+    #[tokio::test]
+    #[ignore]
+    async fn test_execute() {
+        dotenvy::dotenv().ok();
+        SHARE.load().await;
+        if let Err(why) = execute().await {
+            tracing::debug!("Failed to execute because {:?}", why);
+        }
+    }
+}
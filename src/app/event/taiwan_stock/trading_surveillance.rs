@@ -0,0 +1,197 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use anyhow::Result;
+use chrono::Local;
+
+use crate::{
+    app::backfill::acl::TradingSurveillanceAclMapper,
+    core::{alert, util::text},
+    domain::{
+        portfolio::repository::PortfolioRepository,
+        surveillance::{SurveillancePeriod, SurveillanceRepository, departures},
+        trace::repository::TraceRepository,
+    },
+    infra::{
+        crawler::{tpex, twse},
+        database::repository::{
+            portfolio::PgPortfolioRepository, surveillance::PgSurveillanceRepository,
+            trace::PgTraceRepository,
+        },
+    },
+};
+
+/// 取得上市櫃注意股與處置股，保存期間歷史並通知持股與追蹤清單的進出。
+///
+/// 只在當日已有收盤報價時執行（休市或收盤資料尚未入庫則略過）。任一市場抓取
+/// 失敗即中止，避免把抓不到的那一半誤判為離開清單。
+pub async fn execute() -> Result<()> {
+    let today = Local::now().date_naive();
+    let repo = PgSurveillanceRepository::new();
+    let trading_days = repo.fetch_recent_trading_days(today, 2).await?;
+    if trading_days.first() != Some(&today) {
+        tracing::info!("{today} 沒有收盤報價，略過注意股與處置股");
+        return Ok(());
+    }
+    let previous_trading_day = trading_days.get(1).copied();
+
+    let (listed, otc) = tokio::try_join!(
+        twse::trading_surveillance::visit(),
+        tpex::trading_surveillance::visit()
+    )?;
+    let periods: Vec<SurveillancePeriod> = listed
+        .iter()
+        .chain(otc.iter())
+        .map(TradingSurveillanceAclMapper::from_dto)
+        .collect();
+    tracing::info!("取得注意股與處置股 {} 筆", periods.len());
+
+    let previous = match previous_trading_day {
+        Some(day) => repo.fetch_active_on(day, None).await?,
+        None => Vec::new(),
+    };
+    let entered = repo.upsert(&periods, previous_trading_day).await?;
+    let current = repo.fetch_active_on(today, None).await?;
+    let left: Vec<SurveillancePeriod> = departures(&previous, &current)
+        .into_iter()
+        .cloned()
+        .collect();
+    if entered.is_empty() && left.is_empty() {
+        return Ok(());
+    }
+
+    let symbols: Vec<String> = entered
+        .iter()
+        .chain(left.iter())
+        .map(|period| period.stock_symbol.clone())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let held: HashSet<String> = PgPortfolioRepository::new()
+        .fetch_active_holdings(Some(symbols))
+        .await?
+        .into_iter()
+        .map(|holding| holding.security_code)
+        .collect();
+    let traced: HashSet<String> = PgTraceRepository::new()
+        .fetch_all()
+        .await?
+        .into_iter()
+        .map(|trace| trace.stock_symbol)
+        .collect();
+
+    if let Some(msg) = build_alert_message(&entered, &left, &held, &traced) {
+        alert::send_message(&text::escape_markdown_v2(msg)).await;
+    }
+
+    Ok(())
+}
+
+/// 組成持股與追蹤清單的注意股／處置股進出通知（未跳脫）；沒有相關股票時回傳 `None`。
+fn build_alert_message(
+    entered: &[SurveillancePeriod],
+    left: &[SurveillancePeriod],
+    held: &HashSet<String>,
+    traced: &HashSet<String>,
+) -> Option<String> {
+    let watch = |symbol: &str| match (held.contains(symbol), traced.contains(symbol)) {
+        (true, _) => Some("持股"),
+        (false, true) => Some("追蹤"),
+        (false, false) => None,
+    };
+    let mut msg = String::new();
+
+    for period in entered {
+        let Some(watch) = watch(&period.stock_symbol) else {
+            continue;
+        };
+        let _ = write!(
+            &mut msg,
+            "[{watch}] {symbol} {name} 列入{label} {start}～{end}",
+            symbol = period.stock_symbol,
+            name = period.name,
+            label = period.kind.label(),
+            start = period.start_date.format("%m/%d"),
+            end = period.end_date.format("%m/%d"),
+        );
+        if !period.measure.is_empty() {
+            let _ = write!(&mut msg, " {}", period.measure);
+        }
+        if !period.reason.is_empty() {
+            let _ = write!(&mut msg, "（{}）", period.reason);
+        }
+        msg.push('\n');
+    }
+
+    for period in left {
+        let Some(watch) = watch(&period.stock_symbol) else {
+            continue;
+        };
+        let _ = writeln!(
+            &mut msg,
+            "[{watch}] {symbol} {name} 解除{label}",
+            symbol = period.stock_symbol,
+            name = period.name,
+            label = period.kind.label(),
+        );
+    }
+
+    if msg.is_empty() {
+        return None;
+    }
+
+    Some(format!("注意股與處置股︰\n{msg}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::domain::surveillance::SurveillanceKind;
+
+    fn period(symbol: &str, kind: SurveillanceKind, measure: &str) -> SurveillancePeriod {
+        SurveillancePeriod {
+            stock_symbol: symbol.to_string(),
+            name: format!("公司{symbol}"),
+            kind,
+            announced_on: NaiveDate::from_ymd_opt(2026, 4, 21).unwrap(),
+            start_date: NaiveDate::from_ymd_opt(2026, 4, 22).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2026, 5, 5).unwrap(),
+            reason: String::new(),
+            measure: measure.to_string(),
+        }
+    }
+
+    /// 只通知持股或追蹤清單內的代號，列入在前、解除在後。
+    #[test]
+    fn build_alert_message_only_includes_watched_symbols() {
+        let entered = vec![
+            period("2330", SurveillanceKind::Disposition, "第一次處置"),
+            period("1101", SurveillanceKind::Attention, ""),
+        ];
+        let left = vec![period("2317", SurveillanceKind::Attention, "")];
+        let held = HashSet::from(["2330".to_string()]);
+        let traced = HashSet::from(["2317".to_string()]);
+
+        let msg = build_alert_message(&entered, &left, &held, &traced).unwrap();
+
+        assert_eq!(
+            msg,
+            "注意股與處置股︰\n\
+             [持股] 2330 公司2330 列入處置股 04/22～05/05 第一次處置\n\
+             [追蹤] 2317 公司2317 解除注意股\n"
+        );
+        assert!(build_alert_message(&entered, &left, &HashSet::new(), &HashSet::new()).is_none());
+    }
+
+    /// 手動執行注意股與處置股通知。
+    #[tokio::test]
+    #[ignore]
+    async fn test_execute() {
+        dotenvy::dotenv().ok();
+        if let Err(why) = execute().await {
+            tracing::debug!("Failed to execute because {:?}", why);
+        }
+    }
+}
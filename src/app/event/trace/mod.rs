@@ -1,3 +1,4 @@
 pub mod price_tasks;
+pub mod quote_feed;
 mod stats;
 pub mod stock_price;
@@ -10,6 +10,8 @@
 //! 3. 價格更新事件 consumer，將指定股票代號交給追蹤 evaluator
 //! 4. 追蹤條件快取刷新任務，定期同步最新 `trace` 設定
 //! 5. 低頻 reconciliation 任務，補償事件遺漏或剛新增追蹤條件的情況
+//!
+//! 價格更新事件同時廣播給 [`quote_feed`](super::quote_feed) 的即時報價訂閱者。
 
 use std::sync::RwLock;
 use std::time::Duration;
@@ -32,7 +34,7 @@ use tokio::{
     task, time,
 };
 
-use super::{stats as trace_stats, stock_price};
+use super::{quote_feed, stats as trace_stats, stock_price};
 use crate::{core::declare, core::logging, infra::cache::SHARE, infra::crawler};
 use crate::{
     core::util::{
@@ -225,12 +227,15 @@ async fn wait_for_interval_or_stop(duration: Duration) {
 /// 事件只代表「這支股票的共享快取已更新」。
 /// 實際追蹤比對時會重新從快取讀值，而不是直接使用這裡傳入的 `price`。
 ///
-/// 若 trace 價格事件 consumer 尚未啟動或已停止，事件會直接被忽略。
+/// 事件會先廣播給即時報價訂閱者（[`quote_feed`](super::quote_feed)），
+/// 再交給追蹤 consumer；若 trace 價格事件 consumer 尚未啟動或已停止，後者會直接被忽略。
 pub fn publish_price_update(symbol: String, price: Decimal) {
     if price == Decimal::ZERO {
         return;
     }
 
+    quote_feed::publish(&symbol);
+
     if !stock_price::has_targets_for_symbol(&symbol) {
         return;
     }
@@ -0,0 +1,339 @@
+//! # 即時報價訂閱
+//!
+//! 將 [`price_tasks`](super::price_tasks) 的價格更新事件廣播給外部訂閱者
+//! （gRPC `SubscribeQuotes`、WebSocket 與 SSE），讓用戶端不必輪詢
+//! `SHARE` 的即時報價快照。
+//!
+//! 廣播內容只有「哪支股票的快照剛更新」，訂閱者依自己的代號清單過濾後，
+//! 再回頭讀取 `SHARE` 的最新快照，與追蹤 evaluator 的做法一致。
+//!
+//! ## 背壓
+//!
+//! 廣播 channel 容量固定，消化太慢的訂閱者不會拖慢爬蟲，而是落後後收到
+//! `Lagged`；此時改為重送其訂閱代號目前的快照。快照是狀態而非事件流，
+//! 略過中間的更新不影響正確性。每個訂閱者往外送的 channel 同樣有上限，
+//! 送不出去時不再讀取廣播，由上述機制合併更新。
+
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    sync::Arc,
+};
+
+use once_cell::sync::Lazy;
+use thiserror::Error;
+use tokio::sync::{
+    broadcast::{self, error::RecvError, error::TryRecvError},
+    mpsc,
+};
+
+use crate::infra::cache::{RealtimeSnapshot, SHARE};
+
+/// 廣播 channel 容量；盤中一輪全市場刷新約兩千檔異動，保留兩輪緩衝。
+const QUOTE_FEED_CAPACITY: usize = 4096;
+/// 單一訂閱可指定的股票代號上限。
+pub const MAX_SUBSCRIBED_SYMBOLS: usize = 200;
+
+/// 價格更新廣播；沒有訂閱者時不送出。
+static QUOTE_FEED: Lazy<broadcast::Sender<Arc<str>>> =
+    Lazy::new(|| broadcast::channel(QUOTE_FEED_CAPACITY).0);
+
+/// 建立訂閱時的參數錯誤。
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SubscribeError {
+    /// 未指定任何股票代號。
+    #[error("at least one stock symbol is required")]
+    NoSymbols,
+    /// 股票代號數量超過上限。
+    #[error("too many stock symbols: {0} (max {MAX_SUBSCRIBED_SYMBOLS})")]
+    TooManySymbols(usize),
+}
+
+/// 廣播單筆價格更新；由 [`price_tasks`](super::price_tasks) 在寫入 `SHARE` 後呼叫。
+pub(super) fn publish(symbol: &str) {
+    if QUOTE_FEED.receiver_count() > 0 {
+        let _ = QUOTE_FEED.send(Arc::from(symbol));
+    }
+}
+
+/// 訂閱指定股票的即時報價更新。
+///
+/// 代號會去除空白並轉為大寫，重複的代號只保留一個。
+///
+/// # Errors
+///
+/// 代號清單為空或超過 [`MAX_SUBSCRIBED_SYMBOLS`] 時回傳 [`SubscribeError`]。
+pub fn subscribe<I, S>(symbols: I) -> Result<QuoteSubscription, SubscribeError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    QuoteSubscription::with_source(symbols, QUOTE_FEED.subscribe(), |symbol| {
+        SHARE.get_stock_snapshot(symbol)
+    })
+}
+
+/// 單一訂閱者的代號過濾與更新合併狀態。
+pub struct QuoteSubscription {
+    symbols: HashSet<String>,
+    receiver: broadcast::Receiver<Arc<str>>,
+    lookup: fn(&str) -> Option<RealtimeSnapshot>,
+    last_sent: HashMap<String, RealtimeSnapshot>,
+}
+
+impl QuoteSubscription {
+    fn with_source<I, S>(
+        symbols: I,
+        receiver: broadcast::Receiver<Arc<str>>,
+        lookup: fn(&str) -> Option<RealtimeSnapshot>,
+    ) -> Result<Self, SubscribeError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let symbols: HashSet<String> = symbols
+            .into_iter()
+            .map(|symbol| symbol.as_ref().trim().to_uppercase())
+            .filter(|symbol| !symbol.is_empty())
+            .collect();
+        if symbols.is_empty() {
+            return Err(SubscribeError::NoSymbols);
+        }
+        if symbols.len() > MAX_SUBSCRIBED_SYMBOLS {
+            return Err(SubscribeError::TooManySymbols(symbols.len()));
+        }
+
+        Ok(Self {
+            symbols,
+            receiver,
+            lookup,
+            last_sent: HashMap::new(),
+        })
+    }
+
+    /// 訂閱的股票代號。
+    pub fn symbols(&self) -> &HashSet<String> {
+        &self.symbols
+    }
+
+    /// 取得訂閱代號目前的快照，作為串流的第一批資料；快取尚無資料的代號略過。
+    pub fn current(&mut self) -> Vec<RealtimeSnapshot> {
+        let symbols: Vec<String> = self.symbols.iter().cloned().collect();
+        self.changed(symbols)
+    }
+
+    /// 等待下一批有變動的快照。
+    ///
+    /// 同一時間已排隊的更新會合併成一批；落後廣播時改為重送全部訂閱代號。
+    /// 廣播關閉時回傳 `None`。
+    pub async fn next_batch(&mut self) -> Option<Vec<RealtimeSnapshot>> {
+        loop {
+            let mut dirty = HashSet::new();
+            let mut resync = false;
+
+            match self.receiver.recv().await {
+                Ok(symbol) => self.mark(&mut dirty, &symbol),
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::debug!("quote subscriber lagged, skipped {} updates", skipped);
+                    resync = true;
+                }
+                Err(RecvError::Closed) => return None,
+            }
+
+            loop {
+                match self.receiver.try_recv() {
+                    Ok(symbol) => self.mark(&mut dirty, &symbol),
+                    Err(TryRecvError::Lagged(_)) => resync = true,
+                    Err(TryRecvError::Empty) | Err(TryRecvError::Closed) => break,
+                }
+            }
+
+            if resync {
+                dirty = self.symbols.clone();
+            }
+            let batch = self.changed(dirty);
+            if !batch.is_empty() {
+                return Some(batch);
+            }
+        }
+    }
+
+    fn mark(&self, dirty: &mut HashSet<String>, symbol: &str) {
+        if self.symbols.contains(symbol) {
+            dirty.insert(symbol.to_string());
+        }
+    }
+
+    /// 讀取最新快照，只回傳與上次送出內容不同者。
+    fn changed(&mut self, symbols: impl IntoIterator<Item = String>) -> Vec<RealtimeSnapshot> {
+        let mut batch = Vec::new();
+        for symbol in symbols {
+            let Some(snapshot) = (self.lookup)(&symbol) else {
+                continue;
+            };
+            if self.last_sent.get(&symbol) == Some(&snapshot) {
+                continue;
+            }
+            self.last_sent.insert(symbol, snapshot.clone());
+            batch.push(snapshot);
+        }
+        batch.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        batch
+    }
+}
+
+/// 把訂閱的快照轉送到有界 channel，直到接收端關閉、廣播結束或 `stop` 完成。
+///
+/// 接收端消化不及時，`send` 會停在這裡等待，期間累積的廣播由
+/// [`QuoteSubscription::next_batch`] 合併，不會無限堆積在記憶體。
+pub async fn forward<T, F>(
+    mut subscription: QuoteSubscription,
+    tx: mpsc::Sender<T>,
+    stop: impl Future<Output = ()>,
+    map: F,
+) where
+    F: Fn(RealtimeSnapshot) -> T,
+{
+    tokio::pin!(stop);
+
+    let mut batch = subscription.current();
+    loop {
+        for snapshot in batch {
+            tokio::select! {
+                _ = &mut stop => return,
+                sent = tx.send(map(snapshot)) => {
+                    if sent.is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+
+        batch = tokio::select! {
+            _ = &mut stop => return,
+            _ = tx.closed() => return,
+            next = subscription.next_batch() => match next {
+                Some(batch) => batch,
+                None => return,
+            },
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::DateTime;
+    use rust_decimal::Decimal;
+
+    use super::*;
+
+    static TEST_PRICES: Lazy<std::sync::RwLock<HashMap<String, Decimal>>> = Lazy::new(|| {
+        std::sync::RwLock::new(HashMap::from([
+            ("T9901".to_string(), Decimal::from(10)),
+            ("T9902".to_string(), Decimal::from(20)),
+        ]))
+    });
+
+    /// 測試用快照來源：價格取自 `TEST_PRICES`，`T9903` 沒有快照。
+    fn lookup(symbol: &str) -> Option<RealtimeSnapshot> {
+        let price = TEST_PRICES.read().unwrap().get(symbol).copied()?;
+        let mut snapshot = RealtimeSnapshot::new(symbol.to_string(), price);
+        snapshot.updated_at = DateTime::UNIX_EPOCH;
+        Some(snapshot)
+    }
+
+    fn subscription(
+        symbols: &[&str],
+        capacity: usize,
+    ) -> (broadcast::Sender<Arc<str>>, QuoteSubscription) {
+        let (tx, rx) = broadcast::channel(capacity);
+        let subscription = QuoteSubscription::with_source(symbols.iter(), rx, lookup).unwrap();
+        (tx, subscription)
+    }
+
+    #[test]
+    fn subscribe_validates_symbols() {
+        let (_tx, rx) = broadcast::channel(1);
+        assert_eq!(
+            QuoteSubscription::with_source([" ", ""], rx, lookup).err(),
+            Some(SubscribeError::NoSymbols)
+        );
+
+        let (_tx, rx) = broadcast::channel(1);
+        let many: Vec<String> = (0..=MAX_SUBSCRIBED_SYMBOLS)
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(
+            QuoteSubscription::with_source(&many, rx, lookup).err(),
+            Some(SubscribeError::TooManySymbols(MAX_SUBSCRIBED_SYMBOLS + 1))
+        );
+
+        let (_tx, subscription) = subscription(&["t9901 ", "T9901"], 1);
+        assert_eq!(
+            subscription.symbols(),
+            &HashSet::from(["T9901".to_string()])
+        );
+    }
+
+    /// 只轉送訂閱的代號，且同一份快照不重送。
+    #[tokio::test]
+    async fn next_batch_filters_and_skips_unchanged() {
+        let (tx, mut subscription) = subscription(&["T9901", "T9903"], 16);
+        assert_eq!(subscription.current().len(), 1);
+
+        tx.send(Arc::from("T9902")).unwrap();
+        tx.send(Arc::from("T9901")).unwrap();
+        tx.send(Arc::from("T9903")).unwrap();
+        let pending = tokio::time::timeout(Duration::from_millis(50), subscription.next_batch());
+        assert!(
+            pending.await.is_err(),
+            "unchanged snapshot must not be sent"
+        );
+
+        TEST_PRICES
+            .write()
+            .unwrap()
+            .insert("T9901".to_string(), Decimal::from(11));
+        tx.send(Arc::from("T9901")).unwrap();
+        let batch = subscription.next_batch().await.unwrap();
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch[0].price, Decimal::from(11));
+    }
+
+    /// 落後廣播時重送所有訂閱代號的最新快照。
+    #[tokio::test]
+    async fn next_batch_resyncs_after_lag() {
+        let (tx, mut subscription) = subscription(&["T9901", "T9902"], 2);
+        for _ in 0..5 {
+            tx.send(Arc::from("T9904")).unwrap();
+        }
+
+        let batch = subscription.next_batch().await.unwrap();
+        let symbols: Vec<&str> = batch.iter().map(|s| s.symbol.as_str()).collect();
+        assert_eq!(symbols.len(), 2);
+        assert!(symbols.contains(&"T9902"));
+    }
+
+    #[tokio::test]
+    async fn forward_stops_on_signal() {
+        let (_feed, subscription) = subscription(&["T9902"], 4);
+        let (tx, mut rx) = mpsc::channel(1);
+        let (stop_tx, stop_rx) = tokio::sync::oneshot::channel::<()>();
+
+        let task = tokio::spawn(forward(
+            subscription,
+            tx,
+            async move {
+                let _ = stop_rx.await;
+            },
+            |snapshot| snapshot.symbol,
+        ));
+
+        assert_eq!(rx.recv().await.as_deref(), Some("T9902"));
+        stop_tx.send(()).unwrap();
+        task.await.unwrap();
+        assert!(rx.recv().await.is_none());
+    }
+}
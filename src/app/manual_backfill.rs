@@ -26,13 +26,36 @@
 //!   歷史日報價缺口（只補空位，不覆寫既有資料）。
 //! - `test_backfill_cagr_period`：
 //!   依 [`MANUAL_CAGR_PERIOD`] 為既有的歷史基準日回填單一統計期間（新增期間後專用）。
-
-use chrono::NaiveDate;
+//! - `test_backfill_estimate_backtest`：
+//!   一次評估全部尚未回測的歷史估價日，寫入 `estimate_backtest`（不受排程單次上限限制）。
+//! - `test_backfill_market_breadth`：
+//!   從 [`MANUAL_MARKET_BREADTH_FROM`] 起依序重算騰落線、McClellan 與新高新低等廣度指標，
+//!   寫入 `market_breadth_indicator`。
+//! - `test_backfill_industry_metrics`：
+//!   從 [`MANUAL_INDUSTRY_METRIC_FROM`] 起依序重算產業報酬、成交佔比、營收年增率與產業指數，
+//!   寫入 `industry_daily_metric`。
+//! - `test_backfill_financial_line_items`：
+//!   從 [`MANUAL_FINANCIAL_LINE_ITEM_FROM_YEAR`] 起逐季下載 MOPS XBRL 季報，
+//!   解析三大報表科目寫入 `financial_statement_line_item`（已有資料的季度略過）。
+//! - `test_backfill_financial_health`：
+//!   從 [`MANUAL_FINANCIAL_HEALTH_FROM_YEAR`] 第一季起依已寫入的三大報表科目重算
+//!   F-score、Altman Z-score 等財務體質分數，寫入 `financial_health_score`。
+//! - `test_backfill_revenue_momentum`：
+//!   從 [`MANUAL_REVENUE_MOMENTUM_FROM`] 起重算月營收滾動合計、年增率加速度、創新高與
+//!   季節性驚喜，寫入 `revenue_momentum`。
+
+use chrono::{Datelike, NaiveDate};
 
 use crate::{
-    app::backfill::{dividend, quote, quote_history, taiwan_stock_index},
-    app::calculation::{cagr, dividend_record},
+    app::backfill::{
+        dividend, financial_statement::line_item, quote, quote_history, taiwan_stock_index,
+    },
+    app::calculation::{
+        cagr, dividend_record, estimate_backtest, financial_health, industry_metric,
+        market_breadth, revenue_momentum,
+    },
     app::event::taiwan_stock::closing,
+    core::{declare::Quarter, util::datetime::ReportQuarter},
     domain::performance::CagrPeriod,
     infra::cache::SHARE,
 };
@@ -69,6 +92,23 @@ const MANUAL_QUOTE_HISTORY_TO: &str = "2021-12-01";
 /// 新增期間後把這裡改成該期間的代碼再執行 `test_backfill_cagr_period`。
 const MANUAL_CAGR_PERIOD: &str = "Y7";
 
+/// 手動重算市場廣度指標的起始日。
+///
+/// 累計指標從這天起算；早於 `daily_stock_price_stats` 第一筆的日期沒有資料，會自然略過。
+const MANUAL_MARKET_BREADTH_FROM: &str = "2015-01-01";
+
+/// 手動重算產業指標的起始日；產業指數從這天的 100 點起算。
+const MANUAL_INDUSTRY_METRIC_FROM: &str = "2015-01-01";
+
+/// 手動回補三大報表科目的起始年度；MOPS 自 2013 年起提供 IFRS XBRL。
+const MANUAL_FINANCIAL_LINE_ITEM_FROM_YEAR: i32 = 2013;
+
+/// 手動重算財務體質分數的起始年度；需要去年同季比較，因此晚科目一年。
+const MANUAL_FINANCIAL_HEALTH_FROM_YEAR: i32 = 2014;
+
+/// 手動重算月營收動能的起始月份（`YYYYMM`）；更早的營收仍會作為歷史參與計算。
+const MANUAL_REVENUE_MOMENTUM_FROM: i64 = 201301;
+
 /// 手動回補指定交易日的各股每日收盤報價。
 ///
 /// 此測試等同把原本的 `backfill::quote::tests::test_execute` 集中到手動回補檔。
@@ -357,3 +397,156 @@ async fn test_backfill_cagr_period() {
         summary.rows_written
     );
 }
+
+/// 一次評估全部尚未回測的歷史估價日。
+///
+/// 排程每次最多處理固定組數，第一次上線時要花數天才能收斂；需要立刻看到
+/// 完整歷史的回測結果時改用此入口。只讀資料庫既有的估價、報價與股利，
+/// 已評估過的估價日不會重算。
+///
+/// 執行範例：
+/// `cargo test app::manual_backfill::test_backfill_estimate_backtest -- --ignored --nocapture`
+#[tokio::test]
+#[ignore]
+async fn test_backfill_estimate_backtest() {
+    dotenvy::dotenv().ok();
+    SHARE.load().await;
+
+    println!("開始 test_backfill_estimate_backtest");
+
+    let summary = estimate_backtest::execute(None)
+        .await
+        .expect("manual estimate backtest failed");
+
+    println!(
+        "結束 test_backfill_estimate_backtest evaluations={} samples={} early_exits={} rows_written={}",
+        summary.evaluations, summary.samples, summary.early_exits, summary.rows_written
+    );
+}
+
+/// 從 [`MANUAL_MARKET_BREADTH_FROM`] 起依序重算市場廣度指標。
+///
+/// 騰落線、EMA 與總和指數逐日遞推，第一次上線或修正過去的
+/// `daily_stock_price_stats` 後，需從該日重算到今天，後續數值才會一致。
+/// 全程只讀資料庫既有的統計與報價；重複執行為冪等。
+///
+/// 執行範例：
+/// `cargo test app::manual_backfill::test_backfill_market_breadth -- --ignored --nocapture`
+#[tokio::test]
+#[ignore]
+async fn test_backfill_market_breadth() {
+    dotenvy::dotenv().ok();
+    SHARE.load().await;
+
+    let from = NaiveDate::parse_from_str(MANUAL_MARKET_BREADTH_FROM, "%Y-%m-%d")
+        .expect("manual market breadth date should be valid");
+    let to = chrono::Local::now().date_naive();
+
+    println!("開始 test_backfill_market_breadth from={from} to={to}");
+
+    let written = market_breadth::rebuild(from, to)
+        .await
+        .expect("manual market breadth rebuild failed");
+
+    println!("結束 test_backfill_market_breadth rows_written={written}");
+}
+
+/// 從 [`MANUAL_INDUSTRY_METRIC_FROM`] 起依序重算產業指標。
+///
+/// 產業指數逐日連乘，第一次上線或修正歷史報價後需從該日重算到今天，
+/// 類股輪動的相對強弱才有足夠的歷史點位。全程只讀資料庫既有資料；重複執行為冪等。
+///
+/// 執行範例：
+/// `cargo test app::manual_backfill::test_backfill_industry_metrics -- --ignored --nocapture`
+#[tokio::test]
+#[ignore]
+async fn test_backfill_industry_metrics() {
+    dotenvy::dotenv().ok();
+    SHARE.load().await;
+
+    let from = NaiveDate::parse_from_str(MANUAL_INDUSTRY_METRIC_FROM, "%Y-%m-%d")
+        .expect("manual industry metric date should be valid");
+    let to = chrono::Local::now().date_naive();
+
+    println!("開始 test_backfill_industry_metrics from={from} to={to}");
+
+    let written = industry_metric::rebuild(from, to)
+        .await
+        .expect("manual industry metric rebuild failed");
+
+    println!("結束 test_backfill_industry_metrics rows_written={written}");
+}
+
+/// 從 [`MANUAL_FINANCIAL_LINE_ITEM_FROM_YEAR`] 起逐季回補三大報表科目。
+///
+/// 每季下載一次全市場 XBRL 壓縮檔（約一至兩百 MiB），解析後寫入
+/// `financial_statement_line_item`。已有資料的季度會略過，中斷後重跑即可續補；
+/// 要強制重抓某季時先刪除該季資料。
+///
+/// 執行範例：
+/// `cargo test app::manual_backfill::test_backfill_financial_line_items -- --ignored --nocapture`
+#[tokio::test]
+#[ignore]
+async fn test_backfill_financial_line_items() {
+    dotenvy::dotenv().ok();
+    SHARE.load().await;
+
+    println!(
+        "開始 test_backfill_financial_line_items from_year={MANUAL_FINANCIAL_LINE_ITEM_FROM_YEAR}"
+    );
+
+    let written = line_item::backfill(MANUAL_FINANCIAL_LINE_ITEM_FROM_YEAR, true)
+        .await
+        .expect("manual financial line item backfill failed");
+
+    println!("結束 test_backfill_financial_line_items rows_written={written}");
+}
+
+/// 手動重算歷史財務體質分數。
+///
+/// 只讀取資料庫中已回補的三大報表科目與收盤價，不呼叫外部來源；
+/// 應在 `test_backfill_financial_line_items` 補齊科目後執行。
+///
+/// 執行範例：
+/// `cargo test app::manual_backfill::test_backfill_financial_health -- --ignored --nocapture`
+#[tokio::test]
+#[ignore]
+async fn test_backfill_financial_health() {
+    dotenvy::dotenv().ok();
+    SHARE.load().await;
+
+    println!("開始 test_backfill_financial_health from_year={MANUAL_FINANCIAL_HEALTH_FROM_YEAR}");
+
+    let written = financial_health::rebuild(
+        ReportQuarter::new(MANUAL_FINANCIAL_HEALTH_FROM_YEAR, Quarter::Q1),
+        line_item::latest_target(),
+    )
+    .await
+    .expect("manual financial health rebuild failed");
+
+    println!("結束 test_backfill_financial_health rows_written={written}");
+}
+
+/// 手動重算歷史月營收動能。
+///
+/// 一次讀取 `"Revenue"` 全部歷史後在記憶體內計算，只寫入
+/// [`MANUAL_REVENUE_MOMENTUM_FROM`] 之後的月份，不呼叫外部來源。
+///
+/// 執行範例：
+/// `cargo test app::manual_backfill::test_backfill_revenue_momentum -- --ignored --nocapture`
+#[tokio::test]
+#[ignore]
+async fn test_backfill_revenue_momentum() {
+    dotenvy::dotenv().ok();
+    SHARE.load().await;
+
+    let today = chrono::Local::now().date_naive();
+    let to = i64::from(today.year()) * 100 + i64::from(today.month());
+    println!("開始 test_backfill_revenue_momentum from={MANUAL_REVENUE_MOMENTUM_FROM} to={to}");
+
+    let written = revenue_momentum::rebuild(MANUAL_REVENUE_MOMENTUM_FROM, to)
+        .await
+        .expect("manual revenue momentum rebuild failed");
+
+    println!("結束 test_backfill_revenue_momentum rows_written={written}");
+}
@@ -10,63 +10,359 @@
 //!
 //! - 這裡定義與傳輸層無關的資料載體與 gateway trait（port）。
 //! - 具體實作（gRPC client）由 `interfaces::rpc::client` 提供（adapter），
-//!   並在 `main` 啟動時透過 [`register_stock_info_gateway`] 注入。
-//! - 未註冊時（例如單元測試）推送會被略過並記 warning，不影響主流程。
+//!   並在 `main` 啟動時透過 [`register_stock_sync_gateway`] 注入。
+//! - 未註冊時（例如單元測試）同步佇列不會送出，待同步的證券留在佇列中，不影響主流程。
+//!
+//! 對外事件匯流排（[`EventBus`]）同樣是 port：領域事件與收盤資料就緒訊號
+//! 包成帶版本的 [`EventEnvelope`] JSON，由 `interfaces::event_bus` 的 Redis Stream
+//! 或 NATS adapter 發布，並在 `main` 啟動時透過 [`register_event_bus`] 注入。
+//! 發布經有界佇列交給獨立的背景 task，中介緩慢或斷線時不會拖慢派發器與收盤流程。
 
 use std::sync::{Arc, OnceLock};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::{self, error::TrySendError};
 
-/// 與傳輸層無關的股票基本資料載體。
-///
-/// 欄位刻意只用基本型別，不引用任何 prost 產生的 DTO。
-#[derive(Debug, Clone)]
-pub struct StockInfoPush {
-    /// 股票代號。
-    pub stock_symbol: String,
-    /// 股票名稱。
-    pub name: String,
-    /// 交易市場別 ID。
-    pub stock_exchange_market_id: i32,
-    /// 產業分類 ID。
-    pub stock_industry_id: i32,
-}
+use crate::domain::events::DomainEvent;
+use crate::domain::registry::sync::{StockSyncRecord, SyncRejection};
 
-/// 將股票基本資料推送到外部服務的 gateway（port）。
+/// 將證券主檔同步到外部服務的 gateway（port）。
+///
+/// 呼叫端是 `app::event::stock_sync` 的同步佇列：送出失敗的證券留在佇列中依次數延後重試，
+/// 並定期以 [`fetch_remote_stocks`](StockSyncGateway::fetch_remote_stocks) 全量比對。
 #[async_trait]
-pub trait StockInfoGateway: Send + Sync {
-    /// 推送單筆股票基本資料。
-    async fn push_stock_info(&self, info: StockInfoPush) -> Result<()>;
+pub trait StockSyncGateway: Send + Sync {
+    /// 批次送出證券主檔，回傳外部服務拒絕寫入的證券；整批失敗時回傳錯誤。
+    async fn sync_stocks(&self, records: Vec<StockSyncRecord>) -> Result<Vec<SyncRejection>>;
+
+    /// 取得外部服務目前保存的全部證券主檔。
+    async fn fetch_remote_stocks(&self) -> Result<Vec<StockSyncRecord>>;
 }
 
 /// 全域 gateway 實例。`OnceLock` 保證只會被成功註冊一次。
-static STOCK_INFO_GATEWAY: OnceLock<Arc<dyn StockInfoGateway>> = OnceLock::new();
+static STOCK_SYNC_GATEWAY: OnceLock<Arc<dyn StockSyncGateway>> = OnceLock::new();
 
-/// 註冊股票資料推送 gateway，應於 `main` 啟動流程呼叫一次。
-pub fn register_stock_info_gateway(gateway: Arc<dyn StockInfoGateway>) {
-    if STOCK_INFO_GATEWAY.set(gateway).is_err() {
-        tracing::warn!("stock info gateway already registered; duplicate registration ignored");
+/// 註冊證券主檔同步 gateway，應於 `main` 啟動流程呼叫一次。
+pub fn register_stock_sync_gateway(gateway: Arc<dyn StockSyncGateway>) {
+    if STOCK_SYNC_GATEWAY.set(gateway).is_err() {
+        tracing::warn!("stock sync gateway already registered; duplicate registration ignored");
     }
 }
 
-/// 透過已註冊的 gateway 推送股票基本資料。
+/// 取得已註冊的證券主檔同步 gateway；尚未註冊時（例如單元測試）回傳 `None`，
+/// 佇列中的證券保留到註冊後再送出。
+pub fn stock_sync_gateway() -> Option<Arc<dyn StockSyncGateway>> {
+    STOCK_SYNC_GATEWAY.get().cloned()
+}
+
+/// 對外事件 JSON 的結構版本；欄位有不相容的變更（改名、改型別、刪除）時遞增，
+/// 新增欄位不需遞增。
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+/// 收盤資料就緒訊號所指的資料集。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadyDataset {
+    /// 上市櫃每日收盤報價與其衍生計算（均線、估價、殖利率排行）。
+    DailyQuotes,
+    /// 上市櫃月營收。
+    MonthlyRevenue,
+}
+
+impl ReadyDataset {
+    /// 對外事件種類名稱。
+    pub fn event_type(self) -> &'static str {
+        match self {
+            ReadyDataset::DailyQuotes => "DailyQuotesReady",
+            ReadyDataset::MonthlyRevenue => "MonthlyRevenueReady",
+        }
+    }
+}
+
+/// 對外發布的事件。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutboundEvent {
+    /// 派發器處理的領域事件。
+    Domain(DomainEvent),
+    /// 某資料集已寫入完成，可供其他服務讀取。
+    DataReady {
+        /// 資料集。
+        dataset: ReadyDataset,
+        /// 資料日期；月營收為該月第一天。
+        date: NaiveDate,
+        /// 寫入的筆數。
+        records: u64,
+        /// 事件發生時間。
+        occurred_at: DateTime<Local>,
+    },
+}
+
+/// 對外事件的 JSON 信封。
 ///
-/// 尚未註冊時（例如單元測試）記 warning 後直接返回——
-/// 推送屬「盡力同步」性質，不應讓主要事件流程失敗。
-pub async fn push_stock_info(info: StockInfoPush) {
-    match STOCK_INFO_GATEWAY.get() {
-        Some(gateway) => {
-            let symbol = info.stock_symbol.clone();
-            if let Err(why) = gateway.push_stock_info(info).await {
-                tracing::error!("Failed to push stock info for {symbol} because {why:?}");
+/// `type` 為事件種類（領域事件為變體名稱，就緒訊號見 [`ReadyDataset::event_type`]），
+/// `data` 為事件本身的欄位；金額以字串表示以保留精度。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventEnvelope {
+    /// 結構版本，見 [`EVENT_SCHEMA_VERSION`]。
+    pub schema_version: u32,
+    /// 事件種類。
+    #[serde(rename = "type")]
+    pub event_type: String,
+    /// 證券代碼；全市場事件為 `null`。
+    pub symbol: Option<String>,
+    /// 事件發生時間。
+    pub occurred_at: DateTime<Local>,
+    /// 發布時間。
+    pub published_at: DateTime<Local>,
+    /// 事件內容。
+    pub data: serde_json::Value,
+}
+
+impl EventEnvelope {
+    /// 將對外事件包成目前版本的信封。
+    pub fn new(event: &OutboundEvent, published_at: DateTime<Local>) -> Result<Self> {
+        let (event_type, symbol, occurred_at, data) = match event {
+            OutboundEvent::Domain(event) => {
+                let mut data =
+                    serde_json::to_value(event).context("Failed to serialize domain event")?;
+                if let Some(fields) = data.as_object_mut() {
+                    fields.remove("type");
+                }
+                (
+                    event.event_type(),
+                    event.symbol().map(str::to_string),
+                    event.occurred_at(),
+                    data,
+                )
             }
-        }
-        None => {
+            OutboundEvent::DataReady {
+                dataset,
+                date,
+                records,
+                occurred_at,
+            } => (
+                dataset.event_type(),
+                None,
+                *occurred_at,
+                serde_json::json!({
+                    "dataset": dataset,
+                    "date": date,
+                    "records": records,
+                }),
+            ),
+        };
+
+        Ok(EventEnvelope {
+            schema_version: EVENT_SCHEMA_VERSION,
+            event_type: event_type.to_string(),
+            symbol,
+            occurred_at,
+            published_at,
+            data,
+        })
+    }
+}
+
+/// 將事件發布到外部訊息中介的 gateway（port）。
+#[async_trait]
+pub trait EventBus: Send + Sync {
+    /// 發布單筆事件。
+    async fn publish(&self, envelope: &EventEnvelope) -> Result<()>;
+}
+
+/// 對外發布佇列的容量；佇列滿代表中介跟不上，新事件直接捨棄。
+const OUTBOUND_QUEUE_CAPACITY: usize = 1024;
+
+/// 對外發布佇列的送出端，由 [`register_event_bus`] 建立。
+static OUTBOUND_QUEUE: OnceLock<mpsc::Sender<OutboundEvent>> = OnceLock::new();
+
+/// 註冊事件匯流排，應於 `main` 啟動流程（tokio runtime 內）呼叫一次；未設定後端時不需註冊。
+///
+/// 註冊時會啟動背景發布 task，依序把佇列中的事件送往 `bus`。
+pub fn register_event_bus(bus: Arc<dyn EventBus>) {
+    let (tx, rx) = mpsc::channel(OUTBOUND_QUEUE_CAPACITY);
+    if OUTBOUND_QUEUE.set(tx).is_err() {
+        tracing::warn!("event bus already registered; duplicate registration ignored");
+        return;
+    }
+    tokio::spawn(run_publisher(bus, rx));
+}
+
+/// 將事件排入對外發布佇列後立即返回，不等待中介回應。
+///
+/// 未註冊代表未啟用對外發布，直接返回；佇列已滿時捨棄事件並記錄警告，
+/// 發布失敗則由背景 task 記錄錯誤，兩者都不影響主流程。
+pub fn publish_event(event: OutboundEvent) {
+    if let Some(queue) = OUTBOUND_QUEUE.get() {
+        enqueue(queue, event);
+    }
+}
+
+/// 以 `try_send` 排入佇列；回傳是否成功排入。
+fn enqueue(queue: &mpsc::Sender<OutboundEvent>, event: OutboundEvent) -> bool {
+    match queue.try_send(event) {
+        Ok(()) => true,
+        Err(TrySendError::Full(event)) => {
             tracing::warn!(
-                "stock info gateway not registered; push skipped for {}",
-                info.stock_symbol
+                "outbound event queue is full; dropping {}",
+                event_type_of(&event)
+            );
+            false
+        }
+        Err(TrySendError::Closed(event)) => {
+            tracing::error!(
+                "outbound event publisher has stopped; dropping {}",
+                event_type_of(&event)
+            );
+            false
+        }
+    }
+}
+
+/// 對外事件的種類名稱，供記錄使用。
+fn event_type_of(event: &OutboundEvent) -> &'static str {
+    match event {
+        OutboundEvent::Domain(event) => event.event_type(),
+        OutboundEvent::DataReady { dataset, .. } => dataset.event_type(),
+    }
+}
+
+/// 背景發布迴圈：依排入順序逐筆包成信封並發布，直到所有送出端關閉。
+async fn run_publisher(bus: Arc<dyn EventBus>, mut rx: mpsc::Receiver<OutboundEvent>) {
+    while let Some(event) = rx.recv().await {
+        let envelope = match EventEnvelope::new(&event, Local::now()) {
+            Ok(envelope) => envelope,
+            Err(why) => {
+                tracing::error!("Failed to build event envelope because {why:?}");
+                continue;
+            }
+        };
+        if let Err(why) = bus.publish(&envelope).await {
+            tracing::error!(
+                "Failed to publish {} to event bus because {why:?}",
+                envelope.event_type
             );
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    /// 領域事件的信封帶版本與種類，`data` 不重複 `type` 欄位且金額保留精度。
+    #[test]
+    fn domain_event_envelope_uses_versioned_schema() {
+        let occurred_at = Local::now();
+        let envelope = EventEnvelope::new(
+            &OutboundEvent::Domain(DomainEvent::StockIndexUpdated {
+                date: NaiveDate::from_ymd_opt(2026, 10, 16).unwrap(),
+                index: dec!(23012.50),
+                change: dec!(-35.12),
+                occurred_at,
+            }),
+            occurred_at,
+        )
+        .unwrap();
+
+        let json = serde_json::to_value(&envelope).unwrap();
+        assert_eq!(json["schema_version"], EVENT_SCHEMA_VERSION);
+        assert_eq!(json["type"], "StockIndexUpdated");
+        assert!(json["symbol"].is_null());
+        assert_eq!(json["data"]["index"], "23012.50");
+        assert_eq!(json["data"]["date"], "2026-10-16");
+        assert!(json["data"].get("type").is_none());
+    }
+
+    #[test]
+    fn data_ready_envelope_names_dataset() {
+        let occurred_at = Local::now();
+        let envelope = EventEnvelope::new(
+            &OutboundEvent::DataReady {
+                dataset: ReadyDataset::MonthlyRevenue,
+                date: NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(),
+                records: 1800,
+                occurred_at,
+            },
+            occurred_at,
+        )
+        .unwrap();
+
+        assert_eq!(envelope.event_type, "MonthlyRevenueReady");
+        assert_eq!(
+            envelope.data,
+            serde_json::json!({"dataset": "monthly_revenue", "date": "2026-09-01", "records": 1800})
+        );
+    }
+
+    fn ready(records: u64) -> OutboundEvent {
+        OutboundEvent::DataReady {
+            dataset: ReadyDataset::DailyQuotes,
+            date: NaiveDate::from_ymd_opt(2026, 10, 16).unwrap(),
+            records,
+            occurred_at: Local::now(),
+        }
+    }
+
+    /// 佇列滿時直接捨棄新事件，呼叫端不會被卡住。
+    #[test]
+    fn enqueue_drops_event_when_queue_is_full() {
+        let (tx, mut rx) = mpsc::channel(1);
+
+        assert!(enqueue(&tx, ready(1)));
+        assert!(!enqueue(&tx, ready(2)));
+
+        let OutboundEvent::DataReady { records, .. } = rx.try_recv().unwrap() else {
+            unreachable!()
+        };
+        assert_eq!(records, 1);
+        assert!(rx.try_recv().is_err());
+    }
+
+    /// 記錄收到的信封；`gate` 關閉前每次發布都會卡住，模擬緩慢的中介。
+    struct SlowBus {
+        gate: tokio::sync::Semaphore,
+        published: tokio::sync::Mutex<Vec<EventEnvelope>>,
+    }
+
+    #[async_trait]
+    impl EventBus for SlowBus {
+        async fn publish(&self, envelope: &EventEnvelope) -> Result<()> {
+            self.gate.acquire().await?.forget();
+            self.published.lock().await.push(envelope.clone());
+            Ok(())
+        }
+    }
+
+    /// 背景 task 依排入順序發布；中介卡住時排入仍立即返回。
+    #[tokio::test]
+    async fn publisher_task_publishes_in_order_without_blocking_callers() {
+        let bus = Arc::new(SlowBus {
+            gate: tokio::sync::Semaphore::new(0),
+            published: tokio::sync::Mutex::new(Vec::new()),
+        });
+        let (tx, rx) = mpsc::channel(8);
+        let publisher = tokio::spawn(run_publisher(bus.clone(), rx));
+
+        for records in 1..=3 {
+            assert!(enqueue(&tx, ready(records)));
+        }
+        assert!(bus.published.lock().await.is_empty());
+
+        bus.gate.add_permits(3);
+        drop(tx);
+        publisher.await.unwrap();
+
+        let published = bus.published.lock().await;
+        let records: Vec<_> = published
+            .iter()
+            .map(|envelope| envelope.data["records"].as_u64().unwrap())
+            .collect();
+        assert_eq!(records, vec![1, 2, 3]);
+    }
+}
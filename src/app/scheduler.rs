@@ -6,8 +6,10 @@ use tokio_cron_scheduler::{Job, JobScheduler};
 
 use crate::{
     app::backfill::{
-        delisted_company, dividend, etf, financial_statement, isin, net_asset_value_per_share,
-        qualified_foreign_institutional_investor, revenue, stock_weight,
+        corporate_action, delisted_company, derivatives, dividend, etf, financial_statement,
+        institutional_investor, isin, margin_trading, net_asset_value_per_share,
+        qualified_foreign_institutional_investor, reprocess, revenue, shareholding_distribution,
+        stock_weight,
     },
     app::calculation,
     app::event,
@@ -72,6 +74,18 @@ async fn run_cron(sched: &JobScheduler) -> Result<()> {
     // 解讀 cron 表達式，因此以下註解標示的時間皆為台北時間。
 
     let jobs = vec![
+        // 每 5 分鐘 重送證券主檔同步佇列中已到重試時間的證券
+        create_job(
+            "0 */5 * * * *",
+            "重送證券主檔同步佇列",
+            event::stock_sync::execute,
+        ),
+        // 00:30 清除超過保留天數的原始回應封存
+        create_job(
+            "0 30 0 * * *",
+            "清除過期原始回應封存",
+            reprocess::purge_archive,
+        ),
         // 01:00 更新興櫃股票的每股淨值
         create_job(
             "0 0 1 * * *",
@@ -96,6 +110,13 @@ async fn run_cron(sched: &JobScheduler) -> Result<()> {
             "補齊季報零淨值之 ROE/ROA 數據",
             financial_statement::quarter::execute,
         ),
+        // 每週六 04:30 重抓目標季度的 MOPS XBRL 季報，更新三大報表科目
+        // 公司陸續申報期間壓縮檔會持續增加，一週一次即可涵蓋
+        create_job(
+            "0 30 4 * * Sat",
+            "更新季報三大報表科目(XBRL)",
+            financial_statement::line_item::execute,
+        ),
         // 05:00 更新台股年度財報(僅有eps 等少數欄位的資料)
         create_job(
             "0 0 5 * * *",
@@ -134,6 +155,19 @@ async fn run_cron(sched: &JobScheduler) -> Result<()> {
             "計算各期間年化報酬率(CAGR)",
             calculation::cagr::execute_scheduled,
         ),
+        // 05:45 全量比對 Go 服務的證券主檔，排在 ISIN、下市、ETF 更新之後
+        create_job(
+            "0 45 5 * * *",
+            "比對證券主檔同步狀態",
+            event::stock_sync::reconcile,
+        ),
+        // 05:50 回測便宜／合理／昂貴價訊號的 3、6、12 個月向後報酬
+        // 與 CAGR 共用除權息資料，排在其後
+        create_job(
+            "0 50 5 * * *",
+            "回測估價訊號向後報酬",
+            calculation::estimate_backtest::execute_scheduled,
+        ),
         // 08:00 提醒本日除權息與明日預計除權息的股票
         create_job(
             "0 0 8 * * *",
@@ -146,12 +180,25 @@ async fn run_cron(sched: &JobScheduler) -> Result<()> {
             "提醒本日持股股利發放",
             event::taiwan_stock::payable_date::execute,
         ),
-        // 08:04 提醒本日開始公開申購的股票
+        // 08:04 提醒公開申購：申購期間依預期獲利排序，截止日與抽籤日再各提醒一次
         create_job(
             "0 4 8 * * *",
-            "提醒本日公開申購股票",
+            "提醒公開申購",
             event::taiwan_stock::public::execute,
         ),
+        // 每月 11 日 08:06 彙整持股與追蹤清單的月營收驚喜（月營收於每月 10 日前公布完畢，
+        // 05:15 的月營收更新會一併重算動能）
+        create_job(
+            "0 6 8 11 * *",
+            "月營收驚喜摘要",
+            event::taiwan_stock::revenue_surprise::execute,
+        ),
+        // 07:00~21:50 平日每 10 分鐘輪詢重大訊息，通知持股與追蹤清單
+        create_job(
+            "0 */10 7-21 * * Mon-Fri",
+            "輪詢重大訊息",
+            event::taiwan_stock::material_information::execute,
+        ),
         // 09:00 更新股票權值佔比
         create_job("0 0 9 * * *", "更新股票加權權值佔比", stock_weight::execute),
         // 09:02 提醒本日已達高低標的股票有那些
@@ -166,8 +213,43 @@ async fn run_cron(sched: &JobScheduler) -> Result<()> {
             "取得每日收盤行情與報價",
             event::taiwan_stock::closing::execute,
         ),
+        // 15:30 取得臺指期貨行情、法人期貨部位與賣權買權比（期貨 13:45 收盤，期交所約 15:00 公布）
+        create_job(
+            "0 30 15 * * *",
+            "取得期貨選擇權市場資料",
+            derivatives::execute,
+        ),
+        // 16:40 取得三大法人買賣超（交易所約 16:00 後公布）
+        create_job(
+            "0 40 16 * * *",
+            "取得三大法人買賣超",
+            institutional_investor::execute,
+        ),
+        // 17:00 偵測減資、變更面額與無法以除權息解釋的跳空，建立待確認的公司行動建議
+        // （需排在 15:00 收盤報價之後，跳空偵測才看得到當日價格）
+        create_job("0 0 17 * * *", "偵測公司行動", corporate_action::execute),
+        // 18:30 取得注意股與處置股並通知持股與追蹤清單的進出（交易所約 18:00 後公告）
+        create_job(
+            "0 30 18 * * Mon-Fri",
+            "注意股與處置股通知",
+            event::taiwan_stock::trading_surveillance::execute,
+        ),
         // 21:00 資料庫內尚未有年度配息數據的股票取出後向第三方查詢後更新回資料庫
         create_job("0 0 21 * * *", "補齊缺失之年度配息數據", dividend::execute),
+        // 21:30 取得融資融券餘額（交易所約 21:00 公布）
+        create_job("0 30 21 * * *", "取得融資融券餘額", margin_trading::execute),
+        // 週五 20:00 取得集保戶股權分散表（TDCC 通常於週五傍晚公布當週資料）
+        create_job(
+            "0 0 20 * * Fri",
+            "取得集保戶股權分散表",
+            shareholding_distribution::execute,
+        ),
+        // 週六 10:00 再取一次集保戶股權分散表，補週五延遲公布的情況
+        create_job(
+            "0 0 10 * * Sat",
+            "補取集保戶股權分散表",
+            shareholding_distribution::execute,
+        ),
         // 22:00 外資持股狀態
         create_job(
             "0 0 22 * * *",
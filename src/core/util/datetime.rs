@@ -60,6 +60,17 @@ impl ReportQuarter {
             Quarter::Q4 => Self::new(self.year + 1, Quarter::Q1),
         }
     }
+
+    /// 回傳季度的最後一天（資產負債表日）。
+    pub fn end_date(self) -> NaiveDate {
+        let (month, day) = match self.quarter {
+            Quarter::Q1 => (3, 31),
+            Quarter::Q2 => (6, 30),
+            Quarter::Q3 => (9, 30),
+            Quarter::Q4 => (12, 31),
+        };
+        NaiveDate::from_ymd_opt(self.year, month, day).expect("quarter end should be a valid date")
+    }
 }
 
 /// Yahoo 補欄位流程提前預抓下一季財報的預設觀察視窗天數。
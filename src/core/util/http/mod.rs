@@ -47,16 +47,21 @@ const MAX_RESPONSE_BODY_BYTES: usize = 8 * 1024 * 1024;
 ///
 /// 這取代了 `res.bytes()` / `res.text()` 這類「一次全部讀進記憶體、
 /// 沒有上限」的讀法。
-async fn read_limited_body(mut res: Response, url: &str) -> Result<Vec<u8>> {
+async fn read_limited_body(res: Response, url: &str) -> Result<Vec<u8>> {
+    read_body_with_limit(res, url, MAX_RESPONSE_BODY_BYTES).await
+}
+
+/// 與 [`read_limited_body`] 相同，但由呼叫端指定大小上限。
+async fn read_body_with_limit(mut res: Response, url: &str, max_bytes: usize) -> Result<Vec<u8>> {
     // 錯誤訊息中的 URL 一律先脫敏，避免把 Telegram token 等敏感片段寫進 log。
     let safe_url = redact_url(url);
 
     // 第一道：宣告大小檢查（若 upstream 有提供）。
     if let Some(content_length) = res.content_length()
-        && content_length > MAX_RESPONSE_BODY_BYTES as u64
+        && content_length > max_bytes as u64
     {
         bail!(
-            "response body too large for {safe_url}: content-length {content_length} bytes exceeds limit {MAX_RESPONSE_BODY_BYTES}"
+            "response body too large for {safe_url}: content-length {content_length} bytes exceeds limit {max_bytes}"
         );
     }
 
@@ -67,10 +72,8 @@ async fn read_limited_body(mut res: Response, url: &str) -> Result<Vec<u8>> {
         .await
         .with_context(|| format!("Error reading response body from {safe_url}"))?
     {
-        if body.len() + chunk.len() > MAX_RESPONSE_BODY_BYTES {
-            bail!(
-                "response body too large for {safe_url}: exceeds limit {MAX_RESPONSE_BODY_BYTES} bytes"
-            );
+        if body.len() + chunk.len() > max_bytes {
+            bail!("response body too large for {safe_url}: exceeds limit {max_bytes} bytes");
         }
         body.extend_from_slice(&chunk);
     }
@@ -165,29 +168,41 @@ fn get_client() -> Result<&'static Client> {
 ///
 /// * `Result<RES>`: The deserialized response, or an error if the request fails or the response cannot be deserialized.
 pub async fn get_json<RES: DeserializeOwned>(url: &str) -> Result<RES> {
+    let body = get_body(url).await?;
+    parse_json(url, &body)
+}
+
+/// 執行 HTTP GET 並回傳成功回應的原始 body，不解析內容。
+///
+/// 供需要封存原始回應的爬蟲使用（見 `infra::archive::fetch_json`）：先封存
+/// 再以 [`parse_json`] 解析，解析失敗的回應同樣留得下來，修正解析規則後可重跑。
+pub async fn get_body(url: &str) -> Result<Vec<u8>> {
     let res = get_response(url, None).await?;
     let status = res.status();
     // 以「串流 + 大小上限」讀取 body，取代無上限的 res.bytes()。
     let res_body = read_limited_body(res, url).await?;
-    let res_body_preview = String::from_utf8_lossy(res_body.as_ref());
-    let safe_url = redact_url(url);
 
     if !status.is_success() {
         return Err(anyhow!(
             "HTTP request failed with status {} for {}. Body: {}",
             status,
-            safe_url,
-            util::text::truncate(&res_body_preview, 200)
+            redact_url(url),
+            util::text::truncate(&String::from_utf8_lossy(&res_body), 200)
         ));
     }
 
+    Ok(res_body)
+}
+
+/// 將 `url` 回傳的 body 解析為 JSON；錯誤訊息附上遮蔽後的 URL 與 body 片段。
+pub fn parse_json<RES: DeserializeOwned>(url: &str, body: &[u8]) -> Result<RES> {
     // with_context 保留 serde 原始錯誤在 source chain；
     // 錯誤訊息中的 body 一律用 truncate 依字元截斷成固定長度片段。
-    serde_json::from_slice(res_body.as_ref()).with_context(|| {
+    serde_json::from_slice(body).with_context(|| {
         format!(
             "Error parsing response JSON from {}. Body: {}",
-            safe_url,
-            util::text::truncate(&res_body_preview, 200)
+            redact_url(url),
+            util::text::truncate(&String::from_utf8_lossy(body), 200)
         )
     })
 }
@@ -212,6 +227,23 @@ pub(crate) async fn get_response_with_client(
     send_with_client(client, Method::GET, url, headers, None::<fn(_) -> _>, None).await
 }
 
+/// 執行 HTTP GET 並回傳原始位元組，body 上限由呼叫端指定。
+///
+/// 供下載壓縮檔等明確超過 8 MiB 預設上限的來源使用；非 2xx 狀態視為錯誤。
+pub async fn get_bytes(url: &str, max_bytes: usize) -> Result<Vec<u8>> {
+    let res = get_response(url, None).await?;
+    let status = res.status();
+    if !status.is_success() {
+        return Err(anyhow!(
+            "HTTP request failed with status {} for {}",
+            status,
+            redact_url(url)
+        ));
+    }
+
+    read_body_with_limit(res, url, max_bytes).await
+}
+
 /// Performs an HTTP GET request and returns the response as text.
 ///
 /// # Arguments
@@ -360,6 +392,37 @@ pub async fn post(
     response.text().await.context("Error parsing response text")
 }
 
+/// Performs an HTTP POST request with form data and returns the response as Big5 encoded text.
+///
+/// # Arguments
+///
+/// * `url`: The URL to send the POST request to.
+/// * `headers`: An optional set of headers to include with the request.
+/// * `params`: A map of form data key-value pairs.
+///
+/// # Returns
+///
+/// * `Result<String>`: The Big5 decoded response text, or an error if the request fails
+///   or the response cannot be parsed.
+pub async fn post_use_big5(
+    url: &str,
+    headers: Option<header::HeaderMap>,
+    params: HashMap<&str, &str>,
+) -> Result<String> {
+    let request_detail = format_form_params_log(&params);
+    send(
+        Method::POST,
+        url,
+        headers,
+        Some(move |rb: RequestBuilder| rb.form(&params)),
+        Some(request_detail),
+    )
+    .await?
+    .text_force_big5()
+    .await
+    .context("Error parsing response text use BIG5")
+}
+
 /// 以指定方法、URL、headers、body 發送 HTTP 請求，含雙層重試：
 /// - **網路層**（TCP 失敗）：最多 `MAX_NETWORK_RETRIES` 次，2^n 秒 backoff。
 /// - **頻率限制**（HTTP 429）：最多 `MAX_RATE_LIMIT_RETRIES` 次，5/15/30s + 最多 2s jitter。
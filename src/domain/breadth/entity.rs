@@ -0,0 +1,214 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+/// 站上均線比例所涵蓋的均線天數，順序同 `daily_stock_price_stats` 欄位。
+pub const MOVING_AVERAGE_PERIODS: [i32; 5] = [5, 20, 60, 120, 240];
+
+/// McClellan 19 日指數移動平均的平滑係數（2 / (19 + 1)）。
+const FAST_ALPHA: Decimal = dec!(0.1);
+
+/// McClellan 39 日指數移動平均的平滑係數（2 / (39 + 1)）。
+const SLOW_ALPHA: Decimal = dec!(0.05);
+
+/// 指標的小數位數；遞推前先四捨五入，使逐日計算與整段重算的結果一致。
+const SCALE: u32 = 4;
+
+/// 單一交易日、單一市場的廣度原始統計。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BreadthCounts {
+    /// 統計日期。
+    pub date: NaiveDate,
+    /// 市場編號：全部 0、上市 2、上櫃 4。
+    pub market_id: i32,
+    /// 上漲家數。
+    pub advances: i32,
+    /// 下跌家數。
+    pub declines: i32,
+    /// 平盤家數。
+    pub unchanged: i32,
+    /// 最高價觸及近 240 個交易日最高價的家數。
+    pub new_highs: i32,
+    /// 最低價觸及近 240 個交易日最低價的家數。
+    pub new_lows: i32,
+    /// 依 [`MOVING_AVERAGE_PERIODS`] 順序，股價站上各均線的家數。
+    pub above_moving_average: [i32; 5],
+    /// 依 [`MOVING_AVERAGE_PERIODS`] 順序，股價跌破各均線的家數。
+    pub below_moving_average: [i32; 5],
+}
+
+/// 單一交易日、單一市場的衍生廣度指標。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BreadthIndicator {
+    /// 統計日期。
+    pub date: NaiveDate,
+    /// 市場編號：全部 0、上市 2、上櫃 4。
+    pub market_id: i32,
+    /// 上漲家數。
+    pub advances: i32,
+    /// 下跌家數。
+    pub declines: i32,
+    /// 平盤家數。
+    pub unchanged: i32,
+    /// 上漲減下跌家數。
+    pub net_advances: i32,
+    /// 騰落線：淨上漲家數的累計值。
+    pub ad_line: i64,
+    /// 比例調整淨上漲：(上漲 − 下跌) / (上漲 + 下跌) × 1000。
+    pub ratio_adjusted_net_advances: Decimal,
+    /// 比例調整淨上漲的 19 日指數移動平均。
+    pub ema_19: Decimal,
+    /// 比例調整淨上漲的 39 日指數移動平均。
+    pub ema_39: Decimal,
+    /// McClellan 擺盪指標：19 日 EMA − 39 日 EMA。
+    pub mcclellan_oscillator: Decimal,
+    /// McClellan 總和指數：擺盪指標的累計值。
+    pub summation_index: Decimal,
+    /// 創年內新高家數。
+    pub new_highs: i32,
+    /// 創年內新低家數。
+    pub new_lows: i32,
+    /// 依 [`MOVING_AVERAGE_PERIODS`] 順序，站上各均線的家數比例（%）；
+    /// 當日沒有可比較的股票時為 `None`。
+    pub pct_above_moving_average: [Option<Decimal>; 5],
+}
+
+impl BreadthIndicator {
+    /// 由當日統計與同市場前一交易日的指標遞推當日指標。
+    ///
+    /// 沒有前一日時（首日或第一次回補）以當日的比例調整淨上漲作為兩條 EMA
+    /// 的起始值，擺盪指標因此從 0 起算；騰落線與總和指數也從當日值起算。
+    /// 這些累計指標看的是走勢而非絕對水位，起點不同不影響判讀。
+    pub fn next(counts: &BreadthCounts, previous: Option<&BreadthIndicator>) -> Self {
+        let net_advances = counts.advances - counts.declines;
+        let issues = counts.advances + counts.declines;
+        let ratio_adjusted = if issues > 0 {
+            (Decimal::from(net_advances) * dec!(1000) / Decimal::from(issues)).round_dp(SCALE)
+        } else {
+            Decimal::ZERO
+        };
+
+        let (ad_line, ema_19, ema_39, summation_base) = match previous {
+            Some(previous) => (
+                previous.ad_line + i64::from(net_advances),
+                ema(previous.ema_19, ratio_adjusted, FAST_ALPHA),
+                ema(previous.ema_39, ratio_adjusted, SLOW_ALPHA),
+                previous.summation_index,
+            ),
+            None => (
+                i64::from(net_advances),
+                ratio_adjusted,
+                ratio_adjusted,
+                Decimal::ZERO,
+            ),
+        };
+        let mcclellan_oscillator = ema_19 - ema_39;
+
+        let mut pct_above_moving_average = [None; 5];
+        for (index, pct) in pct_above_moving_average.iter_mut().enumerate() {
+            let above = counts.above_moving_average[index];
+            let total = above + counts.below_moving_average[index];
+            if total > 0 {
+                *pct = Some((Decimal::from(above) * dec!(100) / Decimal::from(total)).round_dp(2));
+            }
+        }
+
+        BreadthIndicator {
+            date: counts.date,
+            market_id: counts.market_id,
+            advances: counts.advances,
+            declines: counts.declines,
+            unchanged: counts.unchanged,
+            net_advances,
+            ad_line,
+            ratio_adjusted_net_advances: ratio_adjusted,
+            ema_19,
+            ema_39,
+            mcclellan_oscillator,
+            summation_index: summation_base + mcclellan_oscillator,
+            new_highs: counts.new_highs,
+            new_lows: counts.new_lows,
+            pct_above_moving_average,
+        }
+    }
+
+    /// 新高減新低家數。
+    pub fn net_new_highs(&self) -> i32 {
+        self.new_highs - self.new_lows
+    }
+}
+
+/// 以平滑係數 `alpha` 將 `value` 併入前一期的指數移動平均。
+fn ema(previous: Decimal, value: Decimal, alpha: Decimal) -> Decimal {
+    (previous + alpha * (value - previous)).round_dp(SCALE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(day: u32, advances: i32, declines: i32) -> BreadthCounts {
+        BreadthCounts {
+            date: NaiveDate::from_ymd_opt(2025, 3, day).expect("測試日期應合法"),
+            market_id: 0,
+            advances,
+            declines,
+            unchanged: 10,
+            new_highs: 7,
+            new_lows: 3,
+            above_moving_average: [60, 55, 50, 45, 0],
+            below_moving_average: [40, 45, 50, 55, 0],
+        }
+    }
+
+    /// 首日以當日值作為 EMA 起點，擺盪指標為 0，累計指標從當日起算。
+    #[test]
+    fn first_day_seeds_from_own_value() {
+        let indicator = BreadthIndicator::next(&counts(3, 600, 400), None);
+        assert_eq!(indicator.net_advances, 200);
+        assert_eq!(indicator.ad_line, 200);
+        assert_eq!(indicator.ratio_adjusted_net_advances, dec!(200));
+        assert_eq!(indicator.ema_19, dec!(200));
+        assert_eq!(indicator.ema_39, dec!(200));
+        assert_eq!(indicator.mcclellan_oscillator, Decimal::ZERO);
+        assert_eq!(indicator.summation_index, Decimal::ZERO);
+        assert_eq!(indicator.net_new_highs(), 4);
+        assert_eq!(
+            indicator.pct_above_moving_average,
+            [
+                Some(dec!(60)),
+                Some(dec!(55)),
+                Some(dec!(50)),
+                Some(dec!(45)),
+                None
+            ]
+        );
+    }
+
+    /// 次日依 0.1 與 0.05 的平滑係數更新兩條 EMA，並累加騰落線與總和指數。
+    #[test]
+    fn next_day_accumulates_from_previous() {
+        let first = BreadthIndicator::next(&counts(3, 600, 400), None);
+        let second = BreadthIndicator::next(&counts(4, 300, 700), Some(&first));
+        assert_eq!(second.ad_line, -200);
+        assert_eq!(second.ratio_adjusted_net_advances, dec!(-400));
+        assert_eq!(second.ema_19, dec!(140));
+        assert_eq!(second.ema_39, dec!(170));
+        assert_eq!(second.mcclellan_oscillator, dec!(-30));
+        assert_eq!(second.summation_index, dec!(-30));
+
+        let third = BreadthIndicator::next(&counts(5, 500, 500), Some(&second));
+        assert_eq!(third.ad_line, -200);
+        assert_eq!(third.ema_19, dec!(126));
+        assert_eq!(third.ema_39, dec!(161.5));
+        assert_eq!(third.summation_index, dec!(-65.5));
+    }
+
+    /// 全數平盤或停牌時不除以零。
+    #[test]
+    fn no_issues_yields_zero_ratio() {
+        let indicator = BreadthIndicator::next(&counts(3, 0, 0), None);
+        assert_eq!(indicator.ratio_adjusted_net_advances, Decimal::ZERO);
+        assert_eq!(indicator.mcclellan_oscillator, Decimal::ZERO);
+    }
+}
@@ -0,0 +1,13 @@
+//! 市場廣度指標領域。
+//!
+//! 由 `daily_stock_price_stats` 的每日漲跌與均線家數，以及年內新高新低家數，
+//! 遞推出騰落線（A/D line）、McClellan 擺盪指標與總和指數、站上均線比例等
+//! 衍生指標，供收盤匯總寫入並以時間序列繪圖。
+
+/// 市場廣度指標實體模組。
+pub mod entity;
+/// 市場廣度指標倉儲介面模組。
+pub mod repository;
+
+pub use entity::{BreadthCounts, BreadthIndicator, MOVING_AVERAGE_PERIODS};
+pub use repository::BreadthIndicatorRepository;
@@ -0,0 +1,26 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use super::entity::{BreadthCounts, BreadthIndicator};
+
+/// 市場廣度指標倉儲介面。
+#[async_trait]
+pub trait BreadthIndicatorRepository: Send + Sync {
+    /// 讀取區間內（含頭尾）每個交易日、每個市場的廣度原始統計，依日期與市場排序。
+    async fn fetch_counts(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<BreadthCounts>>;
+
+    /// 讀取各市場在指定日期之前的最後一筆指標，作為遞推的前一日狀態。
+    async fn fetch_latest_before(&self, date: NaiveDate) -> Result<Vec<BreadthIndicator>>;
+
+    /// 寫入或覆蓋指標，回傳影響列數。
+    async fn upsert(&self, indicators: &[BreadthIndicator]) -> Result<u64>;
+
+    /// 讀取單一市場截至 `to`（未提供時為最新）的最近 `days` 筆指標，由新到舊。
+    async fn fetch_history(
+        &self,
+        market_id: i32,
+        to: Option<NaiveDate>,
+        days: i64,
+    ) -> Result<Vec<BreadthIndicator>>;
+}
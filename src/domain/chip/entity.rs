@@ -0,0 +1,402 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+/// 單一法人類別在某交易日的買進、賣出與買賣超股數。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TradeVolume {
+    /// 買進股數
+    pub buy: i64,
+    /// 賣出股數
+    pub sell: i64,
+    /// 買賣超股數（賣超為負數）
+    pub net: i64,
+}
+
+impl TradeVolume {
+    /// 建立買賣股數值物件。
+    pub fn new(buy: i64, sell: i64, net: i64) -> Self {
+        Self { buy, sell, net }
+    }
+}
+
+/// 個股單日三大法人買賣超領域實體。
+///
+/// 「外資」依交易所口徑不含外資自營商，外資自營商另計於 `foreign_dealer_net`；
+/// 自營商為自行買賣與避險的合計，兩者的買賣超另外保留以區分避險部位。
+/// 所有數量單位皆為「股」。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstitutionalTrade {
+    /// 股票代號
+    pub stock_symbol: String,
+    /// 交易日期
+    pub date: NaiveDate,
+    /// 外陸資（不含外資自營商）
+    pub foreign: TradeVolume,
+    /// 外資自營商買賣超股數
+    pub foreign_dealer_net: i64,
+    /// 投信
+    pub investment_trust: TradeVolume,
+    /// 自營商（自行買賣＋避險）
+    pub dealer: TradeVolume,
+    /// 自營商（自行買賣）買賣超股數
+    pub dealer_proprietary_net: i64,
+    /// 自營商（避險）買賣超股數
+    pub dealer_hedge_net: i64,
+    /// 三大法人買賣超股數合計（以來源公布值為準）
+    pub total_net: i64,
+}
+
+impl InstitutionalTrade {
+    /// 建立個股單日三大法人買賣超實體。
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        stock_symbol: String,
+        date: NaiveDate,
+        foreign: TradeVolume,
+        foreign_dealer_net: i64,
+        investment_trust: TradeVolume,
+        dealer: TradeVolume,
+        dealer_proprietary_net: i64,
+        dealer_hedge_net: i64,
+        total_net: i64,
+    ) -> Self {
+        Self {
+            stock_symbol,
+            date,
+            foreign,
+            foreign_dealer_net,
+            investment_trust,
+            dealer,
+            dealer_proprietary_net,
+            dealer_hedge_net,
+            total_net,
+        }
+    }
+
+    /// 外資及陸資合計買賣超（含外資自營商），即一般財經媒體所稱的「外資買賣超」。
+    pub fn foreign_total_net(&self) -> i64 {
+        self.foreign.net + self.foreign_dealer_net
+    }
+}
+
+/// 融資或融券單邊在某交易日的交易量與餘額。
+///
+/// 融資的「買進」是新增部位、「賣出」是了結；融券方向相反，
+/// 「賣出」為放空、「買進」為回補。數量單位皆為「張」。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CreditBalance {
+    /// 買進張數
+    pub buy: i64,
+    /// 賣出張數
+    pub sell: i64,
+    /// 現金（融資）或現券（融券）償還張數
+    pub redemption: i64,
+    /// 前日餘額
+    pub previous_balance: i64,
+    /// 今日餘額
+    pub balance: i64,
+    /// 限額
+    pub quota: i64,
+}
+
+impl CreditBalance {
+    /// 建立融資或融券單邊的值物件。
+    pub fn new(
+        buy: i64,
+        sell: i64,
+        redemption: i64,
+        previous_balance: i64,
+        balance: i64,
+        quota: i64,
+    ) -> Self {
+        Self {
+            buy,
+            sell,
+            redemption,
+            previous_balance,
+            balance,
+            quota,
+        }
+    }
+
+    /// 餘額較前日的增減張數。
+    pub fn change(&self) -> i64 {
+        self.balance - self.previous_balance
+    }
+
+    /// 使用率（%）＝ 今日餘額 ÷ 限額 × 100，四捨五入至小數 4 位；限額為 0 時無法計算。
+    pub fn utilization_percent(&self) -> Option<Decimal> {
+        percent(self.balance, self.quota)
+    }
+}
+
+/// 個股單日融資融券領域實體。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarginTrading {
+    /// 股票代號
+    pub stock_symbol: String,
+    /// 交易日期
+    pub date: NaiveDate,
+    /// 融資
+    pub margin: CreditBalance,
+    /// 融券
+    pub short: CreditBalance,
+    /// 資券互抵張數
+    pub offset: i64,
+}
+
+impl MarginTrading {
+    /// 建立個股單日融資融券實體。
+    pub fn new(
+        stock_symbol: String,
+        date: NaiveDate,
+        margin: CreditBalance,
+        short: CreditBalance,
+        offset: i64,
+    ) -> Self {
+        Self {
+            stock_symbol,
+            date,
+            margin,
+            short,
+            offset,
+        }
+    }
+
+    /// 券資比（%）＝ 融券餘額 ÷ 融資餘額 × 100，四捨五入至小數 4 位。
+    ///
+    /// 融資餘額為 0 時無法計算，回傳 `None` 而不是 0，以免被誤判為「沒有空單」。
+    pub fn short_to_margin_ratio_percent(&self) -> Option<Decimal> {
+        percent(self.short.balance, self.margin.balance)
+    }
+}
+
+/// 計算 `numerator ÷ denominator × 100`；分母不為正數時回傳 `None`。
+fn percent(numerator: i64, denominator: i64) -> Option<Decimal> {
+    if denominator <= 0 {
+        return None;
+    }
+    Some((Decimal::from(numerator) * Decimal::ONE_HUNDRED / Decimal::from(denominator)).round_dp(4))
+}
+
+/// 集保戶股權分散表的單一持股分級。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShareholdingTier {
+    /// 持股分級：1–15 為持股區間，16 為差異數調整，17 為合計
+    pub level: i32,
+    /// 人數
+    pub holders: i64,
+    /// 股數
+    pub shares: i64,
+    /// 占集保庫存數比例（%）
+    pub percentage: Decimal,
+}
+
+impl ShareholdingTier {
+    /// 建立持股分級值物件。
+    pub fn new(level: i32, holders: i64, shares: i64, percentage: Decimal) -> Self {
+        Self {
+            level,
+            holders,
+            shares,
+            percentage,
+        }
+    }
+}
+
+/// 持股 400 張以上的起始分級（400,001 股以上）。
+pub const LEVEL_OVER_400_LOTS: i32 = 12;
+/// 持股 1,000 張以上的分級（1,000,001 股以上）。
+pub const LEVEL_OVER_1000_LOTS: i32 = 15;
+/// 最後一個持股區間分級；之後的 16、17 為差異數調整與合計。
+const LAST_HOLDING_LEVEL: i32 = 15;
+/// 合計列的分級。
+const TOTAL_LEVEL: i32 = 17;
+
+/// 個股單週集保戶股權分散快照領域實體。
+///
+/// 保留來源公布的全部分級（含差異數調整與合計），衍生指標只加總 1–15 級的
+/// 持股區間，避免把合計列重複計入。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShareholdingDistribution {
+    /// 股票代號
+    pub stock_symbol: String,
+    /// 資料日期（每週最後一個營業日）
+    pub date: NaiveDate,
+    /// 依分級排序的持股分級
+    pub tiers: Vec<ShareholdingTier>,
+}
+
+/// 相鄰兩週股權分散快照的週變化。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShareholdingWeeklyChange {
+    /// 400 張以上大戶持股比例變化（百分點）
+    pub over_400_lots_percentage: Decimal,
+    /// 1,000 張以上大戶持股比例變化（百分點）
+    pub over_1000_lots_percentage: Decimal,
+    /// 400 張以上大戶人數變化
+    pub over_400_lots_holders: i64,
+    /// 總股東人數變化
+    pub total_holders: i64,
+}
+
+impl ShareholdingDistribution {
+    /// 建立股權分散快照，分級依 `level` 排序。
+    pub fn new(stock_symbol: String, date: NaiveDate, mut tiers: Vec<ShareholdingTier>) -> Self {
+        tiers.sort_by_key(|tier| tier.level);
+        Self {
+            stock_symbol,
+            date,
+            tiers,
+        }
+    }
+
+    /// 持股區間（1–15 級）中不低於 `level` 的分級。
+    fn holding_tiers_from(&self, level: i32) -> impl Iterator<Item = &ShareholdingTier> {
+        self.tiers
+            .iter()
+            .filter(move |tier| (level..=LAST_HOLDING_LEVEL).contains(&tier.level))
+    }
+
+    /// 持股分級不低於 `level` 的持股比例合計（%）。
+    pub fn percentage_at_or_above(&self, level: i32) -> Decimal {
+        self.holding_tiers_from(level)
+            .map(|tier| tier.percentage)
+            .sum()
+    }
+
+    /// 持股分級不低於 `level` 的人數合計。
+    pub fn holders_at_or_above(&self, level: i32) -> i64 {
+        self.holding_tiers_from(level)
+            .map(|tier| tier.holders)
+            .sum()
+    }
+
+    /// 總股東人數；以合計列為準，缺少合計列時加總 1–15 級。
+    pub fn total_holders(&self) -> i64 {
+        self.tiers
+            .iter()
+            .find(|tier| tier.level == TOTAL_LEVEL)
+            .map(|tier| tier.holders)
+            .unwrap_or_else(|| self.holders_at_or_above(1))
+    }
+
+    /// 與前一週快照比較的大戶持股與股東人數變化。
+    pub fn week_over_week(&self, previous: &ShareholdingDistribution) -> ShareholdingWeeklyChange {
+        ShareholdingWeeklyChange {
+            over_400_lots_percentage: self.percentage_at_or_above(LEVEL_OVER_400_LOTS)
+                - previous.percentage_at_or_above(LEVEL_OVER_400_LOTS),
+            over_1000_lots_percentage: self.percentage_at_or_above(LEVEL_OVER_1000_LOTS)
+                - previous.percentage_at_or_above(LEVEL_OVER_1000_LOTS),
+            over_400_lots_holders: self.holders_at_or_above(LEVEL_OVER_400_LOTS)
+                - previous.holders_at_or_above(LEVEL_OVER_400_LOTS),
+            total_holders: self.total_holders() - previous.total_holders(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn foreign_total_net_includes_foreign_dealer() {
+        let trade = InstitutionalTrade::new(
+            "2330".to_string(),
+            NaiveDate::from_ymd_opt(2026, 4, 30).unwrap(),
+            TradeVolume::new(300, 500, -200),
+            50,
+            TradeVolume::default(),
+            TradeVolume::default(),
+            0,
+            0,
+            -150,
+        );
+
+        assert_eq!(trade.foreign_total_net(), -150);
+    }
+
+    #[test]
+    fn margin_trading_ratios() {
+        let trading = MarginTrading::new(
+            "2330".to_string(),
+            NaiveDate::from_ymd_opt(2026, 4, 30).unwrap(),
+            CreditBalance::new(120, 80, 0, 1_960, 2_000, 8_000),
+            CreditBalance::new(30, 60, 0, 270, 300, 8_000),
+            5,
+        );
+
+        assert_eq!(trading.margin.change(), 40);
+        assert_eq!(trading.short.change(), 30);
+        assert_eq!(trading.margin.utilization_percent(), Some(dec!(25)));
+        assert_eq!(trading.short_to_margin_ratio_percent(), Some(dec!(15)));
+    }
+
+    #[test]
+    fn margin_trading_ratios_without_denominator_are_none() {
+        let trading = MarginTrading::new(
+            "0050".to_string(),
+            NaiveDate::from_ymd_opt(2026, 4, 30).unwrap(),
+            CreditBalance::default(),
+            CreditBalance::new(0, 10, 0, 0, 10, 0),
+            0,
+        );
+
+        assert_eq!(trading.margin.utilization_percent(), None);
+        assert_eq!(trading.short_to_margin_ratio_percent(), None);
+    }
+
+    fn distribution(date: NaiveDate, big: Decimal, whale: Decimal) -> ShareholdingDistribution {
+        ShareholdingDistribution::new(
+            "2330".to_string(),
+            date,
+            vec![
+                ShareholdingTier::new(17, 1_000, 100_000_000, dec!(100)),
+                ShareholdingTier::new(1, 900, 1_000_000, dec!(100) - big),
+                ShareholdingTier::new(12, 60, 1_000_000, big - whale),
+                ShareholdingTier::new(15, 40, 1_000_000, whale),
+                ShareholdingTier::new(16, 0, 0, dec!(0)),
+            ],
+        )
+    }
+
+    #[test]
+    fn shareholding_big_holder_metrics_ignore_total_rows() {
+        let date = NaiveDate::from_ymd_opt(2026, 4, 24).unwrap();
+        let snapshot = distribution(date, dec!(70.5), dec!(60));
+
+        assert_eq!(snapshot.tiers[0].level, 1);
+        assert_eq!(
+            snapshot.percentage_at_or_above(LEVEL_OVER_400_LOTS),
+            dec!(70.5)
+        );
+        assert_eq!(
+            snapshot.percentage_at_or_above(LEVEL_OVER_1000_LOTS),
+            dec!(60)
+        );
+        assert_eq!(snapshot.holders_at_or_above(LEVEL_OVER_400_LOTS), 100);
+        assert_eq!(snapshot.total_holders(), 1_000);
+    }
+
+    #[test]
+    fn shareholding_week_over_week_change() {
+        let previous = distribution(
+            NaiveDate::from_ymd_opt(2026, 4, 17).unwrap(),
+            dec!(70),
+            dec!(61),
+        );
+        let current = distribution(
+            NaiveDate::from_ymd_opt(2026, 4, 24).unwrap(),
+            dec!(70.5),
+            dec!(60),
+        );
+
+        let change = current.week_over_week(&previous);
+
+        assert_eq!(change.over_400_lots_percentage, dec!(0.5));
+        assert_eq!(change.over_1000_lots_percentage, dec!(-1));
+        assert_eq!(change.over_400_lots_holders, 0);
+        assert_eq!(change.total_holders, 0);
+    }
+}
@@ -0,0 +1,16 @@
+//! 籌碼面領域。
+//!
+//! 收錄交易所每日公布、反映資金動向的籌碼資料，例如三大法人買賣超、融資融券餘額與集保戶股權分散。
+
+/// 籌碼面領域實體模組。
+pub mod entity;
+/// 籌碼面倉儲介面模組。
+pub mod repository;
+
+pub use entity::{
+    CreditBalance, InstitutionalTrade, LEVEL_OVER_400_LOTS, LEVEL_OVER_1000_LOTS, MarginTrading,
+    ShareholdingDistribution, ShareholdingTier, ShareholdingWeeklyChange, TradeVolume,
+};
+pub use repository::{
+    InstitutionalTradeRepository, MarginTradingRepository, ShareholdingDistributionRepository,
+};
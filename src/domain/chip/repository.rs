@@ -0,0 +1,67 @@
+use super::entity::{InstitutionalTrade, MarginTrading, ShareholdingDistribution};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+/// 三大法人買賣超的倉儲合約 (Repository Trait)。
+#[async_trait]
+pub trait InstitutionalTradeRepository: Send + Sync {
+    /// 以整日原子替換寫入某交易日的三大法人買賣超，回傳寫入筆數。
+    ///
+    /// 同一交易日的上市與上櫃資料必須一起傳入：實作會先刪除該日全部資料再寫入，
+    /// 只傳一個市場會把另一個市場的資料一併清掉。
+    async fn replace_by_date(&self, date: NaiveDate, trades: &[InstitutionalTrade]) -> Result<u64>;
+
+    /// 查詢個股在 `[from, to]` 區間內的三大法人買賣超（依日期降序）。
+    ///
+    /// # 參數
+    /// * `from` / `to` - 可選的日期界線，`None` 代表不限制。
+    /// * `limit` - 最多回傳筆數。
+    async fn fetch_by_symbol(
+        &self,
+        stock_symbol: &str,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+        limit: i64,
+    ) -> Result<Vec<InstitutionalTrade>>;
+}
+
+/// 融資融券餘額的倉儲合約 (Repository Trait)。
+#[async_trait]
+pub trait MarginTradingRepository: Send + Sync {
+    /// 以整日原子替換寫入某交易日的融資融券餘額，回傳寫入筆數。
+    ///
+    /// 與三大法人相同，上市與上櫃資料必須一起傳入。
+    async fn replace_by_date(&self, date: NaiveDate, tradings: &[MarginTrading]) -> Result<u64>;
+
+    /// 查詢個股在 `[from, to]` 區間內的融資融券餘額（依日期降序）。
+    ///
+    /// # 參數
+    /// * `from` / `to` - 可選的日期界線，`None` 代表不限制。
+    /// * `limit` - 最多回傳筆數。
+    async fn fetch_by_symbol(
+        &self,
+        stock_symbol: &str,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+        limit: i64,
+    ) -> Result<Vec<MarginTrading>>;
+}
+
+/// 集保戶股權分散表的倉儲合約 (Repository Trait)。
+#[async_trait]
+pub trait ShareholdingDistributionRepository: Send + Sync {
+    /// 以整週原子替換寫入某資料日期的股權分散快照，回傳寫入的分級列數。
+    async fn replace_by_date(
+        &self,
+        date: NaiveDate,
+        distributions: &[ShareholdingDistribution],
+    ) -> Result<u64>;
+
+    /// 查詢個股最近 `limit` 週的股權分散快照（依日期降序）。
+    async fn fetch_by_symbol(
+        &self,
+        stock_symbol: &str,
+        limit: i64,
+    ) -> Result<Vec<ShareholdingDistribution>>;
+}
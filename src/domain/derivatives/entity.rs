@@ -0,0 +1,262 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+/// 臺股期貨契約代號。
+pub const CONTRACT_TX: &str = "TX";
+/// 小型臺指期貨契約代號。
+pub const CONTRACT_MTX: &str = "MTX";
+
+/// 期交所三大法人的身份別。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TraderType {
+    /// 自營商
+    Dealer,
+    /// 投信
+    InvestmentTrust,
+    /// 外資及陸資
+    ForeignInvestor,
+}
+
+impl TraderType {
+    /// 由期交所公布的身份別名稱轉換；無法辨識時回傳 `None`。
+    pub fn from_source_name(name: &str) -> Option<Self> {
+        match name.trim() {
+            "自營商" => Some(Self::Dealer),
+            "投信" => Some(Self::InvestmentTrust),
+            "外資及陸資" | "外資" => Some(Self::ForeignInvestor),
+            _ => None,
+        }
+    }
+
+    /// 儲存與 API 使用的穩定代碼。
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Dealer => "dealer",
+            Self::InvestmentTrust => "investment_trust",
+            Self::ForeignInvestor => "foreign",
+        }
+    }
+
+    /// 由 [`TraderType::code`] 產生的代碼還原。
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "dealer" => Some(Self::Dealer),
+            "investment_trust" => Some(Self::InvestmentTrust),
+            "foreign" => Some(Self::ForeignInvestor),
+            _ => None,
+        }
+    }
+}
+
+/// 期貨單一契約月份的每日行情（一般交易時段）領域實體。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuturesDailyQuote {
+    /// 交易日期
+    pub date: NaiveDate,
+    /// 契約代號（`TX`、`MTX`）
+    pub contract: String,
+    /// 到期月份（`YYYYMM`；週契約帶 `W` 後綴）
+    pub contract_month: String,
+    /// 開盤價；當日無成交時為 `None`
+    pub open: Option<Decimal>,
+    /// 最高價
+    pub high: Option<Decimal>,
+    /// 最低價
+    pub low: Option<Decimal>,
+    /// 收盤價
+    pub close: Option<Decimal>,
+    /// 漲跌價
+    pub change: Option<Decimal>,
+    /// 成交量（口）
+    pub volume: i64,
+    /// 結算價
+    pub settlement_price: Option<Decimal>,
+    /// 未沖銷契約數（口）
+    pub open_interest: i64,
+}
+
+impl FuturesDailyQuote {
+    /// 是否為月契約；週契約與其他特殊月份代碼不參與近月判斷。
+    pub fn is_monthly(&self) -> bool {
+        self.contract_month.len() == 6 && self.contract_month.bytes().all(|b| b.is_ascii_digit())
+    }
+}
+
+/// 單一法人身份別在單一期貨契約的交易與未平倉部位領域實體。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuturesInstitutionalPosition {
+    /// 交易日期
+    pub date: NaiveDate,
+    /// 契約代號（`TX`、`MTX`）
+    pub contract: String,
+    /// 法人身份別
+    pub trader_type: TraderType,
+    /// 多方交易口數
+    pub long_trade_volume: i64,
+    /// 空方交易口數
+    pub short_trade_volume: i64,
+    /// 多空交易口數淨額
+    pub net_trade_volume: i64,
+    /// 多方未平倉口數
+    pub long_open_interest: i64,
+    /// 空方未平倉口數
+    pub short_open_interest: i64,
+    /// 多空未平倉口數淨額
+    pub net_open_interest: i64,
+    /// 多空未平倉契約金額淨額（千元）
+    pub net_open_interest_amount: i64,
+}
+
+/// 臺指選擇權每日賣權買權比領域實體。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PutCallRatio {
+    /// 交易日期
+    pub date: NaiveDate,
+    /// 賣權成交量
+    pub put_volume: i64,
+    /// 買權成交量
+    pub call_volume: i64,
+    /// 買賣權成交量比率（%）
+    pub volume_ratio_percent: Decimal,
+    /// 賣權未平倉量
+    pub put_open_interest: i64,
+    /// 買權未平倉量
+    pub call_open_interest: i64,
+    /// 買賣權未平倉量比率（%）
+    pub open_interest_ratio_percent: Decimal,
+}
+
+/// 單一交易日的期貨選擇權市場快照。
+///
+/// 期交所三份資料公布時間不同，賣權買權比可能暫缺；衍生指標一律回傳
+/// `Option`，缺資料時不補 0。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivativesDailySnapshot {
+    /// 交易日期
+    pub date: NaiveDate,
+    /// 各契約月份的期貨行情
+    pub futures: Vec<FuturesDailyQuote>,
+    /// 三大法人期貨部位
+    pub positions: Vec<FuturesInstitutionalPosition>,
+    /// 臺指選擇權賣權買權比
+    pub put_call_ratio: Option<PutCallRatio>,
+}
+
+impl DerivativesDailySnapshot {
+    /// 指定契約的近月（到期月份最早的月契約）行情。
+    pub fn front_month(&self, contract: &str) -> Option<&FuturesDailyQuote> {
+        self.futures
+            .iter()
+            .filter(|quote| quote.contract == contract && quote.is_monthly())
+            .min_by(|a, b| a.contract_month.cmp(&b.contract_month))
+    }
+
+    /// 指定契約全部月份的未沖銷契約數合計。
+    pub fn total_open_interest(&self, contract: &str) -> i64 {
+        self.futures
+            .iter()
+            .filter(|quote| quote.contract == contract)
+            .map(|quote| quote.open_interest)
+            .sum()
+    }
+
+    /// 指定法人在指定契約的多空未平倉口數淨額。
+    pub fn net_open_interest(&self, contract: &str, trader_type: TraderType) -> Option<i64> {
+        self.positions
+            .iter()
+            .find(|position| position.contract == contract && position.trader_type == trader_type)
+            .map(|position| position.net_open_interest)
+    }
+
+    /// 臺股期貨近月收盤價相對現貨指數的基差（期貨 − 現貨，正值為正價差）。
+    pub fn basis(&self, index_close: Decimal) -> Option<Decimal> {
+        self.front_month(CONTRACT_TX)
+            .and_then(|quote| quote.close)
+            .map(|close| close - index_close)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn quote(contract: &str, month: &str, close: Option<Decimal>, oi: i64) -> FuturesDailyQuote {
+        FuturesDailyQuote {
+            date: NaiveDate::from_ymd_opt(2026, 4, 24).unwrap(),
+            contract: contract.to_string(),
+            contract_month: month.to_string(),
+            open: close,
+            high: close,
+            low: close,
+            close,
+            change: Some(dec!(10)),
+            volume: 100,
+            settlement_price: close,
+            open_interest: oi,
+        }
+    }
+
+    fn position(contract: &str, trader_type: TraderType, net: i64) -> FuturesInstitutionalPosition {
+        FuturesInstitutionalPosition {
+            date: NaiveDate::from_ymd_opt(2026, 4, 24).unwrap(),
+            contract: contract.to_string(),
+            trader_type,
+            long_trade_volume: 0,
+            short_trade_volume: 0,
+            net_trade_volume: 0,
+            long_open_interest: 0,
+            short_open_interest: 0,
+            net_open_interest: net,
+            net_open_interest_amount: 0,
+        }
+    }
+
+    /// 近月以到期月份最早的月契約為準，週契約不參與；基差以近月收盤計算。
+    #[test]
+    fn front_month_skips_weekly_contracts() {
+        let snapshot = DerivativesDailySnapshot {
+            date: NaiveDate::from_ymd_opt(2026, 4, 24).unwrap(),
+            futures: vec![
+                quote(CONTRACT_TX, "202606", Some(dec!(21050)), 1_000),
+                quote(CONTRACT_TX, "202604W4", Some(dec!(20990)), 500),
+                quote(CONTRACT_TX, "202605", Some(dec!(21100)), 80_000),
+                quote(CONTRACT_MTX, "202605", Some(dec!(21101)), 30_000),
+            ],
+            positions: vec![
+                position(CONTRACT_TX, TraderType::ForeignInvestor, -25_000),
+                position(CONTRACT_MTX, TraderType::ForeignInvestor, 3_000),
+            ],
+            put_call_ratio: None,
+        };
+
+        let front = snapshot.front_month(CONTRACT_TX).unwrap();
+        assert_eq!(front.contract_month, "202605");
+        assert_eq!(snapshot.total_open_interest(CONTRACT_TX), 81_500);
+        assert_eq!(snapshot.basis(dec!(21000)), Some(dec!(100)));
+        assert_eq!(
+            snapshot.net_open_interest(CONTRACT_TX, TraderType::ForeignInvestor),
+            Some(-25_000)
+        );
+        assert_eq!(
+            snapshot.net_open_interest(CONTRACT_TX, TraderType::Dealer),
+            None
+        );
+    }
+
+    #[test]
+    fn trader_type_round_trips_codes() {
+        for trader_type in [
+            TraderType::Dealer,
+            TraderType::InvestmentTrust,
+            TraderType::ForeignInvestor,
+        ] {
+            assert_eq!(TraderType::from_code(trader_type.code()), Some(trader_type));
+        }
+        assert_eq!(
+            TraderType::from_source_name("外資及陸資"),
+            Some(TraderType::ForeignInvestor)
+        );
+        assert_eq!(TraderType::from_source_name("其他"), None);
+    }
+}
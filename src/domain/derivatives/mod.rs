@@ -0,0 +1,15 @@
+//! 期貨選擇權市場領域。
+//!
+//! 收錄期交所每日公布、用來判讀大盤多空氣氛的衍生性商品資料，
+//! 例如臺指期貨行情、三大法人期貨未平倉部位與臺指選擇權賣權買權比。
+
+/// 期貨選擇權市場領域實體模組。
+pub mod entity;
+/// 期貨選擇權市場倉儲介面模組。
+pub mod repository;
+
+pub use entity::{
+    CONTRACT_MTX, CONTRACT_TX, DerivativesDailySnapshot, FuturesDailyQuote,
+    FuturesInstitutionalPosition, PutCallRatio, TraderType,
+};
+pub use repository::DerivativesRepository;
@@ -0,0 +1,20 @@
+use super::entity::DerivativesDailySnapshot;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+/// 期貨選擇權每日市場資料的倉儲合約 (Repository Trait)。
+#[async_trait]
+pub trait DerivativesRepository: Send + Sync {
+    /// 以整日原子替換寫入某交易日的期貨行情、法人部位與賣權買權比，回傳寫入筆數。
+    ///
+    /// 三類資料在同一交易內替換；快照缺少賣權買權比時會一併清除該日舊值。
+    async fn replace_by_date(&self, snapshot: &DerivativesDailySnapshot) -> Result<u64>;
+
+    /// 查詢 `[from, to]` 區間內每個交易日的快照（依日期降序）。
+    async fn fetch_range(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<DerivativesDailySnapshot>>;
+}
@@ -0,0 +1,177 @@
+use chrono::{NaiveDate, NaiveDateTime};
+
+/// 重大訊息的關鍵字分類。
+///
+/// 依序比對，先命中者為準：減資與面額變更的主旨常同時提到「股利」或
+/// 「董事會決議」，必須排在股利分派之前，才不會被歸到較不重要的類別。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnnouncementCategory {
+    /// 減資（現金減資、彌補虧損減資）
+    CapitalReduction,
+    /// 股票分割或面額變更
+    Split,
+    /// 買回庫藏股
+    Buyback,
+    /// 股利分派、除權息
+    DividendResolution,
+    /// 董事長、總經理等經營階層異動
+    ExecutiveChange,
+    /// 其他
+    Other,
+}
+
+/// 分類關鍵字表；每組的任一關鍵字出現在主旨即命中。
+const CATEGORY_KEYWORDS: [(AnnouncementCategory, &[&str]); 5] = [
+    (AnnouncementCategory::CapitalReduction, &["減資"]),
+    (
+        AnnouncementCategory::Split,
+        &["面額變更", "變更面額", "股票分割", "分割股票", "每股面額"],
+    ),
+    (
+        AnnouncementCategory::Buyback,
+        &["庫藏股", "買回本公司股份", "買回股份"],
+    ),
+    (
+        AnnouncementCategory::DividendResolution,
+        &["股利", "配息", "除權", "除息", "盈餘分配", "盈餘分派"],
+    ),
+    (
+        AnnouncementCategory::ExecutiveChange,
+        &[
+            "董事長異動",
+            "董事長變動",
+            "總經理異動",
+            "總經理變動",
+            "執行長異動",
+            "財務主管異動",
+            "會計主管異動",
+            "發言人異動",
+        ],
+    ),
+];
+
+impl AnnouncementCategory {
+    /// 依主旨關鍵字分類；未命中任何關鍵字時為 [`AnnouncementCategory::Other`]。
+    pub fn classify(subject: &str) -> Self {
+        CATEGORY_KEYWORDS
+            .iter()
+            .find(|(_, keywords)| keywords.iter().any(|keyword| subject.contains(keyword)))
+            .map(|(category, _)| *category)
+            .unwrap_or(Self::Other)
+    }
+
+    /// 儲存使用的穩定代碼。
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::CapitalReduction => "capital_reduction",
+            Self::Split => "split",
+            Self::Buyback => "buyback",
+            Self::DividendResolution => "dividend",
+            Self::ExecutiveChange => "executive_change",
+            Self::Other => "other",
+        }
+    }
+
+    /// 由 [`AnnouncementCategory::code`] 產生的代碼還原；未知代碼視為其他。
+    pub fn from_code(code: &str) -> Self {
+        match code {
+            "capital_reduction" => Self::CapitalReduction,
+            "split" => Self::Split,
+            "buyback" => Self::Buyback,
+            "dividend" => Self::DividendResolution,
+            "executive_change" => Self::ExecutiveChange,
+            _ => Self::Other,
+        }
+    }
+
+    /// 通知訊息使用的中文標籤。
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::CapitalReduction => "減資",
+            Self::Split => "面額變更",
+            Self::Buyback => "庫藏股",
+            Self::DividendResolution => "股利",
+            Self::ExecutiveChange => "人事異動",
+            Self::Other => "其他",
+        }
+    }
+
+    /// 是否會改變流通股數而需要登錄公司行動（`corporate_action`）。
+    pub fn suggests_corporate_action(self) -> bool {
+        matches!(self, Self::CapitalReduction | Self::Split)
+    }
+}
+
+/// 公開資訊觀測站重大訊息領域實體。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaterialAnnouncement {
+    /// 股票代號
+    pub stock_symbol: String,
+    /// 公司名稱
+    pub company_name: String,
+    /// 發言日期與時間（台北時間）
+    pub announced_at: NaiveDateTime,
+    /// 主旨
+    pub subject: String,
+    /// 符合條款
+    pub clause: String,
+    /// 事實發生日
+    pub fact_date: Option<NaiveDate>,
+    /// 說明
+    pub description: String,
+    /// 關鍵字分類
+    pub category: AnnouncementCategory,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_prefers_share_changing_categories() {
+        let cases = [
+            (
+                "公告本公司董事會決議現金減資",
+                AnnouncementCategory::CapitalReduction,
+            ),
+            (
+                "董事會決議減資彌補虧損並不分派股利",
+                AnnouncementCategory::CapitalReduction,
+            ),
+            ("本公司變更每股面額為1元", AnnouncementCategory::Split),
+            ("董事會決議買回本公司股份", AnnouncementCategory::Buyback),
+            (
+                "公告本公司董事會決議股利分派",
+                AnnouncementCategory::DividendResolution,
+            ),
+            (
+                "公告本公司總經理異動",
+                AnnouncementCategory::ExecutiveChange,
+            ),
+            ("代子公司公告取得使用權資產", AnnouncementCategory::Other),
+        ];
+        for (subject, expected) in cases {
+            assert_eq!(
+                AnnouncementCategory::classify(subject),
+                expected,
+                "{subject}"
+            );
+        }
+    }
+
+    #[test]
+    fn category_codes_round_trip() {
+        for category in [
+            AnnouncementCategory::CapitalReduction,
+            AnnouncementCategory::Split,
+            AnnouncementCategory::Buyback,
+            AnnouncementCategory::DividendResolution,
+            AnnouncementCategory::ExecutiveChange,
+            AnnouncementCategory::Other,
+        ] {
+            assert_eq!(AnnouncementCategory::from_code(category.code()), category);
+        }
+        assert!(AnnouncementCategory::Split.suggests_corporate_action());
+        assert!(!AnnouncementCategory::Buyback.suggests_corporate_action());
+    }
+}
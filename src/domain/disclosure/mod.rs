@@ -0,0 +1,11 @@
+//! 資訊揭露領域。
+//!
+//! 收錄公開資訊觀測站的重大訊息，並依主旨關鍵字分類，供持股與追蹤清單的即時通知使用。
+
+/// 資訊揭露領域實體模組。
+pub mod entity;
+/// 資訊揭露倉儲介面模組。
+pub mod repository;
+
+pub use entity::{AnnouncementCategory, MaterialAnnouncement};
+pub use repository::MaterialAnnouncementRepository;
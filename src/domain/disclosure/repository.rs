@@ -0,0 +1,23 @@
+use super::entity::MaterialAnnouncement;
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// 重大訊息的倉儲合約 (Repository Trait)。
+#[async_trait]
+pub trait MaterialAnnouncementRepository: Send + Sync {
+    /// 寫入重大訊息並回傳「這次才新增」的資料。
+    ///
+    /// 以 `(股票代號, 發言時間, 主旨)` 去重：輪詢重複取得的訊息不會再次回傳，
+    /// 呼叫端可直接拿回傳值決定要通知哪些訊息。
+    async fn insert_new(
+        &self,
+        announcements: &[MaterialAnnouncement],
+    ) -> Result<Vec<MaterialAnnouncement>>;
+
+    /// 查詢個股最近的重大訊息（依發言時間降序）。
+    async fn fetch_by_symbol(
+        &self,
+        stock_symbol: &str,
+        limit: i64,
+    ) -> Result<Vec<MaterialAnnouncement>>;
+}
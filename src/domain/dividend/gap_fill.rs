@@ -0,0 +1,159 @@
+//! 除息填息追蹤。
+//!
+//! 以除息前一交易日的收盤價為目標，從除息日起逐日比對收盤價，第一次收盤價
+//! 回到目標價即為填息；除息日當天就回到目標價記為 1 個交易日。
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+/// 單次除息的填息結果 (Entity)。
+///
+/// 以 `(代號, 發放年度, 期間)` 對應一筆 [`super::entity::Dividend`]。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DividendGapFill {
+    /// 證券代號。
+    pub security_code: String,
+    /// 發放年度。
+    pub year: i32,
+    /// 期間標記（空字串為年度、`H1`/`H2`、`Q1`–`Q4`）。
+    pub quarter: String,
+    /// 除息日。
+    pub ex_dividend_date: NaiveDate,
+    /// 現金股利。
+    pub cash_dividend: Decimal,
+    /// 除息前一交易日收盤價，即填息的目標價。
+    pub pre_ex_close: Decimal,
+    /// 填息日；尚未填息時為 `None`。
+    pub filled_on: Option<NaiveDate>,
+    /// 從除息日起算到填息日的交易日數（含頭尾）；尚未填息時為 `None`。
+    pub trading_days_to_fill: Option<i32>,
+}
+
+impl DividendGapFill {
+    /// 以除息日起的收盤價序列（依日期遞增）更新填息結果。
+    ///
+    /// 已填息者不再變動；序列內找不到回到目標價的收盤價時維持未填息。
+    pub fn track(&mut self, closes: &[(NaiveDate, Decimal)]) {
+        if self.filled_on.is_some() {
+            return;
+        }
+        if let Some((days, (date, _))) = closes
+            .iter()
+            .filter(|(date, _)| *date >= self.ex_dividend_date)
+            .enumerate()
+            .find(|(_, (_, close))| *close >= self.pre_ex_close)
+        {
+            self.filled_on = Some(*date);
+            self.trading_days_to_fill = i32::try_from(days + 1).ok();
+        }
+    }
+}
+
+/// 單一股票的填息統計 (Value Object)。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GapFillSummary {
+    /// 納入統計的除息次數。
+    pub events: usize,
+    /// 已填息次數。
+    pub filled: usize,
+    /// 填息率（%），四捨五入到小數兩位。
+    pub fill_ratio: Decimal,
+    /// 已填息事件的填息交易日數中位數；沒有填息紀錄時為 `None`。
+    pub median_days_to_fill: Option<Decimal>,
+}
+
+impl GapFillSummary {
+    /// 由同一股票的填息結果彙總；沒有任何事件時回傳 `None`。
+    pub fn from_events(events: &[DividendGapFill]) -> Option<Self> {
+        if events.is_empty() {
+            return None;
+        }
+
+        let mut days: Vec<i32> = events
+            .iter()
+            .filter_map(|event| event.trading_days_to_fill)
+            .collect();
+        days.sort_unstable();
+        let filled = days.len();
+        let median_days_to_fill = match filled {
+            0 => None,
+            n if n % 2 == 1 => Some(Decimal::from(days[n / 2])),
+            n => Some(Decimal::from(days[n / 2 - 1] + days[n / 2]) / dec!(2)),
+        };
+
+        Some(Self {
+            events: events.len(),
+            filled,
+            fill_ratio: (Decimal::from(filled) / Decimal::from(events.len()) * dec!(100))
+                .round_dp(2),
+            median_days_to_fill,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, m, d).unwrap()
+    }
+
+    fn event(days: Option<i32>) -> DividendGapFill {
+        DividendGapFill {
+            security_code: "2330".to_string(),
+            year: 2026,
+            quarter: "Q1".to_string(),
+            ex_dividend_date: date(6, 12),
+            cash_dividend: dec!(5),
+            pre_ex_close: dec!(100),
+            filled_on: None,
+            trading_days_to_fill: days,
+        }
+    }
+
+    #[test]
+    fn track_counts_trading_days_from_ex_date() {
+        let mut gap_fill = event(None);
+        let closes = [
+            (date(6, 11), dec!(100)),
+            (date(6, 12), dec!(95.5)),
+            (date(6, 15), dec!(97)),
+            (date(6, 16), dec!(100)),
+            (date(6, 17), dec!(101)),
+        ];
+
+        gap_fill.track(&closes[..3]);
+        assert_eq!(gap_fill.filled_on, None, "尚未回到除息前收盤價");
+
+        gap_fill.track(&closes);
+        assert_eq!(gap_fill.filled_on, Some(date(6, 16)));
+        assert_eq!(gap_fill.trading_days_to_fill, Some(3));
+
+        // 已填息者不受之後的價格影響。
+        gap_fill.track(&[(date(6, 12), dec!(120))]);
+        assert_eq!(gap_fill.filled_on, Some(date(6, 16)));
+    }
+
+    #[test]
+    fn summary_reports_ratio_and_median() {
+        let summary = GapFillSummary::from_events(&[
+            event(Some(1)),
+            event(Some(12)),
+            event(None),
+            event(Some(4)),
+            event(Some(30)),
+        ])
+        .unwrap();
+        assert_eq!(summary.events, 5);
+        assert_eq!(summary.filled, 4);
+        assert_eq!(summary.fill_ratio, dec!(80.00));
+        assert_eq!(summary.median_days_to_fill, Some(dec!(8)));
+
+        let unfilled = GapFillSummary::from_events(&[event(None)]).unwrap();
+        assert_eq!(unfilled.fill_ratio, dec!(0));
+        assert_eq!(unfilled.median_days_to_fill, None);
+        assert!(GapFillSummary::from_events(&[]).is_none());
+    }
+}
@@ -1,2 +1,3 @@
 pub mod entity;
+pub mod gap_fill;
 pub mod repository;
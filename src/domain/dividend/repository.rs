@@ -1,4 +1,5 @@
 use crate::domain::dividend::entity::{Dividend, StockDividendInfo, StockDividendPayableDateInfo};
+use crate::domain::dividend::gap_fill::DividendGapFill;
 use anyhow::Result;
 use async_trait::async_trait;
 use chrono::{DateTime, Local, NaiveDate};
@@ -54,4 +55,43 @@ pub trait DividendRepository: Send + Sync {
 
     /// 儲存或更新單筆股利實體。
     async fn save(&self, dividend: &Dividend) -> Result<()>;
+
+    /// 取得單一公司在 `as_of` 當時已知的各期股利（每期取當時最新的版本）。
+    ///
+    /// 回傳實體的 `created_time` 為首次得知該期股利的時間，`updated_time` 為得知該版本的時間。
+    async fn fetch_dividends_as_of(
+        &self,
+        security_code: &str,
+        as_of: DateTime<Local>,
+    ) -> Result<Vec<Dividend>>;
+}
+
+/// 填息結果之倉儲介面 (Repository Trait)。
+#[async_trait]
+pub trait DividendGapFillRepository: Send + Sync {
+    /// 取得需要追蹤填息的除息事件，並帶入除息前一交易日收盤價與既有結果。
+    ///
+    /// 包含「尚未計算過」的所有除息事件，以及除息日不早於 `since` 且尚未
+    /// 填息的事件；除息日晚於 `until` 或找不到除息前收盤價者略過。
+    async fn fetch_pending(
+        &self,
+        since: NaiveDate,
+        until: NaiveDate,
+    ) -> Result<Vec<DividendGapFill>>;
+
+    /// 取得指定股票從指定日期（含）起的每日收盤價，依日期遞增。
+    async fn fetch_closes(
+        &self,
+        security_code: &str,
+        from: NaiveDate,
+    ) -> Result<Vec<(NaiveDate, rust_decimal::Decimal)>>;
+
+    /// 寫入或更新填息結果。
+    async fn upsert(&self, gap_fills: &[DividendGapFill]) -> Result<()>;
+
+    /// 取得指定股票的所有填息結果，依除息日由新至舊。
+    async fn fetch_by_security_codes(
+        &self,
+        security_codes: &[String],
+    ) -> Result<Vec<DividendGapFill>>;
 }
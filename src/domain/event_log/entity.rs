@@ -0,0 +1,117 @@
+use chrono::{DateTime, Local};
+
+use crate::domain::events::DomainEvent;
+
+/// 單次查詢預設回傳的事件筆數上限。
+pub const DEFAULT_QUERY_LIMIT: i64 = 500;
+
+/// 已寫入事件日誌的領域事件。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredEvent {
+    /// 日誌序號，依寫入順序遞增（可能因交易回滾而不連續）。
+    pub sequence: i64,
+    /// 寫入日誌的時間。
+    pub recorded_at: DateTime<Local>,
+    /// 事件內容。
+    pub event: DomainEvent,
+}
+
+/// 事件日誌查詢條件，各條件為 `None` 時不篩選，結果依序號由小至大排列。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventQuery {
+    /// 事件種類（[`DomainEvent::event_type`]，如 `"StockRegistered"`）。
+    pub event_type: Option<String>,
+    /// 證券代碼。
+    pub symbol: Option<String>,
+    /// 發生時間下限（含）。
+    pub from: Option<DateTime<Local>>,
+    /// 發生時間上限（不含）。
+    pub to: Option<DateTime<Local>>,
+    /// 只取序號大於此值的事件，用於分頁或接續先前的重播。
+    pub after_sequence: Option<i64>,
+    /// 回傳筆數上限。
+    pub limit: i64,
+}
+
+impl Default for EventQuery {
+    fn default() -> Self {
+        EventQuery {
+            event_type: None,
+            symbol: None,
+            from: None,
+            to: None,
+            after_sequence: None,
+            limit: DEFAULT_QUERY_LIMIT,
+        }
+    }
+}
+
+impl EventQuery {
+    /// 以相同條件取得 `sequence` 之後的下一頁。
+    pub fn next_page(&self, sequence: i64) -> Self {
+        EventQuery {
+            after_sequence: Some(sequence),
+            ..self.clone()
+        }
+    }
+
+    /// 事件是否符合查詢條件（不含序號與筆數限制）。
+    pub fn matches(&self, event: &DomainEvent) -> bool {
+        let occurred_at = event.occurred_at();
+        self.event_type
+            .as_deref()
+            .is_none_or(|event_type| event.event_type() == event_type)
+            && self
+                .symbol
+                .as_deref()
+                .is_none_or(|symbol| event.symbol() == Some(symbol))
+            && self.from.is_none_or(|from| occurred_at >= from)
+            && self.to.is_none_or(|to| occurred_at < to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    #[test]
+    fn matches_filters_by_type_symbol_and_time() {
+        let now = Local::now();
+        let event = DomainEvent::StockRegistered {
+            symbol: "2330".to_string(),
+            name: "台積電".to_string(),
+            market_id: 2,
+            industry_id: 24,
+            occurred_at: now,
+        };
+
+        assert!(EventQuery::default().matches(&event));
+        assert!(
+            EventQuery {
+                event_type: Some("StockRegistered".to_string()),
+                symbol: Some("2330".to_string()),
+                from: Some(now),
+                to: Some(now + Duration::seconds(1)),
+                ..Default::default()
+            }
+            .matches(&event)
+        );
+        assert!(
+            !EventQuery {
+                symbol: Some("2317".to_string()),
+                ..Default::default()
+            }
+            .matches(&event)
+        );
+        assert!(
+            !EventQuery {
+                to: Some(now),
+                ..Default::default()
+            }
+            .matches(&event)
+        );
+        assert_eq!(EventQuery::default().next_page(42).after_sequence, Some(42));
+    }
+}
@@ -0,0 +1,16 @@
+//! 領域事件日誌領域。
+//!
+//! 派發器送出的每一個 [`DomainEvent`](crate::domain::events::DomainEvent) 都以遞增序號
+//! 追加保存，可依事件種類、證券代碼與發生時間查詢，並重播給任意處理器，
+//! 用於重建衍生狀態或追查某則通知為何發出。
+
+/// 事件日誌實體模組。
+pub mod entity;
+/// 事件日誌倉儲介面模組。
+pub mod repository;
+/// 事件日誌領域之測試替身；僅在測試建置下編譯。
+#[cfg(test)]
+pub mod test_double;
+
+pub use entity::{EventQuery, StoredEvent};
+pub use repository::EventLogRepository;
@@ -0,0 +1,16 @@
+use super::entity::{EventQuery, StoredEvent};
+use crate::domain::events::DomainEvent;
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// 領域事件日誌的倉儲合約 (Repository Trait)。
+///
+/// 日誌只追加不修改；序號由倉儲配發。
+#[async_trait]
+pub trait EventLogRepository: Send + Sync {
+    /// 追加一筆事件，回傳配發的序號。
+    async fn append(&self, event: &DomainEvent) -> Result<i64>;
+
+    /// 依條件查詢事件，依序號由小至大排列。
+    async fn query(&self, query: &EventQuery) -> Result<Vec<StoredEvent>>;
+}
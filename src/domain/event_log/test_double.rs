@@ -0,0 +1,71 @@
+//! 事件日誌領域的測試替身（test double）。
+//!
+//! 只在 `cfg(test)` 下編譯，供派發器、重播與 manual backfill job 的測試共用，
+//! 不屬於產品程式碼。
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Local;
+use std::sync::Mutex;
+
+use crate::domain::event_log::entity::{EventQuery, StoredEvent};
+use crate::domain::event_log::repository::EventLogRepository;
+use crate::domain::events::DomainEvent;
+
+/// 以記憶體保存事件的假日誌，查詢行為與 PostgreSQL 實作相同。
+///
+/// 追加的事件依序配發序號，可再查詢或以 [`InMemoryEventLog::appended`] 檢查寫入內容。
+#[derive(Default)]
+pub(crate) struct InMemoryEventLog {
+    events: Mutex<Vec<StoredEvent>>,
+}
+
+impl InMemoryEventLog {
+    /// 建立預先存有指定事件的假日誌。
+    pub(crate) fn with_events(events: Vec<StoredEvent>) -> Self {
+        Self {
+            events: Mutex::new(events),
+        }
+    }
+
+    /// 取得目前保存的全部事件，依序號排列。
+    pub(crate) fn appended(&self) -> Vec<DomainEvent> {
+        self.events
+            .lock()
+            .expect("測試鎖不應中毒")
+            .iter()
+            .map(|stored| stored.event.clone())
+            .collect()
+    }
+}
+
+#[async_trait]
+impl EventLogRepository for InMemoryEventLog {
+    async fn append(&self, event: &DomainEvent) -> Result<i64> {
+        let mut events = self.events.lock().expect("測試鎖不應中毒");
+        let sequence = events.last().map_or(1, |stored| stored.sequence + 1);
+        events.push(StoredEvent {
+            sequence,
+            recorded_at: Local::now(),
+            event: event.clone(),
+        });
+        Ok(sequence)
+    }
+
+    async fn query(&self, query: &EventQuery) -> Result<Vec<StoredEvent>> {
+        Ok(self
+            .events
+            .lock()
+            .expect("測試鎖不應中毒")
+            .iter()
+            .filter(|stored| {
+                query
+                    .after_sequence
+                    .is_none_or(|after| stored.sequence > after)
+            })
+            .filter(|stored| query.matches(&stored.event))
+            .take(query.limit as usize)
+            .cloned()
+            .collect())
+    }
+}
@@ -1,11 +1,16 @@
 use chrono::{DateTime, Local};
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 
 /// <summary>
 /// 表示領域內發生的重要事件 (Domain Event)。
 /// 所有事件皆為唯讀且不可變，代表已發生的事實。
+/// 序列化時以 `type` 欄位標示事件種類（與變體名稱相同），供事件日誌保存與重播。
 /// </summary>
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, strum::IntoStaticStr, strum::VariantNames,
+)]
+#[serde(tag = "type")]
 pub enum DomainEvent {
     /// <summary>
     /// 當新的證券主檔被成功註冊時觸發。
@@ -73,6 +78,24 @@ pub enum DomainEvent {
         occurred_at: DateTime<Local>,
     },
 
+    /// <summary>
+    /// 當臺指期貨、法人期貨部位與賣權買權比的每日資料寫入完成時觸發。
+    /// </summary>
+    DerivativesMarketUpdated {
+        /// 交易日期
+        date: chrono::NaiveDate,
+        /// 臺股期貨近月收盤價
+        futures_close: Option<Decimal>,
+        /// 臺股期貨近月漲跌點數
+        futures_change: Option<Decimal>,
+        /// 外資臺股期貨多空未平倉口數淨額
+        foreign_net_open_interest: Option<i64>,
+        /// 臺指選擇權買賣權未平倉量比率（%）
+        put_call_ratio_percent: Option<Decimal>,
+        /// 事件發生時間
+        occurred_at: DateTime<Local>,
+    },
+
     /// <summary>
     /// 當每日帳戶市值重新計算與儲存完成時觸發。
     /// </summary>
@@ -95,3 +118,82 @@ pub enum DomainEvent {
         occurred_at: DateTime<Local>,
     },
 }
+
+impl DomainEvent {
+    /// <summary>
+    /// 事件種類名稱，與變體名稱及序列化後的 `type` 欄位相同（如 `"StockRegistered"`）。
+    /// </summary>
+    pub fn event_type(&self) -> &'static str {
+        self.into()
+    }
+
+    /// <summary>
+    /// 事件所屬的證券代碼；大盤、市值與除權息等全市場事件回傳 `None`。
+    /// </summary>
+    pub fn symbol(&self) -> Option<&str> {
+        match self {
+            DomainEvent::StockRegistered { symbol, .. }
+            | DomainEvent::StockIdentityChanged { symbol, .. }
+            | DomainEvent::NetAssetValueUpdated { symbol, .. } => Some(symbol),
+            DomainEvent::StockIndexUpdated { .. }
+            | DomainEvent::DerivativesMarketUpdated { .. }
+            | DomainEvent::MoneyFlowRecalculated { .. }
+            | DomainEvent::ExDividendReminderTriggered { .. } => None,
+        }
+    }
+
+    /// <summary>
+    /// 事件發生時間。
+    /// </summary>
+    pub fn occurred_at(&self) -> DateTime<Local> {
+        match self {
+            DomainEvent::StockRegistered { occurred_at, .. }
+            | DomainEvent::StockIdentityChanged { occurred_at, .. }
+            | DomainEvent::NetAssetValueUpdated { occurred_at, .. }
+            | DomainEvent::StockIndexUpdated { occurred_at, .. }
+            | DomainEvent::DerivativesMarketUpdated { occurred_at, .. }
+            | DomainEvent::MoneyFlowRecalculated { occurred_at, .. }
+            | DomainEvent::ExDividendReminderTriggered { occurred_at, .. } => *occurred_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    /// 序列化後帶有 `type` 欄位，金額以字串保存避免精度流失，且能還原成相同事件。
+    #[test]
+    fn serde_round_trip_keeps_type_and_precision() {
+        let event = DomainEvent::NetAssetValueUpdated {
+            symbol: "0050".to_string(),
+            old_nav: dec!(150.10),
+            new_nav: dec!(151.2345),
+            occurred_at: Local::now(),
+        };
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "NetAssetValueUpdated");
+        assert_eq!(json["new_nav"], "151.2345");
+
+        let restored: DomainEvent = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, event);
+        assert_eq!(restored.event_type(), "NetAssetValueUpdated");
+        assert_eq!(restored.symbol(), Some("0050"));
+    }
+
+    #[test]
+    fn market_wide_events_have_no_symbol() {
+        let occurred_at = Local::now();
+        let event = DomainEvent::MoneyFlowRecalculated {
+            date: chrono::NaiveDate::from_ymd_opt(2026, 6, 5).unwrap(),
+            occurred_at,
+        };
+
+        assert_eq!(event.event_type(), "MoneyFlowRecalculated");
+        assert_eq!(event.symbol(), None);
+        assert_eq!(event.occurred_at(), occurred_at);
+    }
+}
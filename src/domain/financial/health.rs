@@ -0,0 +1,529 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::declare::Quarter,
+    domain::financial::line_item::{FinancialLineItem, LineItem, PeriodType},
+};
+
+/// 普通股每股面額（元），用來由股本換算流通股數。
+///
+/// 少數公司採用非 10 元面額，市值會因此失真；這類公司只影響 Altman Z 與
+/// 自由現金流量殖利率。
+const PAR_VALUE: Decimal = dec!(10);
+
+/// 計算財務體質分數所需的資產負債表時點餘額（元）。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceSnapshot {
+    /// 資產總計。
+    pub total_assets: Option<Decimal>,
+    /// 流動資產合計。
+    pub current_assets: Option<Decimal>,
+    /// 流動負債合計。
+    pub current_liabilities: Option<Decimal>,
+    /// 非流動負債合計。
+    pub noncurrent_liabilities: Option<Decimal>,
+    /// 負債總計。
+    pub total_liabilities: Option<Decimal>,
+    /// 權益總計。
+    pub total_equity: Option<Decimal>,
+    /// 保留盈餘。
+    pub retained_earnings: Option<Decimal>,
+    /// 普通股股本。
+    pub ordinary_share: Option<Decimal>,
+}
+
+impl BalanceSnapshot {
+    /// 由單一公司、單一季度的科目取出季末餘額。
+    pub fn from_items(items: &[FinancialLineItem]) -> Self {
+        let value = |item| find(items, item, PeriodType::Instant);
+        BalanceSnapshot {
+            total_assets: value(LineItem::TotalAssets),
+            current_assets: value(LineItem::CurrentAssets),
+            current_liabilities: value(LineItem::CurrentLiabilities),
+            noncurrent_liabilities: value(LineItem::NoncurrentLiabilities),
+            total_liabilities: value(LineItem::TotalLiabilities),
+            total_equity: value(LineItem::TotalEquity),
+            retained_earnings: value(LineItem::RetainedEarnings),
+            ordinary_share: value(LineItem::OrdinaryShare),
+        }
+    }
+
+    /// 流動比率。
+    fn current_ratio(&self) -> Option<Decimal> {
+        ratio(self.current_assets?, self.current_liabilities?)
+    }
+
+    /// 長期負債佔總資產比例。
+    fn leverage(&self) -> Option<Decimal> {
+        ratio(self.noncurrent_liabilities?, self.total_assets?)
+    }
+
+    /// 負債權益比。
+    fn debt_to_equity(&self) -> Option<Decimal> {
+        ratio(self.total_liabilities?, self.total_equity?)
+    }
+}
+
+/// 計算財務體質分數所需的損益與現金流量期間數（元）。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlowSnapshot {
+    /// 營業收入。
+    pub revenue: Option<Decimal>,
+    /// 營業毛利。
+    pub gross_profit: Option<Decimal>,
+    /// 稅前淨利。
+    pub profit_before_tax: Option<Decimal>,
+    /// 財務成本（利息費用）。
+    pub finance_costs: Option<Decimal>,
+    /// 本期淨利。
+    pub net_income: Option<Decimal>,
+    /// 營業活動之淨現金流。
+    pub operating_cash_flow: Option<Decimal>,
+    /// 資本支出（負值為流出）。
+    pub capital_expenditure: Option<Decimal>,
+}
+
+impl FlowSnapshot {
+    /// 由單一公司、單一季度的科目取出年初至季末累計數。
+    pub fn from_items(items: &[FinancialLineItem]) -> Self {
+        let value = |item| find(items, item, PeriodType::YearToDate);
+        FlowSnapshot {
+            revenue: value(LineItem::Revenue),
+            gross_profit: value(LineItem::GrossProfit),
+            profit_before_tax: value(LineItem::ProfitBeforeTax),
+            finance_costs: value(LineItem::FinanceCosts),
+            net_income: value(LineItem::NetIncome),
+            operating_cash_flow: value(LineItem::OperatingCashFlow),
+            capital_expenditure: value(LineItem::CapitalExpenditure),
+        }
+    }
+
+    /// 以「本期累計 + 去年全年 − 去年同期累計」推算近四季數值。
+    ///
+    /// 第四季的累計數即為全年，直接沿用；任一期缺值的欄位無法推算。
+    pub fn trailing(
+        quarter: Quarter,
+        year_to_date: &FlowSnapshot,
+        prior_annual: &FlowSnapshot,
+        prior_year_to_date: &FlowSnapshot,
+    ) -> Self {
+        if quarter == Quarter::Q4 {
+            return year_to_date.clone();
+        }
+
+        let roll = |current: Option<Decimal>, annual: Option<Decimal>, prior: Option<Decimal>| {
+            Some(current? + annual? - prior?)
+        };
+        FlowSnapshot {
+            revenue: roll(
+                year_to_date.revenue,
+                prior_annual.revenue,
+                prior_year_to_date.revenue,
+            ),
+            gross_profit: roll(
+                year_to_date.gross_profit,
+                prior_annual.gross_profit,
+                prior_year_to_date.gross_profit,
+            ),
+            profit_before_tax: roll(
+                year_to_date.profit_before_tax,
+                prior_annual.profit_before_tax,
+                prior_year_to_date.profit_before_tax,
+            ),
+            finance_costs: roll(
+                year_to_date.finance_costs,
+                prior_annual.finance_costs,
+                prior_year_to_date.finance_costs,
+            ),
+            net_income: roll(
+                year_to_date.net_income,
+                prior_annual.net_income,
+                prior_year_to_date.net_income,
+            ),
+            operating_cash_flow: roll(
+                year_to_date.operating_cash_flow,
+                prior_annual.operating_cash_flow,
+                prior_year_to_date.operating_cash_flow,
+            ),
+            capital_expenditure: roll(
+                year_to_date.capital_expenditure,
+                prior_annual.capital_expenditure,
+                prior_year_to_date.capital_expenditure,
+            ),
+        }
+    }
+
+    /// 息前稅前淨利；未申報財務成本視為沒有利息費用。
+    fn ebit(&self) -> Option<Decimal> {
+        Some(self.profit_before_tax? + self.finance_costs.unwrap_or_default())
+    }
+
+    /// 毛利率。
+    fn gross_margin(&self) -> Option<Decimal> {
+        ratio(self.gross_profit?, self.revenue?)
+    }
+}
+
+/// 計算財務體質分數時採用的全部輸入，連同分數一併保存以便追溯。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinancialHealthInputs {
+    /// 本季季末餘額。
+    pub balance: BalanceSnapshot,
+    /// 去年同季季末餘額。
+    pub prior_balance: BalanceSnapshot,
+    /// 本年累計數。
+    pub year_to_date: FlowSnapshot,
+    /// 去年同期累計數。
+    pub prior_year_to_date: FlowSnapshot,
+    /// 近四季數值。
+    pub trailing: FlowSnapshot,
+    /// 計算市值採用的收盤價日期。
+    pub price_date: Option<NaiveDate>,
+    /// 計算市值採用的收盤價（元）。
+    pub closing_price: Option<Decimal>,
+}
+
+impl FinancialHealthInputs {
+    /// 以季末收盤價與股本推算的普通股市值（元）。
+    fn market_value(&self) -> Option<Decimal> {
+        let market_value = self.closing_price? * self.balance.ordinary_share? / PAR_VALUE;
+        (market_value > Decimal::ZERO).then_some(market_value)
+    }
+}
+
+/// Piotroski F-score 的九項檢查；資料不足的項目為 `None`。
+///
+/// 原始定義比較相鄰兩個會計年度，這裡改以本年累計與去年同期累計比較，
+/// 讓每一季都能更新分數，同時避開季節性。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PiotroskiCriteria {
+    /// 資產報酬率為正。
+    pub positive_roa: Option<bool>,
+    /// 營業現金流為正。
+    pub positive_operating_cash_flow: Option<bool>,
+    /// 資產報酬率較去年同期上升。
+    pub improving_roa: Option<bool>,
+    /// 營業現金流大於淨利（盈餘品質）。
+    pub cash_flow_exceeds_income: Option<bool>,
+    /// 長期負債佔總資產比例下降。
+    pub lower_leverage: Option<bool>,
+    /// 流動比率上升。
+    pub higher_current_ratio: Option<bool>,
+    /// 股本沒有增加（未稀釋）。
+    pub no_dilution: Option<bool>,
+    /// 毛利率上升。
+    pub higher_gross_margin: Option<bool>,
+    /// 資產週轉率上升。
+    pub higher_asset_turnover: Option<bool>,
+}
+
+impl PiotroskiCriteria {
+    /// 依本期與去年同期數據逐項檢查。
+    pub fn evaluate(inputs: &FinancialHealthInputs) -> Self {
+        let current = &inputs.year_to_date;
+        let prior = &inputs.prior_year_to_date;
+        let balance = &inputs.balance;
+        let prior_balance = &inputs.prior_balance;
+
+        let roa = ratio_of(current.net_income, balance.total_assets);
+        let prior_roa = ratio_of(prior.net_income, prior_balance.total_assets);
+        let turnover = ratio_of(current.revenue, balance.total_assets);
+        let prior_turnover = ratio_of(prior.revenue, prior_balance.total_assets);
+
+        PiotroskiCriteria {
+            positive_roa: roa.map(|roa| roa > Decimal::ZERO),
+            positive_operating_cash_flow: current
+                .operating_cash_flow
+                .map(|cfo| cfo > Decimal::ZERO),
+            improving_roa: greater(roa, prior_roa),
+            cash_flow_exceeds_income: greater(current.operating_cash_flow, current.net_income),
+            lower_leverage: greater(prior_balance.leverage(), balance.leverage()),
+            higher_current_ratio: greater(balance.current_ratio(), prior_balance.current_ratio()),
+            no_dilution: match (balance.ordinary_share, prior_balance.ordinary_share) {
+                (Some(current), Some(prior)) => Some(current <= prior),
+                _ => None,
+            },
+            higher_gross_margin: greater(current.gross_margin(), prior.gross_margin()),
+            higher_asset_turnover: greater(turnover, prior_turnover),
+        }
+    }
+
+    fn checks(&self) -> [Option<bool>; 9] {
+        [
+            self.positive_roa,
+            self.positive_operating_cash_flow,
+            self.improving_roa,
+            self.cash_flow_exceeds_income,
+            self.lower_leverage,
+            self.higher_current_ratio,
+            self.no_dilution,
+            self.higher_gross_margin,
+            self.higher_asset_turnover,
+        ]
+    }
+
+    /// 通過的項目數（0–9）；只要有一項無法判斷就不給分，避免低估缺資料的公司。
+    pub fn score(&self) -> Option<i16> {
+        self.checks().into_iter().try_fold(0, |score, check| {
+            check.map(|passed| score + i16::from(passed))
+        })
+    }
+}
+
+/// 單一公司、單一季度的財務體質分數。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FinancialHealthScore {
+    /// 股票代號。
+    pub security_code: String,
+    /// 財報年度（西元）。
+    pub year: i32,
+    /// 財報季別。
+    pub quarter: Quarter,
+    /// 季末日期。
+    pub period_end: NaiveDate,
+    /// Piotroski F-score（0–9）。
+    pub f_score: Option<i16>,
+    /// F-score 各項檢查結果。
+    pub f_score_criteria: PiotroskiCriteria,
+    /// Altman Z-score（製造業原始模型）。
+    pub altman_z_score: Option<Decimal>,
+    /// 應計比率：(近四季淨利 − 營業現金流) ÷ 平均總資產；越低代表盈餘越多由現金支撐。
+    pub accruals_ratio: Option<Decimal>,
+    /// 現金轉換率：近四季營業現金流 ÷ 淨利（淨利為正時才計算）。
+    pub cash_conversion_ratio: Option<Decimal>,
+    /// 自由現金流量殖利率（%）：近四季 (營業現金流 − 資本支出) ÷ 市值。
+    pub fcf_yield_pct: Option<Decimal>,
+    /// 利息保障倍數：近四季息前稅前淨利 ÷ 財務成本（有利息費用時才計算）。
+    pub interest_coverage: Option<Decimal>,
+    /// 負債權益比。
+    pub debt_to_equity: Option<Decimal>,
+    /// 負債權益比較去年同季的變化；負值代表槓桿下降。
+    pub debt_to_equity_change: Option<Decimal>,
+    /// 計算採用的輸入。
+    pub inputs: FinancialHealthInputs,
+}
+
+impl FinancialHealthScore {
+    /// 依輸入計算全部分數，數值四捨五入到小數第四位。
+    pub fn evaluate(
+        security_code: &str,
+        year: i32,
+        quarter: Quarter,
+        period_end: NaiveDate,
+        inputs: FinancialHealthInputs,
+    ) -> Self {
+        let criteria = PiotroskiCriteria::evaluate(&inputs);
+        let trailing = &inputs.trailing;
+        let balance = &inputs.balance;
+        let market_value = inputs.market_value();
+
+        let altman_z_score = (|| {
+            let total_assets = positive(balance.total_assets?)?;
+            let working_capital = balance.current_assets? - balance.current_liabilities?;
+            let total_liabilities = positive(balance.total_liabilities?)?;
+            Some(
+                dec!(1.2) * working_capital / total_assets
+                    + dec!(1.4) * balance.retained_earnings? / total_assets
+                    + dec!(3.3) * trailing.ebit()? / total_assets
+                    + dec!(0.6) * market_value? / total_liabilities
+                    + trailing.revenue? / total_assets,
+            )
+        })();
+
+        let accruals_ratio = (|| {
+            let average_assets = match inputs.prior_balance.total_assets {
+                Some(prior) => (balance.total_assets? + prior) / dec!(2),
+                None => balance.total_assets?,
+            };
+            ratio(
+                trailing.net_income? - trailing.operating_cash_flow?,
+                average_assets,
+            )
+        })();
+
+        let cash_conversion_ratio = (|| {
+            ratio(
+                trailing.operating_cash_flow?,
+                positive(trailing.net_income?)?,
+            )
+        })();
+
+        let fcf_yield_pct = (|| {
+            let free_cash_flow = trailing.operating_cash_flow?
+                - trailing.capital_expenditure.unwrap_or_default().abs();
+            Some(ratio(free_cash_flow, market_value?)? * dec!(100))
+        })();
+
+        let interest_coverage = (|| ratio(trailing.ebit()?, positive(trailing.finance_costs?)?))();
+
+        let debt_to_equity = balance.debt_to_equity();
+        let debt_to_equity_change =
+            (|| Some(debt_to_equity? - inputs.prior_balance.debt_to_equity()?))();
+
+        let round = |value: Option<Decimal>| value.map(|value| value.round_dp(4));
+        FinancialHealthScore {
+            security_code: security_code.to_string(),
+            year,
+            quarter,
+            period_end,
+            f_score: criteria.score(),
+            f_score_criteria: criteria,
+            altman_z_score: round(altman_z_score),
+            accruals_ratio: round(accruals_ratio),
+            cash_conversion_ratio: round(cash_conversion_ratio),
+            fcf_yield_pct: round(fcf_yield_pct),
+            interest_coverage: round(interest_coverage),
+            debt_to_equity: round(debt_to_equity),
+            debt_to_equity_change: round(debt_to_equity_change),
+            inputs,
+        }
+    }
+}
+
+/// 取出指定科目與期間的數值。
+fn find(items: &[FinancialLineItem], item: LineItem, period: PeriodType) -> Option<Decimal> {
+    items
+        .iter()
+        .find(|line| line.item == item && line.period == period)
+        .map(|line| line.value)
+}
+
+/// 分母為零時無法計算的比率。
+fn ratio(numerator: Decimal, denominator: Decimal) -> Option<Decimal> {
+    if denominator.is_zero() {
+        return None;
+    }
+    numerator.checked_div(denominator)
+}
+
+fn ratio_of(numerator: Option<Decimal>, denominator: Option<Decimal>) -> Option<Decimal> {
+    ratio(numerator?, denominator?)
+}
+
+fn positive(value: Decimal) -> Option<Decimal> {
+    (value > Decimal::ZERO).then_some(value)
+}
+
+fn greater(left: Option<Decimal>, right: Option<Decimal>) -> Option<bool> {
+    Some(left? > right?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn balance(total_assets: Decimal, ordinary_share: Decimal) -> BalanceSnapshot {
+        BalanceSnapshot {
+            total_assets: Some(total_assets),
+            current_assets: Some(dec!(400)),
+            current_liabilities: Some(dec!(200)),
+            noncurrent_liabilities: Some(dec!(100)),
+            total_liabilities: Some(dec!(300)),
+            total_equity: Some(total_assets - dec!(300)),
+            retained_earnings: Some(dec!(250)),
+            ordinary_share: Some(ordinary_share),
+        }
+    }
+
+    fn flow(revenue: Decimal, net_income: Decimal) -> FlowSnapshot {
+        FlowSnapshot {
+            revenue: Some(revenue),
+            gross_profit: Some(revenue * dec!(0.4)),
+            profit_before_tax: Some(net_income + dec!(20)),
+            finance_costs: Some(dec!(10)),
+            net_income: Some(net_income),
+            operating_cash_flow: Some(net_income + dec!(30)),
+            capital_expenditure: Some(dec!(-50)),
+        }
+    }
+
+    fn inputs() -> FinancialHealthInputs {
+        let year_to_date = flow(dec!(600), dec!(60));
+        FinancialHealthInputs {
+            balance: balance(dec!(1000), dec!(100)),
+            prior_balance: balance(dec!(900), dec!(100)),
+            prior_year_to_date: flow(dec!(500), dec!(40)),
+            trailing: year_to_date.clone(),
+            year_to_date,
+            price_date: NaiveDate::from_ymd_opt(2024, 12, 31),
+            closing_price: Some(dec!(50)),
+        }
+    }
+
+    /// 近四季以本期累計加上去年全年、扣除去年同期推算；第四季直接沿用累計數。
+    #[test]
+    fn trailing_rolls_year_to_date() {
+        let current = flow(dec!(300), dec!(30));
+        let annual = flow(dec!(1000), dec!(100));
+        let prior = flow(dec!(250), dec!(20));
+
+        let trailing = FlowSnapshot::trailing(Quarter::Q2, &current, &annual, &prior);
+        assert_eq!(trailing.revenue, Some(dec!(1050)));
+        assert_eq!(trailing.net_income, Some(dec!(110)));
+
+        let missing =
+            FlowSnapshot::trailing(Quarter::Q2, &current, &FlowSnapshot::default(), &prior);
+        assert_eq!(missing.revenue, None);
+
+        assert_eq!(
+            FlowSnapshot::trailing(Quarter::Q4, &current, &annual, &prior),
+            current
+        );
+    }
+
+    /// 九項全部可判斷時才給 F-score。
+    #[test]
+    fn f_score_requires_every_criterion() {
+        let criteria = PiotroskiCriteria::evaluate(&inputs());
+        assert_eq!(criteria.positive_roa, Some(true));
+        assert_eq!(criteria.improving_roa, Some(true));
+        assert_eq!(criteria.cash_flow_exceeds_income, Some(true));
+        // 總資產增加而長期負債不變，槓桿下降。
+        assert_eq!(criteria.lower_leverage, Some(true));
+        assert_eq!(criteria.higher_current_ratio, Some(false));
+        assert_eq!(criteria.no_dilution, Some(true));
+        assert_eq!(criteria.higher_gross_margin, Some(false));
+        assert_eq!(criteria.higher_asset_turnover, Some(true));
+        assert_eq!(criteria.score(), Some(7));
+
+        let mut partial = inputs();
+        partial.prior_balance.ordinary_share = None;
+        assert_eq!(PiotroskiCriteria::evaluate(&partial).score(), None);
+    }
+
+    /// 各項分數依公式計算並四捨五入到小數第四位。
+    #[test]
+    fn evaluate_computes_scores() {
+        let period_end = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        let score = FinancialHealthScore::evaluate("2330", 2024, Quarter::Q4, period_end, inputs());
+
+        // 市值 = 50 × 100 ÷ 10 = 500。
+        // Z = 1.2×200/1000 + 1.4×250/1000 + 3.3×90/1000 + 0.6×500/300 + 600/1000
+        assert_eq!(score.altman_z_score, Some(dec!(2.4870)));
+        // (60 − 90) ÷ ((1000 + 900) ÷ 2)
+        assert_eq!(score.accruals_ratio, Some(dec!(-0.0316)));
+        assert_eq!(score.cash_conversion_ratio, Some(dec!(1.5)));
+        // (90 − 50) ÷ 500 × 100
+        assert_eq!(score.fcf_yield_pct, Some(dec!(8)));
+        assert_eq!(score.interest_coverage, Some(dec!(9)));
+        assert_eq!(score.debt_to_equity, Some(dec!(0.4286)));
+        assert_eq!(score.debt_to_equity_change, Some(dec!(-0.0714)));
+        assert_eq!(score.f_score, Some(7));
+    }
+
+    /// 缺少股價時只影響需要市值的分數。
+    #[test]
+    fn evaluate_without_price_skips_market_scores() {
+        let mut inputs = inputs();
+        inputs.closing_price = None;
+        let period_end = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        let score = FinancialHealthScore::evaluate("2330", 2024, Quarter::Q4, period_end, inputs);
+
+        assert_eq!(score.altman_z_score, None);
+        assert_eq!(score.fcf_yield_pct, None);
+        assert_eq!(score.interest_coverage, Some(dec!(9)));
+    }
+}
@@ -0,0 +1,222 @@
+use rust_decimal::Decimal;
+use strum::{Display, EnumString};
+
+use crate::core::declare::Quarter;
+
+/// 財務報表種類。
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Display, EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum StatementKind {
+    /// 綜合損益表。
+    IncomeStatement,
+    /// 資產負債表。
+    BalanceSheet,
+    /// 現金流量表。
+    CashFlow,
+}
+
+/// 科目數值涵蓋的期間。
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Display, EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum PeriodType {
+    /// 季末時點餘額（資產負債表）。
+    Instant,
+    /// 單季三個月的發生數。
+    Quarter,
+    /// 年初至季末的累計發生數；第四季即全年。
+    YearToDate,
+}
+
+/// 正規化後的財報科目。
+///
+/// 各產業（一般業、金融、保險…）的 XBRL 分類標準用詞不同，
+/// 這裡只保留跨產業可比較的主要科目，代碼同時作為資料庫中的 `item_code`。
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Display, EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum LineItem {
+    // === 綜合損益表 ===
+    /// 營業收入。
+    Revenue,
+    /// 營業成本。
+    CostOfRevenue,
+    /// 營業毛利。
+    GrossProfit,
+    /// 營業費用。
+    OperatingExpenses,
+    /// 營業利益。
+    OperatingIncome,
+    /// 營業外收入及支出。
+    NonOperatingIncome,
+    /// 財務成本（利息費用）。
+    FinanceCosts,
+    /// 稅前淨利。
+    ProfitBeforeTax,
+    /// 所得稅費用。
+    IncomeTaxExpense,
+    /// 本期淨利。
+    NetIncome,
+    /// 歸屬於母公司業主之淨利。
+    NetIncomeAttributableToParent,
+    /// 基本每股盈餘（元）。
+    BasicEarningsPerShare,
+    /// 稀釋每股盈餘（元）。
+    DilutedEarningsPerShare,
+
+    // === 資產負債表 ===
+    /// 現金及約當現金。
+    CashAndEquivalents,
+    /// 應收帳款淨額。
+    AccountsReceivable,
+    /// 存貨。
+    Inventories,
+    /// 流動資產合計。
+    CurrentAssets,
+    /// 不動產、廠房及設備。
+    PropertyPlantAndEquipment,
+    /// 資產總計。
+    TotalAssets,
+    /// 短期借款。
+    ShortTermBorrowings,
+    /// 流動負債合計。
+    CurrentLiabilities,
+    /// 應付公司債。
+    BondsPayable,
+    /// 長期借款。
+    LongTermBorrowings,
+    /// 非流動負債合計。
+    NoncurrentLiabilities,
+    /// 負債總計。
+    TotalLiabilities,
+    /// 普通股股本。
+    OrdinaryShare,
+    /// 保留盈餘。
+    RetainedEarnings,
+    /// 歸屬於母公司業主之權益。
+    EquityAttributableToParent,
+    /// 權益總計。
+    TotalEquity,
+
+    // === 現金流量表 ===
+    /// 折舊費用。
+    Depreciation,
+    /// 攤銷費用。
+    Amortisation,
+    /// 營業活動之淨現金流入（流出）。
+    OperatingCashFlow,
+    /// 取得不動產、廠房及設備（資本支出，負值為流出）。
+    CapitalExpenditure,
+    /// 投資活動之淨現金流入（流出）。
+    InvestingCashFlow,
+    /// 發放現金股利。
+    DividendsPaid,
+    /// 籌資活動之淨現金流入（流出）。
+    FinancingCashFlow,
+    /// 本期現金及約當現金增加（減少）數。
+    NetChangeInCash,
+}
+
+impl LineItem {
+    /// 科目所屬的報表。
+    pub fn statement(self) -> StatementKind {
+        use LineItem::*;
+        match self {
+            Revenue
+            | CostOfRevenue
+            | GrossProfit
+            | OperatingExpenses
+            | OperatingIncome
+            | NonOperatingIncome
+            | FinanceCosts
+            | ProfitBeforeTax
+            | IncomeTaxExpense
+            | NetIncome
+            | NetIncomeAttributableToParent
+            | BasicEarningsPerShare
+            | DilutedEarningsPerShare => StatementKind::IncomeStatement,
+            CashAndEquivalents
+            | AccountsReceivable
+            | Inventories
+            | CurrentAssets
+            | PropertyPlantAndEquipment
+            | TotalAssets
+            | ShortTermBorrowings
+            | CurrentLiabilities
+            | BondsPayable
+            | LongTermBorrowings
+            | NoncurrentLiabilities
+            | TotalLiabilities
+            | OrdinaryShare
+            | RetainedEarnings
+            | EquityAttributableToParent
+            | TotalEquity => StatementKind::BalanceSheet,
+            Depreciation | Amortisation | OperatingCashFlow | CapitalExpenditure
+            | InvestingCashFlow | DividendsPaid | FinancingCashFlow | NetChangeInCash => {
+                StatementKind::CashFlow
+            }
+        }
+    }
+}
+
+/// 單一公司、單一季度、單一科目與期間的財報數值。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FinancialLineItem {
+    /// 股票代號。
+    pub security_code: String,
+    /// 財報年度（西元）。
+    pub year: i32,
+    /// 財報季別。
+    pub quarter: Quarter,
+    /// 正規化科目。
+    pub item: LineItem,
+    /// 數值涵蓋的期間。
+    pub period: PeriodType,
+    /// 科目金額（元）；每股盈餘為每股元。
+    pub value: Decimal,
+    /// 來源 XBRL 元素，例如 `ifrs-full:Revenue`，供追查分類標準差異。
+    pub element: String,
+    /// 是否取自合併報表；沒有子公司的公司只申報個體報表。
+    pub consolidated: bool,
+}
+
+impl FinancialLineItem {
+    /// 科目所屬的報表。
+    pub fn statement(&self) -> StatementKind {
+        self.item.statement()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    /// 科目代碼與資料庫的 `item_code` 互轉一致。
+    #[test]
+    fn line_item_code_round_trips() {
+        assert_eq!(LineItem::NetIncome.to_string(), "net_income");
+        assert_eq!(
+            LineItem::from_str("capital_expenditure").unwrap(),
+            LineItem::CapitalExpenditure
+        );
+        assert_eq!(PeriodType::YearToDate.to_string(), "year_to_date");
+        assert_eq!(
+            StatementKind::from_str("cash_flow").unwrap(),
+            StatementKind::CashFlow
+        );
+    }
+
+    /// 每個科目都歸屬到正確的報表。
+    #[test]
+    fn line_item_belongs_to_statement() {
+        assert_eq!(
+            LineItem::BasicEarningsPerShare.statement(),
+            StatementKind::IncomeStatement
+        );
+        assert_eq!(
+            LineItem::TotalEquity.statement(),
+            StatementKind::BalanceSheet
+        );
+        assert_eq!(LineItem::Depreciation.statement(), StatementKind::CashFlow);
+    }
+}
@@ -1,4 +1,12 @@
 /// 財報與營收領域之實體定義。
 pub mod entity;
+/// 由三大報表科目計算的財務體質分數。
+pub mod health;
+/// 三大報表正規化科目。
+pub mod line_item;
 /// 財報與營收領域之倉儲介面。
 pub mod repository;
+/// 月營收滾動合計、年增率加速度、創新高與季節性驚喜。
+pub mod revenue_momentum;
+/// 月營收、財務報表與股利的版本歷史（首次得知時間與修正紀錄）。
+pub mod revision;
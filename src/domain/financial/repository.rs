@@ -1,10 +1,18 @@
+use std::collections::HashMap;
+
 use crate::{
     core::declare::Quarter,
-    domain::financial::entity::{FinancialStatement, MonthlyRevenue},
+    domain::financial::{
+        entity::{FinancialStatement, MonthlyRevenue},
+        health::FinancialHealthScore,
+        line_item::{FinancialLineItem, StatementKind},
+        revenue_momentum::{RevenueMomentum, RevenuePoint},
+        revision::{FundamentalKind, FundamentalRevision},
+    },
 };
 use anyhow::Result;
 use async_trait::async_trait;
-use chrono::NaiveDate;
+use chrono::{DateTime, Local, NaiveDate};
 use rust_decimal::Decimal;
 
 /// 財報與營收領域之倉儲介面 (Repository Trait)。
@@ -53,6 +61,15 @@ pub trait FinancialRepository: Send + Sync {
         quarters: Vec<Quarter>,
     ) -> Result<Decimal>;
 
+    /// 取得單一公司在 `as_of` 當時已知的各期財報（每期取當時最新的版本）。
+    ///
+    /// 回傳實體的 `created_time` 為首次得知該期數字的時間，`updated_time` 為得知該版本的時間。
+    async fn fetch_financial_statements_as_of(
+        &self,
+        security_code: &str,
+        as_of: DateTime<Local>,
+    ) -> Result<Vec<FinancialStatement>>;
+
     // === 月營收 (MonthlyRevenue) ===
 
     /// 新增或更新單月營收實體。
@@ -64,8 +81,88 @@ pub trait FinancialRepository: Send + Sync {
     /// 重建最新營收日期索引表 (revenue_last_date)。
     async fn rebuild_revenue_last_date(&self) -> Result<()>;
 
+    /// 取得單一公司在 `as_of` 當時已知的各月營收（每月取當時最新的版本），依月份排序。
+    ///
+    /// 回傳實體的 `create_time` 為首次得知該月營收的時間。
+    async fn fetch_monthly_revenues_as_of(
+        &self,
+        security_code: &str,
+        as_of: DateTime<Local>,
+    ) -> Result<Vec<MonthlyRevenue>>;
+
     // === 價格估值 (PriceEstimate) ===
 
     /// 依指定日期與年份區間，批次重建所有個股價格估值。
     async fn rebuild_price_estimates(&self, date: NaiveDate, years: String) -> Result<()>;
 }
+
+/// 三大報表正規化科目之倉儲介面。
+#[async_trait]
+pub trait FinancialLineItemRepository: Send + Sync {
+    /// 批次新增或更新科目數值，回傳寫入筆數。
+    async fn upsert(&self, items: &[FinancialLineItem]) -> Result<u64>;
+
+    /// 取得單一公司、單一季度的科目；`statement` 為 `None` 時回傳三大報表全部科目。
+    async fn fetch(
+        &self,
+        security_code: &str,
+        year: i32,
+        quarter: Quarter,
+        statement: Option<StatementKind>,
+    ) -> Result<Vec<FinancialLineItem>>;
+
+    /// 取得指定季度全部公司的科目。
+    async fn fetch_quarter(&self, year: i32, quarter: Quarter) -> Result<Vec<FinancialLineItem>>;
+
+    /// 指定季度已寫入科目的公司數，供回補流程判斷是否已處理過。
+    async fn count_companies(&self, year: i32, quarter: Quarter) -> Result<i64>;
+}
+
+/// 財務體質分數之倉儲介面。
+#[async_trait]
+pub trait FinancialHealthRepository: Send + Sync {
+    /// 取得每檔股票在 `on`（含）之前最近的收盤價與其日期，供換算季末市值。
+    async fn fetch_closing_prices(
+        &self,
+        on: NaiveDate,
+    ) -> Result<HashMap<String, (NaiveDate, Decimal)>>;
+
+    /// 批次新增或更新分數，回傳寫入筆數。
+    async fn upsert(&self, scores: &[FinancialHealthScore]) -> Result<u64>;
+
+    /// 取得單一公司最新一季的分數；指定 `as_of` 時只看季末在該日（含）之前的季度。
+    async fn fetch_latest(
+        &self,
+        security_code: &str,
+        as_of: Option<NaiveDate>,
+    ) -> Result<Option<FinancialHealthScore>>;
+}
+
+/// 月營收動能之倉儲介面。
+#[async_trait]
+pub trait RevenueMomentumRepository: Send + Sync {
+    /// 取得全部公司截至 `until`（`YYYYMM`，含）的單月營收，依公司與月份排序。
+    async fn fetch_revenues(&self, until: i64) -> Result<Vec<RevenuePoint>>;
+
+    /// 批次新增或更新動能指標，回傳寫入筆數。
+    async fn upsert(&self, momentums: &[RevenueMomentum]) -> Result<u64>;
+
+    /// 取得指定月份的動能指標；`security_codes` 為 `None` 時回傳全部公司。
+    async fn fetch_month(
+        &self,
+        month: i64,
+        security_codes: Option<Vec<String>>,
+    ) -> Result<Vec<RevenueMomentum>>;
+}
+
+/// 基本面版本歷史之倉儲介面。
+#[async_trait]
+pub trait FundamentalRevisionRepository: Send + Sync {
+    /// 取得單一公司某類資料的版本歷史，依期間與版本排序；`period` 為 `None` 時回傳全部期間。
+    async fn fetch_history(
+        &self,
+        kind: FundamentalKind,
+        security_code: &str,
+        period: Option<&str>,
+    ) -> Result<Vec<FundamentalRevision>>;
+}
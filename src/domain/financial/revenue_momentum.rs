@@ -0,0 +1,296 @@
+use std::collections::BTreeMap;
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+/// 季節性常態最多回看的年數。
+const SEASONAL_MAX_YEARS: usize = 5;
+
+/// 季節性常態至少需要的歷年樣本數；不足時不計算驚喜幅度。
+const SEASONAL_MIN_YEARS: usize = 2;
+
+/// 判斷創新高前至少需要的歷史月數，避免剛上市的公司每個月都是新高。
+const ALL_TIME_HIGH_MIN_HISTORY: usize = 12;
+
+/// 單一公司單月的營收。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevenuePoint {
+    /// 股票代號。
+    pub security_code: String,
+    /// 營收月份（`YYYYMM`）。
+    pub month: i64,
+    /// 當月營收。
+    pub revenue: Decimal,
+}
+
+/// 單一公司單月的營收動能與驚喜指標。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevenueMomentum {
+    /// 股票代號。
+    pub security_code: String,
+    /// 營收月份（`YYYYMM`）。
+    pub month: i64,
+    /// 當月營收。
+    pub revenue: Decimal,
+    /// 近 3 個月營收合計；任一月缺值時為 `None`。
+    pub rolling_3m: Option<Decimal>,
+    /// 近 12 個月營收合計；任一月缺值時為 `None`。
+    pub rolling_12m: Option<Decimal>,
+    /// 單月營收年增率（%）。
+    pub yoy_pct: Option<Decimal>,
+    /// 近 3 個月合計年增率（%）。
+    pub rolling_3m_yoy_pct: Option<Decimal>,
+    /// 近 12 個月合計年增率（%）。
+    pub rolling_12m_yoy_pct: Option<Decimal>,
+    /// 年增率加速度（百分點）：近 3 個月年增率減去前一個 3 個月區間的年增率。
+    pub yoy_acceleration_pct: Option<Decimal>,
+    /// 當月營收是否高於過去所有月份。
+    pub all_time_high: bool,
+    /// 依歷年同月季節性推估的當月營收。
+    pub seasonal_expected: Option<Decimal>,
+    /// 營收驚喜（%）：實際營收相對季節性推估的差距。
+    pub surprise_pct: Option<Decimal>,
+    /// 推估季節性常態採用的歷年樣本數。
+    pub seasonal_years: i16,
+}
+
+impl RevenueMomentum {
+    /// 是否值得在月營收摘要中提醒：創新高，或驚喜幅度絕對值達到門檻。
+    pub fn is_notable(&self, surprise_threshold_pct: Decimal) -> bool {
+        self.all_time_high
+            || self
+                .surprise_pct
+                .is_some_and(|surprise| surprise.abs() >= surprise_threshold_pct)
+    }
+}
+
+/// 單一公司依月份排序的營收歷史。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RevenueHistory {
+    security_code: String,
+    /// 以月份序號（年 × 12 + 月 − 1）為鍵，方便跨年位移。
+    revenues: BTreeMap<i64, Decimal>,
+}
+
+impl RevenueHistory {
+    /// 建立空的營收歷史。
+    pub fn new(security_code: &str) -> Self {
+        RevenueHistory {
+            security_code: security_code.to_string(),
+            revenues: BTreeMap::new(),
+        }
+    }
+
+    /// 加入單月營收；同一月份重複加入時以後者為準。
+    pub fn insert(&mut self, month: i64, revenue: Decimal) {
+        self.revenues.insert(month_index(month), revenue);
+    }
+
+    /// 已有營收的月份（`YYYYMM`），由舊到新。
+    pub fn months(&self) -> impl Iterator<Item = i64> + '_ {
+        self.revenues.keys().map(|index| index_month(*index))
+    }
+
+    /// 計算指定月份的動能指標；該月沒有營收時回傳 `None`。
+    pub fn momentum(&self, month: i64) -> Option<RevenueMomentum> {
+        let index = month_index(month);
+        let revenue = *self.revenues.get(&index)?;
+
+        let rolling_3m = self.rolling(index, 3);
+        let rolling_12m = self.rolling(index, 12);
+        let rolling_3m_yoy_pct = growth_pct(rolling_3m, self.rolling(index - 12, 3));
+        let previous_3m_yoy_pct =
+            growth_pct(self.rolling(index - 3, 3), self.rolling(index - 15, 3));
+
+        let history: Vec<Decimal> = self
+            .revenues
+            .range(..index)
+            .map(|(_, value)| *value)
+            .collect();
+        let all_time_high = history.len() >= ALL_TIME_HIGH_MIN_HISTORY
+            && history.iter().all(|previous| revenue > *previous);
+
+        let (seasonal_expected, seasonal_years) = self.seasonal_expected(index);
+
+        Some(RevenueMomentum {
+            security_code: self.security_code.clone(),
+            month,
+            revenue,
+            rolling_3m,
+            rolling_12m,
+            yoy_pct: growth_pct(Some(revenue), self.revenues.get(&(index - 12)).copied()),
+            rolling_3m_yoy_pct,
+            rolling_12m_yoy_pct: growth_pct(rolling_12m, self.rolling(index - 12, 12)),
+            yoy_acceleration_pct: rolling_3m_yoy_pct
+                .zip(previous_3m_yoy_pct)
+                .map(|(current, previous)| current - previous),
+            all_time_high,
+            seasonal_expected: seasonal_expected.map(|value| value.round_dp(4)),
+            surprise_pct: growth_pct(Some(revenue), seasonal_expected),
+            seasonal_years: seasonal_years as i16,
+        })
+    }
+
+    /// 截至 `end`（含）連續 `months` 個月的合計。
+    fn rolling(&self, end: i64, months: i64) -> Option<Decimal> {
+        (end - months + 1..=end).try_fold(Decimal::ZERO, |sum, index| {
+            self.revenues.get(&index).map(|value| sum + value)
+        })
+    }
+
+    /// 以歷年同月「當月營收 ÷ 前 12 個月平均」的中位數乘上今年前 12 個月平均，
+    /// 推估當月營收；以前 12 個月平均為基準可同時排除公司規模成長的影響。
+    fn seasonal_expected(&self, index: i64) -> (Option<Decimal>, usize) {
+        let Some(base) = self.rolling(index - 1, 12).map(|sum| sum / dec!(12)) else {
+            return (None, 0);
+        };
+        let mut ratios: Vec<Decimal> = (1..=SEASONAL_MAX_YEARS as i64)
+            .filter_map(|years| {
+                let past = index - 12 * years;
+                let revenue = self.revenues.get(&past)?;
+                let average = self.rolling(past - 1, 12)? / dec!(12);
+                (average > Decimal::ZERO).then(|| revenue / average)
+            })
+            .collect();
+        if ratios.len() < SEASONAL_MIN_YEARS || base <= Decimal::ZERO {
+            return (None, ratios.len());
+        }
+
+        ratios.sort();
+        let middle = ratios.len() / 2;
+        let median = if ratios.len().is_multiple_of(2) {
+            (ratios[middle - 1] + ratios[middle]) / dec!(2)
+        } else {
+            ratios[middle]
+        };
+        (Some(base * median), ratios.len())
+    }
+}
+
+/// 將多家公司的營收依公司分組成營收歷史。
+pub fn group_histories(points: &[RevenuePoint]) -> BTreeMap<String, RevenueHistory> {
+    let mut histories: BTreeMap<String, RevenueHistory> = BTreeMap::new();
+    for point in points {
+        histories
+            .entry(point.security_code.clone())
+            .or_insert_with(|| RevenueHistory::new(&point.security_code))
+            .insert(point.month, point.revenue);
+    }
+    histories
+}
+
+/// `YYYYMM` 轉月份序號。
+fn month_index(month: i64) -> i64 {
+    (month / 100) * 12 + (month % 100) - 1
+}
+
+/// 月份序號轉 `YYYYMM`。
+fn index_month(index: i64) -> i64 {
+    (index / 12) * 100 + index % 12 + 1
+}
+
+/// 成長率（%）；基期非正數時無法計算。
+fn growth_pct(current: Option<Decimal>, base: Option<Decimal>) -> Option<Decimal> {
+    let base = base?;
+    if base <= Decimal::ZERO {
+        return None;
+    }
+    Some(((current? / base - Decimal::ONE) * dec!(100)).round_dp(4))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 四年每月營收：每年成長 10%，每年 12 月為旺季（平常月份的兩倍）。
+    fn history() -> RevenueHistory {
+        let mut history = RevenueHistory::new("2330");
+        for (year, level) in [
+            (2021, dec!(100)),
+            (2022, dec!(110)),
+            (2023, dec!(121)),
+            (2024, dec!(133.1)),
+        ] {
+            for month in 1..=12 {
+                let revenue = if month == 12 { level * dec!(2) } else { level };
+                history.insert(year * 100 + month, revenue);
+            }
+        }
+        history
+    }
+
+    #[test]
+    fn month_index_round_trips_across_years() {
+        assert_eq!(index_month(month_index(202401) - 1), 202312);
+        assert_eq!(index_month(month_index(202412) + 1), 202501);
+        let history = history();
+        assert_eq!(history.months().next(), Some(202101));
+    }
+
+    /// 滾動合計需要連續月份，年增率以去年同期合計為基期。
+    #[test]
+    fn momentum_rolls_and_compares_with_last_year() {
+        let momentum = history().momentum(202411).unwrap();
+        assert_eq!(momentum.rolling_3m, Some(dec!(399.3)));
+        assert_eq!(momentum.rolling_12m, Some(dec!(1706.1)));
+        assert_eq!(momentum.yoy_pct, Some(dec!(10)));
+        assert_eq!(momentum.rolling_3m_yoy_pct, Some(dec!(10)));
+        assert_eq!(momentum.rolling_12m_yoy_pct, Some(dec!(10)));
+        assert_eq!(momentum.yoy_acceleration_pct, Some(dec!(0)));
+
+        let first = history().momentum(202101).unwrap();
+        assert_eq!(first.rolling_3m, None);
+        assert_eq!(first.yoy_pct, None);
+        assert!(history().momentum(202501).is_none());
+    }
+
+    /// 旺季本來就高，只有超出季節性常態的部分才算驚喜。
+    #[test]
+    fn seasonal_surprise_discounts_expected_peak() {
+        let momentum = history().momentum(202412).unwrap();
+        assert_eq!(momentum.seasonal_years, 2);
+        assert!(momentum.all_time_high);
+        // 2021 年 12 月之前沒有完整 12 個月，只有 2022、2023 兩年可當樣本。
+        let surprise = momentum.surprise_pct.unwrap();
+        assert!(surprise.abs() < dec!(1), "surprise {surprise}");
+
+        let mut boosted = history();
+        boosted.insert(202412, dec!(399.3));
+        let momentum = boosted.momentum(202412).unwrap();
+        assert!(momentum.surprise_pct.unwrap() > dec!(45));
+        assert!(momentum.is_notable(dec!(20)));
+    }
+
+    /// 歷史太短時不判斷創新高，也不推估季節性。
+    #[test]
+    fn short_history_has_no_high_or_surprise() {
+        let mut history = RevenueHistory::new("6666");
+        for month in 1..=6 {
+            history.insert(202400 + month, Decimal::from(month));
+        }
+        let momentum = history.momentum(202406).unwrap();
+        assert!(!momentum.all_time_high);
+        assert_eq!(momentum.seasonal_expected, None);
+        assert_eq!(momentum.seasonal_years, 0);
+        assert!(!momentum.is_notable(dec!(20)));
+    }
+
+    #[test]
+    fn group_histories_splits_by_company() {
+        let points = vec![
+            RevenuePoint {
+                security_code: "2330".to_string(),
+                month: 202401,
+                revenue: dec!(1),
+            },
+            RevenuePoint {
+                security_code: "1101".to_string(),
+                month: 202401,
+                revenue: dec!(2),
+            },
+        ];
+        let histories = group_histories(&points);
+        assert_eq!(histories.len(), 2);
+        assert_eq!(histories["1101"].momentum(202401).unwrap().revenue, dec!(2));
+    }
+}
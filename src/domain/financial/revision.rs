@@ -0,0 +1,137 @@
+use chrono::{DateTime, Local};
+use serde_json::Value;
+use strum::{Display, EnumString};
+
+/// 保留版本歷史的基本面資料種類，字串值與 `fundamental_revision.kind` 相同。
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Display, EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum FundamentalKind {
+    /// 月營收（`"Revenue"`）。
+    Revenue,
+    /// 財務報表（`financial_statement`）。
+    FinancialStatement,
+    /// 股利（`dividend`）。
+    Dividend,
+}
+
+/// 基本面資料某一期的單一版本。
+///
+/// 每次寫入的數值與前一版不同就會多一個版本；`first_seen_time` 是第一次得知
+/// 該期數字的時間，`recorded_time` 是得知此版本數值的時間。
+#[derive(Debug, Clone, PartialEq)]
+pub struct FundamentalRevision {
+    /// 資料種類。
+    pub kind: FundamentalKind,
+    /// 股票代號。
+    pub security_code: String,
+    /// 資料期間，格式見 [`revenue_period`] 與 [`statement_period`]。
+    pub period: String,
+    /// 版本序號，從 1 開始。
+    pub revision: i32,
+    /// 該版本的整列資料（欄位名稱與資料表相同）。
+    pub payload: Value,
+    /// 首次得知該期數字的時間。
+    pub first_seen_time: DateTime<Local>,
+    /// 得知此版本數值的時間。
+    pub recorded_time: DateTime<Local>,
+}
+
+impl FundamentalRevision {
+    /// 是否為公布後的修正版本。
+    pub fn is_restatement(&self) -> bool {
+        self.revision > 1
+    }
+
+    /// 與前一版相比數值有變動的欄位，依欄位名稱排序。
+    pub fn changed_fields(&self, previous: &FundamentalRevision) -> Vec<String> {
+        let (Some(current), Some(previous)) =
+            (self.payload.as_object(), previous.payload.as_object())
+        else {
+            return Vec::new();
+        };
+        let mut fields: Vec<String> = current
+            .iter()
+            .filter(|(field, value)| !same_value(previous.get(*field), Some(value)))
+            .map(|(field, _)| field.clone())
+            .chain(
+                previous
+                    .keys()
+                    .filter(|field| !current.contains_key(*field))
+                    .cloned(),
+            )
+            .collect();
+        fields.sort();
+        fields
+    }
+}
+
+/// 月營收的資料期間（`YYYYMM`）。
+pub fn revenue_period(month: i64) -> String {
+    month.to_string()
+}
+
+/// 財務報表與股利的資料期間（`年度-季別`，全年度的季別為空字串）。
+pub fn statement_period(year: i64, quarter: &str) -> String {
+    format!("{year}-{quarter}")
+}
+
+/// 比較兩個欄位值；數字依數值比較，避免 `1.5000` 與 `1.5` 被視為不同。
+fn same_value(a: Option<&Value>, b: Option<&Value>) -> bool {
+    match (a, b) {
+        (Some(Value::Number(a)), Some(Value::Number(b))) => a.as_f64() == b.as_f64(),
+        (a, b) => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use serde_json::json;
+
+    use super::*;
+
+    fn revision(revision: i32, payload: Value) -> FundamentalRevision {
+        FundamentalRevision {
+            kind: FundamentalKind::Revenue,
+            security_code: "2330".to_string(),
+            period: revenue_period(202409),
+            revision,
+            payload,
+            first_seen_time: Local::now(),
+            recorded_time: Local::now(),
+        }
+    }
+
+    #[test]
+    fn kind_round_trips_table_value() {
+        assert_eq!(
+            FundamentalKind::FinancialStatement.to_string(),
+            "financial_statement"
+        );
+        assert_eq!(
+            FundamentalKind::from_str("dividend").unwrap(),
+            FundamentalKind::Dividend
+        );
+        assert_eq!(statement_period(2024, ""), "2024-");
+        assert_eq!(statement_period(2024, "Q3"), "2024-Q3");
+    }
+
+    /// 只列出數值真的改變的欄位，小數位數不同不算修正。
+    #[test]
+    fn changed_fields_compares_values() {
+        let first = revision(
+            1,
+            json!({"Monthly": 100.5, "LastMonth": 90, "Date": 202409}),
+        );
+        let second = revision(
+            2,
+            json!({"Monthly": 101.0, "LastMonth": 90.0000, "Date": 202409, "avg_price": 1}),
+        );
+
+        assert!(!first.is_restatement());
+        assert!(second.is_restatement());
+        assert_eq!(second.changed_fields(&first), vec!["Monthly", "avg_price"]);
+        assert_eq!(first.changed_fields(&second), vec!["Monthly", "avg_price"]);
+    }
+}
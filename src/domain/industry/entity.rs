@@ -0,0 +1,205 @@
+use chrono::{Datelike, Months, NaiveDate};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+/// 產業指數的起始點位。
+pub const BASE_INDEX_LEVEL: Decimal = dec!(100);
+
+/// 月營收的法定公告期限（次月 10 日）。
+const REVENUE_DEADLINE_DAY: u32 = 10;
+
+/// 單一交易日、單一產業由報價彙總而成的原始統計。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndustryDailyAggregate {
+    /// 交易日。
+    pub date: NaiveDate,
+    /// 產業分類編號（`stock_industry.stock_industry_id`）。
+    pub industry_id: i32,
+    /// 納入統計的股票數。
+    pub stocks: i32,
+    /// 上漲家數。
+    pub advances: i32,
+    /// 下跌家數。
+    pub declines: i32,
+    /// 平盤家數。
+    pub unchanged: i32,
+    /// 以前一日市值加權的當日報酬率（%）；產業內沒有發行股數資料時為 `None`。
+    pub cap_weighted_return_pct: Option<Decimal>,
+    /// 產業內個股當日報酬率的簡單平均（%）。
+    pub equal_weighted_return_pct: Option<Decimal>,
+    /// 成交金額合計。
+    pub trade_value: Decimal,
+    /// 成交金額佔全體上市櫃股票的比例（%）。
+    pub turnover_share_pct: Option<Decimal>,
+}
+
+/// 單一產業某個營收月份的彙總營收。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndustryRevenueGrowth {
+    /// 產業分類編號。
+    pub industry_id: i32,
+    /// 有去年同月營收可比較的公司數。
+    pub companies: i32,
+    /// 產業合計營收相對去年同月的成長率（%）。
+    pub yoy_pct: Option<Decimal>,
+}
+
+/// 單一交易日、單一產業的產業指標。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndustryDailyMetric {
+    /// 交易日。
+    pub date: NaiveDate,
+    /// 產業分類編號。
+    pub industry_id: i32,
+    /// 納入統計的股票數。
+    pub stocks: i32,
+    /// 上漲家數。
+    pub advances: i32,
+    /// 下跌家數。
+    pub declines: i32,
+    /// 平盤家數。
+    pub unchanged: i32,
+    /// 市值加權報酬率（%）。
+    pub cap_weighted_return_pct: Option<Decimal>,
+    /// 等權重報酬率（%）。
+    pub equal_weighted_return_pct: Option<Decimal>,
+    /// 以市值加權報酬率逐日連乘的產業指數，自第一筆的 [`BASE_INDEX_LEVEL`] 起算。
+    pub index_level: Decimal,
+    /// 成交金額合計。
+    pub trade_value: Decimal,
+    /// 成交金額佔比（%）。
+    pub turnover_share_pct: Option<Decimal>,
+    /// 營收成長採用的營收月份（`YYYYMM`）；當時尚無可比較的營收時為 `None`。
+    pub revenue_period: Option<i64>,
+    /// 上述月份有去年同月營收可比較的公司數。
+    pub revenue_companies: i32,
+    /// 產業合計月營收年增率（%）。
+    pub revenue_yoy_pct: Option<Decimal>,
+}
+
+impl IndustryDailyMetric {
+    /// 由當日統計、營收成長與同產業前一交易日的指標組出當日指標。
+    ///
+    /// 產業指數以前一日點位乘上當日市值加權報酬；沒有前一日時從
+    /// [`BASE_INDEX_LEVEL`] 起算，當日報酬無法計算時沿用前一日點位。
+    pub fn next(
+        aggregate: &IndustryDailyAggregate,
+        revenue_period: i64,
+        revenue: Option<&IndustryRevenueGrowth>,
+        previous: Option<&IndustryDailyMetric>,
+    ) -> Self {
+        let index_level = match (previous, aggregate.cap_weighted_return_pct) {
+            (Some(previous), Some(pct)) => {
+                (previous.index_level * (Decimal::ONE + pct / dec!(100))).round_dp(4)
+            }
+            (Some(previous), None) => previous.index_level,
+            (None, _) => BASE_INDEX_LEVEL,
+        };
+
+        IndustryDailyMetric {
+            date: aggregate.date,
+            industry_id: aggregate.industry_id,
+            stocks: aggregate.stocks,
+            advances: aggregate.advances,
+            declines: aggregate.declines,
+            unchanged: aggregate.unchanged,
+            cap_weighted_return_pct: aggregate.cap_weighted_return_pct,
+            equal_weighted_return_pct: aggregate.equal_weighted_return_pct,
+            index_level,
+            trade_value: aggregate.trade_value,
+            turnover_share_pct: aggregate.turnover_share_pct,
+            revenue_period: revenue.map(|_| revenue_period),
+            revenue_companies: revenue.map_or(0, |growth| growth.companies),
+            revenue_yoy_pct: revenue.and_then(|growth| growth.yoy_pct),
+        }
+    }
+}
+
+/// 查詢用的產業指標，附上產業名稱。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndustrySnapshot {
+    /// 產業名稱。
+    pub industry_name: String,
+    /// 當日指標。
+    pub metric: IndustryDailyMetric,
+}
+
+/// 指定交易日可以採用的最新營收月份（`YYYYMM`）。
+///
+/// 月營收在次月 10 日前陸續公布，期限前同業只公布一部分，合計的年增率會隨
+/// 公布順序跳動；因此期限（含當日）之後才改用新月份，避免用到當時還不完整
+/// 的資料。
+pub fn revenue_period_as_of(date: NaiveDate) -> i64 {
+    let months_back = if date.day() >= REVENUE_DEADLINE_DAY {
+        1
+    } else {
+        2
+    };
+    let period = date
+        .with_day(1)
+        .and_then(|first| first.checked_sub_months(Months::new(months_back)))
+        .unwrap_or(date);
+    i64::from(period.year()) * 100 + i64::from(period.month())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).expect("測試日期應合法")
+    }
+
+    fn aggregate(day: u32, cap_weighted_return_pct: Option<Decimal>) -> IndustryDailyAggregate {
+        IndustryDailyAggregate {
+            date: date(2025, 3, day),
+            industry_id: 24,
+            stocks: 10,
+            advances: 6,
+            declines: 3,
+            unchanged: 1,
+            cap_weighted_return_pct,
+            equal_weighted_return_pct: Some(dec!(0.5)),
+            trade_value: dec!(1000),
+            turnover_share_pct: Some(dec!(12.5)),
+        }
+    }
+
+    /// 期限當天起才改用上個月的營收，跨年時正確退回去年 12 月或 11 月。
+    #[test]
+    fn revenue_period_waits_for_deadline() {
+        assert_eq!(revenue_period_as_of(date(2025, 3, 9)), 202501);
+        assert_eq!(revenue_period_as_of(date(2025, 3, 10)), 202502);
+        assert_eq!(revenue_period_as_of(date(2025, 1, 10)), 202412);
+        assert_eq!(revenue_period_as_of(date(2025, 1, 9)), 202411);
+    }
+
+    /// 產業指數首日為 100，之後以市值加權報酬連乘；報酬缺值時沿用前一日。
+    #[test]
+    fn index_level_compounds_cap_weighted_return() {
+        let first = IndustryDailyMetric::next(&aggregate(3, Some(dec!(2))), 202501, None, None);
+        assert_eq!(first.index_level, dec!(100));
+        assert_eq!(first.revenue_period, None);
+
+        let second =
+            IndustryDailyMetric::next(&aggregate(4, Some(dec!(-1))), 202501, None, Some(&first));
+        assert_eq!(second.index_level, dec!(99));
+
+        let third = IndustryDailyMetric::next(&aggregate(5, None), 202501, None, Some(&second));
+        assert_eq!(third.index_level, dec!(99));
+    }
+
+    /// 營收月份只在確實有彙總時才記錄。
+    #[test]
+    fn revenue_growth_is_attached_with_period() {
+        let growth = IndustryRevenueGrowth {
+            industry_id: 24,
+            companies: 8,
+            yoy_pct: Some(dec!(15.2)),
+        };
+        let metric = IndustryDailyMetric::next(&aggregate(3, None), 202501, Some(&growth), None);
+        assert_eq!(metric.revenue_period, Some(202501));
+        assert_eq!(metric.revenue_companies, 8);
+        assert_eq!(metric.revenue_yoy_pct, Some(dec!(15.2)));
+    }
+}
@@ -0,0 +1,22 @@
+//! 產業分析領域。
+//!
+//! 以 `stocks.stock_industry_id` 將上市櫃股票分組，每日彙總市值加權與等權重
+//! 報酬、漲跌家數、成交金額佔比，並以月營收計算產業合計年增率。市值加權報酬
+//! 逐日連乘成產業指數，再與加權指數比較近 1 週、1 個月、3 個月的相對強弱，
+//! 排出類股輪動。
+
+/// 產業指標實體模組。
+pub mod entity;
+/// 產業指標倉儲介面模組。
+pub mod repository;
+/// 類股輪動模組。
+pub mod rotation;
+
+pub use entity::{
+    BASE_INDEX_LEVEL, IndustryDailyAggregate, IndustryDailyMetric, IndustryRevenueGrowth,
+    IndustrySnapshot, revenue_period_as_of,
+};
+pub use repository::IndustryMetricRepository;
+pub use rotation::{
+    IndustryLevel, IndustryRotation, RotationSnapshot, RotationWindow, WindowStrength,
+};
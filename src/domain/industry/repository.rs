@@ -0,0 +1,36 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use super::{
+    entity::{
+        IndustryDailyAggregate, IndustryDailyMetric, IndustryRevenueGrowth, IndustrySnapshot,
+    },
+    rotation::RotationSnapshot,
+};
+
+/// 產業指標倉儲介面。
+#[async_trait]
+pub trait IndustryMetricRepository: Send + Sync {
+    /// 讀取區間內（含頭尾）每個交易日、每個產業的報價彙總，依日期與產業排序。
+    async fn fetch_daily_aggregates(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<IndustryDailyAggregate>>;
+
+    /// 讀取指定營收月份（`YYYYMM`）各產業的合計營收年增率。
+    async fn fetch_revenue_growth(&self, period: i64) -> Result<Vec<IndustryRevenueGrowth>>;
+
+    /// 讀取各產業在指定日期之前的最後一筆指標，作為產業指數的遞推起點。
+    async fn fetch_latest_before(&self, date: NaiveDate) -> Result<Vec<IndustryDailyMetric>>;
+
+    /// 寫入或覆蓋指標，回傳影響列數。
+    async fn upsert(&self, metrics: &[IndustryDailyMetric]) -> Result<u64>;
+
+    /// 讀取截至 `to`（未提供時為最新）最近一個交易日的全部產業指標。
+    async fn fetch_snapshots(&self, to: Option<NaiveDate>) -> Result<Vec<IndustrySnapshot>>;
+
+    /// 讀取截至 `to`（未提供時為最新）計算類股輪動所需的點位；沒有任何指標時回傳 `None`。
+    async fn fetch_rotation(&self, to: Option<NaiveDate>) -> Result<Option<RotationSnapshot>>;
+}
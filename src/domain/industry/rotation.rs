@@ -0,0 +1,299 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+/// 類股輪動比較的回顧期間。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RotationWindow {
+    /// 近 1 週（5 個交易日）。
+    OneWeek,
+    /// 近 1 個月（20 個交易日）。
+    OneMonth,
+    /// 近 3 個月（60 個交易日）。
+    ThreeMonths,
+}
+
+impl RotationWindow {
+    /// 全部回顧期間，由短至長。
+    pub const ALL: [Self; 3] = [Self::OneWeek, Self::OneMonth, Self::ThreeMonths];
+
+    /// 回顧的交易日數。
+    pub fn trading_days(self) -> usize {
+        match self {
+            Self::OneWeek => 5,
+            Self::OneMonth => 20,
+            Self::ThreeMonths => 60,
+        }
+    }
+
+    /// API 使用的代碼。
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::OneWeek => "1w",
+            Self::OneMonth => "1m",
+            Self::ThreeMonths => "3m",
+        }
+    }
+
+    /// 由代碼還原；未知代碼回傳 `None`。
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|window| window.code() == code)
+    }
+}
+
+/// 單一產業在某個交易日的指數點位。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndustryLevel {
+    /// 交易日。
+    pub date: NaiveDate,
+    /// 產業分類編號。
+    pub industry_id: i32,
+    /// 產業名稱。
+    pub industry_name: String,
+    /// 納入統計的股票數。
+    pub stocks: i32,
+    /// 產業指數點位。
+    pub index_level: Decimal,
+}
+
+/// 計算類股輪動所需的資料：終點日與各回顧起點日的產業點位及加權指數。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RotationSnapshot {
+    /// 終點交易日。
+    pub date: NaiveDate,
+    /// 各回顧期間的起點交易日；歷史不足的期間不列入。
+    pub anchors: Vec<(RotationWindow, NaiveDate)>,
+    /// 終點日與各起點日的產業點位。
+    pub levels: Vec<IndustryLevel>,
+    /// 終點日與各起點日的加權指數（TAIEX）收盤點位。
+    pub benchmark: HashMap<NaiveDate, Decimal>,
+}
+
+/// 單一產業在某個回顧期間的表現。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowStrength {
+    /// 回顧期間。
+    pub window: RotationWindow,
+    /// 起點交易日。
+    pub from: NaiveDate,
+    /// 產業指數報酬率（%）。
+    pub return_pct: Decimal,
+    /// 加權指數同期報酬率（%）。
+    pub benchmark_return_pct: Option<Decimal>,
+    /// 相對強弱：產業相對加權指數的超額報酬（%），以 (1 + 產業) / (1 + 大盤) − 1 計算。
+    pub relative_strength_pct: Option<Decimal>,
+    /// 依相對強弱由強到弱的一起始名次。
+    pub rank: Option<u32>,
+}
+
+/// 單一產業的類股輪動結果。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndustryRotation {
+    /// 產業分類編號。
+    pub industry_id: i32,
+    /// 產業名稱。
+    pub industry_name: String,
+    /// 終點日納入統計的股票數。
+    pub stocks: i32,
+    /// 終點日產業指數點位。
+    pub index_level: Decimal,
+    /// 各回顧期間的表現，由短至長；起點日沒有點位的期間省略。
+    pub windows: Vec<WindowStrength>,
+}
+
+impl IndustryRotation {
+    /// 取得指定回顧期間的表現。
+    pub fn window(&self, window: RotationWindow) -> Option<&WindowStrength> {
+        self.windows
+            .iter()
+            .find(|strength| strength.window == window)
+    }
+}
+
+impl RotationSnapshot {
+    /// 計算每個產業在各回顧期間的相對強弱並排名。
+    ///
+    /// 回傳順序依 `sort` 期間的名次，沒有名次的產業排在最後並依產業編號排列。
+    pub fn rank(&self, sort: RotationWindow) -> Vec<IndustryRotation> {
+        let mut levels: HashMap<(i32, NaiveDate), Decimal> = HashMap::new();
+        for level in &self.levels {
+            levels.insert((level.industry_id, level.date), level.index_level);
+        }
+
+        let mut rotations: Vec<IndustryRotation> = self
+            .levels
+            .iter()
+            .filter(|level| level.date == self.date)
+            .map(|current| IndustryRotation {
+                industry_id: current.industry_id,
+                industry_name: current.industry_name.clone(),
+                stocks: current.stocks,
+                index_level: current.index_level,
+                windows: self
+                    .anchors
+                    .iter()
+                    .filter_map(|(window, from)| {
+                        let start = levels.get(&(current.industry_id, *from))?;
+                        let return_pct = change_pct(*start, current.index_level)?;
+                        let benchmark_return_pct =
+                            match (self.benchmark.get(from), self.benchmark.get(&self.date)) {
+                                (Some(start), Some(end)) => change_pct(*start, *end),
+                                _ => None,
+                            };
+                        Some(WindowStrength {
+                            window: *window,
+                            from: *from,
+                            return_pct,
+                            benchmark_return_pct,
+                            relative_strength_pct: benchmark_return_pct
+                                .and_then(|benchmark| relative_strength(return_pct, benchmark)),
+                            rank: None,
+                        })
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        for (window, _) in &self.anchors {
+            let mut ordered: Vec<(usize, usize, Decimal)> = rotations
+                .iter()
+                .enumerate()
+                .filter_map(|(row, rotation)| {
+                    let position = rotation
+                        .windows
+                        .iter()
+                        .position(|strength| strength.window == *window)?;
+                    let strength = rotation.windows[position].relative_strength_pct?;
+                    Some((row, position, strength))
+                })
+                .collect();
+            ordered.sort_by(|a, b| {
+                b.2.cmp(&a.2)
+                    .then(rotations[a.0].industry_id.cmp(&rotations[b.0].industry_id))
+            });
+            for (rank, (row, position, _)) in ordered.into_iter().enumerate() {
+                rotations[row].windows[position].rank = Some(rank as u32 + 1);
+            }
+        }
+
+        rotations.sort_by_key(|rotation| {
+            (
+                rotation
+                    .window(sort)
+                    .and_then(|strength| strength.rank)
+                    .unwrap_or(u32::MAX),
+                rotation.industry_id,
+            )
+        });
+        rotations
+    }
+}
+
+/// 區間報酬率（%）；起點非正數時無法計算。
+fn change_pct(start: Decimal, end: Decimal) -> Option<Decimal> {
+    if start <= Decimal::ZERO {
+        return None;
+    }
+    Some(((end / start - Decimal::ONE) * dec!(100)).round_dp(4))
+}
+
+/// 以報酬率（%）計算相對強弱（%）。
+fn relative_strength(return_pct: Decimal, benchmark_pct: Decimal) -> Option<Decimal> {
+    let benchmark = Decimal::ONE + benchmark_pct / dec!(100);
+    if benchmark <= Decimal::ZERO {
+        return None;
+    }
+    Some(
+        (((Decimal::ONE + return_pct / dec!(100)) / benchmark - Decimal::ONE) * dec!(100))
+            .round_dp(4),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, d).expect("測試日期應合法")
+    }
+
+    fn level(day: u32, industry_id: i32, index_level: Decimal) -> IndustryLevel {
+        IndustryLevel {
+            date: date(day),
+            industry_id,
+            industry_name: format!("產業{industry_id}"),
+            stocks: 5,
+            index_level,
+        }
+    }
+
+    fn snapshot() -> RotationSnapshot {
+        RotationSnapshot {
+            date: date(28),
+            anchors: vec![
+                (RotationWindow::OneWeek, date(21)),
+                (RotationWindow::OneMonth, date(3)),
+            ],
+            levels: vec![
+                level(3, 1, dec!(100)),
+                level(21, 1, dec!(100)),
+                level(28, 1, dec!(110)),
+                level(3, 24, dec!(100)),
+                level(21, 24, dec!(120)),
+                level(28, 24, dec!(105)),
+                // 新產業只有終點日點位，沒有任何期間可比較。
+                level(28, 30, dec!(100)),
+            ],
+            benchmark: HashMap::from([
+                (date(3), dec!(20000)),
+                (date(21), dec!(20000)),
+                (date(28), dec!(22000)),
+            ]),
+        }
+    }
+
+    /// 相對強弱以大盤報酬調整，各期間分開排名，沒有資料的產業排最後。
+    #[test]
+    fn rank_orders_by_relative_strength() {
+        let rotations = snapshot().rank(RotationWindow::OneWeek);
+        assert_eq!(
+            rotations
+                .iter()
+                .map(|rotation| rotation.industry_id)
+                .collect::<Vec<_>>(),
+            vec![1, 24, 30]
+        );
+
+        let cement = rotations[0].window(RotationWindow::OneWeek).unwrap();
+        assert_eq!(cement.return_pct, dec!(10));
+        assert_eq!(cement.benchmark_return_pct, Some(dec!(10)));
+        assert_eq!(cement.relative_strength_pct, Some(Decimal::ZERO));
+        assert_eq!(cement.rank, Some(1));
+
+        let semis = rotations[1].window(RotationWindow::OneWeek).unwrap();
+        assert_eq!(semis.return_pct, dec!(-12.5));
+        assert_eq!(semis.rank, Some(2));
+        assert_eq!(
+            rotations[1]
+                .window(RotationWindow::OneMonth)
+                .unwrap()
+                .relative_strength_pct,
+            Some(dec!(-4.5455))
+        );
+        assert!(rotations[2].windows.is_empty());
+        assert!(rotations[0].window(RotationWindow::ThreeMonths).is_none());
+    }
+
+    /// 改以一個月排序時名次跟著改變。
+    #[test]
+    fn rank_respects_sort_window() {
+        let rotations = snapshot().rank(RotationWindow::OneMonth);
+        assert_eq!(rotations[0].industry_id, 1);
+        assert_eq!(
+            rotations[0].window(RotationWindow::OneMonth).unwrap().rank,
+            Some(1)
+        );
+    }
+}
@@ -1,12 +1,21 @@
+pub mod breadth;
+pub mod chip;
 pub mod config;
+pub mod derivatives;
+pub mod disclosure;
 pub mod dividend;
+pub mod event_log;
 pub mod events;
 pub mod financial;
+pub mod industry;
 pub mod market_index;
 pub mod money_flow;
 pub mod performance;
 pub mod portfolio;
 pub mod quote;
 pub mod registry;
+pub mod subscription;
+pub mod surveillance;
 pub mod trace;
+pub mod valuation;
 pub mod yield_rank;
@@ -1,5 +1,6 @@
-use chrono::NaiveDate;
+use chrono::{DateTime, Local, NaiveDate};
 use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
 
 /// 固定投入金額（元）。所有 CAGR 模擬皆以此金額為期初投入。
 pub const PRINCIPAL: i64 = 10_000;
@@ -220,6 +221,140 @@ pub struct CorporateAction {
     pub note: String,
 }
 
+impl CorporateAction {
+    /// 由股數變動比例推得事件類型：小於 1 是減資或反向分割，其餘視為分割。
+    ///
+    /// 讓登錄者少填一個容易與比例矛盾的欄位。
+    pub fn action_type_for(share_ratio: Decimal) -> &'static str {
+        if share_ratio < Decimal::ONE {
+            "capital_reduction"
+        } else {
+            "split"
+        }
+    }
+}
+
+/// 自動產生、待人工確認的公司行動建議。
+///
+/// 來源（例如重大訊息）通常只揭露「將辦理減資」而沒有可直接套用的生效日
+/// 與比例，因此兩者皆可為空，由維運人員確認時補齊後再寫入 [`CorporateAction`]。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorporateActionProposal {
+    /// 股票代號。
+    pub stock_symbol: String,
+    /// 建議來源，例如 `mops`。
+    pub source: String,
+    /// 來源內的識別；同來源同代號的相同識別只建議一次。
+    pub reference: String,
+    /// 事件類型：`split` 或 `capital_reduction`。
+    pub action_type: String,
+    /// 預估生效日。
+    pub effective_date: Option<NaiveDate>,
+    /// 預估股數變動比例。
+    pub share_ratio: Option<Decimal>,
+    /// 備註。
+    pub note: String,
+}
+
+/// 尚待審核的公司行動建議，附上資料庫流水號供後台確認或駁回時指定。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingCorporateActionProposal {
+    /// 流水號。
+    pub serial: i64,
+    /// 建議內容。
+    pub proposal: CorporateActionProposal,
+    /// 建立時間。
+    pub created_at: DateTime<Local>,
+}
+
+/// 漲跌幅限制由 7% 放寬為 10% 的第一個交易日。
+const PRICE_LIMIT_WIDENED_ON: NaiveDate = match NaiveDate::from_ymd_opt(2015, 6, 1) {
+    Some(date) => date,
+    None => panic!("invalid date"),
+};
+
+/// 判定跳空是否超出漲跌幅時額外保留的容忍度，吸收跳動單位造成的誤差。
+const PRICE_LIMIT_TOLERANCE: Decimal = dec!(0.005);
+
+/// 指定交易日適用的漲跌幅限制（比例）。
+pub fn price_limit_on(date: NaiveDate) -> Decimal {
+    if date < PRICE_LIMIT_WIDENED_ON {
+        dec!(0.07)
+    } else {
+        dec!(0.10)
+    }
+}
+
+/// 由事件前後的價格推估股數變動比例：持有 1 股在事件後變成幾股。
+///
+/// 減資與變更面額都不改變公司市值，價格變動與股數變動互為倒數；現金減資會
+/// 另外退還股款，推估值因此略有誤差，仍需人工確認。任一價格非正數時回傳 `None`。
+pub fn implied_share_ratio(price_before: Decimal, price_after: Decimal) -> Option<Decimal> {
+    if price_before <= Decimal::ZERO || price_after <= Decimal::ZERO {
+        return None;
+    }
+    Some((price_before / price_after).round_dp(4).normalize())
+}
+
+/// 前一筆收盤價到當日收盤價之間的跳空，用於從報價反推未登錄的公司行動。
+///
+/// 停止買賣期間沒有報價，`previous_close` 是停牌前最後一筆收盤價。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceGap {
+    /// 股票代號。
+    pub stock_symbol: String,
+    /// 跳空發生的交易日。
+    pub date: NaiveDate,
+    /// 前一筆收盤價。
+    pub previous_close: Decimal,
+    /// 當日收盤價。
+    pub close: Decimal,
+}
+
+impl PriceGap {
+    /// 漲跌比例（當日收盤 / 前一筆收盤 − 1）；前一筆收盤價非正數時回傳 `None`。
+    pub fn change_ratio(&self) -> Option<Decimal> {
+        if self.previous_close <= Decimal::ZERO {
+            return None;
+        }
+        Some(self.close / self.previous_close - Decimal::ONE)
+    }
+
+    /// 跳空幅度是否超出當日漲跌幅限制。
+    ///
+    /// 正常交易不可能超出漲跌幅，超出代表參考價被重新設定過 —— 除權息或公司
+    /// 行動。除權息由查詢端排除，這裡只判斷幅度。新股上市前五日與部分國外成分
+    /// ETF 沒有漲跌幅限制，會產生誤判，交由人工駁回。
+    pub fn exceeds_price_limit(&self) -> bool {
+        self.change_ratio()
+            .is_some_and(|ratio| ratio.abs() > price_limit_on(self.date) + PRICE_LIMIT_TOLERANCE)
+    }
+
+    /// 轉為待確認的公司行動建議；未超出漲跌幅或價格無效時回傳 `None`。
+    ///
+    /// 生效日即跳空當日（該日收盤價已是調整後價格），與 [`CorporateAction`] 的定義一致。
+    pub fn to_proposal(&self) -> Option<CorporateActionProposal> {
+        if !self.exceeds_price_limit() {
+            return None;
+        }
+        let share_ratio = implied_share_ratio(self.previous_close, self.close)?;
+
+        Some(CorporateActionProposal {
+            stock_symbol: self.stock_symbol.clone(),
+            source: "price_gap".to_string(),
+            reference: self.date.to_string(),
+            action_type: CorporateAction::action_type_for(share_ratio).to_string(),
+            effective_date: Some(self.date),
+            share_ratio: Some(share_ratio),
+            note: format!(
+                "無除權息的跳空：{} → {}",
+                self.previous_close.normalize(),
+                self.close.normalize()
+            ),
+        })
+    }
+}
+
 /// 單一口徑的模擬結果。
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct SimulationOutcome {
@@ -421,6 +556,68 @@ mod tests {
         assert_eq!(make(None, None).sort_key(), None);
     }
 
+    #[test]
+    fn price_limit_widens_from_seven_to_ten_percent_in_2015() {
+        assert_eq!(price_limit_on(date(2015, 5, 29)), dec!(0.07));
+        assert_eq!(price_limit_on(date(2015, 6, 1)), dec!(0.10));
+    }
+
+    #[test]
+    fn implied_share_ratio_is_the_inverse_of_the_price_change() {
+        // 0050 的 1:4 分割：188.65 → 47.16。
+        assert_eq!(
+            implied_share_ratio(dec!(188.65), dec!(47.16)),
+            Some(dec!(4.0002))
+        );
+        // 減資四成：停牌前 30 元、恢復參考價 50 元，1 股剩 0.6 股。
+        assert_eq!(implied_share_ratio(dec!(30), dec!(50)), Some(dec!(0.6)));
+        assert_eq!(implied_share_ratio(dec!(0), dec!(50)), None);
+        assert_eq!(implied_share_ratio(dec!(30), dec!(-1)), None);
+    }
+
+    #[test]
+    fn price_gap_within_the_limit_is_not_proposed() {
+        let gap = |date: NaiveDate, close| PriceGap {
+            stock_symbol: "2330".to_string(),
+            date,
+            previous_close: dec!(100),
+            close,
+        };
+
+        // 漲停 10% 是正常交易，不是公司行動。
+        assert!(!gap(date(2026, 4, 1), dec!(110)).exceeds_price_limit());
+        assert!(gap(date(2026, 4, 1), dec!(110)).to_proposal().is_none());
+        // 2015 年以前漲跌幅只有 7%，同樣的 9% 跳空在當時就已超出。
+        assert!(!gap(date(2026, 4, 1), dec!(91)).exceeds_price_limit());
+        assert!(gap(date(2014, 4, 1), dec!(91)).exceeds_price_limit());
+    }
+
+    #[test]
+    fn price_gap_beyond_the_limit_becomes_a_proposal() {
+        let gap = PriceGap {
+            stock_symbol: "0050".to_string(),
+            date: date(2025, 6, 18),
+            previous_close: dec!(188.65),
+            close: dec!(47.16),
+        };
+
+        let proposal = gap.to_proposal().expect("超出漲跌幅應產生建議");
+        assert_eq!(proposal.source, "price_gap");
+        assert_eq!(proposal.reference, "2025-06-18");
+        assert_eq!(proposal.action_type, "split");
+        assert_eq!(proposal.effective_date, Some(date(2025, 6, 18)));
+        assert_eq!(proposal.share_ratio, Some(dec!(4.0002)));
+
+        let reduction = PriceGap {
+            close: dec!(300),
+            ..gap
+        };
+        assert_eq!(
+            reduction.to_proposal().expect("減資").action_type,
+            "capital_reduction"
+        );
+    }
+
     #[test]
     fn coverage_ratio_uses_universe_and_positive_ratio_uses_counted() {
         let coverage = CagrCoverage {
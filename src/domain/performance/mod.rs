@@ -10,9 +10,12 @@ pub mod simulator;
 pub mod source;
 
 pub use entity::{
-    BASE_DATE_GRACE_DAYS, CagrCoverage, CagrMetric, CagrPeriod, CorporateAction, DividendEvent,
-    PAR_VALUE, PRINCIPAL, SimulationOutcome, StockCagr,
+    BASE_DATE_GRACE_DAYS, CagrCoverage, CagrMetric, CagrPeriod, CorporateAction,
+    CorporateActionProposal, DividendEvent, PAR_VALUE, PRINCIPAL, PendingCorporateActionProposal,
+    PriceGap, SimulationOutcome, StockCagr, implied_share_ratio, price_limit_on,
 };
 pub use query::{CagrRankingItem, CagrRankingPage, CagrRankingQuery, CagrSortKey};
-pub use repository::{CagrRepository, CorporateActionRepository};
+pub use repository::{
+    CagrRepository, CorporateActionProposalRepository, CorporateActionRepository,
+};
 pub use source::CagrSourceRepository;
@@ -1,9 +1,11 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use chrono::NaiveDate;
+use rust_decimal::Decimal;
 
 use crate::domain::performance::entity::{
-    CagrCoverage, CagrMetric, CagrPeriod, CorporateAction, StockCagr,
+    CagrCoverage, CagrMetric, CagrPeriod, CorporateAction, CorporateActionProposal,
+    PendingCorporateActionProposal, PriceGap, StockCagr,
 };
 use crate::domain::performance::query::{CagrRankingPage, CagrRankingQuery};
 
@@ -82,8 +84,9 @@ pub trait CagrRepository: Send + Sync {
 
 /// 公司行動（分割／減資）之倉儲介面。
 ///
-/// 內容由人工維護：ETF 的受益權單位分割不在除權息表內，也沒有可靠的公開端點
-/// 可自動匯入，因此提供寫入介面讓維運人員從 backfill admin 頁面登錄。
+/// 寫入一律經過人工：偵測器只產生 [`CorporateActionProposalRepository`] 中的
+/// 建議，由維運人員在 backfill admin 頁面確認後才落到這裡；ETF 的受益權單位
+/// 分割等偵測不到的事件，仍可直接登錄。
 #[async_trait]
 pub trait CorporateActionRepository: Send + Sync {
     /// 新增或更新一筆公司行動，回傳寫入的筆數。
@@ -94,3 +97,42 @@ pub trait CorporateActionRepository: Send + Sync {
     /// 列出指定股票的所有公司行動，依生效日由早至晚排序。
     async fn fetch_by_symbol(&self, stock_symbol: &str) -> Result<Vec<CorporateAction>>;
 }
+
+/// 公司行動建議之倉儲介面。
+#[async_trait]
+pub trait CorporateActionProposalRepository: Send + Sync {
+    /// 新增一筆待確認的建議；同來源、同代號、同識別已存在時不重複新增。
+    ///
+    /// 回傳是否為新建議。
+    async fn propose(&self, proposal: &CorporateActionProposal) -> Result<bool>;
+
+    /// 列出所有待確認的建議，依建立時間由舊至新排序。
+    async fn fetch_pending(&self) -> Result<Vec<PendingCorporateActionProposal>>;
+
+    /// 確認一筆待確認的建議，並以確認時的內容寫入公司行動。
+    ///
+    /// 生效日與比例以呼叫端傳入者為準（來源推估值可能需要修正）。狀態更新與
+    /// 寫入公司行動必須在同一交易內完成。建議不存在或已非待確認時回傳 `None`。
+    async fn confirm(
+        &self,
+        serial: i64,
+        effective_date: NaiveDate,
+        share_ratio: Decimal,
+        note: &str,
+    ) -> Result<Option<CorporateAction>>;
+
+    /// 駁回一筆待確認的建議；建議不存在或已非待確認時回傳 `false`。
+    async fn reject(&self, serial: i64) -> Result<bool>;
+
+    /// 取得區間內漲跌幅超過 `min_change`、且無法以除權息解釋的跳空。
+    ///
+    /// 已登錄為公司行動、或同代號同生效日已有建議（不論狀態）的跳空一併排除，
+    /// 避免公告來源與報價來源對同一事件重複建議。是否超出當日漲跌幅由
+    /// [`PriceGap::exceeds_price_limit`] 判斷，這裡只做粗篩。
+    async fn fetch_unexplained_price_gaps(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        min_change: Decimal,
+    ) -> Result<Vec<PriceGap>>;
+}
@@ -45,6 +45,17 @@ pub trait CagrSourceRepository: Send + Sync {
         to: NaiveDate,
     ) -> Result<Vec<(String, NaiveDate, Decimal)>>;
 
+    /// 取得指定股票在日期區間（含兩端）內的最後一筆報價（日期與收盤價）。
+    ///
+    /// 用於估價回測的出場價：持有期間內下市或停牌、期末交易日沒有報價的股票，
+    /// 以區間內最後一筆收盤價結算，而不是整筆排除造成倖存者偏差。
+    async fn fetch_last_quote_within(
+        &self,
+        symbols: &[String],
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<(String, NaiveDate, Decimal)>>;
+
     /// 取得指定日期之後所有股票的除權息事件。
     ///
     /// 實作必須處理兩件事，否則結果會系統性錯誤：
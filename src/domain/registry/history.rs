@@ -0,0 +1,373 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use strum::{Display, EnumString};
+
+use crate::domain::{events::DomainEvent, registry::entity::Stock};
+
+/// <summary>
+/// 證券主檔異動種類，字串值與 `security_master_event.kind` 相同。
+/// </summary>
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Display, EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum SecurityChangeKind {
+    /// 掛牌上市（櫃）。
+    Listed,
+    /// 終止上市（櫃）。
+    Delisted,
+    /// 轉換交易市場（例如上櫃轉上市）。
+    MarketTransfer,
+    /// 更名。
+    Renamed,
+    /// 產業重新分類。
+    IndustryReclassified,
+    /// 變更證券代號；舊代號與新代號各記一筆，以 `related_symbol` 互相對應。
+    SymbolChanged,
+}
+
+/// <summary>
+/// 證券主檔的單筆異動紀錄（只追加）。
+/// 每筆都帶有異動後的完整狀態，因此某日最新的一筆即為該日的主檔狀態。
+/// </summary>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityMasterEvent {
+    /// 證券代號。
+    pub security_code: String,
+    /// 異動種類。
+    pub kind: SecurityChangeKind,
+    /// 異動生效日。
+    pub effective_date: NaiveDate,
+    /// 異動後的名稱。
+    pub name: String,
+    /// 異動後的交易所市場代碼。
+    pub market_id: i32,
+    /// 異動後的產業分類代碼。
+    pub industry_id: i32,
+    /// 異動後是否仍掛牌。
+    pub listed: bool,
+    /// 異動前的名稱（僅更名時）。
+    pub previous_name: Option<String>,
+    /// 異動前的市場代碼（僅轉換市場時）。
+    pub previous_market_id: Option<i32>,
+    /// 異動前的產業代碼（僅產業重新分類時）。
+    pub previous_industry_id: Option<i32>,
+    /// 代號變更時對應的另一個代號。
+    pub related_symbol: Option<String>,
+}
+
+impl SecurityMasterEvent {
+    /// <summary>
+    /// 由證券主檔聚合根產生的領域事件轉換成異動紀錄。
+    /// `listing_date` 為來源提供的掛牌日；未提供時以事件發生日為準。
+    /// 一次身份變更可能同時涉及名稱、市場與產業，會各自產生一筆紀錄。
+    /// </summary>
+    pub fn from_domain_event(event: &DomainEvent, listing_date: Option<NaiveDate>) -> Vec<Self> {
+        match event {
+            DomainEvent::StockRegistered {
+                symbol,
+                name,
+                market_id,
+                industry_id,
+                occurred_at,
+            } => vec![SecurityMasterEvent {
+                security_code: symbol.clone(),
+                kind: SecurityChangeKind::Listed,
+                effective_date: listing_date.unwrap_or_else(|| occurred_at.date_naive()),
+                name: name.clone(),
+                market_id: *market_id,
+                industry_id: *industry_id,
+                listed: true,
+                previous_name: None,
+                previous_market_id: None,
+                previous_industry_id: None,
+                related_symbol: None,
+            }],
+            DomainEvent::StockIdentityChanged {
+                symbol,
+                old_name,
+                new_name,
+                old_market_id,
+                new_market_id,
+                old_industry_id,
+                new_industry_id,
+                occurred_at,
+            } => {
+                let base = SecurityMasterEvent {
+                    security_code: symbol.clone(),
+                    kind: SecurityChangeKind::Renamed,
+                    effective_date: occurred_at.date_naive(),
+                    name: new_name.clone(),
+                    market_id: *new_market_id,
+                    industry_id: *new_industry_id,
+                    listed: true,
+                    previous_name: None,
+                    previous_market_id: None,
+                    previous_industry_id: None,
+                    related_symbol: None,
+                };
+                let mut changes = Vec::new();
+                if old_market_id != new_market_id {
+                    changes.push(SecurityMasterEvent {
+                        kind: SecurityChangeKind::MarketTransfer,
+                        previous_market_id: Some(*old_market_id),
+                        ..base.clone()
+                    });
+                }
+                if old_name != new_name {
+                    changes.push(SecurityMasterEvent {
+                        kind: SecurityChangeKind::Renamed,
+                        previous_name: Some(old_name.clone()),
+                        ..base.clone()
+                    });
+                }
+                if old_industry_id != new_industry_id {
+                    changes.push(SecurityMasterEvent {
+                        kind: SecurityChangeKind::IndustryReclassified,
+                        previous_industry_id: Some(*old_industry_id),
+                        ..base
+                    });
+                }
+                changes
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// <summary>
+    /// 建立終止上市（櫃）紀錄，名稱、市場與產業沿用下市前的主檔。
+    /// </summary>
+    pub fn delisted(stock: &Stock, effective_date: NaiveDate) -> Self {
+        SecurityMasterEvent {
+            security_code: stock.symbol().0.clone(),
+            kind: SecurityChangeKind::Delisted,
+            effective_date,
+            name: stock.name().to_string(),
+            market_id: stock.market_id(),
+            industry_id: stock.industry_id(),
+            listed: false,
+            previous_name: None,
+            previous_market_id: None,
+            previous_industry_id: None,
+            related_symbol: None,
+        }
+    }
+
+    /// <summary>
+    /// 建立代號變更的一對紀錄：舊代號停止掛牌、新代號沿用其名稱、市場與產業開始掛牌。
+    /// 現有資料來源沒有代號變更公告，需由人工確認後經 `app::backfill::symbol_change` 寫入。
+    /// </summary>
+    pub fn symbol_changed(stock: &Stock, new_symbol: &str, effective_date: NaiveDate) -> [Self; 2] {
+        let old = SecurityMasterEvent {
+            security_code: stock.symbol().0.clone(),
+            kind: SecurityChangeKind::SymbolChanged,
+            effective_date,
+            name: stock.name().to_string(),
+            market_id: stock.market_id(),
+            industry_id: stock.industry_id(),
+            listed: false,
+            previous_name: None,
+            previous_market_id: None,
+            previous_industry_id: None,
+            related_symbol: Some(new_symbol.to_string()),
+        };
+        let new = SecurityMasterEvent {
+            security_code: new_symbol.to_string(),
+            listed: true,
+            related_symbol: Some(old.security_code.clone()),
+            ..old.clone()
+        };
+        [old, new]
+    }
+}
+
+/// <summary>
+/// 某日的證券主檔狀態（投資範圍中的一檔證券）。
+/// </summary>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecuritySnapshot {
+    /// 證券代號。
+    pub security_code: String,
+    /// 當日名稱。
+    pub name: String,
+    /// 當日交易所市場代碼。
+    pub market_id: i32,
+    /// 當日產業分類代碼。
+    pub industry_id: i32,
+    /// 以此代號開始掛牌的日期。
+    pub listed_date: NaiveDate,
+}
+
+/// <summary>
+/// 依異動紀錄重建 `date` 當日仍掛牌的證券（投資範圍），依代號排序。
+/// 與倉儲的 `fetch_universe` 規則相同：每檔取生效日在當日（含）之前最新的一筆，
+/// 同日多筆以輸入順序較後者為準。
+/// </summary>
+pub fn universe_on(events: &[SecurityMasterEvent], date: NaiveDate) -> Vec<SecuritySnapshot> {
+    let mut latest: BTreeMap<&str, (&SecurityMasterEvent, Option<NaiveDate>)> = BTreeMap::new();
+    let mut ordered: Vec<&SecurityMasterEvent> = events
+        .iter()
+        .filter(|event| event.effective_date <= date)
+        .collect();
+    ordered.sort_by_key(|event| event.effective_date);
+
+    for event in ordered {
+        let entry = latest
+            .entry(event.security_code.as_str())
+            .or_insert((event, None));
+        entry.0 = event;
+        if event.listed
+            && matches!(
+                event.kind,
+                SecurityChangeKind::Listed | SecurityChangeKind::SymbolChanged
+            )
+        {
+            entry.1 = Some(event.effective_date);
+        }
+    }
+
+    latest
+        .into_values()
+        .filter(|(event, _)| event.listed)
+        .map(|(event, listed_date)| SecuritySnapshot {
+            security_code: event.security_code.clone(),
+            name: event.name.clone(),
+            market_id: event.market_id,
+            industry_id: event.industry_id,
+            listed_date: listed_date.unwrap_or(event.effective_date),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Local, TimeZone};
+    use rust_decimal::Decimal;
+
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn stock(symbol: &str, name: &str, market_id: i32) -> Stock {
+        Stock::reconstitute(
+            symbol.to_string(),
+            name.to_string(),
+            false,
+            Decimal::ZERO,
+            Decimal::ZERO,
+            Decimal::ZERO,
+            Local::now(),
+            market_id,
+            24,
+            0,
+            0,
+            Decimal::ZERO,
+        )
+    }
+
+    /// 上櫃轉上市同時更名時，拆成兩筆紀錄並保留舊值。
+    #[test]
+    fn identity_change_splits_into_kinds() {
+        let occurred_at = Local.with_ymd_and_hms(2024, 3, 4, 9, 0, 0).unwrap();
+        let event = DomainEvent::StockIdentityChanged {
+            symbol: "6666".to_string(),
+            old_name: "舊名".to_string(),
+            new_name: "新名".to_string(),
+            old_market_id: 4,
+            new_market_id: 2,
+            old_industry_id: 24,
+            new_industry_id: 24,
+            occurred_at,
+        };
+
+        let changes = SecurityMasterEvent::from_domain_event(&event, None);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].kind, SecurityChangeKind::MarketTransfer);
+        assert_eq!(changes[0].previous_market_id, Some(4));
+        assert_eq!(changes[0].market_id, 2);
+        assert_eq!(changes[1].kind, SecurityChangeKind::Renamed);
+        assert_eq!(changes[1].previous_name.as_deref(), Some("舊名"));
+        assert!(
+            changes
+                .iter()
+                .all(|change| change.effective_date == date(2024, 3, 4))
+        );
+    }
+
+    #[test]
+    fn registration_uses_listing_date() {
+        let event = DomainEvent::StockRegistered {
+            symbol: "2330".to_string(),
+            name: "台積電".to_string(),
+            market_id: 2,
+            industry_id: 24,
+            occurred_at: Local::now(),
+        };
+
+        let changes = SecurityMasterEvent::from_domain_event(&event, Some(date(1994, 9, 5)));
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, SecurityChangeKind::Listed);
+        assert_eq!(changes[0].effective_date, date(1994, 9, 5));
+        assert!(
+            SecurityMasterEvent::from_domain_event(
+                &DomainEvent::MoneyFlowRecalculated {
+                    date: date(2024, 1, 2),
+                    occurred_at: Local::now(),
+                },
+                None
+            )
+            .is_empty()
+        );
+    }
+
+    /// 回測當時已下市的公司仍在投資範圍內，之後才掛牌的公司則不在。
+    #[test]
+    fn universe_on_respects_listing_and_delisting_dates() {
+        let listed = |symbol: &str, on| SecurityMasterEvent {
+            security_code: symbol.to_string(),
+            kind: SecurityChangeKind::Listed,
+            effective_date: on,
+            name: symbol.to_string(),
+            market_id: 4,
+            industry_id: 24,
+            listed: true,
+            previous_name: None,
+            previous_market_id: None,
+            previous_industry_id: None,
+            related_symbol: None,
+        };
+        let mut transfer = listed("1111", date(2022, 6, 1));
+        transfer.kind = SecurityChangeKind::MarketTransfer;
+        transfer.market_id = 2;
+        transfer.previous_market_id = Some(4);
+        let [old, new] = SecurityMasterEvent::symbol_changed(
+            &stock("3333", "換號", 2),
+            "3334",
+            date(2023, 1, 2),
+        );
+        let events = vec![
+            listed("1111", date(2010, 1, 4)),
+            transfer,
+            listed("2222", date(2010, 1, 4)),
+            SecurityMasterEvent::delisted(&stock("2222", "2222", 4), date(2021, 5, 3)),
+            listed("3333", date(2015, 1, 5)),
+            old,
+            new,
+            listed("4444", date(2024, 1, 2)),
+        ];
+
+        let universe = universe_on(&events, date(2020, 12, 31));
+        let codes: Vec<&str> = universe.iter().map(|s| s.security_code.as_str()).collect();
+        assert_eq!(codes, vec!["1111", "2222", "3333"]);
+        assert_eq!(universe[0].market_id, 4);
+
+        let universe = universe_on(&events, date(2023, 6, 30));
+        let codes: Vec<&str> = universe.iter().map(|s| s.security_code.as_str()).collect();
+        assert_eq!(codes, vec!["1111", "3334"]);
+        assert_eq!(universe[0].market_id, 2);
+        assert_eq!(universe[0].listed_date, date(2010, 1, 4));
+        assert_eq!(universe[1].listed_date, date(2023, 1, 2));
+        assert_eq!(universe[1].name, "換號");
+    }
+}
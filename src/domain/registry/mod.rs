@@ -1,2 +1,6 @@
 pub mod entity;
+/// 時點證券主檔：上下市、轉市場、更名、改代號與產業重新分類的異動紀錄。
+pub mod history;
 pub mod repository;
+/// 證券主檔對外同步：同步佇列、重試間隔與全量比對。
+pub mod sync;
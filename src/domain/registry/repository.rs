@@ -1,6 +1,13 @@
-use crate::domain::registry::entity::Stock;
+use std::collections::HashMap;
+
+use crate::domain::registry::{
+    entity::Stock,
+    history::{SecurityMasterEvent, SecuritySnapshot},
+    sync::{StockSyncRecord, StockSyncTask, SyncReason},
+};
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::NaiveDate;
 use rust_decimal::Decimal;
 
 /// <summary>
@@ -53,3 +60,67 @@ pub trait StockRepository: Send + Sync {
     /// </summary>
     async fn update_weight(&self, stock_symbol: &str, weight: Decimal) -> Result<()>;
 }
+
+/// <summary>
+/// 時點證券主檔倉儲特徵介面 (Repository Trait)。
+/// 以只追加的異動紀錄保存上下市、轉市場、更名、改代號與產業重新分類，
+/// 並提供任一歷史日期的投資範圍，避免研究時只看到存活至今的公司（存活者偏差）。
+/// </summary>
+#[async_trait]
+pub trait SecurityMasterRepository: Send + Sync {
+    /// <summary>
+    /// 寫入異動紀錄；同一證券、同一種類、同一生效日已存在時略過。回傳實際寫入筆數。
+    /// </summary>
+    async fn record(&self, events: &[SecurityMasterEvent]) -> Result<u64>;
+
+    /// <summary>
+    /// 取得單一證券的全部異動紀錄，依生效日排序。
+    /// </summary>
+    async fn fetch_history(&self, security_code: &str) -> Result<Vec<SecurityMasterEvent>>;
+
+    /// <summary>
+    /// 取得指定日期仍掛牌的證券（投資範圍），依代號排序；`market_ids` 為 `None` 時不限市場。
+    /// </summary>
+    async fn fetch_universe(
+        &self,
+        date: NaiveDate,
+        market_ids: Option<Vec<i32>>,
+    ) -> Result<Vec<SecuritySnapshot>>;
+
+    /// <summary>
+    /// 取得每檔證券最後一個有成交的交易日，供來源未提供下市日期時推估。
+    /// </summary>
+    async fn fetch_last_trading_dates(&self) -> Result<HashMap<String, NaiveDate>>;
+}
+
+/// <summary>
+/// 證券主檔對外同步佇列倉儲特徵介面 (Repository Trait)。
+/// 佇列持久化保存待同步的證券，外部服務暫時無法連線時不會遺失變更，並依失敗次數延後重試。
+/// </summary>
+#[async_trait]
+pub trait StockSyncQueueRepository: Send + Sync {
+    /// <summary>
+    /// 排入待同步的證券；已在佇列中者更新原因並立即可送出。回傳排入筆數。
+    /// </summary>
+    async fn enqueue(&self, stock_symbols: &[String], reason: SyncReason) -> Result<u64>;
+
+    /// <summary>
+    /// 取得已到重試時間的待同步證券，附上證券主檔目前的內容，依排入先後排序。
+    /// </summary>
+    async fn fetch_due(&self, limit: i64) -> Result<Vec<StockSyncTask>>;
+
+    /// <summary>
+    /// 移除已同步完成的證券；送出期間又被重新排入者保留。
+    /// </summary>
+    async fn complete(&self, tasks: &[StockSyncTask]) -> Result<u64>;
+
+    /// <summary>
+    /// 記錄同步失敗並依失敗次數延後下次重試；送出期間又被重新排入者不延後。
+    /// </summary>
+    async fn fail(&self, tasks: &[StockSyncTask], error: &str) -> Result<()>;
+
+    /// <summary>
+    /// 取得全部證券主檔（含已下市）的同步內容，供全量比對。
+    /// </summary>
+    async fn fetch_sync_records(&self) -> Result<Vec<StockSyncRecord>>;
+}
@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use rust_decimal::Decimal;
+use strum::{Display, EnumString};
+
+use crate::domain::registry::entity::Stock;
+
+/// 同步失敗後第一次重試的等待時間。
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(30);
+/// 重試等待時間上限。
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(60 * 60);
+/// 比對每股淨值時保留的小數位數；遠端以浮點數保存，超過此精度的差異視為相同。
+const NAV_COMPARE_SCALE: u32 = 2;
+
+/// <summary>
+/// 需要同步證券主檔至外部服務的原因，字串值與 `stock_sync_queue.reason` 相同。
+/// </summary>
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Display, EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum SyncReason {
+    /// 新增證券。
+    Registered,
+    /// 名稱、市場或產業變更。
+    IdentityChanged,
+    /// 每股淨值變更。
+    NetAssetValueUpdated,
+    /// 終止上市（櫃）。
+    Delisted,
+    /// 定期全量比對發現差異。
+    Reconcile,
+}
+
+/// <summary>
+/// 同步至外部服務的證券主檔內容，本地與遠端以同一格式比對。
+/// </summary>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StockSyncRecord {
+    /// 證券代號。
+    pub stock_symbol: String,
+    /// 證券名稱。
+    pub name: String,
+    /// 交易所市場代碼。
+    pub market_id: i32,
+    /// 產業分類代碼。
+    pub industry_id: i32,
+    /// 每股淨值。
+    pub net_asset_value_per_share: Decimal,
+    /// 是否已下市（櫃）。
+    pub suspend_listing: bool,
+}
+
+impl StockSyncRecord {
+    /// <summary>
+    /// 兩邊內容是否一致；每股淨值只比到小數第二位。
+    /// </summary>
+    pub fn same_as(&self, other: &StockSyncRecord) -> bool {
+        self.stock_symbol == other.stock_symbol
+            && self.name == other.name
+            && self.market_id == other.market_id
+            && self.industry_id == other.industry_id
+            && self.suspend_listing == other.suspend_listing
+            && self.net_asset_value_per_share.round_dp(NAV_COMPARE_SCALE)
+                == other.net_asset_value_per_share.round_dp(NAV_COMPARE_SCALE)
+    }
+}
+
+impl From<&Stock> for StockSyncRecord {
+    fn from(stock: &Stock) -> Self {
+        StockSyncRecord {
+            stock_symbol: stock.symbol().0.clone(),
+            name: stock.name().to_string(),
+            market_id: stock.market_id(),
+            industry_id: stock.industry_id(),
+            net_asset_value_per_share: stock.net_asset_value_per_share(),
+            suspend_listing: stock.suspend_listing(),
+        }
+    }
+}
+
+/// <summary>
+/// 同步佇列中待送出的證券。
+/// 同一證券只保留一筆；送出時讀取證券主檔的最新內容，而非排入當下的內容。
+/// </summary>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StockSyncTask {
+    /// 佇列序號。
+    pub serial: i64,
+    /// 證券代號。
+    pub stock_symbol: String,
+    /// 最近一次排入的原因。
+    pub reason: SyncReason,
+    /// 已失敗次數。
+    pub attempts: i32,
+    /// 每次重新排入遞增；送出期間若又被排入，完成時不會誤刪新的排入。
+    pub version: i32,
+    /// 證券主檔目前的內容；主檔已不存在時為 `None`。
+    pub record: Option<StockSyncRecord>,
+}
+
+/// <summary>
+/// 外部服務拒絕寫入的證券與原因。
+/// </summary>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncRejection {
+    /// 證券代號。
+    pub stock_symbol: String,
+    /// 拒絕原因。
+    pub reason: String,
+}
+
+/// <summary>
+/// 第 `attempts` 次失敗後到下次重試的等待時間：從 30 秒起每次加倍，最長 1 小時。
+/// </summary>
+pub fn retry_delay(attempts: i32) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    RETRY_BASE_DELAY
+        .saturating_mul(2u32.saturating_pow(exponent))
+        .min(RETRY_MAX_DELAY)
+}
+
+/// <summary>
+/// 本地證券主檔與外部服務的比對結果，代號皆已排序。
+/// </summary>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RegistryDiff {
+    /// 遠端沒有的證券。
+    pub missing_remote: Vec<String>,
+    /// 兩邊內容不一致的證券。
+    pub changed: Vec<String>,
+    /// 只存在於遠端的證券（本地無從判斷，只回報不處理）。
+    pub unknown_remote: Vec<String>,
+}
+
+impl RegistryDiff {
+    /// <summary>
+    /// 需要重新同步的證券代號。
+    /// </summary>
+    pub fn to_sync(&self) -> Vec<String> {
+        self.missing_remote
+            .iter()
+            .chain(&self.changed)
+            .cloned()
+            .collect()
+    }
+}
+
+/// <summary>
+/// 比對本地與遠端的證券主檔。
+/// </summary>
+pub fn diff_registry(local: &[StockSyncRecord], remote: &[StockSyncRecord]) -> RegistryDiff {
+    let remote_by_symbol: HashMap<&str, &StockSyncRecord> = remote
+        .iter()
+        .map(|record| (record.stock_symbol.as_str(), record))
+        .collect();
+    let local_symbols: HashMap<&str, ()> = local
+        .iter()
+        .map(|record| (record.stock_symbol.as_str(), ()))
+        .collect();
+
+    let mut diff = RegistryDiff::default();
+    for record in local {
+        match remote_by_symbol.get(record.stock_symbol.as_str()) {
+            None => diff.missing_remote.push(record.stock_symbol.clone()),
+            Some(remote) if !record.same_as(remote) => {
+                diff.changed.push(record.stock_symbol.clone())
+            }
+            Some(_) => {}
+        }
+    }
+    diff.unknown_remote = remote
+        .iter()
+        .filter(|record| !local_symbols.contains_key(record.stock_symbol.as_str()))
+        .map(|record| record.stock_symbol.clone())
+        .collect();
+
+    diff.missing_remote.sort();
+    diff.changed.sort();
+    diff.unknown_remote.sort();
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn record(symbol: &str, name: &str, nav: Decimal) -> StockSyncRecord {
+        StockSyncRecord {
+            stock_symbol: symbol.to_string(),
+            name: name.to_string(),
+            market_id: 2,
+            industry_id: 24,
+            net_asset_value_per_share: nav,
+            suspend_listing: false,
+        }
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_an_hour() {
+        assert_eq!(retry_delay(1), Duration::from_secs(30));
+        assert_eq!(retry_delay(2), Duration::from_secs(60));
+        assert_eq!(retry_delay(4), Duration::from_secs(240));
+        assert_eq!(retry_delay(8), Duration::from_secs(3600));
+        assert_eq!(retry_delay(i32::MAX), Duration::from_secs(3600));
+    }
+
+    /// 浮點數造成的淨值尾差不算差異；遠端多出的代號只回報。
+    #[test]
+    fn diff_registry_classifies_symbols() {
+        let local = vec![
+            record("2330", "台積電", dec!(120.55)),
+            record("2317", "鴻海", dec!(80)),
+            record("0050", "元大台灣50", dec!(150)),
+        ];
+        let remote = vec![
+            record("2330", "台積電", dec!(120.5500000001)),
+            record("2317", "鴻海精密", dec!(80)),
+            record("9999", "已刪除", dec!(0)),
+        ];
+
+        let diff = diff_registry(&local, &remote);
+
+        assert_eq!(
+            diff,
+            RegistryDiff {
+                missing_remote: vec!["0050".to_string()],
+                changed: vec!["2317".to_string()],
+                unknown_remote: vec!["9999".to_string()],
+            }
+        );
+        assert_eq!(diff.to_sync(), vec!["0050", "2317"]);
+    }
+}
@@ -0,0 +1,210 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+/// 一般投資人每筆申購固定為一張（1,000 股）。
+pub const SUBSCRIPTION_LOT_SHARES: Decimal = dec!(1000);
+/// 申購處理費，不論是否中籤都會扣除。
+pub const SUBSCRIPTION_FEE: Decimal = dec!(20);
+/// 中籤通知郵寄工本費，只有中籤才會扣除。
+pub const ALLOTMENT_FEE: Decimal = dec!(50);
+/// 賣出時的證券交易稅率。
+const SELL_TAX_RATE: Decimal = dec!(0.003);
+
+/// 一檔公開申購 (Entity)。
+///
+/// 以 `(代號, 申購開始日)` 識別；同一代號日後再次辦理現金增資公開申購時
+/// 會是另一筆。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicSubscription {
+    /// 股票代號。
+    pub stock_symbol: String,
+    /// 股票名稱。
+    pub stock_name: String,
+    /// 發行市場（上市、上櫃、初上市等）。
+    pub market: String,
+    /// 申購開始日。
+    pub offering_start_date: NaiveDate,
+    /// 申購截止日。
+    pub offering_end_date: NaiveDate,
+    /// 抽籤日；尚未公布時為 `None`。
+    pub drawing_date: Option<NaiveDate>,
+    /// 承銷價；尚未公布時為 `None`。
+    pub offering_price: Option<Decimal>,
+    /// 撥券日；尚未公布時為 `None`。
+    pub issue_date: Option<NaiveDate>,
+}
+
+/// 當日應送出的提醒種類。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SubscriptionReminder {
+    /// 申購期間內（每檔只提醒一次，由呼叫端去重）。
+    Open,
+    /// 今日為申購截止日。
+    Deadline,
+    /// 今日抽籤。
+    Drawing,
+}
+
+impl PublicSubscription {
+    /// 指定日期是否在申購期間內（含起訖日）。
+    pub fn is_open_on(&self, date: NaiveDate) -> bool {
+        self.offering_start_date <= date && date <= self.offering_end_date
+    }
+
+    /// 指定日期應送出的提醒；截止日優先於一般的申購期間提醒。
+    pub fn reminder_on(&self, date: NaiveDate) -> Option<SubscriptionReminder> {
+        if date == self.offering_end_date {
+            Some(SubscriptionReminder::Deadline)
+        } else if self.is_open_on(date) {
+            Some(SubscriptionReminder::Open)
+        } else if self.drawing_date == Some(date) {
+            Some(SubscriptionReminder::Drawing)
+        } else {
+            None
+        }
+    }
+
+    /// 市價與承銷價的價差；承銷價未公布或價格非正數時為 `None`。
+    pub fn spread(&self, market_price: Decimal) -> Option<Decimal> {
+        let offering_price = self.offering_price.filter(|price| *price > Decimal::ZERO)?;
+        (market_price > Decimal::ZERO).then(|| market_price - offering_price)
+    }
+
+    /// 價差佔市價的百分比，四捨五入到小數兩位。
+    pub fn spread_percentage(&self, market_price: Decimal) -> Option<Decimal> {
+        self.spread(market_price)
+            .map(|spread| (spread / market_price * dec!(100)).round_dp(2))
+    }
+
+    /// 以現價估算中籤一張的預期獲利：價差乘上一張股數，扣除申購處理費與
+    /// 中籤工本費；未計賣出的手續費與證交稅。
+    pub fn expected_profit_per_lot(&self, market_price: Decimal) -> Option<Decimal> {
+        self.spread(market_price)
+            .map(|spread| spread * SUBSCRIPTION_LOT_SHARES - SUBSCRIPTION_FEE - ALLOTMENT_FEE)
+    }
+
+    /// 計算一筆申購的已實現損益。
+    ///
+    /// 未中籤只損失申購處理費；中籤且已賣出時為賣出金額扣除證交稅後，減去
+    /// 承銷金額、申購處理費與中籤工本費（券商手續費依折扣不同，不計入）。
+    /// 中籤但尚未賣出或承銷價未知時為 `None`。
+    pub fn realized_profit(&self, won: bool, sell_price: Option<Decimal>) -> Option<Decimal> {
+        if !won {
+            return Some(-SUBSCRIPTION_FEE);
+        }
+        let offering_price = self.offering_price?;
+        let proceeds = sell_price? * SUBSCRIPTION_LOT_SHARES;
+        let tax = (proceeds * SELL_TAX_RATE).floor();
+        Some(
+            proceeds
+                - tax
+                - offering_price * SUBSCRIPTION_LOT_SHARES
+                - SUBSCRIPTION_FEE
+                - ALLOTMENT_FEE,
+        )
+    }
+}
+
+/// 依中籤一張的預期獲利由高到低排序；無法估算者排在最後並維持原順序。
+pub fn rank_by_expected_profit<T>(items: &mut [(T, Option<Decimal>)]) {
+    items.sort_by(|(_, a), (_, b)| match (a, b) {
+        (Some(a), Some(b)) => b.cmp(a),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+}
+
+/// 成員參與的一筆公開申購 (Entity)。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubscriptionEntry {
+    /// 流水號。
+    pub serial: i64,
+    /// 股票代號。
+    pub stock_symbol: String,
+    /// 申購開始日，與代號共同對應 [`PublicSubscription`]。
+    pub offering_start_date: NaiveDate,
+    /// 成員編號。
+    pub member_id: i64,
+    /// 是否中籤；尚未抽籤時為 `None`。
+    pub won: Option<bool>,
+    /// 中籤後的賣出價。
+    pub sell_price: Option<Decimal>,
+    /// 已實現損益；尚未抽籤或中籤未賣出時為 `None`。
+    pub realized_profit: Option<Decimal>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn subscription(offering_price: Option<Decimal>) -> PublicSubscription {
+        PublicSubscription {
+            stock_symbol: "6789".to_string(),
+            stock_name: "測試".to_string(),
+            market: "初上市".to_string(),
+            offering_start_date: date(2026, 7, 14),
+            offering_end_date: date(2026, 7, 16),
+            drawing_date: Some(date(2026, 7, 20)),
+            offering_price,
+            issue_date: Some(date(2026, 7, 28)),
+        }
+    }
+
+    #[test]
+    fn reminder_follows_offering_calendar() {
+        let s = subscription(Some(dec!(50)));
+        assert_eq!(s.reminder_on(date(2026, 7, 13)), None);
+        assert_eq!(
+            s.reminder_on(date(2026, 7, 14)),
+            Some(SubscriptionReminder::Open)
+        );
+        assert_eq!(
+            s.reminder_on(date(2026, 7, 16)),
+            Some(SubscriptionReminder::Deadline)
+        );
+        assert_eq!(s.reminder_on(date(2026, 7, 17)), None);
+        assert_eq!(
+            s.reminder_on(date(2026, 7, 20)),
+            Some(SubscriptionReminder::Drawing)
+        );
+    }
+
+    #[test]
+    fn expected_profit_deducts_fees() {
+        let s = subscription(Some(dec!(50)));
+        assert_eq!(s.spread(dec!(62.5)), Some(dec!(12.5)));
+        assert_eq!(s.spread_percentage(dec!(62.5)), Some(dec!(20.00)));
+        assert_eq!(s.expected_profit_per_lot(dec!(62.5)), Some(dec!(12430)));
+        // 承銷價未公布或沒有市價時無法估算。
+        assert_eq!(subscription(None).expected_profit_per_lot(dec!(62.5)), None);
+        assert_eq!(s.expected_profit_per_lot(Decimal::ZERO), None);
+    }
+
+    #[test]
+    fn realized_profit_covers_win_and_loss() {
+        let s = subscription(Some(dec!(50)));
+        assert_eq!(s.realized_profit(false, None), Some(dec!(-20)));
+        // 60 * 1000 = 60,000；證交稅 180；60,000 - 180 - 50,000 - 20 - 50。
+        assert_eq!(s.realized_profit(true, Some(dec!(60))), Some(dec!(9750)));
+        assert_eq!(s.realized_profit(true, None), None);
+    }
+
+    #[test]
+    fn ranking_puts_unknown_profit_last() {
+        let mut items = vec![
+            ("a", None),
+            ("b", Some(dec!(1000))),
+            ("c", Some(dec!(-500))),
+            ("d", Some(dec!(8000))),
+        ];
+        rank_by_expected_profit(&mut items);
+        let order: Vec<&str> = items.iter().map(|(name, _)| *name).collect();
+        assert_eq!(order, vec!["d", "b", "c", "a"]);
+    }
+}
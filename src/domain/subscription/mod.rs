@@ -0,0 +1,15 @@
+//! 公開申購（抽籤）領域。
+//!
+//! 收錄證交所公開申購公告，依現價估算每張中籤的預期價差並排序，於申購期間、
+//! 截止日與抽籤日提醒；另記錄成員實際參與的申購、是否中籤與已實現損益。
+
+/// 公開申購領域實體模組。
+pub mod entity;
+/// 公開申購倉儲介面模組。
+pub mod repository;
+
+pub use entity::{
+    ALLOTMENT_FEE, PublicSubscription, SUBSCRIPTION_FEE, SUBSCRIPTION_LOT_SHARES,
+    SubscriptionEntry, SubscriptionReminder, rank_by_expected_profit,
+};
+pub use repository::SubscriptionRepository;
@@ -0,0 +1,41 @@
+use super::entity::{PublicSubscription, SubscriptionEntry};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+/// 公開申購與參與紀錄的倉儲合約 (Repository Trait)。
+#[async_trait]
+pub trait SubscriptionRepository: Send + Sync {
+    /// 寫入公開申購公告；同一 `(代號, 申購開始日)` 重複寫入時更新日期與承銷價。
+    async fn upsert(&self, subscriptions: &[PublicSubscription]) -> Result<()>;
+
+    /// 取得申購截止日或抽籤日不早於指定日期的公開申購，依申購開始日由新至舊。
+    async fn fetch_since(&self, date: NaiveDate) -> Result<Vec<PublicSubscription>>;
+
+    /// 取得指定公開申購。
+    async fn fetch_one(
+        &self,
+        stock_symbol: &str,
+        offering_start_date: NaiveDate,
+    ) -> Result<Option<PublicSubscription>>;
+
+    /// 登記成員參與申購；重複登記回傳原紀錄，公開申購不存在時回傳 `None`。
+    async fn enter(
+        &self,
+        stock_symbol: &str,
+        offering_start_date: NaiveDate,
+        member_id: i64,
+    ) -> Result<Option<SubscriptionEntry>>;
+
+    /// 記錄抽籤結果與賣出價，並寫入已實現損益；紀錄不存在時回傳 `None`。
+    async fn record_result(
+        &self,
+        serial: i64,
+        won: bool,
+        sell_price: Option<Decimal>,
+    ) -> Result<Option<SubscriptionEntry>>;
+
+    /// 取得申購截止日不早於指定日期的參與紀錄，依申購開始日由新至舊。
+    async fn fetch_entries_since(&self, date: NaiveDate) -> Result<Vec<SubscriptionEntry>>;
+}
@@ -0,0 +1,161 @@
+use std::collections::HashSet;
+
+use chrono::NaiveDate;
+
+/// 交易監視的種類。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SurveillanceKind {
+    /// 注意股：交易資訊異常，當日公告，沒有交易限制。
+    Attention,
+    /// 處置股：連續列為注意股後的處置，期間內分盤撮合並可能須預收款券。
+    Disposition,
+}
+
+impl SurveillanceKind {
+    /// 資料庫與 API 使用的代碼。
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Attention => "attention",
+            Self::Disposition => "disposition",
+        }
+    }
+
+    /// 由代碼還原；未知代碼回傳 `None`。
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "attention" => Some(Self::Attention),
+            "disposition" => Some(Self::Disposition),
+            _ => None,
+        }
+    }
+
+    /// 中文名稱。
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Attention => "注意股",
+            Self::Disposition => "處置股",
+        }
+    }
+}
+
+/// 單一股票被列入注意股或處置股的一段期間 (Entity)。
+///
+/// 注意股只公告當日，起訖日相同；連續交易日重複公告時由倉儲延長同一段
+/// 期間的迄日，而不是另起一筆。處置股的起訖日取自公告的處置期間。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SurveillancePeriod {
+    /// 股票代號。
+    pub stock_symbol: String,
+    /// 股票名稱。
+    pub name: String,
+    /// 種類。
+    pub kind: SurveillanceKind,
+    /// 公告日期。
+    pub announced_on: NaiveDate,
+    /// 起日（含）。
+    pub start_date: NaiveDate,
+    /// 迄日（含）。
+    pub end_date: NaiveDate,
+    /// 列入原因（注意交易資訊或處置條件）。
+    pub reason: String,
+    /// 處置措施（例如「第一次處置」、每五分鐘撮合）；注意股為空字串。
+    pub measure: String,
+}
+
+impl SurveillancePeriod {
+    /// 指定日期是否落在期間內。
+    pub fn is_active_on(&self, date: NaiveDate) -> bool {
+        self.start_date <= date && date <= self.end_date
+    }
+}
+
+/// 找出「前一交易日仍在清單、今日已不在」的期間，也就是離開清單的股票。
+///
+/// 以 `(代號, 種類)` 比對：處置股到期後緊接著另一段處置（第二次處置）時，
+/// 代號仍在清單上，不算離開。
+pub fn departures<'a>(
+    previous: &'a [SurveillancePeriod],
+    current: &[SurveillancePeriod],
+) -> Vec<&'a SurveillancePeriod> {
+    let still_listed: HashSet<(&str, SurveillanceKind)> = current
+        .iter()
+        .map(|period| (period.stock_symbol.as_str(), period.kind))
+        .collect();
+
+    previous
+        .iter()
+        .filter(|period| !still_listed.contains(&(period.stock_symbol.as_str(), period.kind)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn period(
+        symbol: &str,
+        kind: SurveillanceKind,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> SurveillancePeriod {
+        SurveillancePeriod {
+            stock_symbol: symbol.to_string(),
+            name: String::new(),
+            kind,
+            announced_on: start,
+            start_date: start,
+            end_date: end,
+            reason: String::new(),
+            measure: String::new(),
+        }
+    }
+
+    #[test]
+    fn kind_code_round_trips() {
+        for kind in [SurveillanceKind::Attention, SurveillanceKind::Disposition] {
+            assert_eq!(SurveillanceKind::from_code(kind.code()), Some(kind));
+        }
+        assert_eq!(SurveillanceKind::from_code("punish"), None);
+    }
+
+    #[test]
+    fn active_range_is_inclusive() {
+        let p = period(
+            "2330",
+            SurveillanceKind::Disposition,
+            date(2026, 4, 21),
+            date(2026, 5, 5),
+        );
+        assert!(p.is_active_on(date(2026, 4, 21)));
+        assert!(p.is_active_on(date(2026, 5, 5)));
+        assert!(!p.is_active_on(date(2026, 4, 20)));
+        assert!(!p.is_active_on(date(2026, 5, 6)));
+    }
+
+    #[test]
+    fn departures_compare_symbol_and_kind() {
+        let d = date(2026, 4, 21);
+        let previous = vec![
+            period("2330", SurveillanceKind::Attention, d, d),
+            period("2317", SurveillanceKind::Disposition, d, d),
+            period("2454", SurveillanceKind::Attention, d, d),
+        ];
+        let next = date(2026, 4, 22);
+        let current = vec![
+            // 注意股升級為處置股：注意股那一段仍算離開。
+            period("2330", SurveillanceKind::Disposition, next, next),
+            // 處置期間接續第二次處置：不算離開。
+            period("2317", SurveillanceKind::Disposition, next, next),
+        ];
+
+        let left: Vec<&str> = departures(&previous, &current)
+            .into_iter()
+            .map(|p| p.stock_symbol.as_str())
+            .collect();
+        assert_eq!(left, vec!["2330", "2454"]);
+    }
+}
@@ -0,0 +1,12 @@
+//! 交易監視領域。
+//!
+//! 收錄證交所與櫃買中心公布的注意股與處置股，保存列入期間的歷史，供持股與
+//! 追蹤清單的進出通知，以及報價查詢時標示撮合與預收款券限制。
+
+/// 交易監視領域實體模組。
+pub mod entity;
+/// 交易監視倉儲介面模組。
+pub mod repository;
+
+pub use entity::{SurveillanceKind, SurveillancePeriod, departures};
+pub use repository::SurveillanceRepository;
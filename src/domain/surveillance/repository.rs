@@ -0,0 +1,33 @@
+use super::entity::SurveillancePeriod;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+/// 注意股與處置股期間的倉儲合約 (Repository Trait)。
+#[async_trait]
+pub trait SurveillanceRepository: Send + Sync {
+    /// 寫入公告的期間並回傳「這次才新列入」的期間。
+    ///
+    /// 同一 `(代號, 種類, 起日)` 重複寫入視為更新（處置延長或措施更正）。
+    /// 注意股若在 `previous_trading_day` 仍在清單上，延長該段期間的迄日而不是
+    /// 新增一筆，也不算新列入。
+    async fn upsert(
+        &self,
+        periods: &[SurveillancePeriod],
+        previous_trading_day: Option<NaiveDate>,
+    ) -> Result<Vec<SurveillancePeriod>>;
+
+    /// 查詢指定日期仍在期間內的資料；指定代號時只查該股。
+    async fn fetch_active_on(
+        &self,
+        date: NaiveDate,
+        stock_symbol: Option<&str>,
+    ) -> Result<Vec<SurveillancePeriod>>;
+
+    /// 取得不晚於指定日期、最近的交易日（有收盤報價的日期），由新至舊。
+    async fn fetch_recent_trading_days(
+        &self,
+        date: NaiveDate,
+        limit: i64,
+    ) -> Result<Vec<NaiveDate>>;
+}
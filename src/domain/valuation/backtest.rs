@@ -0,0 +1,464 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+/// 估價回測的持有期間。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BacktestHorizon {
+    /// 3 個月。
+    ThreeMonths,
+    /// 6 個月。
+    SixMonths,
+    /// 12 個月。
+    TwelveMonths,
+}
+
+impl BacktestHorizon {
+    /// 全部持有期間，由短至長。
+    pub const ALL: [Self; 3] = [Self::ThreeMonths, Self::SixMonths, Self::TwelveMonths];
+
+    /// 持有月數。
+    pub fn months(self) -> u32 {
+        match self {
+            Self::ThreeMonths => 3,
+            Self::SixMonths => 6,
+            Self::TwelveMonths => 12,
+        }
+    }
+
+    /// 資料庫與 API 使用的代碼。
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::ThreeMonths => "3m",
+            Self::SixMonths => "6m",
+            Self::TwelveMonths => "12m",
+        }
+    }
+
+    /// 由代碼還原；未知代碼回傳 `None`。
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|horizon| horizon.code() == code)
+    }
+
+    /// 由持有月數還原；未知月數回傳 `None`。
+    pub fn from_months(months: i32) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|horizon| horizon.months() as i32 == months)
+    }
+}
+
+/// 估價訊號：收盤價落在便宜價、昂貴價之間的哪一側。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum EstimateSignal {
+    /// 收盤價不高於便宜價。
+    Undervalued,
+    /// 收盤價介於便宜價與昂貴價之間。
+    Fair,
+    /// 收盤價不低於昂貴價。
+    Overvalued,
+}
+
+impl EstimateSignal {
+    /// 全部訊號，依便宜到昂貴排列。
+    pub const ALL: [Self; 3] = [Self::Undervalued, Self::Fair, Self::Overvalued];
+
+    /// 資料庫與 API 使用的代碼。
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Undervalued => "undervalued",
+            Self::Fair => "fair",
+            Self::Overvalued => "overvalued",
+        }
+    }
+
+    /// 由代碼還原；未知代碼回傳 `None`。
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|signal| signal.code() == code)
+    }
+
+    /// 中文名稱。
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Undervalued => "低估",
+            Self::Fair => "合理",
+            Self::Overvalued => "高估",
+        }
+    }
+
+    /// 依收盤價與估價區間分類。
+    ///
+    /// 估價不完整（便宜價非正數、昂貴價不高於便宜價）或收盤價非正數時回傳
+    /// `None`，這類樣本不應被算進任何一組。
+    pub fn classify(closing_price: Decimal, cheap: Decimal, expensive: Decimal) -> Option<Self> {
+        if closing_price <= Decimal::ZERO || cheap <= Decimal::ZERO || expensive <= cheap {
+            return None;
+        }
+        Some(if closing_price <= cheap {
+            Self::Undervalued
+        } else if closing_price >= expensive {
+            Self::Overvalued
+        } else {
+            Self::Fair
+        })
+    }
+}
+
+/// 回測彙總的統計範圍。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BacktestScope {
+    /// 全市場。
+    All,
+    /// 單一交易所市場（`stock_exchange_market_id`）。
+    Market(i32),
+    /// 單一產業（`stock_industry_id`）。
+    Industry(i32),
+}
+
+impl BacktestScope {
+    /// 資料庫與 API 使用的範圍種類代碼。
+    pub fn kind(self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::Market(_) => "market",
+            Self::Industry(_) => "industry",
+        }
+    }
+
+    /// 範圍編號；全市場為 0。
+    pub fn id(self) -> i32 {
+        match self {
+            Self::All => 0,
+            Self::Market(id) | Self::Industry(id) => id,
+        }
+    }
+
+    /// 由種類代碼與編號還原；未知種類回傳 `None`。
+    pub fn from_parts(kind: &str, id: i32) -> Option<Self> {
+        match kind {
+            "all" => Some(Self::All),
+            "market" => Some(Self::Market(id)),
+            "industry" => Some(Self::Industry(id)),
+            _ => None,
+        }
+    }
+}
+
+/// 某個估價日的單檔估價快照。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EstimateSnapshot {
+    /// 股票代號。
+    pub security_code: String,
+    /// 交易所市場編號。
+    pub market_id: i32,
+    /// 產業分類編號。
+    pub industry_id: i32,
+    /// 估價當時的收盤價。
+    pub closing_price: Decimal,
+    /// 加權便宜價。
+    pub cheap: Decimal,
+    /// 加權昂貴價。
+    pub expensive: Decimal,
+}
+
+/// 單檔股票自估價日起持有一段期間的報酬樣本。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForwardReturnSample {
+    /// 股票代號。
+    pub security_code: String,
+    /// 交易所市場編號。
+    pub market_id: i32,
+    /// 產業分類編號。
+    pub industry_id: i32,
+    /// 估價日的訊號。
+    pub signal: EstimateSignal,
+    /// 含息（不再投入）的區間總報酬率（%）。
+    pub return_pct: Decimal,
+}
+
+/// 單一估價日、持有期間、範圍與訊號的報酬彙總 (Entity)。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignalSummary {
+    /// 估價日。
+    pub date: NaiveDate,
+    /// 持有期間。
+    pub horizon: BacktestHorizon,
+    /// 統計範圍。
+    pub scope: BacktestScope,
+    /// 訊號。
+    pub signal: EstimateSignal,
+    /// 樣本數。
+    pub samples: i64,
+    /// 平均報酬率（%）；無樣本時為 `None`。
+    pub mean_return_pct: Option<Decimal>,
+    /// 報酬率中位數（%）；無樣本時為 `None`。
+    pub median_return_pct: Option<Decimal>,
+    /// 報酬為正的樣本比例（%）；無樣本時為 `None`。
+    pub win_rate_pct: Option<Decimal>,
+}
+
+/// 將單一估價日與持有期間的報酬樣本依範圍與訊號彙總。
+///
+/// 全市場的三個訊號一律輸出（即使沒有樣本），讓「這一天已經評估過」可以從
+/// 資料表本身判斷；市場與產業只輸出有樣本的組合。
+pub fn summarize(
+    date: NaiveDate,
+    horizon: BacktestHorizon,
+    samples: &[ForwardReturnSample],
+) -> Vec<SignalSummary> {
+    let mut groups: BTreeMap<(BacktestScope, EstimateSignal), Vec<Decimal>> = BTreeMap::new();
+    for signal in EstimateSignal::ALL {
+        groups.entry((BacktestScope::All, signal)).or_default();
+    }
+    for sample in samples {
+        for scope in [
+            BacktestScope::All,
+            BacktestScope::Market(sample.market_id),
+            BacktestScope::Industry(sample.industry_id),
+        ] {
+            groups
+                .entry((scope, sample.signal))
+                .or_default()
+                .push(sample.return_pct);
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|((scope, signal), mut returns)| {
+            returns.sort();
+            let count = returns.len();
+            let (mean, median, win_rate) = if count == 0 {
+                (None, None, None)
+            } else {
+                let size = Decimal::from(count);
+                let sum: Decimal = returns.iter().sum();
+                let median = if count % 2 == 1 {
+                    returns[count / 2]
+                } else {
+                    (returns[count / 2 - 1] + returns[count / 2]) / dec!(2)
+                };
+                let wins = returns
+                    .iter()
+                    .filter(|value| **value > Decimal::ZERO)
+                    .count();
+                (
+                    Some((sum / size).round_dp(4)),
+                    Some(median.round_dp(4)),
+                    Some((Decimal::from(wins) * dec!(100) / size).round_dp(2)),
+                )
+            };
+            SignalSummary {
+                date,
+                horizon,
+                scope,
+                signal,
+                samples: count as i64,
+                mean_return_pct: mean,
+                median_return_pct: median,
+                win_rate_pct: win_rate,
+            }
+        })
+        .collect()
+}
+
+/// 回測報告的查詢條件。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BacktestReportQuery {
+    /// 持有期間；`None` 表示全部。
+    pub horizon: Option<BacktestHorizon>,
+    /// 範圍種類（`all`、`market`、`industry`）；`None` 表示全部。
+    pub scope_kind: Option<&'static str>,
+    /// 範圍編號；只在指定範圍種類時有意義。
+    pub scope_id: Option<i32>,
+    /// 估價日下限（含）。
+    pub from: Option<NaiveDate>,
+    /// 估價日上限（含）。
+    pub to: Option<NaiveDate>,
+}
+
+/// 單一訊號跨多個估價日的彙總。
+///
+/// 平均報酬與勝率以樣本數加權，等同把所有估價日的樣本放在一起計算；
+/// 中位數無法合併，改以各估價日中位數的平均呈現。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignalReport {
+    /// 訊號。
+    pub signal: EstimateSignal,
+    /// 有樣本的估價日數。
+    pub dates: i64,
+    /// 樣本總數。
+    pub samples: i64,
+    /// 樣本加權平均報酬率（%）。
+    pub mean_return_pct: Option<Decimal>,
+    /// 各估價日報酬率中位數的平均（%）。
+    pub average_median_return_pct: Option<Decimal>,
+    /// 樣本加權勝率（%）。
+    pub win_rate_pct: Option<Decimal>,
+}
+
+/// 單一持有期間與範圍的回測報告 (Aggregate Root)。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BacktestReport {
+    /// 持有期間。
+    pub horizon: BacktestHorizon,
+    /// 統計範圍。
+    pub scope: BacktestScope,
+    /// 範圍名稱（市場或產業名稱；全市場為「全市場」）。
+    pub scope_name: String,
+    /// 最早估價日。
+    pub first_date: NaiveDate,
+    /// 最晚估價日。
+    pub last_date: NaiveDate,
+    /// 各訊號的彙總，依低估、合理、高估排列；沒有樣本的訊號省略。
+    pub signals: Vec<SignalReport>,
+    /// 低估組與高估組都有樣本的估價日數。
+    pub compared_dates: i64,
+    /// 上述估價日中，低估組平均報酬高於高估組的比例（%）。
+    pub undervalued_beat_ratio_pct: Option<Decimal>,
+}
+
+impl BacktestReport {
+    /// 低估組減高估組的樣本加權平均報酬率（百分點）；任一組無樣本時為 `None`。
+    ///
+    /// 正值越大代表估價訊號越有鑑別力；接近零或為負代表不值得依賴。
+    pub fn spread_pct(&self) -> Option<Decimal> {
+        let mean = |signal: EstimateSignal| {
+            self.signals
+                .iter()
+                .find(|report| report.signal == signal)
+                .and_then(|report| report.mean_return_pct)
+        };
+        Some(mean(EstimateSignal::Undervalued)? - mean(EstimateSignal::Overvalued)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(
+        market_id: i32,
+        industry_id: i32,
+        signal: EstimateSignal,
+        pct: Decimal,
+    ) -> ForwardReturnSample {
+        ForwardReturnSample {
+            security_code: "2330".to_string(),
+            market_id,
+            industry_id,
+            signal,
+            return_pct: pct,
+        }
+    }
+
+    /// 收盤價剛好等於便宜價或昂貴價時歸入兩端；估價不完整的樣本不分類。
+    #[test]
+    fn classify_uses_inclusive_bounds() {
+        let classify = |price| EstimateSignal::classify(price, dec!(50), dec!(80));
+        assert_eq!(classify(dec!(50)), Some(EstimateSignal::Undervalued));
+        assert_eq!(classify(dec!(65)), Some(EstimateSignal::Fair));
+        assert_eq!(classify(dec!(80)), Some(EstimateSignal::Overvalued));
+        assert_eq!(EstimateSignal::classify(dec!(65), dec!(0), dec!(80)), None);
+        assert_eq!(EstimateSignal::classify(dec!(65), dec!(80), dec!(80)), None);
+    }
+
+    /// 代碼必須能來回轉換。
+    #[test]
+    fn codes_round_trip() {
+        for horizon in BacktestHorizon::ALL {
+            assert_eq!(BacktestHorizon::from_code(horizon.code()), Some(horizon));
+            assert_eq!(
+                BacktestHorizon::from_months(horizon.months() as i32),
+                Some(horizon)
+            );
+        }
+        for signal in EstimateSignal::ALL {
+            assert_eq!(EstimateSignal::from_code(signal.code()), Some(signal));
+        }
+        for scope in [
+            BacktestScope::All,
+            BacktestScope::Market(2),
+            BacktestScope::Industry(24),
+        ] {
+            assert_eq!(
+                BacktestScope::from_parts(scope.kind(), scope.id()),
+                Some(scope)
+            );
+        }
+    }
+
+    /// 每個樣本同時計入全市場、所屬市場與所屬產業；全市場的空訊號仍輸出。
+    #[test]
+    fn summarize_groups_by_scope_and_signal() {
+        let date = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+        let summaries = summarize(
+            date,
+            BacktestHorizon::SixMonths,
+            &[
+                sample(2, 24, EstimateSignal::Undervalued, dec!(10)),
+                sample(2, 24, EstimateSignal::Undervalued, dec!(-2)),
+                sample(4, 24, EstimateSignal::Undervalued, dec!(4)),
+            ],
+        );
+        let find = |scope, signal| {
+            summaries
+                .iter()
+                .find(|summary| summary.scope == scope && summary.signal == signal)
+                .cloned()
+        };
+
+        let all = find(BacktestScope::All, EstimateSignal::Undervalued).unwrap();
+        assert_eq!(all.samples, 3);
+        assert_eq!(all.mean_return_pct, Some(dec!(4)));
+        assert_eq!(all.median_return_pct, Some(dec!(4)));
+        assert_eq!(all.win_rate_pct, Some(dec!(66.67)));
+
+        let twse = find(BacktestScope::Market(2), EstimateSignal::Undervalued).unwrap();
+        assert_eq!(twse.samples, 2);
+        assert_eq!(twse.median_return_pct, Some(dec!(4)));
+        assert_eq!(
+            find(BacktestScope::Industry(24), EstimateSignal::Undervalued)
+                .unwrap()
+                .samples,
+            3
+        );
+
+        let empty = find(BacktestScope::All, EstimateSignal::Overvalued).unwrap();
+        assert_eq!(empty.samples, 0);
+        assert_eq!(empty.mean_return_pct, None);
+        assert!(find(BacktestScope::Market(2), EstimateSignal::Overvalued).is_none());
+    }
+
+    /// 價差只在低估與高估兩組都有平均報酬時才有意義。
+    #[test]
+    fn spread_requires_both_ends() {
+        let signal = |signal, mean| SignalReport {
+            signal,
+            dates: 1,
+            samples: 1,
+            mean_return_pct: Some(mean),
+            average_median_return_pct: Some(mean),
+            win_rate_pct: Some(dec!(100)),
+        };
+        let date = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+        let mut report = BacktestReport {
+            horizon: BacktestHorizon::TwelveMonths,
+            scope: BacktestScope::All,
+            scope_name: "全市場".to_string(),
+            first_date: date,
+            last_date: date,
+            signals: vec![
+                signal(EstimateSignal::Undervalued, dec!(12.5)),
+                signal(EstimateSignal::Overvalued, dec!(3)),
+            ],
+            compared_dates: 1,
+            undervalued_beat_ratio_pct: Some(dec!(100)),
+        };
+        assert_eq!(report.spread_pct(), Some(dec!(9.5)));
+        report.signals.pop();
+        assert_eq!(report.spread_pct(), None);
+    }
+}
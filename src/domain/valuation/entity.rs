@@ -0,0 +1,143 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+/// 計算歷史估值區間的回顧年數。
+pub const BAND_YEARS: [i32; 2] = [5, 10];
+
+/// 參與歷史估值區間的指標。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValuationMetric {
+    /// 殖利率（%）：每日收盤價對應最近一個年度的股利合計。
+    DividendYield,
+    /// 本益比。
+    PriceEarnings,
+    /// 股價淨值比。
+    PriceBook,
+}
+
+impl ValuationMetric {
+    /// 資料庫與 API 使用的代碼。
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::DividendYield => "dividend_yield",
+            Self::PriceEarnings => "per",
+            Self::PriceBook => "pbr",
+        }
+    }
+
+    /// 由代碼還原；未知代碼回傳 `None`。
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "dividend_yield" => Some(Self::DividendYield),
+            "per" => Some(Self::PriceEarnings),
+            "pbr" => Some(Self::PriceBook),
+            _ => None,
+        }
+    }
+
+    /// 中文名稱。
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::DividendYield => "殖利率",
+            Self::PriceEarnings => "本益比",
+            Self::PriceBook => "股價淨值比",
+        }
+    }
+
+    /// 指標越高是否代表股價越便宜；只有殖利率如此。
+    pub fn higher_is_cheaper(self) -> bool {
+        matches!(self, Self::DividendYield)
+    }
+}
+
+/// 個股某一指標在自身歷史中的位置 (Entity)。
+///
+/// `percentile` 為回顧期間內「不高於當日數值」的樣本比例（%），100 代表
+/// 當日數值是期間最高。樣本只取有效數值：本益比與股價淨值比排除 0 與負值
+/// （虧損或淨值為負時交易所不提供），殖利率排除沒有股利資料的日期。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValuationBand {
+    /// 股票代號。
+    pub security_code: String,
+    /// 計算基準日。
+    pub date: NaiveDate,
+    /// 指標。
+    pub metric: ValuationMetric,
+    /// 回顧年數（5 或 10）。
+    pub years: i32,
+    /// 基準日的指標數值。
+    pub current: Decimal,
+    /// 當日數值在回顧期間內的百分位（0–100）。
+    pub percentile: Decimal,
+    /// 回顧期間最小值。
+    pub minimum: Decimal,
+    /// 回顧期間中位數。
+    pub median: Decimal,
+    /// 回顧期間最大值。
+    pub maximum: Decimal,
+    /// 回顧期間的有效樣本（交易日）數。
+    pub samples: i64,
+}
+
+impl ValuationBand {
+    /// 以「越高越便宜」統一方向的百分位：殖利率原樣，本益比與股價淨值比取
+    /// `100 - percentile`，讓不同指標可以直接比較或排序。
+    pub fn cheapness(&self) -> Decimal {
+        if self.metric.higher_is_cheaper() {
+            self.percentile
+        } else {
+            dec!(100) - self.percentile
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn band(metric: ValuationMetric, percentile: Decimal) -> ValuationBand {
+        ValuationBand {
+            security_code: "2330".to_string(),
+            date: NaiveDate::from_ymd_opt(2026, 8, 21).unwrap(),
+            metric,
+            years: 5,
+            current: dec!(20),
+            percentile,
+            minimum: dec!(10),
+            median: dec!(18),
+            maximum: dec!(30),
+            samples: 1200,
+        }
+    }
+
+    /// 代碼必須能來回轉換，未知代碼不可被誤判為任何指標。
+    #[test]
+    fn metric_code_round_trips() {
+        for metric in [
+            ValuationMetric::DividendYield,
+            ValuationMetric::PriceEarnings,
+            ValuationMetric::PriceBook,
+        ] {
+            assert_eq!(ValuationMetric::from_code(metric.code()), Some(metric));
+        }
+        assert_eq!(ValuationMetric::from_code("pe"), None);
+    }
+
+    /// 殖利率百分位越高越便宜；本益比與股價淨值比方向相反。
+    #[test]
+    fn cheapness_aligns_direction() {
+        assert_eq!(
+            band(ValuationMetric::DividendYield, dec!(85)).cheapness(),
+            dec!(85)
+        );
+        assert_eq!(
+            band(ValuationMetric::PriceEarnings, dec!(85)).cheapness(),
+            dec!(15)
+        );
+        assert_eq!(
+            band(ValuationMetric::PriceBook, dec!(0)).cheapness(),
+            dec!(100)
+        );
+    }
+}
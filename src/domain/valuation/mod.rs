@@ -0,0 +1,33 @@
+//! 歷史估值區間領域。
+//!
+//! 將個股當日的殖利率、本益比與股價淨值比放回自身過去 5 年與 10 年的歷史中，
+//! 以百分位與最小／中位數／最大值描述目前估值的相對位置，供估值查詢與條件選股。
+//!
+//! 另提供可插拔的估價模型（殖利率／本益比／股價淨值比區間、葛拉漢數、股利
+//! 折現），各模型的假設存放於系統設定並隨每筆結果保存，方便比較與回測。
+//!
+//! 估價回測則把每個歷史估價日的股票依收盤價分成低估、合理、高估三組，量測
+//! 之後 3／6／12 個月的含息報酬，依全市場、市場與產業彙總，用來判斷便宜價、
+//! 合理價、昂貴價是否真的有預測力。
+
+/// 估價訊號回測模組。
+pub mod backtest;
+/// 歷史估值區間領域實體模組。
+pub mod entity;
+/// 估價模型模組。
+pub mod model;
+/// 歷史估值區間倉儲介面模組。
+pub mod repository;
+
+pub use backtest::{
+    BacktestHorizon, BacktestReport, BacktestReportQuery, BacktestScope, EstimateSignal,
+    EstimateSnapshot, ForwardReturnSample, SignalReport, SignalSummary, summarize,
+};
+pub use entity::{BAND_YEARS, ValuationBand, ValuationMetric};
+pub use model::{
+    ModelEstimate, PriceRange, QuantileRequest, ValuationInputs, ValuationModel,
+    ValuationModelKind, build_model, estimate_all,
+};
+pub use repository::{
+    EstimateBacktestRepository, ValuationBandRepository, ValuationModelRepository,
+};
@@ -0,0 +1,533 @@
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use rust_decimal::{
+    Decimal,
+    prelude::{FromPrimitive, ToPrimitive},
+};
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use super::entity::ValuationMetric;
+
+/// 已註冊的估價模型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValuationModelKind {
+    /// 殖利率區間：股利 ÷ 歷史殖利率分位數。
+    YieldBand,
+    /// 本益比區間：近四季 EPS × 歷史本益比分位數。
+    PerBand,
+    /// 股價淨值比區間：每股淨值 × 歷史股價淨值比分位數。
+    PbrBand,
+    /// 葛拉漢數：√(倍數 × EPS × 每股淨值)。
+    Graham,
+    /// 股利折現模型（高登成長模型）：D × (1 + g) ÷ (r − g)。
+    DividendDiscount,
+}
+
+impl ValuationModelKind {
+    /// 全部模型，依計算與顯示順序排列。
+    pub const ALL: [Self; 5] = [
+        Self::YieldBand,
+        Self::PerBand,
+        Self::PbrBand,
+        Self::Graham,
+        Self::DividendDiscount,
+    ];
+
+    /// 資料庫、設定鍵與 API 使用的代碼。
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::YieldBand => "yield_band",
+            Self::PerBand => "per_band",
+            Self::PbrBand => "pbr_band",
+            Self::Graham => "graham",
+            Self::DividendDiscount => "ddm",
+        }
+    }
+
+    /// 由代碼還原；未知代碼回傳 `None`。
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.code() == code)
+    }
+
+    /// 中文名稱。
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::YieldBand => "殖利率區間",
+            Self::PerBand => "本益比區間",
+            Self::PbrBand => "股價淨值比區間",
+            Self::Graham => "葛拉漢數",
+            Self::DividendDiscount => "股利折現",
+        }
+    }
+
+    /// 系統設定表中存放此模型假設（JSON）的鍵名。
+    pub fn config_key(self) -> String {
+        format!("valuation-model:{}", self.code())
+    }
+}
+
+/// 便宜、合理、昂貴三個價位。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceRange {
+    /// 便宜價。
+    pub cheap: Decimal,
+    /// 合理價。
+    pub fair: Decimal,
+    /// 昂貴價。
+    pub expensive: Decimal,
+}
+
+impl PriceRange {
+    /// 以合理價上下各留安全邊際（%）展開成三個價位。
+    fn around(fair: Decimal, margin_percent: Decimal) -> Self {
+        let margin = margin_percent / dec!(100);
+        Self {
+            cheap: (fair * (Decimal::ONE - margin)).round_dp(2),
+            fair: fair.round_dp(2),
+            expensive: (fair * (Decimal::ONE + margin)).round_dp(2),
+        }
+    }
+}
+
+/// 區間模型需要的歷史分位數：指標、回顧年數與便宜／合理／昂貴三個百分位。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuantileRequest {
+    /// 指標。
+    pub metric: ValuationMetric,
+    /// 回顧年數。
+    pub years: i32,
+    /// 便宜、合理、昂貴價位各自對應的百分位（0–100）。
+    pub percentiles: [Decimal; 3],
+}
+
+/// 單一股票在計算日可供各模型使用的輸入。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValuationInputs {
+    /// 股票代號。
+    pub security_code: String,
+    /// 計算日。
+    pub date: NaiveDate,
+    /// 計算日（或 31 天內最近一日）收盤價。
+    pub closing_price: Decimal,
+    /// 最近一個年度的股利合計。
+    pub dividend: Option<Decimal>,
+    /// 近四季 EPS。
+    pub trailing_eps: Option<Decimal>,
+    /// 每股淨值。
+    pub book_value_per_share: Option<Decimal>,
+    /// 各指標依模型要求取得的歷史分位數，順序為便宜、合理、昂貴。
+    pub quantiles: HashMap<ValuationMetric, [Decimal; 3]>,
+}
+
+/// 可插拔的估價模型。
+///
+/// 每個模型自帶假設，並在資料不足（例如虧損、未配息）時回傳 `None`，
+/// 而不是產生沒有意義的價位。
+pub trait ValuationModel: Send + Sync {
+    /// 模型種類。
+    fn kind(&self) -> ValuationModelKind;
+
+    /// 模型需要的歷史分位數；不需要時為 `None`。
+    fn quantile_request(&self) -> Option<QuantileRequest> {
+        None
+    }
+
+    /// 目前使用的假設，以 JSON 表示，隨每筆結果保存以便比較與回測。
+    fn assumptions(&self) -> String;
+
+    /// 依輸入估算三個價位。
+    fn estimate(&self, inputs: &ValuationInputs) -> Option<PriceRange>;
+}
+
+/// 區間模型的假設。
+///
+/// 百分位以指標本身的方向表示：本益比與股價淨值比的便宜價取低百分位，
+/// 殖利率的便宜價則取高百分位（高殖利率對應低股價）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BandAssumptions {
+    /// 回顧年數。
+    pub years: i32,
+    /// 便宜價對應的百分位。
+    pub cheap_percentile: Decimal,
+    /// 合理價對應的百分位。
+    pub fair_percentile: Decimal,
+    /// 昂貴價對應的百分位。
+    pub expensive_percentile: Decimal,
+}
+
+/// 殖利率、本益比與股價淨值比的歷史區間模型。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BandModel {
+    /// 指標。
+    pub metric: ValuationMetric,
+    /// 假設。
+    pub assumptions: BandAssumptions,
+}
+
+impl BandModel {
+    /// 指標的預設假設：回顧 5 年，以 20／50／80 百分位為界。
+    pub fn with_defaults(metric: ValuationMetric) -> Self {
+        let (cheap, expensive) = if metric.higher_is_cheaper() {
+            (dec!(80), dec!(20))
+        } else {
+            (dec!(20), dec!(80))
+        };
+        Self {
+            metric,
+            assumptions: BandAssumptions {
+                years: 5,
+                cheap_percentile: cheap,
+                fair_percentile: dec!(50),
+                expensive_percentile: expensive,
+            },
+        }
+    }
+}
+
+impl ValuationModel for BandModel {
+    fn kind(&self) -> ValuationModelKind {
+        match self.metric {
+            ValuationMetric::DividendYield => ValuationModelKind::YieldBand,
+            ValuationMetric::PriceEarnings => ValuationModelKind::PerBand,
+            ValuationMetric::PriceBook => ValuationModelKind::PbrBand,
+        }
+    }
+
+    fn quantile_request(&self) -> Option<QuantileRequest> {
+        Some(QuantileRequest {
+            metric: self.metric,
+            years: self.assumptions.years,
+            percentiles: [
+                self.assumptions.cheap_percentile,
+                self.assumptions.fair_percentile,
+                self.assumptions.expensive_percentile,
+            ],
+        })
+    }
+
+    fn assumptions(&self) -> String {
+        serde_json::to_string(&self.assumptions).unwrap_or_default()
+    }
+
+    fn estimate(&self, inputs: &ValuationInputs) -> Option<PriceRange> {
+        let quantiles = inputs.quantiles.get(&self.metric)?;
+        let price = |quantile: Decimal| -> Option<Decimal> {
+            if quantile <= Decimal::ZERO {
+                return None;
+            }
+            let value = match self.metric {
+                ValuationMetric::DividendYield => {
+                    inputs.dividend.filter(|d| *d > Decimal::ZERO)? / quantile * dec!(100)
+                }
+                ValuationMetric::PriceEarnings => {
+                    inputs.trailing_eps.filter(|eps| *eps > Decimal::ZERO)? * quantile
+                }
+                ValuationMetric::PriceBook => {
+                    inputs
+                        .book_value_per_share
+                        .filter(|bv| *bv > Decimal::ZERO)?
+                        * quantile
+                }
+            };
+            Some(value.round_dp(2))
+        };
+        Some(PriceRange {
+            cheap: price(quantiles[0])?,
+            fair: price(quantiles[1])?,
+            expensive: price(quantiles[2])?,
+        })
+    }
+}
+
+/// 葛拉漢數模型。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GrahamModel {
+    /// 本益比與股價淨值比乘積的上限，葛拉漢原著為 15 × 1.5 = 22.5。
+    pub multiplier: Decimal,
+    /// 便宜價與昂貴價相對合理價的安全邊際（%）。
+    pub margin_of_safety_percent: Decimal,
+}
+
+impl Default for GrahamModel {
+    fn default() -> Self {
+        Self {
+            multiplier: dec!(22.5),
+            margin_of_safety_percent: dec!(20),
+        }
+    }
+}
+
+impl ValuationModel for GrahamModel {
+    fn kind(&self) -> ValuationModelKind {
+        ValuationModelKind::Graham
+    }
+
+    fn assumptions(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    fn estimate(&self, inputs: &ValuationInputs) -> Option<PriceRange> {
+        let eps = inputs.trailing_eps.filter(|eps| *eps > Decimal::ZERO)?;
+        let book_value = inputs
+            .book_value_per_share
+            .filter(|bv| *bv > Decimal::ZERO)?;
+        let product = (self.multiplier * eps * book_value).to_f64()?;
+        let fair = Decimal::from_f64(product.sqrt())?;
+        Some(PriceRange::around(fair, self.margin_of_safety_percent))
+    }
+}
+
+/// 股利折現模型（高登成長模型）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DividendDiscountModel {
+    /// 要求報酬率（%）。
+    pub required_return_percent: Decimal,
+    /// 股利永續成長率（%），必須低於要求報酬率。
+    pub growth_percent: Decimal,
+    /// 便宜價與昂貴價相對合理價的安全邊際（%）。
+    pub margin_of_safety_percent: Decimal,
+}
+
+impl Default for DividendDiscountModel {
+    fn default() -> Self {
+        Self {
+            required_return_percent: dec!(8),
+            growth_percent: dec!(2),
+            margin_of_safety_percent: dec!(20),
+        }
+    }
+}
+
+impl ValuationModel for DividendDiscountModel {
+    fn kind(&self) -> ValuationModelKind {
+        ValuationModelKind::DividendDiscount
+    }
+
+    fn assumptions(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    fn estimate(&self, inputs: &ValuationInputs) -> Option<PriceRange> {
+        let dividend = inputs.dividend.filter(|d| *d > Decimal::ZERO)?;
+        let spread = self.required_return_percent - self.growth_percent;
+        if spread <= Decimal::ZERO {
+            return None;
+        }
+        let fair = dividend * (dec!(100) + self.growth_percent) / spread;
+        Some(PriceRange::around(fair, self.margin_of_safety_percent))
+    }
+}
+
+/// 依設定值建立模型；`config` 為系統設定中的 JSON，未設定時使用預設假設。
+///
+/// # Errors
+/// 設定值不是該模型可接受的 JSON 時回傳錯誤。
+pub fn build_model(
+    kind: ValuationModelKind,
+    config: Option<&str>,
+) -> Result<Box<dyn ValuationModel>> {
+    let context = || format!("Failed to parse assumptions of {}", kind.code());
+    let model: Box<dyn ValuationModel> = match kind {
+        ValuationModelKind::YieldBand
+        | ValuationModelKind::PerBand
+        | ValuationModelKind::PbrBand => {
+            let metric = match kind {
+                ValuationModelKind::YieldBand => ValuationMetric::DividendYield,
+                ValuationModelKind::PerBand => ValuationMetric::PriceEarnings,
+                _ => ValuationMetric::PriceBook,
+            };
+            let mut model = BandModel::with_defaults(metric);
+            if let Some(config) = config {
+                model.assumptions = serde_json::from_str(config).with_context(context)?;
+            }
+            Box::new(model)
+        }
+        ValuationModelKind::Graham => Box::new(match config {
+            Some(config) => serde_json::from_str::<GrahamModel>(config).with_context(context)?,
+            None => GrahamModel::default(),
+        }),
+        ValuationModelKind::DividendDiscount => Box::new(match config {
+            Some(config) => {
+                serde_json::from_str::<DividendDiscountModel>(config).with_context(context)?
+            }
+            None => DividendDiscountModel::default(),
+        }),
+    };
+    Ok(model)
+}
+
+/// 單一模型對單一股票在某一日的估價結果 (Entity)。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelEstimate {
+    /// 股票代號。
+    pub security_code: String,
+    /// 計算日。
+    pub date: NaiveDate,
+    /// 模型。
+    pub model: ValuationModelKind,
+    /// 計算時採用的收盤價。
+    pub closing_price: Decimal,
+    /// 三個價位。
+    pub range: PriceRange,
+    /// 計算時的模型假設（JSON）。
+    pub assumptions: String,
+}
+
+/// 以全部模型估算一檔股票，略過資料不足的模型。
+pub fn estimate_all(
+    models: &[Box<dyn ValuationModel>],
+    inputs: &ValuationInputs,
+) -> Vec<ModelEstimate> {
+    models
+        .iter()
+        .filter_map(|model| {
+            Some(ModelEstimate {
+                security_code: inputs.security_code.clone(),
+                date: inputs.date,
+                model: model.kind(),
+                closing_price: inputs.closing_price,
+                range: model.estimate(inputs)?,
+                assumptions: model.assumptions(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs() -> ValuationInputs {
+        ValuationInputs {
+            security_code: "2330".to_string(),
+            date: NaiveDate::from_ymd_opt(2026, 8, 21).unwrap(),
+            closing_price: dec!(100),
+            dividend: Some(dec!(4)),
+            trailing_eps: Some(dec!(8)),
+            book_value_per_share: Some(dec!(50)),
+            quantiles: HashMap::from([
+                (
+                    ValuationMetric::DividendYield,
+                    [dec!(5), dec!(4), dec!(2.5)],
+                ),
+                (
+                    ValuationMetric::PriceEarnings,
+                    [dec!(10), dec!(12.5), dec!(15)],
+                ),
+                (ValuationMetric::PriceBook, [dec!(1.2), dec!(1.6), dec!(2)]),
+            ]),
+        }
+    }
+
+    /// 區間模型以歷史分位數換算價位；殖利率越高價位越低。
+    #[test]
+    fn band_models_convert_quantiles_to_prices() {
+        let inputs = inputs();
+        let yield_band = BandModel::with_defaults(ValuationMetric::DividendYield);
+        assert_eq!(
+            yield_band.estimate(&inputs),
+            Some(PriceRange {
+                cheap: dec!(80),
+                fair: dec!(100),
+                expensive: dec!(160),
+            })
+        );
+        let per_band = BandModel::with_defaults(ValuationMetric::PriceEarnings);
+        assert_eq!(
+            per_band.estimate(&inputs),
+            Some(PriceRange {
+                cheap: dec!(80),
+                fair: dec!(100),
+                expensive: dec!(120),
+            })
+        );
+        assert_eq!(
+            yield_band.quantile_request().unwrap().percentiles,
+            [dec!(80), dec!(50), dec!(20)]
+        );
+
+        let mut losing = inputs.clone();
+        losing.trailing_eps = Some(dec!(-1));
+        assert_eq!(per_band.estimate(&losing), None);
+        losing.quantiles.clear();
+        assert_eq!(
+            BandModel::with_defaults(ValuationMetric::PriceBook).estimate(&losing),
+            None
+        );
+    }
+
+    /// 葛拉漢數 √(22.5 × 8 × 50) = 94.87，上下各 20% 安全邊際。
+    #[test]
+    fn graham_number_applies_margin_of_safety() {
+        let range = GrahamModel::default().estimate(&inputs()).unwrap();
+        assert_eq!(range.fair, dec!(94.87));
+        assert_eq!(range.cheap, dec!(75.89));
+        assert_eq!(range.expensive, dec!(113.84));
+    }
+
+    /// 4 × 1.02 ÷ (8% − 2%) = 68；要求報酬率不高於成長率時無解。
+    #[test]
+    fn dividend_discount_requires_return_above_growth() {
+        let model = DividendDiscountModel::default();
+        assert_eq!(model.estimate(&inputs()).unwrap().fair, dec!(68));
+
+        let invalid = DividendDiscountModel {
+            growth_percent: dec!(8),
+            ..DividendDiscountModel::default()
+        };
+        assert_eq!(invalid.estimate(&inputs()), None);
+    }
+
+    /// 設定值覆蓋預設假設，且隨結果保存的假設就是實際使用的值。
+    #[test]
+    fn build_model_reads_json_assumptions() {
+        let model = build_model(
+            ValuationModelKind::DividendDiscount,
+            Some(r#"{"required_return_percent":"10","growth_percent":"0","margin_of_safety_percent":"10"}"#),
+        )
+        .unwrap();
+        assert_eq!(model.estimate(&inputs()).unwrap().fair, dec!(40));
+        assert!(
+            model
+                .assumptions()
+                .contains("\"required_return_percent\":\"10\"")
+        );
+
+        let band = build_model(
+            ValuationModelKind::PbrBand,
+            Some(r#"{"years":10,"cheap_percentile":10,"fair_percentile":50,"expensive_percentile":90}"#),
+        )
+        .unwrap();
+        assert_eq!(band.kind(), ValuationModelKind::PbrBand);
+        assert_eq!(band.quantile_request().unwrap().years, 10);
+
+        assert!(build_model(ValuationModelKind::Graham, Some("{}")).is_err());
+        assert_eq!(
+            estimate_all(
+                &ValuationModelKind::ALL
+                    .map(|kind| build_model(kind, None).unwrap())
+                    .into_iter()
+                    .collect::<Vec<_>>(),
+                &inputs()
+            )
+            .len(),
+            5
+        );
+    }
+
+    /// 代碼必須能來回轉換。
+    #[test]
+    fn model_kind_code_round_trips() {
+        for kind in ValuationModelKind::ALL {
+            assert_eq!(ValuationModelKind::from_code(kind.code()), Some(kind));
+        }
+        assert_eq!(ValuationModelKind::from_code("dcf"), None);
+        assert_eq!(
+            ValuationModelKind::Graham.config_key(),
+            "valuation-model:graham"
+        );
+    }
+}
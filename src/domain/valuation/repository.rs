@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use super::backtest::{
+    BacktestHorizon, BacktestReport, BacktestReportQuery, EstimateSnapshot, SignalSummary,
+};
+use super::entity::ValuationBand;
+use super::model::{ModelEstimate, QuantileRequest, ValuationInputs};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+/// 歷史估值區間的倉儲合約 (Repository Trait)。
+#[async_trait]
+pub trait ValuationBandRepository: Send + Sync {
+    /// 補齊截至指定交易日的每日歷史殖利率，回傳寫入的筆數。
+    ///
+    /// 第一次執行回補最長回顧期間的全部歷史；之後只重算最近 30 天，涵蓋
+    /// 晚公布或更正的股利。
+    async fn rebuild_daily_yields(&self, date: NaiveDate) -> Result<u64>;
+
+    /// 重建指定交易日所有股票、各指標與各回顧年數的估值區間，回傳寫入的筆數。
+    async fn rebuild_bands(&self, date: NaiveDate) -> Result<u64>;
+
+    /// 取得個股在指定日期（含）前 31 天內最新一個基準日的全部估值區間；
+    /// 未指定日期時取最新一日。
+    async fn fetch_latest(
+        &self,
+        security_code: &str,
+        date: Option<NaiveDate>,
+    ) -> Result<Vec<ValuationBand>>;
+}
+
+/// 估價模型輸入與結果的倉儲合約 (Repository Trait)。
+#[async_trait]
+pub trait ValuationModelRepository: Send + Sync {
+    /// 取得指定日期所有可估價股票的基本輸入；`quantiles` 留空由呼叫端依模型補上。
+    ///
+    /// 排除已下市與 ETF，收盤價取指定日期（含）前 31 天內最新一筆。
+    async fn fetch_inputs(&self, date: NaiveDate) -> Result<Vec<ValuationInputs>>;
+
+    /// 依模型要求計算每檔股票截至指定日期的歷史分位數，以股票代號為鍵。
+    async fn fetch_quantiles(
+        &self,
+        date: NaiveDate,
+        request: &QuantileRequest,
+    ) -> Result<HashMap<String, [Decimal; 3]>>;
+
+    /// 寫入估價結果；同一 `(股票, 日期, 模型)` 重複寫入視為更新。
+    async fn upsert_estimates(&self, estimates: &[ModelEstimate]) -> Result<()>;
+
+    /// 取得個股在指定日期（含）前 31 天內最新一個計算日的各模型結果；
+    /// 未指定日期時取最新一日。
+    async fn fetch_estimates(
+        &self,
+        security_code: &str,
+        date: Option<NaiveDate>,
+    ) -> Result<Vec<ModelEstimate>>;
+}
+
+/// 估價訊號回測的倉儲合約 (Repository Trait)。
+#[async_trait]
+pub trait EstimateBacktestRepository: Send + Sync {
+    /// 取得不晚於 `until`、且尚未以指定持有期間評估過的估價日，由早至晚排序。
+    ///
+    /// 呼叫端以「最新交易日 − 持有月數」作為 `until`，只有持有期已走完的
+    /// 估價日才會被評估。
+    async fn fetch_pending_dates(
+        &self,
+        horizon: BacktestHorizon,
+        until: NaiveDate,
+    ) -> Result<Vec<NaiveDate>>;
+
+    /// 取得指定估價日全部股票的估價快照。
+    async fn fetch_snapshots(&self, date: NaiveDate) -> Result<Vec<EstimateSnapshot>>;
+
+    /// 以新結果取代指定估價日與持有期間的全部彙總，回傳寫入的筆數。
+    async fn replace_summaries(
+        &self,
+        date: NaiveDate,
+        horizon: BacktestHorizon,
+        summaries: &[SignalSummary],
+    ) -> Result<u64>;
+
+    /// 依條件彙整多個估價日的回測報告，依持有期間與範圍排序。
+    async fn fetch_reports(&self, query: &BacktestReportQuery) -> Result<Vec<BacktestReport>>;
+}
@@ -0,0 +1,426 @@
+//! 外部來源原始回應封存。
+//!
+//! 爬蟲從 TWSE/TPEx 抓回的原始 JSON 在解析後就被丟掉；一旦日後發現解析規則
+//! 有誤（例如欄位改名、本益比佔位符處理錯誤），只能重新向來源抓取，而來源
+//! 常常只提供近期資料。本模組把原始 body 以 gzip 壓縮後落地到本機，讓
+//! `app::backfill::reprocess` 可以對任意歷史日期重跑防腐層映射。
+//!
+//! 目錄結構（保母級說明）：
+//!
+//! ```text
+//! {root}/{source}/{endpoint}/{params}/{fetched_at}.json.gz
+//! archive/twse/quote/20260430/20260430T153012123.json.gz
+//! ```
+//!
+//! - `source`／`endpoint`／`params` 三段都會先經 [`sanitize_segment`] 清洗，
+//!   來源參數不可能跳出封存根目錄。
+//! - 同一組參數可以有多份封存（例如盤後重抓），讀取時取 `fetched_at` 最新者。
+//! - 寫入先落到 `.tmp` 再 rename，讀取端永遠看不到寫到一半的檔案。
+//! - 封存是「盡力而為」：失敗只記錄 warning，絕不讓爬蟲主流程因此失敗。
+//! - [`fetch_json`] 在解析前封存，解析失敗的回應（正是需要重跑的情況）也會留下。
+
+use std::{
+    fs,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use chrono::{Duration, Local, NaiveDateTime};
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use serde::de::DeserializeOwned;
+
+use crate::core::{config::SETTINGS, util::http};
+
+/// 未設定封存目錄時使用的預設根目錄（相對於工作目錄）。
+pub const DEFAULT_DIR: &str = "archive";
+/// 未設定保留天數時使用的預設值。
+///
+/// 一年足以涵蓋「發現解析錯誤 → 修正 → 重跑」的合理時間窗，
+/// 收盤報價兩個來源每天壓縮後約 1 MiB，一年不到 400 MiB。
+pub const DEFAULT_RETENTION_DAYS: i64 = 365;
+/// 封存檔副檔名。
+const FILE_SUFFIX: &str = ".json.gz";
+/// 封存檔名使用的抓取時間格式（毫秒精度，字典序即時間序）。
+const FETCHED_AT_FORMAT: &str = "%Y%m%dT%H%M%S%3f";
+
+/// 一份原始回應的封存鍵：來源、端點與請求參數。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchiveKey<'a> {
+    /// 資料來源，例如 `twse`、`tpex`。
+    pub source: &'a str,
+    /// 來源內的端點，例如 `quote`、`pe_ratio`。
+    pub endpoint: &'a str,
+    /// 請求參數摘要，通常是資料日期 `YYYYMMDD`。
+    pub params: &'a str,
+}
+
+impl<'a> ArchiveKey<'a> {
+    /// 建立封存鍵。
+    pub fn new(source: &'a str, endpoint: &'a str, params: &'a str) -> Self {
+        Self {
+            source,
+            endpoint,
+            params,
+        }
+    }
+
+    /// 回傳此鍵在 `root` 底下的封存目錄。
+    fn dir(&self, root: &Path) -> PathBuf {
+        root.join(sanitize_segment(self.source))
+            .join(sanitize_segment(self.endpoint))
+            .join(sanitize_segment(self.params))
+    }
+}
+
+/// 是否已啟用原始回應封存。
+pub fn is_enabled() -> bool {
+    SETTINGS.archive.enabled
+}
+
+/// 回傳生效的封存根目錄。
+fn root_dir() -> PathBuf {
+    let dir = SETTINGS.archive.dir.trim();
+    PathBuf::from(if dir.is_empty() { DEFAULT_DIR } else { dir })
+}
+
+/// 回傳生效的保留天數；未設定或非正數時使用 [`DEFAULT_RETENTION_DAYS`]。
+fn retention_days() -> i64 {
+    match SETTINGS.archive.retention_days {
+        days if days > 0 => days,
+        _ => DEFAULT_RETENTION_DAYS,
+    }
+}
+
+/// 下載 JSON 回應、封存原始 body 後再解析。
+///
+/// 封存在解析之前：解析失敗代表解析規則可能有誤，這份原始回應正是日後重跑需要的。
+pub async fn fetch_json<T: DeserializeOwned>(url: &str, key: ArchiveKey<'_>) -> Result<T> {
+    fetch_json_in(enabled_root(), url, key).await
+}
+
+/// [`fetch_json`] 的實作；`root` 為 `None` 時不封存。
+async fn fetch_json_in<T: DeserializeOwned>(
+    root: Option<PathBuf>,
+    url: &str,
+    key: ArchiveKey<'_>,
+) -> Result<T> {
+    let body = http::get_body(url).await?;
+    save_in(root, key, &body).await;
+    http::parse_json(url, &body)
+}
+
+/// 封存一份原始回應；未啟用時直接略過。
+///
+/// 壓縮與寫檔在 blocking 執行緒進行，避免佔住 async runtime。
+/// 任何失敗都只記錄 warning：封存是輔助功能，不能反過來讓收盤報價抓取失敗。
+pub async fn save(key: ArchiveKey<'_>, body: &[u8]) {
+    save_in(enabled_root(), key, body).await;
+}
+
+/// 已啟用封存時回傳根目錄。
+fn enabled_root() -> Option<PathBuf> {
+    is_enabled().then(root_dir)
+}
+
+/// 封存到 `root`；`root` 為 `None` 時略過。
+async fn save_in(root: Option<PathBuf>, key: ArchiveKey<'_>, body: &[u8]) {
+    let Some(root) = root else {
+        return;
+    };
+
+    let dir = key.dir(&root);
+    let body = body.to_vec();
+    let fetched_at = Local::now().naive_local();
+    let result = tokio::task::spawn_blocking(move || write_to_dir(&dir, fetched_at, &body)).await;
+
+    match result {
+        Ok(Ok(path)) => tracing::debug!("原始回應已封存: {}", path.display()),
+        Ok(Err(why)) => tracing::warn!(
+            "原始回應封存失敗 source={} endpoint={} params={}: {why:#}",
+            key.source,
+            key.endpoint,
+            key.params
+        ),
+        Err(why) => tracing::warn!("原始回應封存工作中斷: {why}"),
+    }
+}
+
+/// 讀取指定封存鍵最新一份封存並解壓縮；查無封存時回傳 `None`。
+pub async fn load_latest(key: ArchiveKey<'_>) -> Result<Option<Vec<u8>>> {
+    let dir = key.dir(&root_dir());
+    tokio::task::spawn_blocking(move || read_latest_from_dir(&dir))
+        .await
+        .context("Failed to join archive reader task")?
+}
+
+/// 刪除超過保留天數的封存檔，回傳刪除的檔案數。
+///
+/// 由排程每日呼叫；未啟用封存時不做任何事，避免誤刪使用者手動放置的檔案。
+pub async fn purge_expired() -> Result<usize> {
+    if !is_enabled() {
+        return Ok(0);
+    }
+
+    let root = root_dir();
+    let now = Local::now().naive_local();
+    let days = retention_days();
+    tokio::task::spawn_blocking(move || purge_expired_in(&root, now, days))
+        .await
+        .context("Failed to join archive purge task")?
+}
+
+/// 將 body 壓縮後寫入 `dir`，回傳最終檔案路徑。
+fn write_to_dir(dir: &Path, fetched_at: NaiveDateTime, body: &[u8]) -> Result<PathBuf> {
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create archive dir {}", dir.display()))?;
+
+    let path = dir.join(file_name(fetched_at));
+    let tmp_path = path.with_extension("tmp");
+    let mut encoder = GzEncoder::new(Vec::with_capacity(body.len() / 4), Compression::default());
+    encoder.write_all(body)?;
+    let compressed = encoder.finish()?;
+
+    fs::write(&tmp_path, compressed)
+        .with_context(|| format!("Failed to write archive {}", tmp_path.display()))?;
+    fs::rename(&tmp_path, &path)
+        .with_context(|| format!("Failed to rename archive {}", tmp_path.display()))?;
+
+    Ok(path)
+}
+
+/// 讀取 `dir` 內抓取時間最新的封存檔並解壓縮。
+fn read_latest_from_dir(dir: &Path) -> Result<Option<Vec<u8>>> {
+    if !dir.exists() {
+        return Ok(None);
+    }
+
+    let latest = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read archive dir {}", dir.display()))?
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            parse_fetched_at(&name).map(|fetched_at| (fetched_at, entry.path()))
+        })
+        .max_by_key(|(fetched_at, _)| *fetched_at);
+
+    let Some((_, path)) = latest else {
+        return Ok(None);
+    };
+
+    let file =
+        fs::File::open(&path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut body = Vec::new();
+    GzDecoder::new(file)
+        .read_to_end(&mut body)
+        .with_context(|| format!("Failed to decompress {}", path.display()))?;
+
+    Ok(Some(body))
+}
+
+/// 刪除 `root` 底下抓取時間早於 `now - retention_days` 的封存檔。
+///
+/// 刪除後若參數目錄已空也一併移除，避免留下大量空目錄；
+/// 檔名無法解析的檔案一律保留不動。
+fn purge_expired_in(root: &Path, now: NaiveDateTime, retention_days: i64) -> Result<usize> {
+    if !root.exists() {
+        return Ok(0);
+    }
+
+    let cutoff = now - Duration::days(retention_days);
+    let mut removed = 0;
+    // 固定三層：source / endpoint / params；層間若混入一般檔案則略過。
+    for source in sub_dirs(root)? {
+        for endpoint in sub_dirs(&source)? {
+            for params_dir in sub_dirs(&endpoint)? {
+                for file in fs::read_dir(&params_dir)?.flatten() {
+                    let name = file.file_name().to_string_lossy().to_string();
+                    if parse_fetched_at(&name).is_some_and(|fetched_at| fetched_at < cutoff) {
+                        fs::remove_file(file.path()).with_context(|| {
+                            format!("Failed to remove archive {}", file.path().display())
+                        })?;
+                        removed += 1;
+                    }
+                }
+                // 目錄非空時 remove_dir 會失敗，正好代表還有檔案要保留。
+                let _ = fs::remove_dir(&params_dir);
+            }
+        }
+    }
+
+    Ok(removed)
+}
+
+/// 列出 `dir` 底下的子目錄。
+fn sub_dirs(dir: &Path) -> Result<Vec<PathBuf>> {
+    Ok(fs::read_dir(dir)
+        .with_context(|| format!("Failed to read archive dir {}", dir.display()))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect())
+}
+
+/// 清洗單一路徑片段：只保留 ASCII 英數與 `-`、`_`、`.`，其餘一律換成 `_`。
+///
+/// 空字串與 `.`、`..` 這類特殊片段改成 `_`，確保封存路徑不會跳出根目錄。
+fn sanitize_segment(raw: &str) -> String {
+    let cleaned: String = raw
+        .trim()
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.') {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    if cleaned.is_empty() || cleaned.chars().all(|ch| ch == '.') {
+        "_".to_string()
+    } else {
+        cleaned
+    }
+}
+
+/// 依抓取時間產生封存檔名。
+fn file_name(fetched_at: NaiveDateTime) -> String {
+    format!("{}{}", fetched_at.format(FETCHED_AT_FORMAT), FILE_SUFFIX)
+}
+
+/// 從封存檔名解析抓取時間；不是封存檔（例如 `.tmp`）時回傳 `None`。
+fn parse_fetched_at(file_name: &str) -> Option<NaiveDateTime> {
+    let stem = file_name.strip_suffix(FILE_SUFFIX)?;
+    NaiveDateTime::parse_from_str(stem, FETCHED_AT_FORMAT).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    /// 建立本次測試專用的暫存目錄，避免與其他測試互相干擾。
+    fn temp_archive_dir(tag: &str) -> PathBuf {
+        let unique = format!(
+            "{}-{}-{}",
+            tag,
+            std::process::id(),
+            Local::now().timestamp_nanos_opt().unwrap_or_default()
+        );
+        std::env::temp_dir()
+            .join("stock_rust-archive-test")
+            .join(unique)
+    }
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 4, day)
+            .and_then(|date| date.and_hms_milli_opt(hour, 30, 12, 123))
+            .expect("測試時間應合法")
+    }
+
+    /// 路徑片段只保留安全字元，`..` 與分隔符號不能跳出根目錄。
+    #[test]
+    fn sanitize_segment_blocks_path_traversal() {
+        assert_eq!(sanitize_segment("twse"), "twse");
+        assert_eq!(sanitize_segment("2026-04-30"), "2026-04-30");
+        assert_eq!(sanitize_segment("../etc"), ".._etc");
+        assert_eq!(sanitize_segment(".."), "_");
+        assert_eq!(sanitize_segment(""), "_");
+        assert_eq!(sanitize_segment("a/b\\c d"), "a_b_c_d");
+
+        let key = ArchiveKey::new("twse", "quote", "../../x");
+        let dir = key.dir(Path::new("root"));
+        assert!(dir.starts_with("root/twse/quote"));
+        assert_eq!(dir.components().count(), 4);
+    }
+
+    /// 檔名與抓取時間可雙向轉換，`.tmp` 等非封存檔不會被誤認。
+    #[test]
+    fn file_name_roundtrip() {
+        let fetched_at = at(30, 15);
+        let name = file_name(fetched_at);
+        assert_eq!(name, "20260430T153012123.json.gz");
+        assert_eq!(parse_fetched_at(&name), Some(fetched_at));
+        assert_eq!(parse_fetched_at("20260430T153012123.tmp"), None);
+        assert_eq!(parse_fetched_at("readme.json.gz"), None);
+    }
+
+    /// 寫入後讀回的內容與原始 body 完全相同，且多份封存時取最新一份。
+    #[test]
+    fn write_then_read_latest_roundtrip() {
+        let root = temp_archive_dir("roundtrip");
+        let key = ArchiveKey::new("twse", "quote", "20260430");
+        let dir = key.dir(&root);
+
+        assert_eq!(read_latest_from_dir(&dir).expect("讀取空目錄"), None);
+
+        write_to_dir(&dir, at(30, 15), br#"{"stat":"old"}"#).expect("寫入舊封存");
+        write_to_dir(&dir, at(30, 18), br#"{"stat":"OK"}"#).expect("寫入新封存");
+
+        let body = read_latest_from_dir(&dir)
+            .expect("讀取封存")
+            .expect("應有封存");
+        assert_eq!(body, br#"{"stat":"OK"}"#);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    /// 回應不是合法 JSON 時解析失敗，但原始 body 仍已封存，修正解析規則後可重跑。
+    #[tokio::test]
+    async fn fetch_json_archives_malformed_payload() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind 測試伺服器");
+        let url = format!("http://{}/quote", listener.local_addr().expect("取得位址"));
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.expect("接受連線");
+            let mut request = [0u8; 1024];
+            let _ = socket.read(&mut request).await;
+            let body = r#"{"stat":"OK","data":[["2330",}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+        });
+
+        let root = temp_archive_dir("malformed");
+        let key = ArchiveKey::new("twse", "quote", "20260430");
+        let parsed = fetch_json_in::<serde_json::Value>(Some(root.clone()), &url, key).await;
+
+        assert!(parsed.is_err(), "不合法的 JSON 應解析失敗");
+        let archived = read_latest_from_dir(&key.dir(&root))
+            .expect("讀取封存")
+            .expect("解析失敗的回應仍應封存");
+        assert_eq!(archived, br#"{"stat":"OK","data":[["2330",}"#);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    /// 只刪除超過保留天數的檔案，刪空的參數目錄一併移除。
+    #[test]
+    fn purge_removes_only_expired_files() {
+        let root = temp_archive_dir("purge");
+        let old_dir = ArchiveKey::new("twse", "quote", "20260401").dir(&root);
+        let new_dir = ArchiveKey::new("twse", "quote", "20260429").dir(&root);
+        write_to_dir(&old_dir, at(1, 15), b"old").expect("寫入過期封存");
+        write_to_dir(&new_dir, at(29, 15), b"new").expect("寫入有效封存");
+
+        let removed = purge_expired_in(&root, at(30, 16), 7).expect("清除過期封存");
+
+        assert_eq!(removed, 1);
+        assert!(!old_dir.exists(), "刪空的參數目錄應被移除");
+        assert!(
+            read_latest_from_dir(&new_dir)
+                .expect("讀取有效封存")
+                .is_some()
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
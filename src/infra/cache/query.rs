@@ -129,12 +129,14 @@ impl Share {
     /// 更新最後交易日報價快取中的既有股票收盤價。
     ///
     /// 僅更新已存在於快取中的股票（date + closing_price），不新增資料。
+    /// 報價日期早於快取中的日期時不更新，重跑或回補歷史日期不會蓋掉最新收盤價。
     pub async fn set_stock_last_price(
         &self,
         daily_quote: &crate::domain::quote::entity::DailyQuote,
     ) {
         if let Ok(mut last_trading_day_quotes) = self.last_trading_day_quotes.write()
             && let Some(quote) = last_trading_day_quotes.get_mut(&daily_quote.stock_symbol)
+            && daily_quote.date >= quote.date
         {
             quote.date = daily_quote.date;
             quote.closing_price = daily_quote.closing_price;
@@ -195,6 +197,44 @@ mod tests {
         assert_eq!(share.get_current_ip(), Some("203.0.113.1".to_string()));
     }
 
+    #[tokio::test]
+    async fn set_stock_last_price_ignores_older_dates() {
+        use crate::domain::quote::entity::DailyQuote;
+        use crate::infra::database::table::quote::last_daily_quotes::LastDailyQuotes;
+        use chrono::NaiveDate;
+        use rust_decimal::Decimal;
+
+        let share = Share::new();
+        let latest = NaiveDate::from_ymd_opt(2025, 6, 2).unwrap();
+        share.last_trading_day_quotes.write().unwrap().insert(
+            "2330".to_string(),
+            LastDailyQuotes {
+                date: latest,
+                closing_price: Decimal::from(1000),
+                stock_symbol: "2330".to_string(),
+            },
+        );
+        let quote = |date, closing_price: i64| DailyQuote {
+            stock_symbol: "2330".to_string(),
+            date,
+            closing_price: Decimal::from(closing_price),
+            ..Default::default()
+        };
+
+        share
+            .set_stock_last_price(&quote(NaiveDate::from_ymd_opt(2025, 5, 30).unwrap(), 900))
+            .await;
+        let cached = share.get_stock_last_price("2330").await.unwrap();
+        assert_eq!(
+            (cached.date, cached.closing_price),
+            (latest, Decimal::from(1000))
+        );
+
+        share.set_stock_last_price(&quote(latest, 1010)).await;
+        let cached = share.get_stock_last_price("2330").await.unwrap();
+        assert_eq!(cached.closing_price, Decimal::from(1010));
+    }
+
     #[tokio::test]
     async fn test_set_and_get_stock_index_round_trips() {
         use crate::domain::market_index::MarketIndex;
@@ -0,0 +1,156 @@
+//! # MOPS 重大訊息採集
+//!
+//! 公開資訊觀測站的「每日重大訊息」由證交所與櫃買中心以 OpenAPI 轉載：
+//! - 上市：`openapi.twse.com.tw/v1/opendata/t187ap04_L`
+//! - 上櫃：`www.tpex.org.tw/openapi/v1/mopsfe_t187ap04_O`
+//!
+//! 兩者內容皆為當日截至目前的全部重大訊息，排程於盤中反覆輪詢，
+//! 去重交給資料庫的唯一鍵處理。兩個來源的欄位名稱不完全一致（證交所的
+//! 「主旨」欄位名稱帶有尾端空白），因此以鍵名正規化後的候選清單取值。
+
+use anyhow::Result;
+use chrono::{NaiveDateTime, NaiveTime};
+use serde_json::{Map, Value};
+
+use crate::{
+    core::util,
+    infra::crawler::{share::MaterialInformationDto, tpex, twse},
+};
+
+/// 單筆原始資料：鍵為來源欄位名稱。
+type RawItem = Map<String, Value>;
+
+/// 各欄位可能的來源鍵名（已去除前後空白）。
+const KEY_SYMBOL: [&str; 2] = ["公司代號", "SecuritiesCompanyCode"];
+const KEY_NAME: [&str; 2] = ["公司名稱", "CompanyName"];
+const KEY_DATE: [&str; 2] = ["發言日期", "AnnouncementDate"];
+const KEY_TIME: [&str; 2] = ["發言時間", "AnnouncementTime"];
+const KEY_SUBJECT: [&str; 2] = ["主旨", "Subject"];
+const KEY_CLAUSE: [&str; 2] = ["符合條款", "Clause"];
+const KEY_FACT_DATE: [&str; 2] = ["事實發生日", "FactDate"];
+const KEY_DESCRIPTION: [&str; 2] = ["說明", "Description"];
+
+/// 取得上市與上櫃公司當日的重大訊息。
+///
+/// 任一來源失敗只記 warning，另一個來源的資料仍會回傳，避免單邊故障
+/// 讓整輪輪詢都沒有通知。
+pub async fn visit() -> Result<Vec<MaterialInformationDto>> {
+    let listed_url = format!("https://openapi.{}/v1/opendata/t187ap04_L", twse::HOST);
+    let otc_url = format!("https://{}/openapi/v1/mopsfe_t187ap04_O", tpex::HOST);
+    let (listed, otc) = tokio::join!(
+        util::http::get_json::<Vec<RawItem>>(&listed_url),
+        util::http::get_json::<Vec<RawItem>>(&otc_url)
+    );
+
+    let mut result = Vec::new();
+    for (market, items) in [("上市", listed), ("上櫃", otc)] {
+        match items {
+            Ok(items) => result.extend(map_items(items)),
+            Err(why) => tracing::warn!("取得{market}重大訊息失敗: {why:#}"),
+        }
+    }
+
+    Ok(result)
+}
+
+/// 依候選鍵名取字串欄位；鍵名比對前先去除前後空白。
+fn field<'a>(item: &'a RawItem, keys: &[&str]) -> Option<&'a str> {
+    item.iter()
+        .find(|(key, _)| keys.contains(&key.trim()))
+        .and_then(|(_, value)| value.as_str())
+        .map(str::trim)
+}
+
+/// 解析發言時間；來源為 `HHMMSS`，少數資料為 `HH:MM:SS` 或省略前導零。
+fn parse_time(raw: &str) -> Option<NaiveTime> {
+    let digits: String = raw.chars().filter(char::is_ascii_digit).collect();
+    let padded = format!("{digits:0>6}");
+    NaiveTime::parse_from_str(&padded, "%H%M%S").ok()
+}
+
+/// 將原始資料整理成 [`MaterialInformationDto`]，缺少代號、主旨或發言日期的資料略過。
+fn map_items(items: Vec<RawItem>) -> Vec<MaterialInformationDto> {
+    let mut result = Vec::with_capacity(items.len());
+
+    for item in &items {
+        let (Some(stock_symbol), Some(subject), Some(date)) = (
+            field(item, &KEY_SYMBOL).filter(|s| !s.is_empty()),
+            field(item, &KEY_SUBJECT).filter(|s| !s.is_empty()),
+            field(item, &KEY_DATE).and_then(util::datetime::parse_taiwan_date_short),
+        ) else {
+            tracing::warn!("略過欄位不完整的重大訊息 {item:?}");
+            continue;
+        };
+        let time = field(item, &KEY_TIME)
+            .and_then(parse_time)
+            .unwrap_or(NaiveTime::MIN);
+
+        result.push(MaterialInformationDto {
+            stock_symbol: stock_symbol.to_string(),
+            company_name: field(item, &KEY_NAME).unwrap_or_default().to_string(),
+            announced_at: NaiveDateTime::new(date, time),
+            subject: subject.to_string(),
+            clause: field(item, &KEY_CLAUSE).unwrap_or_default().to_string(),
+            fact_date: field(item, &KEY_FACT_DATE)
+                .and_then(util::datetime::parse_taiwan_date_short),
+            description: field(item, &KEY_DESCRIPTION)
+                .unwrap_or_default()
+                .to_string(),
+        });
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    /// 驗證鍵名尾端空白、民國日期與發言時間的轉換，並略過欄位不完整的資料。
+    #[test]
+    fn map_items_normalizes_keys_and_dates() {
+        let items: Vec<RawItem> = serde_json::from_str(
+            r#"[
+                {"出表日期":"1150424","發言日期":"1150424","發言時間":"93015","公司代號":"2330","公司名稱":"台積電","主旨 ":"公告本公司董事會決議辦理減資","符合條款":"第11款","事實發生日":"1150424","說明":"1.減資比率：10%"},
+                {"發言日期":"1150424","發言時間":"17:30:12","公司代號":"6488","公司名稱":"環球晶","主旨":"代子公司公告取得資產","符合條款":"第20款","事實發生日":"","說明":""},
+                {"發言日期":"1150424","公司代號":"1101","主旨 ":""}
+            ]"#,
+        )
+        .unwrap();
+
+        let rows = map_items(items);
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].stock_symbol, "2330");
+        assert_eq!(
+            rows[0].announced_at,
+            NaiveDate::from_ymd_opt(2026, 4, 24)
+                .unwrap()
+                .and_hms_opt(9, 30, 15)
+                .unwrap()
+        );
+        assert_eq!(rows[0].subject, "公告本公司董事會決議辦理減資");
+        assert_eq!(rows[0].clause, "第11款");
+        assert_eq!(rows[0].fact_date, NaiveDate::from_ymd_opt(2026, 4, 24));
+        assert_eq!(
+            rows[1].announced_at.time(),
+            NaiveTime::from_hms_opt(17, 30, 12).unwrap()
+        );
+        assert_eq!(rows[1].fact_date, None);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_visit() {
+        dotenvy::dotenv().ok();
+        match visit().await {
+            Ok(result) => {
+                tracing::debug!("result: {}", result.len());
+            }
+            Err(why) => {
+                tracing::debug!("Failed to visit because {:?}", why);
+            }
+        }
+    }
+}
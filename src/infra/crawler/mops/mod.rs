@@ -1,8 +1,10 @@
 //! 公開資訊觀測站（MOPS）/ 財務比較 E 點通。
 //!
-//! 目前此模組提供年度財報採集器，但尚未接入正式補齊流程。
+//! 提供年度財報採集器（尚未接入正式補齊流程）、每日重大訊息採集器與季報 XBRL 採集器。
 
 pub mod annual_profit;
+pub mod material_information;
+pub mod xbrl;
 
 /// MOPS 財務比較 E 點通主機。
 pub const HOST: &str = "mopsfin.twse.com.tw";
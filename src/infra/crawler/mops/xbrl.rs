@@ -0,0 +1,487 @@
+//! # MOPS XBRL 季報採集
+//!
+//! 公開資訊觀測站將每季全體上市櫃公司申報的 XBRL instance 打包成單一壓縮檔
+//! （`tifrs-{年}Q{季}.zip`），檔內每家公司一份 instance，檔名格式為
+//! `tifrs-fr1-m1-{產業別}-{cr|ir}-{代號}-{年}Q{季}.xml`：
+//! - 產業別：`ci` 一般業、`basi` 銀行、`bd` 證券、`fh` 金控、`ins` 保險、`mim` 異業
+//! - `cr` 為合併報表、`ir` 為個體報表；兩者都有時只取合併報表
+//!
+//! 此模組只負責下載與解析 instance：保留「無維度、期間截止於本季季末」的數值
+//! 事實（fact），去掉比較期與權益變動表等依維度拆分的明細。科目與 IFRS 分類
+//! 標準元素的對應交由防腐層處理。
+//!
+//! instance 結構單純（context 與 fact 皆為扁平元素、數值事實不含子元素），
+//! 因此以正規表示式擷取，不引入完整的 XML 解析器。
+
+use std::{
+    collections::{HashMap, HashSet},
+    io::{Cursor, Read},
+    str::FromStr,
+};
+
+use anyhow::{Context, Result, anyhow};
+use chrono::NaiveDate;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use rust_decimal::Decimal;
+use zip::ZipArchive;
+
+use crate::core::{declare::Quarter, util::datetime::ReportQuarter};
+
+/// 季報 XBRL 壓縮檔的下載主機。
+pub const DOWNLOAD_HOST: &str = "mops.twse.com.tw";
+
+/// 全市場單季壓縮檔的大小上限；實際約一至兩百 MiB，保留餘裕。
+const MAX_ARCHIVE_BYTES: usize = 512 * 1024 * 1024;
+
+static REG_INSTANCE_NAME: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"tifrs-fr\d+-m\d+-([a-z]+)-(cr|ir)-([0-9A-Z]+)-(\d{4})Q([1-4])\.xml$")
+        .expect("Failed to compile XBRL instance name regex")
+});
+
+static REG_CONTEXT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?s)<(?:xbrli:)?context\b[^>]*\bid="([^"]+)"[^>]*>(.*?)</(?:xbrli:)?context>"#)
+        .expect("Failed to compile XBRL context regex")
+});
+
+static REG_INSTANT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"<(?:xbrli:)?instant>\s*(\d{4}-\d{2}-\d{2})\s*<")
+        .expect("Failed to compile XBRL instant regex")
+});
+
+static REG_DURATION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?s)<(?:xbrli:)?startDate>\s*(\d{4}-\d{2}-\d{2})\s*<.*?<(?:xbrli:)?endDate>\s*(\d{4}-\d{2}-\d{2})\s*<",
+    )
+    .expect("Failed to compile XBRL duration regex")
+});
+
+static REG_FACT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"<([\w.-]+:[\w.-]+)\s([^>]*\bcontextRef="([^"]+)"[^>]*)>([^<]*)"#)
+        .expect("Failed to compile XBRL fact regex")
+});
+
+/// XBRL context 的期間。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum XbrlPeriod {
+    /// 時點（資產負債表）。
+    Instant(NaiveDate),
+    /// 區間（損益表、現金流量表），起訖皆含。
+    Duration {
+        /// 起始日。
+        start: NaiveDate,
+        /// 截止日。
+        end: NaiveDate,
+    },
+}
+
+impl XbrlPeriod {
+    /// 時點或區間的截止日。
+    pub fn end(&self) -> NaiveDate {
+        match self {
+            XbrlPeriod::Instant(date) => *date,
+            XbrlPeriod::Duration { end, .. } => *end,
+        }
+    }
+}
+
+/// 單一數值事實。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XbrlFact {
+    /// 含前綴的元素名稱，例如 `ifrs-full:Revenue`。
+    pub element: String,
+    /// 事實的期間。
+    pub period: XbrlPeriod,
+    /// 申報數值（元，未乘 `decimals` 縮放）。
+    pub value: Decimal,
+}
+
+impl XbrlFact {
+    /// 不含前綴的元素名稱，例如 `Revenue`。
+    pub fn local_name(&self) -> &str {
+        self.element
+            .split_once(':')
+            .map_or(self.element.as_str(), |(_, name)| name)
+    }
+}
+
+/// 單一公司單季的 XBRL 申報。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XbrlFiling {
+    /// 股票代號。
+    pub security_code: String,
+    /// 財報年度。
+    pub year: i32,
+    /// 財報季別。
+    pub quarter: Quarter,
+    /// 是否為合併報表。
+    pub consolidated: bool,
+    /// 本季季末（含）截止、無維度的數值事實。
+    pub facts: Vec<XbrlFact>,
+}
+
+/// 壓縮檔內 instance 檔名解析結果。
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct InstanceName {
+    security_code: String,
+    year: i32,
+    quarter: Quarter,
+    consolidated: bool,
+}
+
+/// 已下載的全市場單季壓縮檔。
+pub struct XbrlArchive {
+    archive: ZipArchive<Cursor<Vec<u8>>>,
+    entries: Vec<(usize, InstanceName)>,
+}
+
+impl XbrlArchive {
+    /// 開啟壓縮檔並挑出每家公司要解析的 instance（合併報表優先）。
+    pub fn open(bytes: Vec<u8>) -> Result<Self> {
+        let mut archive =
+            ZipArchive::new(Cursor::new(bytes)).context("Failed to open MOPS XBRL archive")?;
+
+        let mut chosen: HashMap<String, (usize, InstanceName)> = HashMap::new();
+        for index in 0..archive.len() {
+            let file = archive
+                .by_index(index)
+                .context("Failed to read MOPS XBRL archive entry")?;
+            let Some(name) = parse_instance_name(file.name()) else {
+                continue;
+            };
+            match chosen.get(&name.security_code) {
+                Some((_, existing)) if existing.consolidated || !name.consolidated => {}
+                _ => {
+                    chosen.insert(name.security_code.clone(), (index, name));
+                }
+            }
+        }
+
+        let mut entries: Vec<(usize, InstanceName)> = chosen.into_values().collect();
+        entries.sort_by(|a, b| a.1.security_code.cmp(&b.1.security_code));
+        Ok(XbrlArchive { archive, entries })
+    }
+
+    /// 壓縮檔內可解析的公司數。
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 壓縮檔內沒有任何可解析的 instance。
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 依序解析每家公司的申報；單一檔案損毀時回傳該筆錯誤，不影響其他公司。
+    pub fn filings(&mut self) -> impl Iterator<Item = Result<XbrlFiling>> + '_ {
+        let archive = &mut self.archive;
+        self.entries.iter().map(move |(index, name)| {
+            let mut file = archive
+                .by_index(*index)
+                .with_context(|| format!("Failed to read XBRL of {}", name.security_code))?;
+            let mut raw = Vec::with_capacity(file.size() as usize);
+            file.read_to_end(&mut raw)
+                .with_context(|| format!("Failed to unzip XBRL of {}", name.security_code))?;
+            let xml = String::from_utf8_lossy(&raw);
+
+            Ok(XbrlFiling {
+                security_code: name.security_code.clone(),
+                year: name.year,
+                quarter: name.quarter,
+                consolidated: name.consolidated,
+                facts: parse_instance(&xml, quarter_end(name.year, name.quarter))?,
+            })
+        })
+    }
+}
+
+/// 全市場單季壓縮檔的下載網址。
+pub fn archive_url(year: i32, quarter: Quarter) -> String {
+    format!(
+        "https://{}/server-java/FileDownLoad?step=9&fileName=tifrs-{}{}.zip&filePath=/home/html/nas/ifrs/{}/",
+        DOWNLOAD_HOST, year, quarter, year
+    )
+}
+
+/// 下載指定季度的全市場 XBRL 壓縮檔。
+pub async fn visit(year: i32, quarter: Quarter) -> Result<XbrlArchive> {
+    let url = archive_url(year, quarter);
+    let bytes = crate::core::util::http::get_bytes(&url, MAX_ARCHIVE_BYTES).await?;
+    // 尚未開放下載的季度會回傳 HTML 說明頁而非 zip。
+    if !bytes.starts_with(b"PK") {
+        return Err(anyhow!(
+            "Failed to download MOPS XBRL archive {}{} because response is not a zip",
+            year,
+            quarter
+        ));
+    }
+
+    XbrlArchive::open(bytes)
+}
+
+/// 季別最後一天。
+pub fn quarter_end(year: i32, quarter: Quarter) -> NaiveDate {
+    ReportQuarter::new(year, quarter).end_date()
+}
+
+/// 解析壓縮檔內的 instance 檔名；非 instance 檔（如 schema、linkbase）回傳 `None`。
+fn parse_instance_name(path: &str) -> Option<InstanceName> {
+    let captures = REG_INSTANCE_NAME.captures(path)?;
+    Some(InstanceName {
+        consolidated: &captures[2] == "cr",
+        security_code: captures[3].to_string(),
+        year: captures[4].parse().ok()?,
+        quarter: Quarter::from_str(&format!("Q{}", &captures[5])).ok()?,
+    })
+}
+
+/// 解析 instance，只保留期間截止於 `period_end`、沒有維度的數值事實。
+///
+/// 同一元素與期間重複出現時保留第一筆。
+pub fn parse_instance(xml: &str, period_end: NaiveDate) -> Result<Vec<XbrlFact>> {
+    let mut contexts: HashMap<&str, XbrlPeriod> = HashMap::new();
+    for captures in REG_CONTEXT.captures_iter(xml) {
+        let body = captures.get(2).map_or("", |body| body.as_str());
+        // 依維度（segment/scenario）拆分的明細不屬於報表主體科目。
+        if body.contains("segment>") || body.contains("scenario>") {
+            continue;
+        }
+        let period = if let Some(instant) = REG_INSTANT.captures(body) {
+            XbrlPeriod::Instant(parse_date(&instant[1])?)
+        } else if let Some(duration) = REG_DURATION.captures(body) {
+            XbrlPeriod::Duration {
+                start: parse_date(&duration[1])?,
+                end: parse_date(&duration[2])?,
+            }
+        } else {
+            continue;
+        };
+        if period.end() == period_end
+            && let Some(id) = captures.get(1)
+        {
+            contexts.insert(id.as_str(), period);
+        }
+    }
+
+    if contexts.is_empty() {
+        return Err(anyhow!(
+            "Failed to parse XBRL instance because no context ends at {}",
+            period_end
+        ));
+    }
+
+    let mut facts: Vec<XbrlFact> = Vec::new();
+    let mut seen: HashSet<(&str, XbrlPeriod)> = HashSet::new();
+    for captures in REG_FACT.captures_iter(xml) {
+        let Some(period) = contexts.get(&captures[3]) else {
+            continue;
+        };
+        if captures[2].contains("xsi:nil=\"true\"") {
+            continue;
+        }
+        let Some(value) = parse_value(&captures[4]) else {
+            continue;
+        };
+        let element = captures.get(1).map_or("", |element| element.as_str());
+        if !seen.insert((element, *period)) {
+            continue;
+        }
+        facts.push(XbrlFact {
+            element: element.to_string(),
+            period: *period,
+            value,
+        });
+    }
+
+    Ok(facts)
+}
+
+fn parse_date(raw: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .with_context(|| format!("Failed to parse XBRL date '{}'", raw))
+}
+
+/// 數值事實的內容；文字區塊等非數值內容回傳 `None`。
+fn parse_value(raw: &str) -> Option<Decimal> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return None;
+    }
+    Decimal::from_str(raw)
+        .or_else(|_| Decimal::from_scientific(raw))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use rust_decimal_macros::dec;
+    use zip::{ZipWriter, write::SimpleFileOptions};
+
+    use super::*;
+
+    // include_str! 的路徑相對於本檔案（mops/xbrl.rs）→ mops/testdata/。
+    const FIXTURE: &str = include_str!("testdata/tifrs_instance.xml");
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn find<'a>(facts: &'a [XbrlFact], element: &str, period: XbrlPeriod) -> Option<&'a XbrlFact> {
+        facts
+            .iter()
+            .find(|fact| fact.element == element && fact.period == period)
+    }
+
+    #[test]
+    fn test_parse_instance_keeps_current_period_facts() {
+        let facts = parse_instance(FIXTURE, date(2024, 6, 30)).unwrap();
+        let instant = XbrlPeriod::Instant(date(2024, 6, 30));
+        let quarter = XbrlPeriod::Duration {
+            start: date(2024, 4, 1),
+            end: date(2024, 6, 30),
+        };
+        let ytd = XbrlPeriod::Duration {
+            start: date(2024, 1, 1),
+            end: date(2024, 6, 30),
+        };
+
+        assert_eq!(
+            find(&facts, "ifrs-full:Assets", instant).unwrap().value,
+            dec!(6165648490000)
+        );
+        // 權益變動表的維度明細不應覆蓋權益總計。
+        assert_eq!(
+            find(&facts, "ifrs-full:Equity", instant).unwrap().value,
+            dec!(3941010530000)
+        );
+        assert_eq!(
+            find(&facts, "ifrs-full:Revenue", quarter).unwrap().value,
+            dec!(673510177000)
+        );
+        assert_eq!(
+            find(&facts, "ifrs-full:Revenue", ytd).unwrap().value,
+            dec!(1266003608000)
+        );
+        assert_eq!(
+            find(&facts, "ifrs-full:BasicEarningsLossPerShare", quarter)
+                .unwrap()
+                .value,
+            dec!(9.56)
+        );
+        assert_eq!(
+            find(
+                &facts,
+                "tifrs-SCF:AcquisitionOfPropertyPlantAndEquipment",
+                ytd
+            )
+            .unwrap()
+            .value,
+            dec!(-459281044000)
+        );
+        assert_eq!(
+            find(
+                &facts,
+                "tifrs-SCF:AcquisitionOfPropertyPlantAndEquipment",
+                ytd
+            )
+            .unwrap()
+            .local_name(),
+            "AcquisitionOfPropertyPlantAndEquipment"
+        );
+    }
+
+    #[test]
+    fn test_parse_instance_skips_comparative_nil_and_text_facts() {
+        let facts = parse_instance(FIXTURE, date(2024, 6, 30)).unwrap();
+        assert!(
+            facts
+                .iter()
+                .all(|fact| fact.period.end() == date(2024, 6, 30))
+        );
+        assert!(
+            !facts
+                .iter()
+                .any(|fact| fact.element == "ifrs-full:FinanceCosts")
+        );
+        assert!(!facts.iter().any(|fact| fact.element.ends_with("TextBlock")));
+        assert_eq!(
+            facts
+                .iter()
+                .filter(|fact| fact.element == "ifrs-full:Equity")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_parse_instance_rejects_wrong_quarter() {
+        assert!(parse_instance(FIXTURE, date(2024, 3, 31)).is_err());
+    }
+
+    #[test]
+    fn test_parse_instance_name() {
+        assert_eq!(
+            parse_instance_name("tifrs-fr1-m1-ci-cr-2330-2024Q2.xml"),
+            Some(InstanceName {
+                security_code: "2330".to_string(),
+                year: 2024,
+                quarter: Quarter::Q2,
+                consolidated: true,
+            })
+        );
+        assert_eq!(
+            parse_instance_name("2024Q2/tifrs-fr1-m1-fh-ir-2881-2024Q2.xml")
+                .map(|name| name.consolidated),
+            Some(false)
+        );
+        assert_eq!(
+            parse_instance_name("tifrs-fr1-m1-ci-cr-2330-2024Q2.xsd"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_archive_prefers_consolidated_instance() {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default();
+        for name in [
+            "tifrs-fr1-m1-ci-ir-2330-2024Q2.xml",
+            "tifrs-fr1-m1-ci-cr-2330-2024Q2.xml",
+            "tifrs-fr1-m1-ci-ir-1101-2024Q2.xml",
+            "readme.txt",
+        ] {
+            writer.start_file(name, options).unwrap();
+            writer.write_all(FIXTURE.as_bytes()).unwrap();
+        }
+        let bytes = writer.finish().unwrap().into_inner();
+
+        let mut archive = XbrlArchive::open(bytes).unwrap();
+        assert_eq!(archive.len(), 2);
+        let filings: Vec<XbrlFiling> = archive.filings().map(|filing| filing.unwrap()).collect();
+        assert_eq!(filings[0].security_code, "1101");
+        assert!(!filings[0].consolidated);
+        assert_eq!(filings[1].security_code, "2330");
+        assert!(filings[1].consolidated);
+        assert!(!filings[1].facts.is_empty());
+    }
+
+    #[test]
+    fn test_archive_url() {
+        assert_eq!(
+            archive_url(2024, Quarter::Q2),
+            "https://mops.twse.com.tw/server-java/FileDownLoad?step=9&fileName=tifrs-2024Q2.zip&filePath=/home/html/nas/ifrs/2024/"
+        );
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_visit() {
+        dotenvy::dotenv().ok();
+        match visit(2024, Quarter::Q2).await {
+            Ok(archive) => tracing::debug!("companies: {}", archive.len()),
+            Err(why) => tracing::debug!("Failed to visit because {:?}", why),
+        }
+    }
+}
@@ -293,6 +293,441 @@ pub struct QfiiDto {
     pub share_holding_percentage: Decimal,
 }
 
+/// 三大法人個股買賣超爬蟲載體 (DTO)。
+///
+/// TWSE T86 與 TPEx 三大法人日報拆欄方式不同（例如 TPEx 另附外資合計、
+/// TWSE 的自營商只有自行買賣與避險兩組買賣股數），由各爬蟲先整理成相同口徑。
+/// 所有數量單位皆為「股」，賣超為負數。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstitutionalTradeDto {
+    /// 證券代號
+    pub stock_symbol: String,
+    /// 交易日期
+    pub date: NaiveDate,
+    /// 外陸資買進股數（不含外資自營商）
+    pub foreign_buy: i64,
+    /// 外陸資賣出股數（不含外資自營商）
+    pub foreign_sell: i64,
+    /// 外陸資買賣超股數（不含外資自營商）
+    pub foreign_net: i64,
+    /// 外資自營商買賣超股數
+    pub foreign_dealer_net: i64,
+    /// 投信買進股數
+    pub investment_trust_buy: i64,
+    /// 投信賣出股數
+    pub investment_trust_sell: i64,
+    /// 投信買賣超股數
+    pub investment_trust_net: i64,
+    /// 自營商買進股數（自行買賣＋避險）
+    pub dealer_buy: i64,
+    /// 自營商賣出股數（自行買賣＋避險）
+    pub dealer_sell: i64,
+    /// 自營商買賣超股數（自行買賣＋避險）
+    pub dealer_net: i64,
+    /// 自營商買賣超股數（自行買賣）
+    pub dealer_proprietary_net: i64,
+    /// 自營商買賣超股數（避險）
+    pub dealer_hedge_net: i64,
+    /// 三大法人買賣超股數合計
+    pub total_net: i64,
+}
+
+/// 個股融資融券彙總爬蟲載體 (DTO)。
+///
+/// TWSE 與 TPEx 的欄位順序不同，由各爬蟲整理成相同口徑；數量單位皆為「張」。
+/// 融券的「買進」是回補、「賣出」是放空，與融資方向相反。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarginTradingDto {
+    /// 證券代號
+    pub stock_symbol: String,
+    /// 交易日期
+    pub date: NaiveDate,
+    /// 融資買進
+    pub margin_buy: i64,
+    /// 融資賣出
+    pub margin_sell: i64,
+    /// 融資現金償還
+    pub margin_redemption: i64,
+    /// 融資前日餘額
+    pub margin_previous_balance: i64,
+    /// 融資今日餘額
+    pub margin_balance: i64,
+    /// 融資限額
+    pub margin_quota: i64,
+    /// 融券賣出（放空）
+    pub short_sell: i64,
+    /// 融券買進（回補）
+    pub short_cover: i64,
+    /// 融券現券償還
+    pub short_redemption: i64,
+    /// 融券前日餘額
+    pub short_previous_balance: i64,
+    /// 融券今日餘額
+    pub short_balance: i64,
+    /// 融券限額
+    pub short_quota: i64,
+    /// 資券互抵
+    pub offset: i64,
+}
+
+/// 集保戶股權分散表單一持股分級爬蟲載體 (DTO)。
+///
+/// TDCC 每檔證券每週 17 列：分級 1–15 為持股區間，16 為差異數調整，17 為合計。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShareholdingTierDto {
+    /// 證券代號
+    pub stock_symbol: String,
+    /// 資料日期（每週最後一個營業日）
+    pub date: NaiveDate,
+    /// 持股分級（1–17）
+    pub level: i32,
+    /// 人數
+    pub holders: i64,
+    /// 股數
+    pub shares: i64,
+    /// 占集保庫存數比例（%）
+    pub percentage: Decimal,
+}
+
+/// 期交所期貨每日行情單一契約月份爬蟲載體 (DTO)。
+///
+/// 僅保留一般交易時段的單月契約，價差組合與盤後時段已在爬蟲端濾除。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuturesDailyQuoteDto {
+    /// 交易日期
+    pub date: NaiveDate,
+    /// 契約代號（如 `TX`、`MTX`）
+    pub contract: String,
+    /// 到期月份（`YYYYMM`，週契約為 `YYYYMMW1` 形式）
+    pub contract_month: String,
+    /// 開盤價；無成交時為 `None`
+    pub open: Option<Decimal>,
+    /// 最高價
+    pub high: Option<Decimal>,
+    /// 最低價
+    pub low: Option<Decimal>,
+    /// 收盤價
+    pub close: Option<Decimal>,
+    /// 漲跌價
+    pub change: Option<Decimal>,
+    /// 成交量（口）
+    pub volume: i64,
+    /// 結算價
+    pub settlement_price: Option<Decimal>,
+    /// 未沖銷契約數（口）
+    pub open_interest: i64,
+}
+
+/// 期交所三大法人期貨交易與未平倉部位爬蟲載體 (DTO)。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuturesInstitutionalPositionDto {
+    /// 交易日期
+    pub date: NaiveDate,
+    /// 契約代號（如 `TX`、`MTX`），由來源的商品名稱對應而來
+    pub contract: String,
+    /// 身份別原始名稱（自營商、投信、外資及陸資）
+    pub trader_type: String,
+    /// 多方交易口數
+    pub long_trade_volume: i64,
+    /// 空方交易口數
+    pub short_trade_volume: i64,
+    /// 多空交易口數淨額
+    pub net_trade_volume: i64,
+    /// 多方未平倉口數
+    pub long_open_interest: i64,
+    /// 空方未平倉口數
+    pub short_open_interest: i64,
+    /// 多空未平倉口數淨額
+    pub net_open_interest: i64,
+    /// 多空未平倉契約金額淨額（千元）
+    pub net_open_interest_amount: i64,
+}
+
+/// 期交所臺指選擇權賣權買權比爬蟲載體 (DTO)。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PutCallRatioDto {
+    /// 交易日期
+    pub date: NaiveDate,
+    /// 賣權成交量
+    pub put_volume: i64,
+    /// 買權成交量
+    pub call_volume: i64,
+    /// 買賣權成交量比率（%）
+    pub volume_ratio_percent: Decimal,
+    /// 賣權未平倉量
+    pub put_open_interest: i64,
+    /// 買權未平倉量
+    pub call_open_interest: i64,
+    /// 買賣權未平倉量比率（%）
+    pub open_interest_ratio_percent: Decimal,
+}
+
+/// 公開資訊觀測站重大訊息爬蟲載體 (DTO)。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaterialInformationDto {
+    /// 公司代號
+    pub stock_symbol: String,
+    /// 公司名稱
+    pub company_name: String,
+    /// 發言日期與時間（台北時間）
+    pub announced_at: chrono::NaiveDateTime,
+    /// 主旨
+    pub subject: String,
+    /// 符合條款（如「第51款」）
+    pub clause: String,
+    /// 事實發生日；來源未提供或無法解析時為 `None`
+    pub fact_date: Option<NaiveDate>,
+    /// 說明
+    pub description: String,
+}
+
+/// 停止買賣後恢復交易的股本變動種類。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CapitalChangeKind {
+    /// 減資（彌補虧損或退還股款）。
+    CapitalReduction,
+    /// 變更每股面額。
+    ParValueChange,
+}
+
+impl CapitalChangeKind {
+    /// 穩定的代碼，用於封存鍵與建議識別。
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::CapitalReduction => "capital_reduction",
+            Self::ParValueChange => "par_value_change",
+        }
+    }
+
+    /// 中文名稱。
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::CapitalReduction => "減資",
+            Self::ParValueChange => "變更面額",
+        }
+    }
+}
+
+/// 減資／變更面額恢復買賣參考價爬蟲載體 (DTO)。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapitalChangeDto {
+    /// 股票代號
+    pub stock_symbol: String,
+    /// 公司名稱
+    pub company_name: String,
+    /// 股本變動種類
+    pub kind: CapitalChangeKind,
+    /// 恢復買賣日期
+    pub resumed_on: NaiveDate,
+    /// 停止買賣前收盤價格
+    pub last_close: Decimal,
+    /// 恢復買賣參考價
+    pub reference_price: Decimal,
+    /// 減資原因；變更面額或來源未提供時為空字串
+    pub reason: String,
+}
+
+/// 將證交所／櫃買中心「恢復買賣參考價」表格整理成 [`CapitalChangeDto`] 清單。
+///
+/// 兩個來源的表格都帶有欄位名稱，但欄位順序與數量不一致（減資表多了
+/// 「減資原因」），因此以欄位名稱關鍵字定位，不依賴欄位位置。找不到必要欄位
+/// 時整表回傳空陣列；單列解析失敗只記 warning 後略過。
+pub(crate) fn map_capital_change_rows(
+    kind: CapitalChangeKind,
+    fields: &[String],
+    data: &[Vec<serde_json::Value>],
+) -> Vec<CapitalChangeDto> {
+    let column = |keyword: &str| fields.iter().position(|field| field.contains(keyword));
+    let (Some(date), Some(symbol), Some(name), Some(last_close), Some(reference)) = (
+        column("恢復買賣日期"),
+        column("代號"),
+        column("名稱"),
+        column("停止買賣前收盤價"),
+        column("恢復買賣參考價"),
+    ) else {
+        if !data.is_empty() {
+            tracing::warn!("{}恢復買賣表格缺少必要欄位: {fields:?}", kind.label());
+        }
+        return Vec::new();
+    };
+    let reason = column("原因");
+
+    let text = |row: &[serde_json::Value], index: usize| -> String {
+        match row.get(index) {
+            Some(serde_json::Value::String(value)) => value.trim().to_string(),
+            Some(serde_json::Value::Null) | None => String::new(),
+            Some(value) => value.to_string(),
+        }
+    };
+
+    let mut result = Vec::with_capacity(data.len());
+    for row in data {
+        let stock_symbol = text(row, symbol);
+        let raw_date = text(row, date);
+        let Some(resumed_on) = util::datetime::parse_taiwan_date(&raw_date)
+            .or_else(|| util::datetime::parse_taiwan_date_short(&raw_date))
+        else {
+            tracing::warn!("略過恢復買賣日期無法解析的資料列 {stock_symbol}: {raw_date}");
+            continue;
+        };
+        let prices = parse_quote_decimal("last_close", &text(row, last_close)).and_then(|close| {
+            parse_quote_decimal("reference_price", &text(row, reference)).map(|r| (close, r))
+        });
+        let (last_close, reference_price) = match prices {
+            Ok(prices) => prices,
+            Err(why) => {
+                tracing::warn!("略過價格無法解析的恢復買賣資料列 {stock_symbol}: {why}");
+                continue;
+            }
+        };
+        if stock_symbol.is_empty() {
+            continue;
+        }
+
+        result.push(CapitalChangeDto {
+            stock_symbol,
+            company_name: text(row, name),
+            kind,
+            resumed_on,
+            last_close,
+            reference_price,
+            reason: reason.map(|index| text(row, index)).unwrap_or_default(),
+        });
+    }
+
+    result
+}
+
+/// 注意股／處置股公告爬蟲載體 (DTO)。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TradingSurveillanceDto {
+    /// 股票代號
+    pub stock_symbol: String,
+    /// 股票名稱
+    pub name: String,
+    /// 是否為處置股；`false` 為注意股
+    pub is_disposition: bool,
+    /// 公告日期
+    pub announced_on: NaiveDate,
+    /// 起日；注意股與公告日期相同
+    pub start_date: NaiveDate,
+    /// 迄日；注意股與公告日期相同
+    pub end_date: NaiveDate,
+    /// 注意交易資訊或處置條件
+    pub reason: String,
+    /// 處置措施；注意股為空字串
+    pub measure: String,
+}
+
+/// 注意股／處置股各欄位可能的來源鍵名（已去除前後空白）。
+///
+/// 證交所 OpenAPI 使用英文鍵名，櫃買中心的資料集有中文也有英文，一併列入候選。
+const SURVEILLANCE_KEY_SYMBOL: [&str; 4] = ["Code", "SecuritiesCompanyCode", "證券代號", "代號"];
+const SURVEILLANCE_KEY_NAME: [&str; 4] = ["Name", "CompanyName", "證券名稱", "名稱"];
+const SURVEILLANCE_KEY_DATE: [&str; 4] = ["Date", "公布日期", "公告日期", "日期"];
+const SURVEILLANCE_KEY_PERIOD: [&str; 3] = ["DispositionPeriod", "處置起迄時間", "處置期間"];
+const SURVEILLANCE_KEY_REASON: [&str; 7] = [
+    "TradingInfoForAttention",
+    "TradingInformation",
+    "ReasonsOfDisposition",
+    "DispositionReasons",
+    "注意交易資訊",
+    "處置條件",
+    "處置原因",
+];
+const SURVEILLANCE_KEY_MEASURE: [&str; 4] = [
+    "DispositionMeasures",
+    "DisposalCondition",
+    "處置措施",
+    "處置內容",
+];
+
+/// 解析民國日期：接受 `YYY/MM/DD`、`YYY-MM-DD` 與無分隔的 `YYYMMDD`。
+fn parse_roc_date(raw: &str) -> Option<NaiveDate> {
+    let raw = raw.trim();
+    util::datetime::parse_taiwan_date(raw).or_else(|| util::datetime::parse_taiwan_date_short(raw))
+}
+
+/// 解析處置起迄時間，例如 `115/04/21～115/05/05` 或 `1150421~1150505`。
+pub(crate) fn parse_roc_date_range(raw: &str) -> Option<(NaiveDate, NaiveDate)> {
+    let mut parts = raw
+        .split(['～', '~', '至'])
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+    let start = parse_roc_date(parts.next()?)?;
+    let end = parse_roc_date(parts.next()?)?;
+    (start <= end).then_some((start, end))
+}
+
+/// 將證交所／櫃買中心 OpenAPI 的注意股或處置股資料整理成 [`TradingSurveillanceDto`] 清單。
+///
+/// 注意股的起迄日皆為公告日期；處置股取處置起迄時間，無法解析時略過並記 warning。
+pub(crate) fn map_surveillance_items(
+    is_disposition: bool,
+    items: &[serde_json::Map<String, serde_json::Value>],
+) -> Vec<TradingSurveillanceDto> {
+    let field = |item: &serde_json::Map<String, serde_json::Value>, keys: &[&str]| -> String {
+        item.iter()
+            .find(|(key, _)| keys.contains(&key.trim()))
+            .map(|(_, value)| match value {
+                serde_json::Value::String(s) => s.trim().to_string(),
+                serde_json::Value::Null => String::new(),
+                other => other.to_string(),
+            })
+            .unwrap_or_default()
+    };
+
+    let mut result = Vec::with_capacity(items.len());
+    for item in items {
+        let stock_symbol = field(item, &SURVEILLANCE_KEY_SYMBOL);
+        if stock_symbol.is_empty() {
+            continue;
+        }
+        let Some(announced_on) = parse_roc_date(&field(item, &SURVEILLANCE_KEY_DATE)) else {
+            tracing::warn!("略過公告日期無法解析的注意／處置股資料 {stock_symbol}");
+            continue;
+        };
+        let (start_date, end_date) = if is_disposition {
+            let period = field(item, &SURVEILLANCE_KEY_PERIOD);
+            let Some(range) = parse_roc_date_range(&period) else {
+                tracing::warn!("略過處置期間無法解析的處置股資料 {stock_symbol}: {period}");
+                continue;
+            };
+            range
+        } else {
+            (announced_on, announced_on)
+        };
+
+        result.push(TradingSurveillanceDto {
+            stock_symbol,
+            name: field(item, &SURVEILLANCE_KEY_NAME),
+            is_disposition,
+            announced_on,
+            start_date,
+            end_date,
+            reason: field(item, &SURVEILLANCE_KEY_REASON),
+            measure: if is_disposition {
+                field(item, &SURVEILLANCE_KEY_MEASURE)
+            } else {
+                String::new()
+            },
+        });
+    }
+
+    result
+}
+
+/// 解析法人買賣股數、融資融券張數等整數欄位。
+///
+/// 來源以含千分位逗號的字串表示（如 `"-1,234,000"`）；空白或 `--` 視為 0。
+/// 其餘無法解析的內容回傳錯誤，讓呼叫端拒絕整列而不是默默寫入 0。
+pub(crate) fn parse_shares(raw: &str) -> Result<i64> {
+    if is_no_data_placeholder(raw) {
+        return Ok(0);
+    }
+    text::parse_i64(raw.trim(), None)
+}
+
 /// 營收資訊爬蟲載體 (DTO)。
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RevenueDto {
@@ -1100,4 +1535,118 @@ mod tests {
 
         assert!(matches!(err, QuoteParseError::MissingField { .. }));
     }
+
+    /// 驗證恢復買賣表格依欄位名稱定位，並接受兩種民國日期格式。
+    #[test]
+    fn map_capital_change_rows_locates_columns_by_name() {
+        let fields: Vec<String> = [
+            "恢復買賣日期",
+            "股票代號",
+            "名稱",
+            "停止買賣前收盤價格",
+            "恢復買賣參考價",
+            "漲停價格",
+            "跌停價格",
+            "減資原因",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let row = |date: &str, symbol: &str, close: &str| -> Vec<serde_json::Value> {
+            [
+                date,
+                symbol,
+                "測試",
+                close,
+                "50.00",
+                "55.00",
+                "45.00",
+                "彌補虧損",
+            ]
+            .iter()
+            .map(|s| serde_json::Value::String(s.to_string()))
+            .collect()
+        };
+        let data = vec![
+            row("115/05/12", "2024", "30.00"),
+            row("1150513", "6109", "1,200.00"),
+            row("尚未公布", "1101", "30.00"),
+        ];
+
+        let result = map_capital_change_rows(CapitalChangeKind::CapitalReduction, &fields, &data);
+
+        assert_eq!(result.len(), 2, "日期無法解析的列應略過");
+        assert_eq!(result[0].stock_symbol, "2024");
+        assert_eq!(
+            result[0].resumed_on,
+            NaiveDate::from_ymd_opt(2026, 5, 12).unwrap()
+        );
+        assert_eq!(result[0].last_close, dec!(30.00));
+        assert_eq!(result[0].reference_price, dec!(50.00));
+        assert_eq!(result[0].reason, "彌補虧損");
+        assert_eq!(
+            result[1].resumed_on,
+            NaiveDate::from_ymd_opt(2026, 5, 13).unwrap()
+        );
+        assert_eq!(result[1].last_close, dec!(1200.00));
+    }
+
+    /// 驗證缺少必要欄位時整表回傳空陣列，而不是錯位解析。
+    #[test]
+    fn map_capital_change_rows_requires_known_columns() {
+        let fields = vec!["代號".to_string(), "名稱".to_string()];
+        let data = vec![vec![serde_json::Value::String("2024".to_string())]];
+
+        assert!(
+            map_capital_change_rows(CapitalChangeKind::ParValueChange, &fields, &data).is_empty()
+        );
+    }
+
+    /// 驗證處置起迄時間接受全形與半形波浪號，並拒絕顛倒的區間。
+    #[test]
+    fn parse_roc_date_range_accepts_common_separators() {
+        let expected = (
+            NaiveDate::from_ymd_opt(2026, 4, 21).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 5, 5).unwrap(),
+        );
+        assert_eq!(parse_roc_date_range("115/04/21～115/05/05"), Some(expected));
+        assert_eq!(parse_roc_date_range("1150421~1150505"), Some(expected));
+        assert_eq!(parse_roc_date_range("1150505~1150421"), None);
+        assert_eq!(parse_roc_date_range("115/04/21"), None);
+    }
+
+    /// 驗證注意股以公告日為起迄、處置股以處置期間為起迄，並接受中英文鍵名。
+    #[test]
+    fn map_surveillance_items_handles_both_lists() {
+        let attention: Vec<serde_json::Map<String, serde_json::Value>> = serde_json::from_str(
+            r#"[{"Number":"1","Code":"2330","Name":"台積電","TradingInfoForAttention":"最近六個營業日累積收盤價漲幅達25%","Date":"1150421"}]"#,
+        )
+        .unwrap();
+        let result = map_surveillance_items(false, &attention);
+        assert_eq!(result.len(), 1);
+        assert!(!result[0].is_disposition);
+        assert_eq!(result[0].start_date, result[0].announced_on);
+        assert_eq!(result[0].end_date, result[0].announced_on);
+        assert!(result[0].measure.is_empty());
+
+        let disposition: Vec<serde_json::Map<String, serde_json::Value>> = serde_json::from_str(
+            r#"[
+                {"公布日期":"115/04/20","證券代號":"8069","證券名稱":"元太","處置起迄時間":"115/04/21～115/05/05","處置條件":"連續三次","處置措施":"第一次處置"},
+                {"公布日期":"115/04/20","證券代號":"6488","證券名稱":"環球晶","處置起迄時間":"尚未公布"}
+            ]"#,
+        )
+        .unwrap();
+        let result = map_surveillance_items(true, &disposition);
+        assert_eq!(result.len(), 1, "處置期間無法解析的資料應略過");
+        assert_eq!(result[0].stock_symbol, "8069");
+        assert_eq!(
+            result[0].start_date,
+            NaiveDate::from_ymd_opt(2026, 4, 21).unwrap()
+        );
+        assert_eq!(
+            result[0].end_date,
+            NaiveDate::from_ymd_opt(2026, 5, 5).unwrap()
+        );
+        assert_eq!(result[0].measure, "第一次處置");
+    }
 }
@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+
+use crate::{
+    core::util,
+    infra::archive::{self, ArchiveKey},
+    infra::crawler::{
+        share::{self, FuturesDailyQuoteDto},
+        taifex,
+    },
+};
+
+/// 收錄的期貨契約：臺股期貨與小型臺指期貨。
+pub const CONTRACTS: [&str; 2] = ["TX", "MTX"];
+
+/// 每列至少應有的欄位數（到「交易時段」為止）。
+const MIN_COLUMNS: usize = 18;
+
+/// 一般交易時段標記；盤後時段的行情不納入日線。
+const REGULAR_SESSION: &str = "一般";
+
+/// 取得指定交易日臺股期貨與小型臺指期貨的每日行情
+///
+/// 資料來源：期交所「期貨每日交易行情下載」（`futDataDown`，Big5 CSV）。
+/// 每個契約各送一次查詢，原始 CSV 依契約分別封存。
+pub async fn visit(date: NaiveDate) -> Result<Vec<FuturesDailyQuoteDto>> {
+    let query_date = date.format(taifex::DATE_FORMAT).to_string();
+    let date_str = date.format("%Y%m%d").to_string();
+    let url = format!("https://{}/cht/3/futDataDown", taifex::HOST);
+    let mut result = Vec::new();
+
+    for contract in CONTRACTS {
+        let params = HashMap::from([
+            ("down_type", "1"),
+            ("commodity_id", contract),
+            ("queryStartDate", query_date.as_str()),
+            ("queryEndDate", query_date.as_str()),
+        ]);
+        let text = util::http::post_use_big5(&url, None, params).await?;
+        archive::save(archive_key(contract, &date_str), text.as_bytes()).await;
+        result.extend(parse_csv(&text, date));
+    }
+
+    Ok(result)
+}
+
+/// 以本機封存的原始 CSV 重跑解析，不連線期交所；任一契約查無封存時回傳 `None`。
+pub async fn visit_archived(date: NaiveDate) -> Result<Option<Vec<FuturesDailyQuoteDto>>> {
+    let date_str = date.format("%Y%m%d").to_string();
+    let mut result = Vec::new();
+
+    for contract in CONTRACTS {
+        let Some(body) = archive::load_latest(archive_key(contract, &date_str)).await? else {
+            return Ok(None);
+        };
+        let text = String::from_utf8(body).with_context(|| {
+            format!("Failed to decode archived TAIFEX {contract} quotes of {date_str}")
+        })?;
+        result.extend(parse_csv(&text, date));
+    }
+
+    Ok(Some(result))
+}
+
+/// 期貨每日行情的封存鍵；`endpoint` 帶契約代號以區分同日的兩次查詢。
+fn archive_key<'a>(contract: &str, date_str: &'a str) -> ArchiveKey<'a> {
+    let endpoint = match contract {
+        "MTX" => "fut_data_mtx",
+        _ => "fut_data_tx",
+    };
+    ArchiveKey::new("taifex", endpoint, date_str)
+}
+
+/// 將期貨每日行情 CSV 整理成 [`FuturesDailyQuoteDto`] 清單。
+///
+/// - 只保留指定日期、一般交易時段、單一到期月份的列；
+///   價差組合（到期月份含 `/`）與盤後時段直接略過。
+/// - 數值無法解析的列記 warning 後略過。
+fn parse_csv(text: &str, date: NaiveDate) -> Vec<FuturesDailyQuoteDto> {
+    let mut result = Vec::new();
+
+    for line in text.lines() {
+        let cells = taifex::split_csv_line(line);
+        if cells.len() < MIN_COLUMNS || taifex::parse_date(cells[0]) != Some(date) {
+            continue;
+        }
+        if cells[17] != REGULAR_SESSION || cells[2].contains('/') {
+            continue;
+        }
+        match parse_row(&cells, date) {
+            Ok(dto) => result.push(dto),
+            Err(why) => tracing::warn!("略過無法解析的期貨行情資料列 {line:?}: {why:#}"),
+        }
+    }
+
+    result
+}
+
+/// 整理單一期貨行情資料列。
+fn parse_row(cells: &[&str], date: NaiveDate) -> Result<FuturesDailyQuoteDto> {
+    Ok(FuturesDailyQuoteDto {
+        date,
+        contract: cells[1].to_string(),
+        contract_month: cells[2].to_string(),
+        open: taifex::parse_optional_decimal(cells[3])?,
+        high: taifex::parse_optional_decimal(cells[4])?,
+        low: taifex::parse_optional_decimal(cells[5])?,
+        close: taifex::parse_optional_decimal(cells[6])?,
+        change: taifex::parse_optional_decimal(cells[7])?,
+        volume: share::parse_shares(cells[9])?,
+        settlement_price: taifex::parse_optional_decimal(cells[10])?,
+        open_interest: share::parse_shares(cells[11])?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    /// 驗證欄位對應，並濾除盤後時段、價差組合與其他日期的列。
+    #[test]
+    fn parse_csv_keeps_regular_session_outrights() {
+        let text = "交易日期,契約,到期月份(週別),開盤價,最高價,最低價,收盤價,漲跌價,漲跌%,成交量,結算價,未沖銷契約數,最後最佳買價,最後最佳賣價,歷史最高價,歷史最低價,是否因訊息面暫停交易,交易時段,價差對單式委託成交量\n\
+                    2026/04/24,TX,202605     ,21000,21150,20950,21100,120,0.57%,98765,21098,85432,21099,21100,23000,17000,,一般,-\n\
+                    2026/04/24,TX,202606     ,-,-,-,-,-,-,0,21050,1234,-,-,23000,17000,,一般,-\n\
+                    2026/04/24,TX,202605/202606,60,62,58,61,1,1.67%,321,-,-,60,61,-,-,,一般,-\n\
+                    2026/04/24,TX,202605     ,21100,21180,21060,21120,20,0.09%,40000,-,-,21119,21120,23000,17000,,盤後,-\n\
+                    2026/04/23,TX,202605     ,20900,21000,20880,20980,-10,-0.05%,90000,20980,85000,20979,20980,23000,17000,,一般,-\n";
+
+        let rows = parse_csv(text, NaiveDate::from_ymd_opt(2026, 4, 24).unwrap());
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].contract, "TX");
+        assert_eq!(rows[0].contract_month, "202605");
+        assert_eq!(rows[0].open, Some(dec!(21000)));
+        assert_eq!(rows[0].close, Some(dec!(21100)));
+        assert_eq!(rows[0].change, Some(dec!(120)));
+        assert_eq!(rows[0].volume, 98_765);
+        assert_eq!(rows[0].settlement_price, Some(dec!(21098)));
+        assert_eq!(rows[0].open_interest, 85_432);
+        assert_eq!(rows[1].contract_month, "202606");
+        assert_eq!(rows[1].close, None);
+        assert_eq!(rows[1].volume, 0);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_visit() {
+        dotenvy::dotenv().ok();
+        match visit(NaiveDate::from_ymd_opt(2026, 4, 24).unwrap()).await {
+            Ok(result) => {
+                tracing::debug!("result: {:?}", result);
+            }
+            Err(why) => {
+                tracing::debug!("Failed to visit because {:?}", why);
+            }
+        }
+    }
+}
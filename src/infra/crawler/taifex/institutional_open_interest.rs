@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+
+use crate::{
+    core::util,
+    infra::archive::{self, ArchiveKey},
+    infra::crawler::{
+        share::{self, FuturesInstitutionalPositionDto},
+        taifex,
+    },
+};
+
+/// 每列應有的欄位數：日期、商品名稱、身份別，以及交易與未平倉的口數／金額共 12 欄。
+const COLUMNS: usize = 15;
+
+/// 取得指定交易日三大法人在臺股期貨與小型臺指期貨的交易與未平倉部位
+///
+/// 資料來源：期交所「三大法人－區分各期貨契約」下載（`futContractsDateDown`，
+/// Big5 CSV）。不指定商品一次取回全部契約，再只保留臺指相關契約。
+pub async fn visit(date: NaiveDate) -> Result<Vec<FuturesInstitutionalPositionDto>> {
+    let query_date = date.format(taifex::DATE_FORMAT).to_string();
+    let url = format!("https://{}/cht/3/futContractsDateDown", taifex::HOST);
+    let params = HashMap::from([
+        ("queryStartDate", query_date.as_str()),
+        ("queryEndDate", query_date.as_str()),
+        ("commodityId", ""),
+    ]);
+    let text = util::http::post_use_big5(&url, None, params).await?;
+    let date_str = date.format("%Y%m%d").to_string();
+    archive::save(archive_key(&date_str), text.as_bytes()).await;
+
+    Ok(parse_csv(&text, date))
+}
+
+/// 以本機封存的原始 CSV 重跑解析，不連線期交所；查無封存時回傳 `None`。
+pub async fn visit_archived(
+    date: NaiveDate,
+) -> Result<Option<Vec<FuturesInstitutionalPositionDto>>> {
+    let date_str = date.format("%Y%m%d").to_string();
+    let Some(body) = archive::load_latest(archive_key(&date_str)).await? else {
+        return Ok(None);
+    };
+    let text = String::from_utf8(body).with_context(|| {
+        format!("Failed to decode archived TAIFEX institutional positions of {date_str}")
+    })?;
+
+    Ok(Some(parse_csv(&text, date)))
+}
+
+/// 三大法人期貨部位的封存鍵；參數為交易日期 `YYYYMMDD`。
+fn archive_key(date_str: &str) -> ArchiveKey<'_> {
+    ArchiveKey::new("taifex", "fut_contracts_date", date_str)
+}
+
+/// 將來源的商品名稱對應為契約代號；非臺指期貨契約回傳 `None`。
+fn contract_of(commodity_name: &str) -> Option<&'static str> {
+    match commodity_name {
+        "臺股期貨" => Some("TX"),
+        "小型臺指期貨" | "小型臺指" => Some("MTX"),
+        _ => None,
+    }
+}
+
+/// 將三大法人期貨部位 CSV 整理成 [`FuturesInstitutionalPositionDto`] 清單。
+///
+/// - 只保留指定日期且商品為臺股期貨、小型臺指期貨的列。
+/// - 數值無法解析的列記 warning 後略過。
+fn parse_csv(text: &str, date: NaiveDate) -> Vec<FuturesInstitutionalPositionDto> {
+    let mut result = Vec::new();
+
+    for line in text.lines() {
+        let cells = taifex::split_csv_line(line);
+        if cells.len() != COLUMNS || taifex::parse_date(cells[0]) != Some(date) {
+            continue;
+        }
+        let Some(contract) = contract_of(cells[1]) else {
+            continue;
+        };
+        match parse_row(&cells, date, contract) {
+            Ok(dto) => result.push(dto),
+            Err(why) => tracing::warn!("略過無法解析的三大法人期貨部位資料列 {line:?}: {why:#}"),
+        }
+    }
+
+    result
+}
+
+/// 整理單一三大法人期貨部位資料列。
+fn parse_row(
+    cells: &[&str],
+    date: NaiveDate,
+    contract: &str,
+) -> Result<FuturesInstitutionalPositionDto> {
+    Ok(FuturesInstitutionalPositionDto {
+        date,
+        contract: contract.to_string(),
+        trader_type: cells[2].to_string(),
+        long_trade_volume: share::parse_shares(cells[3])?,
+        short_trade_volume: share::parse_shares(cells[5])?,
+        net_trade_volume: share::parse_shares(cells[7])?,
+        long_open_interest: share::parse_shares(cells[9])?,
+        short_open_interest: share::parse_shares(cells[11])?,
+        net_open_interest: share::parse_shares(cells[13])?,
+        net_open_interest_amount: share::parse_shares(cells[14])?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 驗證欄位對應，並只保留臺指期貨相關契約。
+    #[test]
+    fn parse_csv_maps_index_futures_only() {
+        let text = "日期,商品名稱,身份別,多方交易口數,多方交易契約金額(千元),空方交易口數,空方交易契約金額(千元),多空交易口數淨額,多空交易契約金額淨額(千元),多方未平倉口數,多方未平倉契約金額(千元),空方未平倉口數,空方未平倉契約金額(千元),多空未平倉口數淨額,多空未平倉契約金額淨額(千元)\n\
+                    2026/04/24,臺股期貨,自營商,10000,420000,9000,378000,1000,42000,8000,336000,7000,294000,1000,42000\n\
+                    2026/04/24,臺股期貨,外資及陸資,60000,2520000,65000,2730000,-5000,-210000,20000,840000,45000,1890000,-25000,-1050000\n\
+                    2026/04/24,電子期貨,外資及陸資,1,1,1,1,0,0,1,1,1,1,0,0\n\
+                    2026/04/24,小型臺指期貨,投信,100,1050,50,525,50,525,3000,31500,500,5250,2500,26250\n";
+
+        let rows = parse_csv(text, NaiveDate::from_ymd_opt(2026, 4, 24).unwrap());
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[1].contract, "TX");
+        assert_eq!(rows[1].trader_type, "外資及陸資");
+        assert_eq!(rows[1].long_trade_volume, 60_000);
+        assert_eq!(rows[1].short_trade_volume, 65_000);
+        assert_eq!(rows[1].net_trade_volume, -5_000);
+        assert_eq!(rows[1].long_open_interest, 20_000);
+        assert_eq!(rows[1].short_open_interest, 45_000);
+        assert_eq!(rows[1].net_open_interest, -25_000);
+        assert_eq!(rows[1].net_open_interest_amount, -1_050_000);
+        assert_eq!(rows[2].contract, "MTX");
+        assert_eq!(rows[2].trader_type, "投信");
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_visit() {
+        dotenvy::dotenv().ok();
+        match visit(NaiveDate::from_ymd_opt(2026, 4, 24).unwrap()).await {
+            Ok(result) => {
+                tracing::debug!("result: {:?}", result);
+            }
+            Err(why) => {
+                tracing::debug!("Failed to visit because {:?}", why);
+            }
+        }
+    }
+}
@@ -1,4 +1,43 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+/// 期貨每日行情（臺股期貨、小型臺指期貨）crawler。
+pub mod futures_daily;
+/// 三大法人期貨交易與未平倉部位 crawler。
+pub mod institutional_open_interest;
+/// 臺指選擇權賣權買權比 crawler。
+pub mod put_call_ratio;
 /// 臺指成分股權重 crawler。
 pub mod stock_weight;
 
 const HOST: &str = "www.taifex.com.tw";
+
+/// 期交所下載 CSV 的查詢日期格式（`yyyy/MM/dd`），同時也是檔案內的日期格式。
+const DATE_FORMAT: &str = "%Y/%m/%d";
+
+/// 將期交所 CSV 的一列拆成欄位並去除前後空白。
+///
+/// 期交所下載檔的數值不含千分位，欄位內也不會出現逗號，因此直接以逗號切分。
+fn split_csv_line(line: &str) -> Vec<&str> {
+    line.split(',')
+        .map(|cell| cell.trim().trim_matches('"'))
+        .collect()
+}
+
+/// 解析期交所 CSV 的 `yyyy/MM/dd` 日期欄位；標題列等非日期內容回傳 `None`。
+fn parse_date(raw: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(raw, DATE_FORMAT).ok()
+}
+
+/// 解析可能為 `-` 的價格欄位；無成交或未揭露時回傳 `None`。
+fn parse_optional_decimal(raw: &str) -> Result<Option<Decimal>> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() || trimmed.chars().all(|c| c == '-') {
+        return Ok(None);
+    }
+
+    Ok(Some(Decimal::from_str(trimmed.trim_end_matches('%'))?))
+}
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use anyhow::{Context, Result, anyhow};
+use chrono::NaiveDate;
+
+use crate::{
+    core::util,
+    infra::archive::{self, ArchiveKey},
+    infra::crawler::{
+        share::{self, PutCallRatioDto},
+        taifex,
+    },
+};
+
+/// 每列應有的欄位數：日期、賣權／買權成交量與比率、賣權／買權未平倉量與比率。
+const COLUMNS: usize = 7;
+
+/// 取得指定交易日臺指選擇權的賣權買權比
+///
+/// 資料來源：期交所「臺指選擇權 Put/Call Ratio」下載（`pcRatioDown`，Big5 CSV）。
+/// 非交易日來源不會有當日資料，回傳 `None`。
+pub async fn visit(date: NaiveDate) -> Result<Option<PutCallRatioDto>> {
+    let query_date = date.format(taifex::DATE_FORMAT).to_string();
+    let url = format!("https://{}/cht/3/pcRatioDown", taifex::HOST);
+    let params = HashMap::from([
+        ("queryStartDate", query_date.as_str()),
+        ("queryEndDate", query_date.as_str()),
+    ]);
+    let text = util::http::post_use_big5(&url, None, params).await?;
+    let date_str = date.format("%Y%m%d").to_string();
+    archive::save(archive_key(&date_str), text.as_bytes()).await;
+
+    parse_csv(&text, date)
+}
+
+/// 以本機封存的原始 CSV 重跑解析，不連線期交所。
+///
+/// 外層 `Option` 表示是否有封存，內層表示封存內是否有當日資料。
+pub async fn visit_archived(date: NaiveDate) -> Result<Option<Option<PutCallRatioDto>>> {
+    let date_str = date.format("%Y%m%d").to_string();
+    let Some(body) = archive::load_latest(archive_key(&date_str)).await? else {
+        return Ok(None);
+    };
+    let text = String::from_utf8(body).with_context(|| {
+        format!("Failed to decode archived TAIFEX put/call ratio of {date_str}")
+    })?;
+
+    parse_csv(&text, date).map(Some)
+}
+
+/// 賣權買權比的封存鍵；參數為交易日期 `YYYYMMDD`。
+fn archive_key(date_str: &str) -> ArchiveKey<'_> {
+    ArchiveKey::new("taifex", "pc_ratio", date_str)
+}
+
+/// 從賣權買權比 CSV 取出指定日期的資料列。
+///
+/// 只有一列有意義，因此當日資料列無法解析時直接回傳錯誤，而不是略過。
+fn parse_csv(text: &str, date: NaiveDate) -> Result<Option<PutCallRatioDto>> {
+    for line in text.lines() {
+        let cells = taifex::split_csv_line(line);
+        if cells.len() != COLUMNS || taifex::parse_date(cells[0]) != Some(date) {
+            continue;
+        }
+        return parse_row(&cells, date)
+            .map(Some)
+            .with_context(|| format!("Failed to parse put/call ratio row {line:?}"));
+    }
+
+    Ok(None)
+}
+
+/// 整理單一賣權買權比資料列。
+fn parse_row(cells: &[&str], date: NaiveDate) -> Result<PutCallRatioDto> {
+    let ratio = |raw: &str| {
+        taifex::parse_optional_decimal(raw)?.ok_or_else(|| anyhow!("missing ratio {raw:?}"))
+    };
+
+    Ok(PutCallRatioDto {
+        date,
+        put_volume: share::parse_shares(cells[1])?,
+        call_volume: share::parse_shares(cells[2])?,
+        volume_ratio_percent: ratio(cells[3])?,
+        put_open_interest: share::parse_shares(cells[4])?,
+        call_open_interest: share::parse_shares(cells[5])?,
+        open_interest_ratio_percent: ratio(cells[6])?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    /// 驗證欄位對應，且只取指定日期的列。
+    #[test]
+    fn parse_csv_picks_requested_date() {
+        let text = "日期,賣權成交量,買權成交量,買賣權成交量比率%,賣權未平倉量,買權未平倉量,買賣權未平倉量比率%\n\
+                    2026/04/24,512345,498765,102.72,123456,110000,112.23\n\
+                    2026/04/23,400000,500000,80.00,120000,115000,104.35\n";
+
+        let row = parse_csv(text, NaiveDate::from_ymd_opt(2026, 4, 23).unwrap())
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(row.put_volume, 400_000);
+        assert_eq!(row.call_volume, 500_000);
+        assert_eq!(row.volume_ratio_percent, dec!(80.00));
+        assert_eq!(row.put_open_interest, 120_000);
+        assert_eq!(row.call_open_interest, 115_000);
+        assert_eq!(row.open_interest_ratio_percent, dec!(104.35));
+        assert!(
+            parse_csv(text, NaiveDate::from_ymd_opt(2026, 4, 25).unwrap())
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_visit() {
+        dotenvy::dotenv().ok();
+        match visit(NaiveDate::from_ymd_opt(2026, 4, 24).unwrap()).await {
+            Ok(result) => {
+                tracing::debug!("result: {:?}", result);
+            }
+            Err(why) => {
+                tracing::debug!("Failed to visit because {:?}", why);
+            }
+        }
+    }
+}
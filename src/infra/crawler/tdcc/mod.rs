@@ -0,0 +1,4 @@
+/// 集保戶股權分散表（每週）
+pub mod shareholding_distribution;
+
+const HOST: &str = "opendata.tdcc.com.tw";
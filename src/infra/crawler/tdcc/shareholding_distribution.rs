@@ -0,0 +1,161 @@
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use chrono::{Local, NaiveDate};
+use rust_decimal::Decimal;
+
+use crate::{
+    core::util,
+    infra::archive::{self, ArchiveKey},
+    infra::crawler::{
+        share::{self, ShareholdingTierDto},
+        tdcc,
+    },
+};
+
+/// 每列應有的欄位數：資料日期、證券代號、持股分級、人數、股數、占集保庫存數比例%。
+const COLUMNS: usize = 6;
+
+/// 取得最新一週的集保戶股權分散表
+///
+/// 資料來源：TDCC 開放資料 `getOD.ashx?id=1-5`（CSV，涵蓋全部集保證券）。
+/// 來源只提供最新一週，無法指定日期；封存以檔案內的資料日期為鍵，
+/// 歷史週次只能透過 [`visit_archived`] 重跑。
+///
+/// 原始內容在解析前先封存，格式變動導致整份解析失敗時仍留有回應可供重跑；
+/// 讀不出資料日期時改以抓取日為鍵。
+pub async fn visit() -> Result<Vec<ShareholdingTierDto>> {
+    let url = format!("https://{}/getOD.ashx?id=1-5", tdcc::HOST);
+    let text = util::http::get(&url, None).await?;
+
+    let date = data_date(&text).unwrap_or_else(|| Local::now().date_naive());
+    let date_str = date.format("%Y%m%d").to_string();
+    archive::save(archive_key(&date_str), text.as_bytes()).await;
+
+    let rows = parse_csv(&text);
+    if rows.is_empty() {
+        tracing::warn!("取得集保戶股權分散表無資料");
+    }
+
+    Ok(rows)
+}
+
+/// 以本機封存的原始 CSV 重跑解析，不連線 TDCC；查無封存時回傳 `None`。
+pub async fn visit_archived(date: NaiveDate) -> Result<Option<Vec<ShareholdingTierDto>>> {
+    let date_str = date.format("%Y%m%d").to_string();
+    let Some(body) = archive::load_latest(archive_key(&date_str)).await? else {
+        return Ok(None);
+    };
+    let text = String::from_utf8(body)
+        .with_context(|| format!("Failed to decode archived TDCC distribution of {date_str}"))?;
+
+    Ok(Some(parse_csv(&text)))
+}
+
+/// 集保戶股權分散表的封存鍵；參數為資料日期 `YYYYMMDD`。
+fn archive_key(date_str: &str) -> ArchiveKey<'_> {
+    ArchiveKey::new("tdcc", "shareholding_distribution", date_str)
+}
+
+/// 取第一筆資料列的資料日期，不要求該列其餘欄位可以解析。
+fn data_date(text: &str) -> Option<NaiveDate> {
+    text.trim_start_matches('\u{feff}')
+        .lines()
+        .filter_map(|line| line.split(',').next())
+        .map(|cell| cell.trim().trim_matches('"'))
+        .find(|cell| !cell.is_empty() && cell.bytes().all(|b| b.is_ascii_digit()))
+        .and_then(|cell| NaiveDate::parse_from_str(cell, "%Y%m%d").ok())
+}
+
+/// 將 TDCC 股權分散表 CSV 整理成 [`ShareholdingTierDto`] 清單。
+///
+/// - 略過 UTF-8 BOM 與標題列；欄位可能以雙引號包住，數值不含千分位。
+/// - 欄位數不符的列直接略過；數值無法解析的列記 warning 後略過。
+fn parse_csv(text: &str) -> Vec<ShareholdingTierDto> {
+    let mut result = Vec::new();
+
+    for line in text.trim_start_matches('\u{feff}').lines() {
+        let cells: Vec<&str> = line
+            .split(',')
+            .map(|cell| cell.trim().trim_matches('"'))
+            .collect();
+        if cells.len() != COLUMNS || !cells[0].bytes().all(|b| b.is_ascii_digit()) {
+            continue;
+        }
+        match parse_row(&cells) {
+            Ok(dto) => result.push(dto),
+            Err(why) => tracing::warn!("略過無法解析的股權分散表資料列 {line:?}: {why:#}"),
+        }
+    }
+
+    result
+}
+
+/// 整理單一股權分散表資料列。
+fn parse_row(cells: &[&str]) -> Result<ShareholdingTierDto> {
+    let stock_symbol = cells[1].trim().to_string();
+    if stock_symbol.is_empty() {
+        anyhow::bail!("empty stock symbol");
+    }
+
+    Ok(ShareholdingTierDto {
+        stock_symbol,
+        date: NaiveDate::parse_from_str(cells[0], "%Y%m%d")?,
+        level: cells[2].parse()?,
+        holders: share::parse_shares(cells[3])?,
+        shares: share::parse_shares(cells[4])?,
+        percentage: Decimal::from_str(cells[5])?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    /// 驗證 BOM、標題列與欄位對應，並略過無法解析的列。
+    #[test]
+    fn parse_csv_maps_columns() {
+        let text = "\u{feff}資料日期,證券代號,持股分級,人數,股數,占集保庫存數比例%\n\
+                    20260424,2330,1,1234567,234567890,0.90\n\
+                    \"20260424\",\"2330\",\"15\",\"1520\",\"23012345678\",\"88.73\"\n\
+                    20260424,2330,x,1,1,1\n";
+
+        let rows = parse_csv(text);
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].stock_symbol, "2330");
+        assert_eq!(rows[0].date, NaiveDate::from_ymd_opt(2026, 4, 24).unwrap());
+        assert_eq!(rows[0].level, 1);
+        assert_eq!(rows[0].holders, 1_234_567);
+        assert_eq!(rows[0].shares, 234_567_890);
+        assert_eq!(rows[0].percentage, dec!(0.90));
+        assert_eq!(rows[1].level, 15);
+        assert_eq!(rows[1].percentage, dec!(88.73));
+    }
+
+    /// 欄位格式變動、整份解析不出資料列時，仍要讀得出封存用的資料日期。
+    #[test]
+    fn data_date_survives_unparseable_rows() {
+        let text = "\u{feff}資料日期,證券代號,持股分級,人數,股數,占集保庫存數比例%,新欄位\n\
+                    \"20260424\",2330,1,1234567,234567890,0.90,x\n";
+
+        assert!(parse_csv(text).is_empty());
+        assert_eq!(data_date(text), NaiveDate::from_ymd_opt(2026, 4, 24));
+        assert_eq!(data_date("資料日期,證券代號\n"), None);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_visit() {
+        dotenvy::dotenv().ok();
+        match visit().await {
+            Ok(result) => {
+                tracing::debug!("result: {}", result.len());
+            }
+            Err(why) => {
+                tracing::debug!("Failed to visit because {:?}", why);
+            }
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::util,
+    infra::archive::{self, ArchiveKey},
+    infra::crawler::{
+        share::{self, CapitalChangeDto, CapitalChangeKind},
+        tpex,
+    },
+};
+
+/// TPEx 減資／變更面額恢復買賣參考價 API 回應。
+#[derive(Default, Serialize, Deserialize, Debug)]
+pub struct CapitalChangeResponse {
+    /// 回應狀態字串。
+    pub stat: Option<String>,
+    /// 回應中的資料表清單；只使用第一個表格。
+    #[serde(default)]
+    pub tables: Vec<Table>,
+}
+
+/// TPEx 回應中的單一表格區塊。
+#[derive(Default, Serialize, Deserialize, Debug)]
+pub struct Table {
+    /// 欄位名稱。
+    #[serde(default)]
+    pub fields: Vec<String>,
+    /// 表格資料列。
+    #[serde(default)]
+    pub data: Vec<Vec<serde_json::Value>>,
+}
+
+/// 取得上櫃股票在區間內恢復買賣的減資或變更面額資料
+///
+/// 區間以「恢復買賣日期」篩選，日期參數為民國年 `YYY/MM/DD`。查無資料時回傳空陣列。
+pub async fn visit(
+    kind: CapitalChangeKind,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<CapitalChangeDto>> {
+    let params = archive_params(from, to);
+    let url = format!(
+        "https://{}/web/stock/exright/{}?l=zh-tw&o=json&d={}&ed={}",
+        tpex::HOST,
+        endpoint(kind),
+        roc_date(from),
+        roc_date(to),
+    );
+
+    let response: CapitalChangeResponse =
+        archive::fetch_json(&url, archive_key(kind, &params)).await?;
+    Ok(parse_response(kind, response))
+}
+
+/// 以本機封存的原始回應重跑解析，不連線 TPEx；查無封存時回傳 `None`。
+pub async fn visit_archived(
+    kind: CapitalChangeKind,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Option<Vec<CapitalChangeDto>>> {
+    let params = archive_params(from, to);
+    let Some(body) = archive::load_latest(archive_key(kind, &params)).await? else {
+        return Ok(None);
+    };
+    let response: CapitalChangeResponse = serde_json::from_slice(&body).with_context(|| {
+        format!(
+            "Failed to parse archived TPEx {} of {params}",
+            endpoint(kind)
+        )
+    })?;
+
+    Ok(Some(parse_response(kind, response)))
+}
+
+/// 各種類對應的 API 路徑。
+fn endpoint(kind: CapitalChangeKind) -> &'static str {
+    match kind {
+        CapitalChangeKind::CapitalReduction => "revivt/revivt_result.php",
+        CapitalChangeKind::ParValueChange => "parvalchg/parvalchg_result.php",
+    }
+}
+
+/// 民國年日期字串 `YYY/MM/DD`。
+fn roc_date(date: NaiveDate) -> String {
+    format!(
+        "{}{}",
+        util::datetime::gregorian_year_to_roc_year(date.year()),
+        date.format("/%m/%d")
+    )
+}
+
+/// 封存參數：查詢區間 `YYYYMMDD-YYYYMMDD`。
+fn archive_params(from: NaiveDate, to: NaiveDate) -> String {
+    format!("{}-{}", from.format("%Y%m%d"), to.format("%Y%m%d"))
+}
+
+/// 上櫃恢復買賣參考價的封存鍵。
+fn archive_key(kind: CapitalChangeKind, params: &str) -> ArchiveKey<'_> {
+    let endpoint = match kind {
+        CapitalChangeKind::CapitalReduction => "revivt",
+        CapitalChangeKind::ParValueChange => "parvalchg",
+    };
+    ArchiveKey::new("tpex", endpoint, params)
+}
+
+/// 取出第一個表格後整理；沒有表格時回傳空陣列。
+fn parse_response(
+    kind: CapitalChangeKind,
+    response: CapitalChangeResponse,
+) -> Vec<CapitalChangeDto> {
+    let Some(table) = response.tables.into_iter().next() else {
+        tracing::debug!(
+            "取得上櫃{}恢復買賣資料無資料: {:?}",
+            kind.label(),
+            response.stat
+        );
+        return Vec::new();
+    };
+
+    share::map_capital_change_rows(kind, &table.fields, &table.data)
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    /// 驗證民國年日期參數的格式。
+    #[test]
+    fn roc_date_uses_republic_year() {
+        let date = NaiveDate::from_ymd_opt(2026, 5, 2).unwrap();
+        assert_eq!(roc_date(date), "115/05/02");
+    }
+
+    /// 驗證只取第一個表格，並依欄位名稱解析。
+    #[test]
+    fn parse_response_maps_first_table() {
+        let response: CapitalChangeResponse = serde_json::from_str(
+            r#"{
+                "stat": "ok",
+                "tables": [{
+                    "fields": ["恢復買賣日期","代號","名稱","停止買賣前收盤價格","恢復買賣參考價","漲停價","跌停價","開盤競價基準","減資原因"],
+                    "data": [["115/04/20","8069","元太","120.00","150.00","165.00","135.00","150.00","退還股款"]]
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        let result = parse_response(CapitalChangeKind::CapitalReduction, response);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].stock_symbol, "8069");
+        assert_eq!(result[0].reference_price, dec!(150.00));
+        assert_eq!(result[0].reason, "退還股款");
+    }
+
+    /// 沒有表格時應得到空陣列而非錯誤。
+    #[test]
+    fn parse_response_without_tables_is_empty() {
+        assert!(
+            parse_response(
+                CapitalChangeKind::ParValueChange,
+                CapitalChangeResponse::default()
+            )
+            .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_visit() {
+        dotenvy::dotenv().ok();
+        let from = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2026, 4, 30).unwrap();
+        match visit(CapitalChangeKind::CapitalReduction, from, to).await {
+            Ok(result) => {
+                tracing::debug!("result: {:?}", result);
+            }
+            Err(why) => {
+                tracing::debug!("Failed to visit because {:?}", why);
+            }
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::util,
+    infra::archive::{self, ArchiveKey},
+    infra::crawler::{
+        share::{self, InstitutionalTradeDto},
+        tpex,
+    },
+};
+
+/// TPEx 三大法人買賣明細資訊 API 回應。
+#[derive(Default, Serialize, Deserialize, Debug)]
+pub struct InstitutionalTradeResponse {
+    /// 回應狀態字串。
+    pub stat: Option<String>,
+    /// 回應中的資料表清單；只使用第一個表格。
+    #[serde(default)]
+    pub tables: Vec<Table>,
+}
+
+/// TPEx 回應中的單一表格區塊。
+#[derive(Default, Serialize, Deserialize, Debug)]
+pub struct Table {
+    /// 表格資料列。
+    pub data: Option<Vec<Vec<String>>>,
+}
+
+/// TPEx 三大法人日報每列應有的欄位數。
+const COLUMNS: usize = 24;
+
+/// 取得上櫃股票三大法人買賣明細（日報、全部類股）
+///
+/// 非交易日或 TPEx 尚未公布時回傳空陣列。
+pub async fn visit(date: NaiveDate) -> Result<Vec<InstitutionalTradeDto>> {
+    let republic_date = util::datetime::gregorian_year_to_roc_year(date.year());
+    let url = format!(
+        "https://{}/web/stock/3insti/daily_trade/3itrade_hedge_result.php?l=zh-tw&o=json&se=EW&t=D&d={}{}",
+        tpex::HOST,
+        republic_date,
+        date.format("/%m/%d"),
+    );
+
+    let date_str = date.format("%Y%m%d").to_string();
+    let response: InstitutionalTradeResponse =
+        archive::fetch_json(&url, archive_key(&date_str)).await?;
+    Ok(parse_response(response, date))
+}
+
+/// 以本機封存的原始回應重跑解析，不連線 TPEx；查無封存時回傳 `None`。
+pub async fn visit_archived(date: NaiveDate) -> Result<Option<Vec<InstitutionalTradeDto>>> {
+    let date_str = date.format("%Y%m%d").to_string();
+    let Some(body) = archive::load_latest(archive_key(&date_str)).await? else {
+        return Ok(None);
+    };
+    let response: InstitutionalTradeResponse =
+        serde_json::from_slice(&body).with_context(|| {
+            format!("Failed to parse archived TPEx institutional trade of {date_str}")
+        })?;
+
+    Ok(Some(parse_response(response, date)))
+}
+
+/// 上櫃三大法人日報的封存鍵；參數為資料日期 `YYYYMMDD`。
+fn archive_key(date_str: &str) -> ArchiveKey<'_> {
+    ArchiveKey::new("tpex", "institutional_investor", date_str)
+}
+
+/// 取出第一個表格的資料列後整理；沒有表格（非交易日）時回傳空陣列。
+fn parse_response(
+    response: InstitutionalTradeResponse,
+    date: NaiveDate,
+) -> Vec<InstitutionalTradeDto> {
+    let Some(rows) = response.tables.into_iter().next().and_then(|t| t.data) else {
+        tracing::warn!("取得上櫃三大法人買賣超 {date} 無資料: {:?}", response.stat);
+        return Vec::new();
+    };
+
+    map_rows(&rows, date)
+}
+
+/// 將 TPEx 三大法人日報的原始資料列整理成 [`InstitutionalTradeDto`] 清單。
+///
+/// # 欄位對應（每列 24 欄）
+/// - `[0]` 代號、`[2..=4]` 外資及陸資（不含外資自營商）買進／賣出／買賣超、
+///   `[7]` 外資自營商買賣超、`[8..=10]` 外資合計（未使用）、
+///   `[11..=13]` 投信、`[16]` 自營商（自行買賣）買賣超、
+///   `[19]` 自營商（避險）買賣超、`[20..=22]` 自營商合計、`[23]` 三大法人合計。
+/// - 欄位數不符的列直接略過；數值無法解析的列記 warning 後略過。
+fn map_rows(data: &[Vec<String>], date: NaiveDate) -> Vec<InstitutionalTradeDto> {
+    let mut result = Vec::with_capacity(data.len());
+
+    for item in data {
+        if item.len() != COLUMNS {
+            continue;
+        }
+        match map_row(item, date) {
+            Ok(dto) => result.push(dto),
+            Err(why) => tracing::warn!("略過無法解析的上櫃三大法人資料列 {:?}: {why:#}", item[0]),
+        }
+    }
+
+    result
+}
+
+/// 整理單一 TPEx 三大法人資料列。
+fn map_row(item: &[String], date: NaiveDate) -> Result<InstitutionalTradeDto> {
+    let shares = |index: usize| share::parse_shares(&item[index]);
+    let stock_symbol = item[0].trim().to_string();
+    if stock_symbol.is_empty() {
+        anyhow::bail!("empty stock symbol");
+    }
+
+    Ok(InstitutionalTradeDto {
+        stock_symbol,
+        date,
+        foreign_buy: shares(2)?,
+        foreign_sell: shares(3)?,
+        foreign_net: shares(4)?,
+        foreign_dealer_net: shares(7)?,
+        investment_trust_buy: shares(11)?,
+        investment_trust_sell: shares(12)?,
+        investment_trust_net: shares(13)?,
+        dealer_buy: shares(20)?,
+        dealer_sell: shares(21)?,
+        dealer_net: shares(22)?,
+        dealer_proprietary_net: shares(16)?,
+        dealer_hedge_net: shares(19)?,
+        total_net: shares(23)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(symbol: &str) -> Vec<String> {
+        [
+            symbol,
+            "元太",
+            "1,200,000",
+            "200,000",
+            "1,000,000",
+            "10,000",
+            "0",
+            "10,000",
+            "1,210,000",
+            "200,000",
+            "1,010,000",
+            "50,000",
+            "80,000",
+            "-30,000",
+            "6,000",
+            "1,000",
+            "5,000",
+            "4,000",
+            "2,000",
+            "2,000",
+            "10,000",
+            "3,000",
+            "7,000",
+            "987,000",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+    }
+
+    /// 驗證 24 欄日報的欄位對應，並略過欄位數不符的列。
+    #[test]
+    fn map_rows_maps_columns() {
+        let date = NaiveDate::from_ymd_opt(2026, 4, 30).unwrap();
+        let data = vec![row("8069"), vec!["合計".to_string()]];
+
+        let result = map_rows(&data, date);
+
+        assert_eq!(result.len(), 1);
+        let dto = &result[0];
+        assert_eq!(dto.stock_symbol, "8069");
+        assert_eq!(dto.foreign_net, 1_000_000);
+        assert_eq!(dto.foreign_dealer_net, 10_000);
+        assert_eq!(dto.investment_trust_buy, 50_000);
+        assert_eq!(dto.investment_trust_net, -30_000);
+        assert_eq!(dto.dealer_proprietary_net, 5_000);
+        assert_eq!(dto.dealer_hedge_net, 2_000);
+        assert_eq!(dto.dealer_buy, 10_000);
+        assert_eq!(dto.dealer_sell, 3_000);
+        assert_eq!(dto.dealer_net, 7_000);
+        assert_eq!(dto.total_net, 987_000);
+    }
+
+    /// 非交易日沒有表格，應得到空陣列而非錯誤。
+    #[test]
+    fn parse_response_without_tables_is_empty() {
+        let date = NaiveDate::from_ymd_opt(2026, 5, 2).unwrap();
+        assert!(parse_response(InstitutionalTradeResponse::default(), date).is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_visit() {
+        dotenvy::dotenv().ok();
+        let date = NaiveDate::from_ymd_opt(2026, 4, 30).unwrap();
+        match visit(date).await {
+            Ok(result) => {
+                tracing::debug!("result: {}", result.len());
+            }
+            Err(why) => {
+                tracing::debug!("Failed to visit because {:?}", why);
+            }
+        }
+    }
+}
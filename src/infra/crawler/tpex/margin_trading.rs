@@ -0,0 +1,188 @@
+use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::util,
+    infra::archive::{self, ArchiveKey},
+    infra::crawler::{
+        share::{self, MarginTradingDto},
+        tpex,
+    },
+};
+
+/// TPEx 融資融券餘額 API 回應。
+#[derive(Default, Serialize, Deserialize, Debug)]
+pub struct MarginBalanceResponse {
+    /// 回應狀態字串。
+    pub stat: Option<String>,
+    /// 回應中的資料表清單；只使用第一個表格。
+    #[serde(default)]
+    pub tables: Vec<Table>,
+}
+
+/// TPEx 回應中的單一表格區塊。
+#[derive(Default, Serialize, Deserialize, Debug)]
+pub struct Table {
+    /// 表格資料列。
+    pub data: Option<Vec<Vec<String>>>,
+}
+
+/// TPEx 融資融券餘額每列應有的欄位數。
+const COLUMNS: usize = 20;
+
+/// 取得上櫃股票融資融券餘額
+///
+/// 數量單位為「張」。非交易日或 TPEx 尚未公布時回傳空陣列。
+pub async fn visit(date: NaiveDate) -> Result<Vec<MarginTradingDto>> {
+    let republic_date = util::datetime::gregorian_year_to_roc_year(date.year());
+    let url = format!(
+        "https://{}/web/stock/margin_trading/margin_balance/margin_bal_result.php?l=zh-tw&o=json&d={}{}",
+        tpex::HOST,
+        republic_date,
+        date.format("/%m/%d"),
+    );
+
+    let date_str = date.format("%Y%m%d").to_string();
+    let response: MarginBalanceResponse = archive::fetch_json(&url, archive_key(&date_str)).await?;
+    Ok(parse_response(response, date))
+}
+
+/// 以本機封存的原始回應重跑解析，不連線 TPEx；查無封存時回傳 `None`。
+pub async fn visit_archived(date: NaiveDate) -> Result<Option<Vec<MarginTradingDto>>> {
+    let date_str = date.format("%Y%m%d").to_string();
+    let Some(body) = archive::load_latest(archive_key(&date_str)).await? else {
+        return Ok(None);
+    };
+    let response: MarginBalanceResponse = serde_json::from_slice(&body)
+        .with_context(|| format!("Failed to parse archived TPEx margin balance of {date_str}"))?;
+
+    Ok(Some(parse_response(response, date)))
+}
+
+/// 上櫃融資融券餘額的封存鍵；參數為資料日期 `YYYYMMDD`。
+fn archive_key(date_str: &str) -> ArchiveKey<'_> {
+    ArchiveKey::new("tpex", "margin_balance", date_str)
+}
+
+/// 取出第一個表格的資料列後整理；沒有表格（非交易日）時回傳空陣列。
+fn parse_response(response: MarginBalanceResponse, date: NaiveDate) -> Vec<MarginTradingDto> {
+    let Some(rows) = response.tables.into_iter().next().and_then(|t| t.data) else {
+        tracing::warn!("取得上櫃融資融券餘額 {date} 無資料: {:?}", response.stat);
+        return Vec::new();
+    };
+
+    map_rows(&rows, date)
+}
+
+/// 將 TPEx 融資融券餘額的原始資料列整理成 [`MarginTradingDto`] 清單。
+///
+/// # 欄位對應（每列 20 欄）
+/// - `[0]` 代號、`[2]` 前資餘額、`[3..=5]` 資買／資賣／現償、`[6]` 資餘額、
+///   `[9]` 資限額、`[10]` 前券餘額、`[11..=13]` 券賣／券買／券償、`[14]` 券餘額、
+///   `[17]` 券限額、`[18]` 資券相抵。
+/// - 屬證金與使用率欄位未使用，使用率改由領域模型依餘額與限額計算。
+/// - 欄位數不符的列直接略過；數值無法解析的列記 warning 後略過。
+fn map_rows(data: &[Vec<String>], date: NaiveDate) -> Vec<MarginTradingDto> {
+    let mut result = Vec::with_capacity(data.len());
+
+    for item in data {
+        if item.len() != COLUMNS {
+            continue;
+        }
+        match map_row(item, date) {
+            Ok(dto) => result.push(dto),
+            Err(why) => tracing::warn!("略過無法解析的上櫃融資融券資料列 {:?}: {why:#}", item[0]),
+        }
+    }
+
+    result
+}
+
+/// 整理單一 TPEx 融資融券資料列。
+fn map_row(item: &[String], date: NaiveDate) -> Result<MarginTradingDto> {
+    let lots = |index: usize| share::parse_shares(&item[index]);
+    let stock_symbol = item[0].trim().to_string();
+    if stock_symbol.is_empty() {
+        anyhow::bail!("empty stock symbol");
+    }
+
+    Ok(MarginTradingDto {
+        stock_symbol,
+        date,
+        margin_previous_balance: lots(2)?,
+        margin_buy: lots(3)?,
+        margin_sell: lots(4)?,
+        margin_redemption: lots(5)?,
+        margin_balance: lots(6)?,
+        margin_quota: lots(9)?,
+        short_previous_balance: lots(10)?,
+        short_sell: lots(11)?,
+        short_cover: lots(12)?,
+        short_redemption: lots(13)?,
+        short_balance: lots(14)?,
+        short_quota: lots(17)?,
+        offset: lots(18)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(symbol: &str) -> Vec<String> {
+        [
+            symbol, "元太", "10,000", "500", "300", "20", "10,180", "0", "4.07", "250,000", "800",
+            "120", "40", "0", "880", "0", "0.35", "250,000", "15", "",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+    }
+
+    /// 驗證 20 欄餘額表的欄位對應，並略過欄位數不符的列。
+    #[test]
+    fn map_rows_maps_columns() {
+        let date = NaiveDate::from_ymd_opt(2026, 4, 30).unwrap();
+        let data = vec![row("8069"), vec!["合計".to_string()]];
+
+        let result = map_rows(&data, date);
+
+        assert_eq!(result.len(), 1);
+        let dto = &result[0];
+        assert_eq!(dto.stock_symbol, "8069");
+        assert_eq!(dto.margin_previous_balance, 10_000);
+        assert_eq!(dto.margin_buy, 500);
+        assert_eq!(dto.margin_sell, 300);
+        assert_eq!(dto.margin_redemption, 20);
+        assert_eq!(dto.margin_balance, 10_180);
+        assert_eq!(dto.margin_quota, 250_000);
+        assert_eq!(dto.short_previous_balance, 800);
+        assert_eq!(dto.short_sell, 120);
+        assert_eq!(dto.short_cover, 40);
+        assert_eq!(dto.short_balance, 880);
+        assert_eq!(dto.offset, 15);
+    }
+
+    /// 非交易日沒有表格，應得到空陣列而非錯誤。
+    #[test]
+    fn parse_response_without_tables_is_empty() {
+        let date = NaiveDate::from_ymd_opt(2026, 5, 2).unwrap();
+        assert!(parse_response(MarginBalanceResponse::default(), date).is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_visit() {
+        dotenvy::dotenv().ok();
+        let date = NaiveDate::from_ymd_opt(2026, 4, 30).unwrap();
+        match visit(date).await {
+            Ok(result) => {
+                tracing::debug!("result: {}", result.len());
+            }
+            Err(why) => {
+                tracing::debug!("Failed to visit because {:?}", why);
+            }
+        }
+    }
+}
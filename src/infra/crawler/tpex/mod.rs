@@ -1,8 +1,16 @@
+/// 減資／變更面額恢復買賣參考價
+pub mod capital_change;
 /// ETF 資訊
 pub mod etf;
+/// 三大法人買賣明細
+pub mod institutional_investor;
+/// 融資融券餘額
+pub mod margin_trading;
 /// 興櫃每股淨值
 pub mod net_asset_value_per_share;
 /// 台股收盤報價-上櫃
 pub(crate) mod quote;
+/// 注意股與處置股
+pub mod trading_surveillance;
 
 pub const HOST: &str = "www.tpex.org.tw";
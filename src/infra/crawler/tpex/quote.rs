@@ -1,11 +1,12 @@
 use crate::{
     core::declare::StockExchange,
-    core::util,
+    core::util::{self, http},
+    infra::archive::{self, ArchiveKey},
     infra::cache::{TTL, TtlCacheInner},
     infra::crawler::{share, share::DailyQuoteDto, tpex},
 };
-use anyhow::Result;
-use chrono::{Datelike, NaiveDate};
+use anyhow::{Context, Result};
+use chrono::{Datelike, Local, NaiveDate};
 use hashbrown::HashMap;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
@@ -36,6 +37,9 @@ pub struct Table {
 /// PeRatioAnalysis 上櫃股票個股本益比、殖利率、股價淨值比
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PeRatioAnalysisResponse {
+    /// 資料日期（民國年 `YYYMMDD`）
+    #[serde(rename = "Date", default)]
+    pub date: String,
     /// 證券代號
     #[serde(rename = "SecuritiesCompanyCode")]
     pub security_code: String,
@@ -48,8 +52,10 @@ pub struct PeRatioAnalysisResponse {
 pub async fn visit(date: NaiveDate) -> Result<Vec<DailyQuoteDto>> {
     let pe_ratio_url = "https://www.tpex.org.tw/openapi/v1/tpex_mainboard_peratio_analysis";
     // 本益比
-    let pe_ratio_response =
-        util::http::get_json::<Vec<PeRatioAnalysisResponse>>(pe_ratio_url).await?;
+    let pe_ratio_body = http::get_body(pe_ratio_url).await?;
+    let pe_ratio_response = latest_pe_ratios(pe_ratio_url, &pe_ratio_body, date).await?;
+
+    let date_str = date.format("%Y%m%d").to_string();
 
     let republic_date = util::datetime::gregorian_year_to_roc_year(date.year());
     //https://www.tpex.org.tw/web/stock/aftertrading/daily_close_quotes/stk_quote_result.php?l=zh-tw&_=1681801169006
@@ -61,10 +67,83 @@ pub async fn visit(date: NaiveDate) -> Result<Vec<DailyQuoteDto>> {
         date
     );
 
-    let quote_response = util::http::get_json::<QuoteResponse>(&quote_url).await?;
+    let quote_response: QuoteResponse =
+        archive::fetch_json(&quote_url, archive_key(QUOTE_ENDPOINT, &date_str)).await?;
     parse_quote_response(quote_response, pe_ratio_response, date).await
 }
 
+/// 以本機封存的原始回應重跑解析，不連線 TPEx。
+///
+/// 收盤行情是必要的，查無封存時回傳 `None`；本益比封存缺漏時以空清單代替，
+/// 與來源當天沒有本益比資料的行為一致（本益比欄位為 0）。不經過 TTL 去重，
+/// 一律回傳封存內的整日資料。
+pub async fn visit_archived(date: NaiveDate) -> Result<Option<Vec<DailyQuoteDto>>> {
+    let date_str = date.format("%Y%m%d").to_string();
+    let Some(quote_body) = archive::load_latest(archive_key(QUOTE_ENDPOINT, &date_str)).await?
+    else {
+        return Ok(None);
+    };
+    let quote_response: QuoteResponse = serde_json::from_slice(&quote_body)
+        .with_context(|| format!("Failed to parse archived TPEx quote of {date_str}"))?;
+    let pe_ratio_response =
+        match archive::load_latest(archive_key(PE_RATIO_ENDPOINT, &date_str)).await? {
+            Some(body) => serde_json::from_slice::<Vec<PeRatioAnalysisResponse>>(&body)
+                .with_context(|| format!("Failed to parse archived TPEx PE ratio of {date_str}"))?,
+            None => Vec::new(),
+        };
+
+    parse_quotes(quote_response, pe_ratio_response, date, false)
+        .await
+        .map(Some)
+}
+
+/// 封存並解析本益比回應，只在資料日期等於 `date` 時回傳內容。
+///
+/// OpenAPI 只提供最新一日的本益比，回補歷史日期時拿到的是別天的資料；
+/// 因此封存參數取回應內容的資料日期（無法判斷時用抓取當天），
+/// 日期不符時以空清單代替，本益比欄位為 0，不把今天的本益比配到舊行情上。
+async fn latest_pe_ratios(
+    url: &str,
+    body: &[u8],
+    date: NaiveDate,
+) -> Result<Vec<PeRatioAnalysisResponse>> {
+    let parsed: Result<Vec<PeRatioAnalysisResponse>> = http::parse_json(url, body);
+    let data_date = parsed
+        .as_ref()
+        .ok()
+        .and_then(|records| pe_ratio_data_date(records))
+        .unwrap_or_else(|| Local::now().date_naive());
+    archive::save(
+        archive_key(PE_RATIO_ENDPOINT, &data_date.format("%Y%m%d").to_string()),
+        body,
+    )
+    .await;
+
+    let records = parsed?;
+    if data_date != date {
+        tracing::info!("TPEx PE ratio is for {data_date}, not merged into quotes of {date}");
+        return Ok(Vec::new());
+    }
+    Ok(records)
+}
+
+/// 本益比回應的資料日期；整份回應同一天，取第一筆可解析者。
+fn pe_ratio_data_date(records: &[PeRatioAnalysisResponse]) -> Option<NaiveDate> {
+    records
+        .iter()
+        .find_map(|record| util::datetime::parse_taiwan_date_short(record.date.trim()))
+}
+
+/// 上櫃收盤行情的封存端點名稱。
+const QUOTE_ENDPOINT: &str = "quote";
+/// 上櫃本益比的封存端點名稱。
+const PE_RATIO_ENDPOINT: &str = "pe_ratio";
+
+/// 上櫃資料的封存鍵；參數為資料日期 `YYYYMMDD`。
+fn archive_key<'a>(endpoint: &'a str, date_str: &'a str) -> ArchiveKey<'a> {
+    ArchiveKey::new("tpex", endpoint, date_str)
+}
+
 /// 解析 TPEX 每日收盤資訊與本益比資料，並將其轉換為 `DailyQuoteDto` 列表。
 ///
 /// # 參數
@@ -78,6 +157,19 @@ pub async fn parse_quote_response(
     quote_response: QuoteResponse,
     pe_ratio_response: Vec<PeRatioAnalysisResponse>,
     date: NaiveDate,
+) -> Result<Vec<DailyQuoteDto>> {
+    parse_quotes(quote_response, pe_ratio_response, date, true).await
+}
+
+/// [`parse_quote_response`] 的實作。
+///
+/// `skip_processed` 為 `true` 時以全域 TTL 略過 24 小時內已處理過的代號；
+/// 重跑封存時傳 `false`，不依賴也不清空線上流程共用的快取。
+async fn parse_quotes(
+    quote_response: QuoteResponse,
+    pe_ratio_response: Vec<PeRatioAnalysisResponse>,
+    date: NaiveDate,
+    skip_processed: bool,
 ) -> Result<Vec<DailyQuoteDto>> {
     let mut pe_ratio_analysis: HashMap<String, PeRatioAnalysisResponse> =
         HashMap::with_capacity(pe_ratio_response.len());
@@ -118,7 +210,7 @@ pub async fn parse_quote_response(
 
             let daily_quote_memory_key = format!("{}-{}", date.format("%Y%m%d"), dto.symbol);
 
-            if TTL.daily_quote_contains_key(&daily_quote_memory_key) {
+            if skip_processed && TTL.daily_quote_contains_key(&daily_quote_memory_key) {
                 continue;
             }
 
@@ -171,6 +263,27 @@ mod tests {
 
     use super::*;
 
+    /// 只有資料日期與要抓的日期相同時才合併本益比。
+    #[tokio::test]
+    async fn latest_pe_ratios_only_match_their_data_date() {
+        let body =
+            br#"[{"Date":"1140602","SecuritiesCompanyCode":"5483","PriceEarningRatio":"12.34"}]"#;
+        let latest = NaiveDate::from_ymd_opt(2025, 6, 2).unwrap();
+
+        let records = latest_pe_ratios("test", body, latest).await.unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(pe_ratio_data_date(&records), Some(latest));
+
+        let older = NaiveDate::from_ymd_opt(2025, 5, 30).unwrap();
+        assert!(
+            latest_pe_ratios("test", body, older)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(latest_pe_ratios("test", b"<html>", latest).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_parse_quote_response() {
         let table = Table {
@@ -197,6 +310,7 @@ mod tests {
         };
 
         let pe_ratio = vec![PeRatioAnalysisResponse {
+            date: String::new(),
             security_code: "5483".to_string(),
             price_earning_ratio: "12.34".to_string(),
         }];
@@ -246,6 +360,7 @@ mod tests {
         };
         // 本益比 API 回傳無資料佔位符。
         let pe_ratio = vec![PeRatioAnalysisResponse {
+            date: String::new(),
             security_code: "5483".to_string(),
             price_earning_ratio: "N/A".to_string(),
         }];
@@ -0,0 +1,59 @@
+//! # 上櫃注意股與處置股
+//!
+//! 資料來源為櫃買中心 OpenAPI：
+//! - 注意股：`/openapi/v1/tpex_trading_warning_information`
+//! - 處置股：`/openapi/v1/tpex_disposal_information`
+//!
+//! 兩者皆為最近一次公告的內容，由排程每日收盤後取得。
+
+use anyhow::Result;
+use serde_json::{Map, Value};
+
+use crate::{
+    core::util,
+    infra::crawler::{
+        share::{self, TradingSurveillanceDto},
+        tpex,
+    },
+};
+
+/// 取得上櫃注意股與處置股公告。
+///
+/// 任一清單失敗即回傳錯誤：只拿到一半會讓呼叫端把另一半誤判為「已離開清單」。
+pub async fn visit() -> Result<Vec<TradingSurveillanceDto>> {
+    let attention_url = format!(
+        "https://{}/openapi/v1/tpex_trading_warning_information",
+        tpex::HOST
+    );
+    let disposition_url = format!(
+        "https://{}/openapi/v1/tpex_disposal_information",
+        tpex::HOST
+    );
+    let (attention, disposition) = tokio::try_join!(
+        util::http::get_json::<Vec<Map<String, Value>>>(&attention_url),
+        util::http::get_json::<Vec<Map<String, Value>>>(&disposition_url)
+    )?;
+
+    let mut result = share::map_surveillance_items(false, &attention);
+    result.extend(share::map_surveillance_items(true, &disposition));
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_visit() {
+        dotenvy::dotenv().ok();
+        match visit().await {
+            Ok(result) => {
+                tracing::debug!("result: {:?}", result);
+            }
+            Err(why) => {
+                tracing::debug!("Failed to visit because {:?}", why);
+            }
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    infra::archive::{self, ArchiveKey},
+    infra::crawler::{
+        share::{self, CapitalChangeDto, CapitalChangeKind},
+        twse,
+    },
+};
+
+/// TWSE 減資（TWTAUU）／變更面額（TWTB8U）恢復買賣參考價 API 回應。
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapitalChangeResponse {
+    /// 回應狀態字串；查無資料時不是 `OK`。
+    pub stat: Option<String>,
+    /// 欄位名稱。
+    #[serde(default)]
+    pub fields: Vec<String>,
+    /// 原始資料列。
+    #[serde(default)]
+    pub data: Vec<Vec<serde_json::Value>>,
+}
+
+/// 取得上市股票在區間內恢復買賣的減資或變更面額資料
+///
+/// 區間以「恢復買賣日期」篩選；公告通常早於恢復買賣日數天，因此區間可以
+/// 涵蓋未來日期。查無資料時回傳空陣列。
+pub async fn visit(
+    kind: CapitalChangeKind,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<CapitalChangeDto>> {
+    let params = archive_params(from, to);
+    let url = format!(
+        "https://www.{}/rwd/zh/{}?response=json&startDate={}&endDate={}",
+        twse::HOST,
+        endpoint(kind),
+        from.format("%Y%m%d"),
+        to.format("%Y%m%d"),
+    );
+
+    let response: CapitalChangeResponse =
+        archive::fetch_json(&url, archive_key(kind, &params)).await?;
+    Ok(parse_response(kind, response))
+}
+
+/// 以本機封存的原始回應重跑解析，不連線 TWSE；查無封存時回傳 `None`。
+pub async fn visit_archived(
+    kind: CapitalChangeKind,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Option<Vec<CapitalChangeDto>>> {
+    let params = archive_params(from, to);
+    let Some(body) = archive::load_latest(archive_key(kind, &params)).await? else {
+        return Ok(None);
+    };
+    let response: CapitalChangeResponse = serde_json::from_slice(&body).with_context(|| {
+        format!(
+            "Failed to parse archived TWSE {} of {params}",
+            endpoint(kind)
+        )
+    })?;
+
+    Ok(Some(parse_response(kind, response)))
+}
+
+/// 各種類對應的 API 路徑。
+fn endpoint(kind: CapitalChangeKind) -> &'static str {
+    match kind {
+        CapitalChangeKind::CapitalReduction => "reducation/TWTAUU",
+        CapitalChangeKind::ParValueChange => "change/TWTB8U",
+    }
+}
+
+/// 封存參數：查詢區間 `YYYYMMDD-YYYYMMDD`。
+fn archive_params(from: NaiveDate, to: NaiveDate) -> String {
+    format!("{}-{}", from.format("%Y%m%d"), to.format("%Y%m%d"))
+}
+
+/// 上市恢復買賣參考價的封存鍵。
+fn archive_key(kind: CapitalChangeKind, params: &str) -> ArchiveKey<'_> {
+    let endpoint = match kind {
+        CapitalChangeKind::CapitalReduction => "twtauu",
+        CapitalChangeKind::ParValueChange => "twtb8u",
+    };
+    ArchiveKey::new("twse", endpoint, params)
+}
+
+/// 檢查回應狀態後整理資料列；狀態不是 `OK` 時回傳空陣列。
+fn parse_response(
+    kind: CapitalChangeKind,
+    response: CapitalChangeResponse,
+) -> Vec<CapitalChangeDto> {
+    let stat = response.stat.unwrap_or_default();
+    if !stat.eq_ignore_ascii_case("OK") {
+        tracing::debug!("取得上市{}恢復買賣資料無資料: {stat}", kind.label());
+        return Vec::new();
+    }
+
+    share::map_capital_change_rows(kind, &response.fields, &response.data)
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    /// 驗證 TWTB8U 回應（無「減資原因」欄）的解析。
+    #[test]
+    fn parse_response_maps_par_value_change() {
+        let response: CapitalChangeResponse = serde_json::from_str(
+            r#"{
+                "stat": "OK",
+                "fields": ["恢復買賣日期","股票代號","名稱","停止買賣前收盤價格","恢復買賣參考價","漲停價格","跌停價格","開始交易基準價","詳細資料"],
+                "data": [["115/06/18","6919","康霈*","1,000.00","100.00","110.00","90.00","100.00",""]]
+            }"#,
+        )
+        .unwrap();
+
+        let result = parse_response(CapitalChangeKind::ParValueChange, response);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].stock_symbol, "6919");
+        assert_eq!(result[0].kind, CapitalChangeKind::ParValueChange);
+        assert_eq!(result[0].last_close, dec!(1000.00));
+        assert_eq!(result[0].reference_price, dec!(100.00));
+        assert!(result[0].reason.is_empty());
+    }
+
+    /// 查無資料時 stat 不是 OK，應得到空陣列而非錯誤。
+    #[test]
+    fn parse_response_without_data_is_empty() {
+        let response = CapitalChangeResponse {
+            stat: Some("很抱歉，沒有符合條件的資料!".to_string()),
+            ..Default::default()
+        };
+        assert!(parse_response(CapitalChangeKind::CapitalReduction, response).is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_visit() {
+        dotenvy::dotenv().ok();
+        let from = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2026, 4, 30).unwrap();
+        match visit(CapitalChangeKind::CapitalReduction, from, to).await {
+            Ok(result) => {
+                tracing::debug!("result: {:?}", result);
+            }
+            Err(why) => {
+                tracing::debug!("Failed to visit because {:?}", why);
+            }
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    infra::archive::{self, ArchiveKey},
+    infra::crawler::{
+        share::{self, InstitutionalTradeDto},
+        twse,
+    },
+};
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// TWSE 三大法人買賣超日報（T86）API 回應。
+pub struct T86Response {
+    /// 回應狀態字串；非交易日或尚未公布時不是 `OK`。
+    pub stat: Option<String>,
+    /// 查詢日期。
+    pub date: Option<String>,
+    /// 回應標題。
+    pub title: Option<String>,
+    /// 欄位名稱清單。
+    #[serde(default)]
+    pub fields: Vec<String>,
+    /// 原始資料列。
+    #[serde(default)]
+    pub data: Vec<Vec<serde_json::Value>>,
+}
+
+/// T86 每列應有的欄位數。
+const T86_COLUMNS: usize = 19;
+
+/// 取得上市股票三大法人買賣超日報
+///
+/// 資料來源：`/rwd/zh/fund/T86?selectType=ALLBUT0999`（不含權證、牛熊證）。
+/// 非交易日或 TWSE 尚未公布時回傳空陣列。
+pub async fn visit(date: NaiveDate) -> Result<Vec<InstitutionalTradeDto>> {
+    let date_str = date.format("%Y%m%d").to_string();
+    let url = format!(
+        "https://www.{}/rwd/zh/fund/T86?date={}&selectType=ALLBUT0999&response=json",
+        twse::HOST,
+        date_str
+    );
+
+    let response: T86Response = archive::fetch_json(&url, archive_key(&date_str)).await?;
+    Ok(parse_response(response, date))
+}
+
+/// 以本機封存的原始回應重跑解析，不連線 TWSE；查無封存時回傳 `None`。
+pub async fn visit_archived(date: NaiveDate) -> Result<Option<Vec<InstitutionalTradeDto>>> {
+    let date_str = date.format("%Y%m%d").to_string();
+    let Some(body) = archive::load_latest(archive_key(&date_str)).await? else {
+        return Ok(None);
+    };
+    let response: T86Response = serde_json::from_slice(&body)
+        .with_context(|| format!("Failed to parse archived TWSE T86 of {date_str}"))?;
+
+    Ok(Some(parse_response(response, date)))
+}
+
+/// 上市三大法人日報的封存鍵；參數為資料日期 `YYYYMMDD`。
+fn archive_key(date_str: &str) -> ArchiveKey<'_> {
+    ArchiveKey::new("twse", "t86", date_str)
+}
+
+/// 檢查回應狀態後整理資料列；狀態不是 `OK` 時回傳空陣列。
+fn parse_response(response: T86Response, date: NaiveDate) -> Vec<InstitutionalTradeDto> {
+    let stat = response.stat.unwrap_or_default();
+    if !stat.eq_ignore_ascii_case("OK") {
+        tracing::warn!("取得上市三大法人買賣超 {date} 無資料: {stat}");
+        return Vec::new();
+    }
+
+    map_t86_rows(&response.data, date)
+}
+
+/// 將 T86 的原始資料列整理成 [`InstitutionalTradeDto`] 清單。
+///
+/// # 欄位對應（每列 19 欄）
+/// - `[0]` 證券代號、`[2..=4]` 外陸資（不含外資自營商）買進／賣出／買賣超、
+///   `[7]` 外資自營商買賣超、`[8..=10]` 投信買進／賣出／買賣超、
+///   `[11]` 自營商買賣超合計、`[12..=14]` 自營商（自行買賣）、
+///   `[15..=17]` 自營商（避險）、`[18]` 三大法人買賣超合計。
+/// - T86 沒有自營商合計的買進／賣出欄，以自行買賣與避險相加。
+/// - 欄位數不符的列直接略過；數值無法解析的列記 warning 後略過。
+fn map_t86_rows(data: &[Vec<serde_json::Value>], date: NaiveDate) -> Vec<InstitutionalTradeDto> {
+    let mut result = Vec::with_capacity(data.len());
+
+    for item in data {
+        if item.len() != T86_COLUMNS {
+            continue;
+        }
+        let cells: Vec<&str> = item.iter().map(|v| v.as_str().unwrap_or("")).collect();
+        match map_t86_row(&cells, date) {
+            Ok(dto) => result.push(dto),
+            Err(why) => tracing::warn!("略過無法解析的 T86 資料列 {:?}: {why:#}", cells[0]),
+        }
+    }
+
+    result
+}
+
+/// 整理單一 T86 資料列。
+fn map_t86_row(cells: &[&str], date: NaiveDate) -> Result<InstitutionalTradeDto> {
+    let shares = |index: usize| share::parse_shares(cells[index]);
+    let stock_symbol = cells[0].trim().to_string();
+    if stock_symbol.is_empty() {
+        anyhow::bail!("empty stock symbol");
+    }
+
+    Ok(InstitutionalTradeDto {
+        stock_symbol,
+        date,
+        foreign_buy: shares(2)?,
+        foreign_sell: shares(3)?,
+        foreign_net: shares(4)?,
+        foreign_dealer_net: shares(7)?,
+        investment_trust_buy: shares(8)?,
+        investment_trust_sell: shares(9)?,
+        investment_trust_net: shares(10)?,
+        dealer_buy: shares(12)? + shares(15)?,
+        dealer_sell: shares(13)? + shares(16)?,
+        dealer_net: shares(11)?,
+        dealer_proprietary_net: shares(14)?,
+        dealer_hedge_net: shares(17)?,
+        total_net: shares(18)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn t86_row(symbol: &str, investment_trust_net: &str) -> Vec<serde_json::Value> {
+        vec![
+            json!(symbol),
+            json!("台積電          "),
+            json!("20,000,000"),
+            json!("25,000,000"),
+            json!("-5,000,000"),
+            json!("0"),
+            json!("0"),
+            json!("0"),
+            json!("3,000,000"),
+            json!("1,000,000"),
+            json!(investment_trust_net),
+            json!("150,000"),
+            json!("500,000"),
+            json!("400,000"),
+            json!("100,000"),
+            json!("300,000"),
+            json!("250,000"),
+            json!("50,000"),
+            json!("-2,850,000"),
+        ]
+    }
+
+    /// 以貼近 T86 真實回應形狀的資料驗證欄位對應與自營商買賣合計。
+    #[test]
+    fn map_t86_rows_maps_rows_and_skips_malformed() {
+        let date = NaiveDate::from_ymd_opt(2026, 4, 30).unwrap();
+        let data = vec![
+            t86_row("2330", "2,000,000"),
+            // 欄位數不足的統計列 → 略過。
+            vec![json!("合計"), json!("123")],
+            // 數值被污染 → 拒絕該列。
+            t86_row("2317", "N/A?"),
+        ];
+
+        let result = map_t86_rows(&data, date);
+
+        assert_eq!(result.len(), 1);
+        let dto = &result[0];
+        assert_eq!(dto.stock_symbol, "2330");
+        assert_eq!(dto.date, date);
+        assert_eq!(dto.foreign_buy, 20_000_000);
+        assert_eq!(dto.foreign_sell, 25_000_000);
+        assert_eq!(dto.foreign_net, -5_000_000);
+        assert_eq!(dto.investment_trust_net, 2_000_000);
+        assert_eq!(dto.dealer_buy, 800_000);
+        assert_eq!(dto.dealer_sell, 650_000);
+        assert_eq!(dto.dealer_net, 150_000);
+        assert_eq!(dto.dealer_proprietary_net, 100_000);
+        assert_eq!(dto.dealer_hedge_net, 50_000);
+        assert_eq!(dto.total_net, -2_850_000);
+    }
+
+    /// 非交易日 TWSE 回傳的狀態不是 OK，應得到空陣列而非錯誤。
+    #[test]
+    fn parse_response_returns_empty_when_stat_is_not_ok() {
+        let response = T86Response {
+            stat: Some("很抱歉，沒有符合條件的資料!".to_string()),
+            ..Default::default()
+        };
+        let date = NaiveDate::from_ymd_opt(2026, 5, 2).unwrap();
+
+        assert!(parse_response(response, date).is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_visit() {
+        dotenvy::dotenv().ok();
+        let date = NaiveDate::from_ymd_opt(2026, 4, 30).unwrap();
+        match visit(date).await {
+            Ok(result) => {
+                tracing::debug!("result: {}", result.len());
+            }
+            Err(why) => {
+                tracing::debug!("Failed to visit because {:?}", why);
+            }
+        }
+    }
+}
@@ -0,0 +1,234 @@
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    infra::archive::{self, ArchiveKey},
+    infra::crawler::{
+        share::{self, MarginTradingDto},
+        twse,
+    },
+};
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// TWSE 融資融券餘額（MI_MARGN）API 回應。
+pub struct MarginResponse {
+    /// 回應狀態字串；非交易日或尚未公布時不是 `OK`。
+    pub stat: Option<String>,
+    /// 查詢日期。
+    pub date: Option<String>,
+    /// 回應中的資料表清單；第一個是信用交易統計，第二個是個股彙總。
+    #[serde(default)]
+    pub tables: Vec<Table>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// MI_MARGN 回應中的單一表格區塊。
+pub struct Table {
+    /// 表格標題。
+    pub title: Option<String>,
+    /// 原始資料列。
+    #[serde(default)]
+    pub data: Vec<Vec<serde_json::Value>>,
+}
+
+/// MI_MARGN 個股彙總每列應有的欄位數。
+const MARGIN_COLUMNS: usize = 16;
+
+/// 取得上市股票融資融券餘額
+///
+/// 資料來源：`/rwd/zh/marginTrading/MI_MARGN?selectType=ALL`，數量單位為「張」。
+/// 非交易日或 TWSE 尚未公布時回傳空陣列。
+pub async fn visit(date: NaiveDate) -> Result<Vec<MarginTradingDto>> {
+    let date_str = date.format("%Y%m%d").to_string();
+    let url = format!(
+        "https://www.{}/rwd/zh/marginTrading/MI_MARGN?date={}&selectType=ALL&response=json",
+        twse::HOST,
+        date_str
+    );
+
+    let response: MarginResponse = archive::fetch_json(&url, archive_key(&date_str)).await?;
+    Ok(parse_response(response, date))
+}
+
+/// 以本機封存的原始回應重跑解析，不連線 TWSE；查無封存時回傳 `None`。
+pub async fn visit_archived(date: NaiveDate) -> Result<Option<Vec<MarginTradingDto>>> {
+    let date_str = date.format("%Y%m%d").to_string();
+    let Some(body) = archive::load_latest(archive_key(&date_str)).await? else {
+        return Ok(None);
+    };
+    let response: MarginResponse = serde_json::from_slice(&body)
+        .with_context(|| format!("Failed to parse archived TWSE MI_MARGN of {date_str}"))?;
+
+    Ok(Some(parse_response(response, date)))
+}
+
+/// 上市融資融券餘額的封存鍵；參數為資料日期 `YYYYMMDD`。
+fn archive_key(date_str: &str) -> ArchiveKey<'_> {
+    ArchiveKey::new("twse", "mi_margn", date_str)
+}
+
+/// 檢查回應狀態後整理各表格的資料列；狀態不是 `OK` 時回傳空陣列。
+///
+/// 統計表的欄位數與個股彙總不同，會在欄位數檢查時自然略過，
+/// 不依賴表格順序。
+fn parse_response(response: MarginResponse, date: NaiveDate) -> Vec<MarginTradingDto> {
+    let stat = response.stat.unwrap_or_default();
+    if !stat.eq_ignore_ascii_case("OK") {
+        tracing::warn!("取得上市融資融券餘額 {date} 無資料: {stat}");
+        return Vec::new();
+    }
+
+    response
+        .tables
+        .iter()
+        .flat_map(|table| map_margin_rows(&table.data, date))
+        .collect()
+}
+
+/// 將 MI_MARGN 個股彙總的原始資料列整理成 [`MarginTradingDto`] 清單。
+///
+/// # 欄位對應（每列 16 欄）
+/// - `[0]` 代號、`[2..=7]` 融資買進／賣出／現金償還／前日餘額／今日餘額／限額、
+///   `[8..=13]` 融券買進（回補）／賣出（放空）／現券償還／前日餘額／今日餘額／限額、
+///   `[14]` 資券互抵、`[15]` 註記（未使用）。
+/// - 欄位數不符的列直接略過；數值無法解析的列記 warning 後略過。
+fn map_margin_rows(data: &[Vec<serde_json::Value>], date: NaiveDate) -> Vec<MarginTradingDto> {
+    let mut result = Vec::with_capacity(data.len());
+
+    for item in data {
+        if item.len() != MARGIN_COLUMNS {
+            continue;
+        }
+        let cells: Vec<&str> = item.iter().map(|v| v.as_str().unwrap_or("")).collect();
+        match map_margin_row(&cells, date) {
+            Ok(dto) => result.push(dto),
+            Err(why) => tracing::warn!("略過無法解析的 MI_MARGN 資料列 {:?}: {why:#}", cells[0]),
+        }
+    }
+
+    result
+}
+
+/// 整理單一 MI_MARGN 資料列。
+fn map_margin_row(cells: &[&str], date: NaiveDate) -> Result<MarginTradingDto> {
+    let lots = |index: usize| share::parse_shares(cells[index]);
+    let stock_symbol = cells[0].trim().to_string();
+    if stock_symbol.is_empty() {
+        anyhow::bail!("empty stock symbol");
+    }
+
+    Ok(MarginTradingDto {
+        stock_symbol,
+        date,
+        margin_buy: lots(2)?,
+        margin_sell: lots(3)?,
+        margin_redemption: lots(4)?,
+        margin_previous_balance: lots(5)?,
+        margin_balance: lots(6)?,
+        margin_quota: lots(7)?,
+        short_cover: lots(8)?,
+        short_sell: lots(9)?,
+        short_redemption: lots(10)?,
+        short_previous_balance: lots(11)?,
+        short_balance: lots(12)?,
+        short_quota: lots(13)?,
+        offset: lots(14)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// 驗證個股彙總的欄位對應，並略過統計表等欄位數不符的列。
+    #[test]
+    fn parse_response_maps_stock_rows() {
+        let date = NaiveDate::from_ymd_opt(2026, 4, 30).unwrap();
+        let response = MarginResponse {
+            stat: Some("OK".to_string()),
+            date: Some("20260430".to_string()),
+            tables: vec![
+                Table {
+                    title: Some("信用交易統計".to_string()),
+                    data: vec![vec![
+                        json!("融資(交易單位)"),
+                        json!("1"),
+                        json!("2"),
+                        json!("3"),
+                        json!("4"),
+                        json!("5"),
+                    ]],
+                },
+                Table {
+                    title: Some("融資融券彙總".to_string()),
+                    data: vec![
+                        [
+                            "2330",
+                            "台積電",
+                            "1,234",
+                            "1,000",
+                            "12",
+                            "20,100",
+                            "20,322",
+                            "6,482,000",
+                            "30",
+                            "210",
+                            "0",
+                            "300",
+                            "480",
+                            "6,482,000",
+                            "5",
+                            "",
+                        ]
+                        .iter()
+                        .map(|s| json!(s))
+                        .collect(),
+                    ],
+                },
+            ],
+        };
+
+        let result = parse_response(response, date);
+
+        assert_eq!(result.len(), 1);
+        let dto = &result[0];
+        assert_eq!(dto.stock_symbol, "2330");
+        assert_eq!(dto.margin_buy, 1_234);
+        assert_eq!(dto.margin_redemption, 12);
+        assert_eq!(dto.margin_previous_balance, 20_100);
+        assert_eq!(dto.margin_balance, 20_322);
+        assert_eq!(dto.margin_quota, 6_482_000);
+        assert_eq!(dto.short_cover, 30);
+        assert_eq!(dto.short_sell, 210);
+        assert_eq!(dto.short_balance, 480);
+        assert_eq!(dto.offset, 5);
+    }
+
+    /// 非交易日狀態不是 OK，應得到空陣列而非錯誤。
+    #[test]
+    fn parse_response_without_ok_is_empty() {
+        let date = NaiveDate::from_ymd_opt(2026, 5, 2).unwrap();
+        let response = MarginResponse {
+            stat: Some("很抱歉，沒有符合條件的資料!".to_string()),
+            ..Default::default()
+        };
+        assert!(parse_response(response, date).is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_visit() {
+        dotenvy::dotenv().ok();
+        let date = NaiveDate::from_ymd_opt(2026, 4, 30).unwrap();
+        match visit(date).await {
+            Ok(result) => {
+                tracing::debug!("result: {}", result.len());
+            }
+            Err(why) => {
+                tracing::debug!("Failed to visit because {:?}", why);
+            }
+        }
+    }
+}
@@ -1,11 +1,17 @@
+/// 減資／變更面額恢復買賣參考價
+pub mod capital_change;
 /// 台股財報
 pub mod eps;
 /// ETF 資訊
 pub mod etf;
 /// 台股休市日期
 pub mod holiday_schedule;
+/// 三大法人買賣超日報
+pub mod institutional_investor;
 /// 國際證券辨識
 pub mod international_securities_identification_number;
+/// 融資融券餘額
+pub mod margin_trading;
 /// 公開申購公告-抽籤日程表
 pub mod public;
 /// 外資及陸資投資持股
@@ -20,5 +26,7 @@ pub mod stock_day;
 pub mod suspend_listing;
 /// 台股加權指數
 pub mod taiwan_capitalization_weighted_stock_index;
+/// 注意股與處置股
+pub mod trading_surveillance;
 
 pub const HOST: &str = "twse.com.tw";
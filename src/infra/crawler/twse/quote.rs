@@ -1,11 +1,11 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{Local, NaiveDate};
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    core::util::http,
+    infra::archive::{self, ArchiveKey},
     infra::cache::{TTL, TtlCacheInner},
     infra::crawler::{share, share::DailyQuoteDto, twse},
 };
@@ -112,10 +112,30 @@ pub async fn visit(date: NaiveDate) -> Result<Vec<DailyQuoteDto>> {
     );
 
     //let headers = build_headers().await;
-    let data = http::get_json::<ListedResponse>(&url).await?;
+    let data: ListedResponse = archive::fetch_json(&url, archive_key(&date_str)).await?;
     parse_listed_response(data, date).await
 }
 
+/// 以本機封存的原始回應重跑解析，不連線 TWSE。
+///
+/// 查無該日封存時回傳 `None`，由呼叫端決定是否略過或改為線上重抓。
+/// 不經過 TTL 去重，一律回傳封存內的整日資料。
+pub async fn visit_archived(date: NaiveDate) -> Result<Option<Vec<DailyQuoteDto>>> {
+    let date_str = date.format("%Y%m%d").to_string();
+    let Some(body) = archive::load_latest(archive_key(&date_str)).await? else {
+        return Ok(None);
+    };
+    let data: ListedResponse = serde_json::from_slice(&body)
+        .with_context(|| format!("Failed to parse archived TWSE MI_INDEX of {date_str}"))?;
+
+    parse_listed(data, date, false).await.map(Some)
+}
+
+/// 上市收盤行情的封存鍵；參數為資料日期 `YYYYMMDD`。
+fn archive_key(date_str: &str) -> ArchiveKey<'_> {
+    ArchiveKey::new("twse", "quote", date_str)
+}
+
 /// 解析 TWSE MI_INDEX API 的回應資料，並將其轉換為 `DailyQuoteDto` 列表。
 ///
 /// # 參數
@@ -127,6 +147,18 @@ pub async fn visit(date: NaiveDate) -> Result<Vec<DailyQuoteDto>> {
 pub async fn parse_listed_response(
     data: ListedResponse,
     date: NaiveDate,
+) -> Result<Vec<DailyQuoteDto>> {
+    parse_listed(data, date, true).await
+}
+
+/// [`parse_listed_response`] 的實作。
+///
+/// `skip_processed` 為 `true` 時以全域 TTL 略過 24 小時內已處理過的代號；
+/// 重跑封存時傳 `false`，整日資料都要重新產出，且不能為此清空線上流程共用的快取。
+async fn parse_listed(
+    data: ListedResponse,
+    date: NaiveDate,
+    skip_processed: bool,
 ) -> Result<Vec<DailyQuoteDto>> {
     // 檢查 API 回應狀態，如查無資料則直接返回空陣列
     if let Some(stat) = &data.stat {
@@ -182,7 +214,7 @@ pub async fn parse_listed_response(
             // 檢查記憶體中的 TTL 快取，避免重複處理相同日期的資料
             let daily_quote_memory_key = format!("{}-{}", date.format("%Y%m%d"), dto.symbol);
 
-            if TTL.daily_quote_contains_key(&daily_quote_memory_key) {
+            if skip_processed && TTL.daily_quote_contains_key(&daily_quote_memory_key) {
                 continue;
             }
 
@@ -442,6 +474,34 @@ mod tests {
         assert!(err.to_string().contains("source format may have changed"));
     }
 
+    /// 驗證線上解析會以 TTL 略過已處理的代號，封存重跑則不受影響。
+    #[tokio::test]
+    async fn test_parse_listed_skip_processed_only_for_live_path() {
+        let date = NaiveDate::from_ymd_opt(2001, 1, 2).unwrap();
+        let response = || ListedResponse {
+            stat: Some("OK".to_string()),
+            tables: vec![Table {
+                title: Some("每日收盤行情".to_string()),
+                fields: Some(quote_table_fields()),
+                data: Some(vec![make_valid_row("9301"), make_valid_row("9302")]),
+                hints: None,
+            }],
+        };
+        TTL.daily_quote_set(
+            "20010102-9301".to_string(),
+            String::new(),
+            Duration::from_secs(60),
+        );
+
+        let live = parse_listed_response(response(), date).await.unwrap();
+        let archived = parse_listed(response(), date, false).await.unwrap();
+
+        assert_eq!(live.len(), 1);
+        assert_eq!(live[0].symbol, "9302");
+        assert_eq!(archived.len(), 2);
+        assert!(TTL.daily_quote_contains_key("20010102-9301"));
+    }
+
     #[tokio::test]
     #[ignore]
     async fn test_visit() {
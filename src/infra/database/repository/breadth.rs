@@ -0,0 +1,431 @@
+use crate::domain::breadth::{
+    BreadthCounts, BreadthIndicator, repository::BreadthIndicatorRepository,
+};
+use crate::infra::database;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::FromRow;
+
+/// 基於 PostgreSQL 的市場廣度指標倉儲實現 (PgBreadthIndicatorRepository)。
+///
+/// 讀取 `daily_stock_price_stats` 與 `"DailyQuotes"` 的年內高低點，並讀寫
+/// `market_breadth_indicator`。
+pub struct PgBreadthIndicatorRepository;
+
+impl PgBreadthIndicatorRepository {
+    /// 建立新的 PgBreadthIndicatorRepository 實例。
+    pub fn new() -> Self {
+        PgBreadthIndicatorRepository
+    }
+}
+
+impl Default for PgBreadthIndicatorRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 廣度原始統計的資料列。
+#[derive(FromRow)]
+struct CountsDbRow {
+    date: NaiveDate,
+    market_id: i32,
+    advances: i32,
+    declines: i32,
+    unchanged: i32,
+    new_highs: i32,
+    new_lows: i32,
+    above_5: i32,
+    below_5: i32,
+    above_20: i32,
+    below_20: i32,
+    above_60: i32,
+    below_60: i32,
+    above_120: i32,
+    below_120: i32,
+    above_240: i32,
+    below_240: i32,
+}
+
+impl From<CountsDbRow> for BreadthCounts {
+    fn from(row: CountsDbRow) -> Self {
+        BreadthCounts {
+            date: row.date,
+            market_id: row.market_id,
+            advances: row.advances,
+            declines: row.declines,
+            unchanged: row.unchanged,
+            new_highs: row.new_highs,
+            new_lows: row.new_lows,
+            above_moving_average: [
+                row.above_5,
+                row.above_20,
+                row.above_60,
+                row.above_120,
+                row.above_240,
+            ],
+            below_moving_average: [
+                row.below_5,
+                row.below_20,
+                row.below_60,
+                row.below_120,
+                row.below_240,
+            ],
+        }
+    }
+}
+
+/// 廣度指標的資料列。
+#[derive(FromRow)]
+struct IndicatorDbRow {
+    date: NaiveDate,
+    stock_exchange_market_id: i32,
+    advances: i32,
+    declines: i32,
+    unchanged: i32,
+    net_advances: i32,
+    ad_line: i64,
+    ratio_adjusted_net_advances: Decimal,
+    ema_19: Decimal,
+    ema_39: Decimal,
+    mcclellan_oscillator: Decimal,
+    summation_index: Decimal,
+    new_highs: i32,
+    new_lows: i32,
+    pct_above_ma5: Option<Decimal>,
+    pct_above_ma20: Option<Decimal>,
+    pct_above_ma60: Option<Decimal>,
+    pct_above_ma120: Option<Decimal>,
+    pct_above_ma240: Option<Decimal>,
+}
+
+impl From<IndicatorDbRow> for BreadthIndicator {
+    fn from(row: IndicatorDbRow) -> Self {
+        BreadthIndicator {
+            date: row.date,
+            market_id: row.stock_exchange_market_id,
+            advances: row.advances,
+            declines: row.declines,
+            unchanged: row.unchanged,
+            net_advances: row.net_advances,
+            ad_line: row.ad_line,
+            ratio_adjusted_net_advances: row.ratio_adjusted_net_advances,
+            ema_19: row.ema_19,
+            ema_39: row.ema_39,
+            mcclellan_oscillator: row.mcclellan_oscillator,
+            summation_index: row.summation_index,
+            new_highs: row.new_highs,
+            new_lows: row.new_lows,
+            pct_above_moving_average: [
+                row.pct_above_ma5,
+                row.pct_above_ma20,
+                row.pct_above_ma60,
+                row.pct_above_ma120,
+                row.pct_above_ma240,
+            ],
+        }
+    }
+}
+
+/// 指標表的欄位清單，與 [`IndicatorDbRow`] 欄位一一對應。
+///
+/// 以 `format!` 組進 SQL 的只有這個常數，不含外部輸入。
+const INDICATOR_COLUMNS: &str = r#"
+    date, stock_exchange_market_id, advances, declines, unchanged, net_advances, ad_line,
+    ratio_adjusted_net_advances, ema_19, ema_39, mcclellan_oscillator, summation_index,
+    new_highs, new_lows, pct_above_ma5, pct_above_ma20, pct_above_ma60, pct_above_ma120,
+    pct_above_ma240
+"#;
+
+#[async_trait]
+impl BreadthIndicatorRepository for PgBreadthIndicatorRepository {
+    async fn fetch_counts(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<BreadthCounts>> {
+        // 新高新低的母體與 daily_stock_price_stats 相同（未停牌且有估價的股票），
+        // 並排除年線尚未成形的新上市股：其「年內高低點」只涵蓋上市後數日，
+        // 幾乎天天創新高或新低，會灌水家數。
+        let sql = r#"
+            WITH extremes AS (
+                SELECT
+                    dq."Date" AS date,
+                    CASE
+                        WHEN GROUPING(s.stock_exchange_market_id) = 1 THEN 0
+                        ELSE s.stock_exchange_market_id
+                    END AS market_id,
+                    COUNT(*) FILTER (
+                        WHERE dq."HighestPrice" >= dq.maximum_price_in_year
+                    )::int AS new_highs,
+                    COUNT(*) FILTER (
+                        WHERE dq."LowestPrice" <= dq.minimum_price_in_year
+                    )::int AS new_lows
+                FROM stocks s
+                JOIN estimate e
+                  ON s."SuspendListing" = FALSE
+                 AND s.stock_symbol = e.security_code
+                JOIN "DailyQuotes" dq
+                  ON e.date = dq."Date"
+                 AND e.security_code = dq.stock_symbol
+                WHERE e.date BETWEEN $1 AND $2
+                  AND dq."MovingAverage240" > 0
+                  AND dq.maximum_price_in_year > 0
+                  AND dq.minimum_price_in_year > 0
+                GROUP BY GROUPING SETS ((dq."Date", s.stock_exchange_market_id), (dq."Date"))
+                -- 與統計表一致：市場編號 0 只保留全市場合計。
+                HAVING GROUPING(s.stock_exchange_market_id) = 1
+                    OR s.stock_exchange_market_id != 0
+            )
+            SELECT
+                st.date,
+                st.stock_exchange_market_id AS market_id,
+                st.stocks_up AS advances,
+                st.stocks_down AS declines,
+                st.stocks_unchanged AS unchanged,
+                COALESCE(x.new_highs, 0) AS new_highs,
+                COALESCE(x.new_lows, 0) AS new_lows,
+                st.above_5_day_moving_average AS above_5,
+                st.below_5_day_moving_average AS below_5,
+                st.above_20_day_moving_average AS above_20,
+                st.below_20_day_moving_average AS below_20,
+                st.above_60_day_moving_average AS above_60,
+                st.below_60_day_moving_average AS below_60,
+                st.above_120_day_moving_average AS above_120,
+                st.below_120_day_moving_average AS below_120,
+                st.above_240_day_moving_average AS above_240,
+                st.below_240_day_moving_average AS below_240
+            FROM daily_stock_price_stats st
+            LEFT JOIN extremes x
+              ON x.date = st.date
+             AND x.market_id = st.stock_exchange_market_id
+            WHERE st.date BETWEEN $1 AND $2
+            ORDER BY st.date, st.stock_exchange_market_id
+        "#;
+
+        let rows = sqlx::query_as::<_, CountsDbRow>(sql)
+            .bind(from)
+            .bind(to)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch breadth counts from PG")?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn fetch_latest_before(&self, date: NaiveDate) -> Result<Vec<BreadthIndicator>> {
+        let sql = format!(
+            r#"
+            SELECT DISTINCT ON (stock_exchange_market_id) {INDICATOR_COLUMNS}
+            FROM market_breadth_indicator
+            WHERE date < $1
+            ORDER BY stock_exchange_market_id, date DESC
+            "#
+        );
+
+        let rows = sqlx::query_as::<_, IndicatorDbRow>(sqlx::AssertSqlSafe(sql.as_str()))
+            .bind(date)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch previous breadth indicators from PG")?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn upsert(&self, indicators: &[BreadthIndicator]) -> Result<u64> {
+        if indicators.is_empty() {
+            return Ok(0);
+        }
+
+        let sql = format!(
+            r#"
+            INSERT INTO market_breadth_indicator ({INDICATOR_COLUMNS})
+            SELECT * FROM UNNEST(
+                $1::date[], $2::int[], $3::int[], $4::int[], $5::int[], $6::int[], $7::bigint[],
+                $8::numeric[], $9::numeric[], $10::numeric[], $11::numeric[], $12::numeric[],
+                $13::int[], $14::int[], $15::numeric[], $16::numeric[], $17::numeric[],
+                $18::numeric[], $19::numeric[]
+            )
+            ON CONFLICT (date, stock_exchange_market_id) DO UPDATE SET
+                advances = EXCLUDED.advances,
+                declines = EXCLUDED.declines,
+                unchanged = EXCLUDED.unchanged,
+                net_advances = EXCLUDED.net_advances,
+                ad_line = EXCLUDED.ad_line,
+                ratio_adjusted_net_advances = EXCLUDED.ratio_adjusted_net_advances,
+                ema_19 = EXCLUDED.ema_19,
+                ema_39 = EXCLUDED.ema_39,
+                mcclellan_oscillator = EXCLUDED.mcclellan_oscillator,
+                summation_index = EXCLUDED.summation_index,
+                new_highs = EXCLUDED.new_highs,
+                new_lows = EXCLUDED.new_lows,
+                pct_above_ma5 = EXCLUDED.pct_above_ma5,
+                pct_above_ma20 = EXCLUDED.pct_above_ma20,
+                pct_above_ma60 = EXCLUDED.pct_above_ma60,
+                pct_above_ma120 = EXCLUDED.pct_above_ma120,
+                pct_above_ma240 = EXCLUDED.pct_above_ma240,
+                updated_time = now()
+            "#
+        );
+
+        let len = indicators.len();
+        let mut dates = Vec::with_capacity(len);
+        let mut market_ids = Vec::with_capacity(len);
+        let mut advances = Vec::with_capacity(len);
+        let mut declines = Vec::with_capacity(len);
+        let mut unchanged = Vec::with_capacity(len);
+        let mut net_advances = Vec::with_capacity(len);
+        let mut ad_lines = Vec::with_capacity(len);
+        let mut ratio_adjusted = Vec::with_capacity(len);
+        let mut ema_19 = Vec::with_capacity(len);
+        let mut ema_39 = Vec::with_capacity(len);
+        let mut oscillators = Vec::with_capacity(len);
+        let mut summations = Vec::with_capacity(len);
+        let mut new_highs = Vec::with_capacity(len);
+        let mut new_lows = Vec::with_capacity(len);
+        let mut pct_above: [Vec<Option<Decimal>>; 5] = Default::default();
+        for indicator in indicators {
+            dates.push(indicator.date);
+            market_ids.push(indicator.market_id);
+            advances.push(indicator.advances);
+            declines.push(indicator.declines);
+            unchanged.push(indicator.unchanged);
+            net_advances.push(indicator.net_advances);
+            ad_lines.push(indicator.ad_line);
+            ratio_adjusted.push(indicator.ratio_adjusted_net_advances);
+            ema_19.push(indicator.ema_19);
+            ema_39.push(indicator.ema_39);
+            oscillators.push(indicator.mcclellan_oscillator);
+            summations.push(indicator.summation_index);
+            new_highs.push(indicator.new_highs);
+            new_lows.push(indicator.new_lows);
+            for (column, pct) in pct_above.iter_mut().zip(indicator.pct_above_moving_average) {
+                column.push(pct);
+            }
+        }
+        let [pct_ma5, pct_ma20, pct_ma60, pct_ma120, pct_ma240] = pct_above;
+
+        let result = sqlx::query(sqlx::AssertSqlSafe(sql.as_str()))
+            .bind(dates)
+            .bind(market_ids)
+            .bind(advances)
+            .bind(declines)
+            .bind(unchanged)
+            .bind(net_advances)
+            .bind(ad_lines)
+            .bind(ratio_adjusted)
+            .bind(ema_19)
+            .bind(ema_39)
+            .bind(oscillators)
+            .bind(summations)
+            .bind(new_highs)
+            .bind(new_lows)
+            .bind(pct_ma5)
+            .bind(pct_ma20)
+            .bind(pct_ma60)
+            .bind(pct_ma120)
+            .bind(pct_ma240)
+            .execute(database::get_connection())
+            .await
+            .context("Failed to upsert breadth indicators to PG")?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn fetch_history(
+        &self,
+        market_id: i32,
+        to: Option<NaiveDate>,
+        days: i64,
+    ) -> Result<Vec<BreadthIndicator>> {
+        // 與 /market/breadth 相同：指定日期時終點只允許落在 31 天回溯窗內。
+        let sql = format!(
+            r#"
+            WITH endpoint AS (
+                SELECT MAX(date) AS date
+                FROM market_breadth_indicator
+                WHERE stock_exchange_market_id = $1
+                  AND ($2::date IS NULL OR (date <= $2 AND date >= $2 - 30))
+            )
+            SELECT {INDICATOR_COLUMNS}
+            FROM market_breadth_indicator
+            WHERE stock_exchange_market_id = $1
+              AND date <= (SELECT date FROM endpoint)
+            ORDER BY date DESC
+            LIMIT $3
+            "#
+        );
+
+        let rows = sqlx::query_as::<_, IndicatorDbRow>(sqlx::AssertSqlSafe(sql.as_str()))
+            .bind(market_id)
+            .bind(to)
+            .bind(days)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch breadth indicator history from PG")?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[cfg_attr(
+        not(feature = "integration-tests"),
+        ignore = "需要外部服務（PostgreSQL/Redis），請加 --features integration-tests 執行"
+    )]
+    async fn test_upsert_and_fetch_history() {
+        dotenvy::dotenv().ok();
+        if database::ping().await.is_err() {
+            println!("跳過 PgBreadthIndicatorRepository DB 整合測試：無資料庫連接");
+            return;
+        }
+
+        let cleanup = || async {
+            let _ = sqlx::query("DELETE FROM market_breadth_indicator WHERE date >= '2099-01-01'")
+                .execute(database::get_connection())
+                .await;
+        };
+        cleanup().await;
+
+        let counts = |day, advances, declines| BreadthCounts {
+            date: NaiveDate::from_ymd_opt(2099, 1, day).unwrap(),
+            market_id: 2,
+            advances,
+            declines,
+            unchanged: 5,
+            new_highs: 3,
+            new_lows: 1,
+            above_moving_average: [10, 10, 10, 10, 0],
+            below_moving_average: [10, 10, 10, 10, 0],
+        };
+        let first = BreadthIndicator::next(&counts(5, 600, 400), None);
+        let second = BreadthIndicator::next(&counts(6, 300, 700), Some(&first));
+        let repo = PgBreadthIndicatorRepository::new();
+        repo.upsert(&[first.clone(), second.clone()])
+            .await
+            .expect("upsert");
+        // 重跑同一天覆蓋舊值。
+        repo.upsert(std::slice::from_ref(&second))
+            .await
+            .expect("upsert again");
+
+        let previous = repo
+            .fetch_latest_before(second.date)
+            .await
+            .expect("fetch latest before");
+        assert!(previous.contains(&first));
+
+        let history = repo
+            .fetch_history(2, Some(second.date), 10)
+            .await
+            .expect("fetch history");
+        cleanup().await;
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0], second);
+        assert_eq!(history[1], first);
+        assert_eq!(history[0].pct_above_moving_average[4], None);
+    }
+}
@@ -181,6 +181,44 @@ impl CagrSourceRepository for PgCagrSourceRepository {
             .collect()
     }
 
+    async fn fetch_last_quote_within(
+        &self,
+        symbols: &[String],
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<(String, NaiveDate, Decimal)>> {
+        if symbols.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let sql = r#"
+            SELECT DISTINCT ON (stock_symbol)
+                   stock_symbol, "Date", "ClosingPrice"
+            FROM "DailyQuotes"
+            WHERE stock_symbol = ANY($1)
+              AND "Date" >= $2 AND "Date" <= $3 AND "ClosingPrice" > 0
+            ORDER BY stock_symbol, "Date" DESC
+        "#;
+
+        let rows = sqlx::query(sql)
+            .bind(symbols)
+            .bind(from)
+            .bind(to)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch last quote within the given range")?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok((
+                    row.try_get::<String, _>("stock_symbol")?,
+                    row.try_get::<NaiveDate, _>("Date")?,
+                    row.try_get::<Decimal, _>("ClosingPrice")?,
+                ))
+            })
+            .collect()
+    }
+
     async fn fetch_dividend_events_since(&self, since: NaiveDate) -> Result<Vec<DividendEvent>> {
         // 兩個必要的防護，缺一結果就會系統性錯誤：
         //
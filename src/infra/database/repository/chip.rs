@@ -0,0 +1,682 @@
+use crate::domain::chip::{
+    entity::{
+        CreditBalance, InstitutionalTrade, MarginTrading, ShareholdingDistribution,
+        ShareholdingTier, TradeVolume,
+    },
+    repository::{
+        InstitutionalTradeRepository, MarginTradingRepository, ShareholdingDistributionRepository,
+    },
+};
+use crate::infra::database;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::FromRow;
+
+/// 基於 PostgreSQL 的三大法人買賣超倉儲實現 (PgInstitutionalTradeRepository)。
+///
+/// 負責 `institutional_investor_trade` 資料表的整日替換寫入與個股歷史查詢。
+pub struct PgInstitutionalTradeRepository;
+
+impl PgInstitutionalTradeRepository {
+    /// 建立新的 PgInstitutionalTradeRepository 實例。
+    pub fn new() -> Self {
+        PgInstitutionalTradeRepository
+    }
+}
+
+impl Default for PgInstitutionalTradeRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 資料庫對應的內部資料列結構體，用於 `sqlx::query_as` 映射。
+#[derive(FromRow)]
+struct InstitutionalTradeDbRow {
+    stock_symbol: String,
+    date: NaiveDate,
+    foreign_buy: i64,
+    foreign_sell: i64,
+    foreign_net: i64,
+    foreign_dealer_net: i64,
+    investment_trust_buy: i64,
+    investment_trust_sell: i64,
+    investment_trust_net: i64,
+    dealer_buy: i64,
+    dealer_sell: i64,
+    dealer_net: i64,
+    dealer_proprietary_net: i64,
+    dealer_hedge_net: i64,
+    total_net: i64,
+}
+
+impl From<InstitutionalTradeDbRow> for InstitutionalTrade {
+    fn from(row: InstitutionalTradeDbRow) -> Self {
+        InstitutionalTrade::new(
+            row.stock_symbol,
+            row.date,
+            TradeVolume::new(row.foreign_buy, row.foreign_sell, row.foreign_net),
+            row.foreign_dealer_net,
+            TradeVolume::new(
+                row.investment_trust_buy,
+                row.investment_trust_sell,
+                row.investment_trust_net,
+            ),
+            TradeVolume::new(row.dealer_buy, row.dealer_sell, row.dealer_net),
+            row.dealer_proprietary_net,
+            row.dealer_hedge_net,
+            row.total_net,
+        )
+    }
+}
+
+#[async_trait]
+impl InstitutionalTradeRepository for PgInstitutionalTradeRepository {
+    /// 於單一交易內刪除該日資料後以 UNNEST 批次寫入。
+    async fn replace_by_date(&self, date: NaiveDate, trades: &[InstitutionalTrade]) -> Result<u64> {
+        let mut symbols = Vec::with_capacity(trades.len());
+        let mut columns: [Vec<i64>; 13] = Default::default();
+        for trade in trades.iter().filter(|trade| trade.date == date) {
+            symbols.push(trade.stock_symbol.clone());
+            let values = [
+                trade.foreign.buy,
+                trade.foreign.sell,
+                trade.foreign.net,
+                trade.foreign_dealer_net,
+                trade.investment_trust.buy,
+                trade.investment_trust.sell,
+                trade.investment_trust.net,
+                trade.dealer.buy,
+                trade.dealer.sell,
+                trade.dealer.net,
+                trade.dealer_proprietary_net,
+                trade.dealer_hedge_net,
+                trade.total_net,
+            ];
+            for (column, value) in columns.iter_mut().zip(values) {
+                column.push(value);
+            }
+        }
+
+        let mut tx = database::get_tx()
+            .await
+            .context("Failed to get_tx in PgInstitutionalTradeRepository::replace_by_date")?;
+
+        sqlx::query("DELETE FROM institutional_investor_trade WHERE date = $1")
+            .bind(date)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to delete institutional investor trades from PG")?;
+
+        // 來源偶有同代號重複列（例如更正後重送），以 DISTINCT ON 只保留最後一筆，
+        // 避免主鍵衝突讓整日寫入失敗。
+        let sql = r#"
+            INSERT INTO institutional_investor_trade (
+                date, stock_symbol,
+                foreign_buy, foreign_sell, foreign_net, foreign_dealer_net,
+                investment_trust_buy, investment_trust_sell, investment_trust_net,
+                dealer_buy, dealer_sell, dealer_net,
+                dealer_proprietary_net, dealer_hedge_net, total_net
+            )
+            SELECT DISTINCT ON (stock_symbol)
+                $1, stock_symbol,
+                foreign_buy, foreign_sell, foreign_net, foreign_dealer_net,
+                investment_trust_buy, investment_trust_sell, investment_trust_net,
+                dealer_buy, dealer_sell, dealer_net,
+                dealer_proprietary_net, dealer_hedge_net, total_net
+            FROM UNNEST(
+                $2::varchar[],
+                $3::bigint[], $4::bigint[], $5::bigint[], $6::bigint[],
+                $7::bigint[], $8::bigint[], $9::bigint[],
+                $10::bigint[], $11::bigint[], $12::bigint[],
+                $13::bigint[], $14::bigint[], $15::bigint[]
+            ) WITH ORDINALITY AS t(
+                stock_symbol,
+                foreign_buy, foreign_sell, foreign_net, foreign_dealer_net,
+                investment_trust_buy, investment_trust_sell, investment_trust_net,
+                dealer_buy, dealer_sell, dealer_net,
+                dealer_proprietary_net, dealer_hedge_net, total_net,
+                ordinality
+            )
+            ORDER BY stock_symbol, ordinality DESC;
+        "#;
+
+        let mut query = sqlx::query(sql).bind(date).bind(&symbols);
+        for column in &columns {
+            query = query.bind(column);
+        }
+        let result = query
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert institutional investor trades to PG")?;
+
+        tx.commit()
+            .await
+            .context("Failed to commit institutional investor trades to PG")?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// 查詢個股區間內的三大法人買賣超。
+    async fn fetch_by_symbol(
+        &self,
+        stock_symbol: &str,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+        limit: i64,
+    ) -> Result<Vec<InstitutionalTrade>> {
+        let sql = r#"
+            SELECT
+                stock_symbol, date,
+                foreign_buy, foreign_sell, foreign_net, foreign_dealer_net,
+                investment_trust_buy, investment_trust_sell, investment_trust_net,
+                dealer_buy, dealer_sell, dealer_net,
+                dealer_proprietary_net, dealer_hedge_net, total_net
+            FROM institutional_investor_trade
+            WHERE stock_symbol = $1
+              AND ($2::date IS NULL OR date >= $2::date)
+              AND ($3::date IS NULL OR date <= $3::date)
+            ORDER BY date DESC
+            LIMIT $4;
+        "#;
+
+        let rows = sqlx::query_as::<_, InstitutionalTradeDbRow>(sql)
+            .bind(stock_symbol)
+            .bind(from)
+            .bind(to)
+            .bind(limit)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch institutional investor trades from PG")?;
+
+        Ok(rows.into_iter().map(InstitutionalTrade::from).collect())
+    }
+}
+
+/// 基於 PostgreSQL 的融資融券餘額倉儲實現 (PgMarginTradingRepository)。
+///
+/// 負責 `margin_trading` 資料表的整日替換寫入與個股歷史查詢；
+/// 融資使用率與券資比於寫入時由領域模型計算後一併保存，供選股直接篩選。
+pub struct PgMarginTradingRepository;
+
+impl PgMarginTradingRepository {
+    /// 建立新的 PgMarginTradingRepository 實例。
+    pub fn new() -> Self {
+        PgMarginTradingRepository
+    }
+}
+
+impl Default for PgMarginTradingRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 資料庫對應的內部資料列結構體，用於 `sqlx::query_as` 映射。
+#[derive(FromRow)]
+struct MarginTradingDbRow {
+    stock_symbol: String,
+    date: NaiveDate,
+    margin_buy: i64,
+    margin_sell: i64,
+    margin_redemption: i64,
+    margin_previous_balance: i64,
+    margin_balance: i64,
+    margin_quota: i64,
+    short_sell: i64,
+    short_cover: i64,
+    short_redemption: i64,
+    short_previous_balance: i64,
+    short_balance: i64,
+    short_quota: i64,
+    offset_volume: i64,
+}
+
+impl From<MarginTradingDbRow> for MarginTrading {
+    fn from(row: MarginTradingDbRow) -> Self {
+        MarginTrading::new(
+            row.stock_symbol,
+            row.date,
+            CreditBalance::new(
+                row.margin_buy,
+                row.margin_sell,
+                row.margin_redemption,
+                row.margin_previous_balance,
+                row.margin_balance,
+                row.margin_quota,
+            ),
+            CreditBalance::new(
+                row.short_cover,
+                row.short_sell,
+                row.short_redemption,
+                row.short_previous_balance,
+                row.short_balance,
+                row.short_quota,
+            ),
+            row.offset_volume,
+        )
+    }
+}
+
+#[async_trait]
+impl MarginTradingRepository for PgMarginTradingRepository {
+    /// 於單一交易內刪除該日資料後以 UNNEST 批次寫入。
+    async fn replace_by_date(&self, date: NaiveDate, tradings: &[MarginTrading]) -> Result<u64> {
+        let mut symbols = Vec::with_capacity(tradings.len());
+        let mut columns: [Vec<i64>; 13] = Default::default();
+        let mut utilizations: Vec<Option<Decimal>> = Vec::with_capacity(tradings.len());
+        let mut ratios: Vec<Option<Decimal>> = Vec::with_capacity(tradings.len());
+        for trading in tradings.iter().filter(|trading| trading.date == date) {
+            symbols.push(trading.stock_symbol.clone());
+            let values = [
+                trading.margin.buy,
+                trading.margin.sell,
+                trading.margin.redemption,
+                trading.margin.previous_balance,
+                trading.margin.balance,
+                trading.margin.quota,
+                trading.short.sell,
+                trading.short.buy,
+                trading.short.redemption,
+                trading.short.previous_balance,
+                trading.short.balance,
+                trading.short.quota,
+                trading.offset,
+            ];
+            for (column, value) in columns.iter_mut().zip(values) {
+                column.push(value);
+            }
+            utilizations.push(trading.margin.utilization_percent());
+            ratios.push(trading.short_to_margin_ratio_percent());
+        }
+
+        let mut tx = database::get_tx()
+            .await
+            .context("Failed to get_tx in PgMarginTradingRepository::replace_by_date")?;
+
+        sqlx::query("DELETE FROM margin_trading WHERE date = $1")
+            .bind(date)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to delete margin trading from PG")?;
+
+        let sql = r#"
+            INSERT INTO margin_trading (
+                date, stock_symbol,
+                margin_buy, margin_sell, margin_redemption,
+                margin_previous_balance, margin_balance, margin_quota,
+                short_sell, short_cover, short_redemption,
+                short_previous_balance, short_balance, short_quota,
+                offset_volume, margin_utilization_percent, short_to_margin_ratio_percent
+            )
+            SELECT DISTINCT ON (stock_symbol)
+                $1, stock_symbol,
+                margin_buy, margin_sell, margin_redemption,
+                margin_previous_balance, margin_balance, margin_quota,
+                short_sell, short_cover, short_redemption,
+                short_previous_balance, short_balance, short_quota,
+                offset_volume, margin_utilization_percent, short_to_margin_ratio_percent
+            FROM UNNEST(
+                $2::varchar[],
+                $3::bigint[], $4::bigint[], $5::bigint[],
+                $6::bigint[], $7::bigint[], $8::bigint[],
+                $9::bigint[], $10::bigint[], $11::bigint[],
+                $12::bigint[], $13::bigint[], $14::bigint[],
+                $15::bigint[], $16::numeric[], $17::numeric[]
+            ) WITH ORDINALITY AS t(
+                stock_symbol,
+                margin_buy, margin_sell, margin_redemption,
+                margin_previous_balance, margin_balance, margin_quota,
+                short_sell, short_cover, short_redemption,
+                short_previous_balance, short_balance, short_quota,
+                offset_volume, margin_utilization_percent, short_to_margin_ratio_percent,
+                ordinality
+            )
+            ORDER BY stock_symbol, ordinality DESC;
+        "#;
+
+        let mut query = sqlx::query(sql).bind(date).bind(&symbols);
+        for column in &columns {
+            query = query.bind(column);
+        }
+        let result = query
+            .bind(&utilizations)
+            .bind(&ratios)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert margin trading to PG")?;
+
+        tx.commit()
+            .await
+            .context("Failed to commit margin trading to PG")?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// 查詢個股區間內的融資融券餘額。
+    async fn fetch_by_symbol(
+        &self,
+        stock_symbol: &str,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+        limit: i64,
+    ) -> Result<Vec<MarginTrading>> {
+        let sql = r#"
+            SELECT
+                stock_symbol, date,
+                margin_buy, margin_sell, margin_redemption,
+                margin_previous_balance, margin_balance, margin_quota,
+                short_sell, short_cover, short_redemption,
+                short_previous_balance, short_balance, short_quota,
+                offset_volume
+            FROM margin_trading
+            WHERE stock_symbol = $1
+              AND ($2::date IS NULL OR date >= $2::date)
+              AND ($3::date IS NULL OR date <= $3::date)
+            ORDER BY date DESC
+            LIMIT $4;
+        "#;
+
+        let rows = sqlx::query_as::<_, MarginTradingDbRow>(sql)
+            .bind(stock_symbol)
+            .bind(from)
+            .bind(to)
+            .bind(limit)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch margin trading from PG")?;
+
+        Ok(rows.into_iter().map(MarginTrading::from).collect())
+    }
+}
+
+/// 基於 PostgreSQL 的集保戶股權分散表倉儲實現 (PgShareholdingDistributionRepository)。
+///
+/// 每檔證券每週以多列（每個持股分級一列）保存於 `shareholding_distribution`。
+pub struct PgShareholdingDistributionRepository;
+
+impl PgShareholdingDistributionRepository {
+    /// 建立新的 PgShareholdingDistributionRepository 實例。
+    pub fn new() -> Self {
+        PgShareholdingDistributionRepository
+    }
+}
+
+impl Default for PgShareholdingDistributionRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 資料庫對應的內部資料列結構體，用於 `sqlx::query_as` 映射。
+#[derive(FromRow)]
+struct ShareholdingTierDbRow {
+    stock_symbol: String,
+    date: NaiveDate,
+    level: i16,
+    holders: i64,
+    shares: i64,
+    percentage: Decimal,
+}
+
+#[async_trait]
+impl ShareholdingDistributionRepository for PgShareholdingDistributionRepository {
+    /// 於單一交易內刪除該週資料後以 UNNEST 批次寫入。
+    async fn replace_by_date(
+        &self,
+        date: NaiveDate,
+        distributions: &[ShareholdingDistribution],
+    ) -> Result<u64> {
+        let mut symbols = Vec::new();
+        let mut levels: Vec<i16> = Vec::new();
+        let mut holders = Vec::new();
+        let mut shares = Vec::new();
+        let mut percentages = Vec::new();
+        for distribution in distributions.iter().filter(|d| d.date == date) {
+            for tier in &distribution.tiers {
+                symbols.push(distribution.stock_symbol.clone());
+                levels.push(tier.level as i16);
+                holders.push(tier.holders);
+                shares.push(tier.shares);
+                percentages.push(tier.percentage);
+            }
+        }
+
+        let mut tx = database::get_tx()
+            .await
+            .context("Failed to get_tx in PgShareholdingDistributionRepository::replace_by_date")?;
+
+        sqlx::query("DELETE FROM shareholding_distribution WHERE date = $1")
+            .bind(date)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to delete shareholding distribution from PG")?;
+
+        let sql = r#"
+            INSERT INTO shareholding_distribution (
+                date, stock_symbol, level, holders, shares, percentage
+            )
+            SELECT DISTINCT ON (stock_symbol, level)
+                $1, stock_symbol, level, holders, shares, percentage
+            FROM UNNEST(
+                $2::varchar[], $3::smallint[], $4::bigint[], $5::bigint[], $6::numeric[]
+            ) WITH ORDINALITY AS t(stock_symbol, level, holders, shares, percentage, ordinality)
+            ORDER BY stock_symbol, level, ordinality DESC;
+        "#;
+
+        let result = sqlx::query(sql)
+            .bind(date)
+            .bind(&symbols)
+            .bind(&levels)
+            .bind(&holders)
+            .bind(&shares)
+            .bind(&percentages)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert shareholding distribution to PG")?;
+
+        tx.commit()
+            .await
+            .context("Failed to commit shareholding distribution to PG")?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// 先取個股最近 `limit` 個資料日期，再一次取回這些日期的全部分級。
+    async fn fetch_by_symbol(
+        &self,
+        stock_symbol: &str,
+        limit: i64,
+    ) -> Result<Vec<ShareholdingDistribution>> {
+        let sql = r#"
+            SELECT stock_symbol, date, level, holders, shares, percentage
+            FROM shareholding_distribution
+            WHERE stock_symbol = $1
+              AND date IN (
+                  SELECT DISTINCT date
+                  FROM shareholding_distribution
+                  WHERE stock_symbol = $1
+                  ORDER BY date DESC
+                  LIMIT $2
+              )
+            ORDER BY date DESC, level;
+        "#;
+
+        let rows = sqlx::query_as::<_, ShareholdingTierDbRow>(sql)
+            .bind(stock_symbol)
+            .bind(limit)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch shareholding distribution from PG")?;
+
+        let mut result: Vec<ShareholdingDistribution> = Vec::new();
+        for row in rows {
+            let tier = ShareholdingTier::new(
+                i32::from(row.level),
+                row.holders,
+                row.shares,
+                row.percentage,
+            );
+            match result.last_mut() {
+                Some(last) if last.date == row.date => last.tiers.push(tier),
+                _ => result.push(ShareholdingDistribution::new(
+                    row.stock_symbol,
+                    row.date,
+                    vec![tier],
+                )),
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[cfg_attr(
+        not(feature = "integration-tests"),
+        ignore = "需要外部服務（PostgreSQL/Redis），請加 --features integration-tests 執行"
+    )]
+    async fn test_replace_by_date_and_fetch_by_symbol() {
+        dotenvy::dotenv().ok();
+        if database::ping().await.is_err() {
+            println!("跳過 PgInstitutionalTradeRepository DB 整合測試：無資料庫連接");
+            return;
+        }
+
+        let repo = PgInstitutionalTradeRepository::new();
+        let date = NaiveDate::from_ymd_opt(2099, 12, 31).unwrap();
+        let trade = |symbol: &str, total_net: i64| {
+            InstitutionalTrade::new(
+                symbol.to_string(),
+                date,
+                TradeVolume::new(300, 100, 200),
+                10,
+                TradeVolume::new(50, 80, -30),
+                TradeVolume::new(7, 2, 5),
+                3,
+                2,
+                total_net,
+            )
+        };
+
+        // 同代號重複列只保留最後一筆。
+        let trades = vec![trade("__T1__", 1), trade("__T2__", 2), trade("__T1__", 185)];
+        let written = repo.replace_by_date(date, &trades).await.unwrap();
+        assert_eq!(written, 2);
+
+        let fetched = repo
+            .fetch_by_symbol("__T1__", Some(date), None, 10)
+            .await
+            .unwrap();
+        assert_eq!(fetched, vec![trade("__T1__", 185)]);
+
+        // 重寫整日會移除不在新清單內的代號。
+        repo.replace_by_date(date, &[trade("__T2__", 2)])
+            .await
+            .unwrap();
+        assert!(
+            repo.fetch_by_symbol("__T1__", Some(date), None, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        repo.replace_by_date(date, &[]).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[cfg_attr(
+        not(feature = "integration-tests"),
+        ignore = "需要外部服務（PostgreSQL/Redis），請加 --features integration-tests 執行"
+    )]
+    async fn test_margin_trading_replace_by_date_and_fetch_by_symbol() {
+        dotenvy::dotenv().ok();
+        if database::ping().await.is_err() {
+            println!("跳過 PgMarginTradingRepository DB 整合測試：無資料庫連接");
+            return;
+        }
+
+        let repo = PgMarginTradingRepository::new();
+        let date = NaiveDate::from_ymd_opt(2099, 12, 31).unwrap();
+        let trading = |symbol: &str, short_balance: i64| {
+            MarginTrading::new(
+                symbol.to_string(),
+                date,
+                CreditBalance::new(120, 80, 0, 1_960, 2_000, 8_000),
+                CreditBalance::new(30, 60, 0, 270, short_balance, 8_000),
+                5,
+            )
+        };
+
+        let tradings = vec![
+            trading("__M1__", 1),
+            trading("__M2__", 2),
+            trading("__M1__", 300),
+        ];
+        let written = repo.replace_by_date(date, &tradings).await.unwrap();
+        assert_eq!(written, 2);
+
+        let fetched = repo
+            .fetch_by_symbol("__M1__", Some(date), None, 10)
+            .await
+            .unwrap();
+        assert_eq!(fetched, vec![trading("__M1__", 300)]);
+
+        repo.replace_by_date(date, &[]).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[cfg_attr(
+        not(feature = "integration-tests"),
+        ignore = "需要外部服務（PostgreSQL/Redis），請加 --features integration-tests 執行"
+    )]
+    async fn test_shareholding_replace_by_date_and_fetch_by_symbol() {
+        dotenvy::dotenv().ok();
+        if database::ping().await.is_err() {
+            println!("跳過 PgShareholdingDistributionRepository DB 整合測試：無資料庫連接");
+            return;
+        }
+
+        let repo = PgShareholdingDistributionRepository::new();
+        let older = NaiveDate::from_ymd_opt(2099, 12, 24).unwrap();
+        let newer = NaiveDate::from_ymd_opt(2099, 12, 31).unwrap();
+        let snapshot = |date: NaiveDate| {
+            ShareholdingDistribution::new(
+                "__S1__".to_string(),
+                date,
+                vec![
+                    ShareholdingTier::new(1, 10, 1_000, Decimal::new(1050, 2)),
+                    ShareholdingTier::new(15, 2, 9_000, Decimal::new(8950, 2)),
+                ],
+            )
+        };
+
+        assert_eq!(
+            repo.replace_by_date(older, &[snapshot(older)])
+                .await
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            repo.replace_by_date(newer, &[snapshot(newer)])
+                .await
+                .unwrap(),
+            2
+        );
+
+        let fetched = repo.fetch_by_symbol("__S1__", 1).await.unwrap();
+        assert_eq!(fetched, vec![snapshot(newer)]);
+        let fetched = repo.fetch_by_symbol("__S1__", 5).await.unwrap();
+        assert_eq!(fetched.len(), 2);
+        assert_eq!(fetched[1], snapshot(older));
+
+        repo.replace_by_date(older, &[]).await.unwrap();
+        repo.replace_by_date(newer, &[]).await.unwrap();
+    }
+}
@@ -1,15 +1,33 @@
-//! 公司行動（分割／減資）的 PostgreSQL 倉儲實作。
+//! 公司行動（分割／減資）與其待確認建議的 PostgreSQL 倉儲實作。
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use chrono::NaiveDate;
+use chrono::{DateTime, Local, NaiveDate};
 use rust_decimal::Decimal;
 use sqlx::Row;
 
-use crate::domain::performance::entity::CorporateAction;
-use crate::domain::performance::repository::CorporateActionRepository;
+use crate::domain::performance::entity::{
+    CorporateAction, CorporateActionProposal, PendingCorporateActionProposal, PriceGap,
+};
+use crate::domain::performance::repository::{
+    CorporateActionProposalRepository, CorporateActionRepository,
+};
 use crate::infra::database;
 
+/// 新增或更新一筆公司行動。
+///
+/// 同一 (代號, 生效日) 重複登錄視為修正：比例打錯時直接重送即可，不必先刪除。
+/// 人工登錄與確認建議共用同一段 SQL，兩條路徑的寫入語義才不會分歧。
+const UPSERT_CORPORATE_ACTION_SQL: &str = r#"
+    INSERT INTO corporate_action (stock_symbol, effective_date, action_type, share_ratio, note)
+    VALUES ($1, $2, $3, $4, $5)
+    ON CONFLICT (stock_symbol, effective_date) DO UPDATE SET
+        action_type = excluded.action_type,
+        share_ratio = excluded.share_ratio,
+        note = excluded.note,
+        updated_time = now()
+"#;
+
 /// 對應資料表 `public.corporate_action`，主鍵為 `(stock_symbol, effective_date)`。
 #[derive(Debug, Clone, Copy, Default)]
 pub struct PgCorporateActionRepository;
@@ -24,30 +42,10 @@ impl PgCorporateActionRepository {
 #[async_trait]
 impl CorporateActionRepository for PgCorporateActionRepository {
     async fn save(&self, action: &CorporateAction) -> Result<u64> {
-        // 同一 (代號, 生效日) 重複登錄視為修正：比例打錯時直接重送即可，
-        // 不必先刪除。
-        let sql = r#"
-            INSERT INTO corporate_action (stock_symbol, effective_date, action_type, share_ratio, note)
-            VALUES ($1, $2, $3, $4, $5)
-            ON CONFLICT (stock_symbol, effective_date) DO UPDATE SET
-                action_type = excluded.action_type,
-                share_ratio = excluded.share_ratio,
-                note = excluded.note,
-                updated_time = now()
-        "#;
-
-        // 比例小於 1 是減資或反向分割，大於 1 是分割；型別由比例推得，
-        // 讓登錄者少填一個容易與比例矛盾的欄位。
-        let action_type = if action.share_ratio < Decimal::ONE {
-            "capital_reduction"
-        } else {
-            "split"
-        };
-
-        let result = sqlx::query(sql)
+        let result = sqlx::query(UPSERT_CORPORATE_ACTION_SQL)
             .bind(&action.stock_symbol)
             .bind(action.effective_date)
-            .bind(action_type)
+            .bind(CorporateAction::action_type_for(action.share_ratio))
             .bind(action.share_ratio)
             .bind(&action.note)
             .execute(database::get_connection())
@@ -84,6 +82,228 @@ impl CorporateActionRepository for PgCorporateActionRepository {
     }
 }
 
+/// 對應資料表 `public.corporate_action_proposal`，以 `(source, stock_symbol, reference)` 去重。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PgCorporateActionProposalRepository;
+
+impl PgCorporateActionProposalRepository {
+    /// 建立實例。
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CorporateActionProposalRepository for PgCorporateActionProposalRepository {
+    async fn propose(&self, proposal: &CorporateActionProposal) -> Result<bool> {
+        // 已被確認或駁回的建議也不重新開啟：來源重送同一則公告不代表有新事件。
+        let sql = r#"
+            INSERT INTO corporate_action_proposal (
+                stock_symbol, source, reference, action_type, effective_date, share_ratio, note
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (source, stock_symbol, reference) DO NOTHING
+        "#;
+
+        let result = sqlx::query(sql)
+            .bind(&proposal.stock_symbol)
+            .bind(&proposal.source)
+            .bind(&proposal.reference)
+            .bind(&proposal.action_type)
+            .bind(proposal.effective_date)
+            .bind(proposal.share_ratio)
+            .bind(&proposal.note)
+            .execute(database::get_connection())
+            .await
+            .context("Failed to save corporate action proposal")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn fetch_pending(&self) -> Result<Vec<PendingCorporateActionProposal>> {
+        let sql = r#"
+            SELECT serial, stock_symbol, source, reference, action_type,
+                   effective_date, share_ratio, note, created_time
+            FROM corporate_action_proposal
+            WHERE status = 'pending'
+            ORDER BY created_time, serial
+        "#;
+
+        let rows = sqlx::query(sql)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch pending corporate action proposals")?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(PendingCorporateActionProposal {
+                    serial: row.try_get::<i64, _>("serial")?,
+                    proposal: CorporateActionProposal {
+                        stock_symbol: row.try_get::<String, _>("stock_symbol")?,
+                        source: row.try_get::<String, _>("source")?,
+                        reference: row.try_get::<String, _>("reference")?,
+                        action_type: row.try_get::<String, _>("action_type")?,
+                        effective_date: row.try_get::<Option<NaiveDate>, _>("effective_date")?,
+                        share_ratio: row.try_get::<Option<Decimal>, _>("share_ratio")?,
+                        note: row.try_get::<String, _>("note")?,
+                    },
+                    created_at: row.try_get::<DateTime<Local>, _>("created_time")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn confirm(
+        &self,
+        serial: i64,
+        effective_date: NaiveDate,
+        share_ratio: Decimal,
+        note: &str,
+    ) -> Result<Option<CorporateAction>> {
+        let mut tx = database::get_tx()
+            .await
+            .context("Failed to begin transaction for corporate action proposal")?;
+
+        // 以 status 條件限定只有待確認者能被確認：重複點擊或兩人同時審核時，
+        // 第二個請求拿不到資料列，不會重複寫入。
+        let sql = r#"
+            UPDATE corporate_action_proposal
+            SET status = 'confirmed',
+                action_type = $2,
+                effective_date = $3,
+                share_ratio = $4,
+                note = $5,
+                updated_time = now()
+            WHERE serial = $1 AND status = 'pending'
+            RETURNING stock_symbol
+        "#;
+        let row = sqlx::query(sql)
+            .bind(serial)
+            .bind(CorporateAction::action_type_for(share_ratio))
+            .bind(effective_date)
+            .bind(share_ratio)
+            .bind(note)
+            .fetch_optional(&mut *tx)
+            .await
+            .context("Failed to confirm corporate action proposal")?;
+        let Some(row) = row else {
+            tx.rollback()
+                .await
+                .context("Failed to rollback corporate action proposal")?;
+            return Ok(None);
+        };
+
+        let action = CorporateAction {
+            stock_symbol: row.try_get::<String, _>("stock_symbol")?,
+            effective_date,
+            share_ratio,
+            note: note.to_owned(),
+        };
+        sqlx::query(UPSERT_CORPORATE_ACTION_SQL)
+            .bind(&action.stock_symbol)
+            .bind(action.effective_date)
+            .bind(CorporateAction::action_type_for(action.share_ratio))
+            .bind(action.share_ratio)
+            .bind(&action.note)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to save confirmed corporate action")?;
+
+        tx.commit()
+            .await
+            .context("Failed to commit corporate action proposal")?;
+
+        Ok(Some(action))
+    }
+
+    async fn reject(&self, serial: i64) -> Result<bool> {
+        let sql = r#"
+            UPDATE corporate_action_proposal
+            SET status = 'rejected', updated_time = now()
+            WHERE serial = $1 AND status = 'pending'
+        "#;
+
+        let result = sqlx::query(sql)
+            .bind(serial)
+            .execute(database::get_connection())
+            .await
+            .context("Failed to reject corporate action proposal")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn fetch_unexplained_price_gaps(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        min_change: Decimal,
+    ) -> Result<Vec<PriceGap>> {
+        // 前一筆收盤價往前多看 60 天：減資停止買賣通常二到四週，區間起點若落在
+        // 恢復買賣日，停牌前最後一筆報價必須能被 LAG 取到。
+        //
+        // 比對除權息日沿用 CAGR 異常偵測的做法，以 to_char 把日期轉成字串去比對
+        // varchar 欄位，避免髒值讓整批轉型失敗。
+        let sql = r#"
+            WITH px AS (
+                SELECT stock_symbol,
+                       "Date",
+                       "ClosingPrice",
+                       LAG("ClosingPrice") OVER (
+                           PARTITION BY stock_symbol ORDER BY "Date"
+                       ) AS prev_price
+                FROM "DailyQuotes"
+                WHERE "Date" BETWEEN $1::date - 60 AND $2 AND "ClosingPrice" > 0
+            )
+            SELECT px.stock_symbol, px."Date", px.prev_price, px."ClosingPrice"
+            FROM px
+            WHERE px."Date" BETWEEN $1 AND $2
+              AND px.prev_price > 0
+              AND ABS(px."ClosingPrice" / px.prev_price - 1) > $3
+              AND NOT EXISTS (
+                  SELECT 1
+                  FROM dividend d
+                  WHERE d.security_code = px.stock_symbol
+                    AND (
+                          d."ex-dividend_date1" = to_char(px."Date", 'YYYY-MM-DD')
+                       OR d."ex-dividend_date2" = to_char(px."Date", 'YYYY-MM-DD')
+                    )
+              )
+              AND NOT EXISTS (
+                  SELECT 1
+                  FROM corporate_action ca
+                  WHERE ca.stock_symbol = px.stock_symbol
+                    AND ca.effective_date = px."Date"
+              )
+              AND NOT EXISTS (
+                  SELECT 1
+                  FROM corporate_action_proposal p
+                  WHERE p.stock_symbol = px.stock_symbol
+                    AND p.effective_date = px."Date"
+              )
+            ORDER BY px."Date", px.stock_symbol
+        "#;
+
+        let rows = sqlx::query(sql)
+            .bind(from)
+            .bind(to)
+            .bind(min_change)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch unexplained price gaps")?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(PriceGap {
+                    stock_symbol: row.try_get::<String, _>("stock_symbol")?,
+                    date: row.try_get::<NaiveDate, _>("Date")?,
+                    previous_close: row.try_get::<Decimal, _>("prev_price")?,
+                    close: row.try_get::<Decimal, _>("ClosingPrice")?,
+                })
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -201,4 +421,115 @@ mod tests {
 
         cleanup().await;
     }
+
+    #[tokio::test]
+    #[cfg_attr(
+        not(feature = "integration-tests"),
+        ignore = "需要外部服務（PostgreSQL/Redis），請加 --features integration-tests 執行"
+    )]
+    async fn test_propose_is_deduplicated() {
+        dotenvy::dotenv().ok();
+        if database::ping().await.is_err() {
+            println!("跳過 test_propose_is_deduplicated：無資料庫連接");
+            return;
+        }
+
+        let cleanup_proposals = || async {
+            let _ = sqlx::query("DELETE FROM corporate_action_proposal WHERE stock_symbol = $1")
+                .bind(FAKE_SYMBOL)
+                .execute(database::get_connection())
+                .await;
+        };
+        cleanup_proposals().await;
+
+        let repo = PgCorporateActionProposalRepository::new();
+        let proposal = CorporateActionProposal {
+            stock_symbol: FAKE_SYMBOL.to_string(),
+            source: "mops".to_string(),
+            reference: "1990-01-05 09:30:00 公告減資".to_string(),
+            action_type: "capital_reduction".to_string(),
+            effective_date: None,
+            share_ratio: None,
+            note: "公告減資".to_string(),
+        };
+
+        assert!(repo.propose(&proposal).await.expect("propose"));
+        assert!(!repo.propose(&proposal).await.expect("propose again"));
+
+        cleanup_proposals().await;
+    }
+
+    #[tokio::test]
+    #[cfg_attr(
+        not(feature = "integration-tests"),
+        ignore = "需要外部服務（PostgreSQL/Redis），請加 --features integration-tests 執行"
+    )]
+    async fn test_confirm_writes_corporate_action_once() {
+        dotenvy::dotenv().ok();
+        if database::ping().await.is_err() {
+            println!("跳過 test_confirm_writes_corporate_action_once：無資料庫連接");
+            return;
+        }
+
+        let cleanup_proposals = || async {
+            let _ = sqlx::query("DELETE FROM corporate_action_proposal WHERE stock_symbol = $1")
+                .bind(FAKE_SYMBOL)
+                .execute(database::get_connection())
+                .await;
+        };
+        cleanup_proposals().await;
+        cleanup().await;
+
+        let repo = PgCorporateActionProposalRepository::new();
+        for reference in ["1990-01-05 twse", "1990-01-05 tpex"] {
+            repo.propose(&CorporateActionProposal {
+                stock_symbol: FAKE_SYMBOL.to_string(),
+                source: "twse".to_string(),
+                reference: reference.to_string(),
+                action_type: "capital_reduction".to_string(),
+                effective_date: Some(date(1990, 1, 5)),
+                share_ratio: Some(dec!(0.6)),
+                note: "減資".to_string(),
+            })
+            .await
+            .expect("propose");
+        }
+
+        let pending: Vec<_> = repo
+            .fetch_pending()
+            .await
+            .expect("fetch_pending")
+            .into_iter()
+            .filter(|item| item.proposal.stock_symbol == FAKE_SYMBOL)
+            .collect();
+        assert_eq!(pending.len(), 2);
+        let (first, second) = (pending[0].serial, pending[1].serial);
+
+        // 確認時可修正比例，寫入的是修正後的值。
+        let action = repo
+            .confirm(first, date(1990, 1, 5), dec!(0.65), "減資三成五")
+            .await
+            .expect("confirm")
+            .expect("待確認的建議應可確認");
+        assert_eq!(action.share_ratio, dec!(0.65));
+        let saved = PgCorporateActionRepository::new()
+            .fetch_by_symbol(FAKE_SYMBOL)
+            .await
+            .expect("fetch_by_symbol");
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].share_ratio, dec!(0.65));
+
+        // 已確認者不能再次確認；駁回也只對待確認者有效。
+        assert!(
+            repo.confirm(first, date(1990, 1, 5), dec!(0.5), "")
+                .await
+                .expect("confirm again")
+                .is_none()
+        );
+        assert!(repo.reject(second).await.expect("reject"));
+        assert!(!repo.reject(second).await.expect("reject again"));
+
+        cleanup_proposals().await;
+        cleanup().await;
+    }
 }
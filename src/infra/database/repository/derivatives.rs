@@ -0,0 +1,475 @@
+use std::collections::BTreeMap;
+
+use crate::domain::derivatives::{
+    entity::{
+        DerivativesDailySnapshot, FuturesDailyQuote, FuturesInstitutionalPosition, PutCallRatio,
+        TraderType,
+    },
+    repository::DerivativesRepository,
+};
+use crate::infra::database;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::FromRow;
+
+/// 基於 PostgreSQL 的期貨選擇權市場倉儲實現 (PgDerivativesRepository)。
+///
+/// 負責 `futures_daily_quote`、`futures_institutional_position` 與
+/// `options_put_call_ratio` 三張表的整日替換寫入與區間查詢。
+pub struct PgDerivativesRepository;
+
+impl PgDerivativesRepository {
+    /// 建立新的 PgDerivativesRepository 實例。
+    pub fn new() -> Self {
+        PgDerivativesRepository
+    }
+}
+
+impl Default for PgDerivativesRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `futures_daily_quote` 的資料列映射。
+#[derive(FromRow)]
+struct FuturesDailyQuoteDbRow {
+    date: NaiveDate,
+    contract: String,
+    contract_month: String,
+    open: Option<Decimal>,
+    high: Option<Decimal>,
+    low: Option<Decimal>,
+    close: Option<Decimal>,
+    change: Option<Decimal>,
+    volume: i64,
+    settlement_price: Option<Decimal>,
+    open_interest: i64,
+}
+
+impl From<FuturesDailyQuoteDbRow> for FuturesDailyQuote {
+    fn from(row: FuturesDailyQuoteDbRow) -> Self {
+        FuturesDailyQuote {
+            date: row.date,
+            contract: row.contract,
+            contract_month: row.contract_month,
+            open: row.open,
+            high: row.high,
+            low: row.low,
+            close: row.close,
+            change: row.change,
+            volume: row.volume,
+            settlement_price: row.settlement_price,
+            open_interest: row.open_interest,
+        }
+    }
+}
+
+/// `futures_institutional_position` 的資料列映射。
+#[derive(FromRow)]
+struct FuturesInstitutionalPositionDbRow {
+    date: NaiveDate,
+    contract: String,
+    trader_type: String,
+    long_trade_volume: i64,
+    short_trade_volume: i64,
+    net_trade_volume: i64,
+    long_open_interest: i64,
+    short_open_interest: i64,
+    net_open_interest: i64,
+    net_open_interest_amount: i64,
+}
+
+/// `options_put_call_ratio` 的資料列映射。
+#[derive(FromRow)]
+struct PutCallRatioDbRow {
+    date: NaiveDate,
+    put_volume: i64,
+    call_volume: i64,
+    volume_ratio_percent: Decimal,
+    put_open_interest: i64,
+    call_open_interest: i64,
+    open_interest_ratio_percent: Decimal,
+}
+
+impl From<PutCallRatioDbRow> for PutCallRatio {
+    fn from(row: PutCallRatioDbRow) -> Self {
+        PutCallRatio {
+            date: row.date,
+            put_volume: row.put_volume,
+            call_volume: row.call_volume,
+            volume_ratio_percent: row.volume_ratio_percent,
+            put_open_interest: row.put_open_interest,
+            call_open_interest: row.call_open_interest,
+            open_interest_ratio_percent: row.open_interest_ratio_percent,
+        }
+    }
+}
+
+/// 依日期取得（或建立）空白快照，供區間查詢組裝三張表的結果。
+fn snapshot_of(
+    snapshots: &mut BTreeMap<NaiveDate, DerivativesDailySnapshot>,
+    date: NaiveDate,
+) -> &mut DerivativesDailySnapshot {
+    snapshots
+        .entry(date)
+        .or_insert_with(|| DerivativesDailySnapshot {
+            date,
+            futures: Vec::new(),
+            positions: Vec::new(),
+            put_call_ratio: None,
+        })
+}
+
+#[async_trait]
+impl DerivativesRepository for PgDerivativesRepository {
+    /// 於單一交易內刪除三張表的當日資料後批次寫入。
+    async fn replace_by_date(&self, snapshot: &DerivativesDailySnapshot) -> Result<u64> {
+        let date = snapshot.date;
+        let futures: Vec<&FuturesDailyQuote> = snapshot
+            .futures
+            .iter()
+            .filter(|quote| quote.date == date)
+            .collect();
+        let positions: Vec<&FuturesInstitutionalPosition> = snapshot
+            .positions
+            .iter()
+            .filter(|position| position.date == date)
+            .collect();
+
+        let mut tx = database::get_tx()
+            .await
+            .context("Failed to get_tx in PgDerivativesRepository::replace_by_date")?;
+
+        for sql in [
+            "DELETE FROM futures_daily_quote WHERE date = $1",
+            "DELETE FROM futures_institutional_position WHERE date = $1",
+            "DELETE FROM options_put_call_ratio WHERE date = $1",
+        ] {
+            sqlx::query(sql)
+                .bind(date)
+                .execute(&mut *tx)
+                .await
+                .context("Failed to delete derivatives market data from PG")?;
+        }
+
+        // 同契約月份重複列只保留最後一筆，避免主鍵衝突讓整日寫入失敗。
+        let futures_sql = r#"
+            INSERT INTO futures_daily_quote (
+                date, contract, contract_month, open, high, low, close, change,
+                volume, settlement_price, open_interest
+            )
+            SELECT DISTINCT ON (contract, contract_month)
+                $1, contract, contract_month, open, high, low, close, change,
+                volume, settlement_price, open_interest
+            FROM UNNEST(
+                $2::varchar[], $3::varchar[],
+                $4::numeric[], $5::numeric[], $6::numeric[], $7::numeric[], $8::numeric[],
+                $9::bigint[], $10::numeric[], $11::bigint[]
+            ) WITH ORDINALITY AS t(
+                contract, contract_month, open, high, low, close, change,
+                volume, settlement_price, open_interest, ordinality
+            )
+            ORDER BY contract, contract_month, ordinality DESC;
+        "#;
+        let futures_result = sqlx::query(futures_sql)
+            .bind(date)
+            .bind(
+                futures
+                    .iter()
+                    .map(|q| q.contract.clone())
+                    .collect::<Vec<_>>(),
+            )
+            .bind(
+                futures
+                    .iter()
+                    .map(|q| q.contract_month.clone())
+                    .collect::<Vec<_>>(),
+            )
+            .bind(futures.iter().map(|q| q.open).collect::<Vec<_>>())
+            .bind(futures.iter().map(|q| q.high).collect::<Vec<_>>())
+            .bind(futures.iter().map(|q| q.low).collect::<Vec<_>>())
+            .bind(futures.iter().map(|q| q.close).collect::<Vec<_>>())
+            .bind(futures.iter().map(|q| q.change).collect::<Vec<_>>())
+            .bind(futures.iter().map(|q| q.volume).collect::<Vec<_>>())
+            .bind(
+                futures
+                    .iter()
+                    .map(|q| q.settlement_price)
+                    .collect::<Vec<_>>(),
+            )
+            .bind(futures.iter().map(|q| q.open_interest).collect::<Vec<_>>())
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert futures daily quotes to PG")?;
+
+        let mut columns: [Vec<i64>; 7] = Default::default();
+        for position in &positions {
+            let values = [
+                position.long_trade_volume,
+                position.short_trade_volume,
+                position.net_trade_volume,
+                position.long_open_interest,
+                position.short_open_interest,
+                position.net_open_interest,
+                position.net_open_interest_amount,
+            ];
+            for (column, value) in columns.iter_mut().zip(values) {
+                column.push(value);
+            }
+        }
+        let positions_sql = r#"
+            INSERT INTO futures_institutional_position (
+                date, contract, trader_type,
+                long_trade_volume, short_trade_volume, net_trade_volume,
+                long_open_interest, short_open_interest, net_open_interest,
+                net_open_interest_amount
+            )
+            SELECT DISTINCT ON (contract, trader_type)
+                $1, contract, trader_type,
+                long_trade_volume, short_trade_volume, net_trade_volume,
+                long_open_interest, short_open_interest, net_open_interest,
+                net_open_interest_amount
+            FROM UNNEST(
+                $2::varchar[], $3::varchar[],
+                $4::bigint[], $5::bigint[], $6::bigint[],
+                $7::bigint[], $8::bigint[], $9::bigint[], $10::bigint[]
+            ) WITH ORDINALITY AS t(
+                contract, trader_type,
+                long_trade_volume, short_trade_volume, net_trade_volume,
+                long_open_interest, short_open_interest, net_open_interest,
+                net_open_interest_amount, ordinality
+            )
+            ORDER BY contract, trader_type, ordinality DESC;
+        "#;
+        let mut query = sqlx::query(positions_sql)
+            .bind(date)
+            .bind(
+                positions
+                    .iter()
+                    .map(|p| p.contract.clone())
+                    .collect::<Vec<_>>(),
+            )
+            .bind(
+                positions
+                    .iter()
+                    .map(|p| p.trader_type.code())
+                    .collect::<Vec<_>>(),
+            );
+        for column in &columns {
+            query = query.bind(column);
+        }
+        let positions_result = query
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert futures institutional positions to PG")?;
+
+        let mut ratio_rows = 0;
+        if let Some(ratio) = snapshot.put_call_ratio.as_ref().filter(|r| r.date == date) {
+            let ratio_sql = r#"
+                INSERT INTO options_put_call_ratio (
+                    date, put_volume, call_volume, volume_ratio_percent,
+                    put_open_interest, call_open_interest, open_interest_ratio_percent
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7);
+            "#;
+            ratio_rows = sqlx::query(ratio_sql)
+                .bind(date)
+                .bind(ratio.put_volume)
+                .bind(ratio.call_volume)
+                .bind(ratio.volume_ratio_percent)
+                .bind(ratio.put_open_interest)
+                .bind(ratio.call_open_interest)
+                .bind(ratio.open_interest_ratio_percent)
+                .execute(&mut *tx)
+                .await
+                .context("Failed to insert options put/call ratio to PG")?
+                .rows_affected();
+        }
+
+        tx.commit()
+            .await
+            .context("Failed to commit derivatives market data to PG")?;
+
+        Ok(futures_result.rows_affected() + positions_result.rows_affected() + ratio_rows)
+    }
+
+    /// 分別查詢三張表的區間資料後依日期組裝快照。
+    async fn fetch_range(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<DerivativesDailySnapshot>> {
+        let futures = sqlx::query_as::<_, FuturesDailyQuoteDbRow>(
+            r#"
+            SELECT
+                date, contract, contract_month, open, high, low, close, change,
+                volume, settlement_price, open_interest
+            FROM futures_daily_quote
+            WHERE date BETWEEN $1 AND $2
+            ORDER BY date, contract, contract_month;
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(database::get_connection())
+        .await
+        .context("Failed to fetch futures daily quotes from PG")?;
+
+        let positions = sqlx::query_as::<_, FuturesInstitutionalPositionDbRow>(
+            r#"
+            SELECT
+                date, contract, trader_type,
+                long_trade_volume, short_trade_volume, net_trade_volume,
+                long_open_interest, short_open_interest, net_open_interest,
+                net_open_interest_amount
+            FROM futures_institutional_position
+            WHERE date BETWEEN $1 AND $2
+            ORDER BY date, contract, trader_type;
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(database::get_connection())
+        .await
+        .context("Failed to fetch futures institutional positions from PG")?;
+
+        let ratios = sqlx::query_as::<_, PutCallRatioDbRow>(
+            r#"
+            SELECT
+                date, put_volume, call_volume, volume_ratio_percent,
+                put_open_interest, call_open_interest, open_interest_ratio_percent
+            FROM options_put_call_ratio
+            WHERE date BETWEEN $1 AND $2;
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(database::get_connection())
+        .await
+        .context("Failed to fetch options put/call ratios from PG")?;
+
+        let mut snapshots = BTreeMap::new();
+        for row in futures {
+            snapshot_of(&mut snapshots, row.date)
+                .futures
+                .push(row.into());
+        }
+        for row in positions {
+            // 身份別代碼由本倉儲寫入；無法辨識代表資料被外部改動，略過而不讓整段查詢失敗。
+            let Some(trader_type) = TraderType::from_code(&row.trader_type) else {
+                tracing::warn!("略過無法辨識的法人身份別 {}", row.trader_type);
+                continue;
+            };
+            snapshot_of(&mut snapshots, row.date)
+                .positions
+                .push(FuturesInstitutionalPosition {
+                    date: row.date,
+                    contract: row.contract,
+                    trader_type,
+                    long_trade_volume: row.long_trade_volume,
+                    short_trade_volume: row.short_trade_volume,
+                    net_trade_volume: row.net_trade_volume,
+                    long_open_interest: row.long_open_interest,
+                    short_open_interest: row.short_open_interest,
+                    net_open_interest: row.net_open_interest,
+                    net_open_interest_amount: row.net_open_interest_amount,
+                });
+        }
+        for row in ratios {
+            let date = row.date;
+            snapshot_of(&mut snapshots, date).put_call_ratio = Some(row.into());
+        }
+
+        Ok(snapshots.into_values().rev().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[tokio::test]
+    #[cfg_attr(
+        not(feature = "integration-tests"),
+        ignore = "需要外部服務（PostgreSQL/Redis），請加 --features integration-tests 執行"
+    )]
+    async fn test_replace_by_date_and_fetch_range() {
+        dotenvy::dotenv().ok();
+        if database::ping().await.is_err() {
+            println!("跳過 PgDerivativesRepository DB 整合測試：無資料庫連接");
+            return;
+        }
+
+        let repo = PgDerivativesRepository::new();
+        let date = NaiveDate::from_ymd_opt(2099, 12, 31).unwrap();
+        let quote = |month: &str, close: Decimal| FuturesDailyQuote {
+            date,
+            contract: "TX".to_string(),
+            contract_month: month.to_string(),
+            open: None,
+            high: None,
+            low: None,
+            close: Some(close),
+            change: Some(dec!(-12)),
+            volume: 100,
+            settlement_price: Some(close),
+            open_interest: 1_000,
+        };
+        let position = FuturesInstitutionalPosition {
+            date,
+            contract: "TX".to_string(),
+            trader_type: TraderType::ForeignInvestor,
+            long_trade_volume: 10,
+            short_trade_volume: 20,
+            net_trade_volume: -10,
+            long_open_interest: 100,
+            short_open_interest: 300,
+            net_open_interest: -200,
+            net_open_interest_amount: -8_400,
+        };
+        let ratio = PutCallRatio {
+            date,
+            put_volume: 1,
+            call_volume: 2,
+            volume_ratio_percent: dec!(50.00),
+            put_open_interest: 3,
+            call_open_interest: 2,
+            open_interest_ratio_percent: dec!(150.00),
+        };
+
+        // 同契約月份重複列只保留最後一筆。
+        let snapshot = DerivativesDailySnapshot {
+            date,
+            futures: vec![quote("209912", dec!(1)), quote("209912", dec!(2))],
+            positions: vec![position.clone()],
+            put_call_ratio: Some(ratio.clone()),
+        };
+        let written = repo.replace_by_date(&snapshot).await.unwrap();
+        assert_eq!(written, 3);
+
+        let fetched = repo.fetch_range(date, date).await.unwrap();
+        assert_eq!(
+            fetched,
+            vec![DerivativesDailySnapshot {
+                date,
+                futures: vec![quote("209912", dec!(2))],
+                positions: vec![position],
+                put_call_ratio: Some(ratio),
+            }]
+        );
+
+        repo.replace_by_date(&DerivativesDailySnapshot {
+            date,
+            futures: Vec::new(),
+            positions: Vec::new(),
+            put_call_ratio: None,
+        })
+        .await
+        .unwrap();
+        assert!(repo.fetch_range(date, date).await.unwrap().is_empty());
+    }
+}
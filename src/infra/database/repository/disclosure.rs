@@ -0,0 +1,228 @@
+use crate::domain::disclosure::{
+    entity::{AnnouncementCategory, MaterialAnnouncement},
+    repository::MaterialAnnouncementRepository,
+};
+use crate::infra::database;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{NaiveDate, NaiveDateTime};
+use sqlx::FromRow;
+
+/// 基於 PostgreSQL 的重大訊息倉儲實現 (PgMaterialAnnouncementRepository)。
+///
+/// 負責 `material_announcement` 資料表的去重寫入與個股查詢。
+pub struct PgMaterialAnnouncementRepository;
+
+impl PgMaterialAnnouncementRepository {
+    /// 建立新的 PgMaterialAnnouncementRepository 實例。
+    pub fn new() -> Self {
+        PgMaterialAnnouncementRepository
+    }
+}
+
+impl Default for PgMaterialAnnouncementRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 資料庫對應的內部資料列結構體，用於 `sqlx::query_as` 映射。
+#[derive(FromRow)]
+struct MaterialAnnouncementDbRow {
+    stock_symbol: String,
+    company_name: String,
+    announced_at: NaiveDateTime,
+    subject: String,
+    clause: String,
+    fact_date: Option<NaiveDate>,
+    description: String,
+    category: String,
+}
+
+impl From<MaterialAnnouncementDbRow> for MaterialAnnouncement {
+    fn from(row: MaterialAnnouncementDbRow) -> Self {
+        MaterialAnnouncement {
+            stock_symbol: row.stock_symbol,
+            company_name: row.company_name,
+            announced_at: row.announced_at,
+            subject: row.subject,
+            clause: row.clause,
+            fact_date: row.fact_date,
+            description: row.description,
+            category: AnnouncementCategory::from_code(&row.category),
+        }
+    }
+}
+
+#[async_trait]
+impl MaterialAnnouncementRepository for PgMaterialAnnouncementRepository {
+    /// 以 UNNEST 批次寫入，衝突即略過，`RETURNING` 只帶回真正新增的列。
+    async fn insert_new(
+        &self,
+        announcements: &[MaterialAnnouncement],
+    ) -> Result<Vec<MaterialAnnouncement>> {
+        if announcements.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let sql = r#"
+            INSERT INTO material_announcement (
+                stock_symbol, company_name, announced_at, subject, clause,
+                fact_date, description, category
+            )
+            SELECT
+                stock_symbol, company_name, announced_at, subject, clause,
+                fact_date, description, category
+            FROM UNNEST(
+                $1::varchar[], $2::varchar[], $3::timestamp[], $4::text[], $5::varchar[],
+                $6::date[], $7::text[], $8::varchar[]
+            ) AS t(
+                stock_symbol, company_name, announced_at, subject, clause,
+                fact_date, description, category
+            )
+            ON CONFLICT (stock_symbol, announced_at, md5(subject)) DO NOTHING
+            RETURNING
+                stock_symbol, company_name, announced_at, subject, clause,
+                fact_date, description, category;
+        "#;
+
+        let rows = sqlx::query_as::<_, MaterialAnnouncementDbRow>(sql)
+            .bind(
+                announcements
+                    .iter()
+                    .map(|a| a.stock_symbol.clone())
+                    .collect::<Vec<_>>(),
+            )
+            .bind(
+                announcements
+                    .iter()
+                    .map(|a| a.company_name.clone())
+                    .collect::<Vec<_>>(),
+            )
+            .bind(
+                announcements
+                    .iter()
+                    .map(|a| a.announced_at)
+                    .collect::<Vec<_>>(),
+            )
+            .bind(
+                announcements
+                    .iter()
+                    .map(|a| a.subject.clone())
+                    .collect::<Vec<_>>(),
+            )
+            .bind(
+                announcements
+                    .iter()
+                    .map(|a| a.clause.clone())
+                    .collect::<Vec<_>>(),
+            )
+            .bind(
+                announcements
+                    .iter()
+                    .map(|a| a.fact_date)
+                    .collect::<Vec<_>>(),
+            )
+            .bind(
+                announcements
+                    .iter()
+                    .map(|a| a.description.clone())
+                    .collect::<Vec<_>>(),
+            )
+            .bind(
+                announcements
+                    .iter()
+                    .map(|a| a.category.code())
+                    .collect::<Vec<_>>(),
+            )
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to insert material announcements to PG")?;
+
+        Ok(rows.into_iter().map(MaterialAnnouncement::from).collect())
+    }
+
+    /// 查詢個股最近的重大訊息。
+    async fn fetch_by_symbol(
+        &self,
+        stock_symbol: &str,
+        limit: i64,
+    ) -> Result<Vec<MaterialAnnouncement>> {
+        let sql = r#"
+            SELECT
+                stock_symbol, company_name, announced_at, subject, clause,
+                fact_date, description, category
+            FROM material_announcement
+            WHERE stock_symbol = $1
+            ORDER BY announced_at DESC
+            LIMIT $2;
+        "#;
+
+        let rows = sqlx::query_as::<_, MaterialAnnouncementDbRow>(sql)
+            .bind(stock_symbol)
+            .bind(limit)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch material announcements from PG")?;
+
+        Ok(rows.into_iter().map(MaterialAnnouncement::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[cfg_attr(
+        not(feature = "integration-tests"),
+        ignore = "需要外部服務（PostgreSQL/Redis），請加 --features integration-tests 執行"
+    )]
+    async fn test_insert_new_returns_only_unseen_announcements() {
+        dotenvy::dotenv().ok();
+        if database::ping().await.is_err() {
+            println!("跳過 PgMaterialAnnouncementRepository DB 整合測試：無資料庫連接");
+            return;
+        }
+
+        let symbol = "__MA__";
+        let cleanup = || async {
+            let _ = sqlx::query("DELETE FROM material_announcement WHERE stock_symbol = $1")
+                .bind(symbol)
+                .execute(database::get_connection())
+                .await;
+        };
+        cleanup().await;
+
+        let repo = PgMaterialAnnouncementRepository::new();
+        let announcement = |subject: &str| MaterialAnnouncement {
+            stock_symbol: symbol.to_string(),
+            company_name: "測試".to_string(),
+            announced_at: NaiveDate::from_ymd_opt(2099, 12, 31)
+                .unwrap()
+                .and_hms_opt(9, 30, 0)
+                .unwrap(),
+            subject: subject.to_string(),
+            clause: "第11款".to_string(),
+            fact_date: None,
+            description: String::new(),
+            category: AnnouncementCategory::classify(subject),
+        };
+
+        let first = repo
+            .insert_new(&[announcement("公告減資"), announcement("公告減資")])
+            .await
+            .unwrap();
+        assert_eq!(first, vec![announcement("公告減資")]);
+
+        // 再次輪詢取得相同訊息時不回傳，新的主旨才回傳。
+        let second = repo
+            .insert_new(&[announcement("公告減資"), announcement("公告股利分派")])
+            .await
+            .unwrap();
+        assert_eq!(second, vec![announcement("公告股利分派")]);
+        assert_eq!(repo.fetch_by_symbol(symbol, 10).await.unwrap().len(), 2);
+
+        cleanup().await;
+    }
+}
@@ -418,4 +418,40 @@ impl DividendRepository for PgDividendRepository {
             .context("Failed to save dividend to database")?;
         Ok(())
     }
+
+    /// 由版本歷史還原單一公司在指定時間當時已知的各期股利。
+    async fn fetch_dividends_as_of(
+        &self,
+        security_code: &str,
+        as_of: DateTime<Local>,
+    ) -> Result<Vec<Dividend>> {
+        let sql = r#"
+            SELECT
+                d.serial, d.security_code, d.year, d.year_of_dividend, d.quarter,
+                d.cash_dividend, d.stock_dividend, d.sum, d."ex-dividend_date1", d."ex-dividend_date2",
+                d.payable_date1, d.payable_date2,
+                v.first_seen_time AS created_time, v.recorded_time AS updated_time,
+                d.capital_reserve_cash_dividend, d.earnings_cash_dividend,
+                d.capital_reserve_stock_dividend, d.earnings_stock_dividend,
+                d.payout_ratio_cash, d.payout_ratio_stock, d.payout_ratio
+            FROM (
+                SELECT DISTINCT ON (period) payload, first_seen_time, recorded_time
+                FROM fundamental_revision
+                WHERE kind = 'dividend'
+                  AND security_code = $1
+                  AND recorded_time <= $2
+                ORDER BY period, revision DESC
+            ) v
+            CROSS JOIN LATERAL jsonb_populate_record(NULL::dividend, v.payload) d
+            ORDER BY d.year, d.quarter;
+        "#;
+        let rows = sqlx::query(sql)
+            .bind(security_code)
+            .bind(as_of)
+            .try_map(Self::row_to_entity)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch point-in-time dividends from PG")?;
+        Ok(rows)
+    }
 }
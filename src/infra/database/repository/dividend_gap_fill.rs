@@ -0,0 +1,252 @@
+use crate::domain::dividend::{gap_fill::DividendGapFill, repository::DividendGapFillRepository};
+use crate::infra::database;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::FromRow;
+
+/// 基於 PostgreSQL 的填息結果倉儲實現 (PgDividendGapFillRepository)。
+///
+/// 負責 `dividend_gap_fill` 資料表的讀寫，以及計算所需的除息事件與收盤價查詢。
+pub struct PgDividendGapFillRepository;
+
+impl PgDividendGapFillRepository {
+    /// 建立新的 PgDividendGapFillRepository 實例。
+    pub fn new() -> Self {
+        PgDividendGapFillRepository
+    }
+}
+
+impl Default for PgDividendGapFillRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 資料庫對應的內部資料列結構體，用於 `sqlx::query_as` 映射。
+#[derive(FromRow)]
+struct GapFillDbRow {
+    security_code: String,
+    year: i32,
+    quarter: String,
+    ex_dividend_date: NaiveDate,
+    cash_dividend: Decimal,
+    pre_ex_close: Decimal,
+    filled_on: Option<NaiveDate>,
+    trading_days_to_fill: Option<i32>,
+}
+
+impl From<GapFillDbRow> for DividendGapFill {
+    fn from(row: GapFillDbRow) -> Self {
+        Self {
+            security_code: row.security_code,
+            year: row.year,
+            quarter: row.quarter,
+            ex_dividend_date: row.ex_dividend_date,
+            cash_dividend: row.cash_dividend,
+            pre_ex_close: row.pre_ex_close,
+            filled_on: row.filled_on,
+            trading_days_to_fill: row.trading_days_to_fill,
+        }
+    }
+}
+
+#[async_trait]
+impl DividendGapFillRepository for PgDividendGapFillRepository {
+    async fn fetch_pending(
+        &self,
+        since: NaiveDate,
+        until: NaiveDate,
+    ) -> Result<Vec<DividendGapFill>> {
+        // 除息日欄位是 varchar 且含「-」「尚未公布」等髒值，先以正規表示式
+        // 篩出合法日期再轉型。除息前收盤價以 LATERAL 取除息日前最後一筆報價。
+        let sql = r#"
+            WITH ex AS (
+                SELECT security_code, "year", quarter, cash_dividend,
+                       "ex-dividend_date1"::date AS ex_dividend_date
+                FROM dividend
+                WHERE cash_dividend > 0
+                  AND "ex-dividend_date1" ~ '^\d{4}-\d{2}-\d{2}$'
+            )
+            SELECT
+                ex.security_code, ex."year" AS year, ex.quarter, ex.ex_dividend_date,
+                ex.cash_dividend, pre."ClosingPrice" AS pre_ex_close,
+                g.filled_on, g.trading_days_to_fill
+            FROM ex
+            JOIN LATERAL (
+                SELECT "ClosingPrice"
+                FROM "DailyQuotes"
+                WHERE stock_symbol = ex.security_code
+                  AND "Date" < ex.ex_dividend_date
+                  AND "ClosingPrice" > 0
+                ORDER BY "Date" DESC
+                LIMIT 1
+            ) pre ON TRUE
+            LEFT JOIN dividend_gap_fill g
+              ON g.security_code = ex.security_code
+             AND g."year" = ex."year"
+             AND g.quarter = ex.quarter
+            WHERE ex.ex_dividend_date <= $2
+              AND (g.security_code IS NULL OR (g.filled_on IS NULL AND ex.ex_dividend_date >= $1))
+            ORDER BY ex.ex_dividend_date, ex.security_code
+        "#;
+
+        let rows = sqlx::query_as::<_, GapFillDbRow>(sql)
+            .bind(since)
+            .bind(until)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch pending dividend gap fills from PG")?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn fetch_closes(
+        &self,
+        security_code: &str,
+        from: NaiveDate,
+    ) -> Result<Vec<(NaiveDate, Decimal)>> {
+        let sql = r#"
+            SELECT "Date", "ClosingPrice"
+            FROM "DailyQuotes"
+            WHERE stock_symbol = $1 AND "Date" >= $2 AND "ClosingPrice" > 0
+            ORDER BY "Date"
+        "#;
+
+        sqlx::query_as(sql)
+            .bind(security_code)
+            .bind(from)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch closing prices for dividend gap fill from PG")
+    }
+
+    async fn upsert(&self, gap_fills: &[DividendGapFill]) -> Result<()> {
+        if gap_fills.is_empty() {
+            return Ok(());
+        }
+
+        let sql = r#"
+            INSERT INTO dividend_gap_fill (
+                security_code, "year", quarter, ex_dividend_date, cash_dividend,
+                pre_ex_close, filled_on, trading_days_to_fill
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (security_code, "year", quarter) DO UPDATE SET
+                ex_dividend_date = excluded.ex_dividend_date,
+                cash_dividend = excluded.cash_dividend,
+                pre_ex_close = excluded.pre_ex_close,
+                filled_on = excluded.filled_on,
+                trading_days_to_fill = excluded.trading_days_to_fill,
+                updated_time = now()
+        "#;
+
+        let mut tx = database::get_tx()
+            .await
+            .context("Failed to begin transaction for dividend gap fill")?;
+        for gap_fill in gap_fills {
+            sqlx::query(sql)
+                .bind(&gap_fill.security_code)
+                .bind(gap_fill.year)
+                .bind(&gap_fill.quarter)
+                .bind(gap_fill.ex_dividend_date)
+                .bind(gap_fill.cash_dividend)
+                .bind(gap_fill.pre_ex_close)
+                .bind(gap_fill.filled_on)
+                .bind(gap_fill.trading_days_to_fill)
+                .execute(&mut *tx)
+                .await
+                .context("Failed to upsert dividend gap fill to PG")?;
+        }
+        tx.commit()
+            .await
+            .context("Failed to commit dividend gap fill")?;
+
+        Ok(())
+    }
+
+    async fn fetch_by_security_codes(
+        &self,
+        security_codes: &[String],
+    ) -> Result<Vec<DividendGapFill>> {
+        if security_codes.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let sql = r#"
+            SELECT
+                security_code, "year" AS year, quarter, ex_dividend_date, cash_dividend,
+                pre_ex_close, filled_on, trading_days_to_fill
+            FROM dividend_gap_fill
+            WHERE security_code = ANY($1)
+            ORDER BY security_code, ex_dividend_date DESC
+        "#;
+
+        let rows = sqlx::query_as::<_, GapFillDbRow>(sql)
+            .bind(security_codes)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch dividend gap fills from PG")?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[tokio::test]
+    #[cfg_attr(
+        not(feature = "integration-tests"),
+        ignore = "需要外部服務（PostgreSQL/Redis），請加 --features integration-tests 執行"
+    )]
+    async fn test_upsert_and_fetch() {
+        dotenvy::dotenv().ok();
+        if database::ping().await.is_err() {
+            println!("跳過 PgDividendGapFillRepository DB 整合測試：無資料庫連接");
+            return;
+        }
+
+        let code = "__GF__";
+        let cleanup = || async {
+            let _ = sqlx::query("DELETE FROM dividend_gap_fill WHERE security_code = $1")
+                .bind(code)
+                .execute(database::get_connection())
+                .await;
+        };
+        cleanup().await;
+
+        let repo = PgDividendGapFillRepository::new();
+        let mut gap_fill = DividendGapFill {
+            security_code: code.to_string(),
+            year: 2099,
+            quarter: "Q1".to_string(),
+            ex_dividend_date: NaiveDate::from_ymd_opt(2099, 12, 1).unwrap(),
+            cash_dividend: dec!(5),
+            pre_ex_close: dec!(100),
+            filled_on: None,
+            trading_days_to_fill: None,
+        };
+        repo.upsert(std::slice::from_ref(&gap_fill))
+            .await
+            .expect("upsert unfilled");
+
+        gap_fill.filled_on = NaiveDate::from_ymd_opt(2099, 12, 3);
+        gap_fill.trading_days_to_fill = Some(3);
+        repo.upsert(std::slice::from_ref(&gap_fill))
+            .await
+            .expect("upsert filled");
+
+        let stored = repo
+            .fetch_by_security_codes(&[code.to_string()])
+            .await
+            .expect("fetch");
+        assert_eq!(stored, vec![gap_fill]);
+
+        cleanup().await;
+    }
+}
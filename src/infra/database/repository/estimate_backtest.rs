@@ -0,0 +1,434 @@
+use std::collections::BTreeMap;
+
+use crate::core::declare::Industry;
+use crate::domain::valuation::{
+    BacktestHorizon, BacktestReport, BacktestReportQuery, BacktestScope, EstimateSignal,
+    EstimateSnapshot, SignalReport, SignalSummary, repository::EstimateBacktestRepository,
+};
+use crate::infra::database;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::FromRow;
+
+/// 基於 PostgreSQL 的估價回測倉儲實現 (PgEstimateBacktestRepository)。
+///
+/// 讀取 `estimate` 的歷史估價，並讀寫 `estimate_backtest` 彙總表。
+pub struct PgEstimateBacktestRepository;
+
+impl PgEstimateBacktestRepository {
+    /// 建立新的 PgEstimateBacktestRepository 實例。
+    pub fn new() -> Self {
+        PgEstimateBacktestRepository
+    }
+}
+
+impl Default for PgEstimateBacktestRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 估價快照的資料列。
+#[derive(FromRow)]
+struct SnapshotDbRow {
+    security_code: String,
+    market_id: i32,
+    industry_id: i32,
+    closing_price: Decimal,
+    cheap: Decimal,
+    expensive: Decimal,
+}
+
+impl From<SnapshotDbRow> for EstimateSnapshot {
+    fn from(row: SnapshotDbRow) -> Self {
+        EstimateSnapshot {
+            security_code: row.security_code,
+            market_id: row.market_id,
+            industry_id: row.industry_id,
+            closing_price: row.closing_price,
+            cheap: row.cheap,
+            expensive: row.expensive,
+        }
+    }
+}
+
+/// 回測報告的資料列：單一持有期間、範圍與訊號。
+#[derive(FromRow)]
+struct ReportDbRow {
+    horizon_months: i32,
+    scope: String,
+    scope_id: i32,
+    scope_name: String,
+    signal: String,
+    dates: i64,
+    samples: i64,
+    mean_return_pct: Option<Decimal>,
+    average_median_return_pct: Option<Decimal>,
+    win_rate_pct: Option<Decimal>,
+    first_date: NaiveDate,
+    last_date: NaiveDate,
+    compared_dates: i64,
+    undervalued_beat_ratio_pct: Option<Decimal>,
+}
+
+#[async_trait]
+impl EstimateBacktestRepository for PgEstimateBacktestRepository {
+    async fn fetch_pending_dates(
+        &self,
+        horizon: BacktestHorizon,
+        until: NaiveDate,
+    ) -> Result<Vec<NaiveDate>> {
+        let sql = r#"
+            SELECT DISTINCT e.date
+            FROM estimate e
+            WHERE e.date <= $2
+              AND NOT EXISTS (
+                  SELECT 1
+                  FROM estimate_backtest b
+                  WHERE b.date = e.date
+                    AND b.horizon_months = $1
+                    AND b.scope = 'all'
+              )
+            ORDER BY e.date
+        "#;
+
+        sqlx::query_scalar(sql)
+            .bind(horizon.months() as i32)
+            .bind(until)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch pending estimate backtest dates from PG")
+    }
+
+    async fn fetch_snapshots(&self, date: NaiveDate) -> Result<Vec<EstimateSnapshot>> {
+        // 不過濾目前已下市的股票：回測要看的是當時的母體，排除會產生倖存者偏差；
+        // 期末查無報價的股票由呼叫端自然略過。
+        let sql = r#"
+            SELECT
+                e.security_code,
+                s.stock_exchange_market_id AS market_id,
+                s.stock_industry_id AS industry_id,
+                e.closing_price,
+                e.cheap,
+                e.expensive
+            FROM estimate e
+            JOIN stocks s ON s.stock_symbol = e.security_code
+            WHERE e.date = $1
+              AND s.stock_industry_id != $2
+            ORDER BY e.security_code
+        "#;
+
+        let rows = sqlx::query_as::<_, SnapshotDbRow>(sql)
+            .bind(date)
+            .bind(Industry::ExchangeTradedFund.serial())
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch estimate snapshots from PG")?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn replace_summaries(
+        &self,
+        date: NaiveDate,
+        horizon: BacktestHorizon,
+        summaries: &[SignalSummary],
+    ) -> Result<u64> {
+        let delete_sql = r#"
+            DELETE FROM estimate_backtest
+            WHERE date = $1 AND horizon_months = $2
+        "#;
+        let insert_sql = r#"
+            INSERT INTO estimate_backtest (
+                date, horizon_months, scope, scope_id, signal,
+                samples, mean_return_pct, median_return_pct, win_rate_pct
+            )
+            SELECT $1, $2, * FROM UNNEST(
+                $3::varchar[], $4::int[], $5::varchar[], $6::bigint[],
+                $7::numeric[], $8::numeric[], $9::numeric[]
+            )
+        "#;
+
+        let mut scopes = Vec::with_capacity(summaries.len());
+        let mut scope_ids = Vec::with_capacity(summaries.len());
+        let mut signals = Vec::with_capacity(summaries.len());
+        let mut samples = Vec::with_capacity(summaries.len());
+        let mut means = Vec::with_capacity(summaries.len());
+        let mut medians = Vec::with_capacity(summaries.len());
+        let mut win_rates = Vec::with_capacity(summaries.len());
+        for summary in summaries {
+            scopes.push(summary.scope.kind());
+            scope_ids.push(summary.scope.id());
+            signals.push(summary.signal.code());
+            samples.push(summary.samples);
+            means.push(summary.mean_return_pct);
+            medians.push(summary.median_return_pct);
+            win_rates.push(summary.win_rate_pct);
+        }
+
+        let mut tx = database::get_tx()
+            .await
+            .context("Failed to begin transaction for estimate backtest")?;
+        sqlx::query(delete_sql)
+            .bind(date)
+            .bind(horizon.months() as i32)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to delete estimate backtest from PG")?;
+        let result = sqlx::query(insert_sql)
+            .bind(date)
+            .bind(horizon.months() as i32)
+            .bind(scopes)
+            .bind(scope_ids)
+            .bind(signals)
+            .bind(samples)
+            .bind(means)
+            .bind(medians)
+            .bind(win_rates)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert estimate backtest to PG")?;
+        tx.commit()
+            .await
+            .context("Failed to commit estimate backtest")?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn fetch_reports(&self, query: &BacktestReportQuery) -> Result<Vec<BacktestReport>> {
+        // 平均報酬與勝率以樣本數加權；勝出比例只比較低估與高估兩組都有樣本的估價日。
+        let sql = r#"
+            WITH filtered AS (
+                SELECT *
+                FROM estimate_backtest
+                WHERE ($1::int IS NULL OR horizon_months = $1)
+                  AND ($2::varchar IS NULL OR scope = $2)
+                  AND ($3::int IS NULL OR scope_id = $3)
+                  AND ($4::date IS NULL OR date >= $4)
+                  AND ($5::date IS NULL OR date <= $5)
+            ), signals AS (
+                SELECT
+                    horizon_months, scope, scope_id, signal,
+                    COUNT(*) FILTER (WHERE samples > 0) AS dates,
+                    SUM(samples)::bigint AS samples,
+                    ROUND(SUM(mean_return_pct * samples) / NULLIF(SUM(samples), 0), 4)
+                        AS mean_return_pct,
+                    ROUND(AVG(median_return_pct), 4) AS average_median_return_pct,
+                    ROUND(SUM(win_rate_pct * samples) / NULLIF(SUM(samples), 0), 2)
+                        AS win_rate_pct,
+                    MIN(date) AS first_date,
+                    MAX(date) AS last_date
+                FROM filtered
+                GROUP BY horizon_months, scope, scope_id, signal
+            ), beats AS (
+                SELECT
+                    u.horizon_months, u.scope, u.scope_id,
+                    COUNT(*) AS compared_dates,
+                    ROUND(
+                        100.0 * COUNT(*) FILTER (WHERE u.mean_return_pct > o.mean_return_pct)
+                            / COUNT(*),
+                        2
+                    ) AS undervalued_beat_ratio_pct
+                FROM filtered u
+                JOIN filtered o
+                  ON o.date = u.date
+                 AND o.horizon_months = u.horizon_months
+                 AND o.scope = u.scope
+                 AND o.scope_id = u.scope_id
+                 AND o.signal = 'overvalued'
+                WHERE u.signal = 'undervalued'
+                  AND u.samples > 0
+                  AND o.samples > 0
+                GROUP BY u.horizon_months, u.scope, u.scope_id
+            )
+            SELECT
+                s.horizon_months, s.scope, s.scope_id,
+                COALESCE(
+                    CASE s.scope
+                        WHEN 'market' THEN m.name
+                        WHEN 'industry' THEN i.name
+                        ELSE '全市場'
+                    END,
+                    ''
+                )::varchar AS scope_name,
+                s.signal, s.dates, s.samples, s.mean_return_pct,
+                s.average_median_return_pct, s.win_rate_pct, s.first_date, s.last_date,
+                COALESCE(b.compared_dates, 0) AS compared_dates,
+                b.undervalued_beat_ratio_pct
+            FROM signals s
+            LEFT JOIN beats b
+              ON b.horizon_months = s.horizon_months
+             AND b.scope = s.scope
+             AND b.scope_id = s.scope_id
+            LEFT JOIN stock_exchange_market m
+              ON s.scope = 'market' AND m.stock_exchange_market_id = s.scope_id
+            LEFT JOIN stock_industry i
+              ON s.scope = 'industry' AND i.stock_industry_id = s.scope_id
+        "#;
+
+        let rows = sqlx::query_as::<_, ReportDbRow>(sql)
+            .bind(query.horizon.map(|horizon| horizon.months() as i32))
+            .bind(query.scope_kind)
+            .bind(query.scope_id)
+            .bind(query.from)
+            .bind(query.to)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch estimate backtest reports from PG")?;
+
+        let mut reports: BTreeMap<(BacktestHorizon, BacktestScope), BacktestReport> =
+            BTreeMap::new();
+        for row in rows {
+            let (Some(horizon), Some(scope), Some(signal)) = (
+                BacktestHorizon::from_months(row.horizon_months),
+                BacktestScope::from_parts(&row.scope, row.scope_id),
+                EstimateSignal::from_code(&row.signal),
+            ) else {
+                continue;
+            };
+            let report = reports
+                .entry((horizon, scope))
+                .or_insert_with(|| BacktestReport {
+                    horizon,
+                    scope,
+                    scope_name: row.scope_name.clone(),
+                    first_date: row.first_date,
+                    last_date: row.last_date,
+                    signals: Vec::new(),
+                    compared_dates: row.compared_dates,
+                    undervalued_beat_ratio_pct: row.undervalued_beat_ratio_pct,
+                });
+            report.first_date = report.first_date.min(row.first_date);
+            report.last_date = report.last_date.max(row.last_date);
+            if row.samples > 0 {
+                report.signals.push(SignalReport {
+                    signal,
+                    dates: row.dates,
+                    samples: row.samples,
+                    mean_return_pct: row.mean_return_pct,
+                    average_median_return_pct: row.average_median_return_pct,
+                    win_rate_pct: row.win_rate_pct,
+                });
+            }
+        }
+
+        Ok(reports
+            .into_values()
+            .map(|mut report| {
+                report.signals.sort_by_key(|signal| signal.signal);
+                report
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+    use crate::domain::valuation::{ForwardReturnSample, summarize};
+
+    #[tokio::test]
+    #[cfg_attr(
+        not(feature = "integration-tests"),
+        ignore = "需要外部服務（PostgreSQL/Redis），請加 --features integration-tests 執行"
+    )]
+    async fn test_replace_and_report_summaries() {
+        dotenvy::dotenv().ok();
+        if database::ping().await.is_err() {
+            println!("跳過 PgEstimateBacktestRepository DB 整合測試：無資料庫連接");
+            return;
+        }
+
+        let first = NaiveDate::from_ymd_opt(2099, 1, 5).unwrap();
+        let second = NaiveDate::from_ymd_opt(2099, 1, 6).unwrap();
+        let cleanup = || async {
+            let _ = sqlx::query("DELETE FROM estimate_backtest WHERE date >= '2099-01-01'")
+                .execute(database::get_connection())
+                .await;
+        };
+        cleanup().await;
+
+        let sample = |signal, pct| ForwardReturnSample {
+            security_code: "__EB__".to_string(),
+            market_id: 2,
+            industry_id: 24,
+            signal,
+            return_pct: pct,
+        };
+        let horizon = BacktestHorizon::ThreeMonths;
+        let repo = PgEstimateBacktestRepository::new();
+        repo.replace_summaries(
+            first,
+            horizon,
+            &summarize(
+                first,
+                horizon,
+                &[
+                    sample(EstimateSignal::Undervalued, dec!(10)),
+                    sample(EstimateSignal::Overvalued, dec!(2)),
+                ],
+            ),
+        )
+        .await
+        .expect("replace first");
+        // 重跑同一天只留下最新結果。
+        repo.replace_summaries(
+            first,
+            horizon,
+            &summarize(
+                first,
+                horizon,
+                &[
+                    sample(EstimateSignal::Undervalued, dec!(8)),
+                    sample(EstimateSignal::Overvalued, dec!(-4)),
+                ],
+            ),
+        )
+        .await
+        .expect("replace first again");
+        repo.replace_summaries(
+            second,
+            horizon,
+            &summarize(
+                second,
+                horizon,
+                &[
+                    sample(EstimateSignal::Undervalued, dec!(1)),
+                    sample(EstimateSignal::Undervalued, dec!(3)),
+                    sample(EstimateSignal::Overvalued, dec!(5)),
+                ],
+            ),
+        )
+        .await
+        .expect("replace second");
+
+        let reports = repo
+            .fetch_reports(&BacktestReportQuery {
+                horizon: Some(horizon),
+                scope_kind: Some("all"),
+                scope_id: None,
+                from: Some(first),
+                to: None,
+            })
+            .await
+            .expect("fetch reports");
+        assert_eq!(reports.len(), 1);
+        let report = &reports[0];
+        assert_eq!(report.scope, BacktestScope::All);
+        assert_eq!(report.first_date, first);
+        assert_eq!(report.last_date, second);
+        assert_eq!(report.compared_dates, 2);
+        assert_eq!(report.undervalued_beat_ratio_pct, Some(dec!(50)));
+        let undervalued = &report.signals[0];
+        assert_eq!(undervalued.signal, EstimateSignal::Undervalued);
+        assert_eq!(undervalued.samples, 3);
+        assert_eq!(undervalued.mean_return_pct, Some(dec!(4)));
+        assert_eq!(report.spread_pct(), Some(dec!(3.5)));
+
+        cleanup().await;
+    }
+}
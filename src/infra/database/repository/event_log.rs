@@ -0,0 +1,172 @@
+use crate::domain::event_log::{
+    entity::{EventQuery, StoredEvent},
+    repository::EventLogRepository,
+};
+use crate::domain::events::DomainEvent;
+use crate::infra::database;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Local};
+use sqlx::FromRow;
+
+/// 基於 PostgreSQL 的領域事件日誌倉儲實現 (PgEventLogRepository)。
+///
+/// 讀寫 `domain_event_log`；事件以 JSON 保存，序號由 `bigserial` 配發。
+pub struct PgEventLogRepository;
+
+impl PgEventLogRepository {
+    /// 建立新的 PgEventLogRepository 實例。
+    pub fn new() -> Self {
+        PgEventLogRepository
+    }
+}
+
+impl Default for PgEventLogRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `domain_event_log` 的資料列。
+#[derive(FromRow)]
+struct EventDbRow {
+    sequence: i64,
+    payload: String,
+    recorded_time: DateTime<Local>,
+}
+
+impl TryFrom<EventDbRow> for StoredEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(row: EventDbRow) -> Result<Self> {
+        Ok(StoredEvent {
+            event: serde_json::from_str(&row.payload).with_context(|| {
+                format!("Failed to parse payload of domain event #{}", row.sequence)
+            })?,
+            sequence: row.sequence,
+            recorded_at: row.recorded_time,
+        })
+    }
+}
+
+#[async_trait]
+impl EventLogRepository for PgEventLogRepository {
+    async fn append(&self, event: &DomainEvent) -> Result<i64> {
+        let payload = serde_json::to_string(event).context("Failed to serialize domain event")?;
+
+        let sequence: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO domain_event_log (event_type, symbol, occurred_at, payload)
+            VALUES ($1, $2, $3, $4::jsonb)
+            RETURNING sequence
+            "#,
+        )
+        .bind(event.event_type())
+        .bind(event.symbol())
+        .bind(event.occurred_at())
+        .bind(payload)
+        .fetch_one(database::get_connection())
+        .await
+        .context("Failed to append domain event to PG")?;
+
+        Ok(sequence)
+    }
+
+    async fn query(&self, query: &EventQuery) -> Result<Vec<StoredEvent>> {
+        let rows: Vec<EventDbRow> = sqlx::query_as(
+            r#"
+            SELECT sequence, payload::text AS payload, recorded_time
+            FROM domain_event_log
+            WHERE ($1::varchar IS NULL OR event_type = $1)
+              AND ($2::varchar IS NULL OR symbol = $2)
+              AND ($3::timestamptz IS NULL OR occurred_at >= $3)
+              AND ($4::timestamptz IS NULL OR occurred_at < $4)
+              AND ($5::bigint IS NULL OR sequence > $5)
+            ORDER BY sequence
+            LIMIT $6
+            "#,
+        )
+        .bind(query.event_type.as_deref())
+        .bind(query.symbol.as_deref())
+        .bind(query.from)
+        .bind(query.to)
+        .bind(query.after_sequence)
+        .bind(query.limit)
+        .fetch_all(database::get_connection())
+        .await
+        .context("Failed to query domain events from PG")?;
+
+        rows.into_iter().map(StoredEvent::try_from).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[tokio::test]
+    #[cfg_attr(
+        not(feature = "integration-tests"),
+        ignore = "需要外部服務（PostgreSQL/Redis），請加 --features integration-tests 執行"
+    )]
+    async fn test_append_and_query() {
+        dotenvy::dotenv().ok();
+        if database::ping().await.is_err() {
+            println!("跳過 PgEventLogRepository DB 整合測試：無資料庫連接");
+            return;
+        }
+
+        let cleanup = || async {
+            let _ = sqlx::query("DELETE FROM domain_event_log WHERE symbol LIKE 'T99%'")
+                .execute(database::get_connection())
+                .await;
+        };
+        cleanup().await;
+
+        let started = Local::now();
+        let nav = |symbol: &str, new_nav| DomainEvent::NetAssetValueUpdated {
+            symbol: symbol.to_string(),
+            old_nav: dec!(10),
+            new_nav,
+            occurred_at: Local::now(),
+        };
+        let events = [
+            nav("T991", dec!(10.5)),
+            nav("T992", dec!(11)),
+            nav("T991", dec!(10.25)),
+        ];
+        let repo = PgEventLogRepository::new();
+        let mut sequences = Vec::new();
+        for event in &events {
+            sequences.push(repo.append(event).await.expect("append"));
+        }
+
+        let by_symbol = repo
+            .query(&EventQuery {
+                event_type: Some("NetAssetValueUpdated".to_string()),
+                symbol: Some("T991".to_string()),
+                from: Some(started),
+                ..Default::default()
+            })
+            .await;
+        let after_first = repo
+            .query(&EventQuery {
+                symbol: Some("T991".to_string()),
+                after_sequence: Some(sequences[0]),
+                ..Default::default()
+            })
+            .await;
+        cleanup().await;
+
+        assert!(sequences.windows(2).all(|pair| pair[0] < pair[1]));
+        let by_symbol = by_symbol.expect("query by symbol");
+        assert_eq!(by_symbol.len(), 2);
+        assert_eq!(by_symbol[0].event, events[0]);
+        assert_eq!(by_symbol[1].event, events[2]);
+        let after_first = after_first.expect("query after sequence");
+        assert_eq!(after_first.len(), 1);
+        assert_eq!(after_first[0].sequence, sequences[2]);
+    }
+}
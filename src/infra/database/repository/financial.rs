@@ -14,7 +14,7 @@ use crate::{
 };
 use anyhow::Result;
 use async_trait::async_trait;
-use chrono::NaiveDate;
+use chrono::{DateTime, Local, NaiveDate};
 use rust_decimal::Decimal;
 
 /// PostgreSQL 實作之財報與營收倉儲。
@@ -218,6 +218,20 @@ impl FinancialRepository for PgFinancialRepository {
         financial_statement::fetch_cumulative_eps(security_code, year, quarters).await
     }
 
+    async fn fetch_financial_statements_as_of(
+        &self,
+        security_code: &str,
+        as_of: DateTime<Local>,
+    ) -> Result<Vec<DomainFinancialStatement>> {
+        // 由版本歷史還原當時已知的財報。
+        let table_statements = financial_statement::fetch_as_of(security_code, as_of).await?;
+        let domain_statements = table_statements
+            .into_iter()
+            .map(DomainFinancialStatement::from)
+            .collect();
+        Ok(domain_statements)
+    }
+
     // === 月營收 (MonthlyRevenue) ===
 
     async fn save_monthly_revenue(&self, revenue: &DomainMonthlyRevenue) -> Result<()> {
@@ -243,6 +257,20 @@ impl FinancialRepository for PgFinancialRepository {
         Ok(())
     }
 
+    async fn fetch_monthly_revenues_as_of(
+        &self,
+        security_code: &str,
+        as_of: DateTime<Local>,
+    ) -> Result<Vec<DomainMonthlyRevenue>> {
+        // 由版本歷史還原當時已知的月營收。
+        let table_revenues = revenue::fetch_as_of(security_code, as_of).await?;
+        let domain_revenues = table_revenues
+            .into_iter()
+            .map(DomainMonthlyRevenue::from)
+            .collect();
+        Ok(domain_revenues)
+    }
+
     // === 價格估值 (PriceEstimate) ===
 
     async fn rebuild_price_estimates(&self, date: NaiveDate, years: String) -> Result<()> {
@@ -0,0 +1,284 @@
+use std::{collections::HashMap, str::FromStr};
+
+use crate::core::declare::Quarter;
+use crate::domain::financial::{
+    health::FinancialHealthScore, repository::FinancialHealthRepository,
+};
+use crate::infra::database;
+use anyhow::{Context, Result, anyhow};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::FromRow;
+
+/// 基於 PostgreSQL 的財務體質分數倉儲實現 (PgFinancialHealthRepository)。
+///
+/// 讀寫 `financial_health_score`，F-score 各項檢查與計算輸入以 JSON 文字存放。
+pub struct PgFinancialHealthRepository;
+
+impl PgFinancialHealthRepository {
+    /// 建立新的 PgFinancialHealthRepository 實例。
+    pub fn new() -> Self {
+        PgFinancialHealthRepository
+    }
+}
+
+impl Default for PgFinancialHealthRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `financial_health_score` 的資料列。
+#[derive(FromRow)]
+struct ScoreDbRow {
+    security_code: String,
+    year: i32,
+    quarter: String,
+    period_end: NaiveDate,
+    f_score: Option<i16>,
+    f_score_criteria: String,
+    altman_z_score: Option<Decimal>,
+    accruals_ratio: Option<Decimal>,
+    cash_conversion_ratio: Option<Decimal>,
+    fcf_yield_pct: Option<Decimal>,
+    interest_coverage: Option<Decimal>,
+    debt_to_equity: Option<Decimal>,
+    debt_to_equity_change: Option<Decimal>,
+    inputs: String,
+}
+
+impl TryFrom<ScoreDbRow> for FinancialHealthScore {
+    type Error = anyhow::Error;
+
+    fn try_from(row: ScoreDbRow) -> Result<Self> {
+        Ok(FinancialHealthScore {
+            quarter: Quarter::from_str(&row.quarter)
+                .map_err(|_| anyhow!("Unknown quarter '{}' in health score", row.quarter))?,
+            f_score_criteria: serde_json::from_str(&row.f_score_criteria)
+                .context("Failed to parse f_score_criteria of health score")?,
+            inputs: serde_json::from_str(&row.inputs)
+                .context("Failed to parse inputs of health score")?,
+            security_code: row.security_code,
+            year: row.year,
+            period_end: row.period_end,
+            f_score: row.f_score,
+            altman_z_score: row.altman_z_score,
+            accruals_ratio: row.accruals_ratio,
+            cash_conversion_ratio: row.cash_conversion_ratio,
+            fcf_yield_pct: row.fcf_yield_pct,
+            interest_coverage: row.interest_coverage,
+            debt_to_equity: row.debt_to_equity,
+            debt_to_equity_change: row.debt_to_equity_change,
+        })
+    }
+}
+
+#[async_trait]
+impl FinancialHealthRepository for PgFinancialHealthRepository {
+    async fn fetch_closing_prices(
+        &self,
+        on: NaiveDate,
+    ) -> Result<HashMap<String, (NaiveDate, Decimal)>> {
+        // 季末可能遇到連假，往前找兩週內最後一個有成交的交易日。
+        let rows: Vec<(String, NaiveDate, Decimal)> = sqlx::query_as(
+            r#"
+            SELECT DISTINCT ON (stock_symbol) stock_symbol, "Date", "ClosingPrice"
+            FROM "DailyQuotes"
+            WHERE "Date" <= $1
+              AND "Date" >= $1::date - 14
+              AND "ClosingPrice" > 0
+            ORDER BY stock_symbol, "Date" DESC
+            "#,
+        )
+        .bind(on)
+        .fetch_all(database::get_connection())
+        .await
+        .context("Failed to fetch quarter-end closing prices from PG")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(security_code, date, price)| (security_code, (date, price)))
+            .collect())
+    }
+
+    async fn upsert(&self, scores: &[FinancialHealthScore]) -> Result<u64> {
+        if scores.is_empty() {
+            return Ok(0);
+        }
+
+        let sql = r#"
+            INSERT INTO financial_health_score (
+                security_code, year, quarter, period_end, f_score, f_score_criteria,
+                altman_z_score, accruals_ratio, cash_conversion_ratio, fcf_yield_pct,
+                interest_coverage, debt_to_equity, debt_to_equity_change, inputs
+            )
+            SELECT * FROM UNNEST(
+                $1::varchar[], $2::int[], $3::varchar[], $4::date[], $5::smallint[], $6::text[],
+                $7::numeric[], $8::numeric[], $9::numeric[], $10::numeric[],
+                $11::numeric[], $12::numeric[], $13::numeric[], $14::text[]
+            )
+            ON CONFLICT (security_code, year, quarter) DO UPDATE SET
+                period_end = EXCLUDED.period_end,
+                f_score = EXCLUDED.f_score,
+                f_score_criteria = EXCLUDED.f_score_criteria,
+                altman_z_score = EXCLUDED.altman_z_score,
+                accruals_ratio = EXCLUDED.accruals_ratio,
+                cash_conversion_ratio = EXCLUDED.cash_conversion_ratio,
+                fcf_yield_pct = EXCLUDED.fcf_yield_pct,
+                interest_coverage = EXCLUDED.interest_coverage,
+                debt_to_equity = EXCLUDED.debt_to_equity,
+                debt_to_equity_change = EXCLUDED.debt_to_equity_change,
+                inputs = EXCLUDED.inputs,
+                updated_time = now()
+        "#;
+
+        let len = scores.len();
+        let mut security_codes = Vec::with_capacity(len);
+        let mut years = Vec::with_capacity(len);
+        let mut quarters = Vec::with_capacity(len);
+        let mut period_ends = Vec::with_capacity(len);
+        let mut f_scores = Vec::with_capacity(len);
+        let mut criteria = Vec::with_capacity(len);
+        let mut altman_z_scores = Vec::with_capacity(len);
+        let mut accruals_ratios = Vec::with_capacity(len);
+        let mut cash_conversion_ratios = Vec::with_capacity(len);
+        let mut fcf_yields = Vec::with_capacity(len);
+        let mut interest_coverages = Vec::with_capacity(len);
+        let mut debt_to_equities = Vec::with_capacity(len);
+        let mut debt_to_equity_changes = Vec::with_capacity(len);
+        let mut inputs = Vec::with_capacity(len);
+        for score in scores {
+            security_codes.push(score.security_code.clone());
+            years.push(score.year);
+            quarters.push(score.quarter.to_string());
+            period_ends.push(score.period_end);
+            f_scores.push(score.f_score);
+            criteria.push(
+                serde_json::to_string(&score.f_score_criteria)
+                    .context("Failed to serialize f_score_criteria")?,
+            );
+            altman_z_scores.push(score.altman_z_score);
+            accruals_ratios.push(score.accruals_ratio);
+            cash_conversion_ratios.push(score.cash_conversion_ratio);
+            fcf_yields.push(score.fcf_yield_pct);
+            interest_coverages.push(score.interest_coverage);
+            debt_to_equities.push(score.debt_to_equity);
+            debt_to_equity_changes.push(score.debt_to_equity_change);
+            inputs.push(
+                serde_json::to_string(&score.inputs)
+                    .context("Failed to serialize health score inputs")?,
+            );
+        }
+
+        let result = sqlx::query(sql)
+            .bind(security_codes)
+            .bind(years)
+            .bind(quarters)
+            .bind(period_ends)
+            .bind(f_scores)
+            .bind(criteria)
+            .bind(altman_z_scores)
+            .bind(accruals_ratios)
+            .bind(cash_conversion_ratios)
+            .bind(fcf_yields)
+            .bind(interest_coverages)
+            .bind(debt_to_equities)
+            .bind(debt_to_equity_changes)
+            .bind(inputs)
+            .execute(database::get_connection())
+            .await
+            .context("Failed to upsert financial health scores to PG")?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn fetch_latest(
+        &self,
+        security_code: &str,
+        as_of: Option<NaiveDate>,
+    ) -> Result<Option<FinancialHealthScore>> {
+        let row: Option<ScoreDbRow> = sqlx::query_as(
+            r#"
+            SELECT security_code, year, quarter, period_end, f_score, f_score_criteria,
+                   altman_z_score, accruals_ratio, cash_conversion_ratio, fcf_yield_pct,
+                   interest_coverage, debt_to_equity, debt_to_equity_change, inputs
+            FROM financial_health_score
+            WHERE security_code = $1
+              AND ($2::date IS NULL OR period_end <= $2)
+            ORDER BY period_end DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(security_code)
+        .bind(as_of)
+        .fetch_optional(database::get_connection())
+        .await
+        .context("Failed to fetch latest financial health score from PG")?;
+
+        row.map(FinancialHealthScore::try_from).transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use crate::domain::financial::health::{BalanceSnapshot, FinancialHealthInputs};
+
+    use super::*;
+
+    #[tokio::test]
+    #[cfg_attr(
+        not(feature = "integration-tests"),
+        ignore = "需要外部服務（PostgreSQL/Redis），請加 --features integration-tests 執行"
+    )]
+    async fn test_upsert_and_fetch_latest() {
+        dotenvy::dotenv().ok();
+        if database::ping().await.is_err() {
+            println!("跳過 PgFinancialHealthRepository DB 整合測試：無資料庫連接");
+            return;
+        }
+
+        let cleanup = || async {
+            let _ = sqlx::query("DELETE FROM financial_health_score WHERE year = 2099")
+                .execute(database::get_connection())
+                .await;
+        };
+        cleanup().await;
+
+        let score = |quarter, month, day| {
+            let inputs = FinancialHealthInputs {
+                balance: BalanceSnapshot {
+                    total_liabilities: Some(dec!(300)),
+                    total_equity: Some(dec!(700)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            FinancialHealthScore::evaluate(
+                "2330",
+                2099,
+                quarter,
+                NaiveDate::from_ymd_opt(2099, month, day).unwrap(),
+                inputs,
+            )
+        };
+        let repo = PgFinancialHealthRepository::new();
+        repo.upsert(&[score(Quarter::Q1, 3, 31), score(Quarter::Q2, 6, 30)])
+            .await
+            .expect("upsert");
+
+        let latest = repo.fetch_latest("2330", None).await.expect("fetch latest");
+        let as_of = repo
+            .fetch_latest("2330", NaiveDate::from_ymd_opt(2099, 5, 15))
+            .await
+            .expect("fetch as of");
+        cleanup().await;
+
+        assert_eq!(latest, Some(score(Quarter::Q2, 6, 30)));
+        let as_of = as_of.expect("Q1 score");
+        assert_eq!(as_of.quarter, Quarter::Q1);
+        assert_eq!(as_of.debt_to_equity, Some(dec!(0.4286)));
+    }
+}
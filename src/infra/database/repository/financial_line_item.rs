@@ -0,0 +1,268 @@
+use std::str::FromStr;
+
+use crate::core::declare::Quarter;
+use crate::domain::financial::{
+    line_item::{FinancialLineItem, LineItem, PeriodType, StatementKind},
+    repository::FinancialLineItemRepository,
+};
+use crate::infra::database;
+use anyhow::{Context, Result, anyhow};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use sqlx::FromRow;
+
+/// 基於 PostgreSQL 的財報科目倉儲實現 (PgFinancialLineItemRepository)。
+///
+/// 讀寫 `financial_statement_line_item`，列舉值以 snake_case 代碼存放。
+pub struct PgFinancialLineItemRepository;
+
+impl PgFinancialLineItemRepository {
+    /// 建立新的 PgFinancialLineItemRepository 實例。
+    pub fn new() -> Self {
+        PgFinancialLineItemRepository
+    }
+}
+
+impl Default for PgFinancialLineItemRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `financial_statement_line_item` 的資料列。
+#[derive(FromRow)]
+struct LineItemDbRow {
+    security_code: String,
+    year: i32,
+    quarter: String,
+    item_code: String,
+    period_type: String,
+    value: Decimal,
+    element: String,
+    consolidated: bool,
+}
+
+impl TryFrom<LineItemDbRow> for FinancialLineItem {
+    type Error = anyhow::Error;
+
+    fn try_from(row: LineItemDbRow) -> Result<Self> {
+        Ok(FinancialLineItem {
+            quarter: Quarter::from_str(&row.quarter)
+                .map_err(|_| anyhow!("Unknown quarter '{}' in line item", row.quarter))?,
+            item: LineItem::from_str(&row.item_code)
+                .map_err(|_| anyhow!("Unknown item code '{}' in line item", row.item_code))?,
+            period: PeriodType::from_str(&row.period_type)
+                .map_err(|_| anyhow!("Unknown period type '{}' in line item", row.period_type))?,
+            security_code: row.security_code,
+            year: row.year,
+            value: row.value,
+            element: row.element,
+            consolidated: row.consolidated,
+        })
+    }
+}
+
+#[async_trait]
+impl FinancialLineItemRepository for PgFinancialLineItemRepository {
+    async fn upsert(&self, items: &[FinancialLineItem]) -> Result<u64> {
+        if items.is_empty() {
+            return Ok(0);
+        }
+
+        let sql = r#"
+            INSERT INTO financial_statement_line_item (
+                security_code, year, quarter, statement, item_code, period_type,
+                value, element, consolidated
+            )
+            SELECT * FROM UNNEST(
+                $1::varchar[], $2::int[], $3::varchar[], $4::varchar[], $5::varchar[],
+                $6::varchar[], $7::numeric[], $8::varchar[], $9::bool[]
+            )
+            ON CONFLICT (security_code, year, quarter, item_code, period_type) DO UPDATE SET
+                statement = EXCLUDED.statement,
+                value = EXCLUDED.value,
+                element = EXCLUDED.element,
+                consolidated = EXCLUDED.consolidated,
+                updated_time = now()
+        "#;
+
+        let len = items.len();
+        let mut security_codes = Vec::with_capacity(len);
+        let mut years = Vec::with_capacity(len);
+        let mut quarters = Vec::with_capacity(len);
+        let mut statements = Vec::with_capacity(len);
+        let mut item_codes = Vec::with_capacity(len);
+        let mut period_types = Vec::with_capacity(len);
+        let mut values = Vec::with_capacity(len);
+        let mut elements = Vec::with_capacity(len);
+        let mut consolidated = Vec::with_capacity(len);
+        for item in items {
+            security_codes.push(item.security_code.clone());
+            years.push(item.year);
+            quarters.push(item.quarter.to_string());
+            statements.push(item.statement().to_string());
+            item_codes.push(item.item.to_string());
+            period_types.push(item.period.to_string());
+            values.push(item.value);
+            elements.push(item.element.clone());
+            consolidated.push(item.consolidated);
+        }
+
+        let result = sqlx::query(sql)
+            .bind(security_codes)
+            .bind(years)
+            .bind(quarters)
+            .bind(statements)
+            .bind(item_codes)
+            .bind(period_types)
+            .bind(values)
+            .bind(elements)
+            .bind(consolidated)
+            .execute(database::get_connection())
+            .await
+            .context("Failed to upsert financial statement line items to PG")?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn fetch(
+        &self,
+        security_code: &str,
+        year: i32,
+        quarter: Quarter,
+        statement: Option<StatementKind>,
+    ) -> Result<Vec<FinancialLineItem>> {
+        let rows: Vec<LineItemDbRow> = sqlx::query_as(
+            r#"
+            SELECT security_code, year, quarter, item_code, period_type, value, element, consolidated
+            FROM financial_statement_line_item
+            WHERE security_code = $1
+              AND year = $2
+              AND quarter = $3
+              AND ($4::varchar IS NULL OR statement = $4)
+            ORDER BY statement, item_code, period_type
+            "#,
+        )
+        .bind(security_code)
+        .bind(year)
+        .bind(quarter.to_string())
+        .bind(statement.map(|statement| statement.to_string()))
+        .fetch_all(database::get_connection())
+        .await
+        .context("Failed to fetch financial statement line items from PG")?;
+
+        rows.into_iter().map(FinancialLineItem::try_from).collect()
+    }
+
+    async fn fetch_quarter(&self, year: i32, quarter: Quarter) -> Result<Vec<FinancialLineItem>> {
+        let rows: Vec<LineItemDbRow> = sqlx::query_as(
+            r#"
+            SELECT security_code, year, quarter, item_code, period_type, value, element, consolidated
+            FROM financial_statement_line_item
+            WHERE year = $1 AND quarter = $2
+            ORDER BY security_code
+            "#,
+        )
+        .bind(year)
+        .bind(quarter.to_string())
+        .fetch_all(database::get_connection())
+        .await
+        .context("Failed to fetch quarter financial statement line items from PG")?;
+
+        rows.into_iter().map(FinancialLineItem::try_from).collect()
+    }
+
+    async fn count_companies(&self, year: i32, quarter: Quarter) -> Result<i64> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COUNT(DISTINCT security_code)
+            FROM financial_statement_line_item
+            WHERE year = $1 AND quarter = $2
+            "#,
+        )
+        .bind(year)
+        .bind(quarter.to_string())
+        .fetch_one(database::get_connection())
+        .await
+        .context("Failed to count financial statement line item companies from PG")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[tokio::test]
+    #[cfg_attr(
+        not(feature = "integration-tests"),
+        ignore = "需要外部服務（PostgreSQL/Redis），請加 --features integration-tests 執行"
+    )]
+    async fn test_upsert_and_fetch() {
+        dotenvy::dotenv().ok();
+        if database::ping().await.is_err() {
+            println!("跳過 PgFinancialLineItemRepository DB 整合測試：無資料庫連接");
+            return;
+        }
+
+        let cleanup = || async {
+            let _ = sqlx::query("DELETE FROM financial_statement_line_item WHERE year = 2099")
+                .execute(database::get_connection())
+                .await;
+        };
+        cleanup().await;
+
+        let item = |item, period, value| FinancialLineItem {
+            security_code: "2330".to_string(),
+            year: 2099,
+            quarter: Quarter::Q2,
+            item,
+            period,
+            value,
+            element: format!("ifrs-full:{item}"),
+            consolidated: true,
+        };
+        let repo = PgFinancialLineItemRepository::new();
+        repo.upsert(&[
+            item(LineItem::Revenue, PeriodType::Quarter, dec!(100)),
+            item(LineItem::Revenue, PeriodType::YearToDate, dec!(180)),
+            item(LineItem::TotalAssets, PeriodType::Instant, dec!(5000)),
+        ])
+        .await
+        .expect("upsert");
+        // 重抓同一季時覆寫數值。
+        repo.upsert(&[item(LineItem::Revenue, PeriodType::Quarter, dec!(101))])
+            .await
+            .expect("upsert again");
+
+        let income = repo
+            .fetch(
+                "2330",
+                2099,
+                Quarter::Q2,
+                Some(StatementKind::IncomeStatement),
+            )
+            .await
+            .expect("fetch income statement");
+        let all = repo
+            .fetch("2330", 2099, Quarter::Q2, None)
+            .await
+            .expect("fetch all");
+        let quarter = repo
+            .fetch_quarter(2099, Quarter::Q2)
+            .await
+            .expect("fetch quarter");
+        let companies = repo
+            .count_companies(2099, Quarter::Q2)
+            .await
+            .expect("count companies");
+        cleanup().await;
+
+        assert_eq!(income.len(), 2);
+        assert!(income.contains(&item(LineItem::Revenue, PeriodType::Quarter, dec!(101))));
+        assert_eq!(all.len(), 3);
+        assert_eq!(quarter.len(), 3);
+        assert_eq!(companies, 1);
+    }
+}
@@ -0,0 +1,178 @@
+use std::str::FromStr;
+
+use crate::domain::financial::{
+    repository::FundamentalRevisionRepository,
+    revision::{FundamentalKind, FundamentalRevision},
+};
+use crate::infra::database;
+use anyhow::{Context, Result, anyhow};
+use async_trait::async_trait;
+use chrono::{DateTime, Local};
+use sqlx::FromRow;
+
+/// 基於 PostgreSQL 的基本面版本歷史倉儲實現 (PgFundamentalRevisionRepository)。
+///
+/// 讀取 `fundamental_revision`；版本由資料表觸發器在月營收、財報與股利寫入時追加，
+/// 這裡只負責查詢。
+pub struct PgFundamentalRevisionRepository;
+
+impl PgFundamentalRevisionRepository {
+    /// 建立新的 PgFundamentalRevisionRepository 實例。
+    pub fn new() -> Self {
+        PgFundamentalRevisionRepository
+    }
+}
+
+impl Default for PgFundamentalRevisionRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `fundamental_revision` 的資料列。
+#[derive(FromRow)]
+struct RevisionDbRow {
+    kind: String,
+    security_code: String,
+    period: String,
+    revision: i32,
+    payload: String,
+    first_seen_time: DateTime<Local>,
+    recorded_time: DateTime<Local>,
+}
+
+impl TryFrom<RevisionDbRow> for FundamentalRevision {
+    type Error = anyhow::Error;
+
+    fn try_from(row: RevisionDbRow) -> Result<Self> {
+        Ok(FundamentalRevision {
+            kind: FundamentalKind::from_str(&row.kind)
+                .map_err(|_| anyhow!("Unknown fundamental kind '{}'", row.kind))?,
+            payload: serde_json::from_str(&row.payload)
+                .context("Failed to parse payload of fundamental revision")?,
+            security_code: row.security_code,
+            period: row.period,
+            revision: row.revision,
+            first_seen_time: row.first_seen_time,
+            recorded_time: row.recorded_time,
+        })
+    }
+}
+
+#[async_trait]
+impl FundamentalRevisionRepository for PgFundamentalRevisionRepository {
+    async fn fetch_history(
+        &self,
+        kind: FundamentalKind,
+        security_code: &str,
+        period: Option<&str>,
+    ) -> Result<Vec<FundamentalRevision>> {
+        let rows: Vec<RevisionDbRow> = sqlx::query_as(
+            r#"
+            SELECT kind, security_code, period, revision, payload::text AS payload,
+                   first_seen_time, recorded_time
+            FROM fundamental_revision
+            WHERE kind = $1
+              AND security_code = $2
+              AND ($3::varchar IS NULL OR period = $3)
+            ORDER BY period, revision
+            "#,
+        )
+        .bind(kind.to_string())
+        .bind(security_code)
+        .bind(period)
+        .fetch_all(database::get_connection())
+        .await
+        .context("Failed to fetch fundamental revisions from PG")?;
+
+        rows.into_iter()
+            .map(FundamentalRevision::try_from)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use crate::domain::financial::{repository::FinancialRepository, revision::revenue_period};
+    use crate::infra::database::repository::financial::PgFinancialRepository;
+
+    use super::*;
+
+    /// 月營收更正後會追加版本，as-of 查詢仍能取得更正前的數字。
+    #[tokio::test]
+    #[cfg_attr(
+        not(feature = "integration-tests"),
+        ignore = "需要外部服務（PostgreSQL/Redis），請加 --features integration-tests 執行"
+    )]
+    async fn test_revenue_revision_and_as_of() {
+        dotenvy::dotenv().ok();
+        if database::ping().await.is_err() {
+            println!("跳過 PgFundamentalRevisionRepository DB 整合測試：無資料庫連接");
+            return;
+        }
+
+        let cleanup = || async {
+            let _ = sqlx::query(r#"DELETE FROM "Revenue" WHERE "Date" = 209901"#)
+                .execute(database::get_connection())
+                .await;
+            let _ = sqlx::query(
+                "DELETE FROM fundamental_revision WHERE kind = 'revenue' AND period = '209901'",
+            )
+            .execute(database::get_connection())
+            .await;
+        };
+        cleanup().await;
+
+        let save = |monthly| async move {
+            sqlx::query(
+                r#"
+                INSERT INTO "Revenue" ("SecurityCode", stock_symbol, "Date", "Monthly")
+                VALUES ('2330', '2330', 209901, $1)
+                ON CONFLICT ("SecurityCode", "Date") DO UPDATE SET "Monthly" = EXCLUDED."Monthly"
+                "#,
+            )
+            .bind(monthly)
+            .execute(database::get_connection())
+            .await
+            .expect("save revenue");
+        };
+        save(dec!(100)).await;
+        // 同樣的數值再寫一次不會產生新版本。
+        save(dec!(100)).await;
+        let before_restatement = Local::now();
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        save(dec!(120)).await;
+
+        let history = PgFundamentalRevisionRepository::new()
+            .fetch_history(
+                FundamentalKind::Revenue,
+                "2330",
+                Some(&revenue_period(209901)),
+            )
+            .await;
+        let repo = PgFinancialRepository::new();
+        let as_of_before = repo
+            .fetch_monthly_revenues_as_of("2330", before_restatement)
+            .await;
+        let as_of_now = repo
+            .fetch_monthly_revenues_as_of("2330", Local::now())
+            .await;
+        cleanup().await;
+
+        let history = history.expect("fetch history");
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].changed_fields(&history[0]), vec!["Monthly"]);
+        assert_eq!(history[0].first_seen_time, history[1].first_seen_time);
+
+        let find = |revenues: Vec<crate::domain::financial::entity::MonthlyRevenue>| {
+            revenues
+                .into_iter()
+                .find(|revenue| revenue.date == 209901)
+                .map(|revenue| revenue.monthly)
+        };
+        assert_eq!(find(as_of_before.expect("as of before")), Some(dec!(100)));
+        assert_eq!(find(as_of_now.expect("as of now")), Some(dec!(120)));
+    }
+}
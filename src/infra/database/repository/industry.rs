@@ -0,0 +1,522 @@
+use std::collections::HashMap;
+
+use crate::domain::industry::{
+    IndustryDailyAggregate, IndustryDailyMetric, IndustryLevel, IndustryRevenueGrowth,
+    IndustrySnapshot, RotationSnapshot, RotationWindow, repository::IndustryMetricRepository,
+};
+use crate::infra::database;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::FromRow;
+
+/// 產業分類編號上限（不含）；9000 以上為 ETF、ETN、受益證券，不屬於產業分析範圍。
+const MAX_INDUSTRY_ID: i32 = 9000;
+
+/// 基於 PostgreSQL 的產業指標倉儲實現 (PgIndustryMetricRepository)。
+///
+/// 由 `"DailyQuotes"`、`stocks` 與 `"Revenue"` 彙總，並讀寫 `industry_daily_metric`。
+pub struct PgIndustryMetricRepository;
+
+impl PgIndustryMetricRepository {
+    /// 建立新的 PgIndustryMetricRepository 實例。
+    pub fn new() -> Self {
+        PgIndustryMetricRepository
+    }
+}
+
+impl Default for PgIndustryMetricRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 報價彙總的資料列。
+#[derive(FromRow)]
+struct AggregateDbRow {
+    date: NaiveDate,
+    industry_id: i32,
+    stocks: i32,
+    advances: i32,
+    declines: i32,
+    unchanged: i32,
+    cap_weighted_return_pct: Option<Decimal>,
+    equal_weighted_return_pct: Option<Decimal>,
+    trade_value: Decimal,
+    turnover_share_pct: Option<Decimal>,
+}
+
+impl From<AggregateDbRow> for IndustryDailyAggregate {
+    fn from(row: AggregateDbRow) -> Self {
+        IndustryDailyAggregate {
+            date: row.date,
+            industry_id: row.industry_id,
+            stocks: row.stocks,
+            advances: row.advances,
+            declines: row.declines,
+            unchanged: row.unchanged,
+            cap_weighted_return_pct: row.cap_weighted_return_pct,
+            equal_weighted_return_pct: row.equal_weighted_return_pct,
+            trade_value: row.trade_value,
+            turnover_share_pct: row.turnover_share_pct,
+        }
+    }
+}
+
+/// 產業營收彙總的資料列。
+#[derive(FromRow)]
+struct RevenueDbRow {
+    industry_id: i32,
+    companies: i32,
+    yoy_pct: Option<Decimal>,
+}
+
+/// 產業指標的資料列；`industry_name` 只有查詢快照時才有值。
+#[derive(FromRow)]
+struct MetricDbRow {
+    date: NaiveDate,
+    stock_industry_id: i32,
+    industry_name: String,
+    stocks: i32,
+    advances: i32,
+    declines: i32,
+    unchanged: i32,
+    cap_weighted_return_pct: Option<Decimal>,
+    equal_weighted_return_pct: Option<Decimal>,
+    index_level: Decimal,
+    trade_value: Decimal,
+    turnover_share_pct: Option<Decimal>,
+    revenue_period: Option<i64>,
+    revenue_companies: i32,
+    revenue_yoy_pct: Option<Decimal>,
+}
+
+impl From<MetricDbRow> for IndustrySnapshot {
+    fn from(row: MetricDbRow) -> Self {
+        IndustrySnapshot {
+            industry_name: row.industry_name,
+            metric: IndustryDailyMetric {
+                date: row.date,
+                industry_id: row.stock_industry_id,
+                stocks: row.stocks,
+                advances: row.advances,
+                declines: row.declines,
+                unchanged: row.unchanged,
+                cap_weighted_return_pct: row.cap_weighted_return_pct,
+                equal_weighted_return_pct: row.equal_weighted_return_pct,
+                index_level: row.index_level,
+                trade_value: row.trade_value,
+                turnover_share_pct: row.turnover_share_pct,
+                revenue_period: row.revenue_period,
+                revenue_companies: row.revenue_companies,
+                revenue_yoy_pct: row.revenue_yoy_pct,
+            },
+        }
+    }
+}
+
+/// 類股輪動點位的資料列。
+#[derive(FromRow)]
+struct LevelDbRow {
+    date: NaiveDate,
+    industry_id: i32,
+    industry_name: String,
+    stocks: i32,
+    index_level: Decimal,
+}
+
+#[async_trait]
+impl IndustryMetricRepository for PgIndustryMetricRepository {
+    async fn fetch_daily_aggregates(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<IndustryDailyAggregate>> {
+        // 報酬以「漲跌價差 / 昨收」計算，昨收 = 收盤價 − 漲跌價差；除權息日交易所
+        // 公布的漲跌已相對參考價，不會把除息缺口算成下跌。
+        let sql = r#"
+            WITH base AS (
+                SELECT
+                    dq."Date" AS date,
+                    s.stock_industry_id AS industry_id,
+                    dq."Change" AS change,
+                    dq."ClosingPrice" - dq."Change" AS previous_close,
+                    dq."TradeValue" AS trade_value,
+                    s.issued_share
+                FROM "DailyQuotes" dq
+                JOIN stocks s ON s.stock_symbol = dq.stock_symbol
+                WHERE dq."Date" BETWEEN $1 AND $2
+                  AND s."SuspendListing" = FALSE
+                  AND s.stock_exchange_market_id IN (2, 4)
+                  AND s.stock_industry_id > 0
+                  AND s.stock_industry_id < $3
+                  AND dq."ClosingPrice" > 0
+                  AND dq."ClosingPrice" - dq."Change" > 0
+            ), totals AS (
+                SELECT date, SUM(trade_value) AS trade_value
+                FROM base
+                GROUP BY date
+            )
+            SELECT
+                b.date,
+                b.industry_id,
+                COUNT(*)::int AS stocks,
+                COUNT(*) FILTER (WHERE b.change > 0)::int AS advances,
+                COUNT(*) FILTER (WHERE b.change < 0)::int AS declines,
+                COUNT(*) FILTER (WHERE b.change = 0)::int AS unchanged,
+                ROUND(
+                    100 * SUM(b.issued_share * b.change) FILTER (WHERE b.issued_share > 0)
+                        / NULLIF(
+                            SUM(b.issued_share * b.previous_close) FILTER (WHERE b.issued_share > 0),
+                            0
+                        ),
+                    4
+                ) AS cap_weighted_return_pct,
+                ROUND(100 * AVG(b.change / b.previous_close), 4) AS equal_weighted_return_pct,
+                SUM(b.trade_value) AS trade_value,
+                ROUND(100 * SUM(b.trade_value) / NULLIF(MAX(t.trade_value), 0), 4)
+                    AS turnover_share_pct
+            FROM base b
+            JOIN totals t ON t.date = b.date
+            GROUP BY b.date, b.industry_id
+            ORDER BY b.date, b.industry_id
+        "#;
+
+        let rows = sqlx::query_as::<_, AggregateDbRow>(sql)
+            .bind(from)
+            .bind(to)
+            .bind(MAX_INDUSTRY_ID)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch industry daily aggregates from PG")?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn fetch_revenue_growth(&self, period: i64) -> Result<Vec<IndustryRevenueGrowth>> {
+        // 以合計營收計算年增率，而不是平均個股年增率：小公司的極端成長率不會主導結果。
+        let sql = r#"
+            SELECT
+                s.stock_industry_id AS industry_id,
+                COUNT(*)::int AS companies,
+                ROUND(
+                    100 * (SUM(r."Monthly") / NULLIF(SUM(r."LastYearThisMonth"), 0) - 1),
+                    4
+                ) AS yoy_pct
+            FROM "Revenue" r
+            JOIN stocks s ON s.stock_symbol = r."SecurityCode"
+            WHERE r."Date" = $1
+              AND r."LastYearThisMonth" > 0
+              AND r."Monthly" >= 0
+              AND s.stock_industry_id > 0
+              AND s.stock_industry_id < $2
+            GROUP BY s.stock_industry_id
+        "#;
+
+        let rows = sqlx::query_as::<_, RevenueDbRow>(sql)
+            .bind(period)
+            .bind(MAX_INDUSTRY_ID)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch industry revenue growth from PG")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| IndustryRevenueGrowth {
+                industry_id: row.industry_id,
+                companies: row.companies,
+                yoy_pct: row.yoy_pct,
+            })
+            .collect())
+    }
+
+    async fn fetch_latest_before(&self, date: NaiveDate) -> Result<Vec<IndustryDailyMetric>> {
+        let sql = r#"
+            SELECT DISTINCT ON (m.stock_industry_id)
+                m.date, m.stock_industry_id, ''::varchar AS industry_name, m.stocks,
+                m.advances, m.declines, m.unchanged, m.cap_weighted_return_pct,
+                m.equal_weighted_return_pct, m.index_level, m.trade_value,
+                m.turnover_share_pct, m.revenue_period, m.revenue_companies, m.revenue_yoy_pct
+            FROM industry_daily_metric m
+            WHERE m.date < $1
+            ORDER BY m.stock_industry_id, m.date DESC
+        "#;
+
+        let rows = sqlx::query_as::<_, MetricDbRow>(sql)
+            .bind(date)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch previous industry metrics from PG")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| IndustrySnapshot::from(row).metric)
+            .collect())
+    }
+
+    async fn upsert(&self, metrics: &[IndustryDailyMetric]) -> Result<u64> {
+        if metrics.is_empty() {
+            return Ok(0);
+        }
+
+        let sql = r#"
+            INSERT INTO industry_daily_metric (
+                date, stock_industry_id, stocks, advances, declines, unchanged,
+                cap_weighted_return_pct, equal_weighted_return_pct, index_level, trade_value,
+                turnover_share_pct, revenue_period, revenue_companies, revenue_yoy_pct
+            )
+            SELECT * FROM UNNEST(
+                $1::date[], $2::int[], $3::int[], $4::int[], $5::int[], $6::int[],
+                $7::numeric[], $8::numeric[], $9::numeric[], $10::numeric[],
+                $11::numeric[], $12::bigint[], $13::int[], $14::numeric[]
+            )
+            ON CONFLICT (date, stock_industry_id) DO UPDATE SET
+                stocks = EXCLUDED.stocks,
+                advances = EXCLUDED.advances,
+                declines = EXCLUDED.declines,
+                unchanged = EXCLUDED.unchanged,
+                cap_weighted_return_pct = EXCLUDED.cap_weighted_return_pct,
+                equal_weighted_return_pct = EXCLUDED.equal_weighted_return_pct,
+                index_level = EXCLUDED.index_level,
+                trade_value = EXCLUDED.trade_value,
+                turnover_share_pct = EXCLUDED.turnover_share_pct,
+                revenue_period = EXCLUDED.revenue_period,
+                revenue_companies = EXCLUDED.revenue_companies,
+                revenue_yoy_pct = EXCLUDED.revenue_yoy_pct,
+                updated_time = now()
+        "#;
+
+        let len = metrics.len();
+        let mut dates = Vec::with_capacity(len);
+        let mut industry_ids = Vec::with_capacity(len);
+        let mut stocks = Vec::with_capacity(len);
+        let mut advances = Vec::with_capacity(len);
+        let mut declines = Vec::with_capacity(len);
+        let mut unchanged = Vec::with_capacity(len);
+        let mut cap_weighted = Vec::with_capacity(len);
+        let mut equal_weighted = Vec::with_capacity(len);
+        let mut index_levels = Vec::with_capacity(len);
+        let mut trade_values = Vec::with_capacity(len);
+        let mut turnover_shares = Vec::with_capacity(len);
+        let mut revenue_periods = Vec::with_capacity(len);
+        let mut revenue_companies = Vec::with_capacity(len);
+        let mut revenue_yoy = Vec::with_capacity(len);
+        for metric in metrics {
+            dates.push(metric.date);
+            industry_ids.push(metric.industry_id);
+            stocks.push(metric.stocks);
+            advances.push(metric.advances);
+            declines.push(metric.declines);
+            unchanged.push(metric.unchanged);
+            cap_weighted.push(metric.cap_weighted_return_pct);
+            equal_weighted.push(metric.equal_weighted_return_pct);
+            index_levels.push(metric.index_level);
+            trade_values.push(metric.trade_value);
+            turnover_shares.push(metric.turnover_share_pct);
+            revenue_periods.push(metric.revenue_period);
+            revenue_companies.push(metric.revenue_companies);
+            revenue_yoy.push(metric.revenue_yoy_pct);
+        }
+
+        let result = sqlx::query(sql)
+            .bind(dates)
+            .bind(industry_ids)
+            .bind(stocks)
+            .bind(advances)
+            .bind(declines)
+            .bind(unchanged)
+            .bind(cap_weighted)
+            .bind(equal_weighted)
+            .bind(index_levels)
+            .bind(trade_values)
+            .bind(turnover_shares)
+            .bind(revenue_periods)
+            .bind(revenue_companies)
+            .bind(revenue_yoy)
+            .execute(database::get_connection())
+            .await
+            .context("Failed to upsert industry daily metrics to PG")?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn fetch_snapshots(&self, to: Option<NaiveDate>) -> Result<Vec<IndustrySnapshot>> {
+        // 與其他市場查詢相同：指定日期時終點只允許落在 31 天回溯窗內。
+        let sql = r#"
+            WITH endpoint AS (
+                SELECT MAX(date) AS date
+                FROM industry_daily_metric
+                WHERE $1::date IS NULL OR (date <= $1 AND date >= $1 - 30)
+            )
+            SELECT
+                m.date, m.stock_industry_id, COALESCE(i.name, '')::varchar AS industry_name,
+                m.stocks, m.advances, m.declines, m.unchanged, m.cap_weighted_return_pct,
+                m.equal_weighted_return_pct, m.index_level, m.trade_value,
+                m.turnover_share_pct, m.revenue_period, m.revenue_companies, m.revenue_yoy_pct
+            FROM industry_daily_metric m
+            LEFT JOIN stock_industry i ON i.stock_industry_id = m.stock_industry_id
+            WHERE m.date = (SELECT date FROM endpoint)
+            ORDER BY m.stock_industry_id
+        "#;
+
+        let rows = sqlx::query_as::<_, MetricDbRow>(sql)
+            .bind(to)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch industry metrics from PG")?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn fetch_rotation(&self, to: Option<NaiveDate>) -> Result<Option<RotationSnapshot>> {
+        let longest = RotationWindow::ALL
+            .iter()
+            .map(|window| window.trading_days())
+            .max()
+            .unwrap_or_default();
+        let dates_sql = r#"
+            WITH endpoint AS (
+                SELECT MAX(date) AS date
+                FROM industry_daily_metric
+                WHERE $1::date IS NULL OR (date <= $1 AND date >= $1 - 30)
+            )
+            SELECT DISTINCT date
+            FROM industry_daily_metric
+            WHERE date <= (SELECT date FROM endpoint)
+            ORDER BY date DESC
+            LIMIT $2
+        "#;
+        let dates: Vec<NaiveDate> = sqlx::query_scalar(dates_sql)
+            .bind(to)
+            .bind(longest as i64 + 1)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch industry metric dates from PG")?;
+        let Some(date) = dates.first().copied() else {
+            return Ok(None);
+        };
+
+        let anchors: Vec<(RotationWindow, NaiveDate)> = RotationWindow::ALL
+            .into_iter()
+            .filter_map(|window| {
+                dates
+                    .get(window.trading_days())
+                    .map(|anchor| (window, *anchor))
+            })
+            .collect();
+        let mut wanted: Vec<NaiveDate> = anchors.iter().map(|(_, anchor)| *anchor).collect();
+        wanted.push(date);
+
+        let levels_sql = r#"
+            SELECT
+                m.date, m.stock_industry_id AS industry_id,
+                COALESCE(i.name, '')::varchar AS industry_name, m.stocks, m.index_level
+            FROM industry_daily_metric m
+            LEFT JOIN stock_industry i ON i.stock_industry_id = m.stock_industry_id
+            WHERE m.date = ANY($1)
+        "#;
+        let levels = sqlx::query_as::<_, LevelDbRow>(levels_sql)
+            .bind(&wanted)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch industry index levels from PG")?;
+
+        let benchmark_sql = r#"
+            SELECT "date", index
+            FROM index
+            WHERE category = 'TAIEX' AND "date" = ANY($1)
+        "#;
+        let benchmark: Vec<(NaiveDate, Decimal)> = sqlx::query_as(benchmark_sql)
+            .bind(&wanted)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch TAIEX levels from PG")?;
+
+        Ok(Some(RotationSnapshot {
+            date,
+            anchors,
+            levels: levels
+                .into_iter()
+                .map(|row| IndustryLevel {
+                    date: row.date,
+                    industry_id: row.industry_id,
+                    industry_name: row.industry_name,
+                    stocks: row.stocks,
+                    index_level: row.index_level,
+                })
+                .collect(),
+            benchmark: benchmark.into_iter().collect::<HashMap<_, _>>(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[tokio::test]
+    #[cfg_attr(
+        not(feature = "integration-tests"),
+        ignore = "需要外部服務（PostgreSQL/Redis），請加 --features integration-tests 執行"
+    )]
+    async fn test_upsert_and_fetch_snapshots() {
+        dotenvy::dotenv().ok();
+        if database::ping().await.is_err() {
+            println!("跳過 PgIndustryMetricRepository DB 整合測試：無資料庫連接");
+            return;
+        }
+
+        let cleanup = || async {
+            let _ = sqlx::query("DELETE FROM industry_daily_metric WHERE date >= '2099-01-01'")
+                .execute(database::get_connection())
+                .await;
+        };
+        cleanup().await;
+
+        let aggregate = |day, pct| IndustryDailyAggregate {
+            date: NaiveDate::from_ymd_opt(2099, 1, day).unwrap(),
+            industry_id: 24,
+            stocks: 3,
+            advances: 2,
+            declines: 1,
+            unchanged: 0,
+            cap_weighted_return_pct: Some(pct),
+            equal_weighted_return_pct: Some(pct),
+            trade_value: dec!(1000),
+            turnover_share_pct: Some(dec!(50)),
+        };
+        let growth = IndustryRevenueGrowth {
+            industry_id: 24,
+            companies: 3,
+            yoy_pct: Some(dec!(12.5)),
+        };
+        let first = IndustryDailyMetric::next(&aggregate(5, dec!(1)), 209811, Some(&growth), None);
+        let second = IndustryDailyMetric::next(&aggregate(6, dec!(10)), 209811, None, Some(&first));
+        let repo = PgIndustryMetricRepository::new();
+        repo.upsert(&[first.clone(), second.clone()])
+            .await
+            .expect("upsert");
+
+        let previous = repo
+            .fetch_latest_before(second.date)
+            .await
+            .expect("fetch latest before");
+        let snapshots = repo
+            .fetch_snapshots(Some(second.date))
+            .await
+            .expect("fetch snapshots");
+        cleanup().await;
+
+        assert!(previous.contains(&first));
+        let semis = snapshots
+            .iter()
+            .find(|snapshot| snapshot.metric.industry_id == 24)
+            .expect("半導體業指標");
+        assert_eq!(semis.metric, second);
+        assert_eq!(semis.metric.index_level, dec!(110));
+        assert!(!semis.industry_name.is_empty());
+    }
+}
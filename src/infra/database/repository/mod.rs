@@ -1,18 +1,36 @@
 use crate::infra::nosql::redis::RedisError;
 use thiserror::Error;
 
+pub mod breadth;
 pub mod cagr_source;
+pub mod chip;
 pub mod config;
 pub mod corporate_action;
+pub mod derivatives;
+pub mod disclosure;
 pub mod dividend;
+pub mod dividend_gap_fill;
+pub mod estimate_backtest;
+pub mod event_log;
 pub mod financial;
+pub mod financial_health;
+pub mod financial_line_item;
+pub mod fundamental_revision;
+pub mod industry;
 pub mod market_index;
 pub mod money_flow;
 pub mod performance;
 pub mod portfolio;
 pub mod quote;
+pub mod revenue_momentum;
+pub mod security_master;
 pub mod stock;
+pub mod stock_sync_queue;
+pub mod subscription;
+pub mod surveillance;
 pub mod trace;
+pub mod valuation_band;
+pub mod valuation_model;
 pub mod yield_rank;
 
 /// 倉儲層結構化錯誤類型。
@@ -0,0 +1,261 @@
+use crate::domain::financial::{
+    repository::RevenueMomentumRepository,
+    revenue_momentum::{RevenueMomentum, RevenuePoint},
+};
+use crate::infra::database;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use sqlx::FromRow;
+
+/// 基於 PostgreSQL 的月營收動能倉儲實現 (PgRevenueMomentumRepository)。
+///
+/// 從 `"Revenue"` 讀取單月營收，並讀寫 `revenue_momentum`。
+pub struct PgRevenueMomentumRepository;
+
+impl PgRevenueMomentumRepository {
+    /// 建立新的 PgRevenueMomentumRepository 實例。
+    pub fn new() -> Self {
+        PgRevenueMomentumRepository
+    }
+}
+
+impl Default for PgRevenueMomentumRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `revenue_momentum` 的資料列。
+#[derive(FromRow)]
+struct MomentumDbRow {
+    security_code: String,
+    month: i64,
+    revenue: Decimal,
+    rolling_3m: Option<Decimal>,
+    rolling_12m: Option<Decimal>,
+    yoy_pct: Option<Decimal>,
+    rolling_3m_yoy_pct: Option<Decimal>,
+    rolling_12m_yoy_pct: Option<Decimal>,
+    yoy_acceleration_pct: Option<Decimal>,
+    all_time_high: bool,
+    seasonal_expected: Option<Decimal>,
+    surprise_pct: Option<Decimal>,
+    seasonal_years: i16,
+}
+
+impl From<MomentumDbRow> for RevenueMomentum {
+    fn from(row: MomentumDbRow) -> Self {
+        RevenueMomentum {
+            security_code: row.security_code,
+            month: row.month,
+            revenue: row.revenue,
+            rolling_3m: row.rolling_3m,
+            rolling_12m: row.rolling_12m,
+            yoy_pct: row.yoy_pct,
+            rolling_3m_yoy_pct: row.rolling_3m_yoy_pct,
+            rolling_12m_yoy_pct: row.rolling_12m_yoy_pct,
+            yoy_acceleration_pct: row.yoy_acceleration_pct,
+            all_time_high: row.all_time_high,
+            seasonal_expected: row.seasonal_expected,
+            surprise_pct: row.surprise_pct,
+            seasonal_years: row.seasonal_years,
+        }
+    }
+}
+
+#[async_trait]
+impl RevenueMomentumRepository for PgRevenueMomentumRepository {
+    async fn fetch_revenues(&self, until: i64) -> Result<Vec<RevenuePoint>> {
+        let rows: Vec<(String, i64, Decimal)> = sqlx::query_as(
+            r#"
+            SELECT "SecurityCode", "Date", "Monthly"
+            FROM "Revenue"
+            WHERE "Date" <= $1
+            ORDER BY "SecurityCode", "Date"
+            "#,
+        )
+        .bind(until)
+        .fetch_all(database::get_connection())
+        .await
+        .context("Failed to fetch monthly revenues for momentum from PG")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(security_code, month, revenue)| RevenuePoint {
+                security_code,
+                month,
+                revenue,
+            })
+            .collect())
+    }
+
+    async fn upsert(&self, momentums: &[RevenueMomentum]) -> Result<u64> {
+        if momentums.is_empty() {
+            return Ok(0);
+        }
+
+        let sql = r#"
+            INSERT INTO revenue_momentum (
+                security_code, month, revenue, rolling_3m, rolling_12m, yoy_pct,
+                rolling_3m_yoy_pct, rolling_12m_yoy_pct, yoy_acceleration_pct,
+                all_time_high, seasonal_expected, surprise_pct, seasonal_years
+            )
+            SELECT * FROM UNNEST(
+                $1::varchar[], $2::bigint[], $3::numeric[], $4::numeric[], $5::numeric[],
+                $6::numeric[], $7::numeric[], $8::numeric[], $9::numeric[],
+                $10::bool[], $11::numeric[], $12::numeric[], $13::smallint[]
+            )
+            ON CONFLICT (security_code, month) DO UPDATE SET
+                revenue = EXCLUDED.revenue,
+                rolling_3m = EXCLUDED.rolling_3m,
+                rolling_12m = EXCLUDED.rolling_12m,
+                yoy_pct = EXCLUDED.yoy_pct,
+                rolling_3m_yoy_pct = EXCLUDED.rolling_3m_yoy_pct,
+                rolling_12m_yoy_pct = EXCLUDED.rolling_12m_yoy_pct,
+                yoy_acceleration_pct = EXCLUDED.yoy_acceleration_pct,
+                all_time_high = EXCLUDED.all_time_high,
+                seasonal_expected = EXCLUDED.seasonal_expected,
+                surprise_pct = EXCLUDED.surprise_pct,
+                seasonal_years = EXCLUDED.seasonal_years,
+                updated_time = now()
+        "#;
+
+        let len = momentums.len();
+        let mut security_codes = Vec::with_capacity(len);
+        let mut months = Vec::with_capacity(len);
+        let mut revenues = Vec::with_capacity(len);
+        let mut rolling_3m = Vec::with_capacity(len);
+        let mut rolling_12m = Vec::with_capacity(len);
+        let mut yoy = Vec::with_capacity(len);
+        let mut rolling_3m_yoy = Vec::with_capacity(len);
+        let mut rolling_12m_yoy = Vec::with_capacity(len);
+        let mut accelerations = Vec::with_capacity(len);
+        let mut all_time_highs = Vec::with_capacity(len);
+        let mut seasonal_expected = Vec::with_capacity(len);
+        let mut surprises = Vec::with_capacity(len);
+        let mut seasonal_years = Vec::with_capacity(len);
+        for momentum in momentums {
+            security_codes.push(momentum.security_code.clone());
+            months.push(momentum.month);
+            revenues.push(momentum.revenue);
+            rolling_3m.push(momentum.rolling_3m);
+            rolling_12m.push(momentum.rolling_12m);
+            yoy.push(momentum.yoy_pct);
+            rolling_3m_yoy.push(momentum.rolling_3m_yoy_pct);
+            rolling_12m_yoy.push(momentum.rolling_12m_yoy_pct);
+            accelerations.push(momentum.yoy_acceleration_pct);
+            all_time_highs.push(momentum.all_time_high);
+            seasonal_expected.push(momentum.seasonal_expected);
+            surprises.push(momentum.surprise_pct);
+            seasonal_years.push(momentum.seasonal_years);
+        }
+
+        let result = sqlx::query(sql)
+            .bind(security_codes)
+            .bind(months)
+            .bind(revenues)
+            .bind(rolling_3m)
+            .bind(rolling_12m)
+            .bind(yoy)
+            .bind(rolling_3m_yoy)
+            .bind(rolling_12m_yoy)
+            .bind(accelerations)
+            .bind(all_time_highs)
+            .bind(seasonal_expected)
+            .bind(surprises)
+            .bind(seasonal_years)
+            .execute(database::get_connection())
+            .await
+            .context("Failed to upsert revenue momentum to PG")?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn fetch_month(
+        &self,
+        month: i64,
+        security_codes: Option<Vec<String>>,
+    ) -> Result<Vec<RevenueMomentum>> {
+        let rows: Vec<MomentumDbRow> = sqlx::query_as(
+            r#"
+            SELECT security_code, month, revenue, rolling_3m, rolling_12m, yoy_pct,
+                   rolling_3m_yoy_pct, rolling_12m_yoy_pct, yoy_acceleration_pct,
+                   all_time_high, seasonal_expected, surprise_pct, seasonal_years
+            FROM revenue_momentum
+            WHERE month = $1
+              AND ($2::varchar[] IS NULL OR security_code = ANY($2))
+            ORDER BY security_code
+            "#,
+        )
+        .bind(month)
+        .bind(security_codes)
+        .fetch_all(database::get_connection())
+        .await
+        .context("Failed to fetch revenue momentum from PG")?;
+
+        Ok(rows.into_iter().map(RevenueMomentum::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[tokio::test]
+    #[cfg_attr(
+        not(feature = "integration-tests"),
+        ignore = "需要外部服務（PostgreSQL/Redis），請加 --features integration-tests 執行"
+    )]
+    async fn test_upsert_and_fetch_month() {
+        dotenvy::dotenv().ok();
+        if database::ping().await.is_err() {
+            println!("跳過 PgRevenueMomentumRepository DB 整合測試：無資料庫連接");
+            return;
+        }
+
+        let cleanup = || async {
+            let _ =
+                sqlx::query("DELETE FROM revenue_momentum WHERE month BETWEEN 209901 AND 209912")
+                    .execute(database::get_connection())
+                    .await;
+        };
+        cleanup().await;
+
+        let momentum = |security_code: &str, surprise_pct| RevenueMomentum {
+            security_code: security_code.to_string(),
+            month: 209903,
+            revenue: dec!(100),
+            rolling_3m: Some(dec!(280)),
+            rolling_12m: None,
+            yoy_pct: Some(dec!(12.5)),
+            rolling_3m_yoy_pct: Some(dec!(8)),
+            rolling_12m_yoy_pct: None,
+            yoy_acceleration_pct: None,
+            all_time_high: true,
+            seasonal_expected: Some(dec!(80)),
+            surprise_pct,
+            seasonal_years: 2,
+        };
+        let repo = PgRevenueMomentumRepository::new();
+        repo.upsert(&[momentum("2330", Some(dec!(25))), momentum("1101", None)])
+            .await
+            .expect("upsert");
+        // 重算同一月份時覆寫數值。
+        repo.upsert(&[momentum("2330", Some(dec!(30)))])
+            .await
+            .expect("upsert again");
+
+        let all = repo.fetch_month(209903, None).await.expect("fetch all");
+        let selected = repo
+            .fetch_month(209903, Some(vec!["2330".to_string()]))
+            .await
+            .expect("fetch selected");
+        cleanup().await;
+
+        assert_eq!(all.len(), 2);
+        assert_eq!(selected, vec![momentum("2330", Some(dec!(30)))]);
+    }
+}
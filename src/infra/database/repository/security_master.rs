@@ -0,0 +1,334 @@
+use std::{collections::HashMap, str::FromStr};
+
+use crate::domain::registry::{
+    history::{SecurityChangeKind, SecurityMasterEvent, SecuritySnapshot},
+    repository::SecurityMasterRepository,
+};
+use crate::infra::database;
+use anyhow::{Context, Result, anyhow};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::FromRow;
+
+/// 基於 PostgreSQL 的時點證券主檔倉儲實現 (PgSecurityMasterRepository)。
+///
+/// 讀寫 `security_master_event`，投資範圍以每檔證券在指定日期（含）之前最新的一筆異動判斷。
+pub struct PgSecurityMasterRepository;
+
+impl PgSecurityMasterRepository {
+    /// 建立新的 PgSecurityMasterRepository 實例。
+    pub fn new() -> Self {
+        PgSecurityMasterRepository
+    }
+}
+
+impl Default for PgSecurityMasterRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `security_master_event` 的資料列。
+#[derive(FromRow)]
+struct EventDbRow {
+    security_code: String,
+    kind: String,
+    effective_date: NaiveDate,
+    name: String,
+    stock_exchange_market_id: i32,
+    stock_industry_id: i32,
+    listed: bool,
+    previous_name: Option<String>,
+    previous_market_id: Option<i32>,
+    previous_industry_id: Option<i32>,
+    related_symbol: Option<String>,
+}
+
+impl TryFrom<EventDbRow> for SecurityMasterEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(row: EventDbRow) -> Result<Self> {
+        Ok(SecurityMasterEvent {
+            kind: SecurityChangeKind::from_str(&row.kind)
+                .map_err(|_| anyhow!("Unknown security master change '{}'", row.kind))?,
+            security_code: row.security_code,
+            effective_date: row.effective_date,
+            name: row.name,
+            market_id: row.stock_exchange_market_id,
+            industry_id: row.stock_industry_id,
+            listed: row.listed,
+            previous_name: row.previous_name,
+            previous_market_id: row.previous_market_id,
+            previous_industry_id: row.previous_industry_id,
+            related_symbol: row.related_symbol,
+        })
+    }
+}
+
+/// 投資範圍查詢的資料列。
+#[derive(FromRow)]
+struct SnapshotDbRow {
+    security_code: String,
+    name: String,
+    stock_exchange_market_id: i32,
+    stock_industry_id: i32,
+    listed_date: NaiveDate,
+}
+
+impl From<SnapshotDbRow> for SecuritySnapshot {
+    fn from(row: SnapshotDbRow) -> Self {
+        SecuritySnapshot {
+            security_code: row.security_code,
+            name: row.name,
+            market_id: row.stock_exchange_market_id,
+            industry_id: row.stock_industry_id,
+            listed_date: row.listed_date,
+        }
+    }
+}
+
+#[async_trait]
+impl SecurityMasterRepository for PgSecurityMasterRepository {
+    async fn record(&self, events: &[SecurityMasterEvent]) -> Result<u64> {
+        if events.is_empty() {
+            return Ok(0);
+        }
+
+        let sql = r#"
+            INSERT INTO security_master_event (
+                security_code, kind, effective_date, name, stock_exchange_market_id,
+                stock_industry_id, listed, previous_name, previous_market_id,
+                previous_industry_id, related_symbol
+            )
+            SELECT * FROM UNNEST(
+                $1::varchar[], $2::varchar[], $3::date[], $4::varchar[], $5::int[],
+                $6::int[], $7::bool[], $8::varchar[], $9::int[],
+                $10::int[], $11::varchar[]
+            )
+            ON CONFLICT (security_code, kind, effective_date) DO NOTHING
+        "#;
+
+        let len = events.len();
+        let mut security_codes = Vec::with_capacity(len);
+        let mut kinds = Vec::with_capacity(len);
+        let mut effective_dates = Vec::with_capacity(len);
+        let mut names = Vec::with_capacity(len);
+        let mut market_ids = Vec::with_capacity(len);
+        let mut industry_ids = Vec::with_capacity(len);
+        let mut listed = Vec::with_capacity(len);
+        let mut previous_names = Vec::with_capacity(len);
+        let mut previous_market_ids = Vec::with_capacity(len);
+        let mut previous_industry_ids = Vec::with_capacity(len);
+        let mut related_symbols = Vec::with_capacity(len);
+        for event in events {
+            security_codes.push(event.security_code.clone());
+            kinds.push(event.kind.to_string());
+            effective_dates.push(event.effective_date);
+            names.push(event.name.clone());
+            market_ids.push(event.market_id);
+            industry_ids.push(event.industry_id);
+            listed.push(event.listed);
+            previous_names.push(event.previous_name.clone());
+            previous_market_ids.push(event.previous_market_id);
+            previous_industry_ids.push(event.previous_industry_id);
+            related_symbols.push(event.related_symbol.clone());
+        }
+
+        let result = sqlx::query(sql)
+            .bind(security_codes)
+            .bind(kinds)
+            .bind(effective_dates)
+            .bind(names)
+            .bind(market_ids)
+            .bind(industry_ids)
+            .bind(listed)
+            .bind(previous_names)
+            .bind(previous_market_ids)
+            .bind(previous_industry_ids)
+            .bind(related_symbols)
+            .execute(database::get_connection())
+            .await
+            .context("Failed to record security master events to PG")?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn fetch_history(&self, security_code: &str) -> Result<Vec<SecurityMasterEvent>> {
+        let rows: Vec<EventDbRow> = sqlx::query_as(
+            r#"
+            SELECT security_code, kind, effective_date, name, stock_exchange_market_id,
+                   stock_industry_id, listed, previous_name, previous_market_id,
+                   previous_industry_id, related_symbol
+            FROM security_master_event
+            WHERE security_code = $1
+            ORDER BY effective_date, serial
+            "#,
+        )
+        .bind(security_code)
+        .fetch_all(database::get_connection())
+        .await
+        .context("Failed to fetch security master history from PG")?;
+
+        rows.into_iter()
+            .map(SecurityMasterEvent::try_from)
+            .collect()
+    }
+
+    async fn fetch_universe(
+        &self,
+        date: NaiveDate,
+        market_ids: Option<Vec<i32>>,
+    ) -> Result<Vec<SecuritySnapshot>> {
+        let rows: Vec<SnapshotDbRow> = sqlx::query_as(
+            r#"
+            SELECT latest.security_code, latest.name, latest.stock_exchange_market_id,
+                   latest.stock_industry_id,
+                   coalesce(
+                       (SELECT max(l.effective_date)
+                        FROM security_master_event l
+                        WHERE l.security_code = latest.security_code
+                          AND l.listed
+                          AND l.kind IN ('listed', 'symbol_changed')
+                          AND l.effective_date <= $1),
+                       latest.effective_date
+                   ) AS listed_date
+            FROM (
+                SELECT DISTINCT ON (security_code)
+                       security_code, name, stock_exchange_market_id, stock_industry_id,
+                       listed, effective_date
+                FROM security_master_event
+                WHERE effective_date <= $1
+                ORDER BY security_code, effective_date DESC, serial DESC
+            ) latest
+            WHERE latest.listed
+              AND ($2::int[] IS NULL OR latest.stock_exchange_market_id = ANY($2))
+            ORDER BY latest.security_code
+            "#,
+        )
+        .bind(date)
+        .bind(market_ids)
+        .fetch_all(database::get_connection())
+        .await
+        .context("Failed to fetch point-in-time universe from PG")?;
+
+        Ok(rows.into_iter().map(SecuritySnapshot::from).collect())
+    }
+
+    async fn fetch_last_trading_dates(&self) -> Result<HashMap<String, NaiveDate>> {
+        let rows: Vec<(String, NaiveDate)> = sqlx::query_as(
+            r#"
+            SELECT stock_symbol, max("Date")
+            FROM "DailyQuotes"
+            GROUP BY stock_symbol
+            "#,
+        )
+        .fetch_all(database::get_connection())
+        .await
+        .context("Failed to fetch last trading dates from PG")?;
+
+        Ok(rows.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[cfg_attr(
+        not(feature = "integration-tests"),
+        ignore = "需要外部服務（PostgreSQL/Redis），請加 --features integration-tests 執行"
+    )]
+    async fn test_record_and_fetch_universe() {
+        dotenvy::dotenv().ok();
+        if database::ping().await.is_err() {
+            println!("跳過 PgSecurityMasterRepository DB 整合測試：無資料庫連接");
+            return;
+        }
+
+        let cleanup = || async {
+            let _ =
+                sqlx::query("DELETE FROM security_master_event WHERE security_code LIKE 'T99%'")
+                    .execute(database::get_connection())
+                    .await;
+        };
+        cleanup().await;
+
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        let event = |symbol: &str, kind, on, market_id, listed| SecurityMasterEvent {
+            security_code: symbol.to_string(),
+            kind,
+            effective_date: on,
+            name: format!("測試{symbol}"),
+            market_id,
+            industry_id: 24,
+            listed,
+            previous_name: None,
+            previous_market_id: None,
+            previous_industry_id: None,
+            related_symbol: None,
+        };
+        let events = vec![
+            event(
+                "T991",
+                SecurityChangeKind::Listed,
+                date(2098, 1, 5),
+                4,
+                true,
+            ),
+            SecurityMasterEvent {
+                previous_market_id: Some(4),
+                ..event(
+                    "T991",
+                    SecurityChangeKind::MarketTransfer,
+                    date(2099, 3, 1),
+                    2,
+                    true,
+                )
+            },
+            event(
+                "T992",
+                SecurityChangeKind::Listed,
+                date(2098, 1, 5),
+                4,
+                true,
+            ),
+            event(
+                "T992",
+                SecurityChangeKind::Delisted,
+                date(2099, 2, 1),
+                4,
+                false,
+            ),
+        ];
+        let repo = PgSecurityMasterRepository::new();
+        let written = repo.record(&events).await;
+        // 重複寫入不會產生新紀錄。
+        let rewritten = repo.record(&events).await;
+        let history = repo.fetch_history("T991").await;
+        let before = repo.fetch_universe(date(2099, 1, 31), Some(vec![4])).await;
+        let after = repo.fetch_universe(date(2099, 6, 30), None).await;
+        cleanup().await;
+
+        assert_eq!(written.expect("record"), 4);
+        assert_eq!(rewritten.expect("record again"), 0);
+        assert_eq!(history.expect("history")[1].previous_market_id, Some(4));
+
+        let before: Vec<_> = before
+            .expect("universe before")
+            .into_iter()
+            .filter(|snapshot| snapshot.security_code.starts_with("T99"))
+            .map(|snapshot| snapshot.security_code)
+            .collect();
+        assert_eq!(before, vec!["T991", "T992"]);
+
+        let after: Vec<_> = after
+            .expect("universe after")
+            .into_iter()
+            .filter(|snapshot| snapshot.security_code.starts_with("T99"))
+            .collect();
+        assert_eq!(after.len(), 1);
+        assert_eq!(after[0].market_id, 2);
+        assert_eq!(after[0].listed_date, date(2098, 1, 5));
+    }
+}
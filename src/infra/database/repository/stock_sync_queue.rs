@@ -0,0 +1,295 @@
+use std::{collections::HashSet, str::FromStr};
+
+use crate::domain::registry::{
+    repository::StockSyncQueueRepository,
+    sync::{StockSyncRecord, StockSyncTask, SyncReason, retry_delay},
+};
+use crate::infra::database;
+use anyhow::{Context, Result, anyhow};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use sqlx::FromRow;
+
+/// 基於 PostgreSQL 的證券主檔同步佇列倉儲實現 (PgStockSyncQueueRepository)。
+///
+/// 讀寫 `stock_sync_queue`，送出內容取自 `stocks` 的最新資料。
+pub struct PgStockSyncQueueRepository;
+
+impl PgStockSyncQueueRepository {
+    /// 建立新的 PgStockSyncQueueRepository 實例。
+    pub fn new() -> Self {
+        PgStockSyncQueueRepository
+    }
+}
+
+impl Default for PgStockSyncQueueRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 佇列與證券主檔合併後的資料列；主檔不存在時主檔欄位皆為 `NULL`。
+#[derive(FromRow)]
+struct TaskDbRow {
+    serial: i64,
+    stock_symbol: String,
+    reason: String,
+    attempts: i32,
+    version: i32,
+    name: Option<String>,
+    stock_exchange_market_id: Option<i32>,
+    stock_industry_id: Option<i32>,
+    net_asset_value_per_share: Option<Decimal>,
+    suspend_listing: Option<bool>,
+}
+
+impl TryFrom<TaskDbRow> for StockSyncTask {
+    type Error = anyhow::Error;
+
+    fn try_from(row: TaskDbRow) -> Result<Self> {
+        let record = match (
+            row.name,
+            row.stock_exchange_market_id,
+            row.stock_industry_id,
+            row.net_asset_value_per_share,
+            row.suspend_listing,
+        ) {
+            (Some(name), Some(market_id), Some(industry_id), Some(nav), Some(suspend_listing)) => {
+                Some(StockSyncRecord {
+                    stock_symbol: row.stock_symbol.clone(),
+                    name,
+                    market_id,
+                    industry_id,
+                    net_asset_value_per_share: nav,
+                    suspend_listing,
+                })
+            }
+            _ => None,
+        };
+
+        Ok(StockSyncTask {
+            reason: SyncReason::from_str(&row.reason)
+                .map_err(|_| anyhow!("Unknown stock sync reason '{}'", row.reason))?,
+            serial: row.serial,
+            stock_symbol: row.stock_symbol,
+            attempts: row.attempts,
+            version: row.version,
+            record,
+        })
+    }
+}
+
+/// `stocks` 的同步內容。
+#[derive(FromRow)]
+struct RecordDbRow {
+    stock_symbol: String,
+    name: String,
+    stock_exchange_market_id: i32,
+    stock_industry_id: i32,
+    net_asset_value_per_share: Decimal,
+    suspend_listing: bool,
+}
+
+impl From<RecordDbRow> for StockSyncRecord {
+    fn from(row: RecordDbRow) -> Self {
+        StockSyncRecord {
+            stock_symbol: row.stock_symbol,
+            name: row.name,
+            market_id: row.stock_exchange_market_id,
+            industry_id: row.stock_industry_id,
+            net_asset_value_per_share: row.net_asset_value_per_share,
+            suspend_listing: row.suspend_listing,
+        }
+    }
+}
+
+#[async_trait]
+impl StockSyncQueueRepository for PgStockSyncQueueRepository {
+    async fn enqueue(&self, stock_symbols: &[String], reason: SyncReason) -> Result<u64> {
+        // 同一批出現重複代號時 ON CONFLICT DO UPDATE 會失敗，先去重。
+        let mut seen = HashSet::new();
+        let symbols: Vec<&str> = stock_symbols
+            .iter()
+            .map(String::as_str)
+            .filter(|symbol| seen.insert(*symbol))
+            .collect();
+        if symbols.is_empty() {
+            return Ok(0);
+        }
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO stock_sync_queue (stock_symbol, reason)
+            SELECT symbol, $2 FROM UNNEST($1::varchar[]) AS symbol
+            ON CONFLICT (stock_symbol) DO UPDATE SET
+                reason = EXCLUDED.reason,
+                version = stock_sync_queue.version + 1,
+                next_attempt_time = now(),
+                updated_time = now()
+            "#,
+        )
+        .bind(symbols)
+        .bind(reason.to_string())
+        .execute(database::get_connection())
+        .await
+        .context("Failed to enqueue stock sync to PG")?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn fetch_due(&self, limit: i64) -> Result<Vec<StockSyncTask>> {
+        let rows: Vec<TaskDbRow> = sqlx::query_as(
+            r#"
+            SELECT q.serial, q.stock_symbol, q.reason, q.attempts, q.version,
+                   s."Name" AS name, s.stock_exchange_market_id, s.stock_industry_id,
+                   s.net_asset_value_per_share, s."SuspendListing" AS suspend_listing
+            FROM stock_sync_queue q
+            LEFT JOIN stocks s ON s.stock_symbol = q.stock_symbol
+            WHERE q.next_attempt_time <= now()
+            ORDER BY q.next_attempt_time, q.serial
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(database::get_connection())
+        .await
+        .context("Failed to fetch due stock sync tasks from PG")?;
+
+        rows.into_iter().map(StockSyncTask::try_from).collect()
+    }
+
+    async fn complete(&self, tasks: &[StockSyncTask]) -> Result<u64> {
+        if tasks.is_empty() {
+            return Ok(0);
+        }
+
+        let serials: Vec<i64> = tasks.iter().map(|task| task.serial).collect();
+        let versions: Vec<i32> = tasks.iter().map(|task| task.version).collect();
+        let result = sqlx::query(
+            r#"
+            DELETE FROM stock_sync_queue q
+            USING UNNEST($1::bigint[], $2::int[]) AS done(serial, version)
+            WHERE q.serial = done.serial
+              AND q.version = done.version
+            "#,
+        )
+        .bind(serials)
+        .bind(versions)
+        .execute(database::get_connection())
+        .await
+        .context("Failed to complete stock sync tasks in PG")?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn fail(&self, tasks: &[StockSyncTask], error: &str) -> Result<()> {
+        if tasks.is_empty() {
+            return Ok(());
+        }
+
+        let serials: Vec<i64> = tasks.iter().map(|task| task.serial).collect();
+        let versions: Vec<i32> = tasks.iter().map(|task| task.version).collect();
+        let delays: Vec<f64> = tasks
+            .iter()
+            .map(|task| retry_delay(task.attempts.saturating_add(1)).as_secs_f64())
+            .collect();
+        sqlx::query(
+            r#"
+            UPDATE stock_sync_queue q SET
+                attempts = q.attempts + 1,
+                last_error = $4,
+                next_attempt_time = now() + make_interval(secs => failed.delay),
+                updated_time = now()
+            FROM UNNEST($1::bigint[], $2::int[], $3::float8[]) AS failed(serial, version, delay)
+            WHERE q.serial = failed.serial
+              AND q.version = failed.version
+            "#,
+        )
+        .bind(serials)
+        .bind(versions)
+        .bind(delays)
+        .bind(error)
+        .execute(database::get_connection())
+        .await
+        .context("Failed to record stock sync failure to PG")?;
+
+        Ok(())
+    }
+
+    async fn fetch_sync_records(&self) -> Result<Vec<StockSyncRecord>> {
+        let rows: Vec<RecordDbRow> = sqlx::query_as(
+            r#"
+            SELECT stock_symbol, "Name" AS name, stock_exchange_market_id, stock_industry_id,
+                   net_asset_value_per_share, "SuspendListing" AS suspend_listing
+            FROM stocks
+            ORDER BY stock_symbol
+            "#,
+        )
+        .fetch_all(database::get_connection())
+        .await
+        .context("Failed to fetch stock sync records from PG")?;
+
+        Ok(rows.into_iter().map(StockSyncRecord::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 失敗後延後重試，送出期間重新排入的證券不會被當成已完成。
+    #[tokio::test]
+    #[cfg_attr(
+        not(feature = "integration-tests"),
+        ignore = "需要外部服務（PostgreSQL/Redis），請加 --features integration-tests 執行"
+    )]
+    async fn test_enqueue_fail_and_complete() {
+        dotenvy::dotenv().ok();
+        if database::ping().await.is_err() {
+            println!("跳過 PgStockSyncQueueRepository DB 整合測試：無資料庫連接");
+            return;
+        }
+
+        let cleanup = || async {
+            let _ = sqlx::query("DELETE FROM stock_sync_queue WHERE stock_symbol LIKE 'T99%'")
+                .execute(database::get_connection())
+                .await;
+        };
+        cleanup().await;
+
+        let repo = PgStockSyncQueueRepository::new();
+        let symbols = vec!["T991".to_string(), "T992".to_string(), "T991".to_string()];
+        let enqueued = repo.enqueue(&symbols, SyncReason::Registered).await;
+        let due = repo.fetch_due(10_000).await.expect("fetch due");
+        let ours: Vec<StockSyncTask> = due
+            .into_iter()
+            .filter(|task| task.stock_symbol.starts_with("T99"))
+            .collect();
+
+        // T991 失敗延後；T992 在送出期間又被排入，完成時應保留。
+        let (t991, t992): (Vec<_>, Vec<_>) = ours
+            .iter()
+            .cloned()
+            .partition(|task| task.stock_symbol == "T991");
+        repo.fail(&t991, "unavailable").await.expect("fail");
+        repo.enqueue(&["T992".to_string()], SyncReason::Reconcile)
+            .await
+            .expect("re-enqueue");
+        let completed = repo.complete(&t992).await;
+        let due_after = repo.fetch_due(10_000).await.expect("fetch due again");
+        cleanup().await;
+
+        assert_eq!(enqueued.expect("enqueue"), 2);
+        assert_eq!(ours.len(), 2);
+        assert!(ours.iter().all(|task| task.record.is_none()));
+        assert_eq!(completed.expect("complete"), 0);
+        let due_after: Vec<_> = due_after
+            .into_iter()
+            .filter(|task| task.stock_symbol.starts_with("T99"))
+            .collect();
+        assert_eq!(due_after.len(), 1);
+        assert_eq!(due_after[0].stock_symbol, "T992");
+        assert_eq!(due_after[0].reason, SyncReason::Reconcile);
+        assert_eq!(due_after[0].version, 2);
+    }
+}
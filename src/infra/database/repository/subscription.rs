@@ -0,0 +1,372 @@
+use crate::domain::subscription::{
+    entity::{PublicSubscription, SubscriptionEntry},
+    repository::SubscriptionRepository,
+};
+use crate::infra::database;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::FromRow;
+
+/// 基於 PostgreSQL 的公開申購倉儲實現 (PgSubscriptionRepository)。
+///
+/// 負責 `public_subscription` 與 `public_subscription_entry` 資料表的讀寫。
+pub struct PgSubscriptionRepository;
+
+impl PgSubscriptionRepository {
+    /// 建立新的 PgSubscriptionRepository 實例。
+    pub fn new() -> Self {
+        PgSubscriptionRepository
+    }
+}
+
+impl Default for PgSubscriptionRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 公開申購資料列。
+#[derive(FromRow)]
+struct SubscriptionDbRow {
+    stock_symbol: String,
+    stock_name: String,
+    market: String,
+    offering_start_date: NaiveDate,
+    offering_end_date: NaiveDate,
+    drawing_date: Option<NaiveDate>,
+    offering_price: Option<Decimal>,
+    issue_date: Option<NaiveDate>,
+}
+
+impl From<SubscriptionDbRow> for PublicSubscription {
+    fn from(row: SubscriptionDbRow) -> Self {
+        Self {
+            stock_symbol: row.stock_symbol,
+            stock_name: row.stock_name,
+            market: row.market,
+            offering_start_date: row.offering_start_date,
+            offering_end_date: row.offering_end_date,
+            drawing_date: row.drawing_date,
+            offering_price: row.offering_price,
+            issue_date: row.issue_date,
+        }
+    }
+}
+
+/// 參與紀錄資料列。
+#[derive(FromRow)]
+struct EntryDbRow {
+    serial: i64,
+    stock_symbol: String,
+    offering_start_date: NaiveDate,
+    member_id: i64,
+    won: Option<bool>,
+    sell_price: Option<Decimal>,
+    realized_profit: Option<Decimal>,
+}
+
+impl From<EntryDbRow> for SubscriptionEntry {
+    fn from(row: EntryDbRow) -> Self {
+        Self {
+            serial: row.serial,
+            stock_symbol: row.stock_symbol,
+            offering_start_date: row.offering_start_date,
+            member_id: row.member_id,
+            won: row.won,
+            sell_price: row.sell_price,
+            realized_profit: row.realized_profit,
+        }
+    }
+}
+
+#[async_trait]
+impl SubscriptionRepository for PgSubscriptionRepository {
+    async fn upsert(&self, subscriptions: &[PublicSubscription]) -> Result<()> {
+        if subscriptions.is_empty() {
+            return Ok(());
+        }
+
+        // 承銷價與抽籤日可能晚於申購公告才公布，衝突時以新值覆蓋，未公布
+        // （null）則保留先前取得的值。
+        let sql = r#"
+            INSERT INTO public_subscription (
+                stock_symbol, offering_start_date, stock_name, market, offering_end_date,
+                drawing_date, offering_price, issue_date
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (stock_symbol, offering_start_date) DO UPDATE SET
+                stock_name = excluded.stock_name,
+                market = excluded.market,
+                offering_end_date = excluded.offering_end_date,
+                drawing_date = COALESCE(excluded.drawing_date, public_subscription.drawing_date),
+                offering_price = COALESCE(excluded.offering_price, public_subscription.offering_price),
+                issue_date = COALESCE(excluded.issue_date, public_subscription.issue_date),
+                updated_time = now()
+        "#;
+
+        let mut tx = database::get_tx()
+            .await
+            .context("Failed to begin transaction for public subscription")?;
+        for subscription in subscriptions {
+            sqlx::query(sql)
+                .bind(&subscription.stock_symbol)
+                .bind(subscription.offering_start_date)
+                .bind(&subscription.stock_name)
+                .bind(&subscription.market)
+                .bind(subscription.offering_end_date)
+                .bind(subscription.drawing_date)
+                .bind(subscription.offering_price)
+                .bind(subscription.issue_date)
+                .execute(&mut *tx)
+                .await
+                .context("Failed to upsert public subscription to PG")?;
+        }
+        tx.commit()
+            .await
+            .context("Failed to commit public subscription")?;
+
+        Ok(())
+    }
+
+    async fn fetch_since(&self, date: NaiveDate) -> Result<Vec<PublicSubscription>> {
+        let sql = r#"
+            SELECT
+                stock_symbol, stock_name, market, offering_start_date, offering_end_date,
+                drawing_date, offering_price, issue_date
+            FROM public_subscription
+            WHERE offering_end_date >= $1 OR drawing_date >= $1
+            ORDER BY offering_start_date DESC, stock_symbol
+        "#;
+        let rows = sqlx::query_as::<_, SubscriptionDbRow>(sql)
+            .bind(date)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch public subscriptions from PG")?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn fetch_one(
+        &self,
+        stock_symbol: &str,
+        offering_start_date: NaiveDate,
+    ) -> Result<Option<PublicSubscription>> {
+        let sql = r#"
+            SELECT
+                stock_symbol, stock_name, market, offering_start_date, offering_end_date,
+                drawing_date, offering_price, issue_date
+            FROM public_subscription
+            WHERE stock_symbol = $1 AND offering_start_date = $2
+        "#;
+        let row = sqlx::query_as::<_, SubscriptionDbRow>(sql)
+            .bind(stock_symbol)
+            .bind(offering_start_date)
+            .fetch_optional(database::get_connection())
+            .await
+            .context("Failed to fetch public subscription from PG")?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn enter(
+        &self,
+        stock_symbol: &str,
+        offering_start_date: NaiveDate,
+        member_id: i64,
+    ) -> Result<Option<SubscriptionEntry>> {
+        // 公開申購不存在時 SELECT 沒有資料列，INSERT 不執行也不回傳；重複登記
+        // 以 no-op 的 DO UPDATE 取回原紀錄。
+        let sql = r#"
+            INSERT INTO public_subscription_entry (stock_symbol, offering_start_date, member_id)
+            SELECT stock_symbol, offering_start_date, $3
+            FROM public_subscription
+            WHERE stock_symbol = $1 AND offering_start_date = $2
+            ON CONFLICT (stock_symbol, offering_start_date, member_id) DO UPDATE SET
+                member_id = excluded.member_id
+            RETURNING serial, stock_symbol, offering_start_date, member_id, won, sell_price, realized_profit
+        "#;
+        let row = sqlx::query_as::<_, EntryDbRow>(sql)
+            .bind(stock_symbol)
+            .bind(offering_start_date)
+            .bind(member_id)
+            .fetch_optional(database::get_connection())
+            .await
+            .context("Failed to insert public subscription entry to PG")?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn record_result(
+        &self,
+        serial: i64,
+        won: bool,
+        sell_price: Option<Decimal>,
+    ) -> Result<Option<SubscriptionEntry>> {
+        let mut tx = database::get_tx()
+            .await
+            .context("Failed to begin transaction for public subscription result")?;
+
+        let sql = r#"
+            SELECT
+                s.stock_symbol, s.stock_name, s.market, s.offering_start_date, s.offering_end_date,
+                s.drawing_date, s.offering_price, s.issue_date
+            FROM public_subscription_entry e
+            JOIN public_subscription s
+              ON s.stock_symbol = e.stock_symbol AND s.offering_start_date = e.offering_start_date
+            WHERE e.serial = $1
+            FOR UPDATE OF e
+        "#;
+        let Some(subscription) = sqlx::query_as::<_, SubscriptionDbRow>(sql)
+            .bind(serial)
+            .fetch_optional(&mut *tx)
+            .await
+            .context("Failed to fetch public subscription entry from PG")?
+            .map(PublicSubscription::from)
+        else {
+            return Ok(None);
+        };
+
+        let sell_price = sell_price.filter(|_| won);
+        let realized_profit = subscription.realized_profit(won, sell_price);
+        let sql = r#"
+            UPDATE public_subscription_entry
+            SET won = $2, sell_price = $3, realized_profit = $4, updated_time = now()
+            WHERE serial = $1
+            RETURNING serial, stock_symbol, offering_start_date, member_id, won, sell_price, realized_profit
+        "#;
+        let row = sqlx::query_as::<_, EntryDbRow>(sql)
+            .bind(serial)
+            .bind(won)
+            .bind(sell_price)
+            .bind(realized_profit)
+            .fetch_one(&mut *tx)
+            .await
+            .context("Failed to update public subscription entry in PG")?;
+
+        tx.commit()
+            .await
+            .context("Failed to commit public subscription result")?;
+
+        Ok(Some(row.into()))
+    }
+
+    async fn fetch_entries_since(&self, date: NaiveDate) -> Result<Vec<SubscriptionEntry>> {
+        let sql = r#"
+            SELECT
+                e.serial, e.stock_symbol, e.offering_start_date, e.member_id, e.won,
+                e.sell_price, e.realized_profit
+            FROM public_subscription_entry e
+            JOIN public_subscription s
+              ON s.stock_symbol = e.stock_symbol AND s.offering_start_date = e.offering_start_date
+            WHERE s.offering_end_date >= $1
+            ORDER BY e.offering_start_date DESC, e.stock_symbol, e.member_id
+        "#;
+        let rows = sqlx::query_as::<_, EntryDbRow>(sql)
+            .bind(date)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch public subscription entries from PG")?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2099, 12, d).unwrap()
+    }
+
+    #[tokio::test]
+    #[cfg_attr(
+        not(feature = "integration-tests"),
+        ignore = "需要外部服務（PostgreSQL/Redis），請加 --features integration-tests 執行"
+    )]
+    async fn test_enter_and_record_result() {
+        dotenvy::dotenv().ok();
+        if database::ping().await.is_err() {
+            println!("跳過 PgSubscriptionRepository DB 整合測試：無資料庫連接");
+            return;
+        }
+
+        let symbol = "__PS__";
+        let cleanup = || async {
+            for sql in [
+                "DELETE FROM public_subscription_entry WHERE stock_symbol = $1",
+                "DELETE FROM public_subscription WHERE stock_symbol = $1",
+            ] {
+                let _ = sqlx::query(sql)
+                    .bind(symbol)
+                    .execute(database::get_connection())
+                    .await;
+            }
+        };
+        cleanup().await;
+
+        let repo = PgSubscriptionRepository::new();
+        assert!(
+            repo.enter(symbol, date(1), 1)
+                .await
+                .expect("enter missing")
+                .is_none(),
+            "公開申購不存在時不應建立參與紀錄"
+        );
+
+        let subscription = PublicSubscription {
+            stock_symbol: symbol.to_string(),
+            stock_name: "測試".to_string(),
+            market: "初上市".to_string(),
+            offering_start_date: date(1),
+            offering_end_date: date(3),
+            drawing_date: None,
+            offering_price: Some(dec!(50)),
+            issue_date: None,
+        };
+        repo.upsert(std::slice::from_ref(&subscription))
+            .await
+            .expect("upsert");
+        // 後續公告補上抽籤日，但承銷價空白時保留原值。
+        repo.upsert(&[PublicSubscription {
+            drawing_date: Some(date(7)),
+            offering_price: None,
+            ..subscription.clone()
+        }])
+        .await
+        .expect("upsert again");
+        let stored = repo
+            .fetch_one(symbol, date(1))
+            .await
+            .expect("fetch_one")
+            .expect("stored");
+        assert_eq!(stored.drawing_date, Some(date(7)));
+        assert_eq!(stored.offering_price, Some(dec!(50)));
+
+        let entry = repo
+            .enter(symbol, date(1), 1)
+            .await
+            .expect("enter")
+            .expect("entry");
+        let again = repo
+            .enter(symbol, date(1), 1)
+            .await
+            .expect("enter again")
+            .expect("entry again");
+        assert_eq!(entry.serial, again.serial, "重複登記應回傳原紀錄");
+
+        let recorded = repo
+            .record_result(entry.serial, true, Some(dec!(60)))
+            .await
+            .expect("record_result")
+            .expect("recorded");
+        assert_eq!(recorded.won, Some(true));
+        assert_eq!(recorded.realized_profit, Some(dec!(9750)));
+
+        cleanup().await;
+    }
+}
@@ -0,0 +1,280 @@
+use crate::domain::surveillance::{
+    entity::{SurveillanceKind, SurveillancePeriod},
+    repository::SurveillanceRepository,
+};
+use crate::infra::database;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::FromRow;
+
+/// 基於 PostgreSQL 的注意股與處置股倉儲實現 (PgSurveillanceRepository)。
+///
+/// 負責 `trading_surveillance` 資料表的期間寫入與查詢。
+pub struct PgSurveillanceRepository;
+
+impl PgSurveillanceRepository {
+    /// 建立新的 PgSurveillanceRepository 實例。
+    pub fn new() -> Self {
+        PgSurveillanceRepository
+    }
+}
+
+impl Default for PgSurveillanceRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 資料庫對應的內部資料列結構體，用於 `sqlx::query_as` 映射。
+#[derive(FromRow)]
+struct SurveillanceDbRow {
+    stock_symbol: String,
+    name: String,
+    kind: String,
+    announced_on: NaiveDate,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    reason: String,
+    measure: String,
+}
+
+impl SurveillanceDbRow {
+    /// 轉成領域模型；種類代碼無法辨識時回傳 `None`。
+    fn into_period(self) -> Option<SurveillancePeriod> {
+        Some(SurveillancePeriod {
+            kind: SurveillanceKind::from_code(&self.kind)?,
+            stock_symbol: self.stock_symbol,
+            name: self.name,
+            announced_on: self.announced_on,
+            start_date: self.start_date,
+            end_date: self.end_date,
+            reason: self.reason,
+            measure: self.measure,
+        })
+    }
+}
+
+#[async_trait]
+impl SurveillanceRepository for PgSurveillanceRepository {
+    /// 逐筆寫入；清單每日只有數十筆，逐筆處理換取延長注意股期間的判斷簡單明確。
+    async fn upsert(
+        &self,
+        periods: &[SurveillancePeriod],
+        previous_trading_day: Option<NaiveDate>,
+    ) -> Result<Vec<SurveillancePeriod>> {
+        if periods.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // 注意股連續交易日重複公告：延長前一交易日仍在期間內的那一段。
+        let extend_sql = r#"
+            UPDATE trading_surveillance
+            SET end_date = GREATEST(end_date, $3),
+                announced_on = GREATEST(announced_on, $4),
+                reason = $5,
+                updated_time = now()
+            WHERE stock_symbol = $1
+              AND kind = $2
+              AND start_date <= $6
+              AND end_date >= $6
+        "#;
+        // `xmax = 0` 代表這次是新增而非衝突更新，藉此分辨「新列入」。
+        let upsert_sql = r#"
+            INSERT INTO trading_surveillance (
+                stock_symbol, name, kind, announced_on, start_date, end_date, reason, measure
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (stock_symbol, kind, start_date) DO UPDATE SET
+                name = excluded.name,
+                announced_on = GREATEST(trading_surveillance.announced_on, excluded.announced_on),
+                end_date = GREATEST(trading_surveillance.end_date, excluded.end_date),
+                reason = excluded.reason,
+                measure = excluded.measure,
+                updated_time = now()
+            RETURNING (xmax = 0) AS inserted
+        "#;
+
+        let mut tx = database::get_tx()
+            .await
+            .context("Failed to begin transaction for trading surveillance")?;
+        let mut entered = Vec::new();
+
+        for period in periods {
+            if let (SurveillanceKind::Attention, Some(previous)) =
+                (period.kind, previous_trading_day)
+            {
+                let extended = sqlx::query(extend_sql)
+                    .bind(&period.stock_symbol)
+                    .bind(period.kind.code())
+                    .bind(period.end_date)
+                    .bind(period.announced_on)
+                    .bind(&period.reason)
+                    .bind(previous)
+                    .execute(&mut *tx)
+                    .await
+                    .context("Failed to extend attention period in PG")?;
+                if extended.rows_affected() > 0 {
+                    continue;
+                }
+            }
+
+            let inserted: bool = sqlx::query_scalar(upsert_sql)
+                .bind(&period.stock_symbol)
+                .bind(&period.name)
+                .bind(period.kind.code())
+                .bind(period.announced_on)
+                .bind(period.start_date)
+                .bind(period.end_date)
+                .bind(&period.reason)
+                .bind(&period.measure)
+                .fetch_one(&mut *tx)
+                .await
+                .context("Failed to upsert trading surveillance to PG")?;
+            if inserted {
+                entered.push(period.clone());
+            }
+        }
+
+        tx.commit()
+            .await
+            .context("Failed to commit trading surveillance")?;
+
+        Ok(entered)
+    }
+
+    async fn fetch_active_on(
+        &self,
+        date: NaiveDate,
+        stock_symbol: Option<&str>,
+    ) -> Result<Vec<SurveillancePeriod>> {
+        let sql = r#"
+            SELECT
+                stock_symbol, name, kind, announced_on, start_date, end_date, reason, measure
+            FROM trading_surveillance
+            WHERE start_date <= $1
+              AND end_date >= $1
+              AND ($2::varchar IS NULL OR stock_symbol = $2)
+            ORDER BY stock_symbol, kind, start_date
+        "#;
+
+        let rows = sqlx::query_as::<_, SurveillanceDbRow>(sql)
+            .bind(date)
+            .bind(stock_symbol)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch active trading surveillance from PG")?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(SurveillanceDbRow::into_period)
+            .collect())
+    }
+
+    async fn fetch_recent_trading_days(
+        &self,
+        date: NaiveDate,
+        limit: i64,
+    ) -> Result<Vec<NaiveDate>> {
+        // 限定往前 30 天，讓 DISTINCT 只需掃描日期索引的一小段。
+        let sql = r#"
+            SELECT DISTINCT "Date"
+            FROM "DailyQuotes"
+            WHERE "Date" BETWEEN $1::date - 30 AND $1
+            ORDER BY "Date" DESC
+            LIMIT $2
+        "#;
+
+        sqlx::query_scalar(sql)
+            .bind(date)
+            .bind(limit)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch recent trading days from PG")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2099, 12, d).unwrap()
+    }
+
+    #[tokio::test]
+    #[cfg_attr(
+        not(feature = "integration-tests"),
+        ignore = "需要外部服務（PostgreSQL/Redis），請加 --features integration-tests 執行"
+    )]
+    async fn test_upsert_extends_consecutive_attention_and_reports_new_entries() {
+        dotenvy::dotenv().ok();
+        if database::ping().await.is_err() {
+            println!("跳過 PgSurveillanceRepository DB 整合測試：無資料庫連接");
+            return;
+        }
+
+        let symbol = "__TS__";
+        let cleanup = || async {
+            let _ = sqlx::query("DELETE FROM trading_surveillance WHERE stock_symbol = $1")
+                .bind(symbol)
+                .execute(database::get_connection())
+                .await;
+        };
+        cleanup().await;
+
+        let repo = PgSurveillanceRepository::new();
+        let attention = |day: u32| SurveillancePeriod {
+            stock_symbol: symbol.to_string(),
+            name: "測試".to_string(),
+            kind: SurveillanceKind::Attention,
+            announced_on: date(day),
+            start_date: date(day),
+            end_date: date(day),
+            reason: "漲幅異常".to_string(),
+            measure: String::new(),
+        };
+
+        let entered = repo.upsert(&[attention(29)], None).await.expect("upsert");
+        assert_eq!(entered.len(), 1, "首次公告應為新列入");
+
+        // 隔一個交易日再被公告：延長同一段期間，不算新列入。
+        let entered = repo
+            .upsert(&[attention(30)], Some(date(29)))
+            .await
+            .expect("upsert consecutive");
+        assert!(entered.is_empty());
+
+        let active = repo
+            .fetch_active_on(date(30), Some(symbol))
+            .await
+            .expect("fetch_active_on");
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].start_date, date(29));
+        assert_eq!(active[0].end_date, date(30));
+
+        // 處置股以起日區分，重送同一段不算新列入。
+        let disposition = SurveillancePeriod {
+            kind: SurveillanceKind::Disposition,
+            start_date: date(30),
+            end_date: date(31),
+            measure: "第一次處置".to_string(),
+            ..attention(30)
+        };
+        assert_eq!(
+            repo.upsert(std::slice::from_ref(&disposition), Some(date(29)))
+                .await
+                .expect("upsert disposition")
+                .len(),
+            1
+        );
+        assert!(
+            repo.upsert(&[disposition], Some(date(29)))
+                .await
+                .expect("upsert disposition again")
+                .is_empty()
+        );
+
+        cleanup().await;
+    }
+}
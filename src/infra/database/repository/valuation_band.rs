@@ -0,0 +1,288 @@
+use crate::domain::valuation::{
+    BAND_YEARS, ValuationBand, ValuationMetric, repository::ValuationBandRepository,
+};
+use crate::infra::database;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::FromRow;
+
+/// 基於 PostgreSQL 的歷史估值區間倉儲實現 (PgValuationBandRepository)。
+///
+/// 負責 `dividend_yield_history` 與 `valuation_band` 兩張表的重建與查詢；
+/// 百分位與區間統計都在 SQL 內以彙總函式完成，避免把十年日資料搬回應用層。
+pub struct PgValuationBandRepository;
+
+impl PgValuationBandRepository {
+    /// 建立新的 PgValuationBandRepository 實例。
+    pub fn new() -> Self {
+        PgValuationBandRepository
+    }
+}
+
+impl Default for PgValuationBandRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 最長回顧年數，決定歷史殖利率首次回補與樣本查詢的起點。
+fn longest_years() -> i32 {
+    BAND_YEARS.iter().copied().max().unwrap_or_default()
+}
+
+/// 資料庫對應的內部資料列結構體，用於 `sqlx::query_as` 映射。
+#[derive(FromRow)]
+struct ValuationBandDbRow {
+    security_code: String,
+    date: NaiveDate,
+    metric: String,
+    years: i32,
+    current: Decimal,
+    percentile: Decimal,
+    minimum: Decimal,
+    median: Decimal,
+    maximum: Decimal,
+    samples: i32,
+}
+
+impl ValuationBandDbRow {
+    /// 轉成領域實體；未知的指標代碼回傳 `None`。
+    fn into_band(self) -> Option<ValuationBand> {
+        Some(ValuationBand {
+            metric: ValuationMetric::from_code(&self.metric)?,
+            security_code: self.security_code,
+            date: self.date,
+            years: self.years,
+            current: self.current,
+            percentile: self.percentile,
+            minimum: self.minimum,
+            median: self.median,
+            maximum: self.maximum,
+            samples: i64::from(self.samples),
+        })
+    }
+}
+
+#[async_trait]
+impl ValuationBandRepository for PgValuationBandRepository {
+    async fn rebuild_daily_yields(&self, date: NaiveDate) -> Result<u64> {
+        // 殖利率沿用 yield_rank 的口徑：收盤價對應當時最近一個年度（今年或
+        // 去年）的全年股利合計。表內已有資料時只從最後一日往前 30 天重算。
+        let sql = r#"
+            INSERT INTO dividend_yield_history (security_code, date, closing_price, dividend, yield)
+            WITH start AS (
+                SELECT COALESCE(MAX(date) - 30, ($1::date - make_interval(years => $2))::date) AS date
+                FROM dividend_yield_history
+            )
+            SELECT
+                dq.stock_symbol, dq."Date", dq."ClosingPrice", d."sum",
+                ROUND(d."sum" / dq."ClosingPrice" * 100, 4)
+            FROM "DailyQuotes" dq
+            CROSS JOIN start
+            JOIN LATERAL (
+                SELECT "sum"
+                FROM dividend
+                WHERE security_code = dq.stock_symbol
+                  AND quarter = ''
+                  AND "year" BETWEEN EXTRACT(YEAR FROM dq."Date")::int - 1
+                                 AND EXTRACT(YEAR FROM dq."Date")::int
+                ORDER BY "year" DESC
+                LIMIT 1
+            ) d ON TRUE
+            WHERE dq."Date" > start.date
+              AND dq."Date" <= $1
+              AND dq."ClosingPrice" > 0
+            ON CONFLICT (security_code, date) DO UPDATE SET
+                closing_price = excluded.closing_price,
+                dividend = excluded.dividend,
+                yield = excluded.yield,
+                updated_time = now()
+        "#;
+
+        let result = sqlx::query(sql)
+            .bind(date)
+            .bind(longest_years())
+            .execute(database::get_connection())
+            .await
+            .context("Failed to rebuild dividend_yield_history in PG")?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn rebuild_bands(&self, date: NaiveDate) -> Result<u64> {
+        // 當日數值取 31 天內各股最新一筆，停牌中的股票仍能以停牌前數值定位。
+        // 百分位定義為「不高於當日數值的樣本比例」，與領域實體的說明一致。
+        let sql = r#"
+            INSERT INTO valuation_band (
+                security_code, date, metric, years, current,
+                percentile, minimum, median, maximum, samples
+            )
+            WITH samples AS (
+                SELECT stock_symbol AS security_code, "Date" AS date,
+                       'per'::varchar AS metric, "PriceEarningRatio" AS value
+                FROM "DailyQuotes"
+                WHERE "Date" > $1::date - make_interval(years => $2)
+                  AND "Date" <= $1
+                  AND "PriceEarningRatio" > 0
+                UNION ALL
+                SELECT stock_symbol, "Date", 'pbr', "price-to-book_ratio"
+                FROM "DailyQuotes"
+                WHERE "Date" > $1::date - make_interval(years => $2)
+                  AND "Date" <= $1
+                  AND "price-to-book_ratio" > 0
+                UNION ALL
+                SELECT security_code, date, 'dividend_yield', yield
+                FROM dividend_yield_history
+                WHERE date > $1::date - make_interval(years => $2)
+                  AND date <= $1
+            ), current AS (
+                SELECT DISTINCT ON (security_code, metric) security_code, metric, value
+                FROM samples
+                WHERE date >= $1::date - 30
+                ORDER BY security_code, metric, date DESC
+            )
+            SELECT
+                s.security_code, $1::date, s.metric, w.years, c.value,
+                ROUND(100.0 * COUNT(*) FILTER (WHERE s.value <= c.value) / COUNT(*), 2),
+                MIN(s.value),
+                ROUND((PERCENTILE_CONT(0.5) WITHIN GROUP (ORDER BY s.value))::numeric, 4),
+                MAX(s.value),
+                COUNT(*)::int
+            FROM samples s
+            JOIN current c ON c.security_code = s.security_code AND c.metric = s.metric
+            JOIN UNNEST($3::int[]) AS w(years)
+              ON s.date > $1::date - make_interval(years => w.years)
+            GROUP BY s.security_code, s.metric, w.years, c.value
+            ON CONFLICT (security_code, date, metric, years) DO UPDATE SET
+                current = excluded.current,
+                percentile = excluded.percentile,
+                minimum = excluded.minimum,
+                median = excluded.median,
+                maximum = excluded.maximum,
+                samples = excluded.samples,
+                updated_time = now()
+        "#;
+
+        let result = sqlx::query(sql)
+            .bind(date)
+            .bind(longest_years())
+            .bind(BAND_YEARS.to_vec())
+            .execute(database::get_connection())
+            .await
+            .context("Failed to rebuild valuation_band in PG")?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn fetch_latest(
+        &self,
+        security_code: &str,
+        date: Option<NaiveDate>,
+    ) -> Result<Vec<ValuationBand>> {
+        let sql = r#"
+            SELECT
+                security_code, date, metric, years, current,
+                percentile, minimum, median, maximum, samples
+            FROM valuation_band
+            WHERE security_code = $1
+              AND date = (
+                  SELECT MAX(date)
+                  FROM valuation_band
+                  WHERE security_code = $1
+                    AND ($2::date IS NULL OR (date <= $2 AND date >= $2 - 30))
+              )
+            ORDER BY metric, years
+        "#;
+
+        let rows = sqlx::query_as::<_, ValuationBandDbRow>(sql)
+            .bind(security_code)
+            .bind(date)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch valuation bands from PG")?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(ValuationBandDbRow::into_band)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[tokio::test]
+    #[cfg_attr(
+        not(feature = "integration-tests"),
+        ignore = "需要外部服務（PostgreSQL/Redis），請加 --features integration-tests 執行"
+    )]
+    async fn test_rebuild_and_fetch_latest() {
+        dotenvy::dotenv().ok();
+        if database::ping().await.is_err() {
+            println!("跳過 PgValuationBandRepository DB 整合測試：無資料庫連接");
+            return;
+        }
+
+        let code = "__VB__";
+        let cleanup = || async {
+            for sql in [
+                r#"DELETE FROM valuation_band WHERE security_code = $1"#,
+                r#"DELETE FROM "DailyQuotes" WHERE stock_symbol = $1"#,
+            ] {
+                let _ = sqlx::query(sql)
+                    .bind(code)
+                    .execute(database::get_connection())
+                    .await;
+            }
+        };
+        cleanup().await;
+
+        // 五個交易日的本益比 10、12、14、16、13：基準日 13 高於其中三筆（含自己）。
+        let base = NaiveDate::from_ymd_opt(2099, 12, 1).unwrap();
+        for (offset, per) in [dec!(10), dec!(12), dec!(14), dec!(16), dec!(13)]
+            .into_iter()
+            .enumerate()
+        {
+            sqlx::query(
+                r#"INSERT INTO "DailyQuotes" (stock_symbol, "Date", "ClosingPrice", "PriceEarningRatio") VALUES ($1, $2, 100, $3)"#,
+            )
+            .bind(code)
+            .bind(base + chrono::TimeDelta::days(offset as i64))
+            .bind(per)
+            .execute(database::get_connection())
+            .await
+            .expect("insert quote");
+        }
+
+        let repo = PgValuationBandRepository::new();
+        let date = base + chrono::TimeDelta::days(4);
+        repo.rebuild_bands(date).await.expect("rebuild bands");
+
+        let bands = repo.fetch_latest(code, None).await.expect("fetch");
+        let per: Vec<&ValuationBand> = bands
+            .iter()
+            .filter(|band| band.metric == ValuationMetric::PriceEarnings)
+            .collect();
+        assert_eq!(per.len(), BAND_YEARS.len());
+        assert_eq!(per[0].date, date);
+        assert_eq!(per[0].current, dec!(13));
+        assert_eq!(per[0].percentile, dec!(60));
+        assert_eq!(per[0].minimum, dec!(10));
+        assert_eq!(per[0].median, dec!(13));
+        assert_eq!(per[0].maximum, dec!(16));
+        assert_eq!(per[0].samples, 5);
+        assert!(
+            repo.fetch_latest(code, NaiveDate::from_ymd_opt(2099, 10, 1))
+                .await
+                .expect("fetch outside window")
+                .is_empty()
+        );
+
+        cleanup().await;
+    }
+}
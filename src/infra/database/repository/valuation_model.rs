@@ -0,0 +1,361 @@
+use std::collections::HashMap;
+
+use crate::core::declare::Industry;
+use crate::domain::valuation::{
+    ModelEstimate, PriceRange, QuantileRequest, ValuationInputs, ValuationMetric,
+    ValuationModelKind, repository::ValuationModelRepository,
+};
+use crate::infra::database;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use rust_decimal::{Decimal, prelude::ToPrimitive};
+use sqlx::FromRow;
+
+/// 基於 PostgreSQL 的估價模型倉儲實現 (PgValuationModelRepository)。
+///
+/// 提供模型計算所需的股價、股利、EPS、淨值與歷史分位數，並讀寫
+/// `valuation_model_estimate` 資料表。
+pub struct PgValuationModelRepository;
+
+impl PgValuationModelRepository {
+    /// 建立新的 PgValuationModelRepository 實例。
+    pub fn new() -> Self {
+        PgValuationModelRepository
+    }
+}
+
+impl Default for PgValuationModelRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 模型輸入的資料列。
+#[derive(FromRow)]
+struct InputsDbRow {
+    security_code: String,
+    closing_price: Decimal,
+    dividend: Option<Decimal>,
+    trailing_eps: Decimal,
+    book_value_per_share: Decimal,
+}
+
+/// 估價結果的資料列。
+#[derive(FromRow)]
+struct EstimateDbRow {
+    security_code: String,
+    date: NaiveDate,
+    model: String,
+    closing_price: Decimal,
+    cheap: Decimal,
+    fair: Decimal,
+    expensive: Decimal,
+    assumptions: String,
+}
+
+impl EstimateDbRow {
+    /// 轉成領域實體；未知的模型代碼回傳 `None`。
+    fn into_estimate(self) -> Option<ModelEstimate> {
+        Some(ModelEstimate {
+            model: ValuationModelKind::from_code(&self.model)?,
+            security_code: self.security_code,
+            date: self.date,
+            closing_price: self.closing_price,
+            range: PriceRange {
+                cheap: self.cheap,
+                fair: self.fair,
+                expensive: self.expensive,
+            },
+            assumptions: self.assumptions,
+        })
+    }
+}
+
+#[async_trait]
+impl ValuationModelRepository for PgValuationModelRepository {
+    async fn fetch_inputs(&self, date: NaiveDate) -> Result<Vec<ValuationInputs>> {
+        // 近四季 EPS 與每股淨值沿用 stocks 表上由財報回補維護的欄位；
+        // 股利口徑與 yield_rank 一致，取今年或去年最近一個年度的合計。
+        let sql = r#"
+            SELECT
+                s.stock_symbol AS security_code,
+                q."ClosingPrice" AS closing_price,
+                d."sum" AS dividend,
+                s.last_four_eps AS trailing_eps,
+                s.net_asset_value_per_share AS book_value_per_share
+            FROM stocks s
+            JOIN LATERAL (
+                SELECT "ClosingPrice"
+                FROM "DailyQuotes"
+                WHERE stock_symbol = s.stock_symbol
+                  AND "Date" <= $1
+                  AND "Date" >= $1::date - 30
+                  AND "ClosingPrice" > 0
+                ORDER BY "Date" DESC
+                LIMIT 1
+            ) q ON TRUE
+            LEFT JOIN LATERAL (
+                SELECT "sum"
+                FROM dividend
+                WHERE security_code = s.stock_symbol
+                  AND quarter = ''
+                  AND "year" BETWEEN EXTRACT(YEAR FROM $1::date)::int - 1
+                                 AND EXTRACT(YEAR FROM $1::date)::int
+                ORDER BY "year" DESC
+                LIMIT 1
+            ) d ON TRUE
+            WHERE s."SuspendListing" = false
+              AND s.stock_industry_id != $2
+            ORDER BY s.stock_symbol
+        "#;
+
+        let rows = sqlx::query_as::<_, InputsDbRow>(sql)
+            .bind(date)
+            .bind(Industry::ExchangeTradedFund.serial())
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch valuation model inputs from PG")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ValuationInputs {
+                security_code: row.security_code,
+                date,
+                closing_price: row.closing_price,
+                dividend: row.dividend,
+                trailing_eps: Some(row.trailing_eps).filter(|eps| !eps.is_zero()),
+                book_value_per_share: Some(row.book_value_per_share).filter(|bv| !bv.is_zero()),
+                quantiles: HashMap::new(),
+            })
+            .collect())
+    }
+
+    async fn fetch_quantiles(
+        &self,
+        date: NaiveDate,
+        request: &QuantileRequest,
+    ) -> Result<HashMap<String, [Decimal; 3]>> {
+        // 樣本條件與 valuation_band 相同：本益比與股價淨值比只取正值。
+        let sql = match request.metric {
+            ValuationMetric::PriceEarnings => {
+                r#"
+                SELECT stock_symbol AS security_code,
+                       (PERCENTILE_CONT($3::float8[]) WITHIN GROUP (ORDER BY "PriceEarningRatio"))::numeric[] AS quantiles
+                FROM "DailyQuotes"
+                WHERE "Date" > $1::date - make_interval(years => $2)
+                  AND "Date" <= $1
+                  AND "PriceEarningRatio" > 0
+                GROUP BY stock_symbol
+                "#
+            }
+            ValuationMetric::PriceBook => {
+                r#"
+                SELECT stock_symbol AS security_code,
+                       (PERCENTILE_CONT($3::float8[]) WITHIN GROUP (ORDER BY "price-to-book_ratio"))::numeric[] AS quantiles
+                FROM "DailyQuotes"
+                WHERE "Date" > $1::date - make_interval(years => $2)
+                  AND "Date" <= $1
+                  AND "price-to-book_ratio" > 0
+                GROUP BY stock_symbol
+                "#
+            }
+            ValuationMetric::DividendYield => {
+                r#"
+                SELECT security_code,
+                       (PERCENTILE_CONT($3::float8[]) WITHIN GROUP (ORDER BY yield))::numeric[] AS quantiles
+                FROM dividend_yield_history
+                WHERE date > $1::date - make_interval(years => $2)
+                  AND date <= $1
+                GROUP BY security_code
+                "#
+            }
+        };
+        let fractions: Vec<f64> = request
+            .percentiles
+            .iter()
+            .map(|percentile| (percentile / Decimal::ONE_HUNDRED).to_f64().unwrap_or(0.5))
+            .collect();
+
+        let rows: Vec<(String, Vec<Decimal>)> = sqlx::query_as(sql)
+            .bind(date)
+            .bind(request.years)
+            .bind(fractions)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch valuation quantiles from PG")?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(security_code, quantiles)| {
+                let quantiles: [Decimal; 3] = quantiles.try_into().ok()?;
+                Some((security_code, quantiles.map(|value| value.round_dp(4))))
+            })
+            .collect())
+    }
+
+    async fn upsert_estimates(&self, estimates: &[ModelEstimate]) -> Result<()> {
+        if estimates.is_empty() {
+            return Ok(());
+        }
+
+        let sql = r#"
+            INSERT INTO valuation_model_estimate (
+                security_code, date, model, closing_price, cheap, fair, expensive, assumptions
+            )
+            SELECT * FROM UNNEST(
+                $1::varchar[], $2::date[], $3::varchar[], $4::numeric[],
+                $5::numeric[], $6::numeric[], $7::numeric[], $8::text[]
+            )
+            ON CONFLICT (security_code, date, model) DO UPDATE SET
+                closing_price = excluded.closing_price,
+                cheap = excluded.cheap,
+                fair = excluded.fair,
+                expensive = excluded.expensive,
+                assumptions = excluded.assumptions,
+                updated_time = now()
+        "#;
+
+        let mut security_codes = Vec::with_capacity(estimates.len());
+        let mut dates = Vec::with_capacity(estimates.len());
+        let mut models = Vec::with_capacity(estimates.len());
+        let mut closing_prices = Vec::with_capacity(estimates.len());
+        let mut cheaps = Vec::with_capacity(estimates.len());
+        let mut fairs = Vec::with_capacity(estimates.len());
+        let mut expensives = Vec::with_capacity(estimates.len());
+        let mut assumptions = Vec::with_capacity(estimates.len());
+        for estimate in estimates {
+            security_codes.push(estimate.security_code.as_str());
+            dates.push(estimate.date);
+            models.push(estimate.model.code());
+            closing_prices.push(estimate.closing_price);
+            cheaps.push(estimate.range.cheap);
+            fairs.push(estimate.range.fair);
+            expensives.push(estimate.range.expensive);
+            assumptions.push(estimate.assumptions.as_str());
+        }
+
+        sqlx::query(sql)
+            .bind(security_codes)
+            .bind(dates)
+            .bind(models)
+            .bind(closing_prices)
+            .bind(cheaps)
+            .bind(fairs)
+            .bind(expensives)
+            .bind(assumptions)
+            .execute(database::get_connection())
+            .await
+            .context("Failed to upsert valuation model estimates to PG")?;
+
+        Ok(())
+    }
+
+    async fn fetch_estimates(
+        &self,
+        security_code: &str,
+        date: Option<NaiveDate>,
+    ) -> Result<Vec<ModelEstimate>> {
+        let sql = r#"
+            SELECT
+                security_code, date, model, closing_price, cheap, fair, expensive, assumptions
+            FROM valuation_model_estimate
+            WHERE security_code = $1
+              AND date = (
+                  SELECT MAX(date)
+                  FROM valuation_model_estimate
+                  WHERE security_code = $1
+                    AND ($2::date IS NULL OR (date <= $2 AND date >= $2 - 30))
+              )
+        "#;
+
+        let rows = sqlx::query_as::<_, EstimateDbRow>(sql)
+            .bind(security_code)
+            .bind(date)
+            .fetch_all(database::get_connection())
+            .await
+            .context("Failed to fetch valuation model estimates from PG")?;
+
+        let mut estimates: Vec<ModelEstimate> = rows
+            .into_iter()
+            .filter_map(EstimateDbRow::into_estimate)
+            .collect();
+        // 依模型註冊順序輸出，API 呈現順序才固定。
+        estimates.sort_by_key(|estimate| {
+            ValuationModelKind::ALL
+                .iter()
+                .position(|kind| *kind == estimate.model)
+        });
+        Ok(estimates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[tokio::test]
+    #[cfg_attr(
+        not(feature = "integration-tests"),
+        ignore = "需要外部服務（PostgreSQL/Redis），請加 --features integration-tests 執行"
+    )]
+    async fn test_upsert_and_fetch_estimates() {
+        dotenvy::dotenv().ok();
+        if database::ping().await.is_err() {
+            println!("跳過 PgValuationModelRepository DB 整合測試：無資料庫連接");
+            return;
+        }
+
+        let code = "__VM__";
+        let cleanup = || async {
+            let _ = sqlx::query("DELETE FROM valuation_model_estimate WHERE security_code = $1")
+                .bind(code)
+                .execute(database::get_connection())
+                .await;
+        };
+        cleanup().await;
+
+        let date = NaiveDate::from_ymd_opt(2099, 12, 1).unwrap();
+        let estimate = |model, fair| ModelEstimate {
+            security_code: code.to_string(),
+            date,
+            model,
+            closing_price: dec!(100),
+            range: PriceRange {
+                cheap: fair - dec!(10),
+                fair,
+                expensive: fair + dec!(10),
+            },
+            assumptions: "{}".to_string(),
+        };
+        let repo = PgValuationModelRepository::new();
+        repo.upsert_estimates(&[
+            estimate(ValuationModelKind::DividendDiscount, dec!(60)),
+            estimate(ValuationModelKind::YieldBand, dec!(90)),
+        ])
+        .await
+        .expect("upsert");
+        repo.upsert_estimates(&[estimate(ValuationModelKind::YieldBand, dec!(95))])
+            .await
+            .expect("upsert again");
+
+        let stored = repo.fetch_estimates(code, None).await.expect("fetch");
+        assert_eq!(
+            stored,
+            vec![
+                estimate(ValuationModelKind::YieldBand, dec!(95)),
+                estimate(ValuationModelKind::DividendDiscount, dec!(60)),
+            ]
+        );
+        assert!(
+            repo.fetch_estimates(code, NaiveDate::from_ymd_opt(2099, 10, 1))
+                .await
+                .expect("fetch outside window")
+                .is_empty()
+        );
+
+        cleanup().await;
+    }
+}
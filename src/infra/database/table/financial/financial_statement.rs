@@ -477,6 +477,56 @@ ORDER BY
         })
 }
 
+/// 取得單一公司在 `as_of` 當時已知的各期財報，每期取當時最新的版本，依年度與季別排序。
+///
+/// 資料來自 `fundamental_revision`，`created_time` 為首次得知該期數字的時間，
+/// `updated_time` 為得知該版本的時間。
+///
+/// # Errors
+/// 當查詢失敗時回傳錯誤。
+pub async fn fetch_as_of(
+    security_code: &str,
+    as_of: DateTime<Local>,
+) -> Result<Vec<FinancialStatement>> {
+    let sql = r#"
+SELECT
+    f.serial,
+    f.security_code,
+    f.year,
+    f.quarter,
+    f.gross_profit,
+    f.operating_profit_margin,
+    f."pre-tax_income",
+    f.net_income,
+    f.net_asset_value_per_share,
+    f.sales_per_share,
+    f.earnings_per_share,
+    f.profit_before_tax,
+    f.return_on_equity,
+    f.return_on_assets,
+    v.first_seen_time AS created_time,
+    v.recorded_time AS updated_time
+FROM (
+    SELECT DISTINCT ON (period) payload, first_seen_time, recorded_time
+    FROM fundamental_revision
+    WHERE kind = 'financial_statement'
+      AND security_code = $1
+      AND recorded_time <= $2
+    ORDER BY period, revision DESC
+) v
+CROSS JOIN LATERAL jsonb_populate_record(NULL::financial_statement, v.payload) f
+ORDER BY f.year, f.quarter
+"#;
+    let result = sqlx::query(sql)
+        .bind(security_code)
+        .bind(as_of)
+        .try_map(FinancialStatement::row_to_entity)
+        .fetch_all(database::get_connection())
+        .await?;
+
+    Ok(result)
+}
+
 /// 取得指定年度、指定季別集合的 EPS 累計。
 ///
 /// # Errors
@@ -64,6 +64,26 @@ impl Revenue {
         }
     }
 
+    /// 將查詢結果轉換為 `Revenue`。
+    fn row_to_entity(row: PgRow) -> std::result::Result<Revenue, sqlx::Error> {
+        Ok(Revenue {
+            date: row.try_get("Date")?,
+            stock_symbol: row.try_get("stock_symbol")?,
+            monthly: row.try_get("Monthly")?,
+            last_month: row.try_get("LastMonth")?,
+            last_year_this_month: row.try_get("LastYearThisMonth")?,
+            monthly_accumulated: row.try_get("MonthlyAccumulated")?,
+            last_year_monthly_accumulated: row.try_get("LastYearMonthlyAccumulated")?,
+            compared_with_last_month: row.try_get("ComparedWithLastMonth")?,
+            compared_with_last_year_same_month: row.try_get("ComparedWithLastYearSameMonth")?,
+            accumulated_compared_with_last_year: row.try_get("AccumulatedComparedWithLastYear")?,
+            avg_price: row.try_get("avg_price")?,
+            lowest_price: row.try_get("lowest_price")?,
+            highest_price: row.try_get("highest_price")?,
+            create_time: row.try_get("CreateTime")?,
+        })
+    }
+
     /// 新增或更新單月營收資料。
     ///
     /// # Errors
@@ -324,44 +344,59 @@ order by "Serial" desc
     )
     .bind(last_month_int)
     .bind(two_month_ago_int)
-    .try_map(|row: PgRow| {
-        let date = row.try_get("Date")?;
-        let stock_symbol = row.try_get("stock_symbol")?;
-        let monthly = row.try_get("Monthly")?;
-        let last_month = row.try_get("LastMonth")?;
-        let last_year_this_month = row.try_get("LastYearThisMonth")?;
-        let monthly_accumulated = row.try_get("MonthlyAccumulated")?;
-        let last_year_monthly_accumulated = row.try_get("LastYearMonthlyAccumulated")?;
-        let compared_with_last_month = row.try_get("ComparedWithLastMonth")?;
-        let compared_with_last_year_same_month = row.try_get("ComparedWithLastYearSameMonth")?;
-        let accumulated_compared_with_last_year = row.try_get("AccumulatedComparedWithLastYear")?;
-        let avg_price = row.try_get("avg_price")?;
-        let lowest_price = row.try_get("lowest_price")?;
-        let highest_price = row.try_get("highest_price")?;
-        let create_time = row.try_get("CreateTime")?;
-        Ok(Revenue {
-            date,
-            stock_symbol,
-            monthly,
-            last_month,
-            last_year_this_month,
-            monthly_accumulated,
-            last_year_monthly_accumulated,
-            compared_with_last_month,
-            compared_with_last_year_same_month,
-            accumulated_compared_with_last_year,
-            avg_price,
-            lowest_price,
-            highest_price,
-            create_time,
-        })
-    })
+    .try_map(Revenue::row_to_entity)
     .fetch_all(database::get_connection())
     .await?;
 
     Ok(revenue)
 }
 
+/// 取得單一公司在 `as_of` 當時已知的各月營收，每月取當時最新的版本，依月份排序。
+///
+/// 資料來自 `fundamental_revision`，`CreateTime` 以首次得知該月營收的時間取代。
+///
+/// # Errors
+/// 當查詢失敗時回傳錯誤。
+pub async fn fetch_as_of(security_code: &str, as_of: DateTime<Local>) -> Result<Vec<Revenue>> {
+    let revenues = sqlx::query(
+        r#"
+select
+    r."stock_symbol",
+    r."Date",
+    r."Monthly",
+    r."LastMonth",
+    r."LastYearThisMonth",
+    r."MonthlyAccumulated",
+    r."LastYearMonthlyAccumulated",
+    r."ComparedWithLastMonth",
+    r."ComparedWithLastYearSameMonth",
+    r."AccumulatedComparedWithLastYear",
+    v.first_seen_time as "CreateTime",
+    r.avg_price,
+    r.lowest_price,
+    r.highest_price
+from (
+    select distinct on (period) payload, first_seen_time
+    from fundamental_revision
+    where kind = 'revenue'
+      and security_code = $1
+      and recorded_time <= $2
+    order by period, revision desc
+) v
+cross join lateral jsonb_populate_record(null::"Revenue", v.payload) r
+order by r."Date"
+        "#,
+    )
+    .bind(security_code)
+    .bind(as_of)
+    .try_map(Revenue::row_to_entity)
+    .fetch_all(database::get_connection())
+    .await
+    .context("Failed to fetch point-in-time revenues from PG")?;
+
+    Ok(revenues)
+}
+
 /// 重建 `revenue_last_date`，讓每檔股票只保留最新一筆營收序號。
 ///
 /// # Errors
@@ -1,3 +1,4 @@
+pub mod archive;
 pub mod cache;
 pub mod crawler;
 pub mod database;
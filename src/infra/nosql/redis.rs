@@ -262,6 +262,29 @@ impl Redis {
         Ok(result == 1)
     }
 
+    /// 以 `XADD` 追加一筆訊息至 Stream，並以 `MAXLEN ~` 約略限制保留筆數。
+    ///
+    /// # 回傳
+    /// Redis 配發的訊息 ID。
+    pub async fn stream_add(
+        &self,
+        stream: &str,
+        max_len: u64,
+        fields: &[(&str, &str)],
+    ) -> Result<String, RedisError> {
+        let mut conn = self.pool.get().await?;
+        let id: String = cmd("XADD")
+            .arg(stream)
+            .arg("MAXLEN")
+            .arg("~")
+            .arg(max_len)
+            .arg("*")
+            .arg(fields)
+            .query_async(&mut conn)
+            .await?;
+        Ok(id)
+    }
+
     /// 取得指定 key 的字串值。
     pub async fn get_string(&self, key: &str) -> Result<String, RedisError> {
         let mut conn = self.pool.get().await?;
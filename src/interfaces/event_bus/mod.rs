@@ -0,0 +1,87 @@
+//! 對外事件匯流排 adapter。
+//!
+//! 實作 `app::ports::EventBus`，把帶版本的事件 JSON 發布給其他服務訂閱：
+//! - [`redis_stream`]：寫入 Redis Stream，沿用 `infra::nosql::redis::CLIENT` 的連線池。
+//! - [`nats`]：以 `async-nats` 客戶端發布到 NATS subject。
+//!
+//! 依 `app.json: eventBus.backend` 選擇後端，由 `main` 啟動時註冊。
+
+use std::sync::Arc;
+
+use anyhow::{Result, bail};
+
+use crate::{app::ports::EventBus, core::config};
+
+/// NATS 發布器。
+pub mod nats;
+/// Redis Stream 發布器。
+pub mod redis_stream;
+
+/// 未設定時使用的 Redis Stream key。
+pub const DEFAULT_STREAM: &str = "stock_crawler:events";
+/// 未設定時 Redis Stream 保留的約略筆數。
+pub const DEFAULT_MAX_LEN: u64 = 100_000;
+/// 未設定時使用的 NATS subject 前綴。
+pub const DEFAULT_SUBJECT_PREFIX: &str = "stock_crawler.events";
+
+/// 依設定建立事件匯流排；未設定後端時回傳 `None`。
+///
+/// # Errors
+///
+/// 後端名稱無法辨識，或選擇 NATS 卻未設定伺服器位址時回傳錯誤。
+pub fn from_settings(settings: &config::EventBus) -> Result<Option<Arc<dyn EventBus>>> {
+    let or_default = |value: &str, default: &str| {
+        if value.trim().is_empty() {
+            default.to_string()
+        } else {
+            value.trim().to_string()
+        }
+    };
+
+    match settings.backend.trim().to_ascii_lowercase().as_str() {
+        "" | "none" => Ok(None),
+        "redis" => Ok(Some(Arc::new(redis_stream::RedisStreamEventBus::new(
+            or_default(&settings.stream, DEFAULT_STREAM),
+            if settings.max_len == 0 {
+                DEFAULT_MAX_LEN
+            } else {
+                settings.max_len
+            },
+        )))),
+        "nats" => {
+            if settings.nats_url.trim().is_empty() {
+                bail!("eventBus.natsUrl is required for the nats backend");
+            }
+            Ok(Some(Arc::new(nats::NatsEventBus::new(
+                &settings.nats_url,
+                or_default(&settings.subject_prefix, DEFAULT_SUBJECT_PREFIX),
+            )?)))
+        }
+        other => bail!("Unknown event bus backend '{other}'"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_settings_selects_backend() {
+        let settings = |backend: &str, nats_url: &str| config::EventBus {
+            backend: backend.to_string(),
+            nats_url: nats_url.to_string(),
+            ..Default::default()
+        };
+
+        assert!(from_settings(&settings("", "")).unwrap().is_none());
+        assert!(from_settings(&settings("none", "")).unwrap().is_none());
+        assert!(from_settings(&settings("Redis", "")).unwrap().is_some());
+        assert!(
+            from_settings(&settings("nats", "nats://localhost:4222"))
+                .unwrap()
+                .is_some()
+        );
+        assert!(from_settings(&settings("nats", "")).is_err());
+        assert!(from_settings(&settings("kafka", "")).is_err());
+    }
+}
@@ -0,0 +1,90 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+
+use crate::{
+    app::ports::{EventBus, EventEnvelope},
+    infra::nosql::redis::CLIENT,
+};
+
+/// 將事件寫入 Redis Stream 的發布器。
+///
+/// 每筆訊息帶 `type`、`schema_version` 與 `payload`（完整的信封 JSON）三個欄位，
+/// 消費端可用 `XREAD`／消費者群組讀取，先依 `type` 過濾再解析 `payload`。
+pub struct RedisStreamEventBus {
+    /// Stream key。
+    stream: String,
+    /// 約略保留筆數。
+    max_len: u64,
+}
+
+impl RedisStreamEventBus {
+    /// 建立寫入指定 Stream 的發布器。
+    pub fn new(stream: String, max_len: u64) -> Self {
+        RedisStreamEventBus { stream, max_len }
+    }
+}
+
+#[async_trait]
+impl EventBus for RedisStreamEventBus {
+    async fn publish(&self, envelope: &EventEnvelope) -> Result<()> {
+        let payload = serde_json::to_string(envelope).context("Failed to serialize envelope")?;
+        let schema_version = envelope.schema_version.to_string();
+
+        CLIENT
+            .stream_add(
+                &self.stream,
+                self.max_len,
+                &[
+                    ("type", envelope.event_type.as_str()),
+                    ("schema_version", schema_version.as_str()),
+                    ("payload", payload.as_str()),
+                ],
+            )
+            .await
+            .with_context(|| {
+                format!("Failed to XADD {} to {}", envelope.event_type, self.stream)
+            })?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Local;
+
+    use super::*;
+    use crate::app::ports::{OutboundEvent, ReadyDataset};
+
+    #[tokio::test]
+    #[cfg_attr(
+        not(feature = "integration-tests"),
+        ignore = "需要外部服務（PostgreSQL/Redis），請加 --features integration-tests 執行"
+    )]
+    async fn test_publish_to_stream() {
+        dotenvy::dotenv().ok();
+        if CLIENT.ping().await.is_err() {
+            println!("skip redis test: Redis is unavailable in current environment");
+            return;
+        }
+
+        let stream = "stock_crawler:events:test";
+        let envelope = EventEnvelope::new(
+            &OutboundEvent::DataReady {
+                dataset: ReadyDataset::DailyQuotes,
+                date: Local::now().date_naive(),
+                records: 1,
+                occurred_at: Local::now(),
+            },
+            Local::now(),
+        )
+        .unwrap();
+
+        let result = RedisStreamEventBus::new(stream.to_string(), 10)
+            .publish(&envelope)
+            .await;
+        let _ = CLIENT.delete(stream).await;
+
+        result.expect("publish to redis stream");
+    }
+}
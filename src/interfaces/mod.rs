@@ -1,3 +1,4 @@
 pub mod bot;
+pub mod event_bus;
 pub mod rpc;
 pub mod web;
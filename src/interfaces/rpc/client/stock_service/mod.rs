@@ -1,122 +1,156 @@
 //! Stock gRPC 客戶端服務實作。
 //!
-//! 提供向遠端 gRPC 服務（如 Go 服務）發送股票資訊的方法。
+//! 提供向遠端 gRPC 服務（如 Go 服務）批次同步證券主檔與取回遠端主檔的方法。
 
 use anyhow::Result;
-use tonic::{Request, Response};
+use rust_decimal::{
+    Decimal,
+    prelude::{FromPrimitive, ToPrimitive},
+};
+use tonic::Request;
 
-use crate::interfaces::rpc::{
-    client::{Grpc, get_client},
-    stock::{StockInfoReply, StockInfoRequest},
+use crate::{
+    app::ports::StockSyncGateway,
+    domain::registry::sync::{StockSyncRecord, SyncRejection},
+    interfaces::rpc::{
+        client::{Grpc, get_client},
+        stock::{ListStocksRequest, StockInfoRequest, SyncStocksReply},
+    },
 };
 
 impl Grpc {
-    /// 將股票資訊更新通知發送至 Go 服務。
-    ///
-    /// 透過 gRPC `update_stock_info` 介面將最新的股票資訊同步至遠端服務。
+    /// 以 client-streaming `SyncStocks` 批次送出證券主檔。
     ///
-    /// # Arguments
+    /// # Errors
     ///
-    /// * `request` - 包含股票詳細資訊的 `StockInfoRequest`。
+    /// 如果 gRPC 調用失敗，則回傳錯誤；個別證券被拒絕時記錄在回覆的 `rejections`。
+    pub async fn sync_stocks(&self, requests: Vec<StockInfoRequest>) -> Result<SyncStocksReply> {
+        let mut client = self.stock.clone();
+        let response = client
+            .sync_stocks(Request::new(tokio_stream::iter(requests)))
+            .await?;
+        Ok(response.into_inner())
+    }
+
+    /// 取得遠端服務保存的全部證券主檔。
     ///
     /// # Errors
     ///
     /// 如果 gRPC 調用失敗，則回傳錯誤。
-    pub async fn update_stock_info(
-        &self,
-        request: StockInfoRequest,
-    ) -> Result<Response<StockInfoReply>> {
+    pub async fn list_stocks(&self) -> Result<Vec<StockInfoRequest>> {
         let mut client = self.stock.clone();
-        Ok(client.update_stock_info(Request::new(request)).await?)
+        let response = client
+            .list_stocks(Request::new(ListStocksRequest {}))
+            .await?;
+        Ok(response.into_inner().stocks)
     }
 }
 
-/// 全域函數：將股票資訊推送至 Go 服務。
-///
-/// 此函數會自動取得或初始化全域 gRPC 客戶端，並發送請求。
-///
-/// # Arguments
-///
-/// * `request` - 包含股票詳細資訊的 `StockInfoRequest`。
-pub async fn push_stock_info_to_go_service(
-    request: StockInfoRequest,
-) -> Result<Response<StockInfoReply>> {
-    get_client().await?.update_stock_info(request).await
-}
-
-/// 把 gRPC 推送接上 `app::ports::StockInfoGateway` port 的 adapter。
+/// 把 gRPC 同步接上 `app::ports::StockSyncGateway` port 的 adapter。
 ///
 /// 依 DDD 分層，app 層不應直接依賴 prost 產生的 `StockInfoRequest`（傳輸層
-/// 細節）。app 只呼叫 `app::ports::push_stock_info`；由這個 adapter 負責
-/// 「與傳輸無關的載體 → gRPC DTO」的轉換，並在 `main` 啟動時註冊。
-pub struct GrpcStockInfoGateway;
+/// 細節）。app 的同步佇列只處理 [`StockSyncRecord`]；由這個 adapter 負責
+/// 與 gRPC DTO 之間的轉換，並在 `main` 啟動時註冊。
+pub struct GrpcStockSyncGateway;
 
 #[async_trait::async_trait]
-impl crate::app::ports::StockInfoGateway for GrpcStockInfoGateway {
-    async fn push_stock_info(&self, info: crate::app::ports::StockInfoPush) -> Result<()> {
-        // 在 adapter 內完成傳輸層 DTO 的組裝；prost 專屬欄位在這裡補預設值。
-        let request = StockInfoRequest {
-            stock_symbol: info.stock_symbol,
-            name: info.name,
-            stock_exchange_market_id: info.stock_exchange_market_id,
-            stock_industry_id: info.stock_industry_id,
-            net_asset_value_per_share: 0.0,
-            suspend_listing: false,
-        };
-        push_stock_info_to_go_service(request).await?;
-        Ok(())
+impl StockSyncGateway for GrpcStockSyncGateway {
+    async fn sync_stocks(&self, records: Vec<StockSyncRecord>) -> Result<Vec<SyncRejection>> {
+        let requests = records.iter().map(StockInfoRequest::from).collect();
+        let reply = get_client().await?.sync_stocks(requests).await?;
+        Ok(reply
+            .rejections
+            .into_iter()
+            .map(|rejection| SyncRejection {
+                stock_symbol: rejection.stock_symbol,
+                reason: rejection.reason,
+            })
+            .collect())
+    }
+
+    async fn fetch_remote_stocks(&self) -> Result<Vec<StockSyncRecord>> {
+        let stocks = get_client().await?.list_stocks().await?;
+        Ok(stocks.into_iter().map(StockSyncRecord::from).collect())
     }
 }
 
-impl From<&crate::domain::registry::entity::Stock> for StockInfoRequest {
-    fn from(stock: &crate::domain::registry::entity::Stock) -> Self {
+impl From<&StockSyncRecord> for StockInfoRequest {
+    fn from(record: &StockSyncRecord) -> Self {
         StockInfoRequest {
-            stock_symbol: stock.symbol().0.clone(),
-            name: stock.name().to_string(),
-            stock_exchange_market_id: stock.market_id(),
-            stock_industry_id: stock.industry_id(),
-            net_asset_value_per_share:
-                <rust_decimal::Decimal as rust_decimal::prelude::ToPrimitive>::to_f64(
-                    &stock.net_asset_value_per_share(),
-                )
-                .unwrap_or(0.0),
-            suspend_listing: stock.suspend_listing(),
+            stock_symbol: record.stock_symbol.clone(),
+            name: record.name.clone(),
+            stock_exchange_market_id: record.market_id,
+            stock_industry_id: record.industry_id,
+            net_asset_value_per_share: record.net_asset_value_per_share.to_f64().unwrap_or(0.0),
+            suspend_listing: record.suspend_listing,
+        }
+    }
+}
+
+impl From<StockInfoRequest> for StockSyncRecord {
+    fn from(request: StockInfoRequest) -> Self {
+        // 遠端以 double 保存淨值，轉回 Decimal 時去掉浮點誤差。
+        let net_asset_value_per_share = Decimal::from_f64(request.net_asset_value_per_share)
+            .unwrap_or_default()
+            .round_dp(4);
+        StockSyncRecord {
+            stock_symbol: request.stock_symbol,
+            name: request.name,
+            market_id: request.stock_exchange_market_id,
+            industry_id: request.stock_industry_id,
+            net_asset_value_per_share,
+            suspend_listing: request.suspend_listing,
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::infra::cache::SHARE;
+    use rust_decimal_macros::dec;
 
     use super::*;
 
-    /// 驗證股票資訊是否能成功推送至遠端 Go 服務。
+    #[test]
+    fn test_record_round_trips_through_request() {
+        let record = StockSyncRecord {
+            stock_symbol: "2330".to_string(),
+            name: "台積電".to_string(),
+            market_id: 2,
+            industry_id: 24,
+            net_asset_value_per_share: dec!(135.27),
+            suspend_listing: false,
+        };
+
+        let request = StockInfoRequest::from(&record);
+        assert_eq!(request.net_asset_value_per_share, 135.27);
+        assert_eq!(StockSyncRecord::from(request), record);
+    }
+
+    /// 驗證證券主檔是否能成功批次同步至遠端 Go 服務。
     ///
     /// 此測試預設忽略，僅在需要手動驗證與遠端服務連線時使用。
     #[tokio::test]
     #[ignore]
-    async fn test_push_stock_info_to_go_service() {
+    async fn test_sync_stocks_to_go_service() {
         dotenvy::dotenv().ok();
-        SHARE.load().await;
-        tracing::debug!("開始 push_stock_info_to_go_service");
-        let request = StockInfoRequest {
+        tracing::debug!("開始 sync_stocks");
+        let record = StockSyncRecord {
             stock_symbol: "7533967".to_string(),
             name: "tonic".to_string(),
-            stock_exchange_market_id: 1,
-            stock_industry_id: 2,
-            net_asset_value_per_share: 1.235,
+            market_id: 1,
+            industry_id: 2,
+            net_asset_value_per_share: dec!(1.235),
             suspend_listing: false,
         };
 
-        match push_stock_info_to_go_service(request).await {
-            Ok(response) => {
-                tracing::debug!("response:{:#?}", response);
+        match GrpcStockSyncGateway.sync_stocks(vec![record]).await {
+            Ok(rejections) => {
+                tracing::debug!("rejections:{:#?}", rejections);
             }
             Err(why) => {
-                tracing::debug!("Failed to push_stock_info_to_go_service because {:?}", why);
+                tracing::debug!("Failed to sync_stocks because {:?}", why);
             }
         }
-        tracing::debug!("結束 push_stock_info_to_go_service");
+        tracing::debug!("結束 sync_stocks");
     }
 }
@@ -61,6 +61,27 @@ pub struct CorporateActionResponse {
     pub actions: ::prost::alloc::vec::Vec<CorporateActionItem>,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct SymbolChangeRequest {
+    #[prost(string, tag = "1")]
+    pub old_symbol: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub new_symbol: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub effective_date: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct SymbolChangeResponse {
+    #[prost(string, tag = "1")]
+    pub old_symbol: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub new_symbol: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub effective_date: ::prost::alloc::string::String,
+    /// 實際寫入的異動紀錄筆數；重複送出同一組變更時為 0
+    #[prost(uint64, tag = "4")]
+    pub recorded: u64,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct CagrRequest {
     #[prost(string, tag = "1")]
     pub date: ::prost::alloc::string::String,
@@ -70,6 +91,31 @@ pub struct CagrPeriodRequest {
     #[prost(string, tag = "1")]
     pub period: ::prost::alloc::string::String,
 }
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ReprocessRequest {
+    #[prost(string, tag = "1")]
+    pub target: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub from: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub to: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct EventReplayRequest {
+    #[prost(string, tag = "1")]
+    pub event_type: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub from: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub to: ::prost::alloc::string::String,
+    #[prost(int64, optional, tag = "5")]
+    pub after_sequence: ::core::option::Option<i64>,
+    /// 交回派發器重新觸發通知與同步等副作用；預設 false 只寫進 log。
+    #[prost(bool, tag = "6")]
+    pub redispatch: bool,
+}
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct ListJobsRequest {}
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
@@ -428,6 +474,35 @@ pub mod manual_backfill_service_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        pub async fn save_symbol_change(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SymbolChangeRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SymbolChangeResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/manual_backfill.ManualBackfillService/SaveSymbolChange",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "manual_backfill.ManualBackfillService",
+                        "SaveSymbolChange",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn start_cagr(
             &mut self,
             request: impl tonic::IntoRequest<super::CagrRequest>,
@@ -483,6 +558,64 @@ pub mod manual_backfill_service_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        pub async fn start_reprocess(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ReprocessRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::BackfillJobResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/manual_backfill.ManualBackfillService/StartReprocess",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "manual_backfill.ManualBackfillService",
+                        "StartReprocess",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn start_event_replay(
+            &mut self,
+            request: impl tonic::IntoRequest<super::EventReplayRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::BackfillJobResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/manual_backfill.ManualBackfillService/StartEventReplay",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "manual_backfill.ManualBackfillService",
+                        "StartEventReplay",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn list_jobs(
             &mut self,
             request: impl tonic::IntoRequest<super::ListJobsRequest>,
@@ -606,6 +739,13 @@ pub mod manual_backfill_service_server {
             tonic::Response<super::CorporateActionResponse>,
             tonic::Status,
         >;
+        async fn save_symbol_change(
+            &self,
+            request: tonic::Request<super::SymbolChangeRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SymbolChangeResponse>,
+            tonic::Status,
+        >;
         async fn start_cagr(
             &self,
             request: tonic::Request<super::CagrRequest>,
@@ -620,6 +760,20 @@ pub mod manual_backfill_service_server {
             tonic::Response<super::BackfillJobResponse>,
             tonic::Status,
         >;
+        async fn start_reprocess(
+            &self,
+            request: tonic::Request<super::ReprocessRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::BackfillJobResponse>,
+            tonic::Status,
+        >;
+        async fn start_event_replay(
+            &self,
+            request: tonic::Request<super::EventReplayRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::BackfillJobResponse>,
+            tonic::Status,
+        >;
         async fn list_jobs(
             &self,
             request: tonic::Request<super::ListJobsRequest>,
@@ -1117,6 +1271,55 @@ pub mod manual_backfill_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/manual_backfill.ManualBackfillService/SaveSymbolChange" => {
+                    #[allow(non_camel_case_types)]
+                    struct SaveSymbolChangeSvc<T: ManualBackfillService>(pub Arc<T>);
+                    impl<
+                        T: ManualBackfillService,
+                    > tonic::server::UnaryService<super::SymbolChangeRequest>
+                    for SaveSymbolChangeSvc<T> {
+                        type Response = super::SymbolChangeResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SymbolChangeRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ManualBackfillService>::save_symbol_change(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SaveSymbolChangeSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/manual_backfill.ManualBackfillService/StartCagr" => {
                     #[allow(non_camel_case_types)]
                     struct StartCagrSvc<T: ManualBackfillService>(pub Arc<T>);
@@ -1212,6 +1415,104 @@ pub mod manual_backfill_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/manual_backfill.ManualBackfillService/StartReprocess" => {
+                    #[allow(non_camel_case_types)]
+                    struct StartReprocessSvc<T: ManualBackfillService>(pub Arc<T>);
+                    impl<
+                        T: ManualBackfillService,
+                    > tonic::server::UnaryService<super::ReprocessRequest>
+                    for StartReprocessSvc<T> {
+                        type Response = super::BackfillJobResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ReprocessRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ManualBackfillService>::start_reprocess(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = StartReprocessSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/manual_backfill.ManualBackfillService/StartEventReplay" => {
+                    #[allow(non_camel_case_types)]
+                    struct StartEventReplaySvc<T: ManualBackfillService>(pub Arc<T>);
+                    impl<
+                        T: ManualBackfillService,
+                    > tonic::server::UnaryService<super::EventReplayRequest>
+                    for StartEventReplaySvc<T> {
+                        type Response = super::BackfillJobResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::EventReplayRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ManualBackfillService>::start_event_replay(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = StartEventReplaySvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/manual_backfill.ManualBackfillService/ListJobs" => {
                     #[allow(non_camel_case_types)]
                     struct ListJobsSvc<T: ManualBackfillService>(pub Arc<T>);
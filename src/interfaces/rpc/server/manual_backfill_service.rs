@@ -9,6 +9,7 @@ use rust_decimal::Decimal;
 use tonic::{Request, Response, Status};
 
 use crate::{
+    app::backfill::{reprocess::ReprocessTarget, symbol_change},
     domain::performance::{CagrPeriod, CorporateAction, CorporateActionRepository},
     infra::database::repository::corporate_action::PgCorporateActionRepository,
     interfaces::rpc::manual_backfill::{
@@ -21,11 +22,15 @@ use crate::{
         CorporateActionRequest,
         CorporateActionResponse,
         DailyQuotesRequest,
+        EventReplayRequest,
         GetJobRequest,
         ListJobsRequest,
         ListJobsResponse,
         QuoteHistoryRequest,
+        ReprocessRequest,
         SecurityCodeRequest,
+        SymbolChangeRequest,
+        SymbolChangeResponse,
         TaiwanStockIndexRequest,
         YearRequest,
         // 服務定義改名為 ManualBackfillService 後，tonic 產生的 trait 也相應更名
@@ -224,6 +229,33 @@ impl ManualBackfillService for ManualBackfillServiceImpl {
         }))
     }
 
+    /// 寫入人工確認的證券代號變更；代號與日期驗證通過後才觸碰資料庫。
+    async fn save_symbol_change(
+        &self,
+        req: Request<SymbolChangeRequest>,
+    ) -> Result<Response<SymbolChangeResponse>, Status> {
+        let req = req.into_inner();
+        let old_symbol = parse_grpc_security_code(req.old_symbol)?;
+        let new_symbol = parse_grpc_security_code(req.new_symbol)?;
+        let effective_date = parse_grpc_date(&req.effective_date)?;
+        if old_symbol == new_symbol {
+            return Err(Status::invalid_argument(
+                "new_symbol must differ from old_symbol",
+            ));
+        }
+
+        let recorded = symbol_change::record(&old_symbol, &new_symbol, effective_date)
+            .await
+            .map_err(|why| Status::internal(format!("failed to record symbol change: {why:#}")))?;
+
+        Ok(Response::new(SymbolChangeResponse {
+            old_symbol,
+            new_symbol,
+            effective_date: effective_date.to_string(),
+            recorded,
+        }))
+    }
+
     /// 建立指定基準日的 CAGR 重算 job。
     ///
     /// 日期留空時使用資料庫最新交易日，與 HTTP admin API 的語義一致。
@@ -263,6 +295,59 @@ impl ManualBackfillService for ManualBackfillServiceImpl {
         }))
     }
 
+    /// 建立以封存原始回應重跑防腐層映射的 job。
+    ///
+    /// 日期區間含首尾；缺少封存的日期會略過並計入 job 摘要。
+    async fn start_reprocess(
+        &self,
+        req: Request<ReprocessRequest>,
+    ) -> Result<Response<BackfillJobResponse>, Status> {
+        let req = req.into_inner();
+        let target = ReprocessTarget::from_code(req.target.trim()).ok_or_else(|| {
+            Status::invalid_argument(format!("unknown reprocess target: {}", req.target))
+        })?;
+        let from = parse_grpc_date(&req.from)?;
+        let to = parse_grpc_date(&req.to)?;
+        if from > to {
+            return Err(Status::invalid_argument("from must not be later than to"));
+        }
+        let job = web::backfill_admin::start_reprocess_job(target, from, to)
+            .await
+            .map_err(start_job_error_to_status)?;
+
+        Ok(Response::new(BackfillJobResponse {
+            job: Some(to_grpc_job(job)),
+        }))
+    }
+
+    /// 建立重播領域事件日誌的 job。
+    ///
+    /// 事件種類與證券代號留空表示不篩選；日期區間含首尾。預設只記錄事件，
+    /// `redispatch` 為 `true` 時才重新觸發通知與同步等副作用。
+    async fn start_event_replay(
+        &self,
+        req: Request<EventReplayRequest>,
+    ) -> Result<Response<BackfillJobResponse>, Status> {
+        let req = req.into_inner();
+        let from = parse_grpc_date(&req.from)?;
+        let to = parse_grpc_date(&req.to)?;
+        let query = web::backfill_admin::event_replay_query(
+            &req.event_type,
+            &req.symbol,
+            from,
+            to,
+            req.after_sequence,
+        )
+        .map_err(|why| Status::invalid_argument(why.to_string()))?;
+        let job = web::backfill_admin::start_event_replay_job(query, req.redispatch)
+            .await
+            .map_err(start_job_error_to_status)?;
+
+        Ok(Response::new(BackfillJobResponse {
+            job: Some(to_grpc_job(job)),
+        }))
+    }
+
     /// 列出目前程序內所有 manual backfill jobs。
     async fn list_jobs(
         &self,
@@ -412,6 +497,56 @@ mod tests {
         assert_eq!(err.code(), Code::InvalidArgument);
     }
 
+    /// 封存重跑 gRPC 應在建立 job 前拒絕未知種類與顛倒的日期區間。
+    #[tokio::test]
+    async fn start_reprocess_rejects_invalid_input_before_job_creation() {
+        let service = ManualBackfillServiceImpl::default();
+        for (target, from, to) in [
+            ("revenue", "2026-04-01", "2026-04-30"),
+            ("quote", "2026-04-30", "2026-04-01"),
+            ("quote", "2026-04", "2026-04-30"),
+        ] {
+            let err = service
+                .start_reprocess(Request::new(ReprocessRequest {
+                    target: target.to_string(),
+                    from: from.to_string(),
+                    to: to.to_string(),
+                }))
+                .await
+                .expect_err("無效輸入應失敗");
+            assert_eq!(err.code(), Code::InvalidArgument, "{target} {from}~{to}");
+        }
+    }
+
+    /// 事件重播 gRPC 應在建立 job 前拒絕未知種類、非法代號與顛倒的日期區間。
+    #[tokio::test]
+    async fn start_event_replay_rejects_invalid_input_before_job_creation() {
+        let service = ManualBackfillServiceImpl::default();
+        for (event_type, symbol, from, to) in [
+            ("Revenue", "", "2026-04-01", "2026-04-30"),
+            ("", "23;30", "2026-04-01", "2026-04-30"),
+            ("", "", "2026-04-30", "2026-04-01"),
+            ("", "", "2026-04", "2026-04-30"),
+        ] {
+            let err = service
+                .start_event_replay(Request::new(EventReplayRequest {
+                    event_type: event_type.to_string(),
+                    symbol: symbol.to_string(),
+                    from: from.to_string(),
+                    to: to.to_string(),
+                    after_sequence: None,
+                    redispatch: false,
+                }))
+                .await
+                .expect_err("無效輸入應失敗");
+            assert_eq!(
+                err.code(),
+                Code::InvalidArgument,
+                "{event_type} {symbol} {from}~{to}"
+            );
+        }
+    }
+
     /// Corporate Action gRPC 應在確認比例合法後才觸碰資料庫。
     #[tokio::test]
     async fn save_corporate_action_rejects_invalid_ratio_before_database_write() {
@@ -428,6 +563,31 @@ mod tests {
         assert_eq!(err.code(), Code::InvalidArgument);
     }
 
+    /// 代號變更 gRPC 應在寫入前擋下不合法或新舊相同的代號。
+    #[tokio::test]
+    async fn save_symbol_change_rejects_invalid_input_before_database_write() {
+        let service = ManualBackfillServiceImpl::default();
+        for (old_symbol, new_symbol, effective_date) in [
+            ("2330", "2330", "2026-04-01"),
+            ("23;30", "2331", "2026-04-01"),
+            ("2330", "2331", "2026-04"),
+        ] {
+            let err = service
+                .save_symbol_change(Request::new(SymbolChangeRequest {
+                    old_symbol: old_symbol.to_string(),
+                    new_symbol: new_symbol.to_string(),
+                    effective_date: effective_date.to_string(),
+                }))
+                .await
+                .expect_err("無效輸入應失敗");
+            assert_eq!(
+                err.code(),
+                Code::InvalidArgument,
+                "{old_symbol} → {new_symbol} {effective_date}"
+            );
+        }
+    }
+
     /// Quote History gRPC 應在建立 job 前驗證月份與代號格式。
     #[tokio::test]
     async fn start_quote_history_rejects_invalid_input_before_job_creation() {
@@ -136,7 +136,10 @@ async fn run_grpc_server(
         .add_service(ManualBackfillServiceServer::new(
             ManualBackfillServiceImpl::default(),
         ))
-        .add_service(StockServiceServer::new(StockServiceImpl::default()))
+        // 報價訂閱是長連線串流，需要關機訊號才會結束，否則 Tonic 無法排空既有 RPC。
+        .add_service(StockServiceServer::new(StockServiceImpl::new(
+            shutdown.clone(),
+        )))
         .serve_with_incoming_shutdown(incoming, crate::core::shutdown::wait_for_shutdown(shutdown))
         .await;
 
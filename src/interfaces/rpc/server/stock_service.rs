@@ -1,25 +1,34 @@
 //! Stock gRPC 服務實作。
 //!
-//! 提供股票相關的查詢服務，包括即時報價查詢與休市日清單查詢。
+//! 提供股票相關的查詢服務，包括即時報價查詢與訂閱、休市日清單查詢。
 
 use futures::future::join_all;
 use rust_decimal::prelude::ToPrimitive;
+use tokio::sync::{mpsc, watch};
+use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
 
 use crate::{
+    app::event::trace::quote_feed,
+    core::shutdown,
     domain::quote::repository::QuoteRepository,
-    infra::cache::SHARE,
+    infra::cache::{self, SHARE},
     infra::crawler::twse,
     infra::database::repository::quote::PgQuoteRepository,
     interfaces::rpc::stock::{
         HolidaySchedule,
         HolidayScheduleReply,
         HolidayScheduleRequest,
+        ListStocksReply,
+        ListStocksRequest,
+        RealtimeSnapshot,
         StockInfoReply,
         StockInfoRequest,
         StockQuotes,
         StockQuotesReply,
         StockQuotesRequest,
+        SubscribeQuotesRequest,
+        SyncStocksReply,
         // 服務定義改名為 StockService 後，tonic 產生的 trait 也相應更名
         stock_service_server::StockService,
     },
@@ -29,11 +38,28 @@ use crate::{
 ///
 /// 實作了 `StockService` trait，提供股票資訊、報價及休市日相關的 RPC 介面。
 #[derive(Default)]
-pub struct StockServiceImpl {}
+pub struct StockServiceImpl {
+    /// 關機訊號；收到後結束進行中的報價訂閱串流，讓 Tonic 能排空連線。
+    shutdown: Option<watch::Receiver<bool>>,
+}
+
+/// 每個報價訂閱串流的待送緩衝筆數；用戶端消化不及時由 `quote_feed` 合併更新。
+const SUBSCRIBER_BUFFER: usize = 64;
+
+impl StockServiceImpl {
+    /// 建立會在關機訊號送出時結束報價訂閱串流的服務。
+    pub fn new(shutdown: watch::Receiver<bool>) -> Self {
+        Self {
+            shutdown: Some(shutdown),
+        }
+    }
+}
 
 #[tonic::async_trait]
 // 實作 tonic 產生的 StockService trait
 impl StockService for StockServiceImpl {
+    type SubscribeQuotesStream = ReceiverStream<Result<RealtimeSnapshot, Status>>;
+
     /// 更新股票資訊。
     ///
     /// 此方法目前尚未實作。
@@ -46,6 +72,26 @@ impl StockService for StockServiceImpl {
         ))
     }
 
+    /// 批次同步證券主檔。
+    ///
+    /// 本服務是證券主檔的來源端，此方法由接收端（Go 服務）實作。
+    async fn sync_stocks(
+        &self,
+        _req: Request<tonic::Streaming<StockInfoRequest>>,
+    ) -> Result<Response<SyncStocksReply>, Status> {
+        Err(Status::unimplemented("sync_stocks is not implemented"))
+    }
+
+    /// 列出全部證券主檔。
+    ///
+    /// 此方法由接收端（Go 服務）實作，供來源端全量比對。
+    async fn list_stocks(
+        &self,
+        _req: Request<ListStocksRequest>,
+    ) -> Result<Response<ListStocksReply>, Status> {
+        Err(Status::unimplemented("list_stocks is not implemented"))
+    }
+
     /// 批次取得股票即時報價。
     ///
     /// 根據請求中的股票代碼列表，並行調用爬蟲取得最新報價。
@@ -75,6 +121,38 @@ impl StockService for StockServiceImpl {
         }))
     }
 
+    /// 訂閱即時報價。
+    ///
+    /// 先回傳訂閱代號目前的快照，之後每當背景任務更新 `SHARE` 快取，
+    /// 推送內容有變動的快照。用戶端斷線或服務關機時串流結束。
+    ///
+    /// # Errors
+    ///
+    /// 代號清單為空或超過上限時回傳 `InvalidArgument`。
+    async fn subscribe_quotes(
+        &self,
+        req: Request<SubscribeQuotesRequest>,
+    ) -> Result<Response<Self::SubscribeQuotesStream>, Status> {
+        let subscription = quote_feed::subscribe(req.into_inner().stock_symbols)
+            .map_err(|why| Status::invalid_argument(why.to_string()))?;
+        let (tx, rx) = mpsc::channel(SUBSCRIBER_BUFFER);
+        let stop = self.shutdown.clone();
+
+        tokio::spawn(quote_feed::forward(
+            subscription,
+            tx,
+            async move {
+                match stop {
+                    Some(receiver) => shutdown::wait_for_shutdown(receiver).await,
+                    None => std::future::pending().await,
+                }
+            },
+            |snapshot| Ok(RealtimeSnapshot::from(&snapshot)),
+        ));
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
     /// 取得指定年度的休市日清單。
     ///
     /// 呼叫 TWSE 爬蟲取得該年度的所有休市日期與原因。
@@ -140,6 +218,25 @@ async fn fetch_current_quotes_for_symbol(stock_symbol: &str) -> Option<StockQuot
     None
 }
 
+impl From<&cache::RealtimeSnapshot> for RealtimeSnapshot {
+    fn from(snapshot: &cache::RealtimeSnapshot) -> Self {
+        RealtimeSnapshot {
+            stock_symbol: snapshot.symbol.clone(),
+            name: snapshot.name.clone(),
+            source_site: snapshot.source_site.clone(),
+            price: snapshot.price.to_f64().unwrap_or_default(),
+            change: snapshot.change.to_f64().unwrap_or_default(),
+            change_range: snapshot.change_range.to_f64().unwrap_or_default(),
+            open: snapshot.open.to_f64().unwrap_or_default(),
+            high: snapshot.high.to_f64().unwrap_or_default(),
+            low: snapshot.low.to_f64().unwrap_or_default(),
+            last_close: snapshot.last_close.to_f64().unwrap_or_default(),
+            volume: snapshot.volume.to_f64().unwrap_or_default(),
+            updated_at: snapshot.updated_at.to_rfc3339(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use tokio::net::TcpListener;
@@ -205,4 +302,41 @@ mod tests {
             .expect("RPC Failed!");
         println!("message:{:#?}", resp.into_inner().holiday)
     }
+
+    /// 驗證訂閱代號清單為空時回傳 InvalidArgument。
+    #[tokio::test]
+    async fn test_subscribe_quotes_rejects_empty_symbols() {
+        let service = StockServiceImpl::default();
+
+        let status = service
+            .subscribe_quotes(Request::new(SubscribeQuotesRequest {
+                stock_symbols: vec![" ".to_string()],
+            }))
+            .await
+            .expect_err("empty symbols should be rejected");
+
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    /// 驗證關機訊號送出後訂閱串流結束，Tonic 才能排空連線。
+    #[tokio::test]
+    async fn test_subscribe_quotes_ends_on_shutdown() {
+        use tokio_stream::StreamExt;
+
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let service = StockServiceImpl::new(shutdown_rx);
+        let mut stream = service
+            .subscribe_quotes(Request::new(SubscribeQuotesRequest {
+                stock_symbols: vec!["T9999".to_string()],
+            }))
+            .await
+            .expect("subscription should start")
+            .into_inner();
+
+        shutdown_tx.send(true).unwrap();
+        let next = tokio::time::timeout(std::time::Duration::from_secs(1), stream.next())
+            .await
+            .expect("stream should end after shutdown");
+        assert!(next.is_none());
+    }
 }
@@ -20,6 +20,29 @@ pub struct StockInfoReply {
     pub message: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SyncStocksReply {
+    /// 成功寫入的筆數
+    #[prost(int32, tag = "1")]
+    pub accepted: i32,
+    /// 無法寫入的股票，其餘視為成功
+    #[prost(message, repeated, tag = "2")]
+    pub rejections: ::prost::alloc::vec::Vec<SyncStockRejection>,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct SyncStockRejection {
+    #[prost(string, tag = "1")]
+    pub stock_symbol: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub reason: ::prost::alloc::string::String,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ListStocksRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListStocksReply {
+    #[prost(message, repeated, tag = "1")]
+    pub stocks: ::prost::alloc::vec::Vec<StockInfoRequest>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct StockQuotes {
     #[prost(string, tag = "1")]
     pub stock_symbol: ::prost::alloc::string::String,
@@ -40,6 +63,42 @@ pub struct StockQuotesReply {
     #[prost(message, repeated, tag = "1")]
     pub stock_prices: ::prost::alloc::vec::Vec<StockQuotes>,
 }
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct SubscribeQuotesRequest {
+    /// 訂閱的股票代號，最多 200 檔
+    #[prost(string, repeated, tag = "1")]
+    pub stock_symbols: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RealtimeSnapshot {
+    #[prost(string, tag = "1")]
+    pub stock_symbol: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    /// 報價資料的採集站點
+    #[prost(string, tag = "3")]
+    pub source_site: ::prost::alloc::string::String,
+    #[prost(double, tag = "4")]
+    pub price: f64,
+    #[prost(double, tag = "5")]
+    pub change: f64,
+    #[prost(double, tag = "6")]
+    pub change_range: f64,
+    #[prost(double, tag = "7")]
+    pub open: f64,
+    #[prost(double, tag = "8")]
+    pub high: f64,
+    #[prost(double, tag = "9")]
+    pub low: f64,
+    #[prost(double, tag = "10")]
+    pub last_close: f64,
+    /// 成交量（張）
+    #[prost(double, tag = "11")]
+    pub volume: f64,
+    /// 快照寫入快取的時間（RFC 3339，UTC）
+    #[prost(string, tag = "12")]
+    pub updated_at: ::prost::alloc::string::String,
+}
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct HolidayScheduleRequest {
     #[prost(int32, tag = "1")]
@@ -68,7 +127,7 @@ pub mod stock_service_client {
     )]
     use tonic::codegen::*;
     use tonic::codegen::http::Uri;
-    /// / 股票資訊服務，提供股票資料更新、即時報價查詢與休市日查詢功能。
+    /// / 股票資訊服務，提供股票資料更新、即時報價查詢與訂閱、休市日查詢功能。
     #[derive(Debug, Clone)]
     pub struct StockServiceClient<T> {
         inner: tonic::client::Grpc<T>,
@@ -149,6 +208,8 @@ pub mod stock_service_client {
             self.inner = self.inner.max_encoding_message_size(limit);
             self
         }
+        /// 已由 SyncStocks 取代，保留供尚未升級的客戶端使用
+        #[deprecated]
         pub async fn update_stock_info(
             &mut self,
             request: impl tonic::IntoRequest<super::StockInfoRequest>,
@@ -170,6 +231,56 @@ pub mod stock_service_client {
                 .insert(GrpcMethod::new("stock.StockService", "UpdateStockInfo"));
             self.inner.unary(req, path, codec).await
         }
+        /// 以單一串流批次同步股票基本資料，串流結束後回傳處理結果
+        pub async fn sync_stocks(
+            &mut self,
+            request: impl tonic::IntoStreamingRequest<Message = super::StockInfoRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SyncStocksReply>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/stock.StockService/SyncStocks",
+            );
+            let mut req = request.into_streaming_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("stock.StockService", "SyncStocks"));
+            self.inner.client_streaming(req, path, codec).await
+        }
+        /// 取得接收端目前保存的全部股票基本資料，供定期全量比對
+        pub async fn list_stocks(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListStocksRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListStocksReply>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/stock.StockService/ListStocks",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("stock.StockService", "ListStocks"));
+            self.inner.unary(req, path, codec).await
+        }
         /// 取得目前的股價
         pub async fn fetch_current_stock_quotes(
             &mut self,
@@ -197,6 +308,31 @@ pub mod stock_service_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        /// 訂閱指定股票的即時報價，先回傳目前快照，之後每次快取更新推送有變動的快照
+        pub async fn subscribe_quotes(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SubscribeQuotesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::RealtimeSnapshot>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/stock.StockService/SubscribeQuotes",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("stock.StockService", "SubscribeQuotes"));
+            self.inner.server_streaming(req, path, codec).await
+        }
         /// 取得股市休市日
         pub async fn fetch_holiday_schedule(
             &mut self,
@@ -237,10 +373,21 @@ pub mod stock_service_server {
     /// Generated trait containing gRPC methods that should be implemented for use with StockServiceServer.
     #[async_trait]
     pub trait StockService: std::marker::Send + std::marker::Sync + 'static {
+        /// 已由 SyncStocks 取代，保留供尚未升級的客戶端使用
         async fn update_stock_info(
             &self,
             request: tonic::Request<super::StockInfoRequest>,
         ) -> std::result::Result<tonic::Response<super::StockInfoReply>, tonic::Status>;
+        /// 以單一串流批次同步股票基本資料，串流結束後回傳處理結果
+        async fn sync_stocks(
+            &self,
+            request: tonic::Request<tonic::Streaming<super::StockInfoRequest>>,
+        ) -> std::result::Result<tonic::Response<super::SyncStocksReply>, tonic::Status>;
+        /// 取得接收端目前保存的全部股票基本資料，供定期全量比對
+        async fn list_stocks(
+            &self,
+            request: tonic::Request<super::ListStocksRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListStocksReply>, tonic::Status>;
         /// 取得目前的股價
         async fn fetch_current_stock_quotes(
             &self,
@@ -249,6 +396,20 @@ pub mod stock_service_server {
             tonic::Response<super::StockQuotesReply>,
             tonic::Status,
         >;
+        /// Server streaming response type for the SubscribeQuotes method.
+        type SubscribeQuotesStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::RealtimeSnapshot, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        /// 訂閱指定股票的即時報價，先回傳目前快照，之後每次快取更新推送有變動的快照
+        async fn subscribe_quotes(
+            &self,
+            request: tonic::Request<super::SubscribeQuotesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::SubscribeQuotesStream>,
+            tonic::Status,
+        >;
         /// 取得股市休市日
         async fn fetch_holiday_schedule(
             &self,
@@ -258,7 +419,7 @@ pub mod stock_service_server {
             tonic::Status,
         >;
     }
-    /// / 股票資訊服務，提供股票資料更新、即時報價查詢與休市日查詢功能。
+    /// / 股票資訊服務，提供股票資料更新、即時報價查詢與訂閱、休市日查詢功能。
     #[derive(Debug)]
     pub struct StockServiceServer<T> {
         inner: Arc<T>,
@@ -381,6 +542,98 @@ pub mod stock_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/stock.StockService/SyncStocks" => {
+                    #[allow(non_camel_case_types)]
+                    struct SyncStocksSvc<T: StockService>(pub Arc<T>);
+                    impl<
+                        T: StockService,
+                    > tonic::server::ClientStreamingService<super::StockInfoRequest>
+                    for SyncStocksSvc<T> {
+                        type Response = super::SyncStocksReply;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                tonic::Streaming<super::StockInfoRequest>,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StockService>::sync_stocks(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SyncStocksSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.client_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/stock.StockService/ListStocks" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListStocksSvc<T: StockService>(pub Arc<T>);
+                    impl<
+                        T: StockService,
+                    > tonic::server::UnaryService<super::ListStocksRequest>
+                    for ListStocksSvc<T> {
+                        type Response = super::ListStocksReply;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListStocksRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StockService>::list_stocks(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListStocksSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/stock.StockService/FetchCurrentStockQuotes" => {
                     #[allow(non_camel_case_types)]
                     struct FetchCurrentStockQuotesSvc<T: StockService>(pub Arc<T>);
@@ -430,6 +683,53 @@ pub mod stock_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/stock.StockService/SubscribeQuotes" => {
+                    #[allow(non_camel_case_types)]
+                    struct SubscribeQuotesSvc<T: StockService>(pub Arc<T>);
+                    impl<
+                        T: StockService,
+                    > tonic::server::ServerStreamingService<
+                        super::SubscribeQuotesRequest,
+                    > for SubscribeQuotesSvc<T> {
+                        type Response = super::RealtimeSnapshot;
+                        type ResponseStream = T::SubscribeQuotesStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SubscribeQuotesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StockService>::subscribe_quotes(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SubscribeQuotesSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/stock.StockService/FetchHolidaySchedule" => {
                     #[allow(non_camel_case_types)]
                     struct FetchHolidayScheduleSvc<T: StockService>(pub Arc<T>);
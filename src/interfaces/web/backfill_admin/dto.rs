@@ -51,6 +51,38 @@ pub(super) struct QuoteHistoryRequest {
     pub(super) to: String,
 }
 
+/// 封存原始回應重跑的 HTTP request body。
+#[derive(Debug, Deserialize)]
+pub(super) struct ReprocessRequest {
+    /// 資料種類代碼，例如 `quote`（上市櫃收盤報價）。
+    pub(super) target: String,
+    /// 起始日期，格式 `YYYY-MM-DD`（含）。
+    pub(super) from: String,
+    /// 結束日期，格式 `YYYY-MM-DD`（含）。
+    pub(super) to: String,
+}
+
+/// 領域事件重播的 HTTP request body。
+#[derive(Debug, Deserialize)]
+pub(super) struct EventReplayRequest {
+    /// 事件種類，例如 `StockRegistered`；留空表示全部種類。
+    #[serde(default)]
+    pub(super) event_type: String,
+    /// 證券代號；留空表示不限。
+    #[serde(default)]
+    pub(super) symbol: String,
+    /// 事件發生的起始日期，格式 `YYYY-MM-DD`（含）。
+    pub(super) from: String,
+    /// 事件發生的結束日期，格式 `YYYY-MM-DD`（含）。
+    pub(super) to: String,
+    /// 只重播序號大於此值的事件，用於接續先前中斷的重播。
+    #[serde(default)]
+    pub(super) after_sequence: Option<i64>,
+    /// 交回派發器重新觸發通知與同步等副作用；預設 `false` 只寫進 log。
+    #[serde(default)]
+    pub(super) redispatch: bool,
+}
+
 /// CAGR 重算的 HTTP request body。
 #[derive(Debug, Deserialize)]
 pub(super) struct CagrRequest {
@@ -80,6 +112,30 @@ pub(super) struct CorporateActionRequest {
     pub(super) note: String,
 }
 
+/// 證券代號變更的 HTTP request body。
+#[derive(Debug, Deserialize)]
+pub(super) struct SymbolChangeRequest {
+    /// 變更前的證券代號。
+    pub(super) old_symbol: String,
+    /// 變更後的證券代號。
+    pub(super) new_symbol: String,
+    /// 新代號開始交易的日期，格式 `YYYY-MM-DD`。
+    pub(super) effective_date: String,
+}
+
+/// 證券代號變更寫入成功時的 HTTP response body。
+#[derive(Debug, Serialize)]
+pub(super) struct SymbolChangeResponse {
+    /// 變更前的證券代號。
+    pub(super) old_symbol: String,
+    /// 變更後的證券代號。
+    pub(super) new_symbol: String,
+    /// 生效日。
+    pub(super) effective_date: String,
+    /// 實際寫入的異動紀錄筆數；重複送出同一組變更時為 0。
+    pub(super) recorded: u64,
+}
+
 /// 公司行動登錄成功時的 HTTP response body。
 #[derive(Debug, Serialize)]
 pub(super) struct CorporateActionResponse {
@@ -100,6 +156,130 @@ pub(super) struct CorporateActionItem {
     pub(super) note: String,
 }
 
+/// 待確認的單筆公司行動建議。
+#[derive(Debug, Serialize)]
+pub(super) struct CorporateActionProposalItem {
+    /// 流水號；確認或駁回時以此指定。
+    pub(super) serial: i64,
+    /// 股票代號。
+    pub(super) stock_symbol: String,
+    /// 建議來源，例如 `twse`、`tpex`、`price_gap`、`mops`。
+    pub(super) source: String,
+    /// 來源內的識別。
+    pub(super) reference: String,
+    /// 事件類型：`split` 或 `capital_reduction`。
+    pub(super) action_type: String,
+    /// 預估生效日；來源未揭露時為 `null`。
+    pub(super) effective_date: Option<String>,
+    /// 預估股數變動比例；來源未揭露時為 `null`。
+    pub(super) share_ratio: Option<String>,
+    /// 備註。
+    pub(super) note: String,
+    /// 建立時間（RFC 3339）。
+    pub(super) created_at: String,
+}
+
+/// 確認公司行動建議的 HTTP request body。
+///
+/// 生效日與比例以確認者填寫的值為準，可修正來源推估的數字。
+#[derive(Debug, Deserialize)]
+pub(super) struct ConfirmCorporateActionProposalRequest {
+    /// 生效日，格式 `YYYY-MM-DD`。
+    pub(super) effective_date: String,
+    /// 股數變動比例：持有 1 股在事件後變成幾股。
+    pub(super) share_ratio: String,
+    /// 備註。
+    #[serde(default)]
+    pub(super) note: String,
+}
+
+/// 確認公司行動建議成功時的 HTTP response body。
+#[derive(Debug, Serialize)]
+pub(super) struct ConfirmCorporateActionProposalResponse {
+    /// 股票代號。
+    pub(super) stock_symbol: String,
+    /// 寫入的公司行動。
+    pub(super) action: CorporateActionItem,
+    /// 自動建立的 CAGR 重算 job；暫時無法建立時為 `null`，見 `cagr_queued`。
+    pub(super) cagr_job: Option<BackfillJob>,
+    /// 已有 CAGR job 執行中等原因暫時無法建立時為 `true`，待可建立時自動補跑。
+    pub(super) cagr_queued: bool,
+}
+
+/// 駁回公司行動建議成功時的 HTTP response body。
+#[derive(Debug, Serialize)]
+pub(super) struct RejectCorporateActionProposalResponse {
+    /// 已駁回的建議流水號。
+    pub(super) serial: i64,
+}
+
+/// 近期公開申購的單筆資料。
+#[derive(Debug, Serialize)]
+pub(super) struct PublicSubscriptionItem {
+    /// 股票代號。
+    pub(super) stock_symbol: String,
+    /// 股票名稱。
+    pub(super) stock_name: String,
+    /// 發行市場。
+    pub(super) market: String,
+    /// 申購開始日。
+    pub(super) offering_start_date: String,
+    /// 申購截止日。
+    pub(super) offering_end_date: String,
+    /// 抽籤日；尚未公布時為 `null`。
+    pub(super) drawing_date: Option<String>,
+    /// 承銷價；尚未公布時為 `null`。
+    pub(super) offering_price: Option<String>,
+    /// 最新收盤價；尚未掛牌時為 `null`。
+    pub(super) market_price: Option<String>,
+    /// 中籤一張的預期獲利；無法估算時為 `null`。
+    pub(super) expected_profit_per_lot: Option<String>,
+    /// 今日是否仍可申購。
+    pub(super) is_open: bool,
+    /// 已登記的參與紀錄。
+    pub(super) entries: Vec<SubscriptionEntryItem>,
+}
+
+/// 成員參與公開申購的單筆紀錄。
+#[derive(Debug, Serialize)]
+pub(super) struct SubscriptionEntryItem {
+    /// 流水號；記錄抽籤結果時以此指定。
+    pub(super) serial: i64,
+    /// 股票代號。
+    pub(super) stock_symbol: String,
+    /// 申購開始日。
+    pub(super) offering_start_date: String,
+    /// 成員編號。
+    pub(super) member_id: i64,
+    /// 是否中籤；尚未抽籤時為 `null`。
+    pub(super) won: Option<bool>,
+    /// 中籤後的賣出價。
+    pub(super) sell_price: Option<String>,
+    /// 已實現損益；尚未抽籤或中籤未賣出時為 `null`。
+    pub(super) realized_profit: Option<String>,
+}
+
+/// 登記參與公開申購的 HTTP request body。
+#[derive(Debug, Deserialize)]
+pub(super) struct EnterPublicSubscriptionRequest {
+    /// 股票代號。
+    pub(super) stock_symbol: String,
+    /// 申購開始日，格式 `YYYY-MM-DD`。
+    pub(super) offering_start_date: String,
+    /// 成員編號。
+    pub(super) member_id: i64,
+}
+
+/// 記錄抽籤結果的 HTTP request body。
+#[derive(Debug, Deserialize)]
+pub(super) struct RecordSubscriptionResultRequest {
+    /// 是否中籤。
+    pub(super) won: bool,
+    /// 中籤後的賣出價；留空表示尚未賣出。
+    #[serde(default)]
+    pub(super) sell_price: String,
+}
+
 /// 建立 job 成功時的 HTTP response body。
 #[derive(Debug, Serialize)]
 pub(super) struct StartJobResponse {
@@ -336,6 +516,46 @@ pub(super) const INDEX_HTML: &str = r##"<!doctype html>
         <button type="submit">Start</button>
         <div class="toast">Fills gaps only; safe to re-run. All ETFs over several years can take hours.</div>
       </form>
+      <form class="panel" data-endpoint="/api/manual-backfill/reprocess">
+        <h2>Reprocess Archive</h2>
+        <label for="reprocess-target">Target</label>
+        <select id="reprocess-target" name="target" required>
+          <option value="quote" selected>quote (twse::quote + tpex::quote)</option>
+          <option value="institutional_investor">institutional_investor (T86 + TPEx)</option>
+          <option value="margin_trading">margin_trading (MI_MARGN + TPEx)</option>
+          <option value="shareholding_distribution">shareholding_distribution (TDCC)</option>
+          <option value="derivatives">derivatives (TAIFEX futures + P/C ratio)</option>
+        </select>
+        <label for="reprocess-from">From date</label>
+        <input id="reprocess-from" name="from" type="date" required>
+        <label for="reprocess-to">To date</label>
+        <input id="reprocess-to" name="to" type="date" required>
+        <button type="submit">Start</button>
+        <div class="toast">Re-runs the mappers over archived raw payloads; no upstream requests. Dates without an archive are skipped.</div>
+      </form>
+      <form class="panel" data-endpoint="/api/manual-backfill/event-replay">
+        <h2>Event Replay</h2>
+        <label for="event-replay-type">Event type (blank = all)</label>
+        <select id="event-replay-type" name="event_type">
+          <option value="" selected>all</option>
+          <option value="StockRegistered">StockRegistered</option>
+          <option value="StockIdentityChanged">StockIdentityChanged</option>
+          <option value="NetAssetValueUpdated">NetAssetValueUpdated</option>
+          <option value="StockIndexUpdated">StockIndexUpdated</option>
+          <option value="DerivativesMarketUpdated">DerivativesMarketUpdated</option>
+          <option value="MoneyFlowRecalculated">MoneyFlowRecalculated</option>
+          <option value="ExDividendReminderTriggered">ExDividendReminderTriggered</option>
+        </select>
+        <label for="event-replay-symbol">Stock symbol (blank = all)</label>
+        <input id="event-replay-symbol" name="symbol" inputmode="latin" placeholder="2330">
+        <label for="event-replay-from">From date</label>
+        <input id="event-replay-from" name="from" type="date" required>
+        <label for="event-replay-to">To date</label>
+        <input id="event-replay-to" name="to" type="date" required>
+        <label><input name="redispatch" type="checkbox"> Re-send notifications and sync (side effects)</label>
+        <button type="submit">Start</button>
+        <div class="toast">Logs matching events only; tick the box to re-run notifications and sync side effects. Replayed events are not logged or published again.</div>
+      </form>
       <form class="panel" data-endpoint="/api/manual-backfill/corporate-action">
         <h2>Corporate Action</h2>
         <label for="ca-symbol">Stock symbol</label>
@@ -347,7 +567,18 @@ pub(super) const INDEX_HTML: &str = r##"<!doctype html>
         <label for="ca-note">Note</label>
         <input id="ca-note" name="note" placeholder="1:4 split">
         <button type="submit">Save</button>
-        <div class="toast">Recalculate CAGR afterwards for the change to take effect.</div>
+        <div class="toast">Recalculate CAGR afterwards for the change to take effect. Detected actions are listed under proposals below.</div>
+      </form>
+      <form class="panel" data-endpoint="/api/manual-backfill/symbol-change">
+        <h2>Symbol Change</h2>
+        <label for="sc-old">Old symbol</label>
+        <input id="sc-old" name="old_symbol" inputmode="latin" placeholder="2330" required>
+        <label for="sc-new">New symbol</label>
+        <input id="sc-new" name="new_symbol" inputmode="latin" placeholder="2331" required>
+        <label for="sc-date">Effective date</label>
+        <input id="sc-date" name="effective_date" type="date" required>
+        <button type="submit">Save</button>
+        <div class="toast">Records the change in the security master: the old symbol stops and the new one starts trading with the same name, market and industry.</div>
       </form>
       <form class="panel" data-endpoint="/api/manual-backfill/cagr">
         <h2>CAGR Recalculation</h2>
@@ -374,6 +605,42 @@ pub(super) const INDEX_HTML: &str = r##"<!doctype html>
         <div class="toast">Fills this period on base dates that already have other periods.</div>
       </form>
     </section>
+    <section class="jobs" aria-label="Corporate action proposals">
+      <table>
+        <thead>
+          <tr>
+            <th style="width: 10%">Symbol</th>
+            <th style="width: 18%">Source</th>
+            <th style="width: 16%">Effective date</th>
+            <th style="width: 12%">Share ratio</th>
+            <th>Note</th>
+            <th style="width: 16%">Review</th>
+          </tr>
+        </thead>
+        <tbody id="proposals-body">
+          <tr><td colspan="6">No pending corporate action proposals.</td></tr>
+        </tbody>
+      </table>
+      <div id="proposals-toast" class="toast"></div>
+    </section>
+    <section class="jobs" aria-label="Public subscriptions">
+      <table>
+        <thead>
+          <tr>
+            <th style="width: 16%">Subscription</th>
+            <th style="width: 18%">Offering / drawing</th>
+            <th style="width: 16%">Offering / market price</th>
+            <th style="width: 12%">Expected profit per lot</th>
+            <th>Entries</th>
+            <th style="width: 14%">Enter</th>
+          </tr>
+        </thead>
+        <tbody id="subscriptions-body">
+          <tr><td colspan="6">No recent public subscriptions.</td></tr>
+        </tbody>
+      </table>
+      <div id="subscriptions-toast" class="toast"></div>
+    </section>
     <section class="jobs" aria-label="Backfill jobs">
       <table>
         <thead>
@@ -403,7 +670,8 @@ pub(super) const INDEX_HTML: &str = r##"<!doctype html>
         const data = {};
         for (const [key, value] of new FormData(form).entries()) {
           const field = form.elements.namedItem(key);
-          data[key] = field && field.type === "number" ? Number(value) : value;
+          if (field && field.type === "checkbox") data[key] = field.checked;
+          else data[key] = field && field.type === "number" ? Number(value) : value;
         }
         button.disabled = true;
         toast.textContent = "Starting...";
@@ -416,7 +684,7 @@ pub(super) const INDEX_HTML: &str = r##"<!doctype html>
           });
           const body = await response.json();
           if (!response.ok) throw new Error(body.error || "request failed");
-          toast.textContent = `Started job ${body.job.id}`;
+          toast.textContent = body.job ? `Started job ${body.job.id}` : "Saved";
           await refreshJobs();
         } catch (error) {
           toast.textContent = error.message;
@@ -466,7 +734,160 @@ pub(super) const INDEX_HTML: &str = r##"<!doctype html>
       }[char]));
     }
 
+    const proposalsBody = document.querySelector("#proposals-body");
+    const proposalsToast = document.querySelector("#proposals-toast");
+
+    async function refreshProposals() {
+      try {
+        const response = await fetch("/api/manual-backfill/corporate-action-proposals");
+        const body = await response.json();
+        if (!response.ok) throw new Error(body.error || "request failed");
+        renderProposals(body);
+      } catch (error) {
+        proposalsToast.textContent = error.message;
+      }
+    }
+
+    function renderProposals(proposals) {
+      if (!proposals.length) {
+        proposalsBody.innerHTML = '<tr><td colspan="6">No pending corporate action proposals.</td></tr>';
+        return;
+      }
+      proposalsBody.replaceChildren(...proposals.map((proposal) => {
+        const row = document.createElement("tr");
+        row.innerHTML = `
+          <td>${escapeHtml(proposal.stock_symbol)}</td>
+          <td>${escapeHtml(proposal.source)}<br>${escapeHtml(proposal.reference)}</td>
+          <td><input name="effective_date" type="date" value="${escapeHtml(proposal.effective_date || "")}"></td>
+          <td><input name="share_ratio" inputmode="decimal" value="${escapeHtml(proposal.share_ratio || "")}"></td>
+          <td><input name="note" value="${escapeHtml(proposal.note)}"></td>
+          <td>
+            <button type="button" data-action="confirm">Confirm</button>
+            <button type="button" data-action="reject">Reject</button>
+          </td>
+        `;
+        row.querySelectorAll("button").forEach((button) => {
+          button.addEventListener("click", () => reviewProposal(proposal.serial, button.dataset.action, row));
+        });
+        return row;
+      }));
+    }
+
+    async function reviewProposal(serial, action, row) {
+      const data = {};
+      row.querySelectorAll("input").forEach((input) => { data[input.name] = input.value; });
+      row.querySelectorAll("button").forEach((button) => { button.disabled = true; });
+      try {
+        const response = await fetch(`/api/manual-backfill/corporate-action-proposals/${serial}/${action}`, {
+          method: "POST",
+          headers: { "content-type": "application/json" },
+          body: JSON.stringify(data)
+        });
+        const body = await response.json();
+        if (!response.ok) throw new Error(body.error || "request failed");
+        if (action === "reject") {
+          proposalsToast.textContent = `Rejected proposal ${serial}`;
+        } else if (body.cagr_job) {
+          proposalsToast.textContent = `Confirmed ${body.stock_symbol}; started CAGR job ${body.cagr_job.id}`;
+        } else {
+          proposalsToast.textContent = `Confirmed ${body.stock_symbol}; CAGR recalculation queued until the running job finishes`;
+        }
+        await Promise.all([refreshProposals(), refreshJobs()]);
+      } catch (error) {
+        proposalsToast.textContent = error.message;
+        row.querySelectorAll("button").forEach((button) => { button.disabled = false; });
+      }
+    }
+
+    const subscriptionsBody = document.querySelector("#subscriptions-body");
+    const subscriptionsToast = document.querySelector("#subscriptions-toast");
+
+    async function refreshSubscriptions() {
+      try {
+        const response = await fetch("/api/manual-backfill/public-subscriptions");
+        const body = await response.json();
+        if (!response.ok) throw new Error(body.error || "request failed");
+        renderSubscriptions(body);
+      } catch (error) {
+        subscriptionsToast.textContent = error.message;
+      }
+    }
+
+    function renderSubscriptions(subscriptions) {
+      if (!subscriptions.length) {
+        subscriptionsBody.innerHTML = '<tr><td colspan="6">No recent public subscriptions.</td></tr>';
+        return;
+      }
+      subscriptionsBody.replaceChildren(...subscriptions.map((subscription) => {
+        const row = document.createElement("tr");
+        const entries = subscription.entries.map((entry) => `
+          <div data-serial="${entry.serial}">
+            Member ${escapeHtml(entry.member_id)}:
+            <select name="won">
+              <option value="" ${entry.won === null ? "selected" : ""}>pending</option>
+              <option value="true" ${entry.won === true ? "selected" : ""}>won</option>
+              <option value="false" ${entry.won === false ? "selected" : ""}>lost</option>
+            </select>
+            <input name="sell_price" inputmode="decimal" placeholder="sell price" value="${escapeHtml(entry.sell_price || "")}">
+            <button type="button">Record</button>
+            ${entry.realized_profit === null ? "" : `P/L ${escapeHtml(entry.realized_profit)}`}
+          </div>
+        `).join("");
+        row.innerHTML = `
+          <td>${escapeHtml(subscription.stock_symbol)} ${escapeHtml(subscription.stock_name)}<br>${escapeHtml(subscription.market)}${subscription.is_open ? " (open)" : ""}</td>
+          <td>${escapeHtml(subscription.offering_start_date)} ~ ${escapeHtml(subscription.offering_end_date)}<br>${escapeHtml(subscription.drawing_date || "-")}</td>
+          <td>${escapeHtml(subscription.offering_price || "-")} / ${escapeHtml(subscription.market_price || "-")}</td>
+          <td>${escapeHtml(subscription.expected_profit_per_lot || "-")}</td>
+          <td>${entries}</td>
+          <td>
+            <input name="member_id" type="number" min="1" step="1" placeholder="member id">
+            <button type="button" data-action="enter">Enter</button>
+          </td>
+        `;
+        row.querySelector('button[data-action="enter"]').addEventListener("click", () => {
+          const memberId = Number(row.querySelector('input[name="member_id"]').value);
+          postSubscription("/api/manual-backfill/public-subscriptions/entries", {
+            stock_symbol: subscription.stock_symbol,
+            offering_start_date: subscription.offering_start_date,
+            member_id: memberId
+          });
+        });
+        row.querySelectorAll("div[data-serial]").forEach((entry) => {
+          entry.querySelector("button").addEventListener("click", () => {
+            const won = entry.querySelector('select[name="won"]').value;
+            if (!won) {
+              subscriptionsToast.textContent = "Choose won or lost before recording";
+              return;
+            }
+            postSubscription(`/api/manual-backfill/public-subscriptions/entries/${entry.dataset.serial}/result`, {
+              won: won === "true",
+              sell_price: entry.querySelector('input[name="sell_price"]').value
+            });
+          });
+        });
+        return row;
+      }));
+    }
+
+    async function postSubscription(endpoint, data) {
+      try {
+        const response = await fetch(endpoint, {
+          method: "POST",
+          headers: { "content-type": "application/json" },
+          body: JSON.stringify(data)
+        });
+        const body = await response.json();
+        if (!response.ok) throw new Error(body.error || "request failed");
+        subscriptionsToast.textContent = `Saved entry ${body.serial}`;
+        await refreshSubscriptions();
+      } catch (error) {
+        subscriptionsToast.textContent = error.message;
+      }
+    }
+
     refreshJobs();
+    refreshProposals();
+    refreshSubscriptions();
     setInterval(refreshJobs, 3000);
   </script>
 </body>
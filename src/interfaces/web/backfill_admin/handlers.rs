@@ -6,19 +6,27 @@ use axum::{
 };
 
 use super::dto::{
-    CagrPeriodRequest, CagrRequest, ClosingAggregateRequest, CorporateActionItem,
-    CorporateActionRequest, CorporateActionResponse, DailyQuotesRequest, ErrorResponse, INDEX_HTML,
-    QuoteHistoryRequest, SecurityCodeRequest, StartJobResponse, TaiwanStockIndexRequest,
+    CagrPeriodRequest, CagrRequest, ClosingAggregateRequest, ConfirmCorporateActionProposalRequest,
+    ConfirmCorporateActionProposalResponse, CorporateActionItem, CorporateActionProposalItem,
+    CorporateActionRequest, CorporateActionResponse, DailyQuotesRequest,
+    EnterPublicSubscriptionRequest, ErrorResponse, EventReplayRequest, INDEX_HTML,
+    PublicSubscriptionItem, QuoteHistoryRequest, RecordSubscriptionResultRequest,
+    RejectCorporateActionProposalResponse, ReprocessRequest, SecurityCodeRequest, StartJobResponse,
+    SubscriptionEntryItem, SymbolChangeRequest, SymbolChangeResponse, TaiwanStockIndexRequest,
     YearRequest,
 };
 use super::job_runner::{
-    parse_request_date, parse_request_month, parse_request_period, parse_request_security_code,
-    parse_request_share_ratio, parse_request_symbol_list, start_cagr_job, start_cagr_period_job,
-    start_closing_aggregate_job, start_daily_quotes_job, start_historical_dividends_job,
-    start_job_error_response, start_multiple_dividend_historical_dividends_job,
-    start_quote_history_job, start_received_dividend_records_job, start_taiwan_stock_index_job,
+    CagrRecalculation, event_replay_query, parse_request_date, parse_request_month,
+    parse_request_period, parse_request_reprocess_target, parse_request_security_code,
+    parse_request_sell_price, parse_request_share_ratio, parse_request_symbol_list, start_cagr_job,
+    start_cagr_period_job, start_closing_aggregate_job, start_daily_quotes_job,
+    start_event_replay_job, start_historical_dividends_job, start_job_error_response,
+    start_multiple_dividend_historical_dividends_job, start_or_queue_cagr_job,
+    start_quote_history_job, start_received_dividend_records_job, start_reprocess_job,
+    start_taiwan_stock_index_job,
 };
 use super::state::{BACKFILL_STATE, BackfillWebState, get_backfill_job, list_backfill_jobs};
+use crate::app::backfill::symbol_change;
 
 /// 建立 backfill admin 的 Web UI 與 JSON API router。
 ///
@@ -27,6 +35,11 @@ use super::state::{BACKFILL_STATE, BackfillWebState, get_backfill_job, list_back
 /// - `GET /api/manual-backfill/jobs`：列出所有 job。
 /// - `GET /api/manual-backfill/jobs/{id}`：查詢單一 job。
 /// - `POST /api/manual-backfill/*`：建立不同類型的回補 job。
+/// - `POST /api/manual-backfill/symbol-change`：寫入人工確認的證券代號變更。
+/// - `GET /api/manual-backfill/corporate-action-proposals`：列出待確認的公司行動建議，
+///   `POST .../{serial}/confirm`、`POST .../{serial}/reject` 進行審核。
+/// - `GET /api/manual-backfill/public-subscriptions`：列出近期公開申購與參與紀錄，
+///   `POST .../entries` 登記參與、`POST .../entries/{serial}/result` 記錄抽籤結果。
 pub fn router() -> Router {
     Router::new()
         .route("/", get(|| async { Redirect::to("/manual-backfill") }))
@@ -61,10 +74,43 @@ pub fn router() -> Router {
             "/api/manual-backfill/quote-history",
             post(start_quote_history),
         )
+        .route("/api/manual-backfill/reprocess", post(start_reprocess))
+        .route(
+            "/api/manual-backfill/event-replay",
+            post(start_event_replay),
+        )
         .route(
             "/api/manual-backfill/corporate-action",
             post(save_corporate_action),
         )
+        .route(
+            "/api/manual-backfill/symbol-change",
+            post(save_symbol_change),
+        )
+        .route(
+            "/api/manual-backfill/corporate-action-proposals",
+            get(list_corporate_action_proposals),
+        )
+        .route(
+            "/api/manual-backfill/corporate-action-proposals/{serial}/confirm",
+            post(confirm_corporate_action_proposal),
+        )
+        .route(
+            "/api/manual-backfill/corporate-action-proposals/{serial}/reject",
+            post(reject_corporate_action_proposal),
+        )
+        .route(
+            "/api/manual-backfill/public-subscriptions",
+            get(list_public_subscriptions),
+        )
+        .route(
+            "/api/manual-backfill/public-subscriptions/entries",
+            post(enter_public_subscription),
+        )
+        .route(
+            "/api/manual-backfill/public-subscriptions/entries/{serial}/result",
+            post(record_subscription_result),
+        )
         .route("/api/manual-backfill/cagr", post(start_cagr))
         .route("/api/manual-backfill/cagr-period", post(start_cagr_period))
         .with_state(BACKFILL_STATE.clone())
@@ -141,6 +187,72 @@ async fn start_quote_history(
     }
 }
 
+/// 建立封存原始回應重跑 job 的 HTTP handler。
+async fn start_reprocess(
+    State(_state): State<BackfillWebState>,
+    Json(req): Json<ReprocessRequest>,
+) -> impl IntoResponse {
+    let target = match parse_request_reprocess_target(&req.target) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    let from = match parse_request_date(&req.from) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    let to = match parse_request_date(&req.to) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    if from > to {
+        return (
+            axum::http::StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "from must not be later than to".to_string(),
+            }),
+        )
+            .into_response();
+    }
+
+    match start_reprocess_job(target, from, to).await {
+        Ok(job) => Json(StartJobResponse { job }).into_response(),
+        Err(err) => start_job_error_response(err),
+    }
+}
+
+/// 建立領域事件重播 job 的 HTTP handler。
+async fn start_event_replay(
+    State(_state): State<BackfillWebState>,
+    Json(req): Json<EventReplayRequest>,
+) -> impl IntoResponse {
+    let from = match parse_request_date(&req.from) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    let to = match parse_request_date(&req.to) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    let query = match event_replay_query(&req.event_type, &req.symbol, from, to, req.after_sequence)
+    {
+        Ok(value) => value,
+        Err(why) => {
+            return (
+                axum::http::StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: why.to_string(),
+                }),
+            )
+                .into_response();
+        }
+    };
+
+    match start_event_replay_job(query, req.redispatch).await {
+        Ok(job) => Json(StartJobResponse { job }).into_response(),
+        Err(err) => start_job_error_response(err),
+    }
+}
+
 /// 登錄公司行動（分割／減資）的 HTTP handler。
 ///
 /// 這不是背景 job：寫入一筆對照資料是瞬間完成的，包成 job 只會讓使用者
@@ -206,6 +318,369 @@ async fn save_corporate_action(
     }
 }
 
+/// 寫入證券代號變更的 HTTP handler。
+///
+/// 與公司行動登錄相同，寫入是瞬間完成的，不包成 job。
+async fn save_symbol_change(
+    State(_state): State<BackfillWebState>,
+    Json(req): Json<SymbolChangeRequest>,
+) -> impl IntoResponse {
+    let old_symbol = match parse_request_security_code(req.old_symbol) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    let new_symbol = match parse_request_security_code(req.new_symbol) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    let effective_date = match parse_request_date(&req.effective_date) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    if old_symbol == new_symbol {
+        return (
+            axum::http::StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "new_symbol must differ from old_symbol".to_string(),
+            }),
+        )
+            .into_response();
+    }
+
+    match symbol_change::record(&old_symbol, &new_symbol, effective_date).await {
+        Ok(recorded) => Json(SymbolChangeResponse {
+            old_symbol,
+            new_symbol,
+            effective_date: effective_date.to_string(),
+            recorded,
+        })
+        .into_response(),
+        Err(why) => (
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("failed to record symbol change: {why:#}"),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// 列出待確認公司行動建議的 HTTP handler。
+async fn list_corporate_action_proposals(
+    State(_state): State<BackfillWebState>,
+) -> impl IntoResponse {
+    use crate::domain::performance::repository::CorporateActionProposalRepository;
+
+    let repository =
+        crate::infra::database::repository::corporate_action::PgCorporateActionProposalRepository::new();
+    match repository.fetch_pending().await {
+        Ok(pending) => Json(
+            pending
+                .into_iter()
+                .map(|item| CorporateActionProposalItem {
+                    serial: item.serial,
+                    stock_symbol: item.proposal.stock_symbol,
+                    source: item.proposal.source,
+                    reference: item.proposal.reference,
+                    action_type: item.proposal.action_type,
+                    effective_date: item.proposal.effective_date.map(|d| d.to_string()),
+                    share_ratio: item
+                        .proposal
+                        .share_ratio
+                        .map(|ratio| ratio.normalize().to_string()),
+                    note: item.proposal.note,
+                    created_at: item.created_at.to_rfc3339(),
+                })
+                .collect::<Vec<_>>(),
+        )
+        .into_response(),
+        Err(why) => (
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("failed to fetch corporate action proposals: {why:#}"),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// 確認公司行動建議的 HTTP handler。
+///
+/// 寫入公司行動後自動建立最新交易日的 CAGR 重算 job，確認的事件才會反映到
+/// 排行榜；歷史基準日不會重算。job 無法建立（例如已有 CAGR job 執行中）時排入補跑，
+/// 待可建立時自動重算一次，回應的 `cagr_queued` 為 `true`。
+async fn confirm_corporate_action_proposal(
+    State(_state): State<BackfillWebState>,
+    Path(serial): Path<i64>,
+    Json(req): Json<ConfirmCorporateActionProposalRequest>,
+) -> impl IntoResponse {
+    use crate::domain::performance::repository::CorporateActionProposalRepository;
+
+    let effective_date = match parse_request_date(&req.effective_date) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    let share_ratio = match parse_request_share_ratio(&req.share_ratio) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+
+    let repository =
+        crate::infra::database::repository::corporate_action::PgCorporateActionProposalRepository::new();
+    let action = match repository
+        .confirm(serial, effective_date, share_ratio, req.note.trim())
+        .await
+    {
+        Ok(Some(action)) => action,
+        Ok(None) => {
+            return (
+                axum::http::StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: format!("pending corporate action proposal not found: {serial}"),
+                }),
+            )
+                .into_response();
+        }
+        Err(why) => {
+            return (
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("failed to confirm corporate action proposal: {why:#}"),
+                }),
+            )
+                .into_response();
+        }
+    };
+
+    let (cagr_job, cagr_queued) = match start_or_queue_cagr_job().await {
+        CagrRecalculation::Started(job) => (Some(job), false),
+        CagrRecalculation::Queued => (None, true),
+    };
+
+    Json(ConfirmCorporateActionProposalResponse {
+        stock_symbol: action.stock_symbol,
+        action: CorporateActionItem {
+            effective_date: action.effective_date.to_string(),
+            share_ratio: action.share_ratio.normalize().to_string(),
+            note: action.note,
+        },
+        cagr_job,
+        cagr_queued,
+    })
+    .into_response()
+}
+
+/// 駁回公司行動建議的 HTTP handler。
+async fn reject_corporate_action_proposal(
+    State(_state): State<BackfillWebState>,
+    Path(serial): Path<i64>,
+) -> impl IntoResponse {
+    use crate::domain::performance::repository::CorporateActionProposalRepository;
+
+    let repository =
+        crate::infra::database::repository::corporate_action::PgCorporateActionProposalRepository::new();
+    match repository.reject(serial).await {
+        Ok(true) => Json(RejectCorporateActionProposalResponse { serial }).into_response(),
+        Ok(false) => (
+            axum::http::StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("pending corporate action proposal not found: {serial}"),
+            }),
+        )
+            .into_response(),
+        Err(why) => (
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("failed to reject corporate action proposal: {why:#}"),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// 列出近 30 天內截止或尚未抽籤的公開申購。
+///
+/// 仍可申購者排在最前並依中籤一張的預期獲利排序，其餘依申購開始日由新至舊。
+async fn list_public_subscriptions(State(_state): State<BackfillWebState>) -> impl IntoResponse {
+    use crate::domain::quote::repository::QuoteRepository;
+    use crate::domain::subscription::{SubscriptionRepository, rank_by_expected_profit};
+
+    let internal_error = |why: anyhow::Error| {
+        (
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("failed to fetch public subscriptions: {why:#}"),
+            }),
+        )
+            .into_response()
+    };
+    let today = chrono::Local::now().date_naive();
+    let since = today - chrono::TimeDelta::days(30);
+    let repository =
+        crate::infra::database::repository::subscription::PgSubscriptionRepository::new();
+    let (subscriptions, entries) = match tokio::try_join!(
+        repository.fetch_since(since),
+        repository.fetch_entries_since(since)
+    ) {
+        Ok(value) => value,
+        Err(why) => return internal_error(why),
+    };
+
+    let quote_repository = crate::infra::database::repository::quote::PgQuoteRepository::new();
+    let mut open = Vec::new();
+    let mut closed = Vec::new();
+    for subscription in subscriptions {
+        let market_price = match quote_repository
+            .fetch_last_quote(&subscription.stock_symbol)
+            .await
+        {
+            Ok(quote) => quote
+                .map(|quote| quote.closing_price)
+                .filter(|price| *price > rust_decimal::Decimal::ZERO),
+            Err(why) => return internal_error(why),
+        };
+        let profit = market_price.and_then(|price| subscription.expected_profit_per_lot(price));
+        let item = PublicSubscriptionItem {
+            stock_symbol: subscription.stock_symbol.clone(),
+            stock_name: subscription.stock_name.clone(),
+            market: subscription.market.clone(),
+            offering_start_date: subscription.offering_start_date.to_string(),
+            offering_end_date: subscription.offering_end_date.to_string(),
+            drawing_date: subscription.drawing_date.map(|d| d.to_string()),
+            offering_price: subscription
+                .offering_price
+                .map(|price| price.normalize().to_string()),
+            market_price: market_price.map(|price| price.normalize().to_string()),
+            expected_profit_per_lot: profit.map(|profit| profit.normalize().to_string()),
+            is_open: subscription.is_open_on(today),
+            entries: entries
+                .iter()
+                .filter(|entry| {
+                    entry.stock_symbol == subscription.stock_symbol
+                        && entry.offering_start_date == subscription.offering_start_date
+                })
+                .map(subscription_entry_item)
+                .collect(),
+        };
+        if item.is_open {
+            open.push((item, profit));
+        } else {
+            closed.push(item);
+        }
+    }
+    rank_by_expected_profit(&mut open);
+
+    Json(
+        open.into_iter()
+            .map(|(item, _)| item)
+            .chain(closed)
+            .collect::<Vec<_>>(),
+    )
+    .into_response()
+}
+
+/// 登記成員參與公開申購的 HTTP handler。
+async fn enter_public_subscription(
+    State(_state): State<BackfillWebState>,
+    Json(req): Json<EnterPublicSubscriptionRequest>,
+) -> impl IntoResponse {
+    use crate::domain::subscription::SubscriptionRepository;
+
+    let stock_symbol = match parse_request_security_code(req.stock_symbol) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    let offering_start_date = match parse_request_date(&req.offering_start_date) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+    if req.member_id <= 0 {
+        return (
+            axum::http::StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "member_id must be greater than zero".to_string(),
+            }),
+        )
+            .into_response();
+    }
+
+    let repository =
+        crate::infra::database::repository::subscription::PgSubscriptionRepository::new();
+    match repository
+        .enter(&stock_symbol, offering_start_date, req.member_id)
+        .await
+    {
+        Ok(Some(entry)) => Json(subscription_entry_item(&entry)).into_response(),
+        Ok(None) => (
+            axum::http::StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!(
+                    "public subscription not found: {stock_symbol} {offering_start_date}"
+                ),
+            }),
+        )
+            .into_response(),
+        Err(why) => (
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("failed to enter public subscription: {why:#}"),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// 記錄抽籤結果與賣出價的 HTTP handler；已實現損益由倉儲依承銷價計算。
+async fn record_subscription_result(
+    State(_state): State<BackfillWebState>,
+    Path(serial): Path<i64>,
+    Json(req): Json<RecordSubscriptionResultRequest>,
+) -> impl IntoResponse {
+    use crate::domain::subscription::SubscriptionRepository;
+
+    let sell_price = match parse_request_sell_price(&req.sell_price) {
+        Ok(value) => value,
+        Err(response) => return response,
+    };
+
+    let repository =
+        crate::infra::database::repository::subscription::PgSubscriptionRepository::new();
+    match repository.record_result(serial, req.won, sell_price).await {
+        Ok(Some(entry)) => Json(subscription_entry_item(&entry)).into_response(),
+        Ok(None) => (
+            axum::http::StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("public subscription entry not found: {serial}"),
+            }),
+        )
+            .into_response(),
+        Err(why) => (
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("failed to record public subscription result: {why:#}"),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// 將參與紀錄轉為 HTTP response 項目。
+fn subscription_entry_item(
+    entry: &crate::domain::subscription::SubscriptionEntry,
+) -> SubscriptionEntryItem {
+    SubscriptionEntryItem {
+        serial: entry.serial,
+        stock_symbol: entry.stock_symbol.clone(),
+        offering_start_date: entry.offering_start_date.to_string(),
+        member_id: entry.member_id,
+        won: entry.won,
+        sell_price: entry.sell_price.map(|price| price.normalize().to_string()),
+        realized_profit: entry
+            .realized_profit
+            .map(|profit| profit.normalize().to_string()),
+    }
+}
+
 /// 建立 CAGR 重算 job 的 HTTP handler。
 async fn start_cagr(
     State(_state): State<BackfillWebState>,
@@ -421,6 +896,108 @@ mod tests {
             );
         }
 
+        // 證券代號變更：代號不合法、新舊相同或日期錯誤都必須在寫入前擋下。
+        for body in [
+            r#"{"old_symbol":"2330","new_symbol":"2330","effective_date":"2026-04-01"}"#,
+            r#"{"old_symbol":"23;30","new_symbol":"2331","effective_date":"2026-04-01"}"#,
+            r#"{"old_symbol":"2330","new_symbol":"","effective_date":"2026-04-01"}"#,
+            r#"{"old_symbol":"2330","new_symbol":"2331","effective_date":"2026-04"}"#,
+        ] {
+            assert_eq!(
+                post("/api/manual-backfill/symbol-change", body).await,
+                StatusCode::BAD_REQUEST,
+                "{body} 應被拒絕"
+            );
+        }
+
+        // 確認公司行動建議：比例或日期不合法時在寫入前擋下。
+        assert_eq!(
+            post(
+                "/api/manual-backfill/corporate-action-proposals/1/confirm",
+                r#"{"effective_date":"2026-05-12","share_ratio":"0"}"#
+            )
+            .await,
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            post(
+                "/api/manual-backfill/corporate-action-proposals/1/confirm",
+                r#"{"effective_date":"not-a-date","share_ratio":"0.6"}"#
+            )
+            .await,
+            StatusCode::BAD_REQUEST
+        );
+        // 流水號不是整數時由 Path extractor 擋下。
+        assert_eq!(
+            post(
+                "/api/manual-backfill/corporate-action-proposals/abc/reject",
+                "{}"
+            )
+            .await,
+            StatusCode::BAD_REQUEST
+        );
+
+        // 公開申購：成員編號、日期與賣出價不合法時在寫入前擋下。
+        assert_eq!(
+            post(
+                "/api/manual-backfill/public-subscriptions/entries",
+                r#"{"stock_symbol":"6789","offering_start_date":"2026-07-14","member_id":0}"#
+            )
+            .await,
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            post(
+                "/api/manual-backfill/public-subscriptions/entries",
+                r#"{"stock_symbol":"6789","offering_start_date":"07/14","member_id":1}"#
+            )
+            .await,
+            StatusCode::BAD_REQUEST
+        );
+        for price in ["0", "-5", "abc"] {
+            assert_eq!(
+                post(
+                    "/api/manual-backfill/public-subscriptions/entries/1/result",
+                    &format!(r#"{{"won":true,"sell_price":"{price}"}}"#)
+                )
+                .await,
+                StatusCode::BAD_REQUEST,
+                "sell_price={price} 應被拒絕"
+            );
+        }
+
+        // 封存重跑：未知種類或起訖顛倒都必須擋下。
+        assert_eq!(
+            post(
+                "/api/manual-backfill/reprocess",
+                r#"{"target":"revenue","from":"2026-04-01","to":"2026-04-30"}"#
+            )
+            .await,
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            post(
+                "/api/manual-backfill/reprocess",
+                r#"{"target":"quote","from":"2026-04-30","to":"2026-04-01"}"#
+            )
+            .await,
+            StatusCode::BAD_REQUEST
+        );
+
+        // 事件重播：未知事件種類、非法代號或起訖顛倒都必須擋下。
+        for body in [
+            r#"{"event_type":"Revenue","from":"2026-04-01","to":"2026-04-30"}"#,
+            r#"{"symbol":"23;30","from":"2026-04-01","to":"2026-04-30"}"#,
+            r#"{"from":"2026-04-30","to":"2026-04-01"}"#,
+            r#"{"from":"2026-04","to":"2026-04-30"}"#,
+        ] {
+            assert_eq!(
+                post("/api/manual-backfill/event-replay", body).await,
+                StatusCode::BAD_REQUEST,
+                "{body} 應被拒絕"
+            );
+        }
+
         // CAGR 基準日格式錯誤（留空才是合法的「採用最新交易日」）。
         // 注意 parse_request_date 沿用 chrono 的寬鬆解析，"2026-8-7" 是合法的，
         // 因此這裡用真正無法解析的值。
@@ -538,6 +1115,9 @@ mod tests {
             "/api/manual-backfill/cagr",
             "/api/manual-backfill/cagr-period",
             "/api/manual-backfill/corporate-action",
+            "/api/manual-backfill/reprocess",
+            "/api/manual-backfill/event-replay",
+            "/api/manual-backfill/symbol-change",
         ] {
             assert!(
                 INDEX_HTML.contains(&format!("data-endpoint=\"{endpoint}\"")),
@@ -545,4 +1125,28 @@ mod tests {
             );
         }
     }
+
+    /// 事件重播預設只記錄；副作用必須由 request 明確開啟。
+    #[test]
+    fn event_replay_redispatch_is_opt_in() {
+        let req: super::super::dto::EventReplayRequest =
+            serde_json::from_str(r#"{"from":"2026-04-01","to":"2026-04-30"}"#)
+                .expect("body should parse");
+        assert!(!req.redispatch);
+        assert!(!INDEX_HTML.contains("name=\"redispatch\" type=\"checkbox\" checked"));
+    }
+
+    #[test]
+    fn proposal_review_panel_uses_the_routed_endpoints() {
+        // 審核區塊不是表單，改以字面路徑確認與 router 同步。
+        assert!(INDEX_HTML.contains("/api/manual-backfill/corporate-action-proposals"));
+        assert!(INDEX_HTML.contains("id=\"proposals-body\""));
+    }
+
+    #[test]
+    fn subscription_panel_uses_the_routed_endpoints() {
+        assert!(INDEX_HTML.contains("\"/api/manual-backfill/public-subscriptions\""));
+        assert!(INDEX_HTML.contains("/api/manual-backfill/public-subscriptions/entries"));
+        assert!(INDEX_HTML.contains("id=\"subscriptions-body\""));
+    }
 }
@@ -1,16 +1,24 @@
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
 use anyhow::{Result, anyhow};
 use axum::Json;
 use axum::response::IntoResponse;
-use chrono::{Local, NaiveDate};
+use chrono::{Local, NaiveDate, NaiveTime};
 
 use crate::{
-    app::backfill::{dividend, quote, quote_history, taiwan_stock_index},
+    app::backfill::{
+        dividend, quote, quote_history,
+        reprocess::{self, ReprocessTarget},
+        taiwan_stock_index,
+    },
     app::calculation::{cagr, dividend_record},
-    app::event::taiwan_stock::closing,
+    app::event::{get_global_dispatcher, handlers::EventHandlerFn, replay, taiwan_stock::closing},
+    domain::event_log::{EventLogRepository, EventQuery},
+    domain::events::DomainEvent,
     domain::performance::CagrPeriod,
+    infra::database::repository::event_log::PgEventLogRepository,
 };
 
 use super::dto::ErrorResponse;
@@ -33,6 +41,15 @@ const JOB_TIMEOUT: Duration = Duration::from_secs(60 * 60);
 /// 逾時只會在半途被砍掉。
 const LONG_RUNNING_JOB_TIMEOUT: Duration = Duration::from_secs(12 * 60 * 60);
 
+/// 補跑 CAGR 重算時，每次嘗試建立 job 的間隔。
+const CAGR_FOLLOW_UP_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 補跑 CAGR 重算的最多嘗試次數；涵蓋一個 [`JOB_TIMEOUT`] 再多留一些餘裕。
+const CAGR_FOLLOW_UP_MAX_ATTEMPTS: u32 = 150;
+
+/// 是否已有等待中的 CAGR 補跑；多次確認只需要一次補跑。
+static CAGR_FOLLOW_UP_PENDING: AtomicBool = AtomicBool::new(false);
+
 /// 建立 manual backfill job 失敗的原因。
 ///
 /// 這是「拒絕建立」的錯誤，不是 job 執行失敗——job 執行失敗會反映在
@@ -280,6 +297,74 @@ pub(crate) async fn start_cagr_job(date: Option<NaiveDate>) -> Result<BackfillJo
     .await
 }
 
+/// [`start_or_queue_cagr_job`] 的結果。
+#[derive(Debug)]
+pub(crate) enum CagrRecalculation {
+    /// 已建立最新交易日的 CAGR 重算 job。
+    Started(BackfillJob),
+    /// 暫時無法建立（例如已有 CAGR job 執行中），待可建立時自動補跑一次。
+    Queued,
+}
+
+/// 建立最新交易日的 CAGR 重算 job；無法建立時排入補跑。
+///
+/// 已在執行的 CAGR job 可能在資料寫入前就讀過公司行動，因此不能只回報錯誤：
+/// 改由背景 task 定期重試，直到建立成功。等待中的補跑只保留一個，
+/// 它開始時會讀到所有已確認的資料。
+pub(crate) async fn start_or_queue_cagr_job() -> CagrRecalculation {
+    start_or_queue_in(
+        &CAGR_FOLLOW_UP_PENDING,
+        || start_cagr_job(None),
+        CAGR_FOLLOW_UP_INTERVAL,
+        CAGR_FOLLOW_UP_MAX_ATTEMPTS,
+    )
+    .await
+}
+
+/// [`start_or_queue_cagr_job`] 的實作；旗標、建立 job 的函式與重試節奏由呼叫端注入。
+async fn start_or_queue_in<F, Fut>(
+    pending: &'static AtomicBool,
+    start: F,
+    interval: Duration,
+    max_attempts: u32,
+) -> CagrRecalculation
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<BackfillJob, StartJobError>> + Send,
+{
+    let why = match start().await {
+        Ok(job) => return CagrRecalculation::Started(job),
+        Err(why) => why,
+    };
+    tracing::info!("CAGR recalculation queued: {why}");
+    if pending
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        return CagrRecalculation::Queued;
+    }
+
+    tokio::spawn(async move {
+        for _ in 0..max_attempts {
+            tokio::time::sleep(interval).await;
+            // 先清除旗標再嘗試：嘗試期間新的確認會自行建立或排入下一次補跑。
+            pending.store(false, Ordering::SeqCst);
+            match start().await {
+                Ok(job) => {
+                    tracing::info!("queued CAGR recalculation started as job {}", job.id);
+                    return;
+                }
+                // 嘗試期間已有其他補跑接手。
+                Err(_) if pending.swap(true, Ordering::SeqCst) => return,
+                Err(why) => tracing::debug!("queued CAGR recalculation still waiting: {why}"),
+            }
+        }
+        pending.store(false, Ordering::SeqCst);
+        tracing::warn!("queued CAGR recalculation gave up after {max_attempts} attempts");
+    });
+    CagrRecalculation::Queued
+}
+
 /// 建立單一統計期間的歷史回填背景 job。
 ///
 /// 新增期間（例如 Y7）後專用：掃出「已有計算結果、但缺少該期間」的基準日
@@ -308,6 +393,95 @@ pub(crate) async fn start_cagr_period_job(
     .await
 }
 
+/// 建立以封存原始回應重跑防腐層映射的背景 job。
+///
+/// Job 不連線任何外部來源，只讀取 `infra::archive` 的本機封存，逐日重新解析、
+/// 映射並原子替換寫入；缺少封存的日期會略過並計入摘要。區間可能長達數月，
+/// 改用 [`LONG_RUNNING_JOB_TIMEOUT`]。
+pub(crate) async fn start_reprocess_job(
+    target: ReprocessTarget,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<BackfillJob, StartJobError> {
+    start_job_with_timeout(
+        BACKFILL_STATE.clone(),
+        "reprocess",
+        format!("{}|{from}~{to}", target.code()),
+        LONG_RUNNING_JOB_TIMEOUT,
+        move || async move {
+            let summary = reprocess::execute(target, from, to).await?;
+            Ok(format!(
+                "reprocess completed: target={}, dates_requested={}, dates_processed={}, dates_missing={}, dates_failed={}, rows_written={}",
+                target.code(),
+                summary.dates_requested,
+                summary.dates_processed,
+                summary.dates_missing,
+                summary.dates_failed,
+                summary.rows_written
+            ))
+        },
+    )
+    .await
+}
+
+/// 建立重播領域事件日誌的背景 job。
+///
+/// 依 `query` 逐頁讀出事件日誌。預設只把事件寫進 log，不觸發任何副作用；
+/// `redispatch` 為 `true` 時才交回全域派發器，重新觸發與原派發相同的副作用
+/// （Telegram 通知、同步佇列等）。重播的事件不會再寫入日誌或對外發布。
+pub(crate) async fn start_event_replay_job(
+    query: EventQuery,
+    redispatch: bool,
+) -> Result<BackfillJob, StartJobError> {
+    let handler = if redispatch {
+        replay::dispatcher_handler(get_global_dispatcher())
+    } else {
+        replay::log_handler()
+    };
+    start_event_replay_job_in(
+        BACKFILL_STATE.clone(),
+        query,
+        Arc::new(PgEventLogRepository::new()),
+        redispatch,
+        handler,
+    )
+    .await
+}
+
+/// [`start_event_replay_job`] 的實作，事件日誌與處理器由呼叫端注入。
+///
+/// `redispatch` 只影響 job 的 input 與 message，讓兩種模式可同時執行並能區分。
+async fn start_event_replay_job_in(
+    state: BackfillWebState,
+    query: EventQuery,
+    repo: Arc<dyn EventLogRepository>,
+    redispatch: bool,
+    handler: EventHandlerFn,
+) -> Result<BackfillJob, StartJobError> {
+    let mode = if redispatch { "redispatch" } else { "log_only" };
+    let input = format!(
+        "{}|{}|{}~{}|after={}|{mode}",
+        query.event_type.as_deref().unwrap_or("*"),
+        query.symbol.as_deref().unwrap_or("*"),
+        query.from.map(|from| from.to_rfc3339()).unwrap_or_default(),
+        query.to.map(|to| to.to_rfc3339()).unwrap_or_default(),
+        query.after_sequence.unwrap_or_default()
+    );
+    start_job(state, "event_replay", input, move || async move {
+        let report = replay::replay(repo.as_ref(), &query, &handler).await?;
+        Ok(format!(
+            "event replay completed: mode={mode}, replayed={}, failed={}, last_sequence={}",
+            report.replayed,
+            report.failed,
+            report
+                .last_sequence
+                .map(|sequence| sequence.to_string())
+                .unwrap_or_else(|| "-".to_string())
+        ))
+    })
+    .await
+}
+
 /// 建立並啟動一個 manual backfill 背景 job。
 ///
 /// 此 helper 封裝共用流程，依序做四件事：
@@ -517,6 +691,69 @@ pub(super) fn parse_request_period(period: &str) -> Result<CagrPeriod, axum::res
     })
 }
 
+/// 解析封存重跑的資料種類代碼。
+#[allow(clippy::result_large_err)]
+pub(super) fn parse_request_reprocess_target(
+    target: &str,
+) -> Result<ReprocessTarget, axum::response::Response> {
+    ReprocessTarget::from_code(target.trim()).ok_or_else(|| {
+        (
+            axum::http::StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: format!("unknown reprocess target: {target}"),
+            }),
+        )
+            .into_response()
+    })
+}
+
+/// 組出事件重播的查詢條件，HTTP 與 gRPC 共用同一套驗證規則。
+///
+/// 空字串的事件種類與證券代號代表不篩選；日期區間含首尾，以本地時間換算成
+/// `[from 00:00, to 隔日 00:00)`。
+pub(crate) fn event_replay_query(
+    event_type: &str,
+    symbol: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+    after_sequence: Option<i64>,
+) -> Result<EventQuery> {
+    let event_type = event_type.trim();
+    let event_type = if event_type.is_empty() {
+        None
+    } else if <DomainEvent as strum::VariantNames>::VARIANTS.contains(&event_type) {
+        Some(event_type.to_string())
+    } else {
+        return Err(anyhow!("unknown event type: {event_type}"));
+    };
+    let symbol = if symbol.trim().is_empty() {
+        None
+    } else {
+        Some(normalize_security_code(symbol.to_string())?)
+    };
+    if from > to {
+        return Err(anyhow!("from must not be later than to"));
+    }
+    let start_of = |date: NaiveDate| {
+        date.and_time(NaiveTime::MIN)
+            .and_local_timezone(Local)
+            .earliest()
+            .ok_or_else(|| anyhow!("{date} has no local midnight"))
+    };
+    let end = to
+        .succ_opt()
+        .ok_or_else(|| anyhow!("to is out of range: {to}"))?;
+
+    Ok(EventQuery {
+        event_type,
+        symbol,
+        from: Some(start_of(from)?),
+        to: Some(start_of(end)?),
+        after_sequence,
+        ..Default::default()
+    })
+}
+
 /// 解析公司行動的股數變動比例。
 ///
 /// 必須大於零：`0` 會讓持股歸零、負數毫無意義，兩者都只會產生錯得離譜的
@@ -544,6 +781,31 @@ pub(super) fn parse_request_share_ratio(
     Ok(ratio)
 }
 
+/// 解析公開申購中籤後的賣出價；空字串代表尚未賣出，回傳 `None`。
+#[allow(clippy::result_large_err)]
+pub(super) fn parse_request_sell_price(
+    raw: &str,
+) -> Result<Option<rust_decimal::Decimal>, axum::response::Response> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Ok(None);
+    }
+    let reject = |message: String| {
+        (
+            axum::http::StatusCode::BAD_REQUEST,
+            Json(ErrorResponse { error: message }),
+        )
+            .into_response()
+    };
+
+    let price = rust_decimal::Decimal::from_str_exact(raw)
+        .map_err(|why| reject(format!("sell_price must be a decimal number: {why}")))?;
+    if price <= rust_decimal::Decimal::ZERO {
+        return Err(reject("sell_price must be greater than zero".to_string()));
+    }
+    Ok(Some(price))
+}
+
 /// 解析以逗號、空白或換行分隔的代號清單；空字串回傳空 `Vec`。
 ///
 /// 空清單在呼叫端有明確語意（全部 ETF），因此不視為錯誤；
@@ -603,6 +865,7 @@ pub(crate) fn normalize_security_code(security_code: String) -> Result<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::event_log::test_double::InMemoryEventLog;
     use axum::http::StatusCode;
     use rust_decimal_macros::dec;
     use tokio::sync::Semaphore;
@@ -652,6 +915,25 @@ mod tests {
         }
     }
 
+    /// 重跑種類代碼必須對得上 `ReprocessTarget`，也接受個別爬蟲名稱。
+    #[test]
+    fn request_reprocess_target_only_accepts_known_codes() {
+        assert_eq!(
+            parse_request_reprocess_target(" quote ").expect("quote 應為合法種類"),
+            ReprocessTarget::DailyQuote
+        );
+        assert_eq!(
+            parse_request_reprocess_target("twse::quote").expect("爬蟲名稱應為合法種類"),
+            ReprocessTarget::DailyQuote
+        );
+        for raw in ["", "revenue", "twse"] {
+            assert_eq!(
+                status_of(parse_request_reprocess_target(raw)),
+                StatusCode::BAD_REQUEST
+            );
+        }
+    }
+
     /// 股數變動比例必須為正數且不得離譜；邊界值 1000 仍屬合法。
     #[test]
     fn request_share_ratio_guards_against_nonsense_values() {
@@ -803,6 +1085,95 @@ mod tests {
         assert_eq!(finished.message, "rows_inserted=42");
     }
 
+    /// 事件重播查詢只接受已知的事件種類與合法代號，日期區間換算成含首尾的時間範圍。
+    #[test]
+    fn event_replay_query_validates_and_covers_whole_days() {
+        let from = NaiveDate::from_ymd_opt(2026, 4, 1).expect("測試日期應合法");
+        let to = NaiveDate::from_ymd_opt(2026, 4, 30).expect("測試日期應合法");
+
+        let query =
+            event_replay_query(" StockRegistered ", " 2330 ", from, to, Some(7)).expect("合法條件");
+        assert_eq!(query.event_type.as_deref(), Some("StockRegistered"));
+        assert_eq!(query.symbol.as_deref(), Some("2330"));
+        assert_eq!(query.from.map(|t| t.date_naive()), Some(from));
+        assert_eq!(
+            query.to.map(|t| t.date_naive()),
+            NaiveDate::from_ymd_opt(2026, 5, 1)
+        );
+        assert_eq!(query.after_sequence, Some(7));
+
+        let all = event_replay_query("", "", from, from, None).expect("空白代表不篩選");
+        assert_eq!((all.event_type, all.symbol), (None, None));
+
+        assert!(event_replay_query("Revenue", "", from, to, None).is_err());
+        assert!(event_replay_query("", "23;30", from, to, None).is_err());
+        assert!(event_replay_query("", "", to, from, None).is_err());
+    }
+
+    /// 事件重播 job 依查詢條件把事件交給處理器，並把重播摘要寫進 job message。
+    #[tokio::test]
+    async fn event_replay_job_replays_matching_events() {
+        let day = NaiveDate::from_ymd_opt(2026, 4, 1).expect("測試日期應合法");
+        let occurred_at = day
+            .and_hms_opt(14, 0, 0)
+            .and_then(|t| t.and_local_timezone(Local).earliest())
+            .expect("測試時間應合法");
+        let nav = |sequence: i64, symbol: &str| crate::domain::event_log::StoredEvent {
+            sequence,
+            recorded_at: occurred_at,
+            event: DomainEvent::NetAssetValueUpdated {
+                symbol: symbol.to_string(),
+                old_nav: rust_decimal::Decimal::from(sequence),
+                new_nav: rust_decimal::Decimal::from(sequence + 1),
+                occurred_at,
+            },
+        };
+        let repo = Arc::new(InMemoryEventLog::with_events(vec![
+            nav(1, "2330"),
+            nav(2, "2317"),
+            nav(3, "2330"),
+        ]));
+        let seen = Arc::new(tokio::sync::Mutex::new(Vec::new()));
+        let handler: EventHandlerFn = {
+            let seen = seen.clone();
+            Box::new(move |event| {
+                let seen = seen.clone();
+                Box::pin(async move {
+                    seen.lock().await.push(event);
+                    Ok(())
+                })
+            })
+        };
+        let query =
+            event_replay_query("NetAssetValueUpdated", "2330", day, day, None).expect("合法條件");
+
+        let state = BackfillWebState::new();
+        let job = start_event_replay_job_in(state.clone(), query, repo, false, handler)
+            .await
+            .expect("job should start");
+        assert_eq!(job.kind, "event_replay");
+        assert!(job.input.starts_with("NetAssetValueUpdated|2330|"));
+        assert!(job.input.ends_with("|log_only"));
+
+        wait_until_finished(&state, &job.id).await;
+
+        let finished = state
+            .jobs
+            .read()
+            .await
+            .get(&job.id)
+            .cloned()
+            .expect("job should remain queryable");
+        assert_eq!(finished.status_label(), "succeeded");
+        assert_eq!(
+            finished.message,
+            "event replay completed: mode=log_only, replayed=2, failed=0, last_sequence=3"
+        );
+        let seen = seen.lock().await;
+        assert_eq!(seen.len(), 2);
+        assert!(seen.iter().all(|event| event.symbol() == Some("2330")));
+    }
+
     /// 輪詢等待指定 job 離開 running 狀態（最多約 5 秒）。
     async fn wait_until_finished(state: &BackfillWebState, id: &str) {
         for _ in 0..500 {
@@ -821,6 +1192,55 @@ mod tests {
         panic!("job {id} did not finish in time");
     }
 
+    /// CAGR 重算暫時無法建立時排入補跑；多次請求只保留一個補跑，成功建立後停止重試。
+    #[tokio::test]
+    async fn cagr_recalculation_is_queued_until_a_job_can_start() {
+        use std::sync::atomic::AtomicUsize;
+
+        static PENDING: AtomicBool = AtomicBool::new(false);
+        let state = BackfillWebState::new();
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let start = {
+            let state = state.clone();
+            let attempts = attempts.clone();
+            move || {
+                let state = state.clone();
+                let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    // 前三次模擬已有 CAGR job 執行中。
+                    if attempt < 3 {
+                        return Err(StartJobError::DuplicateActiveJob {
+                            existing_id: "running".to_string(),
+                        });
+                    }
+                    start_job(state, "cagr", attempt.to_string(), || async {
+                        Ok("done".to_string())
+                    })
+                    .await
+                }
+            }
+        };
+        let interval = Duration::from_millis(10);
+
+        let first = start_or_queue_in(&PENDING, start.clone(), interval, 50).await;
+        let second = start_or_queue_in(&PENDING, start, interval, 50).await;
+        assert!(matches!(first, CagrRecalculation::Queued));
+        assert!(matches!(second, CagrRecalculation::Queued));
+
+        for _ in 0..100 {
+            if !state.jobs.read().await.is_empty() {
+                break;
+            }
+            tokio::time::sleep(interval).await;
+        }
+        tokio::time::sleep(interval * 5).await;
+        let jobs = state.jobs.read().await;
+        assert_eq!(jobs.len(), 1, "只應補跑一次");
+        assert!(jobs.values().all(|job| job.kind == "cagr"));
+        assert_eq!(attempts.load(Ordering::SeqCst), 4);
+        assert!(!PENDING.load(Ordering::SeqCst));
+    }
+
     /// 驗證相同 (kind, input) 的 job 執行期間會被拒絕，結束後可再啟動。
     #[tokio::test]
     async fn duplicate_active_job_is_rejected() {
@@ -2,10 +2,15 @@
 //!
 //! 健康檢查與 Swagger UI 不會套用此 middleware；其餘 `/api/v1` 路徑都必須
 //! 使用 `DATA_API_KEY`。比較採固定時間演算法，避免以提早結束的字串比較洩漏 key 前綴。
+//!
+//! 瀏覽器的 `WebSocket` 無法自訂 `Authorization` 標頭，因此即時報價推送另以
+//! [`require_stream_key`] 驗證，額外接受 `Sec-WebSocket-Protocol` 帶入的 key。
+//! key 不接受放在 query string，避免落入代理伺服器日誌、瀏覽歷史與 Referer；
+//! 瀏覽器訂閱 SSE 時改以 `fetch` 帶標頭讀取串流。
 
 use axum::{
     body::Body,
-    http::{Request, StatusCode, header},
+    http::{HeaderMap, Request, StatusCode, header},
     middleware::Next,
     response::Response,
 };
@@ -13,22 +18,121 @@ use subtle::ConstantTimeEq;
 
 use super::handlers::error_response;
 
+/// 以 `Sec-WebSocket-Protocol` 帶 key 時一併提出的子協定，伺服器以它回應交握。
+///
+/// 瀏覽器端寫法為 `new WebSocket(url, ["bearer", key])`；key 本身不會被回送。
+pub(super) const STREAM_KEY_PROTOCOL: &str = "bearer";
+
 /// 驗證 request 的 `Authorization: Bearer <DATA_API_KEY>` 標頭。
 pub(super) async fn require_bearer_key(request: Request<Body>, next: Next) -> Response {
-    // 未設定 key 時拒絕所有受保護請求，避免部署漏設環境變數而意外公開資料。
-    let Ok(expected) = std::env::var("DATA_API_KEY") else {
-        tracing::error!("DATA_API_KEY is not configured");
+    let Some(expected) = configured_key() else {
         return error_response(StatusCode::UNAUTHORIZED, "未授權");
     };
-    let supplied = request
-        .headers()
-        .get(header::AUTHORIZATION)
-        .and_then(|value| value.to_str().ok())
-        .and_then(|value| value.strip_prefix("Bearer "));
-    let authorized =
-        supplied.is_some_and(|value| expected.as_bytes().ct_eq(value.as_bytes()).into());
-    if !authorized {
+    if !bearer_authorized(request.headers(), &expected) {
         return error_response(StatusCode::UNAUTHORIZED, "未授權");
     }
     next.run(request).await
 }
+
+/// 驗證即時報價推送的 key：`Authorization` 標頭或 `Sec-WebSocket-Protocol` 任一相符即放行。
+pub(super) async fn require_stream_key(request: Request<Body>, next: Next) -> Response {
+    let Some(expected) = configured_key() else {
+        return error_response(StatusCode::UNAUTHORIZED, "未授權");
+    };
+    if !stream_authorized(request.headers(), &expected) {
+        return error_response(StatusCode::UNAUTHORIZED, "未授權");
+    }
+    next.run(request).await
+}
+
+/// 讀取 `DATA_API_KEY`。
+///
+/// 未設定 key 時拒絕所有受保護請求，避免部署漏設環境變數而意外公開資料。
+fn configured_key() -> Option<String> {
+    let key = std::env::var("DATA_API_KEY").ok();
+    if key.is_none() {
+        tracing::error!("DATA_API_KEY is not configured");
+    }
+    key
+}
+
+fn key_matches(expected: &str, supplied: &str) -> bool {
+    expected.as_bytes().ct_eq(supplied.as_bytes()).into()
+}
+
+fn bearer_authorized(headers: &HeaderMap, expected: &str) -> bool {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|value| key_matches(expected, value))
+}
+
+fn stream_authorized(headers: &HeaderMap, expected: &str) -> bool {
+    if bearer_authorized(headers, expected) {
+        return true;
+    }
+
+    // 子協定清單可能分散在多個標頭，每個標頭內再以逗號分隔。
+    headers
+        .get_all(header::SEC_WEBSOCKET_PROTOCOL)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|protocol| key_matches(expected, protocol.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    const KEY: &str = "s3cret-key";
+
+    fn headers(pairs: &[(header::HeaderName, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn stream_accepts_key_from_websocket_protocol() {
+        let offered = headers(&[(header::SEC_WEBSOCKET_PROTOCOL, "bearer, s3cret-key")]);
+        assert!(stream_authorized(&offered, KEY));
+
+        let split = headers(&[
+            (header::SEC_WEBSOCKET_PROTOCOL, "bearer"),
+            (header::SEC_WEBSOCKET_PROTOCOL, "s3cret-key"),
+        ]);
+        assert!(stream_authorized(&split, KEY));
+
+        let wrong = headers(&[(header::SEC_WEBSOCKET_PROTOCOL, "bearer, s3cret-kez")]);
+        assert!(!stream_authorized(&wrong, KEY));
+        let marker_only = headers(&[(header::SEC_WEBSOCKET_PROTOCOL, "bearer")]);
+        assert!(!stream_authorized(&marker_only, KEY));
+    }
+
+    /// key 不接受放在 query string。
+    #[test]
+    fn stream_rejects_key_in_query_string() {
+        let request = Request::get("/api/v1/quotes/stream?symbols=2330&access_token=s3cret-key")
+            .body(Body::empty())
+            .unwrap();
+        assert!(!stream_authorized(request.headers(), KEY));
+    }
+
+    /// 一般端點只接受 `Authorization` 標頭，不因推送端點的放寬而接受其他來源。
+    #[test]
+    fn bearer_ignores_websocket_protocol() {
+        let bearer = headers(&[(header::AUTHORIZATION, "Bearer s3cret-key")]);
+        assert!(bearer_authorized(&bearer, KEY));
+        assert!(stream_authorized(&bearer, KEY));
+
+        let protocol = headers(&[(header::SEC_WEBSOCKET_PROTOCOL, "bearer, s3cret-key")]);
+        assert!(!bearer_authorized(&protocol, KEY));
+        assert!(!bearer_authorized(&headers(&[]), KEY));
+    }
+}
@@ -64,6 +64,10 @@ enum StockScreenSortValue {
     DividendYield,
     /// 估值百分比。
     ValuationPercentage,
+    /// 券資比。
+    ShortToMarginRatio,
+    /// 融資使用率。
+    MarginUtilization,
 }
 
 /// OpenAPI 文件使用的股利行事曆事件類型（§4.9）。
@@ -98,6 +102,32 @@ enum QfiiSortValue {
     Shares,
 }
 
+/// OpenAPI 文件使用的三大法人類別。
+#[derive(ToSchema)]
+#[schema(rename_all = "snake_case")]
+#[allow(dead_code)] // 此 enum 僅提供 OpenAPI schema；runtime 仍以 String 回傳精確 422。
+enum InstitutionalInvestorParamValue {
+    /// 外陸資（不含外資自營商）。
+    Foreign,
+    /// 投信。
+    InvestmentTrust,
+    /// 自營商（自行買賣＋避險）。
+    Dealer,
+    /// 三大法人合計。
+    Total,
+}
+
+/// OpenAPI 文件使用的買賣超方向。
+#[derive(ToSchema)]
+#[schema(rename_all = "snake_case")]
+#[allow(dead_code)] // 此 enum 僅提供 OpenAPI schema。
+enum TradeDirectionParamValue {
+    /// 買超，由多到少。
+    Buy,
+    /// 賣超，由多到少。
+    Sell,
+}
+
 /// OpenAPI 文件使用的排序方向。
 #[derive(ToSchema)]
 #[schema(rename_all = "snake_case")]
@@ -301,6 +331,23 @@ pub(super) struct StockProfile {
     pub(super) issued_share: Option<f64>,
     /// 歷史高低點；沒有紀錄時為 `null`。
     pub(super) history: Option<QuoteHistoryRecord>,
+    /// 今日仍在期間內的注意股／處置股紀錄；不在清單上時為空陣列。
+    pub(super) trading_surveillance: Vec<TradingSurveillance>,
+}
+
+/// 注意股或處置股的一段期間。
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub(super) struct TradingSurveillance {
+    /// 種類：`attention`（注意股）或 `disposition`（處置股）。
+    pub(super) kind: &'static str,
+    /// 起日（含），`YYYY-MM-DD`。
+    pub(super) start_date: String,
+    /// 迄日（含），`YYYY-MM-DD`。
+    pub(super) end_date: String,
+    /// 列入原因。
+    pub(super) reason: String,
+    /// 處置措施；注意股為空字串。
+    pub(super) measure: String,
 }
 
 /// 搜尋股票的成功回應。
@@ -363,6 +410,8 @@ pub(super) struct RealtimeSnapshotResponse {
     pub(super) source_site: String,
     /// 快照寫入快取的 UTC ISO 8601 時間。
     pub(super) updated_at: String,
+    /// 今日仍在期間內的注意股／處置股紀錄；查詢失敗時為 `null`，不影響報價本身。
+    pub(super) trading_surveillance: Option<Vec<TradingSurveillance>>,
 }
 
 /// 單月營收資料。
@@ -500,6 +549,32 @@ pub(super) struct Dividend {
     pub(super) stock_payable_date: Option<String>,
     /// 最後更新時間，UTC ISO 8601；DB 欄位 `updated_time`。
     pub(super) updated_at: Option<String>,
+    /// 填息結果；尚未除息或尚未計算時為 `null`。
+    pub(super) gap_fill: Option<DividendGapFill>,
+}
+
+/// 單次除息的填息結果。
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub(super) struct DividendGapFill {
+    /// 除息前一交易日收盤價（填息目標價）。
+    pub(super) pre_ex_close: Option<f64>,
+    /// 填息日；尚未填息時為 `null`。
+    pub(super) filled_on: Option<String>,
+    /// 除息日起算到填息日的交易日數（除息當日填息為 1）；尚未填息時為 `null`。
+    pub(super) trading_days_to_fill: Option<i32>,
+}
+
+/// 個股歷次除息的填息統計。
+#[derive(Debug, Serialize, ToSchema)]
+pub(super) struct DividendGapFillSummary {
+    /// 納入統計的除息次數。
+    pub(super) events: usize,
+    /// 已填息次數。
+    pub(super) filled: usize,
+    /// 填息率（%）。
+    pub(super) fill_ratio: Option<f64>,
+    /// 已填息事件的填息交易日數中位數；沒有填息紀錄時為 `null`。
+    pub(super) median_days_to_fill: Option<f64>,
 }
 
 /// 股利歷史的成功回應（§3.4 envelope）。
@@ -511,6 +586,8 @@ pub(super) struct DividendHistoryResponse {
     pub(super) data_as_of: Option<String>,
     /// 股利清單，依 §3.4 期間順序由新到舊。
     pub(super) dividends: Vec<Dividend>,
+    /// 歷次除息的填息統計（不受年度篩選影響）；沒有除息紀錄時為 `null`。
+    pub(super) gap_fill_summary: Option<DividendGapFillSummary>,
 }
 
 /// 單一交易日的個股估值計算結果。
@@ -578,6 +655,146 @@ pub(super) struct StockValuationResponse {
     pub(super) data_as_of: Option<String>,
     /// 最近有效估值；31 天視窗內無資料時為 `null`。
     pub(super) valuation: Option<StockValuation>,
+    /// 同一查詢截止日下，殖利率、本益比、股價淨值比在自身 5／10 年歷史中的
+    /// 位置；31 天視窗內無資料時為空陣列。
+    pub(super) historical_bands: Vec<HistoricalValuationBand>,
+    /// 同一查詢截止日下各估價模型的結果，依模型註冊順序排列；31 天視窗內
+    /// 無資料時為空陣列。
+    pub(super) models: Vec<ValuationModelResult>,
+    /// 查詢截止日前最新一季的財務體質分數；尚未計算時為 `null`。
+    pub(super) financial_health: Option<FinancialHealth>,
+}
+
+/// 估價訊號回測中單一訊號跨估價日的彙總。
+#[derive(Debug, Serialize, ToSchema)]
+pub(super) struct EstimateBacktestSignal {
+    /// 訊號：`undervalued`（收盤價≤便宜價）、`fair` 或 `overvalued`（收盤價≥昂貴價）。
+    pub(super) signal: &'static str,
+    /// 訊號中文名稱。
+    pub(super) label: &'static str,
+    /// 有樣本的估價日數。
+    pub(super) dates: i64,
+    /// 樣本總數。
+    pub(super) samples: i64,
+    /// 樣本加權平均的含息區間報酬率（%）。
+    pub(super) mean_return: Option<f64>,
+    /// 各估價日報酬率中位數的平均（%）。
+    pub(super) average_median_return: Option<f64>,
+    /// 樣本加權的正報酬比例（%）。
+    pub(super) win_rate: Option<f64>,
+}
+
+/// 單一持有期間與範圍的估價訊號回測報告。
+#[derive(Debug, Serialize, ToSchema)]
+pub(super) struct EstimateBacktestReport {
+    /// 持有期間：`3m`、`6m` 或 `12m`。
+    pub(super) horizon: &'static str,
+    /// 統計範圍：`all`、`market` 或 `industry`。
+    pub(super) scope: &'static str,
+    /// 範圍編號；`all` 為 0。
+    pub(super) scope_id: i32,
+    /// 範圍名稱。
+    pub(super) scope_name: String,
+    /// 最早估價日，格式 `YYYY-MM-DD`。
+    pub(super) first_date: String,
+    /// 最晚估價日，格式 `YYYY-MM-DD`。
+    pub(super) last_date: String,
+    /// 各訊號的彙總，依低估、合理、高估排列；沒有樣本的訊號省略。
+    pub(super) signals: Vec<EstimateBacktestSignal>,
+    /// 低估組減高估組的平均報酬（百分點）；越大代表訊號越有鑑別力。
+    pub(super) spread: Option<f64>,
+    /// 低估組與高估組都有樣本的估價日數。
+    pub(super) compared_dates: i64,
+    /// 上述估價日中低估組平均報酬勝過高估組的比例（%）。
+    pub(super) undervalued_beat_ratio: Option<f64>,
+}
+
+/// 估價訊號回測 endpoint 回應。
+#[derive(Debug, Serialize, ToSchema)]
+pub(super) struct EstimateBacktestResponse {
+    /// 依持有期間、範圍排序的報告；尚無回測結果時為空陣列。
+    pub(super) reports: Vec<EstimateBacktestReport>,
+}
+
+/// 單一估價模型的計算結果與當時採用的假設。
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub(super) struct ValuationModelResult {
+    /// 模型代碼：`yield_band`、`per_band`、`pbr_band`、`graham` 或 `ddm`。
+    pub(super) model: &'static str,
+    /// 模型中文名稱。
+    pub(super) label: &'static str,
+    /// 計算日，格式 `YYYY-MM-DD`。
+    pub(super) date: String,
+    /// 計算時採用的收盤價。
+    pub(super) closing_price: Option<f64>,
+    /// 便宜價。
+    pub(super) cheap: Option<f64>,
+    /// 合理價。
+    pub(super) fair: Option<f64>,
+    /// 昂貴價。
+    pub(super) expensive: Option<f64>,
+    /// 計算時的模型假設；欄位依模型而異。
+    #[schema(value_type = Object)]
+    pub(super) assumptions: serde_json::Value,
+}
+
+/// 最新一季財報計算的財務體質分數與當時採用的輸入。
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub(super) struct FinancialHealth {
+    /// 財報期間，格式 `YYYY-Q1`～`YYYY-Q4`。
+    pub(super) period: String,
+    /// 季末日期，格式 `YYYY-MM-DD`。
+    pub(super) period_end: String,
+    /// Piotroski F-score（0–9）；任一項資料不足時為 `null`。
+    pub(super) f_score: Option<i16>,
+    /// F-score 九項檢查結果；無法判斷的項目為 `null`。
+    #[schema(value_type = Object)]
+    pub(super) f_score_criteria: serde_json::Value,
+    /// Altman Z-score。
+    pub(super) altman_z_score: Option<f64>,
+    /// 應計比率：(近四季淨利 − 營業現金流) ÷ 平均總資產。
+    pub(super) accruals_ratio: Option<f64>,
+    /// 現金轉換率：近四季營業現金流 ÷ 淨利。
+    pub(super) cash_conversion_ratio: Option<f64>,
+    /// 以季末收盤價計算的自由現金流量殖利率百分比。
+    pub(super) fcf_yield_percent: Option<f64>,
+    /// 利息保障倍數：近四季息前稅前淨利 ÷ 財務成本。
+    pub(super) interest_coverage: Option<f64>,
+    /// 負債權益比。
+    pub(super) debt_to_equity: Option<f64>,
+    /// 負債權益比較去年同季的變化；負值代表槓桿下降。
+    pub(super) debt_to_equity_change: Option<f64>,
+    /// 計算採用的科目數值（元）與季末收盤價。
+    #[schema(value_type = Object)]
+    pub(super) inputs: serde_json::Value,
+}
+
+/// 單一指標在個股自身歷史中的位置。
+///
+/// `percentile` 為回顧期間內不高於當日數值的交易日比例；殖利率越高越便宜，
+/// 本益比與股價淨值比則相反，因此另提供方向一致的 `cheapness_percentile`。
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub(super) struct HistoricalValuationBand {
+    /// 指標：`dividend_yield`、`per` 或 `pbr`。
+    pub(super) metric: &'static str,
+    /// 回顧年數：5 或 10。
+    pub(super) years: i32,
+    /// 計算基準日，格式 `YYYY-MM-DD`。
+    pub(super) date: String,
+    /// 基準日的指標數值；殖利率單位為 %。
+    pub(super) current: Option<f64>,
+    /// 當日數值在回顧期間內的百分位（0–100）。
+    pub(super) percentile: Option<f64>,
+    /// 越高越便宜的百分位（0–100）。
+    pub(super) cheapness_percentile: Option<f64>,
+    /// 回顧期間最小值。
+    pub(super) minimum: Option<f64>,
+    /// 回顧期間中位數。
+    pub(super) median: Option<f64>,
+    /// 回顧期間最大值。
+    pub(super) maximum: Option<f64>,
+    /// 回顧期間的有效樣本（交易日）數；上市未滿回顧年數時會少於完整期間。
+    pub(super) samples: i64,
 }
 
 /// 單一交易日的市場廣度統計。
@@ -636,6 +853,145 @@ pub(super) struct MarketBreadthResponse {
     pub(super) history: Vec<MarketBreadth>,
 }
 
+/// 單一交易日的市場廣度衍生指標。
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub(super) struct MarketBreadthIndicatorPoint {
+    /// 統計日期，格式 `YYYY-MM-DD`。
+    pub(super) date: String,
+    /// 上漲家數。
+    pub(super) advances: i32,
+    /// 下跌家數。
+    pub(super) declines: i32,
+    /// 平盤家數。
+    pub(super) unchanged: i32,
+    /// 上漲減下跌家數。
+    pub(super) net_advances: i32,
+    /// 騰落線：淨上漲家數的累計值；起點為指標開始計算的第一天，宜看走勢而非水位。
+    pub(super) ad_line: i64,
+    /// 比例調整淨上漲：(上漲 − 下跌) / (上漲 + 下跌) × 1000。
+    pub(super) ratio_adjusted_net_advances: Option<f64>,
+    /// McClellan 擺盪指標：比例調整淨上漲的 19 日 EMA 減 39 日 EMA。
+    pub(super) mcclellan_oscillator: Option<f64>,
+    /// McClellan 總和指數：擺盪指標的累計值。
+    pub(super) summation_index: Option<f64>,
+    /// 創年內（近 240 個交易日）新高家數。
+    pub(super) new_highs: i32,
+    /// 創年內新低家數。
+    pub(super) new_lows: i32,
+    /// 新高減新低家數。
+    pub(super) net_new_highs: i32,
+    /// 股價高於五日均線的家數比例（%）。
+    pub(super) pct_above_ma5: Option<f64>,
+    /// 股價高於二十日均線的家數比例（%）。
+    pub(super) pct_above_ma20: Option<f64>,
+    /// 股價高於六十日均線的家數比例（%）。
+    pub(super) pct_above_ma60: Option<f64>,
+    /// 股價高於一百二十日均線的家數比例（%）。
+    pub(super) pct_above_ma120: Option<f64>,
+    /// 股價高於二百四十日均線的家數比例（%）。
+    pub(super) pct_above_ma240: Option<f64>,
+}
+
+/// 市場廣度指標成功回應。
+#[derive(Debug, Serialize, ToSchema)]
+pub(super) struct MarketBreadthIndicatorResponse {
+    /// 市場名稱：`all`、`twse` 或 `tpex`。
+    pub(super) market: String,
+    /// `history[0]` 的日期。
+    pub(super) data_as_of: String,
+    /// 由新到舊的交易日指標序列。
+    pub(super) history: Vec<MarketBreadthIndicatorPoint>,
+}
+
+/// 單一產業在某個交易日的指標。
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub(super) struct IndustryMetric {
+    /// 產業分類編號。
+    pub(super) industry_id: i32,
+    /// 產業名稱。
+    pub(super) industry_name: String,
+    /// 交易日，格式 `YYYY-MM-DD`。
+    pub(super) date: String,
+    /// 納入統計的上市櫃股票數。
+    pub(super) stocks: i32,
+    /// 上漲家數。
+    pub(super) advances: i32,
+    /// 下跌家數。
+    pub(super) declines: i32,
+    /// 平盤家數。
+    pub(super) unchanged: i32,
+    /// 以前一日市值加權的當日報酬率（%）。
+    pub(super) cap_weighted_return: Option<f64>,
+    /// 個股當日報酬率的簡單平均（%）。
+    pub(super) equal_weighted_return: Option<f64>,
+    /// 產業指數：市值加權報酬逐日連乘，自開始計算日的 100 點起算。
+    pub(super) index_level: Option<f64>,
+    /// 成交金額合計。
+    pub(super) trade_value: Option<f64>,
+    /// 成交金額佔全體上市櫃股票的比例（%）。
+    pub(super) turnover_share: Option<f64>,
+    /// 營收年增率採用的營收月份，格式 `YYYYMM`；次月 10 日起才改用新月份。
+    pub(super) revenue_period: Option<i64>,
+    /// 該月份有去年同月營收可比較的公司數。
+    pub(super) revenue_companies: i32,
+    /// 產業合計月營收年增率（%）。
+    pub(super) revenue_yoy: Option<f64>,
+}
+
+/// 產業指標 endpoint 回應。
+#[derive(Debug, Serialize, ToSchema)]
+pub(super) struct IndustryMetricsResponse {
+    /// 指標的交易日。
+    pub(super) data_as_of: String,
+    /// 依產業編號排序的全部產業指標。
+    pub(super) industries: Vec<IndustryMetric>,
+}
+
+/// 單一產業在某個回顧期間相對加權指數的強弱。
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub(super) struct RotationWindowStrength {
+    /// 回顧期間：`1w`（5 個交易日）、`1m`（20 個交易日）或 `3m`（60 個交易日）。
+    pub(super) window: &'static str,
+    /// 起點交易日，格式 `YYYY-MM-DD`。
+    pub(super) from: String,
+    /// 產業指數報酬率（%）。
+    pub(super) r#return: Option<f64>,
+    /// 加權指數同期報酬率（%）。
+    pub(super) benchmark_return: Option<f64>,
+    /// 相對強弱（%）：(1 + 產業報酬) / (1 + 加權指數報酬) − 1。
+    pub(super) relative_strength: Option<f64>,
+    /// 依相對強弱由強到弱的一起始名次；缺加權指數時為 null。
+    pub(super) rank: Option<u32>,
+}
+
+/// 類股輪動中的單一產業。
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub(super) struct SectorRotationItem {
+    /// 產業分類編號。
+    pub(super) industry_id: i32,
+    /// 產業名稱。
+    pub(super) industry_name: String,
+    /// 終點日納入統計的股票數。
+    pub(super) stocks: i32,
+    /// 終點日產業指數點位。
+    pub(super) index_level: Option<f64>,
+    /// 各回顧期間的表現，由短至長；歷史不足的期間省略。
+    pub(super) windows: Vec<RotationWindowStrength>,
+}
+
+/// 類股輪動 endpoint 回應。
+#[derive(Debug, Serialize, ToSchema)]
+pub(super) struct SectorRotationResponse {
+    /// 終點交易日。
+    pub(super) data_as_of: String,
+    /// 比較基準，固定為 `TAIEX`。
+    pub(super) benchmark: &'static str,
+    /// 排序依據的回顧期間。
+    pub(super) sort: &'static str,
+    /// 依 `sort` 期間名次排列的產業。
+    pub(super) industries: Vec<SectorRotationItem>,
+}
+
 /// 殖利率排行中的單一股票。
 #[derive(Debug, Clone, Serialize, ToSchema)]
 pub(super) struct DividendYieldRank {
@@ -670,7 +1026,7 @@ pub(super) struct DividendYieldRankingResponse {
 
 /// 條件選股結果中的單一股票。
 ///
-/// 四組來源期間即使已超過新鮮度上限仍會保留，方便呼叫端判斷資料為何被
+/// 五組來源期間即使已超過新鮮度上限仍會保留，方便呼叫端判斷資料為何被
 /// 轉成 `null`；過期的指標本身不參與篩選，也不會被誤當成目前數值。
 #[derive(Debug, Clone, Serialize, ToSchema)]
 pub(super) struct ScreenedStock {
@@ -694,14 +1050,42 @@ pub(super) struct ScreenedStock {
     pub(super) valuation_band: Option<String>,
     /// 最新且仍在 31 天內的估值百分比；缺值或過期時為 `null`。
     pub(super) valuation_percentage: Option<f64>,
+    /// 最新且仍在 7 天內的券資比百分比；缺值、融資餘額為 0 或過期時為 `null`。
+    pub(super) short_to_margin_ratio_percent: Option<f64>,
+    /// 最新且仍在 7 天內的融資使用率百分比；缺值、限額為 0 或過期時為 `null`。
+    pub(super) margin_utilization_percent: Option<f64>,
+    /// 最新且仍在 31 天內、當日殖利率在自身歷史中的百分位；缺值或過期時為 `null`。
+    pub(super) yield_percentile: Option<f64>,
+    /// 最新且仍在 31 天內、當日本益比在自身歷史中的百分位；缺值或過期時為 `null`。
+    pub(super) per_percentile: Option<f64>,
+    /// 最新且仍在 31 天內、當日股價淨值比在自身歷史中的百分位；缺值或過期時為 `null`。
+    pub(super) pbr_percentile: Option<f64>,
+    /// 最新且仍在兩季內的 Piotroski F-score（0–9）；缺值或過期時為 `null`。
+    pub(super) f_score: Option<i16>,
+    /// 最新且仍在兩季內的 Altman Z-score；缺值或過期時為 `null`。
+    pub(super) altman_z_score: Option<f64>,
+    /// 最新且仍在兩季內的應計比率；缺值或過期時為 `null`。
+    pub(super) accruals_ratio: Option<f64>,
+    /// 最新且仍在兩季內的自由現金流量殖利率百分比；缺值或過期時為 `null`。
+    pub(super) fcf_yield_percent: Option<f64>,
+    /// 最新且仍在兩季內的利息保障倍數；缺值或過期時為 `null`。
+    pub(super) interest_coverage: Option<f64>,
+    /// 最新且仍在兩季內的負債權益比；缺值或過期時為 `null`。
+    pub(super) debt_to_equity: Option<f64>,
     /// 該股票最新營收月份，格式 `YYYY-MM`；過期時仍保留。
     pub(super) revenue_month: Option<String>,
     /// 該股票最新季度財報期間，格式 `YYYY-Q1`～`YYYY-Q4`；過期時仍保留。
     pub(super) financial_period: Option<String>,
+    /// 該股票最新財務體質分數期間，格式 `YYYY-Q1`～`YYYY-Q4`；過期時仍保留。
+    pub(super) health_period: Option<String>,
     /// 該股票最新估值日期，格式 `YYYY-MM-DD`；過期時仍保留。
     pub(super) valuation_date: Option<String>,
     /// 該股票最新殖利率日期，格式 `YYYY-MM-DD`；過期時仍保留。
     pub(super) yield_date: Option<String>,
+    /// 該股票最新融資融券日期，格式 `YYYY-MM-DD`；過期時仍保留。
+    pub(super) margin_date: Option<String>,
+    /// 該股票最新歷史估值區間基準日，格式 `YYYY-MM-DD`；過期時仍保留。
+    pub(super) band_date: Option<String>,
 }
 
 /// 條件選股成功回應（§3.4 envelope）。
@@ -732,6 +1116,38 @@ pub(super) struct MarketIndexPoint {
     pub(super) transaction: Option<f64>,
     /// 成交股數。
     pub(super) trading_volume: Option<f64>,
+    /// 同日期交所期貨選擇權摘要；期交所資料尚未寫入或該日無資料時為 `null`。
+    pub(super) derivatives: Option<MarketDerivativesSummary>,
+}
+
+/// 單一交易日的臺指期貨與選擇權摘要，隨大盤指數資料點一起輸出。
+///
+/// 期貨欄位取臺股期貨（TX）近月一般交易時段；法人部位為 TX 多空未平倉
+/// 口數淨額（正值偏多）。個別來源缺漏時對應欄位為 `null`，不補 0。
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub(super) struct MarketDerivativesSummary {
+    /// 近月契約到期月份（`YYYYMM`）。
+    pub(super) futures_contract_month: Option<String>,
+    /// 近月收盤價。
+    pub(super) futures_close: Option<f64>,
+    /// 近月漲跌點數。
+    pub(super) futures_change: Option<f64>,
+    /// 近月結算價。
+    pub(super) futures_settlement_price: Option<f64>,
+    /// TX 全部月份未沖銷契約數合計（口）。
+    pub(super) futures_open_interest: i64,
+    /// 基差：近月收盤價減加權指數收盤（點），正值為正價差。
+    pub(super) basis: Option<f64>,
+    /// 外資及陸資 TX 多空未平倉口數淨額。
+    pub(super) foreign_net_open_interest: Option<i64>,
+    /// 投信 TX 多空未平倉口數淨額。
+    pub(super) investment_trust_net_open_interest: Option<i64>,
+    /// 自營商 TX 多空未平倉口數淨額。
+    pub(super) dealer_net_open_interest: Option<i64>,
+    /// 臺指選擇權買賣權成交量比率（%）。
+    pub(super) put_call_volume_ratio_percent: Option<f64>,
+    /// 臺指選擇權買賣權未平倉量比率（%）。
+    pub(super) put_call_open_interest_ratio_percent: Option<f64>,
 }
 
 /// 大盤指數歷史的成功回應（§3.4 envelope）。
@@ -812,6 +1228,197 @@ pub(super) struct QfiiHoldingRankingResponse {
     pub(super) stocks: Vec<QfiiHolding>,
 }
 
+/// 個股單日三大法人買賣超，單位為股，賣超為負數。
+///
+/// 「外資」依交易所口徑不含外資自營商，外資自營商另列；自營商為自行買賣與
+/// 避險合計。
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub(super) struct InstitutionalTrade {
+    /// 交易日期（YYYY-MM-DD）。
+    pub(super) date: String,
+    /// 外陸資買進股數（不含外資自營商）。
+    pub(super) foreign_buy: i64,
+    /// 外陸資賣出股數（不含外資自營商）。
+    pub(super) foreign_sell: i64,
+    /// 外陸資買賣超股數（不含外資自營商）。
+    pub(super) foreign_net: i64,
+    /// 外資自營商買賣超股數。
+    pub(super) foreign_dealer_net: i64,
+    /// 投信買進股數。
+    pub(super) investment_trust_buy: i64,
+    /// 投信賣出股數。
+    pub(super) investment_trust_sell: i64,
+    /// 投信買賣超股數。
+    pub(super) investment_trust_net: i64,
+    /// 自營商買進股數（自行買賣＋避險）。
+    pub(super) dealer_buy: i64,
+    /// 自營商賣出股數（自行買賣＋避險）。
+    pub(super) dealer_sell: i64,
+    /// 自營商買賣超股數（自行買賣＋避險）。
+    pub(super) dealer_net: i64,
+    /// 自營商（自行買賣）買賣超股數。
+    pub(super) dealer_proprietary_net: i64,
+    /// 自營商（避險）買賣超股數。
+    pub(super) dealer_hedge_net: i64,
+    /// 三大法人買賣超股數合計。
+    pub(super) total_net: i64,
+}
+
+/// 個股三大法人買賣超歷史的成功回應。
+#[derive(Debug, Serialize, ToSchema)]
+pub(super) struct InstitutionalTradeHistoryResponse {
+    /// 股票代號。
+    pub(super) stock_symbol: String,
+    /// 回傳資料中最新的交易日；查無資料時為 `null`。
+    pub(super) data_as_of: Option<String>,
+    /// 依交易日期由新到舊排序的買賣超。
+    pub(super) trades: Vec<InstitutionalTrade>,
+}
+
+/// 個股單日融資融券，單位為張。
+///
+/// 融券的賣出為放空、買進為回補；增減為今日餘額減前日餘額。
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub(super) struct MarginTrading {
+    /// 交易日期（YYYY-MM-DD）。
+    pub(super) date: String,
+    /// 融資買進。
+    pub(super) margin_buy: i64,
+    /// 融資賣出。
+    pub(super) margin_sell: i64,
+    /// 融資現金償還。
+    pub(super) margin_redemption: i64,
+    /// 融資今日餘額。
+    pub(super) margin_balance: i64,
+    /// 融資餘額增減。
+    pub(super) margin_change: i64,
+    /// 融資限額。
+    pub(super) margin_quota: i64,
+    /// 融資使用率百分比；限額為 0 時為 `null`。
+    pub(super) margin_utilization_percent: Option<f64>,
+    /// 融券賣出（放空）。
+    pub(super) short_sell: i64,
+    /// 融券買進（回補）。
+    pub(super) short_cover: i64,
+    /// 融券現券償還。
+    pub(super) short_redemption: i64,
+    /// 融券今日餘額。
+    pub(super) short_balance: i64,
+    /// 融券餘額增減。
+    pub(super) short_change: i64,
+    /// 融券限額。
+    pub(super) short_quota: i64,
+    /// 資券互抵。
+    pub(super) offset: i64,
+    /// 券資比百分比；融資餘額為 0 時為 `null`。
+    pub(super) short_to_margin_ratio_percent: Option<f64>,
+}
+
+/// 個股融資融券歷史的成功回應。
+#[derive(Debug, Serialize, ToSchema)]
+pub(super) struct MarginTradingHistoryResponse {
+    /// 股票代號。
+    pub(super) stock_symbol: String,
+    /// 回傳資料中最新的交易日；查無資料時為 `null`。
+    pub(super) data_as_of: Option<String>,
+    /// 依交易日期由新到舊排序的融資融券。
+    pub(super) records: Vec<MarginTrading>,
+}
+
+/// 集保戶股權分散表的單一持股分級。
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub(super) struct ShareholdingTier {
+    /// 持股分級：1–15 為持股區間，16 為差異數調整，17 為合計。
+    pub(super) level: i32,
+    /// 人數。
+    pub(super) holders: i64,
+    /// 股數。
+    pub(super) shares: i64,
+    /// 占集保庫存數比例（%）。
+    pub(super) percentage: Option<f64>,
+}
+
+/// 單週股權分散的大戶指標與週變化。
+///
+/// 大戶依 TDCC 分級定義：400 張以上為第 12–15 級，1,000 張以上為第 15 級。
+/// 週變化以前一個有資料的週次計算，最舊一週沒有前值時為 `null`。
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub(super) struct ShareholdingWeek {
+    /// 資料日期（YYYY-MM-DD）。
+    pub(super) date: String,
+    /// 總股東人數。
+    pub(super) total_holders: i64,
+    /// 400 張以上大戶人數。
+    pub(super) over_400_lots_holders: i64,
+    /// 400 張以上大戶持股比例（%）。
+    pub(super) over_400_lots_percentage: Option<f64>,
+    /// 1,000 張以上大戶持股比例（%）。
+    pub(super) over_1000_lots_percentage: Option<f64>,
+    /// 400 張以上大戶持股比例週變化（百分點）。
+    pub(super) over_400_lots_percentage_change: Option<f64>,
+    /// 1,000 張以上大戶持股比例週變化（百分點）。
+    pub(super) over_1000_lots_percentage_change: Option<f64>,
+    /// 400 張以上大戶人數週變化。
+    pub(super) over_400_lots_holders_change: Option<i64>,
+    /// 總股東人數週變化。
+    pub(super) total_holders_change: Option<i64>,
+}
+
+/// 個股集保戶股權分散的成功回應。
+#[derive(Debug, Serialize, ToSchema)]
+pub(super) struct ShareholdingDistributionResponse {
+    /// 股票代號。
+    pub(super) stock_symbol: String,
+    /// 最新一週的資料日期；查無資料時為 `null`。
+    pub(super) data_as_of: Option<String>,
+    /// 最新一週的全部持股分級；查無資料時為空陣列。
+    pub(super) latest_tiers: Vec<ShareholdingTier>,
+    /// 依資料日期由新到舊排序的週指標。
+    pub(super) weeks: Vec<ShareholdingWeek>,
+}
+
+/// 三大法人買賣超排行中的單一股票。
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub(super) struct InstitutionalTradeRank {
+    /// 一起始的名次。
+    pub(super) rank: u32,
+    /// 股票代號。
+    pub(super) stock_symbol: String,
+    /// 股票名稱。
+    pub(super) name: String,
+    /// 市場編號（上市 2、上櫃 4）。
+    pub(super) market_id: i32,
+    /// 產業分類編號。
+    pub(super) industry_id: i32,
+    /// 指定法人類別的買賣超股數（排行依據）。
+    pub(super) net_shares: i64,
+    /// 當日收盤價；查無當日報價時為 `null`。
+    pub(super) closing_price: Option<f64>,
+    /// 以收盤價估算的買賣超金額（元）；查無當日報價時為 `null`。
+    pub(super) estimated_net_value: Option<f64>,
+    /// 外陸資買賣超股數（不含外資自營商）。
+    pub(super) foreign_net: i64,
+    /// 投信買賣超股數。
+    pub(super) investment_trust_net: i64,
+    /// 自營商買賣超股數。
+    pub(super) dealer_net: i64,
+    /// 三大法人買賣超股數合計。
+    pub(super) total_net: i64,
+}
+
+/// 三大法人買賣超排行的成功回應。
+#[derive(Debug, Serialize, ToSchema)]
+pub(super) struct InstitutionalTradeRankingResponse {
+    /// 排行所用的交易日；資料表為空時為 `null`。
+    pub(super) data_as_of: Option<String>,
+    /// 法人類別：`foreign`、`investment_trust`、`dealer` 或 `total`。
+    pub(super) investor: String,
+    /// 方向：`buy`（買超）或 `sell`（賣超）。
+    pub(super) direction: String,
+    /// 依買賣超股數絕對值由大到小、同值股票代號由小到大排序的股票。
+    pub(super) stocks: Vec<InstitutionalTradeRank>,
+}
+
 /// 搜尋 endpoint 的 query string。
 #[derive(Debug, Deserialize, IntoParams)]
 pub(super) struct SearchParams {
@@ -872,6 +1479,24 @@ pub(super) struct ValuationParams {
     pub(super) date: Option<String>,
 }
 
+/// 估價訊號回測 endpoint 的 query string。
+#[derive(Debug, Deserialize, IntoParams)]
+pub(super) struct EstimateBacktestParams {
+    /// 持有期間：`3m`、`6m` 或 `12m`；未提供時回傳全部。
+    pub(super) horizon: Option<String>,
+    /// 統計範圍：`all`（預設）、`market` 或 `industry`。
+    #[param(default = "all")]
+    pub(super) scope: Option<String>,
+    /// 範圍編號：`market` 為市場編號（上市 2、上櫃 4），`industry` 為產業
+    /// 分類編號；未提供時回傳該範圍的全部市場或產業。`scope=all` 時不可指定。
+    #[param(minimum = 1)]
+    pub(super) scope_id: Option<i32>,
+    /// 估價日下限（含），格式 `YYYY-MM-DD`。
+    pub(super) from: Option<String>,
+    /// 估價日上限（含），格式 `YYYY-MM-DD`。
+    pub(super) to: Option<String>,
+}
+
 /// 市場廣度 endpoint 的 query string。
 #[derive(Debug, Deserialize, IntoParams)]
 pub(super) struct MarketBreadthParams {
@@ -885,6 +1510,36 @@ pub(super) struct MarketBreadthParams {
     pub(super) days: Option<u8>,
 }
 
+/// 市場廣度指標 endpoint 的 query string。
+#[derive(Debug, Deserialize, IntoParams)]
+pub(super) struct MarketBreadthIndicatorParams {
+    /// 市場：`all`（預設）、`twse` 或 `tpex`。
+    #[param(value_type = MarketParamValue, inline, default = "all")]
+    pub(super) market: Option<String>,
+    /// 查詢截止日，格式 `YYYY-MM-DD`；未提供時取最新資料。
+    pub(super) date: Option<String>,
+    /// 最近有資料的交易日筆數，預設 120，範圍 1–500。
+    #[param(minimum = 1, maximum = 500, default = 120)]
+    pub(super) days: Option<u16>,
+}
+
+/// 產業指標 endpoint 的 query string。
+#[derive(Debug, Deserialize, IntoParams)]
+pub(super) struct IndustryMetricsParams {
+    /// 查詢截止日，格式 `YYYY-MM-DD`；未提供時取最新資料。
+    pub(super) date: Option<String>,
+}
+
+/// 類股輪動 endpoint 的 query string。
+#[derive(Debug, Deserialize, IntoParams)]
+pub(super) struct SectorRotationParams {
+    /// 查詢截止日，格式 `YYYY-MM-DD`；未提供時取最新資料。
+    pub(super) date: Option<String>,
+    /// 排序依據的回顧期間：`1w`、`1m`（預設）或 `3m`。
+    #[param(default = "1m")]
+    pub(super) sort: Option<String>,
+}
+
 /// 殖利率排行 endpoint 的 query string。
 #[derive(Debug, Deserialize, IntoParams)]
 pub(super) struct DividendYieldRankingParams {
@@ -948,10 +1603,64 @@ pub(super) struct QfiiHoldingRankingParams {
     pub(super) limit: Option<u8>,
 }
 
+/// 個股三大法人買賣超歷史 endpoint 的 query string。
+#[derive(Debug, Deserialize, IntoParams)]
+pub(super) struct InstitutionalTradeHistoryParams {
+    /// 起始日期，格式 `YYYY-MM-DD`。
+    pub(super) from: Option<String>,
+    /// 結束日期，格式 `YYYY-MM-DD`。
+    pub(super) to: Option<String>,
+    /// 最多回傳筆數，預設 20，範圍 1–250。
+    #[param(minimum = 1, maximum = 250, default = 20)]
+    pub(super) limit: Option<u16>,
+}
+
+/// 個股融資融券歷史 endpoint 的 query string。
+#[derive(Debug, Deserialize, IntoParams)]
+pub(super) struct MarginTradingHistoryParams {
+    /// 起始日期，格式 `YYYY-MM-DD`。
+    pub(super) from: Option<String>,
+    /// 結束日期，格式 `YYYY-MM-DD`。
+    pub(super) to: Option<String>,
+    /// 最多回傳筆數，預設 20，範圍 1–250。
+    #[param(minimum = 1, maximum = 250, default = 20)]
+    pub(super) limit: Option<u16>,
+}
+
+/// 個股集保戶股權分散 endpoint 的 query string。
+#[derive(Debug, Deserialize, IntoParams)]
+pub(super) struct ShareholdingDistributionParams {
+    /// 回傳週數，預設 12，範圍 1–104。
+    #[param(minimum = 1, maximum = 104, default = 12)]
+    pub(super) weeks: Option<u8>,
+}
+
+/// 三大法人買賣超排行 endpoint 的 query string。
+#[derive(Debug, Deserialize, IntoParams)]
+pub(super) struct InstitutionalTradeRankingParams {
+    /// 查詢截止日，格式 `YYYY-MM-DD`；取不晚於此日的最近交易日，未提供時取最新資料。
+    pub(super) date: Option<String>,
+    /// 市場：`all`（預設）、`twse` 或 `tpex`。
+    #[param(value_type = MarketParamValue, inline, default = "all")]
+    pub(super) market: Option<String>,
+    /// 可選的正整數產業分類編號。
+    #[param(minimum = 1)]
+    pub(super) industry_id: Option<i32>,
+    /// 法人類別：`total`（預設）、`foreign`、`investment_trust` 或 `dealer`。
+    #[param(value_type = InstitutionalInvestorParamValue, inline, default = "total")]
+    pub(super) investor: Option<String>,
+    /// 方向：`buy`（預設，買超）或 `sell`（賣超）。
+    #[param(value_type = TradeDirectionParamValue, inline, default = "buy")]
+    pub(super) direction: Option<String>,
+    /// 最多回傳筆數，預設 20，範圍 1–50。
+    #[param(minimum = 1, maximum = 50, default = 20)]
+    pub(super) limit: Option<u8>,
+}
+
 /// 條件選股 endpoint 的固定白名單 query string。
 ///
 /// 所有數值欄位先在 handler 驗證範圍，再轉成 PostgreSQL `NUMERIC` 綁定值；
-/// `sort_by` 與 `sort_order` 只會映射到程式內建的十二個排序分支。
+/// `sort_by` 與 `sort_order` 只會映射到程式內建的十六個排序分支。
 #[derive(Debug, Deserialize, IntoParams)]
 pub(super) struct StockScreeningParams {
     /// 市場：`all`（預設）、`twse` 或 `tpex`。
@@ -975,6 +1684,42 @@ pub(super) struct StockScreeningParams {
     /// 最低殖利率百分比，範圍 0–1000。
     #[param(minimum = 0, maximum = 1000)]
     pub(super) min_dividend_yield_percent: Option<f64>,
+    /// 最低券資比百分比，範圍 0–10000。
+    #[param(minimum = 0, maximum = 10000)]
+    pub(super) min_short_to_margin_ratio_percent: Option<f64>,
+    /// 最高融資使用率百分比，範圍 0–100。
+    #[param(minimum = 0, maximum = 100)]
+    pub(super) max_margin_utilization_percent: Option<f64>,
+    /// 歷史估值百分位的回顧年數：5（預設）或 10。
+    #[param(minimum = 5, maximum = 10, default = 5)]
+    pub(super) band_years: Option<i32>,
+    /// 殖利率在自身歷史中的最低百分位，範圍 0–100。
+    #[param(minimum = 0, maximum = 100)]
+    pub(super) min_yield_percentile: Option<f64>,
+    /// 本益比在自身歷史中的最高百分位，範圍 0–100。
+    #[param(minimum = 0, maximum = 100)]
+    pub(super) max_per_percentile: Option<f64>,
+    /// 股價淨值比在自身歷史中的最高百分位，範圍 0–100。
+    #[param(minimum = 0, maximum = 100)]
+    pub(super) max_pbr_percentile: Option<f64>,
+    /// 最低 Piotroski F-score，範圍 0–9。
+    #[param(minimum = 0, maximum = 9)]
+    pub(super) min_f_score: Option<u8>,
+    /// 最低 Altman Z-score，範圍 -1000–1000。
+    #[param(minimum = -1000, maximum = 1000)]
+    pub(super) min_altman_z_score: Option<f64>,
+    /// 最高應計比率，範圍 -10–10。
+    #[param(minimum = -10, maximum = 10)]
+    pub(super) max_accruals_ratio: Option<f64>,
+    /// 最低自由現金流量殖利率百分比，範圍 -1000–1000。
+    #[param(minimum = -1000, maximum = 1000)]
+    pub(super) min_fcf_yield_percent: Option<f64>,
+    /// 最低利息保障倍數，範圍 -100000–100000。
+    #[param(minimum = -100000, maximum = 100000)]
+    pub(super) min_interest_coverage: Option<f64>,
+    /// 最高負債權益比，範圍 0–1000。
+    #[param(minimum = 0, maximum = 1000)]
+    pub(super) max_debt_to_equity: Option<f64>,
     /// 排序欄位固定 enum；預設 `stock_symbol`。
     #[param(value_type = StockScreenSortValue, inline, default = "stock_symbol")]
     pub(super) sort_by: Option<String>,
@@ -1204,3 +1949,11 @@ pub(super) struct CagrSymbolResponse {
     /// 全部八個期間，依期間長度由短至長；含資料不足者。
     pub(super) items: Vec<CagrPeriodItem>,
 }
+
+/// 即時報價推送（SSE、WebSocket）的 query string。
+#[derive(Debug, Deserialize, IntoParams)]
+pub(super) struct QuoteStreamParams {
+    /// 以逗號分隔的股票代號，最多 200 檔。
+    #[serde(default)]
+    pub(super) symbols: String,
+}
@@ -11,22 +11,52 @@ use axum::{
 };
 use chrono::{DateTime, Datelike, Local, NaiveDate, Utc};
 use rust_decimal::Decimal;
+use std::collections::HashMap;
 use std::str::FromStr;
 
 use super::dto::{
     CagrCoverageInfo, CagrPeriodItem, CagrRankingItem, CagrRankingParams, CagrRankingResponse,
     CagrSummary, CagrSymbolParams, CagrSymbolResponse, DailyQuote, Dividend, DividendCalendarEvent,
-    DividendCalendarParams, DividendCalendarResponse, DividendHistoryParams,
-    DividendHistoryResponse, DividendYieldRank, DividendYieldRankingParams,
-    DividendYieldRankingResponse, ErrorBody, FinancialStatement, FinancialStatementHistoryResponse,
-    HealthResponse, HistoricalQuote, HistoryParams, LatestQuoteResponse, MarketBreadth,
-    MarketBreadthParams, MarketBreadthResponse, MarketIndexHistoryParams,
-    MarketIndexHistoryResponse, MarketIndexPoint, MonthlyRevenue, MonthlyRevenueResponse,
-    PriceHistoryResponse, QfiiHolding, QfiiHoldingRankingParams, QfiiHoldingRankingResponse,
-    QuoteHistoryRecord, RealtimeSnapshotResponse, RevenueHistoryParams, ScreenedStock,
-    SearchParams, SearchResponse, StatementHistoryParams, Stock, StockProfile,
-    StockScreeningParams, StockScreeningResponse, StockValuation, StockValuationResponse,
-    ValuationParams,
+    DividendCalendarParams, DividendCalendarResponse, DividendGapFill, DividendGapFillSummary,
+    DividendHistoryParams, DividendHistoryResponse, DividendYieldRank, DividendYieldRankingParams,
+    DividendYieldRankingResponse, ErrorBody, EstimateBacktestParams, EstimateBacktestReport,
+    EstimateBacktestResponse, EstimateBacktestSignal, FinancialHealth, FinancialStatement,
+    FinancialStatementHistoryResponse, HealthResponse, HistoricalQuote, HistoricalValuationBand,
+    HistoryParams, IndustryMetric, IndustryMetricsParams, IndustryMetricsResponse,
+    InstitutionalTrade, InstitutionalTradeHistoryParams, InstitutionalTradeHistoryResponse,
+    InstitutionalTradeRank, InstitutionalTradeRankingParams, InstitutionalTradeRankingResponse,
+    LatestQuoteResponse, MarginTrading, MarginTradingHistoryParams, MarginTradingHistoryResponse,
+    MarketBreadth, MarketBreadthIndicatorParams, MarketBreadthIndicatorPoint,
+    MarketBreadthIndicatorResponse, MarketBreadthParams, MarketBreadthResponse,
+    MarketDerivativesSummary, MarketIndexHistoryParams, MarketIndexHistoryResponse,
+    MarketIndexPoint, MonthlyRevenue, MonthlyRevenueResponse, PriceHistoryResponse, QfiiHolding,
+    QfiiHoldingRankingParams, QfiiHoldingRankingResponse, QuoteHistoryRecord,
+    RealtimeSnapshotResponse, RevenueHistoryParams, RotationWindowStrength, ScreenedStock,
+    SearchParams, SearchResponse, SectorRotationItem, SectorRotationParams, SectorRotationResponse,
+    ShareholdingDistributionParams, ShareholdingDistributionResponse, ShareholdingTier,
+    ShareholdingWeek, StatementHistoryParams, Stock, StockProfile, StockScreeningParams,
+    StockScreeningResponse, StockValuation, StockValuationResponse, TradingSurveillance,
+    ValuationModelResult, ValuationParams,
+};
+use crate::domain::breadth::{BreadthIndicator, BreadthIndicatorRepository};
+use crate::domain::chip::{
+    InstitutionalTrade as DomainInstitutionalTrade, InstitutionalTradeRepository,
+    LEVEL_OVER_400_LOTS, LEVEL_OVER_1000_LOTS, MarginTrading as DomainMarginTrading,
+    MarginTradingRepository, ShareholdingDistribution as DomainShareholdingDistribution,
+    ShareholdingDistributionRepository,
+};
+use crate::domain::derivatives::{
+    CONTRACT_TX, DerivativesDailySnapshot, DerivativesRepository, TraderType,
+};
+use crate::domain::dividend::{
+    gap_fill::{DividendGapFill as DomainDividendGapFill, GapFillSummary},
+    repository::DividendGapFillRepository,
+};
+use crate::domain::financial::{
+    health::FinancialHealthScore, repository::FinancialHealthRepository,
+};
+use crate::domain::industry::{
+    IndustryMetricRepository, IndustryRotation, IndustrySnapshot, RotationWindow,
 };
 use crate::domain::performance::entity::{
     CagrMetric, CagrPeriod, PRINCIPAL, SimulationOutcome, StockCagr as DomainStockCagr,
@@ -35,8 +65,31 @@ use crate::domain::performance::query::{
     CagrRankingItem as DomainCagrRankingItem, CagrRankingQuery, CagrSortKey,
 };
 use crate::domain::performance::repository::CagrRepository;
-use crate::infra::database::repository::performance::PgCagrRepository;
-use crate::infra::{cache::SHARE, database};
+use crate::domain::surveillance::{SurveillancePeriod, SurveillanceRepository};
+use crate::domain::valuation::{
+    BAND_YEARS, BacktestHorizon, BacktestReport, BacktestReportQuery, EstimateBacktestRepository,
+    ModelEstimate, ValuationBand, ValuationBandRepository, ValuationModelRepository,
+};
+use crate::infra::database::repository::{
+    breadth::PgBreadthIndicatorRepository,
+    chip::{
+        PgInstitutionalTradeRepository, PgMarginTradingRepository,
+        PgShareholdingDistributionRepository,
+    },
+    derivatives::PgDerivativesRepository,
+    dividend_gap_fill::PgDividendGapFillRepository,
+    estimate_backtest::PgEstimateBacktestRepository,
+    financial_health::PgFinancialHealthRepository,
+    industry::PgIndustryMetricRepository,
+    performance::PgCagrRepository,
+    surveillance::PgSurveillanceRepository,
+    valuation_band::PgValuationBandRepository,
+    valuation_model::PgValuationModelRepository,
+};
+use crate::infra::{
+    cache::{RealtimeSnapshot, SHARE},
+    database,
+};
 
 /// 產生不含內部實作細節的統一 JSON 錯誤回應。
 pub(super) fn error_response(status: StatusCode, message: &str) -> Response {
@@ -159,11 +212,16 @@ pub(super) async fn price_history(
 #[utoipa::path(get, path = "/api/v1/stocks/{symbol}/profile", tag = "data-api", params(("symbol" = String, Path, description = "股票代號")), responses((status = 200, body = StockProfile), (status = 401, body = ErrorBody), (status = 404, body = ErrorBody)), security(("bearer_auth" = [])))]
 pub(super) async fn stock_profile(Path(symbol): Path<String>) -> Response {
     let row: Result<Option<ProfileRow>, _> = sqlx::query_as(r#"SELECT s.stock_symbol, s."SecurityCode" AS security_code, s."Name" AS name, s.stock_exchange_market_id, s.stock_industry_id, s."SuspendListing" AS suspend_listing, s.last_one_eps, s.last_four_eps, s.net_asset_value_per_share, s.return_on_equity, s.weight, s.issued_share, q.date, q.opening_price, q.highest_price, q.lowest_price, q.closing_price, q.change, q.change_range, q.trading_volume, q.transaction, q.trade_value, q.moving_average_5, q.moving_average_10, q.moving_average_20, q.moving_average_60, q.moving_average_120, q.moving_average_240, q.price_earning_ratio, q.record_time, q.updated_time, h.maximum_price, h.maximum_price_date_on, h.minimum_price, h.minimum_price_date_on, h."maximum_price-to-book_ratio" AS maximum_price_to_book_ratio, h."maximum_price-to-book_ratio_date_on" AS maximum_price_to_book_ratio_date_on, h."minimum_price-to-book_ratio" AS minimum_price_to_book_ratio, h."minimum_price-to-book_ratio_date_on" AS minimum_price_to_book_ratio_date_on FROM stocks s LEFT JOIN last_daily_quotes q ON s.stock_symbol = q.stock_symbol LEFT JOIN quote_history_record h ON s."SecurityCode" = h.security_code WHERE s.stock_symbol = $1"#).bind(symbol).fetch_optional(database::get_connection()).await;
-    match row {
-        Ok(Some(row)) => Json::<StockProfile>(row.into()).into_response(),
-        Ok(None) => error_response(StatusCode::NOT_FOUND, "找不到股票代號"),
-        Err(error) => database_error(error),
-    }
+    let mut profile: StockProfile = match row {
+        Ok(Some(row)) => row.into(),
+        Ok(None) => return error_response(StatusCode::NOT_FOUND, "找不到股票代號"),
+        Err(error) => return database_error(error),
+    };
+    profile.trading_surveillance = match active_surveillance(&profile.stock.stock_symbol).await {
+        Ok(items) => items,
+        Err(error) => return repository_error(error),
+    };
+    Json(profile).into_response()
 }
 
 /// 查詢第三方採集的近即時報價快照，不宣稱為交易所保證即時行情。
@@ -175,8 +233,24 @@ pub(super) async fn realtime_snapshot(Path(symbol): Path<String>) -> Response {
     let Some(snapshot) = SHARE.get_stock_snapshot(&symbol) else {
         return error_response(StatusCode::NOT_FOUND, "查無此股票的即時報價快照");
     };
+    // 快照本身來自快取；注意股／處置股查詢失敗只記錄錯誤並回 null，不讓報價跟著失敗。
+    let trading_surveillance = match active_surveillance(&symbol).await {
+        Ok(items) => Some(items),
+        Err(error) => {
+            tracing::error!(?error, "data API surveillance query failed");
+            None
+        }
+    };
+    Json(snapshot_response(snapshot, trading_surveillance)).into_response()
+}
+
+/// 將快取中的即時報價快照轉為 API 回應；推送串流不查注意股／處置股，傳入 `None`。
+pub(super) fn snapshot_response(
+    snapshot: RealtimeSnapshot,
+    trading_surveillance: Option<Vec<TradingSurveillance>>,
+) -> RealtimeSnapshotResponse {
     let convert = |value: Decimal| value.to_string().parse::<f64>().ok();
-    Json(RealtimeSnapshotResponse {
+    RealtimeSnapshotResponse {
         stock_symbol: snapshot.symbol,
         name: snapshot.name,
         price: convert(snapshot.price),
@@ -189,8 +263,28 @@ pub(super) async fn realtime_snapshot(Path(symbol): Path<String>) -> Response {
         volume_lots: convert(snapshot.volume),
         source_site: snapshot.source_site,
         updated_at: snapshot.updated_at.to_rfc3339(),
-    })
-    .into_response()
+        trading_surveillance,
+    }
+}
+
+/// 查詢股票今日仍在期間內的注意股／處置股紀錄。
+async fn active_surveillance(symbol: &str) -> anyhow::Result<Vec<TradingSurveillance>> {
+    let periods = PgSurveillanceRepository::new()
+        .fetch_active_on(Local::now().date_naive(), Some(symbol))
+        .await?;
+    Ok(periods.iter().map(TradingSurveillance::from).collect())
+}
+
+impl From<&SurveillancePeriod> for TradingSurveillance {
+    fn from(period: &SurveillancePeriod) -> Self {
+        Self {
+            kind: period.kind.code(),
+            start_date: period.start_date.to_string(),
+            end_date: period.end_date.to_string(),
+            reason: period.reason.clone(),
+            measure: period.measure.clone(),
+        }
+    }
 }
 
 /// 查詢單一股票的月營收歷史（§4.1）。
@@ -344,21 +438,44 @@ pub(super) async fn dividend_history(
     // 排序（§3.4）：股利所屬年度新到舊 → 同年度依 A、H2、H1、Q4…Q1 →
     // 最後以發放年度 DESC 穩定排序（同一期股利可能分年發放）。
     let rows: Result<Vec<DividendRow>, _> = sqlx::query_as(r#"SELECT "year", year_of_dividend, quarter, cash_dividend, stock_dividend, "sum", earnings_cash_dividend, capital_reserve_cash_dividend, earnings_stock_dividend, capital_reserve_stock_dividend, payout_ratio_cash, payout_ratio_stock, payout_ratio, "ex-dividend_date1" AS ex_dividend_date1, "ex-dividend_date2" AS ex_dividend_date2, payable_date1, payable_date2, updated_time FROM dividend WHERE security_code = $1 AND ($2::int IS NULL OR year_of_dividend >= $2) AND ($3::int IS NULL OR year_of_dividend <= $3) ORDER BY year_of_dividend DESC, CASE quarter WHEN '' THEN 7 WHEN 'H2' THEN 6 WHEN 'H1' THEN 5 WHEN 'Q4' THEN 4 WHEN 'Q3' THEN 3 WHEN 'Q2' THEN 2 WHEN 'Q1' THEN 1 ELSE 0 END DESC, "year" DESC LIMIT $4"#).bind(&symbol).bind(params.from_year).bind(params.to_year).bind(i64::from(limit)).fetch_all(database::get_connection()).await;
-    match rows {
-        Ok(rows) => {
-            let data_as_of = rows
-                .first()
-                .map(|row| format!("{}-{}", row.year_of_dividend, quarter_to_api(&row.quarter)));
-            let dividends = rows.into_iter().map(|row| row.into_dto(&symbol)).collect();
-            Json(DividendHistoryResponse {
-                stock_symbol: symbol,
-                data_as_of,
-                dividends,
-            })
-            .into_response()
-        }
-        Err(error) => database_error(error),
-    }
+    let rows = match rows {
+        Ok(rows) => rows,
+        Err(error) => return database_error(error),
+    };
+    // 填息結果以 (發放年度, 期間) 對應股利列；統計取全部歷史，不受年度篩選影響。
+    let gap_fills = match PgDividendGapFillRepository::new()
+        .fetch_by_security_codes(std::slice::from_ref(&symbol))
+        .await
+    {
+        Ok(gap_fills) => gap_fills,
+        Err(error) => return repository_error(error),
+    };
+    let by_period: HashMap<(i32, &str), &DomainDividendGapFill> = gap_fills
+        .iter()
+        .map(|gap_fill| ((gap_fill.year, gap_fill.quarter.as_str()), gap_fill))
+        .collect();
+    let data_as_of = rows
+        .first()
+        .map(|row| format!("{}-{}", row.year_of_dividend, quarter_to_api(&row.quarter)));
+    let dividends = rows
+        .into_iter()
+        .map(|row| {
+            let gap_fill = by_period
+                .get(&(row.year, row.quarter.as_str()))
+                .map(|gap_fill| DividendGapFill::from(*gap_fill));
+            Dividend {
+                gap_fill,
+                ..row.into_dto(&symbol)
+            }
+        })
+        .collect();
+    Json(DividendHistoryResponse {
+        stock_symbol: symbol,
+        data_as_of,
+        dividends,
+        gap_fill_summary: GapFillSummary::from_events(&gap_fills).map(Into::into),
+    })
+    .into_response()
 }
 
 /// 查詢個股最近有效估值（§4.4）。
@@ -386,20 +503,121 @@ pub(super) async fn stock_valuation(
     // `(security_code, date)` 唯一索引支援代號等值與日期反向搜尋；指定截止日
     // 時的 `$2 - 30` 與截止日合計涵蓋 31 個日曆日。
     let row: Result<Option<ValuationRow>, _> = sqlx::query_as(r#"SELECT security_code, date, closing_price, percentage, year_count, cheap, fair, expensive, price_cheap, price_fair, price_expensive, dividend_cheap, dividend_fair, dividend_expensive, eps_cheap, eps_fair, eps_expensive, pbr_cheap, pbr_fair, pbr_expensive, per_cheap, per_fair, per_expensive FROM estimate WHERE security_code = $1 AND ($2::date IS NULL OR (date <= $2 AND date >= $2 - 30)) ORDER BY date DESC LIMIT 1"#).bind(&symbol).bind(date).fetch_optional(database::get_connection()).await;
-    match row {
-        Ok(row) => {
-            let valuation = row.map(Into::into);
-            let data_as_of = valuation
-                .as_ref()
-                .map(|value: &StockValuation| value.date.clone());
-            Json(StockValuationResponse {
-                stock_symbol: symbol,
-                data_as_of,
-                valuation,
-            })
-            .into_response()
+    let row = match row {
+        Ok(row) => row,
+        Err(error) => return database_error(error),
+    };
+    // 歷史估值區間與 estimate 採同一截止日與 31 天視窗，但各自取最新基準日。
+    let historical_bands = match PgValuationBandRepository::new()
+        .fetch_latest(&symbol, date)
+        .await
+    {
+        Ok(bands) => bands.iter().map(Into::into).collect(),
+        Err(error) => return repository_error(error),
+    };
+    let models = match PgValuationModelRepository::new()
+        .fetch_estimates(&symbol, date)
+        .await
+    {
+        Ok(estimates) => estimates.iter().map(Into::into).collect(),
+        Err(error) => return repository_error(error),
+    };
+    // 財務體質以季末日期對齊查詢截止日，避免回看歷史時用到當時尚未存在的季度。
+    let financial_health = match PgFinancialHealthRepository::new()
+        .fetch_latest(&symbol, date)
+        .await
+    {
+        Ok(score) => score.as_ref().map(Into::into),
+        Err(error) => return repository_error(error),
+    };
+    let valuation = row.map(Into::into);
+    let data_as_of = valuation
+        .as_ref()
+        .map(|value: &StockValuation| value.date.clone());
+    Json(StockValuationResponse {
+        stock_symbol: symbol,
+        data_as_of,
+        valuation,
+        historical_bands,
+        models,
+        financial_health,
+    })
+    .into_response()
+}
+
+/// 便宜／合理／昂貴價訊號的向後報酬回測。
+///
+/// 每個歷史估價日依收盤價把股票分成低估、合理、高估三組，量測之後 3／6／12
+/// 個月的含息（不再投入）報酬，再跨估價日彙總；`spread` 與
+/// `undervalued_beat_ratio` 是判斷訊號是否值得信任的主要依據。
+///
+/// # Errors
+///
+/// 參數不合法回 422，驗證失敗回 401，倉儲查詢失敗回不含 SQL 細節的 500。
+#[utoipa::path(get, path = "/api/v1/market/estimate-backtest", tag = "data-api", params(EstimateBacktestParams), responses((status = 200, body = EstimateBacktestResponse), (status = 401, body = ErrorBody), (status = 422, body = ErrorBody), (status = 500, body = ErrorBody)), security(("bearer_auth" = [])))]
+pub(super) async fn estimate_backtest(Query(params): Query<EstimateBacktestParams>) -> Response {
+    let horizon = match params.horizon.as_deref() {
+        None => None,
+        Some(code) => match BacktestHorizon::from_code(code) {
+            Some(value) => Some(value),
+            None => {
+                return error_response(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    "horizon 必須為 3m、6m 或 12m",
+                );
+            }
+        },
+    };
+    let scope_kind = match params.scope.as_deref().unwrap_or("all") {
+        "all" => "all",
+        "market" => "market",
+        "industry" => "industry",
+        _ => {
+            return error_response(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "scope 必須為 all、market 或 industry",
+            );
         }
-        Err(error) => database_error(error),
+    };
+    if params.scope_id.is_some_and(|value| value <= 0) {
+        return error_response(StatusCode::UNPROCESSABLE_ENTITY, "scope_id 必須為正整數");
+    }
+    if scope_kind == "all" && params.scope_id.is_some() {
+        return error_response(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "scope=all 時不可指定 scope_id",
+        );
+    }
+    let from = match parse_optional_date(params.from.as_deref()) {
+        Ok(value) => value,
+        Err(message) => return error_response(StatusCode::UNPROCESSABLE_ENTITY, message),
+    };
+    let to = match parse_optional_date(params.to.as_deref()) {
+        Ok(value) => value,
+        Err(message) => return error_response(StatusCode::UNPROCESSABLE_ENTITY, message),
+    };
+    if let (Some(from), Some(to)) = (from, to)
+        && from > to
+    {
+        return error_response(StatusCode::UNPROCESSABLE_ENTITY, "from 不可晚於 to");
+    }
+
+    let query = BacktestReportQuery {
+        horizon,
+        scope_kind: Some(scope_kind),
+        scope_id: params.scope_id,
+        from,
+        to,
+    };
+    match PgEstimateBacktestRepository::new()
+        .fetch_reports(&query)
+        .await
+    {
+        Ok(reports) => Json(EstimateBacktestResponse {
+            reports: reports.iter().map(Into::into).collect(),
+        })
+        .into_response(),
+        Err(error) => repository_error(error),
     }
 }
 
@@ -446,6 +664,117 @@ pub(super) async fn market_breadth(Query(params): Query<MarketBreadthParams>) ->
     }
 }
 
+/// 查詢指定市場最近數個交易日的市場廣度衍生指標，供繪製騰落線、
+/// McClellan 擺盪指標、新高新低與站上均線比例的走勢。
+///
+/// 指標於每日收盤匯總時由 `daily_stock_price_stats` 遞推寫入；`days` 與
+/// `date` 的語意同 `/market/breadth`，只是筆數上限放寬到 500 以涵蓋兩年走勢。
+///
+/// # Errors
+///
+/// 市場、日期或 days 不合法回 422，查無任何指標回 404，驗證失敗回 401，
+/// 資料庫失敗回不含 SQL 細節的 500。
+#[utoipa::path(get, path = "/api/v1/market/breadth-indicators", tag = "data-api", params(MarketBreadthIndicatorParams), responses((status = 200, body = MarketBreadthIndicatorResponse), (status = 401, body = ErrorBody), (status = 404, body = ErrorBody), (status = 422, body = ErrorBody), (status = 500, body = ErrorBody)), security(("bearer_auth" = [])))]
+pub(super) async fn market_breadth_indicators(
+    Query(params): Query<MarketBreadthIndicatorParams>,
+) -> Response {
+    let market = params.market.as_deref().unwrap_or("all");
+    let market_id = match market_id_for_stats(market) {
+        Some(value) => value,
+        None => {
+            return error_response(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "market 必須為 all、twse 或 tpex",
+            );
+        }
+    };
+    let date = match parse_optional_date(params.date.as_deref()) {
+        Ok(value) => value,
+        Err(message) => return error_response(StatusCode::UNPROCESSABLE_ENTITY, message),
+    };
+    let days = params.days.unwrap_or(120);
+    if !(1..=500).contains(&days) {
+        return error_response(StatusCode::UNPROCESSABLE_ENTITY, "days 必須介於 1 至 500");
+    }
+    match PgBreadthIndicatorRepository::new()
+        .fetch_history(market_id, date, i64::from(days))
+        .await
+    {
+        Ok(indicators) if indicators.is_empty() => {
+            error_response(StatusCode::NOT_FOUND, "查無市場廣度指標")
+        }
+        Ok(indicators) => Json(MarketBreadthIndicatorResponse {
+            market: market.to_string(),
+            data_as_of: indicators[0].date.to_string(),
+            history: indicators.iter().map(Into::into).collect(),
+        })
+        .into_response(),
+        Err(error) => repository_error(error),
+    }
+}
+
+/// 查詢最近一個交易日全部產業的指標。
+///
+/// 指標於每日收盤匯總時寫入 `industry_daily_metric`；指定日期時只接受 31 天
+/// 回溯窗內的最新一天，與其他市場查詢一致。
+///
+/// # Errors
+///
+/// 日期不合法回 422，查無任何指標回 404，驗證失敗回 401，資料庫失敗回
+/// 不含 SQL 細節的 500。
+#[utoipa::path(get, path = "/api/v1/market/industries", tag = "data-api", params(IndustryMetricsParams), responses((status = 200, body = IndustryMetricsResponse), (status = 401, body = ErrorBody), (status = 404, body = ErrorBody), (status = 422, body = ErrorBody), (status = 500, body = ErrorBody)), security(("bearer_auth" = [])))]
+pub(super) async fn industry_metrics(Query(params): Query<IndustryMetricsParams>) -> Response {
+    let date = match parse_optional_date(params.date.as_deref()) {
+        Ok(value) => value,
+        Err(message) => return error_response(StatusCode::UNPROCESSABLE_ENTITY, message),
+    };
+    match PgIndustryMetricRepository::new()
+        .fetch_snapshots(date)
+        .await
+    {
+        Ok(snapshots) if snapshots.is_empty() => {
+            error_response(StatusCode::NOT_FOUND, "查無產業指標")
+        }
+        Ok(snapshots) => Json(IndustryMetricsResponse {
+            data_as_of: snapshots[0].metric.date.to_string(),
+            industries: snapshots.iter().map(Into::into).collect(),
+        })
+        .into_response(),
+        Err(error) => repository_error(error),
+    }
+}
+
+/// 依近 1 週、1 個月、3 個月相對加權指數的強弱排出類股輪動。
+///
+/// 產業以市值加權報酬連乘的產業指數計算區間報酬，回顧期間以「有產業指標的
+/// 交易日」往前數，不受休市日影響。
+///
+/// # Errors
+///
+/// 日期或 sort 不合法回 422，查無任何指標回 404，驗證失敗回 401，資料庫
+/// 失敗回不含 SQL 細節的 500。
+#[utoipa::path(get, path = "/api/v1/market/sector-rotation", tag = "data-api", params(SectorRotationParams), responses((status = 200, body = SectorRotationResponse), (status = 401, body = ErrorBody), (status = 404, body = ErrorBody), (status = 422, body = ErrorBody), (status = 500, body = ErrorBody)), security(("bearer_auth" = [])))]
+pub(super) async fn sector_rotation(Query(params): Query<SectorRotationParams>) -> Response {
+    let date = match parse_optional_date(params.date.as_deref()) {
+        Ok(value) => value,
+        Err(message) => return error_response(StatusCode::UNPROCESSABLE_ENTITY, message),
+    };
+    let Some(sort) = RotationWindow::from_code(params.sort.as_deref().unwrap_or("1m")) else {
+        return error_response(StatusCode::UNPROCESSABLE_ENTITY, "sort 必須為 1w、1m 或 3m");
+    };
+    match PgIndustryMetricRepository::new().fetch_rotation(date).await {
+        Ok(None) => error_response(StatusCode::NOT_FOUND, "查無產業指標"),
+        Ok(Some(snapshot)) => Json(SectorRotationResponse {
+            data_as_of: snapshot.date.to_string(),
+            benchmark: "TAIEX",
+            sort: sort.code(),
+            industries: snapshot.rank(sort).iter().map(Into::into).collect(),
+        })
+        .into_response(),
+        Err(error) => repository_error(error),
+    }
+}
+
 /// 查詢單一有效交易日的殖利率排行（§4.6）。
 ///
 /// 先從 `yield_rank` 決定 31 天視窗內唯一資料日，再 JOIN 原始報價與股利列，
@@ -528,11 +857,11 @@ pub(super) async fn screen_stocks(Query(params): Query<StockScreeningParams>) ->
     // 查詢日由 handler 明確綁定，SQL 不直接讀 CURRENT_DATE；純函式測試可傳入
     // 任意日期驗證月份、季度與 31 天邊界，production 則採伺服器台北本地日。
     let query_date = Local::now().date_naive();
-    // ORDER BY 字串只可能來自下方十二個 &'static str 分支，絕不插入呼叫端
+    // ORDER BY 字串只可能來自下方十六個 &'static str 分支，絕不插入呼叫端
     // 原始文字；其他條件仍全部使用 bind parameter。
-    let sql = format!("{SCREEN_STOCKS_SQL}\n{}\nLIMIT $9", validated.order_by);
+    let sql = format!("{SCREEN_STOCKS_SQL}\n{}\nLIMIT $11", validated.order_by);
     // SQLx 0.9 要求動態字串顯式稽核；此處唯一動態部分已由
-    // `screen_order_by` 限制為十二個靜態常數，因此可安全標記。
+    // `screen_order_by` 限制為十六個靜態常數，因此可安全標記。
     let rows: Result<Vec<ScreenedStockRow>, _> = sqlx::query_as(sqlx::AssertSqlSafe(sql.as_str()))
         .bind(validated.market_id)
         .bind(params.industry_id)
@@ -542,13 +871,25 @@ pub(super) async fn screen_stocks(Query(params): Query<StockScreeningParams>) ->
         .bind(validated.min_roe_percent)
         .bind(validated.min_dividend_yield_percent)
         .bind(params.valuation_band.as_deref())
+        .bind(validated.min_short_to_margin_ratio_percent)
+        .bind(validated.max_margin_utilization_percent)
         .bind(i64::from(validated.limit))
+        .bind(validated.band_years)
+        .bind(validated.min_yield_percentile)
+        .bind(validated.max_per_percentile)
+        .bind(validated.max_pbr_percentile)
+        .bind(validated.min_f_score)
+        .bind(validated.min_altman_z_score)
+        .bind(validated.max_accruals_ratio)
+        .bind(validated.min_fcf_yield_percent)
+        .bind(validated.min_interest_coverage)
+        .bind(validated.max_debt_to_equity)
         .fetch_all(database::get_connection())
         .await;
 
     match rows {
         Ok(rows) => Json(StockScreeningResponse {
-            // 每股四種資料可能來自不同日期，因此不可偽造單一 data_as_of。
+            // 每股五種資料可能來自不同日期，因此不可偽造單一 data_as_of。
             data_as_of: None,
             stocks: rows.into_iter().map(Into::into).collect(),
         })
@@ -582,7 +923,22 @@ WITH latest AS (
         e.fair,
         e.expensive,
         y.date AS yield_raw_date,
-        y.yield AS yield_raw
+        y.yield AS yield_raw,
+        m.date AS margin_raw_date,
+        m.short_to_margin_ratio_percent AS short_ratio_raw,
+        m.margin_utilization_percent AS margin_utilization_raw,
+        b.date AS band_raw_date,
+        b.yield_percentile AS yield_percentile_raw,
+        b.per_percentile AS per_percentile_raw,
+        b.pbr_percentile AS pbr_percentile_raw,
+        h.year AS health_year,
+        h.quarter AS health_quarter,
+        h.f_score AS f_score_raw,
+        h.altman_z_score AS altman_z_score_raw,
+        h.accruals_ratio AS accruals_ratio_raw,
+        h.fcf_yield_pct AS fcf_yield_raw,
+        h.interest_coverage AS interest_coverage_raw,
+        h.debt_to_equity AS debt_to_equity_raw
     FROM stocks s
     LEFT JOIN LATERAL (
         SELECT "Date", "ComparedWithLastYearSameMonth"
@@ -617,6 +973,37 @@ WITH latest AS (
         ORDER BY date DESC
         LIMIT 1
     ) y ON TRUE
+    LEFT JOIN LATERAL (
+        SELECT date, short_to_margin_ratio_percent, margin_utilization_percent
+        FROM margin_trading
+        WHERE stock_symbol = s.stock_symbol
+        ORDER BY date DESC
+        LIMIT 1
+    ) m ON TRUE
+    LEFT JOIN LATERAL (
+        SELECT
+            date,
+            MAX(percentile) FILTER (WHERE metric = 'dividend_yield') AS yield_percentile,
+            MAX(percentile) FILTER (WHERE metric = 'per') AS per_percentile,
+            MAX(percentile) FILTER (WHERE metric = 'pbr') AS pbr_percentile
+        FROM valuation_band
+        WHERE security_code = s.stock_symbol
+          AND years = $12
+          AND date = (
+              SELECT MAX(date)
+              FROM valuation_band
+              WHERE security_code = s.stock_symbol AND years = $12
+          )
+        GROUP BY date
+    ) b ON TRUE
+    LEFT JOIN LATERAL (
+        SELECT year, quarter, f_score, altman_z_score, accruals_ratio, fcf_yield_pct,
+               interest_coverage, debt_to_equity
+        FROM financial_health_score
+        WHERE security_code = s.stock_symbol
+        ORDER BY period_end DESC
+        LIMIT 1
+    ) h ON TRUE
     WHERE (($1::int = 0 AND s.stock_exchange_market_id IN (2, 4))
         OR s.stock_exchange_market_id = $1)
       AND ($2::int IS NULL OR s.stock_industry_id = $2)
@@ -652,23 +1039,87 @@ WITH latest AS (
             END
         END AS valuation_band,
         CASE WHEN yield_raw_date BETWEEN $3::date - 30 AND $3::date
-          THEN yield_raw END AS dividend_yield_percent
+          THEN yield_raw END AS dividend_yield_percent,
+        CASE WHEN margin_raw_date BETWEEN $3::date - 7 AND $3::date
+          THEN short_ratio_raw END AS short_to_margin_ratio_percent,
+        CASE WHEN margin_raw_date BETWEEN $3::date - 7 AND $3::date
+          THEN margin_utilization_raw END AS margin_utilization_percent,
+        CASE WHEN band_raw_date BETWEEN $3::date - 30 AND $3::date
+          THEN yield_percentile_raw END AS yield_percentile,
+        CASE WHEN band_raw_date BETWEEN $3::date - 30 AND $3::date
+          THEN per_percentile_raw END AS per_percentile,
+        CASE WHEN band_raw_date BETWEEN $3::date - 30 AND $3::date
+          THEN pbr_percentile_raw END AS pbr_percentile,
+        CASE WHEN health_year IS NOT NULL
+          AND ((EXTRACT(YEAR FROM $3::date)::int * 4
+                + EXTRACT(QUARTER FROM $3::date)::int)
+              - (health_year * 4
+                + SUBSTRING(health_quarter FROM 2)::int)) BETWEEN 0 AND 2
+          THEN f_score_raw END AS f_score,
+        CASE WHEN health_year IS NOT NULL
+          AND ((EXTRACT(YEAR FROM $3::date)::int * 4
+                + EXTRACT(QUARTER FROM $3::date)::int)
+              - (health_year * 4
+                + SUBSTRING(health_quarter FROM 2)::int)) BETWEEN 0 AND 2
+          THEN altman_z_score_raw END AS altman_z_score,
+        CASE WHEN health_year IS NOT NULL
+          AND ((EXTRACT(YEAR FROM $3::date)::int * 4
+                + EXTRACT(QUARTER FROM $3::date)::int)
+              - (health_year * 4
+                + SUBSTRING(health_quarter FROM 2)::int)) BETWEEN 0 AND 2
+          THEN accruals_ratio_raw END AS accruals_ratio,
+        CASE WHEN health_year IS NOT NULL
+          AND ((EXTRACT(YEAR FROM $3::date)::int * 4
+                + EXTRACT(QUARTER FROM $3::date)::int)
+              - (health_year * 4
+                + SUBSTRING(health_quarter FROM 2)::int)) BETWEEN 0 AND 2
+          THEN fcf_yield_raw END AS fcf_yield_percent,
+        CASE WHEN health_year IS NOT NULL
+          AND ((EXTRACT(YEAR FROM $3::date)::int * 4
+                + EXTRACT(QUARTER FROM $3::date)::int)
+              - (health_year * 4
+                + SUBSTRING(health_quarter FROM 2)::int)) BETWEEN 0 AND 2
+          THEN interest_coverage_raw END AS interest_coverage,
+        CASE WHEN health_year IS NOT NULL
+          AND ((EXTRACT(YEAR FROM $3::date)::int * 4
+                + EXTRACT(QUARTER FROM $3::date)::int)
+              - (health_year * 4
+                + SUBSTRING(health_quarter FROM 2)::int)) BETWEEN 0 AND 2
+          THEN debt_to_equity_raw END AS debt_to_equity
     FROM latest
 )
 SELECT
     stock_symbol, name, market_id, industry_id,
     revenue_yoy_percent, earnings_per_share, return_on_equity,
     dividend_yield_percent, valuation_band, valuation_percentage,
+    short_to_margin_ratio_percent, margin_utilization_percent,
+    yield_percentile, per_percentile, pbr_percentile,
+    f_score, altman_z_score, accruals_ratio, fcf_yield_percent,
+    interest_coverage, debt_to_equity,
     revenue_raw_month AS revenue_month,
     financial_year, financial_quarter,
+    health_year, health_quarter,
     valuation_raw_date AS valuation_date,
-    yield_raw_date AS yield_date
+    yield_raw_date AS yield_date,
+    margin_raw_date AS margin_date,
+    band_raw_date AS band_date
 FROM normalized
 WHERE ($4::numeric IS NULL OR revenue_yoy_percent >= $4)
   AND ($5::numeric IS NULL OR earnings_per_share >= $5)
   AND ($6::numeric IS NULL OR return_on_equity >= $6)
   AND ($7::numeric IS NULL OR dividend_yield_percent >= $7)
   AND ($8::text IS NULL OR valuation_band = $8)
+  AND ($9::numeric IS NULL OR short_to_margin_ratio_percent >= $9)
+  AND ($10::numeric IS NULL OR margin_utilization_percent <= $10)
+  AND ($13::numeric IS NULL OR yield_percentile >= $13)
+  AND ($14::numeric IS NULL OR per_percentile <= $14)
+  AND ($15::numeric IS NULL OR pbr_percentile <= $15)
+  AND ($16::smallint IS NULL OR f_score >= $16)
+  AND ($17::numeric IS NULL OR altman_z_score >= $17)
+  AND ($18::numeric IS NULL OR accruals_ratio <= $18)
+  AND ($19::numeric IS NULL OR fcf_yield_percent >= $19)
+  AND ($20::numeric IS NULL OR interest_coverage >= $20)
+  AND ($21::numeric IS NULL OR debt_to_equity <= $21)
 "#;
 
 /// 查詢台股大盤指數（TAIEX）歷史走勢（§4.8）。
@@ -708,19 +1159,41 @@ pub(super) async fn market_index_history(
     // `index-date_category-uidx` 以 (date, category) 為鍵，P0-4 已驗證此
     // 查詢走索引反向掃描（0.06ms）。`$1/$2` 為 NULL 時代表不限制區間。
     let rows: Result<Vec<IndexPointRow>, _> = sqlx::query_as(r#"SELECT "date", index, change, trade_value, "transaction", trading_volume FROM index WHERE category = 'TAIEX' AND ($1::date IS NULL OR "date" >= $1) AND ($2::date IS NULL OR "date" <= $2) ORDER BY "date" DESC LIMIT $3"#).bind(from).bind(to).bind(i64::from(limit)).fetch_all(database::get_connection()).await;
-    match rows {
-        Ok(rows) => {
-            // 清單固定新到舊（§3.1），第一筆即最新一筆；空清單時
-            // `data_as_of` 維持 null，不揣測日期。
-            let data_as_of = rows.first().map(|row| row.date.to_string());
-            Json(MarketIndexHistoryResponse {
-                data_as_of,
-                points: rows.into_iter().map(Into::into).collect(),
-            })
-            .into_response()
-        }
-        Err(error) => database_error(error),
-    }
+    let rows = match rows {
+        Ok(rows) => rows,
+        Err(error) => return database_error(error),
+    };
+    // 清單固定新到舊（§3.1），第一筆即最新一筆；空清單時
+    // `data_as_of` 維持 null，不揣測日期。
+    let data_as_of = rows.first().map(|row| row.date.to_string());
+    // 期貨選擇權摘要以指數資料點的日期區間一次查回，再依日期對應。
+    let snapshots = match rows.first().zip(rows.last()) {
+        Some((newest, oldest)) => match PgDerivativesRepository::new()
+            .fetch_range(oldest.date, newest.date)
+            .await
+        {
+            Ok(snapshots) => snapshots,
+            Err(error) => return repository_error(error),
+        },
+        None => Vec::new(),
+    };
+    let by_date: HashMap<NaiveDate, &DerivativesDailySnapshot> = snapshots
+        .iter()
+        .map(|snapshot| (snapshot.date, snapshot))
+        .collect();
+    let points = rows
+        .into_iter()
+        .map(|row| {
+            let summary = by_date
+                .get(&row.date)
+                .map(|snapshot| market_derivatives_summary(snapshot, row.index));
+            MarketIndexPoint {
+                derivatives: summary,
+                ..row.into()
+            }
+        })
+        .collect();
+    Json(MarketIndexHistoryResponse { data_as_of, points }).into_response()
 }
 
 /// 查詢日期區間內的除權息與股利發放行事曆（§4.9）。
@@ -844,36 +1317,308 @@ ORDER BY event_date ASC, stock_symbol ASC
 LIMIT $4
 "#;
 
-/// 查詢外資（QFII）持股比例或持股數排行（§4.10）。
+/// 查詢外資（QFII）持股比例或持股數排行（§4.10）。
+///
+/// 這是**當前快照**：`stocks` 表只保存最近一次排程更新（每日 22:00 UTC）
+/// 的數字，沒有歷史序列，無法回答「外資最近增減持」的趨勢問題。排除
+/// 暫停上市（`"SuspendListing" = true`）與持股數為 0 的股票；市場條件同
+/// §3.6，`all` 僅含上市＋上櫃。查無資料回 `200` 空陣列。
+///
+/// # Errors
+///
+/// `market`、`sort_by` 不在固定 enum、`industry_id` 非正整數或 `limit`
+/// 超出 1–50 回 422；驗證失敗回 401；資料庫查詢失敗時記錄內部錯誤並回
+/// 不含 SQL 細節的 500。
+#[utoipa::path(get, path = "/api/v1/market/qfii-holding-ranking", tag = "data-api", params(QfiiHoldingRankingParams), responses((status = 200, body = QfiiHoldingRankingResponse), (status = 401, body = ErrorBody), (status = 422, body = ErrorBody), (status = 500, body = ErrorBody)), security(("bearer_auth" = [])))]
+pub(super) async fn qfii_holding_ranking(
+    Query(params): Query<QfiiHoldingRankingParams>,
+) -> Response {
+    let market = params.market.as_deref().unwrap_or("all");
+    let market_id = match market_id_for_stocks(market) {
+        Some(value) => value,
+        None => {
+            return error_response(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "market 必須為 all、twse 或 tpex",
+            );
+        }
+    };
+    if params.industry_id.is_some_and(|value| value <= 0) {
+        return error_response(StatusCode::UNPROCESSABLE_ENTITY, "industry_id 必須為正整數");
+    }
+    let order_by = match qfii_order_by(params.sort_by.as_deref().unwrap_or("percentage")) {
+        Ok(value) => value,
+        Err(message) => return error_response(StatusCode::UNPROCESSABLE_ENTITY, message),
+    };
+    let limit = params.limit.unwrap_or(20);
+    if !(1..=50).contains(&limit) {
+        return error_response(StatusCode::UNPROCESSABLE_ENTITY, "limit 必須介於 1 至 50");
+    }
+    // ORDER BY 只可能來自 `qfii_order_by` 的兩個 &'static str 分支，呼叫端
+    // 無法提供 SQL 片段；其餘條件全部使用 bind parameter。SQLx 0.9 對動態
+    // 字串要求顯式稽核（AssertSqlSafe），與 screen_stocks 的做法一致。
+    let sql = format!("{QFII_RANKING_SQL}\n{order_by}\nLIMIT $3");
+    let rows: Result<Vec<QfiiHoldingRow>, _> = sqlx::query_as(sqlx::AssertSqlSafe(sql.as_str()))
+        .bind(market_id)
+        .bind(params.industry_id)
+        .bind(i64::from(limit))
+        .fetch_all(database::get_connection())
+        .await;
+    match rows {
+        Ok(rows) => Json(QfiiHoldingRankingResponse {
+            // `stocks` 表快照沒有列級更新日期，依 §4.10 不可偽造一個。
+            data_as_of: None,
+            stocks: rows
+                .into_iter()
+                .enumerate()
+                // 名次由查詢結果順序在程式端產生（一起始），與殖利率排行一致。
+                .map(|(index, row)| row.into_dto(index as u32 + 1))
+                .collect(),
+        })
+        .into_response(),
+        Err(error) => database_error(error),
+    }
+}
+
+/// QFII 排行的參數化 SQL 主體（不含排序與 LIMIT）。
+///
+/// 綁定參數：
+/// - `$1`：市場 id；`0` 是 API 內部的 all 哨兵，只展開為固定 `IN (2, 4)`
+///   （上市＋上櫃，刻意排除公開發行與興櫃，§3.6）。
+/// - `$2`：可選產業 id；NULL 時不過濾。
+/// - `$3`：回傳筆數上限（附加於排序分支之後）。
+///
+/// 排除條件寫死在 SQL：暫停上市股票的快照數字已無參考價值；持股數 0 多為
+/// 外資從未進場或資料未更新的股票，混入排行尾端沒有意義。stocks 表僅數千
+/// 列，P0-4 EXPLAIN 實測 Seq Scan + top-N 排序約 1.8ms，無需索引。
+const QFII_RANKING_SQL: &str = r#"
+SELECT stock_symbol, "Name" AS name,
+       stock_exchange_market_id AS market_id, stock_industry_id AS industry_id,
+       qfii_shares_held, qfii_share_holding_percentage, issued_share
+FROM stocks
+WHERE (($1 = 0 AND stock_exchange_market_id IN (2, 4))
+    OR stock_exchange_market_id = $1)
+  AND ($2::int IS NULL OR stock_industry_id = $2)
+  AND "SuspendListing" = false
+  AND qfii_shares_held <> 0
+"#;
+
+/// 查詢單一股票的三大法人每日買賣超歷史。
+///
+/// 先確認股票存在（未知代號回 404），再依日期區間由新到舊回傳；已知代號
+/// 但區間內沒有資料時回 `200` 空陣列。數字來自每日 16:40 排程抓取的
+/// TWSE T86 與 TPEx 三大法人日報，單位為股。
+///
+/// # Errors
+///
+/// 日期格式錯誤、`from` 晚於 `to` 或 `limit` 超出 1–250 回 422；驗證失敗
+/// 回 401；倉儲查詢失敗回不含 SQL 細節的 500。
+#[utoipa::path(get, path = "/api/v1/stocks/{symbol}/institutional-investors", tag = "data-api", params(("symbol" = String, Path, description = "股票代號"), InstitutionalTradeHistoryParams), responses((status = 200, body = InstitutionalTradeHistoryResponse), (status = 401, body = ErrorBody), (status = 404, body = ErrorBody), (status = 422, body = ErrorBody), (status = 500, body = ErrorBody)), security(("bearer_auth" = [])))]
+pub(super) async fn institutional_trade_history(
+    Path(symbol): Path<String>,
+    Query(params): Query<InstitutionalTradeHistoryParams>,
+) -> Response {
+    let (from, to) = match parse_range(params.from.as_deref(), params.to.as_deref()) {
+        Ok(range) => range,
+        Err(message) => return error_response(StatusCode::UNPROCESSABLE_ENTITY, message),
+    };
+    let limit = params.limit.unwrap_or(20);
+    if !(1..=250).contains(&limit) {
+        return error_response(StatusCode::UNPROCESSABLE_ENTITY, "limit 必須介於 1 至 250");
+    }
+    if let Some(response) = ensure_stock_exists(&symbol).await {
+        return response;
+    }
+
+    let trades = match PgInstitutionalTradeRepository::new()
+        .fetch_by_symbol(&symbol, from, to, i64::from(limit))
+        .await
+    {
+        Ok(value) => value,
+        Err(error) => return repository_error(error),
+    };
+
+    Json(InstitutionalTradeHistoryResponse {
+        stock_symbol: symbol,
+        data_as_of: trades.first().map(|trade| trade.date.to_string()),
+        trades: trades.into_iter().map(Into::into).collect(),
+    })
+    .into_response()
+}
+
+/// 查詢單一股票的融資融券每日餘額歷史。
+///
+/// 行為與三大法人歷史相同：未知代號回 404，已知代號但區間內沒有資料時
+/// 回 `200` 空陣列。數字來自每日 21:30 排程抓取的 TWSE MI_MARGN 與 TPEx
+/// 融資融券餘額，單位為張；融資使用率與券資比為寫入時計算的百分比。
+///
+/// # Errors
+///
+/// 日期格式錯誤、`from` 晚於 `to` 或 `limit` 超出 1–250 回 422；驗證失敗
+/// 回 401；倉儲查詢失敗回不含 SQL 細節的 500。
+#[utoipa::path(get, path = "/api/v1/stocks/{symbol}/margin-trading", tag = "data-api", params(("symbol" = String, Path, description = "股票代號"), MarginTradingHistoryParams), responses((status = 200, body = MarginTradingHistoryResponse), (status = 401, body = ErrorBody), (status = 404, body = ErrorBody), (status = 422, body = ErrorBody), (status = 500, body = ErrorBody)), security(("bearer_auth" = [])))]
+pub(super) async fn margin_trading_history(
+    Path(symbol): Path<String>,
+    Query(params): Query<MarginTradingHistoryParams>,
+) -> Response {
+    let (from, to) = match parse_range(params.from.as_deref(), params.to.as_deref()) {
+        Ok(range) => range,
+        Err(message) => return error_response(StatusCode::UNPROCESSABLE_ENTITY, message),
+    };
+    let limit = params.limit.unwrap_or(20);
+    if !(1..=250).contains(&limit) {
+        return error_response(StatusCode::UNPROCESSABLE_ENTITY, "limit 必須介於 1 至 250");
+    }
+    if let Some(response) = ensure_stock_exists(&symbol).await {
+        return response;
+    }
+
+    let records = match PgMarginTradingRepository::new()
+        .fetch_by_symbol(&symbol, from, to, i64::from(limit))
+        .await
+    {
+        Ok(value) => value,
+        Err(error) => return repository_error(error),
+    };
+
+    Json(MarginTradingHistoryResponse {
+        stock_symbol: symbol,
+        data_as_of: records.first().map(|record| record.date.to_string()),
+        records: records.into_iter().map(Into::into).collect(),
+    })
+    .into_response()
+}
+
+/// 查詢單一股票最近數週的集保戶股權分散與大戶持股週變化。
+///
+/// 未知代號回 404；已知代號但沒有資料時回 `200`、`latest_tiers` 與 `weeks`
+/// 皆為空陣列。會多取一週作為最舊一週的比較基準，因此 `weeks` 內每一週
+/// 在有前一週資料時都有週變化。
+///
+/// # Errors
+///
+/// `weeks` 超出 1–104 回 422；驗證失敗回 401；倉儲查詢失敗回不含 SQL
+/// 細節的 500。
+#[utoipa::path(get, path = "/api/v1/stocks/{symbol}/shareholding-distribution", tag = "data-api", params(("symbol" = String, Path, description = "股票代號"), ShareholdingDistributionParams), responses((status = 200, body = ShareholdingDistributionResponse), (status = 401, body = ErrorBody), (status = 404, body = ErrorBody), (status = 422, body = ErrorBody), (status = 500, body = ErrorBody)), security(("bearer_auth" = [])))]
+pub(super) async fn shareholding_distribution(
+    Path(symbol): Path<String>,
+    Query(params): Query<ShareholdingDistributionParams>,
+) -> Response {
+    let weeks = params.weeks.unwrap_or(12);
+    if !(1..=104).contains(&weeks) {
+        return error_response(StatusCode::UNPROCESSABLE_ENTITY, "weeks 必須介於 1 至 104");
+    }
+    if let Some(response) = ensure_stock_exists(&symbol).await {
+        return response;
+    }
+
+    let snapshots = match PgShareholdingDistributionRepository::new()
+        .fetch_by_symbol(&symbol, i64::from(weeks) + 1)
+        .await
+    {
+        Ok(value) => value,
+        Err(error) => return repository_error(error),
+    };
+
+    Json(build_shareholding_response(
+        symbol,
+        &snapshots,
+        usize::from(weeks),
+    ))
+    .into_response()
+}
+
+/// 將依日期降序的快照整理成回應；`snapshots` 可比 `weeks` 多一週作為比較基準。
+fn build_shareholding_response(
+    stock_symbol: String,
+    snapshots: &[DomainShareholdingDistribution],
+    weeks: usize,
+) -> ShareholdingDistributionResponse {
+    let latest_tiers = snapshots
+        .first()
+        .map(|snapshot| {
+            snapshot
+                .tiers
+                .iter()
+                .map(|tier| ShareholdingTier {
+                    level: tier.level,
+                    holders: tier.holders,
+                    shares: tier.shares,
+                    percentage: decimal_to_f64(Some(tier.percentage)),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let weeks = snapshots
+        .iter()
+        .enumerate()
+        .take(weeks)
+        .map(|(index, snapshot)| {
+            let change = snapshots
+                .get(index + 1)
+                .map(|previous| snapshot.week_over_week(previous));
+            ShareholdingWeek {
+                date: snapshot.date.to_string(),
+                total_holders: snapshot.total_holders(),
+                over_400_lots_holders: snapshot.holders_at_or_above(LEVEL_OVER_400_LOTS),
+                over_400_lots_percentage: decimal_to_f64(Some(
+                    snapshot.percentage_at_or_above(LEVEL_OVER_400_LOTS),
+                )),
+                over_1000_lots_percentage: decimal_to_f64(Some(
+                    snapshot.percentage_at_or_above(LEVEL_OVER_1000_LOTS),
+                )),
+                over_400_lots_percentage_change: decimal_to_f64(
+                    change.map(|c| c.over_400_lots_percentage),
+                ),
+                over_1000_lots_percentage_change: decimal_to_f64(
+                    change.map(|c| c.over_1000_lots_percentage),
+                ),
+                over_400_lots_holders_change: change.map(|c| c.over_400_lots_holders),
+                total_holders_change: change.map(|c| c.total_holders),
+            }
+        })
+        .collect();
+
+    ShareholdingDistributionResponse {
+        stock_symbol,
+        data_as_of: snapshots.first().map(|snapshot| snapshot.date.to_string()),
+        latest_tiers,
+        weeks,
+    }
+}
+
+/// 查詢全市場三大法人買賣超排行。
 ///
-/// 這是**當前快照**：`stocks` 表只保存最近一次排程更新（每日 22:00 UTC）
-/// 的數字，沒有歷史序列，無法回答「外資最近增減持」的趨勢問題。排除
-/// 暫停上市（`"SuspendListing" = true`）與持股數為 0 的股票；市場條件同
-/// §3.6，`all` 僅含上市＋上櫃。查無資料回 `200` 空陣列。
+/// 排行日取「不晚於 `date` 的最近一個有資料的交易日」，未提供 `date` 時取
+/// 最新一日；`buy` 只列買超（> 0）、`sell` 只列賣超（< 0），皆依股數絕對值
+/// 由大到小。市場條件同 §3.6，`all` 僅含上市＋上櫃，並排除暫停上市股票。
+/// 估算金額以當日收盤價乘上買賣超股數，查無當日報價時為 `null`。
 ///
 /// # Errors
 ///
-/// `market`、`sort_by` 不在固定 enum、`industry_id` 非正整數或 `limit`
-/// 超出 1–50 回 422；驗證失敗回 401；資料庫查詢失敗時記錄內部錯誤並回
-/// 不含 SQL 細節的 500。
-#[utoipa::path(get, path = "/api/v1/market/qfii-holding-ranking", tag = "data-api", params(QfiiHoldingRankingParams), responses((status = 200, body = QfiiHoldingRankingResponse), (status = 401, body = ErrorBody), (status = 422, body = ErrorBody), (status = 500, body = ErrorBody)), security(("bearer_auth" = [])))]
-pub(super) async fn qfii_holding_ranking(
-    Query(params): Query<QfiiHoldingRankingParams>,
+/// `market`、`investor`、`direction` 不在固定 enum、`industry_id` 非正整數、
+/// 日期格式錯誤或 `limit` 超出 1–50 回 422；驗證失敗回 401；資料庫查詢
+/// 失敗時回不含 SQL 細節的 500。
+#[utoipa::path(get, path = "/api/v1/market/institutional-investor-ranking", tag = "data-api", params(InstitutionalTradeRankingParams), responses((status = 200, body = InstitutionalTradeRankingResponse), (status = 401, body = ErrorBody), (status = 422, body = ErrorBody), (status = 500, body = ErrorBody)), security(("bearer_auth" = [])))]
+pub(super) async fn institutional_trade_ranking(
+    Query(params): Query<InstitutionalTradeRankingParams>,
 ) -> Response {
-    let market = params.market.as_deref().unwrap_or("all");
-    let market_id = match market_id_for_stocks(market) {
-        Some(value) => value,
-        None => {
-            return error_response(
-                StatusCode::UNPROCESSABLE_ENTITY,
-                "market 必須為 all、twse 或 tpex",
-            );
-        }
+    let Some(market_id) = market_id_for_stocks(params.market.as_deref().unwrap_or("all")) else {
+        return error_response(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "market 必須為 all、twse 或 tpex",
+        );
     };
     if params.industry_id.is_some_and(|value| value <= 0) {
         return error_response(StatusCode::UNPROCESSABLE_ENTITY, "industry_id 必須為正整數");
     }
-    let order_by = match qfii_order_by(params.sort_by.as_deref().unwrap_or("percentage")) {
+    let investor = params.investor.as_deref().unwrap_or("total");
+    let column = match institutional_net_column(investor) {
+        Ok(value) => value,
+        Err(message) => return error_response(StatusCode::UNPROCESSABLE_ENTITY, message),
+    };
+    let direction = params.direction.as_deref().unwrap_or("buy");
+    let (filter, order) = match institutional_direction(direction) {
         Ok(value) => value,
         Err(message) => return error_response(StatusCode::UNPROCESSABLE_ENTITY, message),
     };
@@ -881,24 +1626,53 @@ pub(super) async fn qfii_holding_ranking(
     if !(1..=50).contains(&limit) {
         return error_response(StatusCode::UNPROCESSABLE_ENTITY, "limit 必須介於 1 至 50");
     }
-    // ORDER BY 只可能來自 `qfii_order_by` 的兩個 &'static str 分支，呼叫端
-    // 無法提供 SQL 片段；其餘條件全部使用 bind parameter。SQLx 0.9 對動態
-    // 字串要求顯式稽核（AssertSqlSafe），與 screen_stocks 的做法一致。
-    let sql = format!("{QFII_RANKING_SQL}\n{order_by}\nLIMIT $3");
-    let rows: Result<Vec<QfiiHoldingRow>, _> = sqlx::query_as(sqlx::AssertSqlSafe(sql.as_str()))
-        .bind(market_id)
-        .bind(params.industry_id)
-        .bind(i64::from(limit))
-        .fetch_all(database::get_connection())
-        .await;
+    let date = match parse_optional_date(params.date.as_deref()) {
+        Ok(value) => value,
+        Err(message) => return error_response(StatusCode::UNPROCESSABLE_ENTITY, message),
+    };
+
+    let latest: Result<(Option<NaiveDate>,), _> = sqlx::query_as(
+        "SELECT max(date) FROM institutional_investor_trade WHERE ($1::date IS NULL OR date <= $1::date)",
+    )
+    .bind(date)
+    .fetch_one(database::get_connection())
+    .await;
+    let date = match latest {
+        Ok((Some(value),)) => value,
+        Ok((None,)) => {
+            return Json(InstitutionalTradeRankingResponse {
+                data_as_of: None,
+                investor: investor.to_owned(),
+                direction: direction.to_owned(),
+                stocks: Vec::new(),
+            })
+            .into_response();
+        }
+        Err(error) => return database_error(error),
+    };
+
+    // 欄位、篩選與排序片段只可能來自 `institutional_net_column` 與
+    // `institutional_direction` 的 &'static str 分支，其餘條件全部 bind。
+    let sql = format!(
+        "{INSTITUTIONAL_RANKING_SQL}\n  AND {column} {filter}\nORDER BY {column} {order}, t.stock_symbol ASC\nLIMIT $4"
+    )
+    .replace("{net_column}", column);
+    let rows: Result<Vec<InstitutionalTradeRankRow>, _> =
+        sqlx::query_as(sqlx::AssertSqlSafe(sql.as_str()))
+            .bind(date)
+            .bind(market_id)
+            .bind(params.industry_id)
+            .bind(i64::from(limit))
+            .fetch_all(database::get_connection())
+            .await;
     match rows {
-        Ok(rows) => Json(QfiiHoldingRankingResponse {
-            // `stocks` 表快照沒有列級更新日期，依 §4.10 不可偽造一個。
-            data_as_of: None,
+        Ok(rows) => Json(InstitutionalTradeRankingResponse {
+            data_as_of: Some(date.to_string()),
+            investor: investor.to_owned(),
+            direction: direction.to_owned(),
             stocks: rows
                 .into_iter()
                 .enumerate()
-                // 名次由查詢結果順序在程式端產生（一起始），與殖利率排行一致。
                 .map(|(index, row)| row.into_dto(index as u32 + 1))
                 .collect(),
         })
@@ -907,28 +1681,26 @@ pub(super) async fn qfii_holding_ranking(
     }
 }
 
-/// QFII 排行的參數化 SQL 主體（不含排序與 LIMIT）。
-///
-/// 綁定參數：
-/// - `$1`：市場 id；`0` 是 API 內部的 all 哨兵，只展開為固定 `IN (2, 4)`
-///   （上市＋上櫃，刻意排除公開發行與興櫃，§3.6）。
-/// - `$2`：可選產業 id；NULL 時不過濾。
-/// - `$3`：回傳筆數上限（附加於排序分支之後）。
+/// 三大法人排行的參數化 SQL 主體（不含方向篩選、排序與 LIMIT）。
 ///
-/// 排除條件寫死在 SQL：暫停上市股票的快照數字已無參考價值；持股數 0 多為
-/// 外資從未進場或資料未更新的股票，混入排行尾端沒有意義。stocks 表僅數千
-/// 列，P0-4 EXPLAIN 實測 Seq Scan + top-N 排序約 1.8ms，無需索引。
-const QFII_RANKING_SQL: &str = r#"
-SELECT stock_symbol, "Name" AS name,
-       stock_exchange_market_id AS market_id, stock_industry_id AS industry_id,
-       qfii_shares_held, qfii_share_holding_percentage, issued_share
-FROM stocks
-WHERE (($1 = 0 AND stock_exchange_market_id IN (2, 4))
-    OR stock_exchange_market_id = $1)
-  AND ($2::int IS NULL OR stock_industry_id = $2)
-  AND "SuspendListing" = false
-  AND qfii_shares_held <> 0
-"#;
+/// `{net_column}` 於組字串時替換為白名單欄位；綁定參數：
+/// - `$1`：排行交易日。
+/// - `$2`：市場 id；`0` 只展開為固定 `IN (2, 4)`（§3.6）。
+/// - `$3`：可選產業 id；NULL 時不過濾。
+/// - `$4`：回傳筆數上限（附加於排序分支之後）。
+const INSTITUTIONAL_RANKING_SQL: &str = r#"
+SELECT t.stock_symbol, s."Name" AS name,
+       s.stock_exchange_market_id AS market_id, s.stock_industry_id AS industry_id,
+       {net_column} AS net_shares, q."ClosingPrice" AS closing_price,
+       t.foreign_net, t.investment_trust_net, t.dealer_net, t.total_net
+FROM institutional_investor_trade t
+JOIN stocks s ON s.stock_symbol = t.stock_symbol
+LEFT JOIN "DailyQuotes" q ON q.stock_symbol = t.stock_symbol AND q."Date" = t.date
+WHERE t.date = $1
+  AND (($2 = 0 AND s.stock_exchange_market_id IN (2, 4))
+    OR s.stock_exchange_market_id = $2)
+  AND ($3::int IS NULL OR s.stock_industry_id = $3)
+  AND s."SuspendListing" = false"#;
 
 /// 回傳不需認證的服務存活狀態。
 #[utoipa::path(get, path = "/api/v1/healthz", tag = "data-api", responses((status = 200, body = HealthResponse)), security())]
@@ -1005,7 +1777,31 @@ struct ValidatedScreeningParams {
     min_roe_percent: Option<Decimal>,
     /// 最低殖利率。
     min_dividend_yield_percent: Option<Decimal>,
-    /// 十二個固定排序分支之一。
+    /// 最低券資比。
+    min_short_to_margin_ratio_percent: Option<Decimal>,
+    /// 最高融資使用率。
+    max_margin_utilization_percent: Option<Decimal>,
+    /// 歷史估值百分位的回顧年數。
+    band_years: i32,
+    /// 殖利率歷史百分位下限。
+    min_yield_percentile: Option<Decimal>,
+    /// 本益比歷史百分位上限。
+    max_per_percentile: Option<Decimal>,
+    /// 股價淨值比歷史百分位上限。
+    max_pbr_percentile: Option<Decimal>,
+    /// Piotroski F-score 下限。
+    min_f_score: Option<i16>,
+    /// Altman Z-score 下限。
+    min_altman_z_score: Option<Decimal>,
+    /// 應計比率上限。
+    max_accruals_ratio: Option<Decimal>,
+    /// 自由現金流量殖利率下限。
+    min_fcf_yield_percent: Option<Decimal>,
+    /// 利息保障倍數下限。
+    min_interest_coverage: Option<Decimal>,
+    /// 負債權益比上限。
+    max_debt_to_equity: Option<Decimal>,
+    /// 十六個固定排序分支之一。
     order_by: &'static str,
     /// 查詢筆數上限。
     limit: u8,
@@ -1059,6 +1855,74 @@ fn validate_screening_params(
         1_000.0,
         "min_dividend_yield_percent 必須介於 0 至 1000",
     )?;
+    let min_short_to_margin_ratio_percent = decimal_in_range(
+        params.min_short_to_margin_ratio_percent,
+        0.0,
+        10_000.0,
+        "min_short_to_margin_ratio_percent 必須介於 0 至 10000",
+    )?;
+    let max_margin_utilization_percent = decimal_in_range(
+        params.max_margin_utilization_percent,
+        0.0,
+        100.0,
+        "max_margin_utilization_percent 必須介於 0 至 100",
+    )?;
+    let band_years = params.band_years.unwrap_or(5);
+    if !BAND_YEARS.contains(&band_years) {
+        return Err("band_years 必須為 5 或 10");
+    }
+    let min_yield_percentile = decimal_in_range(
+        params.min_yield_percentile,
+        0.0,
+        100.0,
+        "min_yield_percentile 必須介於 0 至 100",
+    )?;
+    let max_per_percentile = decimal_in_range(
+        params.max_per_percentile,
+        0.0,
+        100.0,
+        "max_per_percentile 必須介於 0 至 100",
+    )?;
+    let max_pbr_percentile = decimal_in_range(
+        params.max_pbr_percentile,
+        0.0,
+        100.0,
+        "max_pbr_percentile 必須介於 0 至 100",
+    )?;
+    if params.min_f_score.is_some_and(|value| value > 9) {
+        return Err("min_f_score 必須介於 0 至 9");
+    }
+    let min_f_score = params.min_f_score.map(i16::from);
+    let min_altman_z_score = decimal_in_range(
+        params.min_altman_z_score,
+        -1_000.0,
+        1_000.0,
+        "min_altman_z_score 必須介於 -1000 至 1000",
+    )?;
+    let max_accruals_ratio = decimal_in_range(
+        params.max_accruals_ratio,
+        -10.0,
+        10.0,
+        "max_accruals_ratio 必須介於 -10 至 10",
+    )?;
+    let min_fcf_yield_percent = decimal_in_range(
+        params.min_fcf_yield_percent,
+        -1_000.0,
+        1_000.0,
+        "min_fcf_yield_percent 必須介於 -1000 至 1000",
+    )?;
+    let min_interest_coverage = decimal_in_range(
+        params.min_interest_coverage,
+        -100_000.0,
+        100_000.0,
+        "min_interest_coverage 必須介於 -100000 至 100000",
+    )?;
+    let max_debt_to_equity = decimal_in_range(
+        params.max_debt_to_equity,
+        0.0,
+        1_000.0,
+        "max_debt_to_equity 必須介於 0 至 1000",
+    )?;
     let order_by = screen_order_by(
         params.sort_by.as_deref().unwrap_or("stock_symbol"),
         params.sort_order.as_deref().unwrap_or("asc"),
@@ -1073,7 +1937,18 @@ fn validate_screening_params(
         || params.min_revenue_yoy_percent.is_some()
         || params.min_eps.is_some()
         || params.min_roe_percent.is_some()
-        || params.min_dividend_yield_percent.is_some();
+        || params.min_dividend_yield_percent.is_some()
+        || params.min_short_to_margin_ratio_percent.is_some()
+        || params.max_margin_utilization_percent.is_some()
+        || params.min_yield_percentile.is_some()
+        || params.max_per_percentile.is_some()
+        || params.max_pbr_percentile.is_some()
+        || params.min_f_score.is_some()
+        || params.min_altman_z_score.is_some()
+        || params.max_accruals_ratio.is_some()
+        || params.min_fcf_yield_percent.is_some()
+        || params.min_interest_coverage.is_some()
+        || params.max_debt_to_equity.is_some();
     if !has_filter {
         return Err("至少需要一個篩選條件");
     }
@@ -1083,6 +1958,18 @@ fn validate_screening_params(
         min_eps,
         min_roe_percent,
         min_dividend_yield_percent,
+        min_short_to_margin_ratio_percent,
+        max_margin_utilization_percent,
+        band_years,
+        min_yield_percentile,
+        max_per_percentile,
+        max_pbr_percentile,
+        min_f_score,
+        min_altman_z_score,
+        max_accruals_ratio,
+        min_fcf_yield_percent,
+        min_interest_coverage,
+        max_debt_to_equity,
         order_by,
         limit,
     })
@@ -1108,7 +1995,7 @@ fn decimal_in_range(
         .transpose()
 }
 
-/// 將排序 enum 映射成十二個固定 SQL 分支。
+/// 將排序 enum 映射成十六個固定 SQL 分支。
 ///
 /// 指標欄位一律加 `NULLS LAST`，否則 PostgreSQL 的降冪預設會把過期或缺值的
 /// NULL 排在最前面；同值再以股票代號升冪，讓分頁外的重複查詢仍穩定。
@@ -1138,6 +2025,18 @@ fn screen_order_by(sort_by: &str, sort_order: &str) -> Result<&'static str, &'st
         ("valuation_percentage", "desc") => {
             Ok("ORDER BY valuation_percentage DESC NULLS LAST, stock_symbol ASC")
         }
+        ("short_to_margin_ratio", "asc") => {
+            Ok("ORDER BY short_to_margin_ratio_percent ASC NULLS LAST, stock_symbol ASC")
+        }
+        ("short_to_margin_ratio", "desc") => {
+            Ok("ORDER BY short_to_margin_ratio_percent DESC NULLS LAST, stock_symbol ASC")
+        }
+        ("margin_utilization", "asc") => {
+            Ok("ORDER BY margin_utilization_percent ASC NULLS LAST, stock_symbol ASC")
+        }
+        ("margin_utilization", "desc") => {
+            Ok("ORDER BY margin_utilization_percent DESC NULLS LAST, stock_symbol ASC")
+        }
         (_, "asc" | "desc") => Err("sort_by 不在允許範圍"),
         _ => Err("sort_order 必須為 asc 或 desc"),
     }
@@ -1195,6 +2094,26 @@ fn qfii_order_by(sort_by: &str) -> Result<&'static str, &'static str> {
     }
 }
 
+/// 將三大法人類別轉成排行使用的白名單欄位。
+fn institutional_net_column(investor: &str) -> Result<&'static str, &'static str> {
+    match investor {
+        "foreign" => Ok("t.foreign_net"),
+        "investment_trust" => Ok("t.investment_trust_net"),
+        "dealer" => Ok("t.dealer_net"),
+        "total" => Ok("t.total_net"),
+        _ => Err("investor 必須為 foreign、investment_trust、dealer 或 total"),
+    }
+}
+
+/// 將買賣超方向轉成固定的篩選條件與排序方向。
+fn institutional_direction(direction: &str) -> Result<(&'static str, &'static str), &'static str> {
+    match direction {
+        "buy" => Ok(("> 0", "DESC")),
+        "sell" => Ok(("< 0", "ASC")),
+        _ => Err("direction 必須為 buy 或 sell"),
+    }
+}
+
 /// 測試 SQL 月份新鮮度公式的純 Rust 對照實作。
 #[cfg(test)]
 fn revenue_month_is_fresh(query_date: NaiveDate, revenue_month: i64) -> bool {
@@ -1746,10 +2665,45 @@ impl From<IndexPointRow> for MarketIndexPoint {
             trade_value: convert(row.trade_value, "trade_value"),
             transaction: convert(row.transaction, "transaction"),
             trading_volume: convert(row.trading_volume, "trading_volume"),
+            derivatives: None,
         }
     }
 }
 
+/// 由期貨選擇權快照整理當日摘要；`index_close` 用於計算基差。
+fn market_derivatives_summary(
+    snapshot: &DerivativesDailySnapshot,
+    index_close: Option<Decimal>,
+) -> MarketDerivativesSummary {
+    let convert = |value, field| analytical_decimal_to_f64(value, CONTRACT_TX, field);
+    let front = snapshot.front_month(CONTRACT_TX);
+    let ratio = snapshot.put_call_ratio.as_ref();
+    MarketDerivativesSummary {
+        futures_contract_month: front.map(|quote| quote.contract_month.clone()),
+        futures_close: convert(front.and_then(|quote| quote.close), "futures_close"),
+        futures_change: convert(front.and_then(|quote| quote.change), "futures_change"),
+        futures_settlement_price: convert(
+            front.and_then(|quote| quote.settlement_price),
+            "futures_settlement_price",
+        ),
+        futures_open_interest: snapshot.total_open_interest(CONTRACT_TX),
+        basis: convert(index_close.and_then(|close| snapshot.basis(close)), "basis"),
+        foreign_net_open_interest: snapshot
+            .net_open_interest(CONTRACT_TX, TraderType::ForeignInvestor),
+        investment_trust_net_open_interest: snapshot
+            .net_open_interest(CONTRACT_TX, TraderType::InvestmentTrust),
+        dealer_net_open_interest: snapshot.net_open_interest(CONTRACT_TX, TraderType::Dealer),
+        put_call_volume_ratio_percent: convert(
+            ratio.map(|r| r.volume_ratio_percent),
+            "put_call_volume_ratio_percent",
+        ),
+        put_call_open_interest_ratio_percent: convert(
+            ratio.map(|r| r.open_interest_ratio_percent),
+            "put_call_open_interest_ratio_percent",
+        ),
+    }
+}
+
 /// 對應股利行事曆 UNION ALL 展開後的單一事件列（§4.9）。
 #[derive(sqlx::FromRow)]
 struct CalendarEventRow {
@@ -1833,6 +2787,99 @@ impl QfiiHoldingRow {
     }
 }
 
+/// 三大法人排行查詢列。
+#[derive(sqlx::FromRow)]
+struct InstitutionalTradeRankRow {
+    /// 股票代號。
+    stock_symbol: String,
+    /// 股票名稱。
+    name: String,
+    /// 上市或上櫃市場 id。
+    market_id: i32,
+    /// 產業分類 id。
+    industry_id: i32,
+    /// 排行依據的法人別買賣超股數。
+    net_shares: i64,
+    /// 當日收盤價；無報價時為 `None`。
+    closing_price: Option<Decimal>,
+    /// 外資買賣超股數。
+    foreign_net: i64,
+    /// 投信買賣超股數。
+    investment_trust_net: i64,
+    /// 自營商買賣超股數。
+    dealer_net: i64,
+    /// 三大法人合計買賣超股數。
+    total_net: i64,
+}
+
+impl InstitutionalTradeRankRow {
+    /// 轉成 API DTO；名次由查詢結果順序在程式端產生。
+    fn into_dto(self, rank: u32) -> InstitutionalTradeRank {
+        let estimated_net_value = self
+            .closing_price
+            .map(|price| price * Decimal::from(self.net_shares));
+        InstitutionalTradeRank {
+            rank,
+            net_shares: self.net_shares,
+            closing_price: decimal_to_f64(self.closing_price),
+            estimated_net_value: decimal_to_f64(estimated_net_value),
+            foreign_net: self.foreign_net,
+            investment_trust_net: self.investment_trust_net,
+            dealer_net: self.dealer_net,
+            total_net: self.total_net,
+            stock_symbol: self.stock_symbol,
+            name: self.name,
+            market_id: self.market_id,
+            industry_id: self.industry_id,
+        }
+    }
+}
+
+/// 將三大法人買賣超領域物件攤平成 HTTP DTO。
+impl From<DomainInstitutionalTrade> for InstitutionalTrade {
+    fn from(trade: DomainInstitutionalTrade) -> Self {
+        InstitutionalTrade {
+            date: trade.date.to_string(),
+            foreign_buy: trade.foreign.buy,
+            foreign_sell: trade.foreign.sell,
+            foreign_net: trade.foreign.net,
+            foreign_dealer_net: trade.foreign_dealer_net,
+            investment_trust_buy: trade.investment_trust.buy,
+            investment_trust_sell: trade.investment_trust.sell,
+            investment_trust_net: trade.investment_trust.net,
+            dealer_buy: trade.dealer.buy,
+            dealer_sell: trade.dealer.sell,
+            dealer_net: trade.dealer.net,
+            dealer_proprietary_net: trade.dealer_proprietary_net,
+            dealer_hedge_net: trade.dealer_hedge_net,
+            total_net: trade.total_net,
+        }
+    }
+}
+
+impl From<DomainMarginTrading> for MarginTrading {
+    fn from(trading: DomainMarginTrading) -> Self {
+        MarginTrading {
+            date: trading.date.to_string(),
+            margin_buy: trading.margin.buy,
+            margin_sell: trading.margin.sell,
+            margin_redemption: trading.margin.redemption,
+            margin_balance: trading.margin.balance,
+            margin_change: trading.margin.change(),
+            margin_quota: trading.margin.quota,
+            margin_utilization_percent: decimal_to_f64(trading.margin.utilization_percent()),
+            short_sell: trading.short.sell,
+            short_cover: trading.short.buy,
+            short_redemption: trading.short.redemption,
+            short_balance: trading.short.balance,
+            short_change: trading.short.change(),
+            short_quota: trading.short.quota,
+            offset: trading.offset,
+            short_to_margin_ratio_percent: decimal_to_f64(trading.short_to_margin_ratio_percent()),
+        }
+    }
+}
+
 /// 對應條件選股正規化後的資料庫列。
 ///
 /// 指標與分類使用 `Option` 表達「來源不存在或已過期」；四個來源期間另行保留，
@@ -1859,16 +2906,46 @@ struct ScreenedStockRow {
     valuation_band: Option<String>,
     /// 新鮮估值百分比。
     valuation_percentage: Option<Decimal>,
+    /// 新鮮券資比。
+    short_to_margin_ratio_percent: Option<Decimal>,
+    /// 新鮮融資使用率。
+    margin_utilization_percent: Option<Decimal>,
+    /// 新鮮殖利率歷史百分位。
+    yield_percentile: Option<Decimal>,
+    /// 新鮮本益比歷史百分位。
+    per_percentile: Option<Decimal>,
+    /// 新鮮股價淨值比歷史百分位。
+    pbr_percentile: Option<Decimal>,
+    /// 新鮮 Piotroski F-score。
+    f_score: Option<i16>,
+    /// 新鮮 Altman Z-score。
+    altman_z_score: Option<Decimal>,
+    /// 新鮮應計比率。
+    accruals_ratio: Option<Decimal>,
+    /// 新鮮自由現金流量殖利率。
+    fcf_yield_percent: Option<Decimal>,
+    /// 新鮮利息保障倍數。
+    interest_coverage: Option<Decimal>,
+    /// 新鮮負債權益比。
+    debt_to_equity: Option<Decimal>,
     /// 最新營收月份的資料庫 `YYYYMM` 編碼。
     revenue_month: Option<i64>,
     /// 最新季度財報年度。
     financial_year: Option<i64>,
     /// 最新季度財報季別。
     financial_quarter: Option<String>,
+    /// 最新財務體質分數年度。
+    health_year: Option<i32>,
+    /// 最新財務體質分數季別。
+    health_quarter: Option<String>,
     /// 最新估值日期。
     valuation_date: Option<NaiveDate>,
     /// 最新殖利率日期。
     yield_date: Option<NaiveDate>,
+    /// 最新融資融券日期。
+    margin_date: Option<NaiveDate>,
+    /// 最新歷史估值區間基準日。
+    band_date: Option<NaiveDate>,
 }
 
 impl From<ScreenedStockRow> for ScreenedStock {
@@ -1881,6 +2958,10 @@ impl From<ScreenedStockRow> for ScreenedStock {
             .financial_year
             .zip(row.financial_quarter)
             .map(|(year, quarter)| format!("{year}-{quarter}"));
+        let health_period = row
+            .health_year
+            .zip(row.health_quarter)
+            .map(|(year, quarter)| format!("{year}-{quarter}"));
         ScreenedStock {
             stock_symbol: row.stock_symbol,
             name: row.name,
@@ -1912,10 +2993,226 @@ impl From<ScreenedStockRow> for ScreenedStock {
                 &symbol,
                 "valuation_percentage",
             ),
+            short_to_margin_ratio_percent: analytical_decimal_to_f64(
+                row.short_to_margin_ratio_percent,
+                &symbol,
+                "short_to_margin_ratio_percent",
+            ),
+            margin_utilization_percent: analytical_decimal_to_f64(
+                row.margin_utilization_percent,
+                &symbol,
+                "margin_utilization_percent",
+            ),
+            yield_percentile: analytical_decimal_to_f64(
+                row.yield_percentile,
+                &symbol,
+                "yield_percentile",
+            ),
+            per_percentile: analytical_decimal_to_f64(
+                row.per_percentile,
+                &symbol,
+                "per_percentile",
+            ),
+            pbr_percentile: analytical_decimal_to_f64(
+                row.pbr_percentile,
+                &symbol,
+                "pbr_percentile",
+            ),
+            f_score: row.f_score,
+            altman_z_score: analytical_decimal_to_f64(
+                row.altman_z_score,
+                &symbol,
+                "altman_z_score",
+            ),
+            accruals_ratio: analytical_decimal_to_f64(
+                row.accruals_ratio,
+                &symbol,
+                "accruals_ratio",
+            ),
+            fcf_yield_percent: analytical_decimal_to_f64(
+                row.fcf_yield_percent,
+                &symbol,
+                "fcf_yield_percent",
+            ),
+            interest_coverage: analytical_decimal_to_f64(
+                row.interest_coverage,
+                &symbol,
+                "interest_coverage",
+            ),
+            debt_to_equity: analytical_decimal_to_f64(
+                row.debt_to_equity,
+                &symbol,
+                "debt_to_equity",
+            ),
             revenue_month: row.revenue_month.map(format_month),
             financial_period,
+            health_period,
             valuation_date: row.valuation_date.map(|date| date.to_string()),
             yield_date: row.yield_date.map(|date| date.to_string()),
+            margin_date: row.margin_date.map(|date| date.to_string()),
+            band_date: row.band_date.map(|date| date.to_string()),
+        }
+    }
+}
+
+impl From<&IndustrySnapshot> for IndustryMetric {
+    fn from(snapshot: &IndustrySnapshot) -> Self {
+        let metric = &snapshot.metric;
+        Self {
+            industry_id: metric.industry_id,
+            industry_name: snapshot.industry_name.clone(),
+            date: metric.date.to_string(),
+            stocks: metric.stocks,
+            advances: metric.advances,
+            declines: metric.declines,
+            unchanged: metric.unchanged,
+            cap_weighted_return: decimal_to_f64(metric.cap_weighted_return_pct),
+            equal_weighted_return: decimal_to_f64(metric.equal_weighted_return_pct),
+            index_level: decimal_to_f64(Some(metric.index_level)),
+            trade_value: decimal_to_f64(Some(metric.trade_value)),
+            turnover_share: decimal_to_f64(metric.turnover_share_pct),
+            revenue_period: metric.revenue_period,
+            revenue_companies: metric.revenue_companies,
+            revenue_yoy: decimal_to_f64(metric.revenue_yoy_pct),
+        }
+    }
+}
+
+impl From<&IndustryRotation> for SectorRotationItem {
+    fn from(rotation: &IndustryRotation) -> Self {
+        Self {
+            industry_id: rotation.industry_id,
+            industry_name: rotation.industry_name.clone(),
+            stocks: rotation.stocks,
+            index_level: decimal_to_f64(Some(rotation.index_level)),
+            windows: rotation
+                .windows
+                .iter()
+                .map(|strength| RotationWindowStrength {
+                    window: strength.window.code(),
+                    from: strength.from.to_string(),
+                    r#return: decimal_to_f64(Some(strength.return_pct)),
+                    benchmark_return: decimal_to_f64(strength.benchmark_return_pct),
+                    relative_strength: decimal_to_f64(strength.relative_strength_pct),
+                    rank: strength.rank,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<&BreadthIndicator> for MarketBreadthIndicatorPoint {
+    fn from(indicator: &BreadthIndicator) -> Self {
+        let [
+            pct_above_ma5,
+            pct_above_ma20,
+            pct_above_ma60,
+            pct_above_ma120,
+            pct_above_ma240,
+        ] = indicator.pct_above_moving_average.map(decimal_to_f64);
+        Self {
+            date: indicator.date.to_string(),
+            advances: indicator.advances,
+            declines: indicator.declines,
+            unchanged: indicator.unchanged,
+            net_advances: indicator.net_advances,
+            ad_line: indicator.ad_line,
+            ratio_adjusted_net_advances: decimal_to_f64(Some(
+                indicator.ratio_adjusted_net_advances,
+            )),
+            mcclellan_oscillator: decimal_to_f64(Some(indicator.mcclellan_oscillator)),
+            summation_index: decimal_to_f64(Some(indicator.summation_index)),
+            new_highs: indicator.new_highs,
+            new_lows: indicator.new_lows,
+            net_new_highs: indicator.net_new_highs(),
+            pct_above_ma5,
+            pct_above_ma20,
+            pct_above_ma60,
+            pct_above_ma120,
+            pct_above_ma240,
+        }
+    }
+}
+
+impl From<&BacktestReport> for EstimateBacktestReport {
+    fn from(report: &BacktestReport) -> Self {
+        Self {
+            horizon: report.horizon.code(),
+            scope: report.scope.kind(),
+            scope_id: report.scope.id(),
+            scope_name: report.scope_name.clone(),
+            first_date: report.first_date.to_string(),
+            last_date: report.last_date.to_string(),
+            signals: report
+                .signals
+                .iter()
+                .map(|signal| EstimateBacktestSignal {
+                    signal: signal.signal.code(),
+                    label: signal.signal.label(),
+                    dates: signal.dates,
+                    samples: signal.samples,
+                    mean_return: decimal_to_f64(signal.mean_return_pct),
+                    average_median_return: decimal_to_f64(signal.average_median_return_pct),
+                    win_rate: decimal_to_f64(signal.win_rate_pct),
+                })
+                .collect(),
+            spread: decimal_to_f64(report.spread_pct()),
+            compared_dates: report.compared_dates,
+            undervalued_beat_ratio: decimal_to_f64(report.undervalued_beat_ratio_pct),
+        }
+    }
+}
+
+impl From<&ModelEstimate> for ValuationModelResult {
+    fn from(estimate: &ModelEstimate) -> Self {
+        Self {
+            model: estimate.model.code(),
+            label: estimate.model.label(),
+            date: estimate.date.to_string(),
+            closing_price: decimal_to_f64(Some(estimate.closing_price)),
+            cheap: decimal_to_f64(Some(estimate.range.cheap)),
+            fair: decimal_to_f64(Some(estimate.range.fair)),
+            expensive: decimal_to_f64(Some(estimate.range.expensive)),
+            // 假設於計算時由 serde 產生，解析失敗只可能是資料被手動改壞，以 null 呈現。
+            assumptions: serde_json::from_str(&estimate.assumptions)
+                .unwrap_or(serde_json::Value::Null),
+        }
+    }
+}
+
+impl From<&FinancialHealthScore> for FinancialHealth {
+    fn from(score: &FinancialHealthScore) -> Self {
+        Self {
+            period: format!("{}-{}", score.year, score.quarter),
+            period_end: score.period_end.to_string(),
+            f_score: score.f_score,
+            f_score_criteria: serde_json::to_value(score.f_score_criteria)
+                .unwrap_or(serde_json::Value::Null),
+            altman_z_score: decimal_to_f64(score.altman_z_score),
+            accruals_ratio: decimal_to_f64(score.accruals_ratio),
+            cash_conversion_ratio: decimal_to_f64(score.cash_conversion_ratio),
+            fcf_yield_percent: decimal_to_f64(score.fcf_yield_pct),
+            interest_coverage: decimal_to_f64(score.interest_coverage),
+            debt_to_equity: decimal_to_f64(score.debt_to_equity),
+            debt_to_equity_change: decimal_to_f64(score.debt_to_equity_change),
+            inputs: serde_json::to_value(&score.inputs).unwrap_or(serde_json::Value::Null),
+        }
+    }
+}
+
+impl From<&ValuationBand> for HistoricalValuationBand {
+    fn from(band: &ValuationBand) -> Self {
+        Self {
+            metric: band.metric.code(),
+            years: band.years,
+            date: band.date.to_string(),
+            current: decimal_to_f64(Some(band.current)),
+            percentile: decimal_to_f64(Some(band.percentile)),
+            cheapness_percentile: decimal_to_f64(Some(band.cheapness())),
+            minimum: decimal_to_f64(Some(band.minimum)),
+            median: decimal_to_f64(Some(band.median)),
+            maximum: decimal_to_f64(Some(band.maximum)),
+            samples: band.samples,
         }
     }
 }
@@ -2092,6 +3389,28 @@ impl DividendRow {
             cash_payable_date: sanitize_date(&self.payable_date1),
             stock_payable_date: sanitize_date(&self.payable_date2),
             updated_at: timestamp(self.updated_time),
+            gap_fill: None,
+        }
+    }
+}
+
+impl From<&DomainDividendGapFill> for DividendGapFill {
+    fn from(gap_fill: &DomainDividendGapFill) -> Self {
+        Self {
+            pre_ex_close: decimal_to_f64(Some(gap_fill.pre_ex_close)),
+            filled_on: gap_fill.filled_on.map(|date| date.to_string()),
+            trading_days_to_fill: gap_fill.trading_days_to_fill,
+        }
+    }
+}
+
+impl From<GapFillSummary> for DividendGapFillSummary {
+    fn from(summary: GapFillSummary) -> Self {
+        Self {
+            events: summary.events,
+            filled: summary.filled,
+            fill_ratio: decimal_to_f64(Some(summary.fill_ratio)),
+            median_days_to_fill: decimal_to_f64(summary.median_days_to_fill),
         }
     }
 }
@@ -2125,6 +3444,7 @@ impl From<ProfileRow> for StockProfile {
             weight: decimal_to_f64(row.weight),
             issued_share: decimal_to_f64(row.issued_share),
             history,
+            trading_surveillance: Vec::new(),
         }
     }
 }
@@ -2501,8 +3821,9 @@ mod tests {
 
     use super::{
         SCREEN_STOCKS_SQL, analytical_date_is_fresh, build_market_breadth_response,
-        financial_period_is_fresh, format_month, market_id_for_stats, market_id_for_stocks,
-        parse_month, parse_optional_date, qfii_order_by, quarter_to_api, resolve_calendar_range,
+        build_shareholding_response, financial_period_is_fresh, format_month,
+        market_derivatives_summary, market_id_for_stats, market_id_for_stocks, parse_month,
+        parse_optional_date, qfii_order_by, quarter_to_api, resolve_calendar_range,
         revenue_month_is_fresh, sanitize_date, screen_order_by, validate_screening_params,
         valuation_band,
     };
@@ -2519,6 +3840,18 @@ mod tests {
             min_eps: None,
             min_roe_percent: None,
             min_dividend_yield_percent: None,
+            min_short_to_margin_ratio_percent: None,
+            max_margin_utilization_percent: None,
+            band_years: None,
+            min_yield_percentile: None,
+            max_per_percentile: None,
+            max_pbr_percentile: None,
+            min_f_score: None,
+            min_altman_z_score: None,
+            max_accruals_ratio: None,
+            min_fcf_yield_percent: None,
+            min_interest_coverage: None,
+            max_debt_to_equity: None,
             sort_by: None,
             sort_order: None,
             limit: None,
@@ -2652,6 +3985,100 @@ mod tests {
         assert!(build_market_breadth_response(Vec::new()).is_none());
     }
 
+    /// 股權分散回應多取的一週只作比較基準，最舊一週沒有前值時週變化為 null。
+    #[test]
+    fn shareholding_response_uses_extra_week_as_baseline() {
+        use crate::domain::chip::{ShareholdingDistribution, ShareholdingTier};
+        let snapshot = |day: u32, whale: i64| {
+            ShareholdingDistribution::new(
+                "2330".to_owned(),
+                NaiveDate::from_ymd_opt(2026, 4, day).unwrap(),
+                vec![
+                    ShareholdingTier::new(1, 100, 1_000, Decimal::new(100 - whale, 0)),
+                    ShareholdingTier::new(15, 2, 9_000, Decimal::new(whale, 0)),
+                ],
+            )
+        };
+        let snapshots = vec![snapshot(24, 62), snapshot(17, 60), snapshot(10, 59)];
+
+        let response = build_shareholding_response("2330".to_owned(), &snapshots, 2);
+        assert_eq!(response.data_as_of.as_deref(), Some("2026-04-24"));
+        assert_eq!(response.latest_tiers.len(), 2);
+        assert_eq!(response.weeks.len(), 2);
+        assert_eq!(response.weeks[0].over_1000_lots_percentage, Some(62.0));
+        assert_eq!(
+            response.weeks[0].over_1000_lots_percentage_change,
+            Some(2.0)
+        );
+        assert_eq!(
+            response.weeks[1].over_1000_lots_percentage_change,
+            Some(1.0)
+        );
+
+        let response = build_shareholding_response("2330".to_owned(), &snapshots, 3);
+        assert_eq!(response.weeks[2].over_400_lots_percentage_change, None);
+        assert_eq!(response.weeks[2].total_holders_change, None);
+        assert!(
+            build_shareholding_response("2330".to_owned(), &[], 12)
+                .weeks
+                .is_empty()
+        );
+    }
+
+    /// 期貨摘要取 TX 近月並以指數收盤算基差；缺少的來源輸出 null 而非 0。
+    #[test]
+    fn market_derivatives_summary_uses_tx_front_month() {
+        use crate::domain::derivatives::{
+            DerivativesDailySnapshot, FuturesDailyQuote, FuturesInstitutionalPosition, TraderType,
+        };
+        let date = NaiveDate::from_ymd_opt(2026, 4, 24).unwrap();
+        let quote = |contract: &str, month: &str, close: i64| FuturesDailyQuote {
+            date,
+            contract: contract.to_owned(),
+            contract_month: month.to_owned(),
+            open: None,
+            high: None,
+            low: None,
+            close: Some(Decimal::new(close, 0)),
+            change: Some(Decimal::new(-30, 0)),
+            volume: 1,
+            settlement_price: Some(Decimal::new(close, 0)),
+            open_interest: 100,
+        };
+        let snapshot = DerivativesDailySnapshot {
+            date,
+            futures: vec![
+                quote("TX", "202606", 21_050),
+                quote("TX", "202605", 21_100),
+                quote("MTX", "202605", 21_101),
+            ],
+            positions: vec![FuturesInstitutionalPosition {
+                date,
+                contract: "TX".to_owned(),
+                trader_type: TraderType::ForeignInvestor,
+                long_trade_volume: 0,
+                short_trade_volume: 0,
+                net_trade_volume: 0,
+                long_open_interest: 0,
+                short_open_interest: 0,
+                net_open_interest: -25_000,
+                net_open_interest_amount: 0,
+            }],
+            put_call_ratio: None,
+        };
+
+        let summary = market_derivatives_summary(&snapshot, Some(Decimal::new(21_000, 0)));
+        assert_eq!(summary.futures_contract_month.as_deref(), Some("202605"));
+        assert_eq!(summary.futures_close, Some(21_100.0));
+        assert_eq!(summary.futures_change, Some(-30.0));
+        assert_eq!(summary.futures_open_interest, 200);
+        assert_eq!(summary.basis, Some(100.0));
+        assert_eq!(summary.foreign_net_open_interest, Some(-25_000));
+        assert_eq!(summary.dealer_net_open_interest, None);
+        assert_eq!(summary.put_call_open_interest_ratio_percent, None);
+        assert_eq!(market_derivatives_summary(&snapshot, None).basis, None);
+    }
+
     /// `all` 不縮小市場集合，不能單獨通過；上市／上櫃或任何一個實質條件
     /// 都可成立，固定預設排序與 limit 也應在驗證後補齊。
     #[test]
@@ -2679,10 +4106,10 @@ mod tests {
         assert!(validate_screening_params(&minimum).is_ok());
     }
 
-    /// 六個排序欄位乘上兩種方向必須完整映射，指標降冪也要明確
+    /// 八個排序欄位乘上兩種方向必須完整映射，指標降冪也要明確
     /// `NULLS LAST`；不在白名單的文字必須在接觸 SQL 前遭拒。
     #[test]
-    fn screening_sort_has_twelve_static_branches() {
+    fn screening_sort_has_sixteen_static_branches() {
         let fields = [
             "stock_symbol",
             "revenue_yoy",
@@ -2690,6 +4117,8 @@ mod tests {
             "roe",
             "dividend_yield",
             "valuation_percentage",
+            "short_to_margin_ratio",
+            "margin_utilization",
         ];
         for field in fields {
             for order in ["asc", "desc"] {
@@ -2725,6 +4154,78 @@ mod tests {
         let mut negative_yield = screening_params();
         negative_yield.min_dividend_yield_percent = Some(-0.01);
         assert!(validate_screening_params(&negative_yield).is_err());
+        for (ratio, utilization, ok) in [
+            (Some(0.0), None, true),
+            (Some(10_000.0), None, true),
+            (Some(-0.01), None, false),
+            (None, Some(100.0), true),
+            (None, Some(100.01), false),
+        ] {
+            let mut params = screening_params();
+            params.min_short_to_margin_ratio_percent = ratio;
+            params.max_margin_utilization_percent = utilization;
+            assert_eq!(validate_screening_params(&params).is_ok(), ok);
+        }
+    }
+
+    /// 歷史估值百分位條件本身即是有效篩選；回顧年數只接受 5 或 10，
+    /// 且單獨指定年數不算篩選條件。
+    #[test]
+    fn screening_band_percentiles_are_filters() {
+        let mut years_only = screening_params();
+        years_only.band_years = Some(10);
+        assert!(validate_screening_params(&years_only).is_err());
+
+        let mut cheap_per = screening_params();
+        cheap_per.max_per_percentile = Some(20.0);
+        let validated = validate_screening_params(&cheap_per).expect("本益比百分位是有效條件");
+        assert_eq!(validated.band_years, 5);
+        assert_eq!(validated.max_per_percentile, Some(Decimal::new(20, 0)));
+
+        for years in [3, 7, 20] {
+            let mut params = screening_params();
+            params.max_per_percentile = Some(20.0);
+            params.band_years = Some(years);
+            assert_eq!(
+                validate_screening_params(&params).err(),
+                Some("band_years 必須為 5 或 10")
+            );
+        }
+        for value in [-0.01, 100.01, f64::NAN] {
+            let mut params = screening_params();
+            params.min_yield_percentile = Some(value);
+            assert!(validate_screening_params(&params).is_err());
+        }
+        let mut pbr = screening_params();
+        pbr.max_pbr_percentile = Some(100.0);
+        pbr.band_years = Some(10);
+        assert!(validate_screening_params(&pbr).is_ok());
+    }
+
+    /// 財務體質分數各自是有效篩選條件，F-score 只接受 0–9。
+    #[test]
+    fn screening_health_scores_are_filters() {
+        let mut quality = screening_params();
+        quality.min_f_score = Some(7);
+        quality.max_debt_to_equity = Some(1.5);
+        let validated = validate_screening_params(&quality).expect("財務體質分數是有效條件");
+        assert_eq!(validated.min_f_score, Some(7));
+        assert_eq!(validated.max_debt_to_equity, Some(Decimal::new(15, 1)));
+
+        let mut out_of_range = screening_params();
+        out_of_range.min_f_score = Some(10);
+        assert_eq!(
+            validate_screening_params(&out_of_range).err(),
+            Some("min_f_score 必須介於 0 至 9")
+        );
+        for value in [-0.01, f64::INFINITY] {
+            let mut params = screening_params();
+            params.max_debt_to_equity = Some(value);
+            assert!(validate_screening_params(&params).is_err());
+        }
+        let mut accruals = screening_params();
+        accruals.max_accruals_ratio = Some(-0.05);
+        assert!(validate_screening_params(&accruals).is_ok());
     }
 
     /// 新鮮度邊界使用月份／季度序號處理跨年，並以「差三月／兩季／三十日」
@@ -2839,7 +4340,7 @@ mod tests {
         let ranking: Vec<String> = sqlx::query_scalar(r#"EXPLAIN (ANALYZE, BUFFERS, FORMAT TEXT) SELECT y.security_code FROM yield_rank y JOIN stocks s ON s.stock_symbol = y.security_code JOIN "DailyQuotes" q ON q."Serial" = y.daily_quotes_serial JOIN dividend d ON d.serial = y.dividend_serial WHERE y.date = (SELECT MAX(date) FROM yield_rank) AND s.stock_exchange_market_id IN (2,4) ORDER BY y.yield DESC, y.security_code ASC LIMIT 20"#)
             .fetch_all(pool).await.expect("ranking EXPLAIN");
         let screen_sql = format!(
-            "EXPLAIN (ANALYZE, BUFFERS, FORMAT TEXT) {SCREEN_STOCKS_SQL}\nORDER BY dividend_yield_percent DESC NULLS LAST, stock_symbol ASC\nLIMIT $9"
+            "EXPLAIN (ANALYZE, BUFFERS, FORMAT TEXT) {SCREEN_STOCKS_SQL}\nORDER BY dividend_yield_percent DESC NULLS LAST, stock_symbol ASC\nLIMIT $11"
         );
         let screen: Vec<String> = sqlx::query_scalar(sqlx::AssertSqlSafe(screen_sql.as_str()))
             .bind(0_i32)
@@ -2850,7 +4351,19 @@ mod tests {
             .bind(Option::<Decimal>::None)
             .bind(Option::<Decimal>::None)
             .bind(Option::<&str>::None)
+            .bind(Option::<Decimal>::None)
+            .bind(Option::<Decimal>::None)
             .bind(20_i64)
+            .bind(5_i32)
+            .bind(Option::<Decimal>::None)
+            .bind(Option::<Decimal>::None)
+            .bind(Option::<Decimal>::None)
+            .bind(Option::<i16>::None)
+            .bind(Option::<Decimal>::None)
+            .bind(Option::<Decimal>::None)
+            .bind(Option::<Decimal>::None)
+            .bind(Option::<Decimal>::None)
+            .bind(Option::<Decimal>::None)
             .fetch_all(pool)
             .await
             .expect("screen EXPLAIN");
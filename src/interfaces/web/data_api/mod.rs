@@ -6,6 +6,9 @@
 mod auth;
 mod dto;
 mod handlers;
+mod stream;
+
+pub(crate) use stream::StreamShutdown;
 
 use axum::{Router, middleware};
 use utoipa::OpenApi;
@@ -14,8 +17,8 @@ use utoipa_swagger_ui::SwaggerUi;
 /// 由 handler 註解生成的 OpenAPI 3 文件。
 #[derive(OpenApi)]
 #[openapi(
-    paths(handlers::search_stocks, handlers::latest_quote, handlers::price_history, handlers::stock_profile, handlers::realtime_snapshot, handlers::monthly_revenues, handlers::financial_statements, handlers::dividend_history, handlers::stock_valuation, handlers::market_breadth, handlers::dividend_yield_ranking, handlers::screen_stocks, handlers::market_index_history, handlers::dividend_calendar, handlers::qfii_holding_ranking, handlers::cagr_ranking, handlers::cagr_by_symbol, handlers::healthz),
-    components(schemas(dto::Stock, dto::DailyQuote, dto::HistoricalQuote, dto::QuoteHistoryRecord, dto::StockProfile, dto::SearchResponse, dto::LatestQuoteResponse, dto::PriceHistoryResponse, dto::RealtimeSnapshotResponse, dto::MonthlyRevenue, dto::MonthlyRevenueResponse, dto::FinancialStatement, dto::FinancialStatementHistoryResponse, dto::Dividend, dto::DividendHistoryResponse, dto::StockValuation, dto::StockValuationResponse, dto::MarketBreadth, dto::MarketBreadthResponse, dto::DividendYieldRank, dto::DividendYieldRankingResponse, dto::ScreenedStock, dto::StockScreeningResponse, dto::MarketIndexPoint, dto::MarketIndexHistoryResponse, dto::DividendCalendarEvent, dto::DividendCalendarResponse, dto::QfiiHolding, dto::QfiiHoldingRankingResponse, dto::CagrCoverageInfo, dto::CagrSummary, dto::CagrRankingItem, dto::CagrRankingResponse, dto::CagrPeriodItem, dto::CagrSymbolResponse, dto::ErrorBody, dto::HealthResponse)),
+    paths(handlers::search_stocks, handlers::latest_quote, handlers::price_history, handlers::stock_profile, handlers::realtime_snapshot, handlers::monthly_revenues, handlers::financial_statements, handlers::dividend_history, handlers::stock_valuation, handlers::estimate_backtest, handlers::market_breadth, handlers::market_breadth_indicators, handlers::industry_metrics, handlers::sector_rotation, handlers::dividend_yield_ranking, handlers::screen_stocks, handlers::market_index_history, handlers::dividend_calendar, handlers::qfii_holding_ranking, handlers::cagr_ranking, handlers::cagr_by_symbol, handlers::institutional_trade_history, handlers::institutional_trade_ranking, handlers::margin_trading_history, handlers::shareholding_distribution, stream::quote_events, stream::quote_websocket, handlers::healthz),
+    components(schemas(dto::Stock, dto::DailyQuote, dto::HistoricalQuote, dto::QuoteHistoryRecord, dto::StockProfile, dto::TradingSurveillance, dto::SearchResponse, dto::LatestQuoteResponse, dto::PriceHistoryResponse, dto::RealtimeSnapshotResponse, dto::MonthlyRevenue, dto::MonthlyRevenueResponse, dto::FinancialStatement, dto::FinancialStatementHistoryResponse, dto::Dividend, dto::DividendGapFill, dto::DividendGapFillSummary, dto::DividendHistoryResponse, dto::StockValuation, dto::StockValuationResponse, dto::HistoricalValuationBand, dto::ValuationModelResult, dto::FinancialHealth, dto::EstimateBacktestSignal, dto::EstimateBacktestReport, dto::EstimateBacktestResponse, dto::MarketBreadth, dto::MarketBreadthResponse, dto::MarketBreadthIndicatorPoint, dto::MarketBreadthIndicatorResponse, dto::IndustryMetric, dto::IndustryMetricsResponse, dto::RotationWindowStrength, dto::SectorRotationItem, dto::SectorRotationResponse, dto::DividendYieldRank, dto::DividendYieldRankingResponse, dto::ScreenedStock, dto::StockScreeningResponse, dto::MarketIndexPoint, dto::MarketDerivativesSummary, dto::MarketIndexHistoryResponse, dto::DividendCalendarEvent, dto::DividendCalendarResponse, dto::QfiiHolding, dto::QfiiHoldingRankingResponse, dto::CagrCoverageInfo, dto::CagrSummary, dto::CagrRankingItem, dto::CagrRankingResponse, dto::CagrPeriodItem, dto::CagrSymbolResponse, dto::InstitutionalTrade, dto::InstitutionalTradeHistoryResponse, dto::InstitutionalTradeRank, dto::InstitutionalTradeRankingResponse, dto::MarginTrading, dto::MarginTradingHistoryResponse, dto::ShareholdingTier, dto::ShareholdingWeek, dto::ShareholdingDistributionResponse, dto::ErrorBody, dto::HealthResponse)),
     tags((name = "data-api", description = "唯讀股票資料查詢")),
     security(("bearer_auth" = [])),
     modifiers(&SecurityAddon)
@@ -80,10 +83,26 @@ pub(super) fn router() -> Router {
             "/stocks/{symbol}/valuation",
             axum::routing::get(handlers::stock_valuation),
         )
+        .route(
+            "/market/estimate-backtest",
+            axum::routing::get(handlers::estimate_backtest),
+        )
         .route(
             "/market/breadth",
             axum::routing::get(handlers::market_breadth),
         )
+        .route(
+            "/market/breadth-indicators",
+            axum::routing::get(handlers::market_breadth_indicators),
+        )
+        .route(
+            "/market/industries",
+            axum::routing::get(handlers::industry_metrics),
+        )
+        .route(
+            "/market/sector-rotation",
+            axum::routing::get(handlers::sector_rotation),
+        )
         .route(
             "/market/dividend-yield-ranking",
             axum::routing::get(handlers::dividend_yield_ranking),
@@ -112,11 +131,34 @@ pub(super) fn router() -> Router {
             "/market/cagr-ranking/{stock_symbol}",
             axum::routing::get(handlers::cagr_by_symbol),
         )
+        .route(
+            "/stocks/{symbol}/institutional-investors",
+            axum::routing::get(handlers::institutional_trade_history),
+        )
+        .route(
+            "/market/institutional-investor-ranking",
+            axum::routing::get(handlers::institutional_trade_ranking),
+        )
+        .route(
+            "/stocks/{symbol}/margin-trading",
+            axum::routing::get(handlers::margin_trading_history),
+        )
+        .route(
+            "/stocks/{symbol}/shareholding-distribution",
+            axum::routing::get(handlers::shareholding_distribution),
+        )
         .layer(middleware::from_fn(auth::require_bearer_key));
+    // 瀏覽器的 WebSocket 無法帶 Authorization 標頭，推送端點另接受子協定帶 key。
+    let streams = Router::new()
+        .route("/quotes/stream", axum::routing::get(stream::quote_events))
+        .route("/quotes/ws", axum::routing::get(stream::quote_websocket))
+        .layer(middleware::from_fn(auth::require_stream_key));
     Router::new()
         .nest(
             "/api/v1",
-            protected.route("/healthz", axum::routing::get(handlers::healthz)),
+            protected
+                .merge(streams)
+                .route("/healthz", axum::routing::get(handlers::healthz)),
         )
         .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
 }
@@ -246,6 +288,25 @@ mod tests {
         assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
     }
 
+    /// 即時報價推送未帶任何 key 時同樣拒絕。
+    #[tokio::test]
+    async fn quote_streams_reject_missing_bearer_key() {
+        for path in [
+            "/api/v1/quotes/stream?symbols=2330",
+            "/api/v1/quotes/ws?symbols=2330",
+        ] {
+            let response = router()
+                .oneshot(
+                    Request::get(path)
+                        .body(Body::empty())
+                        .expect("request should build"),
+                )
+                .await
+                .expect("router should serve request");
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{path}");
+        }
+    }
+
     /// OpenAPI 契約須列出所有資料查詢路徑與健康檢查，供 Go client codegen 使用。
     #[test]
     fn openapi_contains_all_data_api_paths() {
@@ -262,7 +323,11 @@ mod tests {
             "/api/v1/stocks/{symbol}/financial-statements",
             "/api/v1/stocks/{symbol}/dividends",
             "/api/v1/stocks/{symbol}/valuation",
+            "/api/v1/market/estimate-backtest",
             "/api/v1/market/breadth",
+            "/api/v1/market/breadth-indicators",
+            "/api/v1/market/industries",
+            "/api/v1/market/sector-rotation",
             "/api/v1/market/dividend-yield-ranking",
             "/api/v1/stocks/screen",
             "/api/v1/market/index-history",
@@ -270,6 +335,12 @@ mod tests {
             "/api/v1/market/qfii-holding-ranking",
             "/api/v1/market/cagr-ranking",
             "/api/v1/market/cagr-ranking/{stock_symbol}",
+            "/api/v1/stocks/{symbol}/institutional-investors",
+            "/api/v1/market/institutional-investor-ranking",
+            "/api/v1/stocks/{symbol}/margin-trading",
+            "/api/v1/stocks/{symbol}/shareholding-distribution",
+            "/api/v1/quotes/stream",
+            "/api/v1/quotes/ws",
             "/api/v1/healthz",
         ] {
             assert!(json.contains(path), "OpenAPI should contain {path}");
@@ -396,6 +467,23 @@ mod tests {
             query_schema(operation, "min_dividend_yield_percent")["maximum"],
             1000
         );
+        assert_eq!(
+            query_schema(operation, "max_margin_utilization_percent")["maximum"],
+            100
+        );
+        assert_eq!(
+            enum_values(&document, query_schema(operation, "sort_by")),
+            serde_json::json!([
+                "stock_symbol",
+                "revenue_yoy",
+                "eps",
+                "roe",
+                "dividend_yield",
+                "valuation_percentage",
+                "short_to_margin_ratio",
+                "margin_utilization"
+            ])
+        );
         let stocks =
             &document["components"]["schemas"]["StockScreeningResponse"]["properties"]["stocks"];
         assert_eq!(stocks["type"], "array");
@@ -428,6 +516,25 @@ mod tests {
             "#/components/schemas/MarketIndexPoint"
         );
         assert!(properties["data_as_of"].to_string().contains("null"));
+        let point = &document["components"]["schemas"]["MarketIndexPoint"]["properties"];
+        assert!(
+            point["derivatives"]
+                .to_string()
+                .contains("MarketDerivativesSummary")
+        );
+        let derivatives =
+            &document["components"]["schemas"]["MarketDerivativesSummary"]["properties"];
+        for field in [
+            "futures_close",
+            "basis",
+            "foreign_net_open_interest",
+            "put_call_open_interest_ratio_percent",
+        ] {
+            assert!(
+                derivatives.get(field).is_some(),
+                "MarketDerivativesSummary 應包含 {field}"
+            );
+        }
 
         // §4.9 行事曆：event_type 五值 enum 預設 all；limit 1–200 預設 50；
         // events 為 DividendCalendarEvent 陣列。
@@ -788,10 +895,14 @@ mod tests {
             .await;
             assert_eq!(status, StatusCode::OK);
             assert!(json["valuation"].is_null());
+            assert_eq!(json["historical_bands"], serde_json::json!([]));
+            assert_eq!(json["models"], serde_json::json!([]));
             let (status, json) = get(&format!("/api/v1/stocks/{symbol}/valuation")).await;
             assert_eq!(status, StatusCode::OK);
             assert_eq!(json["valuation"]["stock_symbol"], symbol);
             assert_eq!(json["data_as_of"], json["valuation"]["date"]);
+            assert!(json["historical_bands"].is_array());
+            assert!(json["models"].is_array());
         }
 
         for market in ["all", "twse", "tpex"] {
@@ -1045,6 +1156,432 @@ mod tests {
         );
     }
 
+    /// 三大法人兩條 path 精確驗證 responses 與 query enum/range/default。
+    #[test]
+    fn openapi_institutional_trade_schemas_pin_field_names() {
+        let document = serde_json::to_value(ApiDoc::openapi()).expect("OpenAPI 可序列化");
+        let history = get_operation(&document, "/api/v1/stocks/{symbol}/institutional-investors");
+        assert_endpoint_responses(history, "InstitutionalTradeHistoryResponse", true);
+        let limit = query_schema(history, "limit");
+        assert_eq!(limit["default"], 20);
+        assert_eq!(limit["minimum"], 1);
+        assert_eq!(limit["maximum"], 250);
+        let trades = &document["components"]["schemas"]["InstitutionalTradeHistoryResponse"]["properties"]
+            ["trades"];
+        assert_eq!(trades["type"], "array");
+        assert_eq!(
+            trades["items"]["$ref"],
+            "#/components/schemas/InstitutionalTrade"
+        );
+
+        let ranking = get_operation(&document, "/api/v1/market/institutional-investor-ranking");
+        assert_endpoint_responses(ranking, "InstitutionalTradeRankingResponse", false);
+        let investor = query_schema(ranking, "investor");
+        assert_eq!(investor["default"], "total");
+        assert_eq!(
+            enum_values(&document, investor),
+            serde_json::json!(["foreign", "investment_trust", "dealer", "total"])
+        );
+        let direction = query_schema(ranking, "direction");
+        assert_eq!(direction["default"], "buy");
+        assert_eq!(
+            enum_values(&document, direction),
+            serde_json::json!(["buy", "sell"])
+        );
+        assert_eq!(query_schema(ranking, "limit")["maximum"], 50);
+    }
+
+    /// 三大法人 endpoint 的驗證與參數檢查都在觸及資料庫之前完成。
+    #[tokio::test]
+    async fn institutional_trade_endpoints_reject_before_any_query() {
+        for path in [
+            "/api/v1/stocks/2330/institutional-investors",
+            "/api/v1/market/institutional-investor-ranking",
+        ] {
+            let response = router()
+                .oneshot(
+                    Request::get(path)
+                        .body(Body::empty())
+                        .expect("request should build"),
+                )
+                .await
+                .expect("router should serve request");
+            assert_eq!(
+                response.status(),
+                StatusCode::UNAUTHORIZED,
+                "{path} 應回 401"
+            );
+        }
+
+        let key = std::env::var("DATA_API_KEY").unwrap_or_else(|_| {
+            let generated = "institutional-param-test-key".to_owned();
+            unsafe { std::env::set_var("DATA_API_KEY", &generated) };
+            generated
+        });
+        for path in [
+            "/api/v1/stocks/2330/institutional-investors?limit=0",
+            "/api/v1/stocks/2330/institutional-investors?limit=251",
+            "/api/v1/stocks/2330/institutional-investors?from=2026-05-01&to=2026-04-01",
+            "/api/v1/market/institutional-investor-ranking?investor=qfii",
+            "/api/v1/market/institutional-investor-ranking?direction=net",
+            "/api/v1/market/institutional-investor-ranking?market=emerging",
+            "/api/v1/market/institutional-investor-ranking?industry_id=0",
+            "/api/v1/market/institutional-investor-ranking?limit=51",
+            "/api/v1/market/institutional-investor-ranking?date=2026-4-30",
+        ] {
+            let response = router()
+                .oneshot(
+                    Request::get(path)
+                        .header("Authorization", format!("Bearer {key}"))
+                        .body(Body::empty())
+                        .expect("request should build"),
+                )
+                .await
+                .expect("router should serve request");
+            assert_eq!(
+                response.status(),
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "{path} 應回 422"
+            );
+        }
+    }
+
+    /// 融資融券歷史 path 精確驗證 responses、limit 範圍與陣列 item。
+    #[test]
+    fn openapi_margin_trading_schema_pins_field_names() {
+        let document = serde_json::to_value(ApiDoc::openapi()).expect("OpenAPI 可序列化");
+        let history = get_operation(&document, "/api/v1/stocks/{symbol}/margin-trading");
+        assert_endpoint_responses(history, "MarginTradingHistoryResponse", true);
+        let limit = query_schema(history, "limit");
+        assert_eq!(limit["default"], 20);
+        assert_eq!(limit["maximum"], 250);
+        let records = &document["components"]["schemas"]["MarginTradingHistoryResponse"]["properties"]
+            ["records"];
+        assert_eq!(records["type"], "array");
+        assert_eq!(
+            records["items"]["$ref"],
+            "#/components/schemas/MarginTrading"
+        );
+        let properties = &document["components"]["schemas"]["MarginTrading"]["properties"];
+        for field in [
+            "margin_balance",
+            "margin_change",
+            "margin_utilization_percent",
+            "short_balance",
+            "short_change",
+            "short_to_margin_ratio_percent",
+        ] {
+            assert!(
+                properties.get(field).is_some(),
+                "MarginTrading 應包含 {field}"
+            );
+        }
+    }
+
+    /// 融資融券 endpoint 的驗證與參數檢查都在觸及資料庫之前完成。
+    #[tokio::test]
+    async fn margin_trading_endpoint_rejects_before_any_query() {
+        let response = router()
+            .oneshot(
+                Request::get("/api/v1/stocks/2330/margin-trading")
+                    .body(Body::empty())
+                    .expect("request should build"),
+            )
+            .await
+            .expect("router should serve request");
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let key = std::env::var("DATA_API_KEY").unwrap_or_else(|_| {
+            let generated = "margin-param-test-key".to_owned();
+            unsafe { std::env::set_var("DATA_API_KEY", &generated) };
+            generated
+        });
+        for path in [
+            "/api/v1/stocks/2330/margin-trading?limit=0",
+            "/api/v1/stocks/2330/margin-trading?limit=251",
+            "/api/v1/stocks/2330/margin-trading?from=2026-05-01&to=2026-04-01",
+            "/api/v1/stocks/screen?min_short_to_margin_ratio_percent=-1",
+            "/api/v1/stocks/screen?max_margin_utilization_percent=101",
+            "/api/v1/stocks/screen?market=twse&sort_by=short_ratio",
+        ] {
+            let response = router()
+                .oneshot(
+                    Request::get(path)
+                        .header("Authorization", format!("Bearer {key}"))
+                        .body(Body::empty())
+                        .expect("request should build"),
+                )
+                .await
+                .expect("router should serve request");
+            assert_eq!(
+                response.status(),
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "{path} 應回 422"
+            );
+        }
+    }
+
+    /// 估價回測 endpoint 的驗證與參數檢查都在觸及資料庫之前完成。
+    #[tokio::test]
+    async fn estimate_backtest_rejects_before_any_query() {
+        let document = serde_json::to_value(ApiDoc::openapi()).expect("OpenAPI 可序列化");
+        let operation = get_operation(&document, "/api/v1/market/estimate-backtest");
+        assert_response(operation, "200", "EstimateBacktestResponse");
+        let properties = &document["components"]["schemas"]["EstimateBacktestReport"]["properties"];
+        for field in [
+            "horizon",
+            "scope",
+            "signals",
+            "spread",
+            "undervalued_beat_ratio",
+        ] {
+            assert!(
+                properties.get(field).is_some(),
+                "EstimateBacktestReport 應包含 {field}"
+            );
+        }
+
+        let key = std::env::var("DATA_API_KEY").unwrap_or_else(|_| {
+            let generated = "estimate-backtest-param-test-key".to_owned();
+            unsafe { std::env::set_var("DATA_API_KEY", &generated) };
+            generated
+        });
+        for (path, authorized, expected) in [
+            (
+                "/api/v1/market/estimate-backtest",
+                false,
+                StatusCode::UNAUTHORIZED,
+            ),
+            (
+                "/api/v1/market/estimate-backtest?horizon=1y",
+                true,
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                "/api/v1/market/estimate-backtest?scope=sector",
+                true,
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                "/api/v1/market/estimate-backtest?scope=all&scope_id=2",
+                true,
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                "/api/v1/market/estimate-backtest?scope=industry&scope_id=0",
+                true,
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                "/api/v1/market/estimate-backtest?from=2026-05-01&to=2026-04-01",
+                true,
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+        ] {
+            let mut request = Request::get(path);
+            if authorized {
+                request = request.header("Authorization", format!("Bearer {key}"));
+            }
+            let response = router()
+                .oneshot(request.body(Body::empty()).expect("request should build"))
+                .await
+                .expect("router should serve request");
+            assert_eq!(response.status(), expected, "{path}");
+        }
+    }
+
+    /// 廣度指標 endpoint 的 schema 與參數檢查；驗證都在觸及資料庫之前完成。
+    #[tokio::test]
+    async fn market_breadth_indicators_schema_and_validation() {
+        let document = serde_json::to_value(ApiDoc::openapi()).expect("OpenAPI 可序列化");
+        let operation = get_operation(&document, "/api/v1/market/breadth-indicators");
+        assert_endpoint_responses(operation, "MarketBreadthIndicatorResponse", true);
+        assert_eq!(query_schema(operation, "days")["default"], 120);
+        assert_eq!(query_schema(operation, "days")["maximum"], 500);
+        let properties =
+            &document["components"]["schemas"]["MarketBreadthIndicatorPoint"]["properties"];
+        for field in [
+            "ad_line",
+            "mcclellan_oscillator",
+            "summation_index",
+            "new_highs",
+            "new_lows",
+            "pct_above_ma20",
+        ] {
+            assert!(
+                properties.get(field).is_some(),
+                "MarketBreadthIndicatorPoint 應包含 {field}"
+            );
+        }
+
+        let key = std::env::var("DATA_API_KEY").unwrap_or_else(|_| {
+            let generated = "breadth-indicator-param-test-key".to_owned();
+            unsafe { std::env::set_var("DATA_API_KEY", &generated) };
+            generated
+        });
+        for (path, authorized, expected) in [
+            (
+                "/api/v1/market/breadth-indicators",
+                false,
+                StatusCode::UNAUTHORIZED,
+            ),
+            (
+                "/api/v1/market/breadth-indicators?market=otc",
+                true,
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                "/api/v1/market/breadth-indicators?days=0",
+                true,
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                "/api/v1/market/breadth-indicators?days=501",
+                true,
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                "/api/v1/market/breadth-indicators?date=2026-02-30",
+                true,
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+        ] {
+            let mut request = Request::get(path);
+            if authorized {
+                request = request.header("Authorization", format!("Bearer {key}"));
+            }
+            let response = router()
+                .oneshot(request.body(Body::empty()).expect("request should build"))
+                .await
+                .expect("router should serve request");
+            assert_eq!(response.status(), expected, "{path}");
+        }
+    }
+
+    /// 產業指標與類股輪動 endpoints 的 schema 與參數檢查；驗證都在觸及資料庫之前完成。
+    #[tokio::test]
+    async fn industry_endpoints_schema_and_validation() {
+        let document = serde_json::to_value(ApiDoc::openapi()).expect("OpenAPI 可序列化");
+        let industries = get_operation(&document, "/api/v1/market/industries");
+        assert_endpoint_responses(industries, "IndustryMetricsResponse", true);
+        let rotation = get_operation(&document, "/api/v1/market/sector-rotation");
+        assert_endpoint_responses(rotation, "SectorRotationResponse", true);
+        assert_eq!(query_schema(rotation, "sort")["default"], "1m");
+        let metric = &document["components"]["schemas"]["IndustryMetric"]["properties"];
+        for field in [
+            "cap_weighted_return",
+            "equal_weighted_return",
+            "turnover_share",
+            "revenue_yoy",
+        ] {
+            assert!(metric.get(field).is_some(), "IndustryMetric 應包含 {field}");
+        }
+        let strength = &document["components"]["schemas"]["RotationWindowStrength"]["properties"];
+        for field in ["return", "benchmark_return", "relative_strength", "rank"] {
+            assert!(
+                strength.get(field).is_some(),
+                "RotationWindowStrength 應包含 {field}"
+            );
+        }
+
+        let key = std::env::var("DATA_API_KEY").unwrap_or_else(|_| {
+            let generated = "industry-param-test-key".to_owned();
+            unsafe { std::env::set_var("DATA_API_KEY", &generated) };
+            generated
+        });
+        for (path, authorized, expected) in [
+            ("/api/v1/market/industries", false, StatusCode::UNAUTHORIZED),
+            (
+                "/api/v1/market/sector-rotation",
+                false,
+                StatusCode::UNAUTHORIZED,
+            ),
+            (
+                "/api/v1/market/industries?date=2026-13-01",
+                true,
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                "/api/v1/market/sector-rotation?sort=6m",
+                true,
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                "/api/v1/market/sector-rotation?date=yesterday",
+                true,
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+        ] {
+            let mut request = Request::get(path);
+            if authorized {
+                request = request.header("Authorization", format!("Bearer {key}"));
+            }
+            let response = router()
+                .oneshot(request.body(Body::empty()).expect("request should build"))
+                .await
+                .expect("router should serve request");
+            assert_eq!(response.status(), expected, "{path}");
+        }
+    }
+
+    /// 股權分散 path 精確驗證 responses、weeks 範圍與陣列 item，且參數檢查
+    /// 在觸及資料庫之前完成。
+    #[tokio::test]
+    async fn shareholding_distribution_schema_and_validation() {
+        let document = serde_json::to_value(ApiDoc::openapi()).expect("OpenAPI 可序列化");
+        let operation = get_operation(
+            &document,
+            "/api/v1/stocks/{symbol}/shareholding-distribution",
+        );
+        assert_endpoint_responses(operation, "ShareholdingDistributionResponse", true);
+        let weeks = query_schema(operation, "weeks");
+        assert_eq!(weeks["default"], 12);
+        assert_eq!(weeks["maximum"], 104);
+        let properties =
+            &document["components"]["schemas"]["ShareholdingDistributionResponse"]["properties"];
+        assert_eq!(
+            properties["weeks"]["items"]["$ref"],
+            "#/components/schemas/ShareholdingWeek"
+        );
+        assert_eq!(
+            properties["latest_tiers"]["items"]["$ref"],
+            "#/components/schemas/ShareholdingTier"
+        );
+
+        let key = std::env::var("DATA_API_KEY").unwrap_or_else(|_| {
+            let generated = "shareholding-param-test-key".to_owned();
+            unsafe { std::env::set_var("DATA_API_KEY", &generated) };
+            generated
+        });
+        for (path, authorized, expected) in [
+            (
+                "/api/v1/stocks/2330/shareholding-distribution",
+                false,
+                StatusCode::UNAUTHORIZED,
+            ),
+            (
+                "/api/v1/stocks/2330/shareholding-distribution?weeks=0",
+                true,
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                "/api/v1/stocks/2330/shareholding-distribution?weeks=105",
+                true,
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+        ] {
+            let mut request = Request::get(path);
+            if authorized {
+                request = request.header("Authorization", format!("Bearer {key}"));
+            }
+            let response = router()
+                .oneshot(request.body(Body::empty()).expect("request should build"))
+                .await
+                .expect("router should serve request");
+            assert_eq!(response.status(), expected, "{path}");
+        }
+    }
+
     /// M4 兩個 endpoint 都必須在 middleware 層拒絕未授權請求。
     #[tokio::test]
     async fn cagr_endpoints_reject_missing_bearer_key() {
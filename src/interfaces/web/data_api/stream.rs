@@ -0,0 +1,343 @@
+//! 即時報價推送端點（SSE 與 WebSocket）。
+//!
+//! 兩者都訂閱 [`quote_feed`] 的盤中價格更新：連線建立時先送出訂閱代號目前的快照，
+//! 之後只推送內容有變動者。訊息內容與 `realtime-snapshot` 相同，但不查詢
+//! 注意股／處置股（`trading_surveillance` 固定為 `null`）。
+//!
+//! 驗證由 `auth::require_stream_key` 處理：除 Bearer 標頭外，WebSocket 也接受瀏覽器
+//! 能帶的 `Sec-WebSocket-Protocol: bearer, <key>`；瀏覽器訂閱 SSE 時以 `fetch` 帶標頭。
+//! 收到關機訊號時 SSE 串流結束、WebSocket 送出 1001 close frame，
+//! 讓 Axum 的平順關機不必等到逾時。
+//! WebSocket 的交握與 frame 處理交給 Axum 的 `ws` extractor。
+
+use std::future::Future;
+
+use axum::{
+    Extension,
+    extract::{
+        Query, WebSocketUpgrade,
+        ws::{CloseFrame, Message, WebSocket, close_code, rejection::WebSocketUpgradeRejection},
+    },
+    http::StatusCode,
+    response::{
+        IntoResponse, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
+};
+use tokio::sync::{mpsc, watch};
+use tokio_stream::wrappers::ReceiverStream;
+
+use super::{
+    auth::STREAM_KEY_PROTOCOL,
+    dto::{ErrorBody, QuoteStreamParams, RealtimeSnapshotResponse},
+    handlers::{error_response, snapshot_response},
+};
+use crate::{
+    app::event::trace::quote_feed::{self, QuoteSubscription},
+    core::shutdown,
+};
+
+/// 關機訊號；由 `interfaces::web::start` 以 `Extension` 掛上。
+#[derive(Clone)]
+pub(crate) struct StreamShutdown(pub(crate) watch::Receiver<bool>);
+
+/// 每個連線的待送緩衝筆數；用戶端消化不及時由 `quote_feed` 合併更新。
+const SUBSCRIBER_BUFFER: usize = 64;
+
+/// 以 Server-Sent Events 推送即時報價快照，事件名稱為 `snapshot`。
+#[utoipa::path(get, path = "/api/v1/quotes/stream", tag = "data-api", params(QuoteStreamParams), responses((status = 200, description = "text/event-stream；每個 `snapshot` 事件的 data 為一筆快照", content_type = "text/event-stream", body = RealtimeSnapshotResponse), (status = 401, body = ErrorBody), (status = 422, body = ErrorBody)), security(("bearer_auth" = [])))]
+pub(super) async fn quote_events(
+    shutdown: Option<Extension<StreamShutdown>>,
+    Query(params): Query<QuoteStreamParams>,
+) -> Response {
+    let subscription = match quote_feed::subscribe(params.symbols.split(',')) {
+        Ok(subscription) => subscription,
+        Err(why) => return error_response(StatusCode::UNPROCESSABLE_ENTITY, &why.to_string()),
+    };
+    let (tx, rx) = mpsc::channel(SUBSCRIBER_BUFFER);
+    tokio::spawn(quote_feed::forward(
+        subscription,
+        tx,
+        wait_for_stop(shutdown),
+        |snapshot| {
+            Event::default()
+                .event("snapshot")
+                .json_data(snapshot_response(snapshot, None))
+        },
+    ));
+
+    Sse::new(ReceiverStream::new(rx))
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// 以 WebSocket 推送即時報價快照，每則文字訊息為一筆快照 JSON。
+#[utoipa::path(get, path = "/api/v1/quotes/ws", tag = "data-api", params(QuoteStreamParams, ("Sec-WebSocket-Protocol" = Option<String>, Header, description = "瀏覽器可用 `bearer, <DATA_API_KEY>` 帶 key，伺服器回應子協定 `bearer`")), responses((status = 101, description = "切換為 WebSocket；每則文字訊息為一筆快照", body = RealtimeSnapshotResponse), (status = 400, body = ErrorBody), (status = 401, body = ErrorBody), (status = 422, body = ErrorBody)), security(("bearer_auth" = [])))]
+pub(super) async fn quote_websocket(
+    shutdown: Option<Extension<StreamShutdown>>,
+    Query(params): Query<QuoteStreamParams>,
+    upgrade: Result<WebSocketUpgrade, WebSocketUpgradeRejection>,
+) -> Response {
+    let Ok(upgrade) = upgrade else {
+        return error_response(StatusCode::BAD_REQUEST, "需要 WebSocket 升級請求");
+    };
+    let subscription = match quote_feed::subscribe(params.symbols.split(',')) {
+        Ok(subscription) => subscription,
+        Err(why) => return error_response(StatusCode::UNPROCESSABLE_ENTITY, &why.to_string()),
+    };
+
+    upgrade
+        .protocols([STREAM_KEY_PROTOCOL])
+        .on_upgrade(move |socket| serve_socket(socket, subscription, wait_for_stop(shutdown)))
+}
+
+/// 等待關機訊號；未掛上 [`StreamShutdown`]（例如單元測試）時永不完成。
+async fn wait_for_stop(shutdown: Option<Extension<StreamShutdown>>) {
+    match shutdown {
+        Some(Extension(StreamShutdown(receiver))) => shutdown::wait_for_shutdown(receiver).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// 在已升級的連線上推送快照，直到用戶端關閉連線或收到關機訊號。
+///
+/// ping/pong 與 close 交握由底層 WebSocket 實作回應；用戶端送來的其他訊息一律忽略。
+async fn serve_socket(
+    mut socket: WebSocket,
+    subscription: QuoteSubscription,
+    stop: impl Future<Output = ()> + Send + 'static,
+) {
+    let (tx, mut snapshots) = mpsc::channel(SUBSCRIBER_BUFFER);
+    tokio::spawn(quote_feed::forward(subscription, tx, stop, |snapshot| {
+        serde_json::to_string(&snapshot_response(snapshot, None))
+    }));
+
+    loop {
+        tokio::select! {
+            snapshot = snapshots.recv() => match snapshot {
+                Some(Ok(json)) => {
+                    if socket.send(Message::Text(json.into())).await.is_err() {
+                        break;
+                    }
+                }
+                Some(Err(why)) => {
+                    tracing::error!("Failed to serialize quote snapshot because {:?}", why);
+                }
+                // forward 結束代表收到關機訊號。
+                None => {
+                    let _ = socket
+                        .send(Message::Close(Some(CloseFrame {
+                            code: close_code::AWAY,
+                            reason: "server shutting down".into(),
+                        })))
+                        .await;
+                    break;
+                }
+            },
+            message = socket.recv() => match message {
+                // 收到 close 後繼續讀，底層會先送出 close 回應再結束串流。
+                Some(Ok(_)) => {}
+                // 多半是違反協定的 frame；盡力回 1002，連線已斷時送出會直接失敗。
+                Some(Err(why)) => {
+                    tracing::debug!("quote websocket read failed: {}", why);
+                    let _ = socket
+                        .send(Message::Close(Some(CloseFrame {
+                            code: close_code::PROTOCOL,
+                            reason: "protocol error".into(),
+                        })))
+                        .await;
+                    break;
+                }
+                None => break,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Router, routing::get};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    use super::*;
+
+    /// 以不含 Bearer 驗證的路由啟動測試伺服器，回傳位址與關機訊號 sender。
+    async fn start_server() -> (std::net::SocketAddr, watch::Sender<bool>) {
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let app = Router::new()
+            .route("/ws", get(quote_websocket))
+            .route("/stream", get(quote_events))
+            .layer(Extension(StreamShutdown(shutdown_rx)));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        (addr, shutdown_tx)
+    }
+
+    /// 讀到 HTTP header 結束為止，回傳 header 文字。
+    async fn read_head(stream: &mut TcpStream) -> String {
+        let mut head = Vec::new();
+        while !head.ends_with(b"\r\n\r\n") {
+            head.push(stream.read_u8().await.unwrap());
+        }
+        String::from_utf8(head).unwrap()
+    }
+
+    /// 完成 WebSocket 交握並回傳連線；訂閱不存在的代號，不會收到任何快照。
+    async fn open_socket(addr: std::net::SocketAddr) -> TcpStream {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(
+                b"GET /ws?symbols=T9999 HTTP/1.1\r\nHost: localhost\r\nConnection: Upgrade\r\n\
+                  Upgrade: websocket\r\nSec-WebSocket-Version: 13\r\n\
+                  Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n",
+            )
+            .await
+            .unwrap();
+
+        let head = read_head(&mut stream).await;
+        assert!(head.starts_with("HTTP/1.1 101"), "{head}");
+        assert!(head.contains("s3pPLMBiTxaQ9kYGzzhZRbK+xOo="));
+        stream
+    }
+
+    /// 讀取一個伺服器 frame（伺服器 frame 不加遮罩），回傳 (首位元組, payload)。
+    async fn read_frame(stream: &mut TcpStream) -> (u8, Vec<u8>) {
+        tokio::time::timeout(std::time::Duration::from_secs(1), async {
+            let first = stream.read_u8().await.unwrap();
+            let len = match stream.read_u8().await.unwrap() {
+                126 => usize::from(stream.read_u16().await.unwrap()),
+                len => usize::from(len),
+            };
+            let mut payload = vec![0; len];
+            stream.read_exact(&mut payload).await.unwrap();
+            (first, payload)
+        })
+        .await
+        .expect("frame should arrive")
+    }
+
+    /// 送出一個加遮罩的用戶端 frame（遮罩全為 0，payload 不變）。
+    async fn write_frame(stream: &mut TcpStream, first: u8, payload: &[u8]) {
+        let mut frame = vec![first, 0x80 | payload.len() as u8, 0, 0, 0, 0];
+        frame.extend_from_slice(payload);
+        stream.write_all(&frame).await.unwrap();
+    }
+
+    /// 交握成功後，關機訊號會讓伺服器送出 1001 close frame。
+    #[tokio::test]
+    async fn websocket_closes_with_going_away_on_shutdown() {
+        let (addr, shutdown_tx) = start_server().await;
+        let mut stream = open_socket(addr).await;
+
+        shutdown_tx.send(true).unwrap();
+        let (first, payload) = read_frame(&mut stream).await;
+        assert_eq!(first, 0x88);
+        assert_eq!(payload[..2], [0x03, 0xE9]);
+    }
+
+    /// 用戶端的 ping 會收到相同內容的 pong，close 會收到 close 回應。
+    #[tokio::test]
+    async fn websocket_answers_ping_and_close() {
+        let (addr, _shutdown_tx) = start_server().await;
+        let mut stream = open_socket(addr).await;
+
+        write_frame(&mut stream, 0x89, b"hi").await;
+        assert_eq!(read_frame(&mut stream).await, (0x8A, b"hi".to_vec()));
+
+        write_frame(&mut stream, 0x88, &[0x03, 0xE8]).await;
+        let (first, payload) = read_frame(&mut stream).await;
+        assert_eq!(first, 0x88);
+        assert_eq!(payload[..2], [0x03, 0xE8]);
+    }
+
+    /// 違反協定的 frame（超過 125 bytes 的控制 frame）會以 1002 關閉連線。
+    #[tokio::test]
+    async fn websocket_rejects_oversized_control_frame() {
+        let (addr, _shutdown_tx) = start_server().await;
+        let mut stream = open_socket(addr).await;
+
+        let mut frame = vec![0x89, 0x80 | 126, 0, 126, 0, 0, 0, 0];
+        frame.extend_from_slice(&[0; 126]);
+        stream.write_all(&frame).await.unwrap();
+
+        let (first, payload) = read_frame(&mut stream).await;
+        assert_eq!(first, 0x88);
+        assert_eq!(payload[..2], [0x03, 0xEA]);
+    }
+
+    /// 以子協定帶 key 時，交握只回應 `bearer`，不把 key 回送給用戶端。
+    #[tokio::test]
+    async fn websocket_selects_bearer_protocol() {
+        let (addr, _shutdown_tx) = start_server().await;
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(
+                b"GET /ws?symbols=T9999 HTTP/1.1\r\nHost: localhost\r\nConnection: Upgrade\r\n\
+                  Upgrade: websocket\r\nSec-WebSocket-Version: 13\r\n\
+                  Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+                  Sec-WebSocket-Protocol: bearer, s3cret-key\r\n\r\n",
+            )
+            .await
+            .unwrap();
+
+        let head = read_head(&mut stream).await.to_ascii_lowercase();
+        assert!(head.starts_with("http/1.1 101"), "{head}");
+        assert!(
+            head.contains("sec-websocket-protocol: bearer\r\n"),
+            "{head}"
+        );
+        assert!(!head.contains("s3cret-key"));
+    }
+
+    #[tokio::test]
+    async fn websocket_requires_upgrade_headers() {
+        let (addr, _shutdown_tx) = start_server().await;
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET /ws?symbols=2330 HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+
+        assert!(read_head(&mut stream).await.starts_with("HTTP/1.1 400"));
+    }
+
+    /// SSE 串流在關機後結束，Axum 的平順關機才不必等到逾時。
+    #[tokio::test]
+    async fn event_stream_ends_on_shutdown() {
+        let (addr, shutdown_tx) = start_server().await;
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET /stream?symbols=T9999 HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+
+        let head = read_head(&mut stream).await;
+        assert!(head.starts_with("HTTP/1.1 200"), "{head}");
+        assert!(head.contains("text/event-stream"));
+
+        shutdown_tx.send(true).unwrap();
+        let mut rest = Vec::new();
+        tokio::time::timeout(std::time::Duration::from_secs(1), async {
+            // chunked body 的結尾是長度為 0 的 chunk。
+            while !rest.ends_with(b"0\r\n\r\n") {
+                rest.push(stream.read_u8().await.unwrap());
+            }
+        })
+        .await
+        .expect("event stream should end after shutdown");
+    }
+
+    #[tokio::test]
+    async fn event_stream_rejects_empty_symbols() {
+        let (addr, _shutdown_tx) = start_server().await;
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET /stream HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+
+        assert!(read_head(&mut stream).await.starts_with("HTTP/1.1 422"));
+    }
+}
@@ -42,8 +42,10 @@ pub async fn start(shutdown: watch::Receiver<bool>) -> Result<WebServerHandle> {
     let addr = std::env::var(MANUAL_BACKFILL_WEB_ADDR)
         .unwrap_or_else(|_| DEFAULT_MANUAL_BACKFILL_WEB_ADDR.to_string())
         .parse::<SocketAddr>()?;
-    // 建立目前 Web 服務需要的所有路由。
-    let app = backfill_admin::router().merge(data_api::router());
+    // 建立目前 Web 服務需要的所有路由；即時報價推送連線需要關機訊號才會結束。
+    let app = backfill_admin::router()
+        .merge(data_api::router())
+        .layer(axum::Extension(data_api::StreamShutdown(shutdown.clone())));
     // bind 必須在 spawn 前完成，讓 port 被占用等錯誤可以在啟動階段直接回報。
     let listener = TcpListener::bind(addr).await?;
     tracing::info!("manual backfill web server listening on http://{}", addr);
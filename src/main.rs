@@ -259,11 +259,18 @@ async fn main() -> Result<(), Box<dyn Error>> {
     core::alert::register_alert_sink(std::sync::Arc::new(
         interfaces::bot::telegram::TelegramAlertSink,
     ));
-    // 2. 股票資料推送：app handler 呼叫 app::ports::push_stock_info，
-    //    由 gRPC adapter 轉成 StockInfoRequest 後推送到 Go 服務。
-    app::ports::register_stock_info_gateway(std::sync::Arc::new(
-        interfaces::rpc::client::stock_service::GrpcStockInfoGateway,
+    // 2. 證券主檔同步：app::event::stock_sync 的佇列經 app::ports::StockSyncGateway，
+    //    由 gRPC adapter 轉成 StockInfoRequest 後以 SyncStocks 串流批次送到 Go 服務。
+    app::ports::register_stock_sync_gateway(std::sync::Arc::new(
+        interfaces::rpc::client::stock_service::GrpcStockSyncGateway,
     ));
+    // 3. 對外事件匯流排（選用）：領域事件與收盤資料就緒訊號經 app::ports::publish_event
+    //    發布到 Redis Stream 或 NATS；`eventBus.backend` 未設定時不註冊，發布即略過。
+    match interfaces::event_bus::from_settings(&core::config::SETTINGS.event_bus) {
+        Ok(Some(bus)) => app::ports::register_event_bus(bus),
+        Ok(None) => {}
+        Err(why) => tracing::error!("Failed to create event bus because {:?}", why),
+    }
 
     // ── 4. 啟動計時器與 server 關機廣播 ─────────────────────────────────────
     // `startup_timer` 讓後面的各啟動階段都能記錄 elapsed 時間，方便找效能瓶頸。